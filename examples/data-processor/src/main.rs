@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::time::{sleep, Duration};
 
-/// 应用配置
+/// 应用配置，启动时从 `[app]` 绑定并注册到容器
 #[derive(Debug, Deserialize, Configuration)]
 pub struct AppConfig {
     pub name: String,
@@ -23,6 +23,7 @@ pub struct DataRecord {
 /// 数据源服务 - 模拟从某个数据源读取数据
 #[derive(Service)]
 pub struct DataSourceService {
+    #[component(inject)]
     config: Arc<AppConfig>,
 }
 
@@ -113,9 +114,13 @@ impl ResultStorageService {
 /// 数据处理任务调度器 - 协调各个服务
 #[derive(Service)]
 pub struct TaskSchedulerService {
+    #[component(inject)]
     data_source: Arc<DataSourceService>,
+    #[component(inject)]
     processor: Arc<DataProcessorService>,
+    #[component(inject)]
     storage: Arc<ResultStorageService>,
+    #[component(inject)]
     config: Arc<AppConfig>,
 }
 
@@ -174,7 +179,10 @@ impl TaskSchedulerService {
 }
 
 /// CLI应用程序入口 - 展示 rspring-core 的非Web使用场景
-#[rspring_application]
+#[rspring_application(
+    configurations(AppConfig),
+    scan(DataSourceService, DataProcessorService, ResultStorageService, TaskSchedulerService)
+)]
 pub struct DataProcessorApplication;
 
 #[tokio::main]
@@ -3,14 +3,24 @@
 //! 提供应用程序生命周期管理和应用上下文功能
 
 use crate::{
+    clock::ApplicationClock,
     config::{ConfigurationManager, AppConfig, ServerConfig, LoggingConfig},
     container::Container,
-    error::{Error, Result},
+    error::{Error, ErrorReporting, ErrorReportingConfig, Result},
+    messaging::MessagingConfig,
+    resilience::{CircuitBreakerRegistry, ResilienceConfig},
+    startup::{startup_recorder, ApplicationReady},
+    task::{TaskExecutionConfig, TaskExecutor},
 };
+use futures::future::BoxFuture;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use tracing::{info, debug, error};
 
+/// 应用关闭时等待错误报告发送完成的最长时间
+const ERROR_REPORT_FLUSH_TIMEOUT: Duration = Duration::from_secs(2);
+
 /// 应用上下文
 /// 
 /// 管理全局的组件容器和配置管理器
@@ -29,16 +39,30 @@ impl ApplicationContext {
     /// 当配置管理器创建失败时返回错误
     pub fn new() -> Result<Self> {
         debug!("创建应用上下文");
+        // 启动耗时从创建应用上下文开始计算
+        startup_recorder();
         
-        let config = Arc::new(ConfigurationManager::new()?);
-        let container = Arc::new(RwLock::new(Container::new()));
-        
+        let context = Self::with_config(ConfigurationManager::new()?);
+
         info!("应用上下文创建成功");
-        
-        Ok(Self {
-            container,
-            config,
-        })
+
+        Ok(context)
+    }
+
+    /// 使用指定的配置管理器创建应用上下文
+    ///
+    /// 用于测试或从非默认位置加载配置，不会开始记录启动耗时
+    ///
+    /// # 示例
+    /// ```rust
+    /// let config = ConfigurationManager::from_dirs(&["config".into()], "staging", "RSPRING")?;
+    /// let context = ApplicationContext::with_config(config);
+    /// ```
+    pub fn with_config(config: ConfigurationManager) -> Self {
+        Self {
+            container: Arc::new(RwLock::new(Container::new())),
+            config: Arc::new(config),
+        }
     }
     
     /// 注册组件到容器
@@ -65,6 +89,62 @@ impl ApplicationContext {
         }
     }
     
+    /// 注册配置类，其中的 `#[Bean]` 方法在自动装配时调用
+    /// 
+    /// # 示例
+    /// ```rust
+    /// context.register_configuration(MailConfiguration).await;
+    /// context.auto_wire().await?;
+    /// ```
+    pub async fn register_configuration<C: crate::BeanConfiguration>(&self, configuration: C) {
+        debug!("注册配置类: {}", std::any::type_name::<C>());
+        self.container.write().await.register_configuration(configuration);
+    }
+    
+    /// 按配置前缀绑定配置类，并以 `Arc<T>` 单例注册到容器供组件注入
+    ///
+    /// # 错误
+    /// 配置不存在或无法转换为配置类型时返回错误
+    ///
+    /// # 示例
+    /// ```rust
+    /// let batch = context.bind_configuration::<BatchConfig>().await?;
+    /// context.auto_wire().await?;
+    /// ```
+    pub async fn bind_configuration<T: crate::config::properties::Configuration>(&self) -> Result<Arc<T>> {
+        let prefix = T::prefix();
+        debug!("绑定配置: {} <- [{}]", std::any::type_name::<T>(), prefix);
+        let configuration = Arc::new(T::bind(&self.config).map_err(|e| {
+            Error::validation(format!("绑定配置 {} 失败: {}", std::any::type_name::<T>(), e))
+        })?);
+        self.container.write().await.injector_mut().register_singleton_arc_with_dependencies(
+            configuration.clone(),
+            None,
+            Vec::new(),
+        )?;
+        Ok(configuration)
+    }
+
+    /// 注册 `component_scan!` 列出的组件，组件在自动装配时按依赖顺序创建
+    ///
+    /// # 错误
+    /// 读取组件的 `#[Value]` 配置失败时返回错误
+    ///
+    /// # 示例
+    /// ```rust
+    /// context.scan_components(component_scan!(UserService, UserRepository)).await?;
+    /// context.auto_wire().await?;
+    /// ```
+    pub async fn scan_components<F>(&self, scan: F) -> Result<()>
+    where
+        F: FnOnce(&ConfigurationManager) -> Result<Vec<crate::BeanDefinition>>,
+    {
+        let definitions = scan(&self.config)?;
+        debug!("扫描到 {} 个组件", definitions.len());
+        self.container.write().await.register_beans(definitions);
+        Ok(())
+    }
+
     /// 获取组件实例
     pub async fn get<T: 'static>(&self) -> Option<Arc<T>> {
         let container = self.container.read().await;
@@ -103,8 +183,13 @@ pub trait RSpringApplication {
 pub struct RSpringApp {
     /// 应用上下文
     context: ApplicationContext,
+    /// 自动装配前绑定的配置类
+    configurations: Vec<ConfigurationBinder>,
 }
 
+/// 按配置前缀绑定配置类并注册到容器
+type ConfigurationBinder = for<'a> fn(&'a ApplicationContext) -> BoxFuture<'a, Result<()>>;
+
 impl RSpringApp {
     /// 创建新的应用程序实例
     /// 
@@ -112,37 +197,87 @@ impl RSpringApp {
     /// 当应用上下文创建失败时返回错误
     pub fn new() -> Result<Self> {
         let context = ApplicationContext::new()?;
-        Ok(Self { context })
+        Ok(Self { context, configurations: Vec::new() })
+    }
+
+    /// 登记在自动装配前绑定的配置类
+    ///
+    /// 启动时通过 [`ApplicationContext::bind_configuration`] 绑定，绑定失败时启动失败
+    pub fn configuration<T: crate::config::properties::Configuration>(mut self) -> Self {
+        self.configurations.push(|context| {
+            Box::pin(async move { context.bind_configuration::<T>().await.map(|_| ()) })
+        });
+        self
     }
     
     /// 运行应用程序
     /// 
     /// 执行完整的应用程序生命周期：
     /// 1. 初始化日志系统
-    /// 2. 加载配置
-    /// 3. 自动装配容器
+    /// 2. 加载配置，绑定登记的配置类
+    /// 3. 自动装配容器，监听端口的组件绑定地址后输出启动报告并发布 [`ApplicationReady`] 事件
     /// 4. 启动应用（等待关闭信号）
+    /// 5. 等待异步任务执行完成，写完日志文件中缓存的日志
     pub async fn run(&self) -> Result<()> {
+        let startup = startup_recorder();
+        
         // 1. 初始化日志系统
-        self.init_logging().await?;
+        startup.time_step("logging", self.init_logging()).await?;
         
         info!("启动 RSpring 应用程序");
         
         // 2. 加载和验证配置
-        self.load_configuration().await?;
+        startup.time_step("configuration", self.load_configuration()).await?;
+        startup.time_step("error_reporting", async { self.init_error_reporting() }).await?;
+        startup.time_step("configurations", self.bind_configurations()).await?;
         
         // 3. 执行自动装配
-        self.context.auto_wire().await?;
+        startup.time_step("clock", self.init_clock()).await?;
+        startup.time_step("auto_wire", self.context.auto_wire()).await?;
+        startup.time_step("task_executor", async { self.init_task_executor() }).await?;
+        startup.time_step("metrics", async { self.init_metrics() }).await?;
+        startup.time_step("audit", async { self.init_audit() }).await?;
+        startup.time_step("resilience", async { self.init_resilience() }).await?;
+        startup.time_step("messaging", async { self.init_messaging() }).await?;
         
-        info!("RSpring 应用程序启动完成");
+        startup.config_sources(&self.context.config);
+        // Web 服务器等组件绑定地址后再输出启动报告，不影响等待关闭信号
+        tokio::spawn(async move {
+            startup.released().await;
+            let report = startup.ready();
+            info!("RSpring 应用程序启动完成，{}", report.summary());
+            if let Err(e) = crate::event::publish_event(ApplicationReady { report }).await {
+                error!("处理启动完成事件失败: {}", e);
+            }
+        });
         
         // 4. 保持运行直到收到关闭信号
         self.await_shutdown().await?;
         
+        // 5. 等待已提交的异步任务完成
+        crate::task::task_executor().shutdown().await;
+        // 上报方式的 flush 会阻塞等待，在阻塞线程池中执行，不占用运行时的工作线程
+        let reporting = crate::error::error_reporting();
+        let flushed = tokio::task::spawn_blocking(move || reporting.flush(ERROR_REPORT_FLUSH_TIMEOUT))
+            .await
+            .unwrap_or(false);
+        if !flushed {
+            error!("关闭前未能发送完所有错误报告");
+        }
+        
         info!("RSpring 应用程序已停止");
+        crate::logging::shutdown_logging();
         Ok(())
     }
     
+    /// 绑定登记的配置类
+    async fn bind_configurations(&self) -> Result<()> {
+        for bind in &self.configurations {
+            bind(&self.context).await?;
+        }
+        Ok(())
+    }
+
     /// 初始化日志系统
     async fn init_logging(&self) -> Result<()> {
         let logging_config = self.context.config
@@ -187,6 +322,108 @@ impl RSpringApp {
         Ok(())
     }
     
+    /// 按 `[error_reporting]` 配置设置全局错误上报器，版本和环境默认使用应用版本和当前 profile
+    fn init_error_reporting(&self) -> Result<()> {
+        let config = ErrorReportingConfig::load(&self.context.config)?;
+        let app_config = self.context.config
+            .get_section::<AppConfig>("app")
+            .unwrap_or_else(|_| AppConfig::default());
+        let reporting = ErrorReporting::from_config(
+            &config,
+            Some(app_config.version),
+            Some(self.context.config.profile().to_string()),
+        )?;
+        debug!(
+            "错误上报方式: {}, 版本: {:?}, 环境: {:?}",
+            reporting.reporter_count(),
+            reporting.release(),
+            reporting.environment()
+        );
+        crate::error::set_error_reporting(Arc::new(reporting));
+        if config.enabled && config.capture_panics {
+            crate::error::install_panic_hook();
+        }
+        Ok(())
+    }
+    
+    /// 将全局时钟注册到容器，已注册的 [`ApplicationClock`] 替换全局时钟
+    async fn init_clock(&self) -> Result<()> {
+        match self.context.get::<ApplicationClock>().await {
+            Some(clock) => {
+                debug!("使用容器中注册的时钟");
+                crate::clock::set_clock(clock.clock());
+            }
+            None => {
+                self.context
+                    .register_singleton(ApplicationClock::new(crate::clock::clock()))
+                    .await;
+            }
+        }
+        Ok(())
+    }
+    
+    /// 按 `[task.execution]` 配置设置全局任务执行器
+    fn init_task_executor(&self) -> Result<()> {
+        let config: TaskExecutionConfig = if self.context.config.contains_key("task.execution") {
+            self.context.config.get_section("task.execution")?
+        } else {
+            TaskExecutionConfig::default()
+        };
+        
+        debug!("任务执行器并发数: {}", config.pool_size);
+        crate::task::set_task_executor(Arc::new(TaskExecutor::new(config)));
+        Ok(())
+    }
+    
+    /// 按 `[metrics]` 配置安装 Prometheus 记录器并启动运行时指标采集
+    fn init_metrics(&self) -> Result<()> {
+        #[cfg(feature = "metrics")]
+        {
+            let config = crate::metrics::MetricsConfig::load(&self.context.config)?;
+            if !config.enabled {
+                return Ok(());
+            }
+            #[cfg(feature = "prometheus")]
+            {
+                crate::metrics::install_prometheus(&config)?;
+                debug!("Prometheus 指标记录器安装完成，公共标签: {:?}", config.tags);
+            }
+            if config.runtime.enabled {
+                crate::metrics::spawn_runtime_metrics(&config.runtime);
+                debug!("Tokio 运行时指标采集已启动，间隔: {:?}", config.runtime.interval);
+            }
+        }
+        Ok(())
+    }
+    
+    /// 按 `[audit]` 配置设置全局审计记录器，保留之前已添加的存储
+    fn init_audit(&self) -> Result<()> {
+        let config = crate::auditing::AuditConfig::load(&self.context.config)?;
+        debug!("审计事件记录: {}, 审计日志: {}", config.enabled, config.log);
+        let recorder = crate::auditing::audit_recorder().reconfigure(&config);
+        crate::auditing::set_audit_recorder(Arc::new(recorder));
+        Ok(())
+    }
+    
+    /// 按 `[resilience]` 配置设置全局断路器注册表
+    fn init_resilience(&self) -> Result<()> {
+        if self.context.config.contains_key("resilience") {
+            let config: ResilienceConfig = self.context.config.get_section("resilience")?;
+            crate::resilience::set_circuit_breaker_registry(Arc::new(CircuitBreakerRegistry::new(config)));
+        }
+        Ok(())
+    }
+    
+    /// 按 `[messaging]` 配置设置全局消息转换器
+    fn init_messaging(&self) -> Result<()> {
+        if self.context.config.contains_key("messaging") {
+            let config: MessagingConfig = self.context.config.get_section("messaging")?;
+            debug!("消息编码格式: {:?}", config.format);
+            crate::messaging::set_message_converter(config.converter()?);
+        }
+        Ok(())
+    }
+    
     /// 等待应用程序关闭信号
     async fn await_shutdown(&self) -> Result<()> {
         info!("应用程序运行中，按 Ctrl+C 停止");
@@ -211,4 +448,64 @@ impl Default for RSpringApp {
 }
 
 /// 为向后兼容保留的类型别名
-pub type AxumBootApplication = RSpringApp;
\ No newline at end of file
+pub type AxumBootApplication = RSpringApp;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct BatchConfig {
+        size: usize,
+    }
+
+    /// 与 `#[derive(Configuration)]` 和 `#[config(prefix = "app.batch")]` 生成的实现相同
+    impl crate::config::properties::Configuration for BatchConfig {
+        fn prefix() -> &'static str {
+            "app.batch"
+        }
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct MissingConfig {
+        #[allow(dead_code)]
+        url: String,
+    }
+
+    impl crate::config::properties::Configuration for MissingConfig {
+        fn prefix() -> &'static str {
+            "missing"
+        }
+    }
+
+    /// 测试按前缀绑定配置类并注册为单例
+    #[tokio::test]
+    async fn test_bind_configuration() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("application.toml"), "[app.batch]\nsize = 20\n").unwrap();
+        let config = ConfigurationManager::from_dirs(&[dir.path().to_path_buf()], "dev", "RSPRING_BIND_TEST").unwrap();
+        let context = ApplicationContext::with_config(config);
+
+        let batch = context.bind_configuration::<BatchConfig>().await.unwrap();
+        assert_eq!(batch.size, 20);
+        assert!(Arc::ptr_eq(&batch, &context.get::<BatchConfig>().await.unwrap()));
+        assert!(context.bind_configuration::<MissingConfig>().await.is_err());
+    }
+
+    /// 测试应用程序启动时绑定登记的配置类
+    #[tokio::test]
+    async fn test_app_binds_configurations() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("application.toml"), "[app.batch]\nsize = 5\n").unwrap();
+        let config = ConfigurationManager::from_dirs(&[dir.path().to_path_buf()], "dev", "RSPRING_APP_BIND_TEST").unwrap();
+        let context = ApplicationContext::with_config(config);
+        let app = RSpringApp { context, configurations: Vec::new() }.configuration::<BatchConfig>();
+
+        app.bind_configurations().await.unwrap();
+        assert_eq!(app.context.get::<BatchConfig>().await.unwrap().size, 5);
+
+        let app = app.configuration::<MissingConfig>();
+        assert!(app.bind_configurations().await.is_err());
+    }
+}
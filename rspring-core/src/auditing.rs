@@ -0,0 +1,118 @@
+//! 审计模块
+//!
+//! 为实体自动维护创建时间、更新时间、创建人和更新人：
+//! - `#[derive(Audited)]` 为包含审计字段的实体实现 [`Auditable`]
+//! - 仓储的 `#[repository(..., audited)]` 在生成的 `save`、`update` 中写入审计列
+//!
+//! 操作人取自安全上下文中的当前主体（见 [`crate::security`]），不在认证上下文中时为 `None`。
+//!
+//! 业务操作和安全事件的审计记录见 [`event`] 模块
+
+pub mod event;
+
+pub use chrono::{DateTime, Utc};
+pub use event::{
+    audit, audit_recorder, set_audit_recorder, AuditConfig, AuditEvent, AuditOutcome, AuditRecorder, AuditSink,
+    InMemoryAuditSink, LogAuditSink, AUDIT_LOG_TARGET,
+};
+
+use crate::security::current_principal;
+
+/// 创建时间列
+pub const CREATED_AT: &str = "created_at";
+/// 更新时间列
+pub const UPDATED_AT: &str = "updated_at";
+/// 创建人列
+pub const CREATED_BY: &str = "created_by";
+/// 更新人列
+pub const UPDATED_BY: &str = "updated_by";
+
+/// 一次写操作的审计信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditStamp {
+    /// 操作时间
+    pub at: DateTime<Utc>,
+    /// 操作人
+    pub by: Option<String>,
+}
+
+impl AuditStamp {
+    /// 以当前时间和当前操作人生成审计信息
+    ///
+    /// 时间截断到微秒，与 MySQL `DATETIME(6)` 的精度一致
+    pub fn now() -> Self {
+        let now = crate::clock::now();
+        Self {
+            at: now - chrono::Duration::nanoseconds(i64::from(now.timestamp_subsec_nanos() % 1000)),
+            by: current_auditor(),
+        }
+    }
+}
+
+/// 获取当前操作人
+pub fn current_auditor() -> Option<String> {
+    current_principal().map(|principal| principal.name)
+}
+
+/// 可审计实体特征
+///
+/// 通常通过 `#[derive(Audited)]` 实现，实体需包含以下字段：
+/// - `created_at: Option<DateTime<Utc>>`
+/// - `updated_at: Option<DateTime<Utc>>`
+/// - `created_by: Option<String>`
+/// - `updated_by: Option<String>`
+///
+/// # 示例
+/// ```rust
+/// #[derive(sqlx::FromRow, Audited)]
+/// pub struct User {
+///     pub id: i64,
+///     pub name: String,
+///     pub created_at: Option<DateTime<Utc>>,
+///     pub updated_at: Option<DateTime<Utc>>,
+///     pub created_by: Option<String>,
+///     pub updated_by: Option<String>,
+/// }
+///
+/// #[derive(Repository)]
+/// #[repository(entity = User, table = "users", columns = "name", audited)]
+/// pub struct UserRepository {
+///     pool: MySqlPool,
+/// }
+/// ```
+pub trait Auditable {
+    /// 创建时间
+    fn created_at(&self) -> Option<DateTime<Utc>>;
+
+    /// 更新时间
+    fn updated_at(&self) -> Option<DateTime<Utc>>;
+
+    /// 创建人
+    fn created_by(&self) -> Option<&str>;
+
+    /// 更新人
+    fn updated_by(&self) -> Option<&str>;
+
+    /// 写入创建信息，同时作为首次更新信息
+    fn mark_created(&mut self, stamp: &AuditStamp);
+
+    /// 写入更新信息
+    fn mark_updated(&mut self, stamp: &AuditStamp);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::{with_principal, Principal};
+
+    /// 测试操作人取自当前主体
+    #[tokio::test]
+    async fn test_audit_stamp() {
+        assert_eq!(AuditStamp::now().by, None);
+
+        let stamp = with_principal(Principal::new("alice"), async { AuditStamp::now() }).await;
+        assert_eq!(stamp.by.as_deref(), Some("alice"));
+        assert_eq!(stamp.at.timestamp_subsec_nanos() % 1000, 0);
+        assert!(Utc::now() >= stamp.at);
+    }
+}
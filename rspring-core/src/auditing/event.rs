@@ -0,0 +1,605 @@
+//! 审计事件模块
+//!
+//! 记录谁在什么时候对什么资源做了什么操作、结果如何：
+//! - [`AuditEvent`] 描述一次操作，操作人默认取当前主体，请求 ID 取自日志上下文
+//! - [`AuditSink`] 抽象事件的存储，内置写日志的 [`LogAuditSink`] 和保存在内存中的 [`InMemoryAuditSink`]；
+//!   MySQL 启动器提供写审计表的实现，Web 启动器的 `audit-webhook` 特性提供推送到 HTTP 端点的实现
+//! - [`AuditRecorder`] 将事件放入有界队列，由后台任务分发给所有存储；存储失败只记录日志，
+//!   队列已满时丢弃事件并计数，不阻塞业务操作
+//!
+//! Web 启动器在认证失败、授权拒绝、登录登出和通过管理端点修改日志级别时自动记录审计事件
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::auditing::current_auditor;
+use crate::config::properties::Configuration;
+use crate::config::ConfigurationManager;
+use crate::error::Result;
+use crate::logging::context::{log_context, REQUEST_ID_FIELD};
+
+/// 审计日志的目标，可以通过 `[logging.levels]` 单独调整级别或输出
+pub const AUDIT_LOG_TARGET: &str = "rspring::audit";
+
+/// 操作结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    /// 成功
+    Success,
+    /// 失败，如密码错误
+    Failure,
+    /// 被拒绝，如权限不足
+    Denied,
+}
+
+impl AuditOutcome {
+    /// 结果名称，如 `"denied"`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditOutcome::Success => "success",
+            AuditOutcome::Failure => "failure",
+            AuditOutcome::Denied => "denied",
+        }
+    }
+}
+
+/// 审计事件
+///
+/// # 示例
+/// ```rust
+/// audit(
+///     AuditEvent::new("order.refund")
+///         .resource(format!("order:{}", order.id))
+///         .metadata("amount", order.amount),
+/// )
+/// .await;
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AuditEvent {
+    /// 发生时间
+    pub timestamp: DateTime<Utc>,
+    /// 操作人，匿名操作为 `None`
+    pub actor: Option<String>,
+    /// 动作，如 `"security.login"`
+    pub action: String,
+    /// 操作的资源，如 `"order:42"`
+    pub resource: Option<String>,
+    /// 操作结果
+    pub outcome: AuditOutcome,
+    /// 请求 ID
+    pub request_id: Option<String>,
+    /// 附加信息
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, Value>,
+}
+
+impl AuditEvent {
+    /// 创建成功的审计事件
+    ///
+    /// 操作人取当前主体，请求 ID 取自当前日志上下文
+    pub fn new(action: impl Into<String>) -> Self {
+        Self {
+            timestamp: crate::clock::now(),
+            actor: current_auditor(),
+            action: action.into(),
+            resource: None,
+            outcome: AuditOutcome::Success,
+            request_id: log_context().remove(REQUEST_ID_FIELD),
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    /// 设置操作人，如登录失败时尝试的用户名
+    pub fn actor(mut self, actor: impl Into<String>) -> Self {
+        self.actor = Some(actor.into());
+        self
+    }
+
+    /// 设置操作的资源
+    pub fn resource(mut self, resource: impl Into<String>) -> Self {
+        self.resource = Some(resource.into());
+        self
+    }
+
+    /// 设置操作结果
+    pub fn outcome(mut self, outcome: AuditOutcome) -> Self {
+        self.outcome = outcome;
+        self
+    }
+
+    /// 加入附加信息，已有同名字段时覆盖
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// 审计事件存储特征
+///
+/// # 示例
+/// ```rust
+/// struct KafkaAuditSink { producer: FutureProducer }
+///
+/// #[async_trait]
+/// impl AuditSink for KafkaAuditSink {
+///     async fn record(&self, event: &AuditEvent) -> Result<()> {
+///         let payload = serde_json::to_vec(event)?;
+///         self.producer.send(FutureRecord::to("audit").payload(&payload), Timeout::Never).await?;
+///         Ok(())
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait AuditSink: Send + Sync {
+    /// 保存审计事件
+    async fn record(&self, event: &AuditEvent) -> Result<()>;
+}
+
+/// 以 [`AUDIT_LOG_TARGET`] 为目标写日志的存储
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogAuditSink;
+
+#[async_trait]
+impl AuditSink for LogAuditSink {
+    async fn record(&self, event: &AuditEvent) -> Result<()> {
+        let metadata = serde_json::to_string(&event.metadata)?;
+        tracing::info!(
+            target: AUDIT_LOG_TARGET,
+            actor = event.actor.as_deref().unwrap_or("-"),
+            action = %event.action,
+            resource = event.resource.as_deref().unwrap_or("-"),
+            outcome = event.outcome.as_str(),
+            metadata = %metadata,
+            "审计事件"
+        );
+        Ok(())
+    }
+}
+
+/// 保存在内存中的存储，用于测试或在管理端点展示最近的事件
+#[derive(Debug, Default)]
+pub struct InMemoryAuditSink {
+    /// 已保存的事件
+    events: Mutex<Vec<AuditEvent>>,
+}
+
+impl InMemoryAuditSink {
+    /// 创建空的存储
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 获取已保存的事件
+    pub fn events(&self) -> Vec<AuditEvent> {
+        self.events.lock().expect("审计事件锁已损坏").clone()
+    }
+
+    /// 清空已保存的事件
+    pub fn clear(&self) {
+        self.events.lock().expect("审计事件锁已损坏").clear();
+    }
+}
+
+#[async_trait]
+impl AuditSink for InMemoryAuditSink {
+    async fn record(&self, event: &AuditEvent) -> Result<()> {
+        self.events
+            .lock()
+            .expect("审计事件锁已损坏")
+            .push(event.clone());
+        Ok(())
+    }
+}
+
+/// 审计记录器，将事件交给后台任务分发给所有存储
+///
+/// 记录事件只放入有界队列，不等待存储完成，慢速的存储（如推送到 HTTP 端点）不会拖慢认证失败等请求；
+/// 队列已满时丢弃事件，丢弃数量通过 [`dropped_count`](Self::dropped_count) 获取。
+/// 后台任务在首次记录事件时启动
+pub struct AuditRecorder {
+    /// 按添加顺序排列的存储
+    sinks: Arc<RwLock<Vec<Arc<dyn AuditSink>>>>,
+    /// 队列容量
+    capacity: usize,
+    /// 发往后台任务的队列，未启动或所在运行时已关闭时重新创建
+    queue: Mutex<Option<mpsc::Sender<Dispatch>>>,
+    /// 因队列已满丢弃的事件数量
+    dropped: AtomicU64,
+    /// 是否按配置加入了 [`LogAuditSink`]，加入时它是第一个存储
+    log: bool,
+}
+
+/// 发往后台任务的消息
+enum Dispatch {
+    /// 分发事件
+    Event(AuditEvent),
+    /// 之前的事件分发完成后通知
+    Flush(oneshot::Sender<()>),
+}
+
+impl Default for AuditRecorder {
+    fn default() -> Self {
+        Self::with_capacity(default_queue_capacity())
+    }
+}
+
+impl AuditRecorder {
+    /// 创建没有存储的记录器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 创建指定队列容量的记录器
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            sinks: Arc::new(RwLock::new(Vec::new())),
+            capacity: capacity.max(1),
+            queue: Mutex::new(None),
+            dropped: AtomicU64::new(0),
+            log: false,
+        }
+    }
+
+    /// 按配置创建记录器，启用日志存储时加入 [`LogAuditSink`]
+    pub fn from_config(config: &AuditConfig) -> Self {
+        let mut recorder = Self::with_capacity(config.queue_capacity);
+        if config.enabled && config.log {
+            recorder.add_sink(Arc::new(LogAuditSink));
+            recorder.log = true;
+        }
+        recorder
+    }
+
+    /// 按新的配置创建记录器，保留已添加的存储
+    ///
+    /// 按原配置加入的 [`LogAuditSink`] 由新的配置决定是否保留
+    ///
+    /// # 示例
+    /// ```rust
+    /// let recorder = audit_recorder().reconfigure(&AuditConfig::load(&config)?);
+    /// set_audit_recorder(Arc::new(recorder));
+    /// ```
+    pub fn reconfigure(&self, config: &AuditConfig) -> Self {
+        let recorder = Self::from_config(config);
+        let sinks = self.sinks.read().expect("审计存储锁已损坏");
+        for sink in sinks.iter().skip(usize::from(self.log)) {
+            recorder.add_sink(sink.clone());
+        }
+        recorder
+    }
+
+    /// 添加存储
+    pub fn add_sink(&self, sink: Arc<dyn AuditSink>) {
+        self.sinks.write().expect("审计存储锁已损坏").push(sink);
+    }
+
+    /// 获取存储数量
+    pub fn sink_count(&self) -> usize {
+        self.sinks.read().expect("审计存储锁已损坏").len()
+    }
+
+    /// 获取因队列已满丢弃的事件数量
+    pub fn dropped_count(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// 将事件放入队列，由后台任务依次交给所有存储
+    ///
+    /// 不等待存储完成；存储返回错误时记录日志并继续交给后续存储，队列已满时丢弃事件
+    pub async fn record(&self, event: &AuditEvent) {
+        if self.sink_count() == 0 {
+            return;
+        }
+        let Some(queue) = self.queue() else {
+            tracing::warn!("不在 Tokio 运行时中，丢弃审计事件 {}", event.action);
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            return;
+        };
+        if queue.try_send(Dispatch::Event(event.clone())).is_err() {
+            let dropped = self.dropped.fetch_add(1, Ordering::Relaxed);
+            if dropped % 1000 == 0 {
+                tracing::warn!("审计事件队列已满，已丢弃 {} 个事件", dropped + 1);
+            }
+        }
+    }
+
+    /// 等待已放入队列的事件分发完成
+    ///
+    /// 用于测试和应用退出前确保事件已保存
+    pub async fn flush(&self) {
+        let queue = self.queue.lock().expect("审计队列锁已损坏").clone();
+        let Some(queue) = queue else {
+            return;
+        };
+        let (done, flushed) = oneshot::channel();
+        if queue.send(Dispatch::Flush(done)).await.is_ok() {
+            let _ = flushed.await;
+        }
+    }
+
+    /// 获取发往后台任务的队列，需要时在当前运行时中启动后台任务
+    fn queue(&self) -> Option<mpsc::Sender<Dispatch>> {
+        let mut queue = self.queue.lock().expect("审计队列锁已损坏");
+        if let Some(sender) = queue.as_ref().filter(|sender| !sender.is_closed()) {
+            return Some(sender.clone());
+        }
+        let runtime = tokio::runtime::Handle::try_current().ok()?;
+        let (sender, receiver) = mpsc::channel(self.capacity);
+        runtime.spawn(dispatch(self.sinks.clone(), receiver));
+        *queue = Some(sender.clone());
+        Some(sender)
+    }
+}
+
+/// 后台任务，依次将事件交给所有存储，记录器释放后处理完剩余事件退出
+async fn dispatch(sinks: Arc<RwLock<Vec<Arc<dyn AuditSink>>>>, mut receiver: mpsc::Receiver<Dispatch>) {
+    while let Some(message) = receiver.recv().await {
+        match message {
+            Dispatch::Event(event) => {
+                let sinks = sinks.read().expect("审计存储锁已损坏").clone();
+                for sink in sinks {
+                    if let Err(e) = sink.record(&event).await {
+                        tracing::warn!("保存审计事件 {} 失败: {}", event.action, e);
+                    }
+                }
+            }
+            Dispatch::Flush(done) => {
+                let _ = done.send(());
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for AuditRecorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuditRecorder")
+            .field("sinks", &self.sink_count())
+            .field("capacity", &self.capacity)
+            .field("dropped", &self.dropped_count())
+            .finish()
+    }
+}
+
+/// 审计配置
+///
+/// # 示例
+/// ```toml
+/// [audit]
+/// enabled = true
+/// log = false
+/// queue_capacity = 1024
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct AuditConfig {
+    /// 是否记录审计事件，关闭时启动器不添加任何存储
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 是否写审计日志
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_log")]
+    pub log: bool,
+    /// 待分发事件的队列容量，队列已满时丢弃新事件
+    ///
+    /// # 默认值
+    /// `1024`
+    #[serde(default = "default_queue_capacity")]
+    pub queue_capacity: usize,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            log: default_log(),
+            queue_capacity: default_queue_capacity(),
+        }
+    }
+}
+
+impl AuditConfig {
+    /// 加载 `[audit]` 配置，没有该配置时使用默认值
+    ///
+    /// # 错误
+    /// 配置格式错误时返回错误
+    pub fn load(config: &ConfigurationManager) -> Result<Self> {
+        if config.contains_key("audit") {
+            config.get_section("audit")
+        } else {
+            Ok(Self::default())
+        }
+    }
+}
+
+impl Configuration for AuditConfig {}
+
+/// 全局审计记录器，默认写审计日志
+static AUDIT_RECORDER: Lazy<RwLock<Arc<AuditRecorder>>> = Lazy::new(|| {
+    RwLock::new(Arc::new(
+        AuditRecorder::from_config(&AuditConfig::default()),
+    ))
+});
+
+/// 设置全局审计记录器
+///
+/// 在作用域中调用时的行为见[全局组件作用域](crate::scope)
+pub fn set_audit_recorder(recorder: Arc<AuditRecorder>) {
+    if let Some(recorder) = crate::scope::set_scoped(recorder) {
+        *AUDIT_RECORDER.write().expect("审计记录器锁已损坏") = recorder;
+    }
+}
+
+/// 获取全局审计记录器
+///
+/// # 示例
+/// ```rust
+/// let sink = MySqlAuditSink::new(pool.clone(), "audit_events")?;
+/// sink.create_table().await?;
+/// audit_recorder().add_sink(Arc::new(sink));
+/// ```
+pub fn audit_recorder() -> Arc<AuditRecorder> {
+    crate::scope::scoped()
+        .unwrap_or_else(|| AUDIT_RECORDER.read().expect("审计记录器锁已损坏").clone())
+}
+
+/// 通过全局审计记录器记录事件
+pub async fn audit(event: AuditEvent) {
+    audit_recorder().record(&event).await
+}
+
+// 默认值函数
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_log() -> bool {
+    true
+}
+
+fn default_queue_capacity() -> usize {
+    1024
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use crate::logging::context::with_log_context;
+    use crate::security::{with_principal, Principal};
+
+    /// 总是失败的存储
+    struct FailingSink;
+
+    #[async_trait]
+    impl AuditSink for FailingSink {
+        async fn record(&self, _event: &AuditEvent) -> Result<()> {
+            Err(Error::internal("存储不可用"))
+        }
+    }
+
+    /// 测试事件的默认字段和存储失败时继续分发
+    #[tokio::test]
+    async fn test_audit_recorder() {
+        let memory = Arc::new(InMemoryAuditSink::new());
+        let recorder = AuditRecorder::new();
+        recorder.add_sink(Arc::new(FailingSink));
+        recorder.add_sink(memory.clone());
+
+        let event = with_principal(Principal::new("alice"), async {
+            with_log_context([(REQUEST_ID_FIELD, "req-1")], async {
+                AuditEvent::new("order.refund")
+                    .resource("order:42")
+                    .metadata("amount", 100)
+            })
+            .await
+        })
+        .await;
+        recorder.record(&event).await;
+        recorder
+            .record(
+                &AuditEvent::new("security.login")
+                    .actor("bob")
+                    .outcome(AuditOutcome::Failure),
+            )
+            .await;
+        recorder.flush().await;
+
+        let events = memory.events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].actor.as_deref(), Some("alice"));
+        assert_eq!(events[0].request_id.as_deref(), Some("req-1"));
+        assert_eq!(
+            serde_json::to_value(&events[1]).unwrap()["outcome"],
+            "failure"
+        );
+        assert_eq!(events[1].request_id, None);
+
+        assert_eq!(
+            AuditRecorder::from_config(&AuditConfig::default()).sink_count(),
+            1
+        );
+        let disabled = AuditConfig {
+            enabled: false,
+            ..AuditConfig::default()
+        };
+        assert_eq!(AuditRecorder::from_config(&disabled).sink_count(), 0);
+    }
+
+    /// 测试重新配置时保留已添加的存储，日志存储由新配置决定
+    #[tokio::test]
+    async fn test_reconfigure() {
+        let memory = Arc::new(InMemoryAuditSink::new());
+        let recorder = AuditRecorder::from_config(&AuditConfig::default());
+        recorder.add_sink(memory.clone());
+
+        let no_log = AuditConfig {
+            log: false,
+            ..AuditConfig::default()
+        };
+        let recorder = recorder.reconfigure(&no_log);
+        assert_eq!(recorder.sink_count(), 1);
+        let recorder = recorder.reconfigure(&AuditConfig::default());
+        assert_eq!(recorder.sink_count(), 2);
+
+        recorder.record(&AuditEvent::new("order.refund")).await;
+        recorder.flush().await;
+        assert_eq!(memory.events().len(), 1);
+    }
+
+    /// 阻塞到收到通知的存储
+    struct BlockingSink {
+        /// 开始保存事件时通知
+        started: mpsc::UnboundedSender<()>,
+        /// 允许继续保存
+        release: Arc<tokio::sync::Semaphore>,
+        /// 已保存的事件
+        memory: InMemoryAuditSink,
+    }
+
+    #[async_trait]
+    impl AuditSink for BlockingSink {
+        async fn record(&self, event: &AuditEvent) -> Result<()> {
+            let _ = self.started.send(());
+            self.release.acquire().await.unwrap().forget();
+            self.memory.record(event).await
+        }
+    }
+
+    /// 测试记录事件不等待存储，队列已满时丢弃并计数
+    #[tokio::test]
+    async fn test_audit_recorder_queue_full() {
+        let (started, mut started_rx) = mpsc::unbounded_channel();
+        let release = Arc::new(tokio::sync::Semaphore::new(0));
+        let sink = Arc::new(BlockingSink {
+            started,
+            release: release.clone(),
+            memory: InMemoryAuditSink::new(),
+        });
+        let recorder = AuditRecorder::with_capacity(1);
+        recorder.add_sink(sink.clone());
+
+        recorder.record(&AuditEvent::new("security.authentication")).await;
+        started_rx.recv().await.unwrap();
+        recorder.record(&AuditEvent::new("security.authentication")).await;
+        recorder.record(&AuditEvent::new("security.authentication")).await;
+        assert_eq!(recorder.dropped_count(), 1);
+
+        release.add_permits(2);
+        recorder.flush().await;
+        assert_eq!(sink.memory.events().len(), 2);
+    }
+}
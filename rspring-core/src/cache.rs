@@ -0,0 +1,321 @@
+//! 缓存抽象模块
+//!
+//! 提供类似 Spring Cache 的缓存 SPI：
+//! - [`Cache`] 和 [`CacheManager`] 特征，可接入内存、Redis 等不同实现
+//! - 默认的 [`InMemoryCacheManager`]，按 `[cache]` 配置为每个缓存设置过期时间和容量
+//! - `#[Cacheable]`、`#[CacheEvict]` 注解通过全局缓存管理器读写缓存
+//!
+//! 缓存值以 JSON 形式保存，缓存的类型需实现 `Serialize` 和 `DeserializeOwned`；
+//! 内存缓存的过期时间按全局时钟的单调时间（见 [`crate::clock::monotonic`]）计算
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config::properties::Configuration;
+
+/// 缓存特征
+#[async_trait]
+pub trait Cache: Send + Sync {
+    /// 缓存名称
+    fn name(&self) -> &str;
+
+    /// 读取缓存，不存在或已过期时返回 `None`
+    async fn get(&self, key: &str) -> Option<Value>;
+
+    /// 写入缓存
+    async fn put(&self, key: &str, value: Value);
+
+    /// 删除缓存
+    async fn evict(&self, key: &str);
+
+    /// 清空缓存
+    async fn clear(&self);
+}
+
+/// 缓存管理器特征
+pub trait CacheManager: Send + Sync {
+    /// 获取指定名称的缓存，不存在时创建
+    fn cache(&self, name: &str) -> Arc<dyn Cache>;
+
+    /// 获取已创建的缓存名称
+    fn cache_names(&self) -> Vec<String>;
+}
+
+/// 读取并反序列化缓存值
+///
+/// 缓存值无法反序列化为 `T` 时视为未命中
+pub async fn get_value<T: DeserializeOwned>(cache: &dyn Cache, key: &str) -> Option<T> {
+    let value = cache.get(key).await?;
+    match serde_json::from_value(value) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            tracing::warn!("缓存 {} 中的值 {} 反序列化失败: {}", cache.name(), key, e);
+            None
+        }
+    }
+}
+
+/// 序列化并写入缓存值
+///
+/// 值无法序列化时不写入
+pub async fn put_value<T: Serialize + ?Sized>(cache: &dyn Cache, key: &str, value: &T) {
+    match serde_json::to_value(value) {
+        Ok(value) => cache.put(key, value).await,
+        Err(e) => tracing::warn!("缓存 {} 中的值 {} 序列化失败: {}", cache.name(), key, e),
+    }
+}
+
+/// 全局缓存管理器
+static CACHE_MANAGER: Lazy<RwLock<Arc<dyn CacheManager>>> =
+    Lazy::new(|| RwLock::new(Arc::new(InMemoryCacheManager::default())));
+
+/// 设置全局缓存管理器，供 `#[Cacheable]`、`#[CacheEvict]` 使用
+///
+/// 未设置时使用默认配置的 [`InMemoryCacheManager`]
+///
+/// 在作用域中调用时的行为见[全局组件作用域](crate::scope)
+pub fn set_cache_manager(manager: Arc<dyn CacheManager>) {
+    if let Some(manager) = crate::scope::set_scoped(manager) {
+        *CACHE_MANAGER.write().expect("缓存管理器锁已损坏") = manager;
+    }
+}
+
+/// 获取全局缓存管理器
+pub fn cache_manager() -> Arc<dyn CacheManager> {
+    crate::scope::scoped()
+        .unwrap_or_else(|| CACHE_MANAGER.read().expect("缓存管理器锁已损坏").clone())
+}
+
+/// 缓存配置
+///
+/// # 示例
+/// ```toml
+/// [cache]
+/// default_ttl = "10m"
+///
+/// [cache.caches.users]
+/// ttl = "30s"
+/// max_entries = 1000
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct CacheConfig {
+    /// 默认过期时间，未设置时不过期
+    #[serde(default, with = "crate::config::duration::option")]
+    pub default_ttl: Option<Duration>,
+    /// 默认最大条目数，未设置时不限制
+    #[serde(default)]
+    pub default_max_entries: Option<usize>,
+    /// 按缓存名称的配置
+    #[serde(default)]
+    pub caches: HashMap<String, CacheSpec>,
+}
+
+impl CacheConfig {
+    /// 获取指定缓存的过期时间
+    pub fn ttl(&self, name: &str) -> Option<Duration> {
+        self.caches
+            .get(name)
+            .and_then(|spec| spec.ttl)
+            .or(self.default_ttl)
+    }
+
+    /// 获取指定缓存的最大条目数
+    pub fn max_entries(&self, name: &str) -> Option<usize> {
+        self.caches
+            .get(name)
+            .and_then(|spec| spec.max_entries)
+            .or(self.default_max_entries)
+    }
+}
+
+impl Configuration for CacheConfig {}
+
+/// 单个缓存的配置
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct CacheSpec {
+    /// 过期时间
+    #[serde(default, with = "crate::config::duration::option")]
+    pub ttl: Option<Duration>,
+    /// 最大条目数，超出时淘汰最早写入的条目
+    #[serde(default)]
+    pub max_entries: Option<usize>,
+}
+
+/// 内存缓存管理器
+#[derive(Default)]
+pub struct InMemoryCacheManager {
+    /// 缓存配置
+    config: CacheConfig,
+    /// 已创建的缓存
+    caches: RwLock<HashMap<String, Arc<InMemoryCache>>>,
+}
+
+impl InMemoryCacheManager {
+    /// 根据缓存配置创建
+    pub fn new(config: CacheConfig) -> Self {
+        Self {
+            config,
+            caches: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl CacheManager for InMemoryCacheManager {
+    fn cache(&self, name: &str) -> Arc<dyn Cache> {
+        if let Some(cache) = self.caches.read().expect("缓存锁已损坏").get(name) {
+            return cache.clone();
+        }
+
+        self.caches
+            .write()
+            .expect("缓存锁已损坏")
+            .entry(name.to_string())
+            .or_insert_with(|| {
+                Arc::new(InMemoryCache::new(
+                    name,
+                    self.config.ttl(name),
+                    self.config.max_entries(name),
+                ))
+            })
+            .clone()
+    }
+
+    fn cache_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .caches
+            .read()
+            .expect("缓存锁已损坏")
+            .keys()
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+/// 内存缓存条目
+struct CacheEntry {
+    /// 缓存值
+    value: Value,
+    /// 写入时的单调时间
+    inserted_at: Instant,
+}
+
+/// 内存缓存
+pub struct InMemoryCache {
+    /// 缓存名称
+    name: String,
+    /// 过期时间
+    ttl: Option<Duration>,
+    /// 最大条目数
+    max_entries: Option<usize>,
+    /// 缓存条目
+    entries: RwLock<HashMap<String, CacheEntry>>,
+}
+
+impl InMemoryCache {
+    /// 创建内存缓存
+    pub fn new(name: impl Into<String>, ttl: Option<Duration>, max_entries: Option<usize>) -> Self {
+        Self {
+            name: name.into(),
+            ttl,
+            max_entries,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// 条目是否已过期，按全局时钟的单调时间计算写入后经过的时间
+    fn is_expired(&self, entry: &CacheEntry) -> bool {
+        self.ttl
+            .is_some_and(|ttl| crate::clock::monotonic().saturating_duration_since(entry.inserted_at) >= ttl)
+    }
+}
+
+#[async_trait]
+impl Cache for InMemoryCache {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn get(&self, key: &str) -> Option<Value> {
+        let entries = self.entries.read().expect("缓存锁已损坏");
+        entries
+            .get(key)
+            .filter(|entry| !self.is_expired(entry))
+            .map(|entry| entry.value.clone())
+    }
+
+    async fn put(&self, key: &str, value: Value) {
+        let mut entries = self.entries.write().expect("缓存锁已损坏");
+        if let Some(max_entries) = self.max_entries {
+            if !entries.contains_key(key) && entries.len() >= max_entries {
+                entries.retain(|_, entry| !self.is_expired(entry));
+            }
+            while !entries.contains_key(key) && entries.len() >= max_entries.max(1) {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.inserted_at)
+                    .map(|(key, _)| key.clone());
+                match oldest {
+                    Some(oldest) => entries.remove(&oldest),
+                    None => break,
+                };
+            }
+        }
+
+        entries.insert(
+            key.to_string(),
+            CacheEntry {
+                value,
+                inserted_at: crate::clock::monotonic(),
+            },
+        );
+    }
+
+    async fn evict(&self, key: &str) {
+        self.entries.write().expect("缓存锁已损坏").remove(key);
+    }
+
+    async fn clear(&self) {
+        self.entries.write().expect("缓存锁已损坏").clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试过期时间和容量上限
+    #[tokio::test]
+    async fn test_in_memory_cache() {
+        let config: CacheConfig = serde_json::from_str(
+            r#"{ "default_ttl": "1h", "caches": { "short": { "ttl": "50ms" }, "small": { "max_entries": 2 } } }"#,
+        )
+        .unwrap();
+        let manager = InMemoryCacheManager::new(config);
+
+        let short = manager.cache("short");
+        put_value(&*short, "a", &1).await;
+        assert_eq!(get_value::<i32>(&*short, "a").await, Some(1));
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(get_value::<i32>(&*short, "a").await, None);
+
+        let small = manager.cache("small");
+        for key in ["a", "b", "c"] {
+            put_value(&*small, key, key).await;
+        }
+        assert_eq!(get_value::<String>(&*small, "a").await, None);
+        assert_eq!(get_value::<String>(&*small, "c").await.as_deref(), Some("c"));
+
+        small.evict("c").await;
+        assert!(small.get("c").await.is_none());
+        assert_eq!(manager.cache_names(), vec!["short", "small"]);
+    }
+}
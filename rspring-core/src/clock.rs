@@ -0,0 +1,172 @@
+//! 时钟模块
+//!
+//! 框架中的时间戳统一从 [`Clock`] 获取，测试中可以替换为可控的时钟：
+//! - 默认使用系统时钟 [`SystemClock`]
+//! - `ApiResponse` 的时间戳、实体审计时间、审计事件时间和任务调度都通过 [`now`] 读取时间
+//! - 缓存过期等计算经过时长的逻辑通过 [`monotonic`] 读取单调时间，系统时间回拨不影响结果
+//! - 应用启动时把全局时钟以 [`ApplicationClock`] 注册到容器，业务组件注入后获取当前时间；
+//!   启动前已注册的 [`ApplicationClock`] 会替换全局时钟
+//!
+//! 超时、重试间隔等基于 Tokio 计时器的等待不受时钟影响，测试中可使用 `tokio::time::pause`
+
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+
+use crate::container::Component;
+
+/// 时钟特征
+///
+/// # 示例
+/// ```rust
+/// struct FixedClock(DateTime<Utc>);
+///
+/// impl Clock for FixedClock {
+///     fn now(&self) -> DateTime<Utc> {
+///         self.0
+///     }
+/// }
+///
+/// set_clock(Arc::new(FixedClock(Utc::now())));
+/// ```
+pub trait Clock: Send + Sync {
+    /// 获取当前时间
+    fn now(&self) -> DateTime<Utc>;
+
+    /// 获取单调时间，用于计算经过的时长，不受系统时间调整影响
+    fn monotonic(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// 系统时钟
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// 注册到容器中的应用时钟
+///
+/// # 示例
+/// ```rust
+/// #[derive(Component)]
+/// pub struct OrderService {
+///     #[component(inject)]
+///     clock: Arc<ApplicationClock>,
+/// }
+///
+/// impl OrderService {
+///     pub fn is_expired(&self, order: &Order) -> bool {
+///         order.expires_at <= self.clock.now()
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ApplicationClock {
+    /// 实际的时钟
+    clock: Arc<dyn Clock>,
+}
+
+impl ApplicationClock {
+    /// 使用指定的时钟创建应用时钟
+    pub fn new(clock: Arc<dyn Clock>) -> Self {
+        Self { clock }
+    }
+
+    /// 获取实际的时钟
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        self.clock.clone()
+    }
+}
+
+impl Default for ApplicationClock {
+    fn default() -> Self {
+        Self::new(Arc::new(SystemClock))
+    }
+}
+
+impl Clock for ApplicationClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
+    fn monotonic(&self) -> Instant {
+        self.clock.monotonic()
+    }
+}
+
+impl Component for ApplicationClock {
+    fn component_name(&self) -> &'static str {
+        "ApplicationClock"
+    }
+}
+
+impl std::fmt::Debug for ApplicationClock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApplicationClock")
+            .field("now", &self.now())
+            .finish()
+    }
+}
+
+/// 全局时钟
+static CLOCK: Lazy<RwLock<Arc<dyn Clock>>> = Lazy::new(|| RwLock::new(Arc::new(SystemClock)));
+
+/// 设置全局时钟
+///
+/// 在作用域中调用时的行为见[全局组件作用域](crate::scope)
+pub fn set_clock(clock: Arc<dyn Clock>) {
+    if let Some(clock) = crate::scope::set_scoped(clock) {
+        *CLOCK.write().expect("全局时钟锁已损坏") = clock;
+    }
+}
+
+/// 获取全局时钟
+pub fn clock() -> Arc<dyn Clock> {
+    crate::scope::scoped().unwrap_or_else(|| CLOCK.read().expect("全局时钟锁已损坏").clone())
+}
+
+/// 获取全局时钟的当前时间
+pub fn now() -> DateTime<Utc> {
+    clock().now()
+}
+
+/// 获取全局时钟的单调时间
+pub fn monotonic() -> Instant {
+    clock().monotonic()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scope::GlobalScope;
+
+    struct FixedClock(DateTime<Utc>);
+
+    impl Clock for FixedClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.0
+        }
+    }
+
+    /// 测试在作用域中替换时钟
+    #[tokio::test]
+    async fn test_clock() {
+        let fixed = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let scope = Arc::new(GlobalScope::new());
+        scope
+            .run(async {
+                set_clock(Arc::new(FixedClock(fixed)));
+                assert_eq!(now(), fixed);
+                assert_eq!(ApplicationClock::new(clock()).now(), fixed);
+            })
+            .await;
+        assert!(now() > fixed);
+    }
+}
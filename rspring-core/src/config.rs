@@ -2,12 +2,13 @@
 //! 
 //! 提供统一的配置读取和管理功能，支持多种格式和验证机制
 
+pub mod duration;
 pub mod manager;
 pub mod properties;
 pub mod validation;
 
 // 重新导出常用类型
-pub use manager::ConfigurationManager;
+pub use manager::{ConfigProperty, ConfigurationManager};
 pub use properties::*;
 pub use validation::ConfigValidator;
 
@@ -0,0 +1,192 @@
+//! 时长配置模块
+//!
+//! 支持在配置文件中以 `"500ms"`、`"30s"`、`"5m"`、`"1h"`、`"7d"` 形式书写时长，
+//! 纯数字按秒处理
+
+use std::time::Duration;
+
+use serde::{Deserialize, Deserializer, Serializer};
+
+use crate::error::{Error, Result};
+
+/// 解析时长字符串
+///
+/// # 参数
+/// * `value` - 时长字符串，如 `"1h"`、`"30s"`、`"250ms"`
+///
+/// # 示例
+/// ```rust
+/// assert_eq!(parse_duration("1h")?, Duration::from_secs(3600));
+/// assert_eq!(parse_duration("250ms")?, Duration::from_millis(250));
+/// ```
+pub fn parse_duration(value: &str) -> Result<Duration> {
+    let value = value.trim();
+    let split = value
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+
+    let number: u64 = number
+        .parse()
+        .map_err(|_| Error::validation(format!("无效的时长: {}", value)))?;
+
+    let secs_per_unit = match unit.trim() {
+        "ms" => return Ok(Duration::from_millis(number)),
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 60 * 60 * 24,
+        other => {
+            return Err(Error::validation(format!(
+                "无效的时长单位: {}，支持 ms/s/m/h/d",
+                other
+            )))
+        }
+    };
+
+    number
+        .checked_mul(secs_per_unit)
+        .map(Duration::from_secs)
+        .ok_or_else(|| Error::validation(format!("时长超出范围: {}", value)))
+}
+
+/// 将时长格式化为配置文件中的字符串形式
+///
+/// 使用能整除的最大单位，如 3600 秒格式化为 `"1h"`
+pub fn format_duration(duration: Duration) -> String {
+    let millis = duration.as_millis();
+    if millis % 1000 != 0 {
+        return format!("{}ms", millis);
+    }
+
+    let secs = duration.as_secs();
+    match secs {
+        0 => "0s".to_string(),
+        _ if secs % 86400 == 0 => format!("{}d", secs / 86400),
+        _ if secs % 3600 == 0 => format!("{}h", secs / 3600),
+        _ if secs % 60 == 0 => format!("{}m", secs / 60),
+        _ => format!("{}s", secs),
+    }
+}
+
+/// 配置文件中的时长值，可以是字符串或整数秒
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DurationValue {
+    Text(String),
+    Secs(u64),
+}
+
+impl DurationValue {
+    fn into_duration<E: serde::de::Error>(self) -> std::result::Result<Duration, E> {
+        match self {
+            Self::Text(text) => parse_duration(&text).map_err(E::custom),
+            Self::Secs(secs) => Ok(Duration::from_secs(secs)),
+        }
+    }
+}
+
+/// 时长字段的序列化函数
+///
+/// # 示例
+/// ```rust
+/// #[derive(Deserialize, Serialize)]
+/// pub struct TimeoutConfig {
+///     #[serde(with = "rspring_core::config::duration")]
+///     pub request: Duration,
+/// }
+/// ```
+pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format_duration(*duration))
+}
+
+/// 时长字段的反序列化函数
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Duration, D::Error> {
+    DurationValue::deserialize(deserializer)?.into_duration()
+}
+
+/// 可选时长字段的序列化函数
+///
+/// # 示例
+/// ```rust
+/// #[serde(default, with = "rspring_core::config::duration::option")]
+/// pub cache_max_age: Option<Duration>,
+/// ```
+pub mod option {
+    use super::*;
+
+    /// 序列化可选时长
+    pub fn serialize<S: Serializer>(
+        duration: &Option<Duration>,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        match duration {
+            Some(duration) => serializer.serialize_some(&format_duration(*duration)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// 反序列化可选时长
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Option<Duration>, D::Error> {
+        Option::<DurationValue>::deserialize(deserializer)?
+            .map(DurationValue::into_duration)
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+
+    /// 测试时长解析
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("250ms").unwrap(), Duration::from_millis(250));
+        assert_eq!(parse_duration("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_duration("30").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_duration("5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse_duration("1h").unwrap(), Duration::from_secs(3600));
+        assert_eq!(parse_duration("7d").unwrap(), Duration::from_secs(604800));
+
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("10y").is_err());
+        assert!(parse_duration("18446744073709551615d").is_err());
+    }
+
+    /// 测试时长格式化
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_millis(1500)), "1500ms");
+        assert_eq!(format_duration(Duration::from_secs(90)), "90s");
+        assert_eq!(format_duration(Duration::from_secs(3600)), "1h");
+        assert_eq!(format_duration(Duration::from_secs(0)), "0s");
+    }
+
+    /// 测试作为配置字段反序列化
+    #[test]
+    fn test_duration_field() {
+        #[derive(Deserialize, Serialize)]
+        struct Timeouts {
+            #[serde(with = "super")]
+            request: Duration,
+            #[serde(default, with = "super::option")]
+            idle: Option<Duration>,
+        }
+
+        let timeouts: Timeouts = serde_json::from_str(r#"{ "request": "1m", "idle": 30 }"#).unwrap();
+        assert_eq!(timeouts.request, Duration::from_secs(60));
+        assert_eq!(timeouts.idle, Some(Duration::from_secs(30)));
+
+        let timeouts: Timeouts = serde_json::from_str(r#"{ "request": "2s" }"#).unwrap();
+        assert!(timeouts.idle.is_none());
+        assert_eq!(serde_json::to_string(&timeouts).unwrap(), r#"{"request":"2s","idle":null}"#);
+
+        let error = serde_json::from_str::<Timeouts>(r#"{ "request": "18446744073709551615m" }"#)
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("时长超出范围"));
+    }
+}
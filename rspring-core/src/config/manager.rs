@@ -4,9 +4,27 @@
 //! 以及环境变量覆盖机制
 
 use crate::error::{Error, Result};
-use config::{Config, ConfigError, Environment, File};
+use config::{Config, ConfigError, Environment, File, Value, ValueKind};
 use serde::de::DeserializeOwned;
+use serde::Serialize;
 use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// config 库为环境变量中的配置值记录的来源
+const ENV_SOURCE_ORIGIN: &str = "the environment";
+
+/// 配置属性
+/// 
+/// 最终生效的配置值及其来源
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigProperty {
+    /// 配置键，如 `"server.port"`
+    pub key: String,
+    /// 配置值，数组作为一个整体
+    pub value: serde_json::Value,
+    /// 来源，配置文件路径或 `env:` 加环境变量名，如 `"env:RSPRING_SERVER_PORT"`
+    pub origin: Option<String>,
+}
 
 /// 配置管理器
 /// 
@@ -17,8 +35,12 @@ pub struct ConfigurationManager {
     config: Config,
     /// 配置文件路径列表
     config_paths: Vec<String>,
+    /// 配置文件所在目录
+    config_dirs: Vec<PathBuf>,
     /// 环境变量前缀
     env_prefix: String,
+    /// 当前激活的环境
+    profile: String,
 }
 
 impl ConfigurationManager {
@@ -49,33 +71,43 @@ impl ConfigurationManager {
         let profile = std::env::var("PROFILE")
             .unwrap_or_else(|_| "dev".to_string());
         
+        Self::from_dirs(&[PathBuf::new()], &profile, env_prefix)
+    }
+    
+    /// 从指定目录加载配置，不读取 `PROFILE` 环境变量
+    /// 
+    /// 先加载所有目录中的基础配置，再加载所有目录中的环境配置，
+    /// 同一层中后面目录的配置覆盖前面目录的配置，环境变量仍然覆盖所有配置文件
+    /// 
+    /// # 参数
+    /// * `dirs` - 配置文件所在目录，空路径表示当前目录
+    /// * `profile` - 激活的环境
+    /// * `env_prefix` - 环境变量前缀
+    /// 
+    /// # 示例
+    /// ```rust
+    /// // 加载 config/application.toml、config/application-staging.toml 和 RSPRING_* 环境变量
+    /// let config = ConfigurationManager::from_dirs(&["config".into()], "staging", "RSPRING")?;
+    /// ```
+    pub fn from_dirs(dirs: &[PathBuf], profile: &str, env_prefix: &str) -> Result<Self> {
         let mut config_builder = Config::builder();
         let mut config_paths = Vec::new();
         
-        // 尝试加载基础配置文件 (TOML, YAML, JSON)
-        let base_names = ["application"];
-        let profile_names = [format!("application-{}", profile)];
+        // 尝试加载基础配置文件 (TOML, YAML, JSON)，再加载环境特定配置
+        let names = ["application".to_string(), format!("application-{}", profile)];
         let extensions = ["toml", "yaml", "yml", "json"];
         
-        // 加载基础配置
-        for name in &base_names {
-            for ext in &extensions {
-                let config_file = format!("{}.{}", name, ext);
-                config_builder = config_builder.add_source(
-                    File::with_name(&config_file).required(false)
-                );
-                config_paths.push(config_file);
-            }
-        }
-        
-        // 加载环境特定配置
-        for name in &profile_names {
-            for ext in &extensions {
-                let config_file = format!("{}.{}", name, ext);
-                config_builder = config_builder.add_source(
-                    File::with_name(&config_file).required(false)
-                );
-                config_paths.push(config_file);
+        for name in &names {
+            for dir in dirs {
+                for ext in &extensions {
+                    let config_file = dir.join(format!("{}.{}", name, ext))
+                        .to_string_lossy()
+                        .into_owned();
+                    config_builder = config_builder.add_source(
+                        File::with_name(&config_file).required(false)
+                    );
+                    config_paths.push(config_file);
+                }
             }
         }
         
@@ -90,7 +122,9 @@ impl ConfigurationManager {
         Ok(Self {
             config,
             config_paths,
+            config_dirs: dirs.to_vec(),
             env_prefix: env_prefix.to_string(),
+            profile: profile.to_string(),
         })
     }
     
@@ -121,6 +155,49 @@ impl ConfigurationManager {
         self.config.get(key)
             .map_err(Error::Configuration)
     }
+
+    /// 解析 `#[Value]` 形式的配置表达式
+    /// 
+    /// - `${key}` - 读取配置值，不存在时只有 `Option` 类型得到 `None`，其他类型返回错误
+    /// - `${key:default}` - 配置不存在时使用默认值
+    /// - 其他文本作为字面值
+    /// 
+    /// 默认值和字面值与环境变量一样按目标类型转换，如 `"8080"` 转换为 `u16`
+    /// 
+    /// # 示例
+    /// ```rust
+    /// let port: u16 = config.resolve("${server.port:8080}")?;
+    /// let api_key: Option<String> = config.resolve("${payment.api_key}")?;
+    /// ```
+    /// 
+    /// # 错误
+    /// 表达式格式错误、配置不存在且没有默认值或值无法转换为目标类型时返回错误
+    pub fn resolve<T: DeserializeOwned>(&self, expression: &str) -> Result<T> {
+        let Some(placeholder) = expression.strip_prefix("${") else {
+            return Value::new(None, expression)
+                .try_deserialize()
+                .map_err(|e| Error::validation(format!("配置值 {} 无法转换: {}", expression, e)));
+        };
+        let placeholder = placeholder
+            .strip_suffix('}')
+            .ok_or_else(|| Error::validation(format!("配置表达式缺少右括号: {}", expression)))?;
+        let (key, default) = match placeholder.split_once(':') {
+            Some((key, default)) => (key.trim(), Some(default)),
+            None => (placeholder.trim(), None),
+        };
+
+        if self.contains_key(key) {
+            return self.get(key);
+        }
+        let value = match default {
+            Some(default) => Value::new(None, default),
+            None => Value::new(None, ValueKind::Nil),
+        };
+        value.try_deserialize().map_err(|e| match default {
+            Some(default) => Error::validation(format!("配置项 {} 的默认值 {} 无法转换: {}", key, default, e)),
+            None => Error::validation(format!("缺少配置项: {}", key)),
+        })
+    }
     
     /// 获取配置章节
     /// 
@@ -187,6 +264,52 @@ impl ConfigurationManager {
             .collect()
     }
     
+    /// 获取所有生效的配置属性及其来源，按配置键排序
+    /// 
+    /// # 示例
+    /// ```rust
+    /// for property in config.properties() {
+    ///     println!("{} = {} ({:?})", property.key, property.value, property.origin);
+    /// }
+    /// ```
+    pub fn properties(&self) -> Vec<ConfigProperty> {
+        let mut properties = Vec::new();
+        self.collect_properties("", &self.config.cache, &mut properties);
+        properties.sort_by(|a, b| a.key.cmp(&b.key));
+        properties
+    }
+    
+    /// 从配置值中递归提取配置属性
+    fn collect_properties(&self, prefix: &str, value: &Value, properties: &mut Vec<ConfigProperty>) {
+        if let ValueKind::Table(table) = &value.kind {
+            for (k, v) in table {
+                let full_key = if prefix.is_empty() {
+                    k.clone()
+                } else {
+                    format!("{}.{}", prefix, k)
+                };
+                self.collect_properties(&full_key, v, properties);
+            }
+            return;
+        }
+        if prefix.is_empty() {
+            return;
+        }
+        
+        let origin = value.origin().map(|origin| {
+            if origin == ENV_SOURCE_ORIGIN {
+                format!("env:{}_{}", self.env_prefix, prefix.replace('.', "_").to_uppercase())
+            } else {
+                origin.to_string()
+            }
+        });
+        properties.push(ConfigProperty {
+            key: prefix.to_string(),
+            value: value.clone().try_deserialize().unwrap_or_default(),
+            origin,
+        });
+    }
+    
     /// 从配置值中递归提取键名
     fn get_keys_from_value(&self, prefix: &str, value: &serde_json::Value) -> Vec<String> {
         let mut keys = Vec::new();
@@ -228,11 +351,18 @@ impl ConfigurationManager {
         &self.env_prefix
     }
     
+    /// 获取当前激活的环境
+    /// 
+    /// 由 `PROFILE` 环境变量或 [`from_dirs`](Self::from_dirs) 的参数指定，默认为 `"dev"`
+    pub fn profile(&self) -> &str {
+        &self.profile
+    }
+    
     /// 重新加载配置
     /// 
-    /// 重新读取配置文件和环境变量
+    /// 重新读取配置目录中的配置文件和环境变量，激活的环境不变
     pub fn reload(&mut self) -> Result<()> {
-        *self = Self::with_prefix(&self.env_prefix.clone())?;
+        *self = Self::from_dirs(&self.config_dirs, &self.profile, &self.env_prefix)?;
         Ok(())
     }
     
@@ -270,7 +400,12 @@ mod tests {
     use super::*;
     use serde::Deserialize;
     use std::fs;
-    use tempfile::tempdir;
+    use tempfile::{tempdir, TempDir};
+    
+    /// 从临时目录加载配置，不切换当前目录，可以与其他测试并行执行
+    fn load(dir: &TempDir, env_prefix: &str) -> ConfigurationManager {
+        ConfigurationManager::from_dirs(&[dir.path().to_path_buf()], "dev", env_prefix).unwrap()
+    }
     
     #[derive(Debug, Deserialize, PartialEq)]
     struct TestConfig {
@@ -310,10 +445,7 @@ port = 8080
         
         fs::write(&config_path, config_content).unwrap();
         
-        // 切换到临时目录
-        let _guard = std::env::set_current_dir(&dir).unwrap();
-        
-        let config = ConfigurationManager::new().unwrap();
+        let config = load(&dir, "RSPRING");
         
         let app_name: String = config.get("app.name").unwrap();
         assert_eq!(app_name, "Test App");
@@ -335,9 +467,7 @@ port = 3000
 "#;
         
         fs::write(&config_path, config_content).unwrap();
-        let _guard = std::env::set_current_dir(&dir).unwrap();
-        
-        let config = ConfigurationManager::new().unwrap();
+        let config = load(&dir, "RSPRING");
         let server_config: ServerSection = config.get_section("server").unwrap();
         
         assert_eq!(server_config.host, "localhost");
@@ -347,8 +477,8 @@ port = 3000
     /// 测试环境变量覆盖
     #[test]
     fn test_environment_override() {
-        // 设置环境变量
-        std::env::set_var("RSPRING_SERVER_PORT", "9000");
+        // 设置环境变量，使用本测试专用的前缀，不影响并行执行的其他测试
+        std::env::set_var("RSPRING_ENV_TEST_SERVER_PORT", "9000");
         
         let dir = tempdir().unwrap();
         let config_path = dir.path().join("application.toml");
@@ -360,16 +490,14 @@ port = 3000
 "#;
         
         fs::write(&config_path, config_content).unwrap();
-        let _guard = std::env::set_current_dir(&dir).unwrap();
-        
-        let config = ConfigurationManager::new().unwrap();
+        let config = load(&dir, "RSPRING_ENV_TEST");
         let port: u16 = config.get("server.port").unwrap();
         
         // 环境变量应该覆盖配置文件
         assert_eq!(port, 9000);
         
         // 清理环境变量
-        std::env::remove_var("RSPRING_SERVER_PORT");
+        std::env::remove_var("RSPRING_ENV_TEST_SERVER_PORT");
     }
 
     /// 测试配置键获取
@@ -391,9 +519,7 @@ url = "mysql://localhost"
 "#;
         
         fs::write(&config_path, config_content).unwrap();
-        let _guard = std::env::set_current_dir(&dir).unwrap();
-        
-        let config = ConfigurationManager::new().unwrap();
+        let config = load(&dir, "RSPRING");
         let keys = config.keys();
         
         // 应该包含所有配置键
@@ -419,9 +545,7 @@ pool_size = 10
 "#;
         
         fs::write(&config_path, config_content).unwrap();
-        let _guard = std::env::set_current_dir(&dir).unwrap();
-        
-        let config = ConfigurationManager::new().unwrap();
+        let config = load(&dir, "RSPRING");
         let server_keys = config.keys_with_prefix("server");
         
         assert!(server_keys.iter().any(|k| k.starts_with("server")));
@@ -439,12 +563,28 @@ port = 3000
 "#;
         
         fs::write(&config_path, config_content).unwrap();
-        let _guard = std::env::set_current_dir(&dir).unwrap();
-        
-        let config = ConfigurationManager::new().unwrap();
+        let config = load(&dir, "RSPRING");
         
         assert!(config.contains_key("server.port"));
         assert!(!config.contains_key("server.host"));
         assert!(!config.contains_key("nonexistent"));
     }
+
+    /// 测试解析配置表达式，默认值按目标类型转换
+    #[test]
+    fn test_resolve() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("application.toml"), "[server]\nport = 3000\n").unwrap();
+        let config = load(&dir, "RSPRING");
+
+        assert_eq!(config.resolve::<u16>("${server.port:8080}").unwrap(), 3000);
+        assert_eq!(config.resolve::<u16>("${server.workers:4}").unwrap(), 4);
+        assert!(config.resolve::<bool>("${app.debug:true}").unwrap());
+        assert_eq!(config.resolve::<String>("${app.name:}").unwrap(), "");
+        assert_eq!(config.resolve::<Option<String>>("${app.name}").unwrap(), None);
+        assert_eq!(config.resolve::<u32>("42").unwrap(), 42);
+        assert!(config.resolve::<String>("${app.name}").is_err());
+        assert!(config.resolve::<u16>("${server.workers:many}").is_err());
+        assert!(config.resolve::<u16>("${server.port").is_err());
+    }
 }
\ No newline at end of file
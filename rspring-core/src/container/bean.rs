@@ -0,0 +1,277 @@
+//! Bean 工厂方法模块
+//!
+//! 支持 Java 配置风格的组件定义：`#[BeanConfiguration]` 标注的 impl 块中，
+//! `#[Bean]` 方法的返回值作为单例组件注册到容器：
+//! - 方法参数为 `Arc<T>` 时注入容器中的单例组件，并作为 Bean 的依赖写入依赖图
+//! - 方法可以返回 `T` 或 `Result<T>`，返回错误时自动装配失败
+//! - 容器在自动装配前按依赖顺序调用 Bean 方法，依赖其他 Bean 的方法在其之后调用
+//! - 每个 Bean 方法的耗时以 Bean 名称记录到启动报告的组件中
+
+use std::any::TypeId;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::container::Container;
+use crate::error::{Error, Result};
+use crate::startup::startup_recorder;
+
+/// Bean 方法的依赖
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BeanDependency {
+    /// 依赖组件的类型 ID
+    pub type_id: TypeId,
+    /// 依赖组件的类型名称
+    pub type_name: &'static str,
+}
+
+impl BeanDependency {
+    /// 创建对类型 `T` 的依赖
+    pub fn of<T: 'static>() -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+        }
+    }
+}
+
+/// 创建 Bean 并注册到容器的操作
+type BeanFactory = Box<dyn FnOnce(&mut Container) -> Result<()> + Send + Sync>;
+
+/// Bean 定义
+///
+/// 通常由 `#[BeanConfiguration]` 生成，也可以手动创建
+///
+/// # 示例
+/// ```rust
+/// let definition = BeanDefinition::new(
+///     "mailer",
+///     vec![BeanDependency::of::<SmtpConfig>()],
+///     |container| Ok(Mailer::new(container.require::<SmtpConfig>()?)),
+/// );
+/// ```
+pub struct BeanDefinition {
+    /// Bean 名称，默认为方法名
+    name: String,
+    /// Bean 的类型 ID
+    type_id: TypeId,
+    /// Bean 的类型名称
+    type_name: &'static str,
+    /// 依赖的组件
+    dependencies: Vec<BeanDependency>,
+    /// 创建并注册 Bean 的操作
+    factory: BeanFactory,
+}
+
+impl BeanDefinition {
+    /// 创建 Bean 定义，`factory` 的返回值以 `name` 注册为单例组件
+    pub fn new<T, F>(name: impl Into<String>, dependencies: Vec<BeanDependency>, factory: F) -> Self
+    where
+        T: 'static + Send + Sync,
+        F: FnOnce(&Container) -> Result<T> + Send + Sync + 'static,
+    {
+        let name = name.into();
+        let bean_name = name.clone();
+        let dependency_ids = dependencies.iter().map(|dependency| dependency.type_id).collect();
+        Self {
+            name,
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            dependencies,
+            factory: Box::new(move |container: &mut Container| {
+                let bean = factory(container)?;
+                container.injector_mut().register_singleton_arc_with_dependencies(
+                    Arc::new(bean),
+                    Some(bean_name),
+                    dependency_ids,
+                )
+            }),
+        }
+    }
+
+    /// Bean 名称
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Bean 的类型 ID
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Bean 的类型名称
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// 依赖的组件
+    pub fn dependencies(&self) -> &[BeanDependency] {
+        &self.dependencies
+    }
+
+    /// 调用工厂方法创建 Bean 并注册到容器，耗时记录到启动报告
+    pub(crate) fn create(self, container: &mut Container) -> Result<()> {
+        let start = Instant::now();
+        (self.factory)(container).map_err(|e| {
+            Error::dependency_injection(format!("创建 Bean {} 失败: {}", self.name, e))
+        })?;
+        startup_recorder().component(self.name, start.elapsed());
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for BeanDefinition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BeanDefinition")
+            .field("name", &self.name)
+            .field("type_name", &self.type_name)
+            .field("dependencies", &self.dependencies)
+            .finish()
+    }
+}
+
+/// 配置类特征
+///
+/// `#[BeanConfiguration]` 为 impl 块的类型实现，返回其中 `#[Bean]` 方法的定义
+///
+/// # 示例
+/// ```rust
+/// pub struct MailConfiguration;
+///
+/// #[BeanConfiguration]
+/// impl MailConfiguration {
+///     #[Bean]
+///     fn smtp_client(&self, settings: Arc<MailSettings>) -> Result<SmtpClient> {
+///         SmtpClient::connect(&settings.host)
+///     }
+///
+///     #[Bean(name = "mailer")]
+///     fn mailer(&self, client: Arc<SmtpClient>) -> Mailer {
+///         Mailer::new(client)
+///     }
+/// }
+///
+/// container.register_configuration(MailConfiguration);
+/// container.auto_wire()?;
+/// let mailer = container.get_singleton::<Mailer>().unwrap();
+/// ```
+pub trait BeanConfiguration: Send + Sync + 'static {
+    /// 获取 Bean 定义
+    fn bean_definitions(self: Arc<Self>) -> Vec<BeanDefinition>;
+}
+
+/// 按依赖顺序创建 Bean，每轮创建依赖已全部注册为单例的 Bean
+///
+/// Bean 方法通过 [`Container::require`] 注入依赖，只能获取单例组件
+///
+/// # 错误
+/// Bean 方法返回错误，或依赖的单例组件未注册、Bean 之间存在循环依赖时返回错误
+pub(crate) fn create_beans(container: &mut Container, mut pending: Vec<BeanDefinition>) -> Result<()> {
+    let registered = |container: &Container, dependency: &BeanDependency| {
+        container.injector().registry().contains_singleton_type_id(&dependency.type_id)
+    };
+    while !pending.is_empty() {
+        let ready = pending.iter().position(|definition| {
+            definition
+                .dependencies
+                .iter()
+                .all(|dependency| registered(container, dependency))
+        });
+        let Some(index) = ready else {
+            let unresolved = pending
+                .iter()
+                .map(|definition| {
+                    let missing = definition
+                        .dependencies
+                        .iter()
+                        .filter(|dependency| !registered(container, dependency))
+                        .map(|dependency| dependency.type_name)
+                        .collect::<Vec<_>>();
+                    format!("{}（缺少 {}）", definition.name, missing.join("、"))
+                })
+                .collect::<Vec<_>>();
+            return Err(Error::dependency_injection(format!(
+                "无法创建 Bean，依赖的单例组件未注册或存在循环依赖: {}",
+                unresolved.join("，")
+            )));
+        };
+        pending.remove(index).create(container)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Settings {
+        host: String,
+    }
+
+    struct Client {
+        settings: Arc<Settings>,
+    }
+
+    struct Beans;
+
+    impl Beans {
+        fn client(&self, settings: Arc<Settings>) -> Client {
+            Client { settings }
+        }
+
+        fn settings(&self) -> Result<Settings> {
+            Ok(Settings { host: "smtp.example.com".to_string() })
+        }
+    }
+
+    /// 与 `#[BeanConfiguration]` 生成的实现相同
+    impl BeanConfiguration for Beans {
+        fn bean_definitions(self: Arc<Self>) -> Vec<BeanDefinition> {
+            let this = self.clone();
+            let client = BeanDefinition::new("client", vec![BeanDependency::of::<Settings>()], move |container| {
+                Ok(this.client(container.require::<Settings>()?))
+            });
+            let settings = BeanDefinition::new("settings", Vec::new(), move |_| self.settings());
+            vec![client, settings]
+        }
+    }
+
+    /// 测试按依赖顺序创建 Bean 并写入依赖图
+    #[test]
+    fn test_bean_configuration() {
+        let mut container = Container::new();
+        container.register_configuration(Beans);
+        container.auto_wire().unwrap();
+
+        let client = container.require::<Client>().unwrap();
+        assert_eq!(client.settings.host, "smtp.example.com");
+        assert!(Arc::ptr_eq(&client.settings, &container.require::<Settings>().unwrap()));
+        assert_eq!(container.stats().total_dependencies, 1);
+        let components = crate::startup::startup_report().components;
+        assert!(components.iter().any(|component| component.name == "settings"));
+
+        let mut container = Container::new();
+        container.register_beans(vec![BeanDefinition::new(
+            "client",
+            vec![BeanDependency::of::<Settings>()],
+            |container| Ok(Beans.client(container.require::<Settings>()?)),
+        )]);
+        let error = container.auto_wire().unwrap_err().to_string();
+        assert!(error.contains("client"), "{}", error);
+
+        // 非单例组件不能注入到 Bean 方法
+        let mut container = Container::new();
+        container
+            .injector_mut()
+            .registry_mut()
+            .register(Settings { host: "smtp.example.com".to_string() }, None)
+            .unwrap();
+        container.register_beans(vec![BeanDefinition::new(
+            "client",
+            vec![BeanDependency::of::<Settings>()],
+            |container| Ok(Beans.client(container.require::<Settings>()?)),
+        )]);
+        let error = container.auto_wire().unwrap_err().to_string();
+        assert!(error.contains("依赖的单例组件未注册"), "{}", error);
+        assert!(error.contains("client（缺少"), "{}", error);
+    }
+}
@@ -217,11 +217,21 @@ impl DependencyInjector {
         component: T,
         name: Option<String>,
         dependencies: Vec<TypeId>,
+    ) -> Result<()> {
+        self.register_singleton_arc_with_dependencies(std::sync::Arc::new(component), name, dependencies)
+    }
+    
+    /// 注册已共享的单例组件并添加到依赖图中
+    pub fn register_singleton_arc_with_dependencies<T: 'static + Send + Sync>(
+        &mut self,
+        component: std::sync::Arc<T>,
+        name: Option<String>,
+        dependencies: Vec<TypeId>,
     ) -> Result<()> {
         let type_id = TypeId::of::<T>();
         
         // 注册单例组件
-        self.registry.register_singleton(component, name)?;
+        self.registry.register_singleton_arc(component, name)?;
         
         // 添加依赖关系
         for dep_type_id in dependencies {
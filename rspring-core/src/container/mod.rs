@@ -5,15 +5,18 @@
 //! - 依赖自动注入
 //! - 生命周期管理
 //! - 循环依赖检测
+//! - `#[Bean]` 工厂方法
 
+pub mod bean;
 pub mod registry;
 pub mod injection;
 
 // 重新导出主要类型
-pub use registry::{ComponentRegistry, ComponentMetadata, ComponentLifecycle, RegistryStats};
+pub use registry::{ComponentRegistry, ComponentEntry, ComponentMetadata, ComponentDescriptor, ComponentLifecycle, RegistryStats};
 pub use injection::{DependencyInjector, InjectionStats};
+pub use bean::{BeanConfiguration, BeanDefinition, BeanDependency};
 
-use std::any::Any;
+use std::any::TypeId;
 
 /// 依赖注入容器
 /// 
@@ -21,6 +24,8 @@ use std::any::Any;
 pub struct Container {
     /// 依赖注入器
     injector: DependencyInjector,
+    /// 等待自动装配时创建的 Bean
+    pending_beans: Vec<BeanDefinition>,
 }
 
 impl Container {
@@ -28,12 +33,13 @@ impl Container {
     pub fn new() -> Self {
         Self {
             injector: DependencyInjector::new(),
+            pending_beans: Vec::new(),
         }
     }
     
-    /// 注册组件
+    /// 注册组件，同时按 [`Component::dependencies`] 记录依赖
     pub fn register<T: 'static + Send + Sync + Component>(&mut self, component: T) -> crate::Result<()> {
-        self.injector.registry_mut().register(component, None)
+        self.injector.register_with_dependencies(component, None, T::dependencies())
     }
     
     /// 注册带名称的组件
@@ -42,12 +48,12 @@ impl Container {
         component: T, 
         name: String
     ) -> crate::Result<()> {
-        self.injector.registry_mut().register(component, Some(name))
+        self.injector.register_with_dependencies(component, Some(name), T::dependencies())
     }
     
-    /// 注册单例组件
+    /// 注册单例组件，同时按 [`Component::dependencies`] 记录依赖
     pub fn register_singleton<T: 'static + Send + Sync + Component>(&mut self, component: T) -> crate::Result<()> {
-        self.injector.registry_mut().register_singleton(component, None)
+        self.injector.register_singleton_with_dependencies(component, None, T::dependencies())
     }
     
     /// 注册带名称的单例组件
@@ -56,7 +62,55 @@ impl Container {
         component: T, 
         name: String
     ) -> crate::Result<()> {
-        self.injector.registry_mut().register_singleton(component, Some(name))
+        self.injector.register_singleton_with_dependencies(component, Some(name), T::dependencies())
+    }
+    
+    /// 注册已共享的单例组件
+    pub fn register_singleton_arc<T: 'static + Send + Sync + Component>(
+        &mut self,
+        component: std::sync::Arc<T>,
+        name: Option<String>
+    ) -> crate::Result<()> {
+        self.injector.register_singleton_arc_with_dependencies(component, name, T::dependencies())
+    }
+    
+    /// 注册配置类，其中的 Bean 在自动装配时按依赖顺序创建并注册为单例组件
+    pub fn register_configuration<C: BeanConfiguration>(&mut self, configuration: C) {
+        self.register_beans(std::sync::Arc::new(configuration).bean_definitions());
+    }
+    
+    /// 注册 Bean 定义，在自动装配时创建
+    pub fn register_beans(&mut self, definitions: Vec<BeanDefinition>) {
+        self.pending_beans.extend(definitions);
+    }
+    
+    /// 取出等待创建的、返回类型 `T` 的 Bean 定义
+    ///
+    /// 用于临时替换组件，之后通过 [`register_beans`](Self::register_beans) 放回
+    pub fn take_beans<T: 'static>(&mut self) -> Vec<BeanDefinition> {
+        let type_id = TypeId::of::<T>();
+        let (taken, pending) = std::mem::take(&mut self.pending_beans)
+            .into_iter()
+            .partition(|definition| definition.type_id() == type_id);
+        self.pending_beans = pending;
+        taken
+    }
+    
+    /// 移除组件
+    pub fn remove<T: 'static>(&mut self) -> bool {
+        self.injector.registry_mut().remove::<T>()
+    }
+    
+    /// 设置是否允许覆盖已注册的组件
+    ///
+    /// 允许时注册同类型的组件会替换原组件，默认不允许
+    pub fn set_allow_override(&mut self, allow: bool) {
+        self.injector.registry_mut().set_allow_override(allow);
+    }
+    
+    /// 是否允许覆盖已注册的组件
+    pub fn allow_override(&self) -> bool {
+        self.injector.registry().allow_override()
     }
     
     /// 获取组件实例
@@ -69,13 +123,28 @@ impl Container {
         self.injector.get_singleton::<T>()
     }
     
+    /// 获取必须存在的单例组件实例
+    /// 
+    /// # 错误
+    /// 组件未以单例注册时返回错误
+    pub fn require<T: 'static>(&self) -> crate::Result<std::sync::Arc<T>> {
+        self.get_singleton::<T>().ok_or_else(|| {
+            crate::Error::dependency_injection(format!(
+                "单例组件未注册: {}",
+                std::any::type_name::<T>()
+            ))
+        })
+    }
+    
     /// 检查是否包含指定类型的组件
     pub fn contains<T: 'static>(&self) -> bool {
         self.injector.registry().contains::<T>()
     }
     
-    /// 执行自动装配
+    /// 执行自动装配，先按依赖顺序创建已注册配置类中的 Bean
     pub fn auto_wire(&mut self) -> crate::Result<()> {
+        let beans = std::mem::take(&mut self.pending_beans);
+        bean::create_beans(self, beans)?;
         self.injector.auto_wire()
     }
     
@@ -96,6 +165,11 @@ impl Container {
         }
     }
     
+    /// 获取所有组件的描述，按组件名称排序
+    pub fn describe(&self) -> Vec<ComponentDescriptor> {
+        self.injector.registry().describe()
+    }
+    
     /// 获取依赖注入器的引用（用于高级操作）
     pub fn injector(&self) -> &DependencyInjector {
         &self.injector
@@ -136,6 +210,18 @@ pub trait Component: Send + Sync {
     /// 
     /// 用于日志记录和调试
     fn component_name(&self) -> &'static str;
+    
+    /// 获取组件依赖的其他组件类型
+    /// 
+    /// `#[derive(Component)]`、`#[derive(Service)]` 和 `#[derive(Repository)]` 根据
+    /// `#[component(inject)]` 标注的 `Arc<T>` 字段生成，
+    /// 通过容器注册时写入依赖图，自动装配时按依赖顺序初始化并校验依赖已注册
+    fn dependencies() -> Vec<TypeId>
+    where
+        Self: Sized,
+    {
+        Vec::new()
+    }
 }
 
 /// 服务组件标记特征
@@ -228,4 +314,49 @@ mod tests {
         assert!(retrieved.is_some());
         assert_eq!(retrieved.unwrap().get_name(), "named");
     }
-}
\ No newline at end of file
+
+    /// 测试允许覆盖时替换已注册的组件，并可恢复原组件
+    #[test]
+    fn test_allow_override() {
+        let mut container = Container::new();
+        container.register_singleton(TestService::new("real".to_string())).unwrap();
+        assert!(container.register_singleton(TestService::new("fake".to_string())).is_err());
+
+        let original = container.get_singleton::<TestService>().unwrap();
+        container.set_allow_override(true);
+        container.register_singleton(TestService::new("fake".to_string())).unwrap();
+        assert_eq!(container.get_singleton::<TestService>().unwrap().get_name(), "fake");
+
+        container.register_singleton_arc(original.clone(), None).unwrap();
+        assert!(std::sync::Arc::ptr_eq(&container.get_singleton::<TestService>().unwrap(), &original));
+        assert_eq!(container.stats().total_components, 1);
+    }
+
+    /// 依赖 TestService 的组件，与 `#[derive(Service)]` 为 `#[component(inject)]` 字段生成的实现相同
+    struct ReportService {
+        _service: std::sync::Arc<TestService>,
+    }
+
+    impl Component for ReportService {
+        fn component_name(&self) -> &'static str {
+            "ReportService"
+        }
+
+        fn dependencies() -> Vec<TypeId> {
+            vec![TypeId::of::<TestService>()]
+        }
+    }
+
+    /// 测试注册时按组件声明的依赖写入依赖图
+    #[test]
+    fn test_component_dependencies() {
+        let service = std::sync::Arc::new(TestService::new("report".to_string()));
+        let mut container = Container::new();
+        container.register_singleton(ReportService { _service: service.clone() }).unwrap();
+        assert!(container.validate().is_err());
+
+        container.register_singleton_arc(service, None).unwrap();
+        container.auto_wire().unwrap();
+        assert_eq!(container.stats().total_dependencies, 1);
+    }
+}
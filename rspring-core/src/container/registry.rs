@@ -3,6 +3,7 @@
 //! 提供组件注册和管理功能，支持不同生命周期的组件管理
 
 use crate::error::{Error, Result};
+use serde::Serialize;
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -17,6 +18,16 @@ pub enum ComponentLifecycle {
     Prototype,
 }
 
+impl ComponentLifecycle {
+    /// 生命周期名称，如 `"singleton"`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Singleton => "singleton",
+            Self::Prototype => "prototype",
+        }
+    }
+}
+
 /// 组件元数据
 #[derive(Debug, Clone)]
 pub struct ComponentMetadata {
@@ -24,6 +35,8 @@ pub struct ComponentMetadata {
     pub name: String,
     /// 组件类型 ID
     pub type_id: TypeId,
+    /// 组件类型名称
+    pub type_name: &'static str,
     /// 生命周期类型
     pub lifecycle: ComponentLifecycle,
     /// 注册时间
@@ -45,6 +58,8 @@ pub struct ComponentRegistry {
     metadata: HashMap<TypeId, ComponentMetadata>,
     /// 组件依赖关系图
     dependencies: HashMap<TypeId, Vec<TypeId>>,
+    /// 是否允许覆盖已注册的组件
+    allow_override: bool,
 }
 
 impl ComponentRegistry {
@@ -57,9 +72,38 @@ impl ComponentRegistry {
             singletons: HashMap::new(),
             metadata: HashMap::new(),
             dependencies: HashMap::new(),
+            allow_override: false,
         }
     }
     
+    /// 设置是否允许覆盖已注册的组件
+    /// 
+    /// 允许时注册同类型的组件会替换原组件，保留其依赖关系，否则返回错误。
+    /// 用于测试中以模拟实现替换组件
+    pub fn set_allow_override(&mut self, allow: bool) {
+        self.allow_override = allow;
+    }
+    
+    /// 是否允许覆盖已注册的组件
+    pub fn allow_override(&self) -> bool {
+        self.allow_override
+    }
+    
+    /// 检查组件是否已注册，允许覆盖时移除原组件
+    fn check_duplicate(&mut self, type_id: TypeId, component_name: &str) -> Result<()> {
+        if !self.contains_type_id(&type_id) {
+            return Ok(());
+        }
+        if !self.allow_override {
+            return Err(Error::container(format!("组件 {} 已经注册", component_name)));
+        }
+        
+        warn!("覆盖已注册的组件: {}", component_name);
+        self.components.remove(&type_id);
+        self.singletons.remove(&type_id);
+        Ok(())
+    }
+    
     /// 注册普通组件
     /// 
     /// # 参数
@@ -84,9 +128,7 @@ impl ComponentRegistry {
         debug!("注册组件: {} (类型: {})", component_name, std::any::type_name::<T>());
         
         // 检查是否已注册
-        if self.components.contains_key(&type_id) || self.singletons.contains_key(&type_id) {
-            return Err(Error::container(format!("组件 {} 已经注册", component_name)));
-        }
+        self.check_duplicate(type_id, &component_name)?;
         
         // 存储组件
         self.components.insert(type_id, Box::new(component));
@@ -95,6 +137,7 @@ impl ComponentRegistry {
         let metadata = ComponentMetadata {
             name: component_name.clone(),
             type_id,
+            type_name: std::any::type_name::<T>(),
             lifecycle: ComponentLifecycle::Prototype,
             registered_at: chrono::Utc::now(),
             description: None,
@@ -120,6 +163,18 @@ impl ComponentRegistry {
         &mut self, 
         component: T,
         name: Option<String>
+    ) -> Result<()> {
+        self.register_singleton_arc(Arc::new(component), name)
+    }
+    
+    /// 注册已共享的单例组件
+    /// 
+    /// 与 [`register_singleton`](Self::register_singleton) 相同，但直接存储传入的实例，
+    /// 用于恢复之前取出的组件
+    pub fn register_singleton_arc<T: 'static + Send + Sync>(
+        &mut self, 
+        component: Arc<T>,
+        name: Option<String>
     ) -> Result<()> {
         let type_id = TypeId::of::<T>();
         let component_name = name.unwrap_or_else(|| {
@@ -129,17 +184,16 @@ impl ComponentRegistry {
         debug!("注册单例组件: {} (类型: {})", component_name, std::any::type_name::<T>());
         
         // 检查是否已注册
-        if self.components.contains_key(&type_id) || self.singletons.contains_key(&type_id) {
-            return Err(Error::container(format!("组件 {} 已经注册", component_name)));
-        }
+        self.check_duplicate(type_id, &component_name)?;
         
         // 存储单例组件
-        self.singletons.insert(type_id, Arc::new(component));
+        self.singletons.insert(type_id, component);
         
         // 存储元数据
         let metadata = ComponentMetadata {
             name: component_name.clone(),
             type_id,
+            type_name: std::any::type_name::<T>(),
             lifecycle: ComponentLifecycle::Singleton,
             registered_at: chrono::Utc::now(),
             description: None,
@@ -205,6 +259,11 @@ impl ComponentRegistry {
     pub fn contains_type_id(&self, type_id: &TypeId) -> bool {
         self.components.contains_key(type_id) || self.singletons.contains_key(type_id)
     }
+
+    /// 检查是否包含指定类型 ID 的单例组件
+    pub fn contains_singleton_type_id(&self, type_id: &TypeId) -> bool {
+        self.singletons.contains_key(type_id)
+    }
     
     /// 移除组件
     /// 
@@ -233,6 +292,45 @@ impl ComponentRegistry {
         removed
     }
     
+    /// 取出组件的注册，包括实例、元数据和依赖关系
+    ///
+    /// 用于临时替换组件，之后通过 [`restore`](Self::restore) 放回。组件未注册时返回 `None`
+    pub fn take<T: 'static>(&mut self) -> Option<ComponentEntry> {
+        let type_id = TypeId::of::<T>();
+        if !self.contains_type_id(&type_id) {
+            return None;
+        }
+        Some(ComponentEntry {
+            type_id,
+            component: self.components.remove(&type_id),
+            singleton: self.singletons.remove(&type_id),
+            metadata: self.metadata.remove(&type_id),
+            dependencies: self.dependencies.remove(&type_id),
+        })
+    }
+    
+    /// 放回由 [`take`](Self::take) 取出的注册，替换该类型当前的注册
+    pub fn restore(&mut self, entry: ComponentEntry) {
+        let type_id = entry.type_id;
+        self.components.remove(&type_id);
+        self.singletons.remove(&type_id);
+        self.metadata.remove(&type_id);
+        self.dependencies.remove(&type_id);
+        
+        if let Some(component) = entry.component {
+            self.components.insert(type_id, component);
+        }
+        if let Some(singleton) = entry.singleton {
+            self.singletons.insert(type_id, singleton);
+        }
+        if let Some(metadata) = entry.metadata {
+            self.metadata.insert(type_id, metadata);
+        }
+        if let Some(dependencies) = entry.dependencies {
+            self.dependencies.insert(type_id, dependencies);
+        }
+    }
+    
     /// 获取组件元数据
     pub fn get_metadata<T: 'static>(&self) -> Option<&ComponentMetadata> {
         let type_id = TypeId::of::<T>();
@@ -244,6 +342,29 @@ impl ComponentRegistry {
         self.metadata.values().collect()
     }
     
+    /// 获取所有组件的描述，按组件名称排序
+    /// 
+    /// 依赖以组件名称表示，未注册的依赖使用类型名称
+    pub fn describe(&self) -> Vec<ComponentDescriptor> {
+        let mut descriptors: Vec<ComponentDescriptor> = self.metadata
+            .values()
+            .map(|metadata| ComponentDescriptor {
+                name: metadata.name.clone(),
+                type_name: metadata.type_name.to_string(),
+                scope: metadata.lifecycle.as_str().to_string(),
+                dependencies: self.get_dependencies(&metadata.type_id)
+                    .iter()
+                    .map(|dependency| self.metadata
+                        .get(dependency)
+                        .map_or_else(|| format!("{:?}", dependency), |m| m.name.clone()))
+                    .collect(),
+                registered_at: metadata.registered_at,
+            })
+            .collect();
+        descriptors.sort_by(|a, b| a.name.cmp(&b.name));
+        descriptors
+    }
+    
     /// 获取组件数量统计
     pub fn stats(&self) -> ComponentStats {
         ComponentStats {
@@ -259,10 +380,11 @@ impl ComponentRegistry {
     /// * `dependent` - 依赖者的类型 ID
     /// * `dependency` - 被依赖者的类型 ID
     pub fn add_dependency(&mut self, dependent: TypeId, dependency: TypeId) {
-        self.dependencies
-            .entry(dependent)
-            .or_default()
-            .push(dependency);
+        let dependencies = self.dependencies.entry(dependent).or_default();
+        // 覆盖注册同一组件时不重复记录
+        if !dependencies.contains(&dependency) {
+            dependencies.push(dependency);
+        }
     }
     
     /// 获取组件的依赖列表
@@ -323,6 +445,28 @@ impl ComponentRegistry {
     }
 }
 
+/// 由 [`ComponentRegistry::take`] 取出的组件注册
+#[derive(Debug)]
+pub struct ComponentEntry {
+    /// 组件类型 ID
+    type_id: TypeId,
+    /// 普通组件实例
+    component: Option<Box<dyn Any + Send + Sync>>,
+    /// 单例组件实例
+    singleton: Option<Arc<dyn Any + Send + Sync>>,
+    /// 组件元数据
+    metadata: Option<ComponentMetadata>,
+    /// 组件的依赖
+    dependencies: Option<Vec<TypeId>>,
+}
+
+impl ComponentEntry {
+    /// 组件名称
+    pub fn name(&self) -> Option<&str> {
+        self.metadata.as_ref().map(|metadata| metadata.name.as_str())
+    }
+}
+
 impl Default for ComponentRegistry {
     fn default() -> Self {
         Self::new()
@@ -340,6 +484,24 @@ pub struct ComponentStats {
     pub singleton_components: usize,
 }
 
+/// 组件描述
+/// 
+/// 用于管理端点等场景查看容器中的组件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ComponentDescriptor {
+    /// 组件名称
+    pub name: String,
+    /// 组件类型名称
+    #[serde(rename = "type")]
+    pub type_name: String,
+    /// 生命周期名称
+    pub scope: String,
+    /// 依赖的组件名称
+    pub dependencies: Vec<String>,
+    /// 注册时间
+    pub registered_at: chrono::DateTime<chrono::Utc>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -461,6 +623,22 @@ mod tests {
         assert!(names.contains(&&"repo1".to_string()));
     }
 
+    #[test]
+    fn test_describe() {
+        let mut registry = ComponentRegistry::new();
+        registry.register(TestService, Some("service1".to_string())).unwrap();
+        registry.register_singleton(TestRepository { value: 1 }, Some("repo1".to_string())).unwrap();
+        registry.add_dependency(TypeId::of::<TestService>(), TypeId::of::<TestRepository>());
+        
+        let descriptors = registry.describe();
+        assert_eq!(descriptors.len(), 2);
+        assert_eq!(descriptors[0].name, "repo1");
+        assert_eq!(descriptors[0].scope, "singleton");
+        assert!(descriptors[0].type_name.ends_with("TestRepository"));
+        assert_eq!(descriptors[1].scope, "prototype");
+        assert_eq!(descriptors[1].dependencies, vec!["repo1".to_string()]);
+    }
+
     #[test]
     fn test_clear() {
         let mut registry = ComponentRegistry::new();
@@ -0,0 +1,285 @@
+//! 数据库初始化模块
+//!
+//! 按 `[datasource.init]` 配置定位 `schema.sql`、`data.sql` 及其环境专属脚本，
+//! 并将脚本拆分为单条语句，由各数据访问启动器在连接池创建后执行。
+//!
+//! 同时定义各数据访问启动器共用的指标名称，连接池指标带有 `pool` 标签：
+//! - `datasource.connections.active`、`datasource.connections.idle`、`datasource.connections.max` -
+//!   使用中、空闲和最大连接数
+//! - `datasource.connections.wait` - 获取连接的等待时间（秒）
+//! - `datasource.connections.timeouts` - 获取连接超时的次数
+//! - `datasource.query.duration` - 查询耗时（秒）
+//! - `datasource.queries.errors`、`datasource.queries.slow` - 执行失败和慢查询的次数
+
+use std::path::{Path, PathBuf};
+
+use crate::config::DataSourceConfig;
+use crate::error::{Error, Result};
+
+/// 使用中的连接数指标
+pub const CONNECTIONS_ACTIVE_METRIC: &str = "datasource.connections.active";
+
+/// 空闲连接数指标
+pub const CONNECTIONS_IDLE_METRIC: &str = "datasource.connections.idle";
+
+/// 最大连接数指标
+pub const CONNECTIONS_MAX_METRIC: &str = "datasource.connections.max";
+
+/// 获取连接的等待时间指标（秒），每次从连接池取出连接时记录
+pub const CONNECTIONS_WAIT_METRIC: &str = "datasource.connections.wait";
+
+/// 获取连接超时的次数指标
+pub const CONNECTIONS_TIMEOUTS_METRIC: &str = "datasource.connections.timeouts";
+
+/// 查询耗时指标（秒）
+pub const QUERY_DURATION_METRIC: &str = "datasource.query.duration";
+
+/// 查询失败次数指标
+pub const QUERY_ERRORS_METRIC: &str = "datasource.queries.errors";
+
+/// 慢查询次数指标
+pub const SLOW_QUERY_METRIC: &str = "datasource.queries.slow";
+
+/// 已加载的初始化脚本
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqlScript {
+    /// 脚本路径
+    pub path: PathBuf,
+    /// 拆分后的语句
+    pub statements: Vec<String>,
+}
+
+/// 加载需要执行的初始化脚本
+///
+/// 按配置的执行时机判断是否需要初始化，不需要时返回空列表。
+/// 脚本按 `schema`、`data` 的顺序排列，每个脚本之后紧跟其环境专属脚本，
+/// 按连接 URL 推断的方言拆分语句
+///
+/// # 错误
+/// 脚本存在但无法读取时返回错误
+pub fn load_init_scripts(config: &DataSourceConfig, profile: &str) -> Result<Vec<SqlScript>> {
+    let init = &config.init;
+    if !init.should_run(&config.url) {
+        return Ok(Vec::new());
+    }
+
+    let dialect = SqlDialect::from_url(&config.url);
+    let mut scripts = Vec::new();
+    for location in init.schema.iter().chain(&init.data) {
+        for path in [PathBuf::from(location), profile_path(Path::new(location), profile)] {
+            if !path.is_file() {
+                tracing::debug!("初始化脚本不存在，已跳过: {}", path.display());
+                continue;
+            }
+
+            let content = std::fs::read_to_string(&path).map_err(|e| {
+                Error::application(format!("读取初始化脚本失败 ({}): {}", path.display(), e))
+            })?;
+            scripts.push(SqlScript {
+                statements: split_statements(&content, &init.separator, dialect),
+                path,
+            });
+        }
+    }
+    Ok(scripts)
+}
+
+/// 环境专属脚本路径，如 `db/data.sql` 对应 `db/data-test.sql`
+fn profile_path(path: &Path, profile: &str) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name = match path.extension() {
+        Some(extension) => format!("{}-{}.{}", stem, profile, extension.to_string_lossy()),
+        None => format!("{}-{}", stem, profile),
+    };
+    path.with_file_name(name)
+}
+
+/// 初始化脚本的 SQL 方言，决定注释、引号和转义符的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlDialect {
+    /// MySQL，支持 `#` 行注释，`'`、`"` 字符串中的 `\` 为转义符
+    MySql,
+    /// PostgreSQL，只有 `E'...'` 字符串中的 `\` 为转义符，支持 `$$`、`$tag$` 美元引用
+    Postgres,
+    /// 其他数据库，如 SQLite，按标准 SQL 处理
+    Standard,
+}
+
+impl SqlDialect {
+    /// 根据连接 URL 的协议推断方言
+    pub fn from_url(url: &str) -> Self {
+        match url.split_once(':').map(|(scheme, _)| scheme) {
+            Some("mysql" | "mariadb") => Self::MySql,
+            Some("postgres" | "postgresql") => Self::Postgres,
+            _ => Self::Standard,
+        }
+    }
+
+    /// 在 `before` 之后打开的引号中，`\` 是否为转义符
+    fn escapes(self, quote: char, before: &str) -> bool {
+        match self {
+            Self::MySql => quote != '`',
+            Self::Postgres => {
+                let mut chars = before.chars().rev();
+                quote == '\''
+                    && matches!(chars.next(), Some('E' | 'e'))
+                    && !chars.next().is_some_and(is_identifier_char)
+            }
+            Self::Standard => false,
+        }
+    }
+}
+
+/// 将脚本拆分为单条语句
+///
+/// 忽略 `--` 行注释和 `/* */` 块注释，MySQL 还忽略 `#` 行注释。引号（`'`、`"`，
+/// PostgreSQL 以外还有 `` ` ``）和 PostgreSQL 美元引用内的分隔符和注释符不拆分
+pub fn split_statements(script: &str, separator: &str, dialect: SqlDialect) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    // 当前所在的引号，以及其中的 `\` 是否为转义符
+    let mut quote: Option<(char, bool)> = None;
+    let mut rest = script;
+
+    while let Some(c) = rest.chars().next() {
+        if let Some((open, escapes)) = quote {
+            current.push(c);
+            rest = &rest[c.len_utf8()..];
+            if escapes && c == '\\' {
+                if let Some(escaped) = rest.chars().next() {
+                    current.push(escaped);
+                    rest = &rest[escaped.len_utf8()..];
+                }
+            } else if c == open {
+                quote = None;
+            }
+        } else if rest.starts_with("--") || (dialect == SqlDialect::MySql && rest.starts_with('#')) {
+            rest = rest.find('\n').map(|end| &rest[end..]).unwrap_or("");
+        } else if rest.starts_with("/*") {
+            rest = rest.find("*/").map(|end| &rest[end + 2..]).unwrap_or("");
+            current.push(' ');
+        } else if let Some(quoted) = (dialect == SqlDialect::Postgres)
+            .then(|| dollar_quoted(rest, &current))
+            .flatten()
+        {
+            current.push_str(quoted);
+            rest = &rest[quoted.len()..];
+        } else if !separator.is_empty() && rest.starts_with(separator) {
+            push_statement(&mut statements, &mut current);
+            rest = &rest[separator.len()..];
+        } else {
+            if matches!(c, '\'' | '"') || (c == '`' && dialect != SqlDialect::Postgres) {
+                quote = Some((c, dialect.escapes(c, &current)));
+            }
+            current.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    push_statement(&mut statements, &mut current);
+    statements
+}
+
+/// 匹配 `rest` 开头的 PostgreSQL 美元引用字符串，如 `$$ ... $$`、`$body$ ... $body$`
+///
+/// `$1` 等位置参数和标识符中的 `$` 不是美元引用
+fn dollar_quoted<'a>(rest: &'a str, before: &str) -> Option<&'a str> {
+    if !rest.starts_with('$') || before.chars().next_back().is_some_and(is_identifier_char) {
+        return None;
+    }
+    let tag_end = rest[1..].find('$')? + 2;
+    let tag = &rest[..tag_end];
+    let name = &tag[1..tag_end - 1];
+    if name.starts_with(|ch: char| ch.is_ascii_digit()) || !name.chars().all(is_identifier_char) {
+        return None;
+    }
+    let end = rest[tag_end..]
+        .find(tag)
+        .map(|end| tag_end + end + tag.len())
+        .unwrap_or(rest.len());
+    Some(&rest[..end])
+}
+
+/// 是否为标识符中的字符
+fn is_identifier_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_' || ch == '$'
+}
+
+/// 保存非空语句并清空缓冲区
+fn push_statement(statements: &mut Vec<String>, current: &mut String) {
+    let statement = current.trim();
+    if !statement.is_empty() {
+        statements.push(statement.to_string());
+    }
+    current.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DatabaseInitMode;
+
+    /// 测试按分隔符拆分语句并忽略注释
+    #[test]
+    fn test_split_statements() {
+        let script = "-- 用户表\nCREATE TABLE users (id INT, name VARCHAR(32));\n\
+            /* 初始数据; 注释中的分隔符 */\n\
+            INSERT INTO users VALUES (1, 'a;b -- c'), (2, 'it\\'s');\n\
+            # 结尾\n";
+
+        assert_eq!(
+            split_statements(script, ";", SqlDialect::MySql),
+            vec![
+                "CREATE TABLE users (id INT, name VARCHAR(32))".to_string(),
+                "INSERT INTO users VALUES (1, 'a;b -- c'), (2, 'it\\'s')".to_string(),
+            ]
+        );
+        assert_eq!(split_statements("SELECT 1\nGO\nSELECT 2", "GO", SqlDialect::Standard).len(), 2);
+    }
+
+    /// 测试 PostgreSQL 脚本中的 `#` 运算符、标准字符串、转义字符串和美元引用
+    #[test]
+    fn test_split_statements_postgres() {
+        let script = "SELECT data #>> '{a,b}' FROM t;\n\
+            INSERT INTO paths VALUES ('C:\\temp\\'), (E'it\\'s; ok');\n\
+            CREATE FUNCTION f() RETURNS int AS $body$ BEGIN RETURN 1; END; $body$ LANGUAGE plpgsql;\n\
+            SELECT $$a;b$$, $1;";
+
+        assert_eq!(
+            split_statements(script, ";", SqlDialect::Postgres),
+            vec![
+                "SELECT data #>> '{a,b}' FROM t".to_string(),
+                "INSERT INTO paths VALUES ('C:\\temp\\'), (E'it\\'s; ok')".to_string(),
+                "CREATE FUNCTION f() RETURNS int AS $body$ BEGIN RETURN 1; END; $body$ LANGUAGE plpgsql"
+                    .to_string(),
+                "SELECT $$a;b$$, $1".to_string(),
+            ]
+        );
+        assert_eq!(SqlDialect::from_url("postgres://localhost/app"), SqlDialect::Postgres);
+        assert_eq!(SqlDialect::from_url("mysql://localhost/app"), SqlDialect::MySql);
+        assert_eq!(SqlDialect::from_url("sqlite::memory:"), SqlDialect::Standard);
+    }
+
+    /// 测试按执行时机和环境加载脚本
+    #[test]
+    fn test_load_init_scripts() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("schema.sql"), "CREATE TABLE t (id INT);").unwrap();
+        std::fs::write(dir.path().join("data-test.sql"), "INSERT INTO t VALUES (1);").unwrap();
+
+        let mut config = DataSourceConfig::new("mysql://localhost/app");
+        config.init.schema = vec![dir.path().join("schema.sql").to_string_lossy().to_string()];
+        config.init.data = vec![dir.path().join("data.sql").to_string_lossy().to_string()];
+        assert!(load_init_scripts(&config, "test").unwrap().is_empty());
+
+        config.init.mode = DatabaseInitMode::Always;
+        let scripts = load_init_scripts(&config, "test").unwrap();
+        assert_eq!(scripts.len(), 2);
+        assert!(scripts[0].path.ends_with("schema.sql"));
+        assert!(scripts[1].path.ends_with("data-test.sql"));
+        assert_eq!(scripts[1].statements, vec!["INSERT INTO t VALUES (1)".to_string()]);
+        assert_eq!(load_init_scripts(&config, "dev").unwrap().len(), 1);
+    }
+}
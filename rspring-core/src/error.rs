@@ -4,10 +4,22 @@
 
 pub mod types;
 pub mod handler;
+pub mod business;
+pub mod reporting;
+#[cfg(feature = "sentry")]
+pub mod sentry;
 
 // 重新导出常用类型和函数
 pub use types::{Error, Result};
 pub use handler::{ErrorHandler, ErrorResponse};
+pub use business::BusinessError;
+pub use reporting::{
+    capture_error, error_reporting, install_panic_hook, is_reportable, set_error_reporting,
+    ErrorReport, ErrorReporter, ErrorReporting, ErrorReportingConfig, InMemoryErrorReporter,
+    ReportSource,
+};
+#[cfg(feature = "sentry")]
+pub use sentry::SentryReporter;
 
 /// 全局错误处理器实例
 /// 
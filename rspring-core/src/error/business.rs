@@ -0,0 +1,87 @@
+//! 业务错误模块
+//!
+//! 应用自定义的业务错误枚举通过 `#[derive(BusinessError)]` 实现 [`BusinessError`]，
+//! 每个变体声明错误码、HTTP 状态码和错误描述：
+//! - 转换为 [`Error::Business`] 时带上变体的状态码，Web 层按该状态码返回响应
+//! - 转换为 [`ErrorResponse`](crate::error::ErrorResponse) 时使用变体的错误码和描述
+
+use crate::error::types::Error;
+
+/// 业务错误特征
+///
+/// # 示例
+/// ```rust
+/// #[derive(Debug, BusinessError)]
+/// pub enum UserError {
+///     #[error(code = "USER_EXISTS", status = 409, message = "用户 {name} 已存在")]
+///     Exists { name: String },
+///
+///     #[error(code = "USER_LOCKED", status = 423, message = "用户已被锁定")]
+///     Locked,
+/// }
+///
+/// pub async fn register(&self, name: &str) -> Result<User> {
+///     if self.repository.exists(name).await? {
+///         return Err(UserError::Exists { name: name.to_string() }.into());
+///     }
+///     // ...
+/// }
+/// ```
+pub trait BusinessError {
+    /// 错误码
+    fn code(&self) -> &'static str;
+
+    /// HTTP 状态码
+    fn status(&self) -> u16;
+
+    /// 错误描述
+    fn message(&self) -> String;
+
+    /// 转换为带有状态码的 [`Error::Business`]
+    fn into_error(self) -> Error
+    where
+        Self: Sized,
+    {
+        Error::business(self.code(), self.message()).with_status(self.status())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    enum OrderError {
+        Exists { id: u64 },
+    }
+
+    /// 与 `#[derive(BusinessError)]` 和 `#[error(code = "ORDER_EXISTS", status = 409, message = "订单 {id} 已存在")]` 生成的实现相同
+    impl BusinessError for OrderError {
+        fn code(&self) -> &'static str {
+            match self {
+                Self::Exists { .. } => "ORDER_EXISTS",
+            }
+        }
+
+        fn status(&self) -> u16 {
+            match self {
+                Self::Exists { .. } => 409,
+            }
+        }
+
+        fn message(&self) -> String {
+            match self {
+                Self::Exists { id } => format!("订单 {id} 已存在", id = id),
+            }
+        }
+    }
+
+    /// 测试转换为带有状态码的业务错误，不影响同一错误码直接创建的错误
+    #[test]
+    fn test_business_error() {
+        let error = OrderError::Exists { id: 7 }.into_error();
+        assert_eq!(error.error_code(), Some("ORDER_EXISTS"));
+        assert!(error.to_string().contains("订单 7 已存在"));
+        assert!(matches!(error, Error::Business { status: Some(409), .. }));
+        assert!(matches!(Error::business("ORDER_EXISTS", "订单已存在"), Error::Business { status: None, .. }));
+    }
+}
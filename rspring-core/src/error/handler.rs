@@ -3,6 +3,7 @@
 //! 提供统一的错误处理逻辑和错误响应格式化
 
 use crate::error::types::{Error, Result};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use tracing::error;
 
@@ -38,7 +39,7 @@ impl ErrorHandler {
             Error::DependencyInjection { message } => {
                 error!(context = context, "依赖注入错误: {}", message);
             }
-            Error::Business { code, message } => {
+            Error::Business { code, message, .. } => {
                 tracing::warn!(
                     context = context, 
                     error_code = code, 
@@ -75,7 +76,7 @@ impl ErrorHandler {
 /// 标准化错误响应结构
 /// 
 /// 用于统一的错误响应格式，便于客户端处理
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ErrorResponse {
     /// 错误码
     pub code: String,
@@ -101,7 +102,7 @@ impl ErrorResponse {
                 message.clone(),
                 None,
             ),
-            Error::Business { code, message } => (
+            Error::Business { code, message, .. } => (
                 code.clone(),
                 message.clone(),
                 None,
@@ -116,6 +117,11 @@ impl ErrorResponse {
                 "未授权访问".to_string(),
                 None,
             ),
+            Error::Forbidden { message } => (
+                "FORBIDDEN".to_string(),
+                message.clone(),
+                None,
+            ),
             Error::Container { message } => (
                 "CONTAINER_ERROR".to_string(),
                 "容器错误".to_string(),
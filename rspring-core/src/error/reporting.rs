@@ -0,0 +1,492 @@
+//! 错误上报模块
+//!
+//! 将需要关注的错误上报到错误追踪服务，如 Sentry：
+//! - [`capture_error`] 上报服务端错误（见 [`is_reportable`]），客户端错误如校验失败不上报
+//! - [`install_panic_hook`] 上报 panic，保留原有的 panic 输出
+//! - Web 启动器的全局异常处理器自动上报控制器返回的服务端错误，并上报其他 5xx 响应
+//!
+//! 每个报告附带 `[error_reporting]` 配置的版本和环境，以及日志上下文中的请求 ID、用户 ID、链路 ID 等字段。
+//! 上报方式由 [`ErrorReporter`] 实现：`sentry` 特性提供 [`SentryReporter`](crate::error::sentry::SentryReporter)，
+//! Web 启动器的 `error-webhook` 特性提供推送到 HTTP 端点的实现
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, Once, RwLock};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use crate::config::properties::Configuration;
+use crate::config::ConfigurationManager;
+use crate::error::types::{Error, Result};
+use crate::logging::context::log_context;
+
+/// 错误来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportSource {
+    /// 代码中捕获的错误
+    Error,
+    /// panic
+    Panic,
+    /// 没有对应错误的 5xx 响应
+    Response,
+}
+
+impl ReportSource {
+    /// 来源名称，如 `"panic"`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportSource::Error => "error",
+            ReportSource::Panic => "panic",
+            ReportSource::Response => "response",
+        }
+    }
+}
+
+/// 错误报告
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ErrorReport {
+    /// 发生时间
+    pub timestamp: DateTime<Utc>,
+    /// 错误来源
+    pub source: ReportSource,
+    /// 错误信息
+    pub message: String,
+    /// 错误类型，如 `"Internal"`
+    pub error_type: Option<String>,
+    /// 发生位置，如 panic 的 `src/order.rs:42:9`
+    pub location: Option<String>,
+    /// 响应状态码
+    pub status: Option<u16>,
+    /// 应用版本
+    pub release: Option<String>,
+    /// 运行环境
+    pub environment: Option<String>,
+    /// 发生时的日志上下文，如请求 ID
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub context: BTreeMap<String, String>,
+}
+
+impl ErrorReport {
+    /// 创建错误报告，上下文取自当前日志上下文
+    pub fn new(source: ReportSource, message: impl Into<String>) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            source,
+            message: message.into(),
+            error_type: None,
+            location: None,
+            status: None,
+            release: None,
+            environment: None,
+            context: log_context(),
+        }
+    }
+
+    /// 由错误创建报告
+    pub fn from_error(error: &Error) -> Self {
+        Self {
+            error_type: Some(error_type(error).to_string()),
+            ..Self::new(ReportSource::Error, error.to_string())
+        }
+    }
+
+    /// 设置响应状态码
+    pub fn status(mut self, status: u16) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// 设置发生位置
+    pub fn location(mut self, location: impl Into<String>) -> Self {
+        self.location = Some(location.into());
+        self
+    }
+}
+
+/// 错误上报特征
+///
+/// 上报可能在 panic 钩子中同步调用，需要网络请求的实现应在后台发送，不阻塞调用方
+///
+/// # 示例
+/// ```rust
+/// struct AlertReporter { alerts: AlertClient }
+///
+/// impl ErrorReporter for AlertReporter {
+///     fn report(&self, report: &ErrorReport) {
+///         let alerts = self.alerts.clone();
+///         let report = report.clone();
+///         tokio::spawn(async move { alerts.send(&report).await });
+///     }
+/// }
+/// ```
+pub trait ErrorReporter: Send + Sync {
+    /// 上报错误
+    fn report(&self, report: &ErrorReport);
+
+    /// 等待已提交的报告发送完成，返回是否在超时前完成
+    fn flush(&self, _timeout: Duration) -> bool {
+        true
+    }
+}
+
+/// 保存在内存中的上报方式，用于测试
+#[derive(Debug, Default)]
+pub struct InMemoryErrorReporter {
+    /// 已上报的错误
+    reports: Mutex<Vec<ErrorReport>>,
+}
+
+impl InMemoryErrorReporter {
+    /// 创建空的上报方式
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 获取已上报的错误
+    pub fn reports(&self) -> Vec<ErrorReport> {
+        self.reports.lock().expect("错误报告锁已损坏").clone()
+    }
+}
+
+impl ErrorReporter for InMemoryErrorReporter {
+    fn report(&self, report: &ErrorReport) {
+        self.reports
+            .lock()
+            .expect("错误报告锁已损坏")
+            .push(report.clone());
+    }
+}
+
+/// 错误上报器，为报告附加版本和环境后交给所有上报方式
+#[derive(Default)]
+pub struct ErrorReporting {
+    /// 应用版本
+    release: Option<String>,
+    /// 运行环境
+    environment: Option<String>,
+    /// 上报方式
+    reporters: RwLock<Vec<Arc<dyn ErrorReporter>>>,
+}
+
+impl ErrorReporting {
+    /// 创建没有上报方式的上报器
+    pub fn new(release: Option<String>, environment: Option<String>) -> Self {
+        Self {
+            release,
+            environment,
+            reporters: RwLock::default(),
+        }
+    }
+
+    /// 按配置创建上报器，未设置版本和环境时使用传入的默认值
+    ///
+    /// 配置了 `[error_reporting.sentry]` 时添加 Sentry 上报方式；关闭上报时不添加任何上报方式
+    ///
+    /// # 错误
+    /// Sentry 配置无效，或配置了 Sentry 但未启用 `sentry` 特性时返回错误
+    pub fn from_config(
+        config: &ErrorReportingConfig,
+        release: Option<String>,
+        environment: Option<String>,
+    ) -> Result<Self> {
+        let reporting = Self::new(
+            config.release.clone().or(release),
+            config.environment.clone().or(environment),
+        );
+        if !config.enabled {
+            return Ok(reporting);
+        }
+        if let Some(sentry) = &config.sentry {
+            #[cfg(feature = "sentry")]
+            reporting.add_reporter(Arc::new(crate::error::sentry::SentryReporter::new(
+                sentry,
+                reporting.release.clone(),
+                reporting.environment.clone(),
+            )?));
+            #[cfg(not(feature = "sentry"))]
+            {
+                let _ = sentry;
+                return Err(Error::validation(
+                    "配置了 [error_reporting.sentry]，但未启用 rspring-core 的 sentry 特性",
+                ));
+            }
+        }
+        Ok(reporting)
+    }
+
+    /// 添加上报方式
+    pub fn add_reporter(&self, reporter: Arc<dyn ErrorReporter>) {
+        self.reporters
+            .write()
+            .expect("错误上报锁已损坏")
+            .push(reporter);
+    }
+
+    /// 获取上报方式数量
+    pub fn reporter_count(&self) -> usize {
+        self.reporters.read().expect("错误上报锁已损坏").len()
+    }
+
+    /// 获取应用版本
+    pub fn release(&self) -> Option<&str> {
+        self.release.as_deref()
+    }
+
+    /// 获取运行环境
+    pub fn environment(&self) -> Option<&str> {
+        self.environment.as_deref()
+    }
+
+    /// 上报错误，报告未设置版本和环境时使用上报器的配置
+    pub fn report(&self, mut report: ErrorReport) {
+        let reporters = self.reporters.read().expect("错误上报锁已损坏").clone();
+        if reporters.is_empty() {
+            return;
+        }
+        report.release = report.release.or_else(|| self.release.clone());
+        report.environment = report.environment.or_else(|| self.environment.clone());
+        for reporter in reporters {
+            reporter.report(&report);
+        }
+    }
+
+    /// 等待所有上报方式发送完成
+    pub fn flush(&self, timeout: Duration) -> bool {
+        let reporters = self.reporters.read().expect("错误上报锁已损坏").clone();
+        reporters.iter().all(|reporter| reporter.flush(timeout))
+    }
+}
+
+impl std::fmt::Debug for ErrorReporting {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ErrorReporting")
+            .field("release", &self.release)
+            .field("environment", &self.environment)
+            .field("reporters", &self.reporter_count())
+            .finish()
+    }
+}
+
+/// 错误上报配置
+///
+/// # 示例
+/// ```toml
+/// [error_reporting]
+/// environment = "prod"
+///
+/// [error_reporting.sentry]
+/// dsn = "https://public@o0.ingest.sentry.io/0"
+/// sample_rate = 0.5
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ErrorReportingConfig {
+    /// 是否上报错误
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 应用版本（可选），不设置时使用 `[app].version`
+    #[serde(default)]
+    pub release: Option<String>,
+    /// 运行环境（可选），不设置时使用当前 profile
+    #[serde(default)]
+    pub environment: Option<String>,
+    /// 是否上报 panic
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_capture_panics")]
+    pub capture_panics: bool,
+    /// Sentry 配置（可选），需要启用 `sentry` 特性
+    #[serde(default)]
+    pub sentry: Option<SentryConfig>,
+}
+
+/// Sentry 配置
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct SentryConfig {
+    /// 项目的 DSN
+    pub dsn: String,
+    /// 上报比例，0 到 1
+    ///
+    /// # 默认值
+    /// `1.0`
+    #[serde(default = "default_sample_rate")]
+    pub sample_rate: f32,
+}
+
+impl Default for ErrorReportingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            release: None,
+            environment: None,
+            capture_panics: default_capture_panics(),
+            sentry: None,
+        }
+    }
+}
+
+impl ErrorReportingConfig {
+    /// 加载 `[error_reporting]` 配置，没有该配置时使用默认值
+    ///
+    /// # 错误
+    /// 配置格式错误时返回错误
+    pub fn load(config: &ConfigurationManager) -> Result<Self> {
+        if config.contains_key("error_reporting") {
+            config.get_section("error_reporting")
+        } else {
+            Ok(Self::default())
+        }
+    }
+}
+
+impl Configuration for ErrorReportingConfig {}
+
+/// 全局错误上报器
+static ERROR_REPORTING: Lazy<RwLock<Arc<ErrorReporting>>> =
+    Lazy::new(|| RwLock::new(Arc::new(ErrorReporting::default())));
+
+/// 设置全局错误上报器
+///
+/// 在作用域中调用时的行为见[全局组件作用域](crate::scope)
+pub fn set_error_reporting(reporting: Arc<ErrorReporting>) {
+    if let Some(reporting) = crate::scope::set_scoped(reporting) {
+        *ERROR_REPORTING.write().expect("错误上报器锁已损坏") = reporting;
+    }
+}
+
+/// 获取全局错误上报器
+pub fn error_reporting() -> Arc<ErrorReporting> {
+    crate::scope::scoped()
+        .unwrap_or_else(|| ERROR_REPORTING.read().expect("错误上报器锁已损坏").clone())
+}
+
+/// 判断错误是否需要上报
+///
+/// 服务端错误需要上报；校验、业务、认证、授权和未找到等客户端错误不上报
+pub fn is_reportable(error: &Error) -> bool {
+    !matches!(
+        error,
+        Error::Validation { .. }
+            | Error::Business { .. }
+            | Error::NotFound { .. }
+            | Error::Unauthorized
+            | Error::Forbidden { .. }
+    )
+}
+
+/// 通过全局错误上报器上报错误，不需要上报的错误被忽略
+///
+/// # 示例
+/// ```rust
+/// if let Err(e) = sync_inventory().await {
+///     capture_error(&e);
+/// }
+/// ```
+pub fn capture_error(error: &Error) {
+    if is_reportable(error) {
+        error_reporting().report(ErrorReport::from_error(error));
+    }
+}
+
+/// 安装上报 panic 的钩子，原有的钩子仍会执行
+///
+/// 多次调用只安装一次
+pub fn install_panic_hook() {
+    static INSTALLED: Once = Once::new();
+    INSTALLED.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let payload = info.payload();
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "panic".to_string());
+            let mut report = ErrorReport::new(ReportSource::Panic, message);
+            report.location = info.location().map(|location| location.to_string());
+            error_reporting().report(report);
+            previous(info);
+        }));
+    });
+}
+
+/// 错误类型名称
+fn error_type(error: &Error) -> &'static str {
+    match error {
+        Error::Configuration(_) => "Configuration",
+        Error::Io(_) => "Io",
+        Error::JsonSerialization(_) => "JsonSerialization",
+        Error::YamlSerialization(_) => "YamlSerialization",
+        Error::TomlSerialization(_) => "TomlSerialization",
+        Error::Container { .. } => "Container",
+        Error::ComponentNotFound { .. } => "ComponentNotFound",
+        Error::DependencyInjection { .. } => "DependencyInjection",
+        Error::Validation { .. } => "Validation",
+        Error::Business { .. } => "Business",
+        Error::NotFound { .. } => "NotFound",
+        Error::Unauthorized => "Unauthorized",
+        Error::Forbidden { .. } => "Forbidden",
+        Error::Internal { .. } => "Internal",
+        Error::Application { .. } => "Application",
+        Error::Runtime { .. } => "Runtime",
+    }
+}
+
+// 默认值函数
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_capture_panics() -> bool {
+    true
+}
+
+fn default_sample_rate() -> f32 {
+    1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logging::context::{with_log_context, REQUEST_ID_FIELD};
+
+    /// 测试只上报服务端错误，报告带有版本、环境和日志上下文
+    #[tokio::test]
+    async fn test_error_reporting() {
+        let memory = Arc::new(InMemoryErrorReporter::new());
+        let reporting = ErrorReporting::new(Some("1.4.2".to_string()), Some("prod".to_string()));
+        reporting.add_reporter(memory.clone());
+
+        with_log_context([(REQUEST_ID_FIELD, "req-1")], async {
+            for error in [
+                Error::internal("连接池耗尽"),
+                Error::validation("名称不能为空"),
+            ] {
+                if is_reportable(&error) {
+                    reporting.report(ErrorReport::from_error(&error));
+                }
+            }
+        })
+        .await;
+        reporting
+            .report(ErrorReport::new(ReportSource::Response, "GET /orders 返回 502").status(502));
+
+        let reports = memory.reports();
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].error_type.as_deref(), Some("Internal"));
+        assert_eq!(reports[0].message, "内部服务器错误: 连接池耗尽");
+        assert_eq!(reports[0].release.as_deref(), Some("1.4.2"));
+        assert_eq!(reports[0].environment.as_deref(), Some("prod"));
+        assert_eq!(reports[0].context[REQUEST_ID_FIELD], "req-1");
+        assert_eq!(reports[1].status, Some(502));
+        assert!(reports[1].context.is_empty());
+        assert!(reporting.flush(Duration::from_millis(10)));
+    }
+}
@@ -0,0 +1,166 @@
+//! Sentry 上报模块
+//!
+//! 启用 `sentry` 特性后，[`SentryReporter`] 将错误报告转换为 Sentry 事件发送到
+//! `[error_reporting.sentry]` 配置的项目。事件在后台发送，应用关闭时等待发送完成
+
+use std::borrow::Cow;
+use std::time::Duration;
+
+use sentry::protocol::{Event, Level, User, Value};
+use sentry::types::Dsn;
+use sentry::{Client, ClientOptions};
+
+use crate::error::reporting::{ErrorReport, ErrorReporter, ReportSource, SentryConfig};
+use crate::error::types::{Error, Result};
+use crate::logging::context::{REQUEST_ID_FIELD, TENANT_FIELD, TRACE_ID_FIELD, USER_ID_FIELD};
+
+/// 作为事件标签的日志上下文字段
+const TAG_FIELDS: [&str; 3] = [REQUEST_ID_FIELD, TENANT_FIELD, TRACE_ID_FIELD];
+
+/// 上报到 Sentry 的方式
+pub struct SentryReporter {
+    /// Sentry 客户端
+    client: Client,
+}
+
+impl SentryReporter {
+    /// 创建 Sentry 上报方式
+    ///
+    /// # 错误
+    /// DSN 无效或上报比例不在 0 到 1 之间时返回错误
+    pub fn new(
+        config: &SentryConfig,
+        release: Option<String>,
+        environment: Option<String>,
+    ) -> Result<Self> {
+        let dsn: Dsn = config
+            .dsn
+            .parse()
+            .map_err(|e| Error::validation(format!("无效的 Sentry DSN: {}", e)))?;
+        if !(0.0..=1.0).contains(&config.sample_rate) {
+            return Err(Error::validation(format!(
+                "Sentry 上报比例必须在 0 到 1 之间: {}",
+                config.sample_rate
+            )));
+        }
+        let options = sentry::apply_defaults(ClientOptions {
+            dsn: Some(dsn),
+            release: release.map(Cow::Owned),
+            environment: environment.map(Cow::Owned),
+            sample_rate: config.sample_rate,
+            ..Default::default()
+        });
+        Ok(Self {
+            client: Client::with_options(options),
+        })
+    }
+}
+
+impl ErrorReporter for SentryReporter {
+    fn report(&self, report: &ErrorReport) {
+        self.client.capture_event(to_event(report), None);
+    }
+
+    fn flush(&self, timeout: Duration) -> bool {
+        self.client.flush(Some(timeout))
+    }
+}
+
+impl std::fmt::Debug for SentryReporter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SentryReporter")
+            .field("dsn", &self.client.dsn().map(|dsn| dsn.host().to_string()))
+            .finish()
+    }
+}
+
+/// 将错误报告转换为 Sentry 事件
+///
+/// 来源、错误类型、状态码和请求 ID、租户、链路 ID 作为标签，用户 ID 作为事件用户，其余上下文作为附加数据
+fn to_event(report: &ErrorReport) -> Event<'static> {
+    let mut event = Event {
+        message: Some(report.message.clone()),
+        level: match report.source {
+            ReportSource::Panic => Level::Fatal,
+            _ => Level::Error,
+        },
+        logger: Some("rspring".to_string()),
+        release: report.release.clone().map(Cow::Owned),
+        environment: report.environment.clone().map(Cow::Owned),
+        user: report.context.get(USER_ID_FIELD).map(|id| User {
+            id: Some(id.clone()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    event
+        .tags
+        .insert("source".to_string(), report.source.as_str().to_string());
+    if let Some(error_type) = &report.error_type {
+        event
+            .tags
+            .insert("error_type".to_string(), error_type.clone());
+    }
+    if let Some(status) = report.status {
+        event.tags.insert("status".to_string(), status.to_string());
+    }
+    if let Some(location) = &report.location {
+        event
+            .extra
+            .insert("location".to_string(), Value::from(location.as_str()));
+    }
+    for (key, value) in &report.context {
+        if TAG_FIELDS.contains(&key.as_str()) {
+            event.tags.insert(key.clone(), value.clone());
+        } else if key != USER_ID_FIELD {
+            event.extra.insert(key.clone(), Value::from(value.as_str()));
+        }
+    }
+    event
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logging::context::JOB_ID_FIELD;
+
+    /// 测试错误报告转换为 Sentry 事件，无效的 DSN 返回错误
+    #[test]
+    fn test_sentry_event() {
+        let mut report =
+            ErrorReport::new(ReportSource::Panic, "索引越界").location("src/order.rs:42:9");
+        report.release = Some("1.4.2".to_string());
+        report
+            .context
+            .insert(REQUEST_ID_FIELD.to_string(), "req-1".to_string());
+        report
+            .context
+            .insert(USER_ID_FIELD.to_string(), "alice".to_string());
+        report
+            .context
+            .insert(TENANT_FIELD.to_string(), "acme".to_string());
+        report
+            .context
+            .insert(JOB_ID_FIELD.to_string(), "job-7".to_string());
+
+        let event = to_event(&report);
+        assert_eq!(event.level, Level::Fatal);
+        assert_eq!(event.release.as_deref(), Some("1.4.2"));
+        assert_eq!(event.tags["source"], "panic");
+        assert_eq!(event.tags[REQUEST_ID_FIELD], "req-1");
+        assert_eq!(event.user.unwrap().id.as_deref(), Some("alice"));
+        assert_eq!(event.extra["location"], "src/order.rs:42:9");
+        assert_eq!(event.tags[TENANT_FIELD], "acme");
+        assert_eq!(event.extra[JOB_ID_FIELD], "job-7");
+
+        let config = |dsn: &str| SentryConfig {
+            dsn: dsn.to_string(),
+            sample_rate: 1.0,
+        };
+        assert!(
+            SentryReporter::new(&config("https://public@o0.ingest.sentry.io/0"), None, None)
+                .is_ok()
+        );
+        assert!(SentryReporter::new(&config("not a dsn"), None, None).is_err());
+    }
+}
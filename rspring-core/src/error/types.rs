@@ -47,9 +47,9 @@ pub enum Error {
     #[error("验证错误: {message}")]
     Validation { message: String },
     
-    /// 业务错误
+    /// 业务错误，`status` 为返回的 HTTP 状态码，未设置时为 400
     #[error("业务错误: {message} (错误码: {code})")]
-    Business { message: String, code: String },
+    Business { message: String, code: String, status: Option<u16> },
     
     /// 资源未找到
     #[error("资源未找到: {resource}")]
@@ -59,6 +59,10 @@ pub enum Error {
     #[error("未授权访问")]
     Unauthorized,
     
+    /// 禁止访问，已认证但没有所需的权限
+    #[error("禁止访问: {message}")]
+    Forbidden { message: String },
+    
     /// 内部服务器错误
     #[error("内部服务器错误: {message}")]
     Internal { message: String },
@@ -136,8 +140,22 @@ impl Error {
     pub fn business(code: impl Into<String>, message: impl Into<String>) -> Self {
         Self::Business { 
             code: code.into(), 
-            message: message.into() 
+            message: message.into(),
+            status: None,
+        }
+    }
+    
+    /// 设置业务错误返回的 HTTP 状态码，其他错误原样返回
+    /// 
+    /// # 示例
+    /// ```rust
+    /// return Err(Error::business("USER_EXISTS", "用户已存在").with_status(409));
+    /// ```
+    pub fn with_status(mut self, status: u16) -> Self {
+        if let Self::Business { status: current, .. } = &mut self {
+            *current = Some(status);
         }
+        self
     }
     
     /// 创建未找到错误
@@ -152,6 +170,20 @@ impl Error {
         }
     }
     
+    /// 创建禁止访问错误
+    /// 
+    /// # 示例
+    /// ```rust
+    /// if !principal.has_role("ADMIN") {
+    ///     return Err(Error::forbidden("需要管理员角色"));
+    /// }
+    /// ```
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::Forbidden { 
+            message: message.into() 
+        }
+    }
+    
     /// 创建内部错误
     /// 
     /// # 示例
@@ -0,0 +1,355 @@
+//! 应用事件模块
+//!
+//! 提供类似 Spring `ApplicationEventPublisher` 的进程内事件发布：
+//! - 任意 `Send + Sync + 'static` 的类型都可以作为事件，按类型分发给 [`ApplicationListener`]
+//! - 普通监听器在发布时立即执行
+//! - 事务监听器绑定到 [`TransactionPhase`]，在事务中发布的事件暂存到事务结束后，
+//!   默认只在提交成功后执行，避免监听器处理已回滚的数据
+//! - `#[TransactionalEventListener]` 注解生成事务监听器
+//! - 观察函数接收所有发布的事件，不区分类型和事务，用于测试中记录事件
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use once_cell::sync::Lazy;
+
+use crate::container::Component;
+use crate::error::Result;
+use crate::transaction::{is_transaction_active, register_synchronization, TransactionPhase};
+
+/// 监听函数
+type ListenerFn = Arc<dyn Fn(Arc<dyn Any + Send + Sync>) -> BoxFuture<'static, Result<()>> + Send + Sync>;
+
+/// 观察函数，参数为事件类型名称和事件
+type ObserverFn = Arc<dyn Fn(&'static str, &Arc<dyn Any + Send + Sync>) + Send + Sync>;
+
+/// 事件监听器
+///
+/// # 示例
+/// ```rust
+/// let publisher = event_publisher();
+/// publisher.register(ApplicationListener::new(|event: UserRegistered| async move {
+///     tracing::info!("用户 {} 已注册", event.name);
+///     Ok(())
+/// }));
+/// publisher.register(
+///     ApplicationListener::transactional(TransactionPhase::AfterCommit, send_welcome_mail)
+///         .fallback_execution(true),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct ApplicationListener {
+    /// 事件类型
+    event_type: TypeId,
+    /// 事件类型名称
+    event_name: &'static str,
+    /// 事务阶段，为 `None` 时发布后立即执行
+    phase: Option<TransactionPhase>,
+    /// 不在事务中发布时是否立即执行
+    fallback_execution: bool,
+    /// 监听函数
+    listener: ListenerFn,
+}
+
+impl ApplicationListener {
+    /// 创建发布后立即执行的监听器
+    pub fn new<E, F, Fut>(listener: F) -> Self
+    where
+        E: Clone + Send + Sync + 'static,
+        F: Fn(E) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        let listener: ListenerFn = Arc::new(move |event| match event.downcast_ref::<E>() {
+            Some(event) => listener(event.clone()).boxed(),
+            None => futures::future::ready(Ok(())).boxed(),
+        });
+        Self {
+            event_type: TypeId::of::<E>(),
+            event_name: std::any::type_name::<E>(),
+            phase: None,
+            fallback_execution: false,
+            listener,
+        }
+    }
+
+    /// 创建事务监听器，在事务中发布的事件到指定阶段才执行
+    ///
+    /// 不在事务中发布的事件默认被忽略，可通过 [`fallback_execution`](Self::fallback_execution) 改为立即执行
+    pub fn transactional<E, F, Fut>(phase: TransactionPhase, listener: F) -> Self
+    where
+        E: Clone + Send + Sync + 'static,
+        F: Fn(E) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        Self {
+            phase: Some(phase),
+            ..Self::new(listener)
+        }
+    }
+
+    /// 设置不在事务中发布时是否立即执行
+    ///
+    /// # 默认值
+    /// `false`
+    pub fn fallback_execution(mut self, fallback_execution: bool) -> Self {
+        self.fallback_execution = fallback_execution;
+        self
+    }
+
+    /// 获取事务阶段，普通监听器为 `None`
+    pub fn phase(&self) -> Option<TransactionPhase> {
+        self.phase
+    }
+}
+
+impl std::fmt::Debug for ApplicationListener {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApplicationListener")
+            .field("event", &self.event_name)
+            .field("phase", &self.phase)
+            .field("fallback_execution", &self.fallback_execution)
+            .finish()
+    }
+}
+
+/// 事件发布器
+#[derive(Default)]
+pub struct EventPublisher {
+    /// 按事件类型注册的监听器
+    listeners: RwLock<HashMap<TypeId, Vec<ApplicationListener>>>,
+    /// 按编号注册的观察函数
+    observers: RwLock<Vec<(u64, ObserverFn)>>,
+    /// 下一个观察函数的编号
+    next_observer_id: AtomicU64,
+}
+
+impl EventPublisher {
+    /// 创建没有监听器的发布器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册监听器
+    pub fn register(&self, listener: ApplicationListener) {
+        self.listeners
+            .write()
+            .expect("事件监听器锁已损坏")
+            .entry(listener.event_type)
+            .or_default()
+            .push(listener);
+    }
+
+    /// 注册观察函数，在监听器执行前同步接收所有发布的事件，返回用于移除的编号
+    ///
+    /// # 示例
+    /// ```rust
+    /// let id = event_publisher().observe(|name, _event| tracing::debug!("发布事件 {}", name));
+    /// event_publisher().remove_observer(id);
+    /// ```
+    pub fn observe<F>(&self, observer: F) -> u64
+    where
+        F: Fn(&'static str, &Arc<dyn Any + Send + Sync>) + Send + Sync + 'static,
+    {
+        let id = self.next_observer_id.fetch_add(1, Ordering::Relaxed);
+        self.observers
+            .write()
+            .expect("事件观察函数锁已损坏")
+            .push((id, Arc::new(observer)));
+        id
+    }
+
+    /// 移除观察函数，不存在时返回 `false`
+    pub fn remove_observer(&self, id: u64) -> bool {
+        let mut observers = self.observers.write().expect("事件观察函数锁已损坏");
+        let count = observers.len();
+        observers.retain(|(observer_id, _)| *observer_id != id);
+        observers.len() != count
+    }
+
+    /// 发布事件
+    ///
+    /// 观察函数先于监听器执行；普通监听器按注册顺序立即执行；事务监听器在事务中注册为对应阶段的同步回调，
+    /// 不在事务中时按 `fallback_execution` 立即执行或忽略
+    ///
+    /// # 错误
+    /// 立即执行的监听器返回错误时停止执行后续监听器并返回该错误。
+    /// 提交前监听器的错误会使事务回滚，其他阶段的错误只记录日志
+    pub async fn publish<E: Send + Sync + 'static>(&self, event: E) -> Result<()> {
+        let listeners = self
+            .listeners
+            .read()
+            .expect("事件监听器锁已损坏")
+            .get(&TypeId::of::<E>())
+            .cloned()
+            .unwrap_or_default();
+        let observers = self.observers.read().expect("事件观察函数锁已损坏").clone();
+        if listeners.is_empty() && observers.is_empty() {
+            return Ok(());
+        }
+
+        let event: Arc<dyn Any + Send + Sync> = Arc::new(event);
+        for (_, observer) in observers {
+            observer(std::any::type_name::<E>(), &event);
+        }
+        let in_transaction = is_transaction_active();
+        for listener in listeners {
+            match listener.phase {
+                Some(phase) if in_transaction => {
+                    let event = event.clone();
+                    register_synchronization(phase, move || (listener.listener)(event));
+                }
+                Some(_) if !listener.fallback_execution => {
+                    tracing::debug!("不在事务中，忽略事务监听器处理的事件 {}", listener.event_name);
+                }
+                _ => (listener.listener)(event.clone()).await?,
+            }
+        }
+        Ok(())
+    }
+
+    /// 获取已注册的监听器数量
+    pub fn listener_count(&self) -> usize {
+        self.listeners
+            .read()
+            .expect("事件监听器锁已损坏")
+            .values()
+            .map(Vec::len)
+            .sum()
+    }
+}
+
+impl std::fmt::Debug for EventPublisher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventPublisher")
+            .field("listeners", &self.listener_count())
+            .field(
+                "observers",
+                &self.observers.read().expect("事件观察函数锁已损坏").len(),
+            )
+            .finish()
+    }
+}
+
+impl Component for EventPublisher {
+    fn component_name(&self) -> &'static str {
+        "EventPublisher"
+    }
+}
+
+/// 全局事件发布器
+static EVENT_PUBLISHER: Lazy<RwLock<Arc<EventPublisher>>> =
+    Lazy::new(|| RwLock::new(Arc::new(EventPublisher::new())));
+
+/// 设置全局事件发布器
+///
+/// 在作用域中调用时的行为见[全局组件作用域](crate::scope)
+pub fn set_event_publisher(publisher: Arc<EventPublisher>) {
+    if let Some(publisher) = crate::scope::set_scoped(publisher) {
+        *EVENT_PUBLISHER.write().expect("事件发布器锁已损坏") = publisher;
+    }
+}
+
+/// 获取全局事件发布器
+pub fn event_publisher() -> Arc<EventPublisher> {
+    crate::scope::scoped()
+        .unwrap_or_else(|| EVENT_PUBLISHER.read().expect("事件发布器锁已损坏").clone())
+}
+
+/// 通过全局事件发布器发布事件
+///
+/// # 错误
+/// 立即执行的监听器返回错误时返回该错误
+pub async fn publish_event<E: Send + Sync + 'static>(event: E) -> Result<()> {
+    event_publisher().publish(event).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use crate::transaction::transactional;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct OrderCreated(u32);
+
+    /// 测试事务事件只在提交后投递，回滚时投递给回滚监听器
+    #[tokio::test]
+    async fn test_transactional_listener() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let publisher = EventPublisher::new();
+        let listeners = [
+            ("immediate", None),
+            ("commit", Some(TransactionPhase::AfterCommit)),
+            ("rollback", Some(TransactionPhase::AfterRollback)),
+        ];
+        for (label, phase) in listeners {
+            let received = received.clone();
+            let listener = move |event: OrderCreated| {
+                let received = received.clone();
+                async move {
+                    received.lock().unwrap().push((label, event.0));
+                    Ok(())
+                }
+            };
+            publisher.register(match phase {
+                Some(phase) => ApplicationListener::transactional(phase, listener),
+                None => ApplicationListener::new(listener),
+            });
+        }
+
+        transactional(async {
+            publisher.publish(OrderCreated(1)).await?;
+            assert_eq!(*received.lock().unwrap(), vec![("immediate", 1)]);
+            Ok::<_, Error>(())
+        })
+        .await
+        .unwrap();
+        assert_eq!(*received.lock().unwrap(), vec![("immediate", 1), ("commit", 1)]);
+
+        received.lock().unwrap().clear();
+        let result: Result<()> = transactional(async {
+            publisher.publish(OrderCreated(2)).await?;
+            Err(Error::validation("库存不足"))
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(*received.lock().unwrap(), vec![("immediate", 2), ("rollback", 2)]);
+
+        // 不在事务中时事务监听器被忽略
+        received.lock().unwrap().clear();
+        publisher.publish(OrderCreated(3)).await.unwrap();
+        assert_eq!(*received.lock().unwrap(), vec![("immediate", 3)]);
+        assert_eq!(publisher.listener_count(), 3);
+    }
+
+    /// 测试观察函数接收所有类型的事件，移除后不再接收
+    #[tokio::test]
+    async fn test_observer() {
+        let publisher = EventPublisher::new();
+        let names = Arc::new(Mutex::new(Vec::new()));
+        let recorded = names.clone();
+        let id = publisher.observe(move |name, event| {
+            assert_eq!(
+                event.downcast_ref::<OrderCreated>().is_some(),
+                name.ends_with("OrderCreated")
+            );
+            recorded.lock().unwrap().push(name);
+        });
+
+        publisher.publish(OrderCreated(1)).await.unwrap();
+        publisher.publish("shipped").await.unwrap();
+        assert_eq!(names.lock().unwrap().len(), 2);
+        assert!(names.lock().unwrap()[0].ends_with("OrderCreated"));
+
+        assert!(publisher.remove_observer(id));
+        assert!(!publisher.remove_observer(id));
+        publisher.publish(OrderCreated(2)).await.unwrap();
+        assert_eq!(names.lock().unwrap().len(), 2);
+    }
+}
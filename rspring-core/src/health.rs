@@ -0,0 +1,232 @@
+//! 健康检查模块
+//!
+//! 提供类似 Spring Boot Actuator 的健康检查抽象：
+//! - 数据源、缓存等组件实现 [`HealthIndicator`] 报告自身状态
+//! - [`HealthRegistry`] 汇总所有指示器，任一组件不可用时整体为 `DOWN`
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::container::Component;
+
+/// 健康状态
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HealthStatus {
+    /// 状态未知
+    #[default]
+    Unknown,
+    /// 正常
+    Up,
+    /// 暂停服务
+    OutOfService,
+    /// 不可用
+    Down,
+}
+
+/// 单个组件的健康信息
+///
+/// # 示例
+/// ```rust
+/// let health = Health::up()
+///     .with_detail("database", "MySQL")
+///     .with_detail("idle", 4);
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct Health {
+    /// 健康状态
+    pub status: HealthStatus,
+    /// 附加信息
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub details: Map<String, Value>,
+}
+
+impl Health {
+    /// 创建指定状态的健康信息
+    pub fn new(status: HealthStatus) -> Self {
+        Self {
+            status,
+            details: Map::new(),
+        }
+    }
+
+    /// 正常
+    pub fn up() -> Self {
+        Self::new(HealthStatus::Up)
+    }
+
+    /// 不可用，错误信息记录在 `error` 中
+    pub fn down(error: impl ToString) -> Self {
+        Self::new(HealthStatus::Down).with_detail("error", error.to_string())
+    }
+
+    /// 添加附加信息
+    pub fn with_detail(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.details.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// 健康指示器特征
+///
+/// # 示例
+/// ```rust
+/// pub struct DiskSpaceHealthIndicator;
+///
+/// #[async_trait]
+/// impl HealthIndicator for DiskSpaceHealthIndicator {
+///     fn name(&self) -> &str {
+///         "diskSpace"
+///     }
+///
+///     async fn health(&self) -> Health {
+///         Health::up().with_detail("free", free_bytes())
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait HealthIndicator: Send + Sync {
+    /// 指示器名称，作为汇总结果中的键
+    fn name(&self) -> &str;
+
+    /// 检查健康状态
+    async fn health(&self) -> Health;
+}
+
+/// 汇总的健康信息
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CompositeHealth {
+    /// 整体状态，取所有组件中最差的状态
+    pub status: HealthStatus,
+    /// 各组件的健康信息
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub components: BTreeMap<String, Health>,
+}
+
+/// 健康指示器注册表
+///
+/// # 示例
+/// ```rust
+/// let registry = HealthRegistry::new();
+/// registry.register(DataSourceHealthIndicator::new("primary", pool.clone()));
+///
+/// let health = registry.check().await;
+/// ```
+#[derive(Default)]
+pub struct HealthRegistry {
+    /// 已注册的指示器
+    indicators: RwLock<Vec<Arc<dyn HealthIndicator>>>,
+}
+
+impl HealthRegistry {
+    /// 创建空的注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册健康指示器
+    pub fn register(&self, indicator: impl HealthIndicator + 'static) {
+        self.register_arc(Arc::new(indicator));
+    }
+
+    /// 注册共享的健康指示器
+    pub fn register_arc(&self, indicator: Arc<dyn HealthIndicator>) {
+        self.indicators
+            .write()
+            .expect("健康指示器注册表锁已损坏")
+            .push(indicator);
+    }
+
+    /// 获取已注册的指示器名称
+    pub fn names(&self) -> Vec<String> {
+        self.indicators
+            .read()
+            .expect("健康指示器注册表锁已损坏")
+            .iter()
+            .map(|indicator| indicator.name().to_string())
+            .collect()
+    }
+
+    /// 并发检查所有指示器并汇总
+    ///
+    /// 没有注册任何指示器时整体为 `UP`
+    pub async fn check(&self) -> CompositeHealth {
+        let indicators = self
+            .indicators
+            .read()
+            .expect("健康指示器注册表锁已损坏")
+            .clone();
+
+        let results = futures::future::join_all(
+            indicators.iter().map(|indicator| indicator.health()),
+        )
+        .await;
+
+        let components: BTreeMap<String, Health> = indicators
+            .iter()
+            .map(|indicator| indicator.name().to_string())
+            .zip(results)
+            .collect();
+        let status = components
+            .values()
+            .map(|health| health.status)
+            .max()
+            .unwrap_or(HealthStatus::Up);
+
+        CompositeHealth { status, components }
+    }
+}
+
+impl std::fmt::Debug for HealthRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HealthRegistry")
+            .field("indicators", &self.names())
+            .finish()
+    }
+}
+
+impl Component for HealthRegistry {
+    fn component_name(&self) -> &'static str {
+        "HealthRegistry"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixed(&'static str, Health);
+
+    #[async_trait]
+    impl HealthIndicator for Fixed {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        async fn health(&self) -> Health {
+            self.1.clone()
+        }
+    }
+
+    /// 测试汇总时取最差的状态
+    #[tokio::test]
+    async fn test_composite_health() {
+        let registry = HealthRegistry::new();
+        assert_eq!(registry.check().await.status, HealthStatus::Up);
+
+        registry.register(Fixed("db", Health::up().with_detail("database", "MySQL")));
+        registry.register(Fixed("redis", Health::down("connection refused")));
+
+        let health = registry.check().await;
+        assert_eq!(health.status, HealthStatus::Down);
+        assert_eq!(health.components["db"].details["database"], "MySQL");
+
+        let json = serde_json::to_value(&health).unwrap();
+        assert_eq!(json["status"], "DOWN");
+        assert_eq!(json["components"]["redis"]["details"]["error"], "connection refused");
+    }
+}
@@ -0,0 +1,89 @@
+//! 方法监控模块
+//!
+//! `#[Timed]` 和 `#[Logged]` 标注的方法通过本模块记录耗时和调用日志，监控代码不进入业务方法：
+//! - `#[Timed]` 将方法耗时记录到 [`METHOD_TIMED_METRIC`] 或指定名称的直方图，单位为秒，
+//!   标签为 `class`、`method` 和 `outcome`（`success` 或 `error`），未启用 `metrics` 特性时不记录
+//! - `#[Logged]` 在方法所在的 tracing span 中记录调用参数、耗时和错误，`redact` 列出的参数显示为 [`REDACTED`]
+//! - 返回 `Result` 的方法返回 `Err` 时结果为 `error`，其余为 `success`
+
+use std::time::Duration;
+
+/// 方法耗时指标的默认名称
+pub const METHOD_TIMED_METRIC: &str = "method.timed";
+
+/// 日志中脱敏参数的显示值
+pub const REDACTED: &str = "******";
+
+/// 记录方法耗时
+///
+/// # 示例
+/// ```rust
+/// let start = Instant::now();
+/// let result = self.gateway.charge(order).await;
+/// record_timed(METHOD_TIMED_METRIC, "PaymentService", "charge", result.is_ok(), start.elapsed());
+/// ```
+pub fn record_timed(
+    metric: &'static str,
+    class: &'static str,
+    method: &'static str,
+    success: bool,
+    elapsed: Duration,
+) {
+    #[cfg(feature = "metrics")]
+    metrics::histogram!(
+        metric,
+        "class" => class,
+        "method" => method,
+        "outcome" => if success { "success" } else { "error" }
+    )
+    .record(elapsed.as_secs_f64());
+
+    #[cfg(not(feature = "metrics"))]
+    let _ = (metric, class, method, success, elapsed);
+}
+
+#[cfg(all(test, feature = "prometheus"))]
+mod tests {
+    use super::*;
+    use crate::metrics::{build_prometheus_recorder, MetricsConfig};
+
+    /// 测试按类、方法和结果记录耗时
+    #[test]
+    fn test_record_timed() {
+        let config: MetricsConfig = serde_json::from_value(serde_json::json!({})).unwrap();
+        let recorder = build_prometheus_recorder(&config).unwrap();
+        let handle = recorder.handle();
+        ::metrics::with_local_recorder(&recorder, || {
+            record_timed(
+                METHOD_TIMED_METRIC,
+                "OrderService",
+                "place",
+                true,
+                Duration::from_millis(20),
+            );
+            record_timed(
+                METHOD_TIMED_METRIC,
+                "OrderService",
+                "place",
+                false,
+                Duration::from_millis(5),
+            );
+        });
+
+        let output = handle.render();
+        assert!(
+            output.contains(
+                "method_timed_count{class=\"OrderService\",method=\"place\",outcome=\"success\"} 1"
+            ),
+            "{}",
+            output
+        );
+        assert!(
+            output.contains(
+                "method_timed_count{class=\"OrderService\",method=\"place\",outcome=\"error\"} 1"
+            ),
+            "{}",
+            output
+        );
+    }
+}
@@ -8,26 +8,98 @@
 //! - 依赖注入容器
 //! - 核心错误处理
 //! - 日志集成
+//! - 健康检查
+//! - 数据库初始化脚本
+//! - 缓存抽象
+//! - 安全上下文与实体审计
+//! - 异步任务执行、重试与断路器
+//! - 消息死信处理
+//! - 定时任务分布式锁
+//! - 声明式事务与事务事件
+//! - 全局组件作用域
+//! - 可替换的时钟
+//! - 方法级权限检查与密码编码
+//! - 方法耗时指标与调用日志
+//! - 指标门面与 Prometheus 导出
 //! - 核心组件注解
 
 pub mod application;
+pub mod auditing;
+pub mod cache;
+pub mod clock;
 pub mod config;
 pub mod container;
+pub mod database;
 pub mod error;
+pub mod event;
+pub mod health;
+pub mod instrumentation;
+pub mod lock;
 pub mod logging;
 pub mod macros;
+pub mod messaging;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod page;
+pub mod resilience;
+pub mod retry;
+pub mod scope;
+pub mod security;
+pub mod startup;
+pub mod task;
+pub mod trace;
+pub mod transaction;
 
 // 重新导出常用类型和特征
 pub use application::{RSpringApp, RSpringApplication, ApplicationContext, AxumBootApplication};
-pub use config::{Configuration, ConfigurationManager, AppConfig, ServerConfig, LoggingConfig};
+pub use auditing::{audit, AuditEvent, AuditOutcome, AuditSink, AuditStamp, Auditable};
+pub use clock::{clock, set_clock, ApplicationClock, Clock, SystemClock};
+pub use config::{Configuration, ConfigurationManager, AppConfig, ServerConfig, ListenerConfig, SslConfig, Http2Config, DataSourceConfig, RedisConfig, LoggingConfig};
 pub use container::{
     Container, Component, Service, Repository, Controller,
-    DependencyInjector, ComponentRegistry
+    DependencyInjector, ComponentRegistry, BeanConfiguration, BeanDefinition, BeanDependency
+};
+pub use error::{BusinessError, Error, Result};
+pub use event::{event_publisher, publish_event, set_event_publisher, ApplicationListener, EventPublisher};
+pub use health::{CompositeHealth, Health, HealthIndicator, HealthRegistry, HealthStatus};
+pub use lock::{lock_provider, set_lock_provider, LockConfig, LockProvider, LockToken};
+pub use messaging::{
+    message_converter, publish_dead_letter, DeadLetter, DeadLetterListener, DeadLetterPolicy,
+    ListenerSettings, MessageConverter,
+};
+#[cfg(feature = "metrics")]
+pub use crate::metrics::MetricsConfig;
+#[cfg(feature = "prometheus")]
+pub use crate::metrics::{install_prometheus, render_prometheus};
+pub use page::{Direction, Order, Page, PageResult, Sort};
+pub use resilience::{circuit_breaker, CircuitBreakerRegistry, CircuitState};
+pub use retry::{retry, RetryPolicy};
+pub use scope::{current_scope, GlobalScope};
+pub use security::{
+    check_access, current_principal, require_principal, with_principal, AccessExpression, FromPrincipal, Principal,
+    SecurityContext,
+};
+#[cfg(feature = "password")]
+pub use security::password::{
+    DelegatingPasswordEncoder, PasswordAlgorithm, PasswordEncoder, PasswordEncoderConfig, PasswordVerification,
+};
+pub use startup::{startup_recorder, startup_report, ApplicationReady, StartupReport};
+pub use task::dump::{task_dump, TaskDump, TaskDumpContributor};
+pub use task::{task_executor, TaskExecutionConfig, TaskExecutor};
+pub use trace::{continue_trace, current_trace_context, outbound_trace_headers, TraceContext};
+pub use transaction::{
+    set_transaction_manager, transaction_manager, transactional, Transaction, TransactionManager, TransactionPhase,
 };
-pub use error::{Error, Result};
 
 // 重新导出宏
 pub use macros::*;
 
 // 重新导出常用外部类型
-pub use serde::{Deserialize, Serialize};
\ No newline at end of file
+pub use serde::{Deserialize, Serialize};
+
+// 宏生成代码使用的外部 crate，不属于公开 API
+#[doc(hidden)]
+pub mod __private {
+    pub use tokio;
+    pub use tracing;
+}
\ No newline at end of file
@@ -0,0 +1,235 @@
+//! 分布式锁模块
+//!
+//! 提供类似 ShedLock 的定时任务锁，保证集群中同一任务同一时刻只在一个实例上执行：
+//! - [`LockProvider`] 抽象锁的获取、续期和释放，内置单机的 [`InMemoryLockProvider`]，
+//!   Redis 和 MySQL 启动器分别提供基于 `SET NX` 和锁表的实现
+//! - 锁的租期为 `lock_at_most_for`，任务执行期间每隔半个租期自动续期，实例崩溃时锁在租期后失效
+//! - 任务完成后锁至少保留到 `lock_at_least_for`，避免各实例时钟偏差导致任务被重复执行
+//! - `#[SchedulerLock]` 注解为任务方法加锁，未获得锁时跳过本次执行
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+
+use crate::error::Result;
+
+/// 已获得的锁
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockToken {
+    /// 锁名称
+    pub name: String,
+    /// 持有者标识，续期和释放时校验
+    pub owner: String,
+    /// 获得锁的时间
+    pub acquired_at: Instant,
+}
+
+impl LockToken {
+    /// 创建锁，持有者标识随机生成
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            owner: uuid::Uuid::new_v4().to_string(),
+            acquired_at: Instant::now(),
+        }
+    }
+}
+
+/// 锁提供者特征
+#[async_trait]
+pub trait LockProvider: Send + Sync {
+    /// 尝试获得锁，锁被其他实例持有时返回 `None`
+    ///
+    /// # 参数
+    /// * `name` - 锁名称
+    /// * `lease` - 租期，到期后锁自动失效
+    async fn try_lock(&self, name: &str, lease: Duration) -> Result<Option<LockToken>>;
+
+    /// 将锁的租期延长为从现在起的 `lease`，锁已失效或被他人持有时返回 `false`
+    async fn extend(&self, lock: &LockToken, lease: Duration) -> Result<bool>;
+
+    /// 释放锁，锁至少保留到获得锁后的 `keep_for`
+    async fn unlock(&self, lock: &LockToken, keep_for: Duration) -> Result<()>;
+}
+
+/// 锁参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LockConfig {
+    /// 最长持有时间，即锁的租期
+    pub lock_at_most_for: Duration,
+    /// 最短持有时间
+    pub lock_at_least_for: Duration,
+}
+
+impl Default for LockConfig {
+    fn default() -> Self {
+        Self {
+            lock_at_most_for: Duration::from_secs(10 * 60),
+            lock_at_least_for: Duration::ZERO,
+        }
+    }
+}
+
+/// 全局锁提供者
+static LOCK_PROVIDER: Lazy<RwLock<Arc<dyn LockProvider>>> =
+    Lazy::new(|| RwLock::new(Arc::new(InMemoryLockProvider::default())));
+
+/// 设置全局锁提供者，供 `#[SchedulerLock]` 使用
+///
+/// 未设置时使用 [`InMemoryLockProvider`]，只能保证单个实例内不重复执行
+///
+/// 在作用域中调用时的行为见[全局组件作用域](crate::scope)
+pub fn set_lock_provider(provider: Arc<dyn LockProvider>) {
+    if let Some(provider) = crate::scope::set_scoped(provider) {
+        *LOCK_PROVIDER.write().expect("锁提供者锁已损坏") = provider;
+    }
+}
+
+/// 获取全局锁提供者
+pub fn lock_provider() -> Arc<dyn LockProvider> {
+    crate::scope::scoped()
+        .unwrap_or_else(|| LOCK_PROVIDER.read().expect("锁提供者锁已损坏").clone())
+}
+
+/// 持有锁执行任务
+///
+/// 获得锁后执行任务并返回 `Some`，锁被其他实例持有时不执行任务，返回 `None`
+///
+/// # 错误
+/// 锁提供者访问失败时返回错误，任务不会执行
+///
+/// # 示例
+/// ```rust
+/// let config = LockConfig { lock_at_most_for: Duration::from_secs(300), ..LockConfig::default() };
+/// if run_locked(&*lock_provider(), "report", config, generate_report()).await?.is_none() {
+///     tracing::debug!("报表任务正在其他实例上执行");
+/// }
+/// ```
+pub async fn run_locked<F>(
+    provider: &dyn LockProvider,
+    name: &str,
+    config: LockConfig,
+    task: F,
+) -> Result<Option<F::Output>>
+where
+    F: Future,
+{
+    let lease = config.lock_at_most_for.max(Duration::from_millis(1));
+    let Some(lock) = provider.try_lock(name, lease).await? else {
+        tracing::debug!("锁 {} 被其他实例持有，跳过执行", name);
+        return Ok(None);
+    };
+
+    tokio::pin!(task);
+    let mut renew = tokio::time::interval_at(tokio::time::Instant::now() + lease / 2, lease / 2);
+    let output = loop {
+        tokio::select! {
+            output = &mut task => break output,
+            _ = renew.tick() => match provider.extend(&lock, lease).await {
+                Ok(true) => tracing::debug!("锁 {} 已续期", name),
+                Ok(false) => tracing::warn!("锁 {} 已失效，任务可能在其他实例上重复执行", name),
+                Err(e) => tracing::warn!("锁 {} 续期失败: {}", name, e),
+            },
+        }
+    };
+
+    if let Err(e) = provider.unlock(&lock, config.lock_at_least_for).await {
+        tracing::warn!("释放锁 {} 失败，将在租期后自动失效: {}", name, e);
+    }
+    Ok(Some(output))
+}
+
+/// 记录获取锁失败，供返回 `()` 的 `#[SchedulerLock]` 方法使用
+#[doc(hidden)]
+pub fn log_lock_failure(name: &str, error: &crate::error::Error) {
+    tracing::error!("获取定时任务锁 {} 失败，跳过本次执行: {}", name, error);
+}
+
+/// 内存锁提供者，只在当前进程内有效
+#[derive(Debug, Default)]
+pub struct InMemoryLockProvider {
+    /// 锁名称到持有者和到期时间
+    locks: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+#[async_trait]
+impl LockProvider for InMemoryLockProvider {
+    async fn try_lock(&self, name: &str, lease: Duration) -> Result<Option<LockToken>> {
+        let mut locks = self.locks.lock().expect("内存锁已损坏");
+        let now = Instant::now();
+        if locks.get(name).is_some_and(|(_, until)| *until > now) {
+            return Ok(None);
+        }
+
+        let lock = LockToken::new(name);
+        locks.insert(name.to_string(), (lock.owner.clone(), now + lease));
+        Ok(Some(lock))
+    }
+
+    async fn extend(&self, lock: &LockToken, lease: Duration) -> Result<bool> {
+        let mut locks = self.locks.lock().expect("内存锁已损坏");
+        let now = Instant::now();
+        match locks.get_mut(&lock.name) {
+            Some((owner, until)) if *owner == lock.owner && *until > now => {
+                *until = now + lease;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn unlock(&self, lock: &LockToken, keep_for: Duration) -> Result<()> {
+        let mut locks = self.locks.lock().expect("内存锁已损坏");
+        if let Some((owner, until)) = locks.get_mut(&lock.name) {
+            if *owner == lock.owner {
+                let keep_until = lock.acquired_at + keep_for;
+                if keep_until > Instant::now() {
+                    *until = keep_until;
+                } else {
+                    locks.remove(&lock.name);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试锁被持有时跳过执行，以及最短持有时间
+    #[tokio::test]
+    async fn test_run_locked() {
+        let provider = InMemoryLockProvider::default();
+        let config = LockConfig {
+            lock_at_most_for: Duration::from_millis(40),
+            lock_at_least_for: Duration::from_millis(200),
+        };
+
+        let (first, second) = tokio::join!(
+            run_locked(&provider, "job", config, async {
+                tokio::time::sleep(Duration::from_millis(60)).await;
+                1
+            }),
+            async {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                run_locked(&provider, "job", config, async { 2 }).await
+            }
+        );
+        // 任务执行时间超过租期，续期后其他调用仍无法获得锁
+        assert_eq!(first.unwrap(), Some(1));
+        assert_eq!(second.unwrap(), None);
+
+        // 任务完成后锁保留到最短持有时间
+        assert_eq!(run_locked(&provider, "job", config, async { 3 }).await.unwrap(), None);
+        assert_eq!(
+            run_locked(&provider, "other", LockConfig::default(), async { 4 }).await.unwrap(),
+            Some(4)
+        );
+    }
+}
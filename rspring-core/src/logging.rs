@@ -1,44 +1,145 @@
 //! 日志系统模块
-//! 
-//! 提供基于 tracing 的统一日志功能
+//!
+//! 提供基于 tracing 的统一日志功能：
+//! - 日志默认输出到控制台，`output` 为 `"syslog"` 或 `"journald"` 时改为发送到 syslog 服务或 journald
+//! - `[logging.levels]` 按模块设置日志级别，运行时可以通过 [`set_log_level`] 修改
+//! - 任务本地的日志上下文中的字段（请求 ID、用户 ID 等）附加到每一条日志
+//! - JSON 格式可以选择 tracing-subscriber 默认的字段结构、Elastic Common Schema 或自定义字段名
+//! - 配置 `file` 后同时写入日志文件，由后台线程非阻塞写入，按大小和时间滚动并保留指定数量的历史文件
+//! - `[logging.throttle]` 限制同一处代码高频输出的日志，并汇总被抑制的数量
 
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
-use crate::config::LoggingConfig;
+pub mod context;
+#[cfg(unix)]
+pub mod journald;
+pub mod json;
+pub mod levels;
+pub mod non_blocking;
+pub mod rolling;
+pub mod syslog;
+pub mod throttle;
+
+pub use context::{
+    log_context, pop_log_field, push_log_field, with_log_context, ContextFormat, JOB_ID_FIELD, REQUEST_ID_FIELD,
+    SPAN_ID_FIELD, TENANT_FIELD, TRACE_ID_FIELD, USER_ID_FIELD,
+};
+#[cfg(unix)]
+pub use journald::JournaldWriter;
+pub use json::JsonFormat;
+pub use levels::{clear_log_level, log_levels, set_log_level, ROOT_LOGGER};
+pub use non_blocking::{non_blocking, NonBlocking, WorkerGuard};
+pub use rolling::RollingFile;
+pub use syslog::SyslogWriter;
+pub use throttle::{ThrottleLayer, THROTTLE_TARGET};
+
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+use tracing_subscriber::{fmt::MakeWriter, layer::SubscriberExt, util::SubscriberInitExt, Layer, Registry};
+use crate::config::{JsonLogSchema, LogOutput, LoggingConfig};
 use crate::error::Result;
 
+/// 日志文件写入线程的守卫，关闭日志系统时取出并丢弃
+static FILE_WRITER_GUARD: Lazy<Mutex<Option<WorkerGuard>>> = Lazy::new(|| Mutex::new(None));
+
 /// 初始化日志系统
-/// 
+///
 /// # 参数
 /// * `config` - 日志配置
-/// 
+///
 /// # 错误
-/// 当日志初始化失败时返回错误
+/// 当日志级别无效或日志初始化失败时返回错误
 pub fn init_logging(config: &LoggingConfig) -> Result<()> {
-    let filter = EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| EnvFilter::new(&config.level));
-    
-    match config.format.as_str() {
-        "json" => {
-            tracing_subscriber::registry()
-                .with(filter)
-                .with(tracing_subscriber::fmt::layer().json())
-                .init();
-        }
-        "compact" => {
-            tracing_subscriber::registry()
-                .with(filter)
-                .with(tracing_subscriber::fmt::layer().compact())
-                .init();
-        }
-        "pretty" | _ => {
-            tracing_subscriber::registry()
-                .with(filter)
-                .with(tracing_subscriber::fmt::layer().pretty())
-                .init();
+    let filter = levels::reloadable_filter(config)?;
+
+    let mut layers = vec![match config.output {
+        LogOutput::Stdout => format_layer(config, std::io::stdout, true),
+        LogOutput::Syslog => system_layer(config, SyslogWriter::connect(&config.syslog)?),
+        #[cfg(unix)]
+        LogOutput::Journald => system_layer(config, JournaldWriter::connect(&config.syslog)?),
+        #[cfg(not(unix))]
+        LogOutput::Journald => {
+            return Err(crate::error::Error::validation("journald 输出仅支持 Unix 系统"));
         }
+    }];
+    if let Some(path) = &config.file {
+        let max_size = config.max_file_size.checked_mul(1024 * 1024).ok_or_else(|| {
+            crate::error::Error::validation(format!(
+                "日志文件大小上限过大: {} MB",
+                config.max_file_size
+            ))
+        })?;
+        let file = RollingFile::open(path, max_size, config.max_files)?.with_rotation(config.rotation);
+        let (writer, guard) = non_blocking(file)?;
+        layers.push(format_layer(config, writer, false));
+        *FILE_WRITER_GUARD.lock().expect("日志守卫锁已损坏") = Some(guard);
     }
-    
+
+    let layers: Box<dyn Layer<Registry> + Send + Sync> = if config.throttle.enabled {
+        Box::new(ThrottleLayer::new(&config.throttle, layers)?)
+    } else {
+        Box::new(layers)
+    };
+    tracing_subscriber::registry()
+        .with(layers.with_filter(filter))
+        .init();
+
     tracing::info!("日志系统已初始化，级别: {}, 格式: {}", config.level, config.format);
-    
+    match config.output {
+        LogOutput::Stdout => {}
+        LogOutput::Syslog => tracing::info!(
+            "日志输出到 syslog: {}",
+            config.syslog.address.as_deref().unwrap_or(syslog::DEFAULT_SYSLOG_SOCKET)
+        ),
+        LogOutput::Journald => tracing::info!("日志输出到 journald"),
+    }
+    if let Some(path) = &config.file {
+        tracing::info!("日志同时写入文件: {}", path);
+    }
+
     Ok(())
-}
\ No newline at end of file
+}
+
+/// 关闭日志系统，写完日志文件中已缓存的日志
+///
+/// 之后的日志只输出到控制台
+pub fn shutdown_logging() {
+    let guard = FILE_WRITER_GUARD.lock().expect("日志守卫锁已损坏").take();
+    drop(guard);
+}
+
+/// 创建输出到 syslog 或 journald 的格式化层
+///
+/// 时间和级别由接收方记录，非 JSON 格式统一使用单行的紧凑格式
+fn system_layer<W>(config: &LoggingConfig, writer: W) -> Box<dyn Layer<Registry> + Send + Sync>
+where
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    if config.format == "json" {
+        return format_layer(config, writer, false);
+    }
+    tracing_subscriber::fmt::layer()
+        .with_writer(writer)
+        .with_ansi(false)
+        .without_time()
+        .with_level(false)
+        .compact()
+        .map_event_format(|format| ContextFormat::new(format, false))
+        .boxed()
+}
+
+/// 按格式创建输出到 `writer` 的格式化层
+fn format_layer<W>(config: &LoggingConfig, writer: W, ansi: bool) -> Box<dyn Layer<Registry> + Send + Sync>
+where
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    let layer = tracing_subscriber::fmt::layer().with_writer(writer).with_ansi(ansi);
+    match config.format.as_str() {
+        "json" if config.json.schema == JsonLogSchema::Default => layer
+            .json()
+            .map_event_format(|format| ContextFormat::new(format, true).with_static_fields(&config.json.fields))
+            .boxed(),
+        "json" => layer.event_format(JsonFormat::new(&config.json)).boxed(),
+        "compact" => layer.compact().map_event_format(|format| ContextFormat::new(format, false)).boxed(),
+        _ => layer.pretty().map_event_format(|format| ContextFormat::new(format, false)).boxed(),
+    }
+}
@@ -0,0 +1,257 @@
+//! 日志上下文模块
+//!
+//! 任务本地的日志上下文（MDC），上下文中的字段附加到期间输出的每一条日志：
+//! - Web 层的请求 ID 中间件设置 `request_id`，认证后设置 `user_id`，任务队列设置 `job_id`
+//! - 链路追踪上下文设置 `trace_id` 和 `span_id`，见 [`trace`](crate::trace) 模块
+//! - 应用可以通过 [`push_log_field`] 和 [`pop_log_field`] 增删字段，如租户 `tenant`
+//! - 文本格式的日志以 `[key=value ...]` 前缀输出字段，JSON 格式的日志输出到 `context` 对象，
+//!   其他 JSON 字段结构见 [`json`](crate::logging::json) 模块
+//!
+//! `TaskExecutor` 提交的异步任务继承提交时的日志上下文
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
+
+use serde_json::Value;
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::registry::LookupSpan;
+
+/// 请求 ID 字段
+pub const REQUEST_ID_FIELD: &str = "request_id";
+
+/// 用户 ID 字段
+pub const USER_ID_FIELD: &str = "user_id";
+
+/// 租户字段
+pub const TENANT_FIELD: &str = "tenant";
+
+/// 链路追踪 ID 字段
+pub const TRACE_ID_FIELD: &str = "trace_id";
+
+/// 链路追踪跨度 ID 字段
+pub const SPAN_ID_FIELD: &str = "span_id";
+
+/// 任务队列中的任务 ID 字段
+pub const JOB_ID_FIELD: &str = "job_id";
+
+tokio::task_local! {
+    /// 当前的日志上下文
+    static LOG_CONTEXT: RefCell<BTreeMap<String, String>>;
+}
+
+/// 在新的日志上下文中执行异步任务
+///
+/// 新的上下文继承当前上下文中的字段，并加入 `fields`；执行期间的增删不影响外层上下文
+///
+/// # 示例
+/// ```rust
+/// with_log_context([(TENANT_FIELD, tenant.id.to_string())], async {
+///     tracing::info!("同步租户数据");
+///     sync_tenant(&tenant).await
+/// })
+/// .await
+/// ```
+pub async fn with_log_context<I, K, V, F>(fields: I, future: F) -> F::Output
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: Into<String>,
+    V: Into<String>,
+    F: Future,
+{
+    let mut context = log_context();
+    context.extend(
+        fields
+            .into_iter()
+            .map(|(key, value)| (key.into(), value.into())),
+    );
+    LOG_CONTEXT.scope(RefCell::new(context), future).await
+}
+
+/// 向当前日志上下文加入字段，已有同名字段时覆盖
+///
+/// 不在日志上下文中时返回 `false`，字段不会被记录
+pub fn push_log_field(key: impl Into<String>, value: impl Into<String>) -> bool {
+    LOG_CONTEXT
+        .try_with(|context| {
+            context.borrow_mut().insert(key.into(), value.into());
+        })
+        .is_ok()
+}
+
+/// 从当前日志上下文移除字段，返回移除的值
+pub fn pop_log_field(key: &str) -> Option<String> {
+    LOG_CONTEXT
+        .try_with(|context| context.borrow_mut().remove(key))
+        .ok()
+        .flatten()
+}
+
+/// 获取当前日志上下文中的所有字段
+///
+/// 不在日志上下文中时返回空集合
+pub fn log_context() -> BTreeMap<String, String> {
+    LOG_CONTEXT
+        .try_with(|context| context.borrow().clone())
+        .unwrap_or_default()
+}
+
+/// 附加日志上下文字段的事件格式
+///
+/// 包装 tracing-subscriber 的事件格式，上下文为空时原样输出。
+/// JSON 格式还可以附加固定字段
+#[derive(Debug, Clone)]
+pub struct ContextFormat<F> {
+    /// 被包装的事件格式
+    inner: F,
+    /// 被包装的格式是否输出 JSON
+    json: bool,
+    /// 序列化后的固定字段，如 `"env":"prod",`
+    static_fields: String,
+}
+
+impl<F> ContextFormat<F> {
+    /// 包装事件格式
+    pub fn new(inner: F, json: bool) -> Self {
+        Self {
+            inner,
+            json,
+            static_fields: String::new(),
+        }
+    }
+
+    /// 设置 JSON 格式附加的固定字段
+    pub fn with_static_fields(mut self, fields: &BTreeMap<String, String>) -> Self {
+        self.static_fields = fields
+            .iter()
+            .map(|(key, value)| {
+                format!(
+                    "{}:{},",
+                    Value::from(key.as_str()),
+                    Value::from(value.as_str())
+                )
+            })
+            .collect();
+        self
+    }
+}
+
+impl<S, N, F> FormatEvent<S, N> for ContextFormat<F>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+    F: FormatEvent<S, N>,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let context = log_context();
+        if context.is_empty() && (!self.json || self.static_fields.is_empty()) {
+            return self.inner.format_event(ctx, writer, event);
+        }
+
+        if !self.json {
+            let fields: Vec<String> = context
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect();
+            write!(writer, "[{}] ", fields.join(" "))?;
+            return self.inner.format_event(ctx, writer, event);
+        }
+
+        // 在 JSON 对象的开头插入固定字段和 `context` 字段
+        let mut line = String::new();
+        self.inner
+            .format_event(ctx, Writer::new(&mut line), event)?;
+        let Some(rest) = line.strip_prefix('{') else {
+            return writer.write_str(&line);
+        };
+        let mut prefix = format!("{{{}", self.static_fields);
+        if !context.is_empty() {
+            let context = serde_json::to_string(&context).map_err(|_| fmt::Error)?;
+            prefix.push_str(&format!("\"context\":{},", context));
+        }
+        if rest.starts_with('}') {
+            prefix.pop();
+        }
+        write!(writer, "{}{}", prefix, rest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::fmt::MakeWriter;
+
+    /// 收集日志输出的写入器
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Captured {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    /// 测试上下文字段的继承、增删和 JSON 输出
+    #[tokio::test]
+    async fn test_log_context() {
+        let captured = Captured::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(captured.clone())
+            .json()
+            .map_event_format(|inner| {
+                ContextFormat::new(inner, true)
+                    .with_static_fields(&BTreeMap::from([("env".to_string(), "prod".to_string())]))
+            })
+            .finish();
+        let _default = tracing::subscriber::set_default(subscriber);
+
+        assert!(!push_log_field(TENANT_FIELD, "acme"));
+        with_log_context([(REQUEST_ID_FIELD, "req-1")], async {
+            assert!(push_log_field(USER_ID_FIELD, "alice"));
+            with_log_context([(TENANT_FIELD, "acme")], async {
+                tracing::info!("inner");
+            })
+            .await;
+            assert_eq!(pop_log_field(USER_ID_FIELD).as_deref(), Some("alice"));
+            assert_eq!(log_context().len(), 1);
+        })
+        .await;
+        tracing::info!("outside");
+
+        let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(
+            lines[0]["context"],
+            serde_json::json!({ "request_id": "req-1", "tenant": "acme", "user_id": "alice" })
+        );
+        assert_eq!(lines[0]["fields"]["message"], "inner");
+        assert!(lines[1].get("context").is_none());
+        assert_eq!(lines[1]["env"], "prod");
+    }
+}
@@ -0,0 +1,221 @@
+//! journald 输出模块
+//!
+//! `logging.output = "journald"` 时日志以 journald 原生协议发送到 `/run/systemd/journal/socket`，
+//! 每条日志带有以下字段，可以通过 `journalctl -t order-service -p warning` 等条件查询：
+//! - `MESSAGE`、`PRIORITY`、`SYSLOG_IDENTIFIER`、`SYSLOG_FACILITY` 和 `SYSLOG_PID`
+//! - `TARGET` 为日志目标，`CODE_FILE` 和 `CODE_LINE` 为输出日志的代码位置
+//!
+//! 标识和设施使用 `[logging.syslog]` 中的 `app_name` 和 `facility`，发送失败的日志被丢弃
+
+use std::io::{self, Write};
+use std::os::unix::net::UnixDatagram;
+use std::path::Path;
+use std::sync::Arc;
+
+use tracing::{Level, Metadata};
+use tracing_subscriber::fmt::MakeWriter;
+
+use crate::config::SyslogConfig;
+use crate::error::{Error, Result};
+use crate::logging::syslog::{app_name, severity};
+
+/// journald 原生协议的套接字
+pub const JOURNALD_SOCKET: &str = "/run/systemd/journal/socket";
+
+/// journald 写入器的共享状态
+#[derive(Debug)]
+struct Inner {
+    /// 到 journald 的套接字
+    socket: UnixDatagram,
+    /// 设施代码
+    facility: u8,
+    /// 日志标识
+    identifier: String,
+    /// 进程 ID
+    pid: u32,
+}
+
+/// journald 写入器，每条日志作为一条日志条目发送
+///
+/// # 示例
+/// ```rust
+/// let writer = JournaldWriter::connect(&config.syslog)?;
+/// tracing_subscriber::fmt().with_writer(writer).with_ansi(false).without_time().init();
+/// ```
+#[derive(Debug, Clone)]
+pub struct JournaldWriter {
+    /// 共享状态
+    inner: Arc<Inner>,
+}
+
+impl JournaldWriter {
+    /// 连接本机的 journald
+    ///
+    /// # 错误
+    /// journald 未运行时返回错误
+    pub fn connect(config: &SyslogConfig) -> Result<Self> {
+        Self::connect_to(JOURNALD_SOCKET, config)
+    }
+
+    /// 连接指定套接字上的 journald
+    ///
+    /// # 错误
+    /// 连接失败时返回错误
+    pub fn connect_to(path: impl AsRef<Path>, config: &SyslogConfig) -> Result<Self> {
+        let path = path.as_ref();
+        let failed = |e: io::Error| {
+            Error::application(format!("连接 journald 失败 ({}): {}", path.display(), e))
+        };
+        let socket = UnixDatagram::unbound().map_err(failed)?;
+        socket.connect(path).map_err(failed)?;
+        Ok(Self {
+            inner: Arc::new(Inner {
+                socket,
+                facility: config.facility.code(),
+                identifier: app_name(config),
+                pid: std::process::id(),
+            }),
+        })
+    }
+}
+
+impl<'a> MakeWriter<'a> for JournaldWriter {
+    type Writer = JournaldEntry<'a>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        JournaldEntry {
+            writer: self,
+            priority: severity(&Level::INFO),
+            target: None,
+            file: None,
+            line: None,
+            buffer: Vec::new(),
+        }
+    }
+
+    fn make_writer_for(&'a self, meta: &Metadata<'_>) -> Self::Writer {
+        JournaldEntry {
+            writer: self,
+            priority: severity(meta.level()),
+            target: Some(meta.target().to_string()),
+            file: meta.file().map(str::to_string),
+            line: meta.line(),
+            buffer: Vec::new(),
+        }
+    }
+}
+
+/// 一条 journald 日志条目，写入的内容作为 `MESSAGE` 在丢弃时发送
+#[derive(Debug)]
+pub struct JournaldEntry<'a> {
+    /// 所属写入器
+    writer: &'a JournaldWriter,
+    /// 严重级别
+    priority: u8,
+    /// 日志目标
+    target: Option<String>,
+    /// 代码文件
+    file: Option<String>,
+    /// 代码行号
+    line: Option<u32>,
+    /// 已写入的内容
+    buffer: Vec<u8>,
+}
+
+impl Write for JournaldEntry<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for JournaldEntry<'_> {
+    fn drop(&mut self) {
+        let message = String::from_utf8_lossy(&self.buffer);
+        let message = message.trim_end();
+        if message.is_empty() {
+            return;
+        }
+        let inner = &self.writer.inner;
+        let mut entry = Vec::with_capacity(message.len() + 256);
+        put_field(&mut entry, "MESSAGE", message);
+        put_field(&mut entry, "PRIORITY", &self.priority.to_string());
+        put_field(&mut entry, "SYSLOG_IDENTIFIER", &inner.identifier);
+        put_field(&mut entry, "SYSLOG_FACILITY", &inner.facility.to_string());
+        put_field(&mut entry, "SYSLOG_PID", &inner.pid.to_string());
+        if let Some(target) = &self.target {
+            put_field(&mut entry, "TARGET", target);
+        }
+        if let Some(file) = &self.file {
+            put_field(&mut entry, "CODE_FILE", file);
+        }
+        if let Some(line) = self.line {
+            put_field(&mut entry, "CODE_LINE", &line.to_string());
+        }
+        let _ = inner.socket.send(&entry);
+    }
+}
+
+/// 按原生协议写入一个字段，包含换行的值使用长度前缀的二进制格式
+fn put_field(entry: &mut Vec<u8>, name: &str, value: &str) {
+    entry.extend_from_slice(name.as_bytes());
+    if value.contains('\n') {
+        entry.push(b'\n');
+        entry.extend_from_slice(&(value.len() as u64).to_le_bytes());
+    } else {
+        entry.push(b'=');
+    }
+    entry.extend_from_slice(value.as_bytes());
+    entry.push(b'\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SyslogFacility;
+    use std::time::Duration;
+
+    /// 测试日志条目的字段，多行消息使用二进制格式
+    #[test]
+    fn test_journald_writer() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("journal.sock");
+        let server = UnixDatagram::bind(&path).unwrap();
+        server
+            .set_read_timeout(Some(Duration::from_secs(2)))
+            .unwrap();
+        let config = SyslogConfig {
+            facility: SyslogFacility::Daemon,
+            app_name: Some("orders".to_string()),
+            address: None,
+        };
+        let writer = JournaldWriter::connect_to(&path, &config).unwrap();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(writer)
+            .with_ansi(false)
+            .without_time()
+            .with_level(false)
+            .with_target(false)
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::error!("同步失败\n第 2 行");
+        });
+
+        let mut buffer = vec![0u8; 4096];
+        let size = server.recv(&mut buffer).unwrap();
+        let entry = &buffer[..size];
+        let message = "同步失败\n第 2 行";
+        let mut expected = b"MESSAGE\n".to_vec();
+        expected.extend_from_slice(&(message.len() as u64).to_le_bytes());
+        expected.extend_from_slice(message.as_bytes());
+        expected.extend_from_slice(b"\nPRIORITY=3\nSYSLOG_IDENTIFIER=orders\nSYSLOG_FACILITY=3\n");
+        assert!(entry.starts_with(&expected));
+        let text = String::from_utf8_lossy(entry);
+        assert!(text.contains("\nTARGET=rspring_core::logging::journald::tests\n"));
+        assert!(text.contains("\nCODE_FILE="));
+    }
+}
@@ -0,0 +1,289 @@
+//! JSON 日志格式模块
+//!
+//! 按 `[logging.json]` 的 `schema` 输出 JSON 日志，每条日志一行：
+//! - `ecs` 按 Elastic Common Schema 输出，日志上下文中的请求 ID、用户 ID、链路追踪 ID 和跨度 ID
+//!   映射为 `http.request.id`、`user.id`、`trace.id` 和 `span.id`，其他上下文字段输出到 `labels.*`
+//! - `custom` 按 `[logging.json.mapping]` 中的字段名输出
+//!
+//! `[logging.json.fields]` 中的固定字段附加到每一条日志；`default` 结构由
+//! [`ContextFormat`](crate::logging::ContextFormat) 在 tracing-subscriber 的 JSON 格式上附加
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::{SecondsFormat, Utc};
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::config::{JsonFieldMapping, JsonLogConfig, JsonLogSchema};
+use crate::logging::context::{log_context, REQUEST_ID_FIELD, SPAN_ID_FIELD, TRACE_ID_FIELD, USER_ID_FIELD};
+
+/// 输出的 ECS 版本
+pub const ECS_VERSION: &str = "8.11.0";
+
+/// 按配置的字段结构输出 JSON 日志的事件格式
+#[derive(Debug, Clone)]
+pub struct JsonFormat {
+    /// 字段结构
+    schema: JsonLogSchema,
+    /// 固定字段
+    fields: BTreeMap<String, String>,
+    /// 自定义结构的字段名
+    mapping: JsonFieldMapping,
+}
+
+impl JsonFormat {
+    /// 按配置创建事件格式
+    pub fn new(config: &JsonLogConfig) -> Self {
+        Self {
+            schema: config.schema,
+            fields: config.fields.clone(),
+            mapping: config.mapping.clone(),
+        }
+    }
+
+    /// 按 Elastic Common Schema 组装日志
+    fn ecs(
+        &self,
+        event: &Event<'_>,
+        message: Option<Value>,
+        fields: Map<String, Value>,
+    ) -> Map<String, Value> {
+        let metadata = event.metadata();
+        let mut object = Map::new();
+        object.insert("@timestamp".to_string(), Value::from(timestamp()));
+        object.insert(
+            "log.level".to_string(),
+            Value::from(metadata.level().as_str().to_lowercase()),
+        );
+        object.insert("message".to_string(), message.unwrap_or_default());
+        object.insert("log.logger".to_string(), Value::from(metadata.target()));
+        object.insert("ecs.version".to_string(), Value::from(ECS_VERSION));
+        if let Some(file) = metadata.file() {
+            object.insert("log.origin.file.name".to_string(), Value::from(file));
+        }
+        if let Some(line) = metadata.line() {
+            object.insert("log.origin.file.line".to_string(), Value::from(line));
+        }
+
+        for (key, value) in log_context() {
+            let key = match key.as_str() {
+                REQUEST_ID_FIELD => "http.request.id".to_string(),
+                USER_ID_FIELD => "user.id".to_string(),
+                TRACE_ID_FIELD => "trace.id".to_string(),
+                SPAN_ID_FIELD => "span.id".to_string(),
+                _ => format!("labels.{}", key),
+            };
+            object.entry(key).or_insert(Value::from(value));
+        }
+        self.extend_static(&mut object);
+        for (key, value) in fields {
+            object.entry(key).or_insert(value);
+        }
+        object
+    }
+
+    /// 按自定义字段名组装日志
+    fn custom(
+        &self,
+        event: &Event<'_>,
+        message: Option<Value>,
+        fields: Map<String, Value>,
+    ) -> Map<String, Value> {
+        let metadata = event.metadata();
+        let mapping = &self.mapping;
+        let mut object = Map::new();
+        let mut insert = |name: &str, value: Value| {
+            if !name.is_empty() {
+                object.insert(name.to_string(), value);
+            }
+        };
+        insert(&mapping.timestamp, Value::from(timestamp()));
+        insert(&mapping.level, Value::from(metadata.level().as_str()));
+        insert(&mapping.message, message.unwrap_or_default());
+        insert(&mapping.target, Value::from(metadata.target()));
+
+        let context: Map<String, Value> = log_context()
+            .into_iter()
+            .map(|(key, value)| (key, Value::from(value)))
+            .collect();
+        nest_or_flatten(&mut object, &mapping.context, context);
+        self.extend_static(&mut object);
+        nest_or_flatten(&mut object, &mapping.fields, fields);
+        object
+    }
+
+    /// 加入固定字段，不覆盖已有的字段
+    fn extend_static(&self, object: &mut Map<String, Value>) {
+        for (key, value) in &self.fields {
+            object
+                .entry(key.clone())
+                .or_insert(Value::from(value.as_str()));
+        }
+    }
+}
+
+impl<S, N> FormatEvent<S, N> for JsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        _ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let mut visitor = JsonVisitor::default();
+        event.record(&mut visitor);
+        let mut fields = visitor.0;
+        let message = fields.remove("message");
+
+        let object = match self.schema {
+            JsonLogSchema::Ecs => self.ecs(event, message, fields),
+            JsonLogSchema::Default | JsonLogSchema::Custom => self.custom(event, message, fields),
+        };
+        let line = serde_json::to_string(&object).map_err(|_| fmt::Error)?;
+        writeln!(writer, "{}", line)
+    }
+}
+
+/// 将字段放入 `name` 对象，`name` 为空时展开到顶层，不覆盖已有的字段
+fn nest_or_flatten(object: &mut Map<String, Value>, name: &str, fields: Map<String, Value>) {
+    if fields.is_empty() {
+        return;
+    }
+    if name.is_empty() {
+        for (key, value) in fields {
+            object.entry(key).or_insert(value);
+        }
+    } else {
+        object.insert(name.to_string(), Value::Object(fields));
+    }
+}
+
+/// 当前 UTC 时间，精确到毫秒
+fn timestamp() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// 收集事件字段的访问器
+#[derive(Default)]
+struct JsonVisitor(Map<String, Value>);
+
+impl Visit for JsonVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn std::error::Error + 'static)) {
+        self.0
+            .insert(field.name().to_string(), Value::from(value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(
+            field.name().to_string(),
+            Value::from(format!("{:?}", value)),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logging::context::{with_log_context, TENANT_FIELD};
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    /// 按配置输出一条日志并解析
+    async fn log_with(config: serde_json::Value) -> Value {
+        let config: JsonLogConfig = serde_json::from_value(config).unwrap();
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let writer = output.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || Captured(writer.clone()))
+            .event_format(JsonFormat::new(&config))
+            .finish();
+        let _default = tracing::subscriber::set_default(subscriber);
+
+        let context = [(REQUEST_ID_FIELD, "req-1"), (TENANT_FIELD, "acme")];
+        with_log_context(context, async {
+            tracing::info!(order_id = 42, "订单已创建");
+        })
+        .await;
+        let output = output.lock().unwrap().clone();
+        serde_json::from_slice(&output).unwrap()
+    }
+
+    /// 收集日志输出的写入器
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// 测试 ECS 和自定义字段结构
+    #[tokio::test]
+    async fn test_json_schema() {
+        let log = log_with(serde_json::json!({
+            "schema": "ecs",
+            "fields": { "service.environment": "prod" },
+        }))
+        .await;
+        assert_eq!(log["log.level"], "info");
+        assert_eq!(log["message"], "订单已创建");
+        assert_eq!(log["ecs.version"], ECS_VERSION);
+        assert_eq!(log["http.request.id"], "req-1");
+        assert_eq!(log["labels.tenant"], "acme");
+        assert_eq!(log["service.environment"], "prod");
+        assert_eq!(log["order_id"], 42);
+        assert!(log["@timestamp"].as_str().unwrap().ends_with('Z'));
+
+        let log = log_with(serde_json::json!({
+            "schema": "custom",
+            "fields": { "region": "eu-west-1" },
+            "mapping": { "timestamp": "", "level": "severity", "message": "msg", "context": "" },
+        }))
+        .await;
+        assert_eq!(
+            log,
+            serde_json::json!({
+                "severity": "INFO",
+                "msg": "订单已创建",
+                "target": module_path!(),
+                "request_id": "req-1",
+                "tenant": "acme",
+                "region": "eu-west-1",
+                "fields": { "order_id": 42 },
+            })
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! 日志级别模块
+//!
+//! 管理根级别和按模块的日志级别：
+//! - 启动时由 `[logging]` 的 `level` 和 `[logging.levels]` 生成过滤指令，设置了 `RUST_LOG` 时以它代替 `level`
+//! - 运行时通过 [`set_log_level`] 和 [`clear_log_level`] 修改级别，立即生效，不需要重启
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+use crate::config::LoggingConfig;
+use crate::error::{Error, Result};
+
+/// 表示根级别的目标名称
+pub const ROOT_LOGGER: &str = "root";
+
+/// 当前的日志级别
+static LEVELS: Lazy<Mutex<LevelState>> = Lazy::new(|| Mutex::new(LevelState::default()));
+
+/// 日志级别状态
+#[derive(Default)]
+struct LevelState {
+    /// 根级别的过滤指令
+    root: String,
+    /// 按模块的级别
+    levels: BTreeMap<String, String>,
+    /// 已安装的过滤器的重载句柄
+    handle: Option<reload::Handle<EnvFilter, Registry>>,
+}
+
+/// 按配置创建可在运行时重载的过滤器
+///
+/// # 错误
+/// 级别或模块名称无效时返回验证错误
+pub(crate) fn reloadable_filter(config: &LoggingConfig) -> Result<reload::Layer<EnvFilter, Registry>> {
+    let root = std::env::var(EnvFilter::DEFAULT_ENV)
+        .ok()
+        .filter(|directives| !directives.trim().is_empty())
+        .unwrap_or_else(|| config.level.clone());
+    let levels = config
+        .levels
+        .iter()
+        .map(|(target, level)| {
+            validate_target(target)?;
+            Ok((target.clone(), parse_level(level)?))
+        })
+        .collect::<Result<BTreeMap<_, _>>>()?;
+
+    let (filter, handle) = reload::Layer::new(build_filter(&root, &levels)?);
+    *LEVELS.lock().expect("日志级别锁已损坏") = LevelState {
+        root,
+        levels,
+        handle: Some(handle),
+    };
+    Ok(filter)
+}
+
+/// 获取当前的日志级别，根级别的键为 [`ROOT_LOGGER`]
+pub fn log_levels() -> BTreeMap<String, String> {
+    let state = LEVELS.lock().expect("日志级别锁已损坏");
+    let mut levels = state.levels.clone();
+    levels.insert(ROOT_LOGGER.to_string(), state.root.clone());
+    levels
+}
+
+/// 设置模块的日志级别，目标为 [`ROOT_LOGGER`] 时设置根级别
+///
+/// # 示例
+/// ```rust
+/// set_log_level("my_app::repository", "debug")?;
+/// ```
+///
+/// # 错误
+/// 级别或模块名称无效时返回验证错误
+pub fn set_log_level(target: &str, level: &str) -> Result<()> {
+    validate_target(target)?;
+    let level = parse_level(level)?;
+    update(|state| {
+        if target == ROOT_LOGGER {
+            state.root = level;
+        } else {
+            state.levels.insert(target.to_string(), level);
+        }
+    })
+}
+
+/// 清除模块的日志级别，之后该模块使用根级别
+///
+/// # 错误
+/// 目标为 [`ROOT_LOGGER`] 或模块名称无效时返回验证错误
+pub fn clear_log_level(target: &str) -> Result<()> {
+    if target == ROOT_LOGGER {
+        return Err(Error::validation("不能清除根日志级别"));
+    }
+    validate_target(target)?;
+    update(|state| {
+        state.levels.remove(target);
+    })
+}
+
+/// 修改日志级别并重载过滤器，新的过滤指令无效时不做修改
+fn update(change: impl FnOnce(&mut LevelState)) -> Result<()> {
+    let mut state = LEVELS.lock().expect("日志级别锁已损坏");
+    let mut next = LevelState {
+        root: state.root.clone(),
+        levels: state.levels.clone(),
+        handle: None,
+    };
+    change(&mut next);
+
+    let filter = build_filter(&next.root, &next.levels)?;
+    if let Some(handle) = &state.handle {
+        handle
+            .reload(filter)
+            .map_err(|e| Error::internal(format!("重载日志过滤器失败: {}", e)))?;
+    }
+    state.root = next.root;
+    state.levels = next.levels;
+    Ok(())
+}
+
+/// 由根级别和按模块的级别生成过滤器
+fn build_filter(root: &str, levels: &BTreeMap<String, String>) -> Result<EnvFilter> {
+    let directives = std::iter::once(root.to_string())
+        .chain(levels.iter().map(|(target, level)| format!("{}={}", target, level)))
+        .collect::<Vec<_>>()
+        .join(",");
+    EnvFilter::builder()
+        .parse(&directives)
+        .map_err(|e| Error::validation(format!("无效的日志级别 ({}): {}", directives, e)))
+}
+
+/// 校验模块名称，只允许由字母、数字、下划线组成并以 `::` 分隔的模块路径，
+/// 避免名称中的 `,`、`=`、`[` 等字符注入额外的过滤指令
+fn validate_target(target: &str) -> Result<()> {
+    let valid = target
+        .split("::")
+        .all(|segment| !segment.is_empty() && segment.chars().all(|ch| ch.is_alphanumeric() || ch == '_'));
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::validation(format!("无效的模块名称: {}", target)))
+    }
+}
+
+/// 校验日志级别并转为小写
+fn parse_level(level: &str) -> Result<String> {
+    LevelFilter::from_str(level)
+        .map(|level| level.to_string().to_lowercase())
+        .map_err(|_| Error::validation(format!("无效的日志级别: {}", level)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试按模块设置和清除级别
+    #[test]
+    fn test_log_levels() {
+        let config: LoggingConfig = serde_json::from_value(serde_json::json!({
+            "level": "warn",
+            "levels": { "my_app::repo": "DEBUG" },
+        }))
+        .unwrap();
+        let _filter = reloadable_filter(&config).unwrap();
+        let root = log_levels()[ROOT_LOGGER].clone();
+        assert_eq!(log_levels()["my_app::repo"], "debug");
+
+        set_log_level("my_app::web", "trace").unwrap();
+        clear_log_level("my_app::repo").unwrap();
+        assert_eq!(
+            log_levels(),
+            BTreeMap::from([
+                ("my_app::web".to_string(), "trace".to_string()),
+                (ROOT_LOGGER.to_string(), root),
+            ])
+        );
+
+        assert!(set_log_level("my_app::web", "verbose").is_err());
+        assert!(set_log_level("my_app=web", "info").is_err());
+        assert!(set_log_level("my_app,hyper=trace", "info").is_err());
+        assert!(set_log_level("my_app[request]", "info").is_err());
+        assert!(set_log_level("my_app:::web", "info").is_err());
+        assert!(clear_log_level("my_app,hyper").is_err());
+        assert!(clear_log_level(ROOT_LOGGER).is_err());
+        assert_eq!(log_levels()["my_app::web"], "trace");
+    }
+}
@@ -0,0 +1,224 @@
+//! 非阻塞日志写入模块
+//!
+//! 日志行通过有界通道交给后台线程写入，记录日志的线程不会因文件 IO 阻塞。
+//! 通道已满或写入失败时丢弃日志行并计数，后台线程在下一次写入前补写一条丢弃提示
+
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use tracing_subscriber::fmt::MakeWriter;
+
+use crate::error::{Error, Result};
+
+/// 通道中最多缓存的日志行数
+const BUFFERED_LINES: usize = 128_000;
+
+/// 发送给后台线程的消息
+enum Message {
+    /// 一条日志
+    Line(Vec<u8>),
+    /// 写完已缓存的日志后退出
+    Shutdown,
+}
+
+/// 非阻塞写入器，可以克隆后在多个线程中使用
+#[derive(Debug, Clone)]
+pub struct NonBlocking {
+    /// 发送日志行的通道
+    sender: SyncSender<Message>,
+    /// 因通道已满或写入失败被丢弃的日志行数
+    dropped: Arc<AtomicU64>,
+}
+
+/// 后台写入线程的守卫
+///
+/// 丢弃时写完已缓存的日志并等待后台线程退出，应在应用退出前保持存活
+#[derive(Debug)]
+pub struct WorkerGuard {
+    /// 发送退出消息的通道
+    sender: SyncSender<Message>,
+    /// 后台写入线程
+    worker: Option<JoinHandle<()>>,
+}
+
+/// 创建写入 `writer` 的非阻塞写入器
+///
+/// # 错误
+/// 后台写入线程无法启动时返回错误
+///
+/// # 示例
+/// ```rust
+/// let file = RollingFile::open("logs/app.log", 100 * 1024 * 1024, 7)?;
+/// let (writer, guard) = non_blocking(file)?;
+/// tracing_subscriber::fmt().with_writer(writer).init();
+/// ```
+pub fn non_blocking<W>(writer: W) -> Result<(NonBlocking, WorkerGuard)>
+where
+    W: Write + Send + 'static,
+{
+    let (sender, receiver) = mpsc::sync_channel(BUFFERED_LINES);
+    let dropped = Arc::new(AtomicU64::new(0));
+    let worker = {
+        let dropped = dropped.clone();
+        thread::Builder::new()
+            .name("rspring-log-writer".to_string())
+            .spawn(move || run_worker(writer, receiver, dropped))
+            .map_err(|e| Error::application(format!("启动日志写入线程失败: {}", e)))?
+    };
+
+    Ok((
+        NonBlocking {
+            sender: sender.clone(),
+            dropped,
+        },
+        WorkerGuard {
+            sender,
+            worker: Some(worker),
+        },
+    ))
+}
+
+/// 后台线程：依次写入收到的日志行
+fn run_worker<W: Write>(mut writer: W, receiver: Receiver<Message>, dropped: Arc<AtomicU64>) {
+    while let Ok(Message::Line(line)) = receiver.recv() {
+        let count = dropped.swap(0, Ordering::Relaxed);
+        if count > 0 && writeln!(writer, "日志写入过慢或写入失败，已丢弃 {} 条日志", count).is_err()
+        {
+            dropped.fetch_add(count, Ordering::Relaxed);
+        }
+        // 写入失败的日志行计入丢弃数，写入恢复后补写丢弃提示
+        if writer.write_all(&line).is_err() {
+            dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+    let _ = writer.flush();
+}
+
+impl Write for NonBlocking {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.sender.try_send(Message::Line(buf.to_vec())) {
+            Ok(()) => Ok(buf.len()),
+            Err(TrySendError::Full(_)) => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+                Ok(buf.len())
+            }
+            Err(TrySendError::Disconnected(_)) => Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "日志写入线程已退出",
+            )),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for NonBlocking {
+    type Writer = NonBlocking;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+impl Drop for WorkerGuard {
+    fn drop(&mut self) {
+        // 阻塞发送，保证退出消息排在已缓存的日志之后
+        let _ = self.sender.send(Message::Shutdown);
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// 共享缓冲区，用于检查后台线程写入的内容
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// 测试守卫丢弃时写完所有缓存的日志
+    #[test]
+    fn test_non_blocking() {
+        let buffer = SharedBuffer::default();
+        let (writer, guard) = non_blocking(buffer.clone()).unwrap();
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let mut writer = writer.make_writer();
+                thread::spawn(move || {
+                    writer
+                        .write_all(format!("line {}\n", i).as_bytes())
+                        .unwrap()
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        drop(guard);
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(output.lines().count(), 4);
+        assert!(output.contains("line 3\n"));
+        assert!(writer.make_writer().write_all(b"late\n").is_err());
+    }
+
+    /// 测试写入失败的日志计入丢弃数，写入恢复后补写丢弃提示
+    #[test]
+    fn test_failed_write() {
+        /// 前两次写入失败的缓冲区
+        struct FlakyBuffer {
+            failures: usize,
+            buffer: SharedBuffer,
+        }
+
+        impl Write for FlakyBuffer {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                if self.failures > 0 {
+                    self.failures -= 1;
+                    return Err(io::Error::other("disk full"));
+                }
+                self.buffer.write(buf)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let buffer = SharedBuffer::default();
+        let flaky = FlakyBuffer {
+            failures: 2,
+            buffer: buffer.clone(),
+        };
+        let (writer, guard) = non_blocking(flaky).unwrap();
+        for line in ["first\n", "second\n", "third\n"] {
+            writer.make_writer().write_all(line.as_bytes()).unwrap();
+        }
+        drop(guard);
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(
+            output,
+            "second\n日志写入过慢或写入失败，已丢弃 1 条日志\nthird\n"
+        );
+    }
+}
@@ -0,0 +1,201 @@
+//! 滚动日志文件模块
+//!
+//! 按文件大小滚动：当前文件超过上限时依次重命名为 `<文件名>.1`、`<文件名>.2`……，
+//! 超出保留数量的旧文件被删除。设置滚动周期后，每小时或每天开始时也会滚动
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, DurationRound, Local, TimeZone};
+
+use crate::config::LogRotation;
+use crate::error::{Error, Result};
+
+/// 按大小滚动的日志文件
+///
+/// # 示例
+/// ```rust
+/// let mut file = RollingFile::open("logs/access.log", 100 * 1024 * 1024, 7)?;
+/// writeln!(file, "127.0.0.1 - - [10/Oct/2024:13:55:36 +0800] \"GET / HTTP/1.1\" 200 12")?;
+/// ```
+#[derive(Debug)]
+pub struct RollingFile {
+    /// 当前日志文件路径
+    path: PathBuf,
+    /// 单个文件的最大字节数
+    max_size: u64,
+    /// 保留的历史文件数量
+    max_files: u32,
+    /// 当前文件
+    file: File,
+    /// 当前文件已写入的字节数
+    size: u64,
+    /// 按时间滚动的周期
+    rotation: LogRotation,
+    /// 下一次按时间滚动的时间
+    next_rollover: Option<DateTime<Local>>,
+}
+
+impl RollingFile {
+    /// 打开日志文件，不存在时创建（包括上级目录），存在时追加写入
+    ///
+    /// # 参数
+    /// * `path` - 日志文件路径
+    /// * `max_size` - 单个文件的最大字节数
+    /// * `max_files` - 保留的历史文件数量，为 0 时滚动后直接丢弃旧内容
+    ///
+    /// # 错误
+    /// 文件或目录无法创建时返回错误
+    pub fn open(path: impl AsRef<Path>, max_size: u64, max_files: u32) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).map_err(|e| {
+                Error::application(format!("创建日志目录失败 ({}): {}", parent.display(), e))
+            })?;
+        }
+
+        let file = open_append(&path).map_err(|e| {
+            Error::application(format!("打开日志文件失败 ({}): {}", path.display(), e))
+        })?;
+        let size = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+
+        Ok(Self {
+            path,
+            max_size,
+            max_files,
+            file,
+            size,
+            rotation: LogRotation::Size,
+            next_rollover: None,
+        })
+    }
+
+    /// 设置按时间滚动的周期
+    ///
+    /// 已有的日志文件按最后修改时间计算下一次滚动的时间，
+    /// 因此重启后写入的第一条日志会先滚动前一个周期的文件
+    pub fn with_rotation(mut self, rotation: LogRotation) -> Self {
+        let modified = self
+            .file
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .filter(|_| self.size > 0)
+            .map(DateTime::<Local>::from)
+            .unwrap_or_else(Local::now);
+        self.rotation = rotation;
+        self.next_rollover = next_rollover(rotation, modified);
+        self
+    }
+
+    /// 获取当前日志文件路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 滚动日志文件
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+
+        if self.max_files == 0 {
+            fs::remove_file(&self.path)?;
+        } else {
+            let _ = fs::remove_file(self.history(self.max_files));
+            for index in (1..self.max_files).rev() {
+                let from = self.history(index);
+                if from.exists() {
+                    fs::rename(from, self.history(index + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.history(1))?;
+        }
+
+        self.file = open_append(&self.path)?;
+        self.size = 0;
+        self.next_rollover = next_rollover(self.rotation, Local::now());
+        Ok(())
+    }
+
+    /// 第 `index` 个历史文件的路径
+    fn history(&self, index: u32) -> PathBuf {
+        let mut name = self.path.as_os_str().to_os_string();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+}
+
+impl Write for RollingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // 不拆分单次写入，保证一条日志完整地落在同一个文件中
+        let expired = self.next_rollover.is_some_and(|at| Local::now() >= at);
+        if self.size > 0 && (expired || self.size + buf.len() as u64 > self.max_size) {
+            self.rotate()?;
+        }
+
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// 计算 `from` 之后下一个周期开始的时间，只按大小滚动时返回 `None`
+fn next_rollover(rotation: LogRotation, from: DateTime<Local>) -> Option<DateTime<Local>> {
+    let next = match rotation {
+        LogRotation::Size => return None,
+        LogRotation::Hourly => from.duration_trunc(Duration::hours(1)).ok()? + Duration::hours(1),
+        LogRotation::Daily => {
+            let midnight = from.date_naive().succ_opt()?.and_hms_opt(0, 0, 0)?;
+            // 夏令时切换导致零点不存在时顺延一小时
+            Local
+                .from_local_datetime(&midnight)
+                .earliest()
+                .or_else(|| Local.from_local_datetime(&(midnight + Duration::hours(1))).earliest())?
+        }
+    };
+    Some(next)
+}
+
+/// 以追加方式打开文件
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试超过大小上限时滚动并只保留指定数量的历史文件
+    #[test]
+    fn test_rolling_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs").join("app.log");
+        let mut file = RollingFile::open(&path, 10, 2).unwrap();
+
+        for line in ["first\n", "second\n", "third\n", "fourth\n"] {
+            file.write_all(line.as_bytes()).unwrap();
+        }
+        file.flush().unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "fourth\n");
+        assert_eq!(fs::read_to_string(file.history(1)).unwrap(), "third\n");
+        assert_eq!(fs::read_to_string(file.history(2)).unwrap(), "second\n");
+        assert!(!file.history(3).exists());
+
+        // 到达滚动时间后，即使未超过大小上限也滚动
+        let mut file = file.with_rotation(LogRotation::Daily);
+        assert!(file.next_rollover.unwrap() > Local::now());
+        file.next_rollover = Some(Local::now() - Duration::seconds(1));
+        file.write_all(b"fifth\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "fifth\n");
+        assert_eq!(fs::read_to_string(file.history(1)).unwrap(), "fourth\n");
+        assert!(file.next_rollover.unwrap() > Local::now());
+    }
+}
@@ -0,0 +1,394 @@
+//! syslog 输出模块
+//!
+//! `logging.output = "syslog"` 时日志发送到 `[logging.syslog]` 配置的 syslog 服务，每条日志一条消息：
+//! - 本地套接字（默认 `/dev/log`）使用 RFC 3164 格式，与 glibc 的 `syslog(3)` 一致
+//! - 远程 UDP、TCP 服务使用 RFC 5424 格式，TCP 按 RFC 6587 以长度前缀分帧
+//! - 日志级别映射为严重级别：ERROR 为 err，WARN 为 warning，INFO 为 info，DEBUG 和 TRACE 为 debug
+//!
+//! 发送失败的日志被丢弃。TCP 连接和写入都有超时，连接断开后在下一条日志时重新连接，
+//! 重新连接失败后的一段时间内不再尝试，直接丢弃日志，避免服务不可用时阻塞记录日志的线程
+
+use std::io::{self, Write};
+use std::net::{TcpStream, ToSocketAddrs, UdpSocket};
+#[cfg(unix)]
+use std::os::unix::net::UnixDatagram;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{Local, SecondsFormat, Utc};
+use tracing::{Level, Metadata};
+use tracing_subscriber::fmt::MakeWriter;
+
+use crate::config::SyslogConfig;
+use crate::error::{Error, Result};
+
+/// 默认的本地 syslog 套接字
+pub const DEFAULT_SYSLOG_SOCKET: &str = "/dev/log";
+
+/// TCP 连接和写入的超时时间
+const TCP_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// TCP 连接失败后再次尝试连接的间隔
+const TCP_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 日志级别对应的 syslog 严重级别
+pub(crate) fn severity(level: &Level) -> u8 {
+    match *level {
+        Level::ERROR => 3,
+        Level::WARN => 4,
+        Level::INFO => 6,
+        _ => 7,
+    }
+}
+
+/// 获取应用名称，未配置时使用当前可执行文件的名称
+pub(crate) fn app_name(config: &SyslogConfig) -> String {
+    config
+        .app_name
+        .clone()
+        .or_else(|| {
+            let exe = std::env::current_exe().ok()?;
+            Some(exe.file_stem()?.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| "rspring".to_string())
+}
+
+/// 当前主机名，获取失败时为 `-`
+pub(crate) fn hostname() -> String {
+    std::fs::read_to_string("/proc/sys/kernel/hostname")
+        .ok()
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// 到 syslog 服务的连接
+#[derive(Debug)]
+enum Transport {
+    /// 本地套接字
+    #[cfg(unix)]
+    Unix(UnixDatagram),
+    /// 远程 UDP 服务
+    Udp(UdpSocket),
+    /// 远程 TCP 服务
+    Tcp {
+        /// 服务地址
+        address: String,
+        /// 当前连接
+        connection: Mutex<TcpConnection>,
+    },
+}
+
+/// TCP 连接状态
+#[derive(Debug, Default)]
+struct TcpConnection {
+    /// 当前连接，断开后为 `None`
+    stream: Option<TcpStream>,
+    /// 连接失败后，在此时间之前不再尝试连接
+    retry_at: Option<Instant>,
+}
+
+/// 连接 TCP 服务，连接和写入都设置超时
+fn connect_tcp(address: &str) -> io::Result<TcpStream> {
+    let mut last_error = None;
+    for remote in address.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&remote, TCP_TIMEOUT) {
+            Ok(stream) => {
+                stream.set_write_timeout(Some(TCP_TIMEOUT))?;
+                return Ok(stream);
+            }
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("无法解析地址: {}", address),
+        )
+    }))
+}
+
+impl Transport {
+    /// 按地址连接 syslog 服务
+    fn connect(address: &str) -> Result<Self> {
+        let failed =
+            |e: io::Error| Error::application(format!("连接 syslog 服务失败 ({}): {}", address, e));
+        if let Some(target) = address.strip_prefix("udp://") {
+            let remote = target
+                .to_socket_addrs()
+                .map_err(failed)?
+                .next()
+                .ok_or_else(|| Error::validation(format!("无法解析 syslog 地址: {}", address)))?;
+            let local = if remote.is_ipv4() {
+                "0.0.0.0:0"
+            } else {
+                "[::]:0"
+            };
+            let socket = UdpSocket::bind(local).map_err(failed)?;
+            socket.connect(remote).map_err(failed)?;
+            return Ok(Self::Udp(socket));
+        }
+        if let Some(target) = address.strip_prefix("tcp://") {
+            let stream = connect_tcp(target).map_err(failed)?;
+            return Ok(Self::Tcp {
+                address: target.to_string(),
+                connection: Mutex::new(TcpConnection {
+                    stream: Some(stream),
+                    retry_at: None,
+                }),
+            });
+        }
+        #[cfg(unix)]
+        {
+            let path = address.strip_prefix("unix://").unwrap_or(address);
+            if path.starts_with('/') {
+                let socket = UnixDatagram::unbound().map_err(failed)?;
+                socket.connect(path).map_err(failed)?;
+                return Ok(Self::Unix(socket));
+            }
+        }
+        Err(Error::validation(format!(
+            "不支持的 syslog 地址: {}",
+            address
+        )))
+    }
+
+    /// 是否为本地套接字
+    fn is_local(&self) -> bool {
+        #[cfg(unix)]
+        if matches!(self, Self::Unix(_)) {
+            return true;
+        }
+        false
+    }
+
+    /// 发送一条消息
+    fn send(&self, message: &[u8]) -> io::Result<()> {
+        match self {
+            #[cfg(unix)]
+            Self::Unix(socket) => socket.send(message).map(|_| ()),
+            Self::Udp(socket) => socket.send(message).map(|_| ()),
+            Self::Tcp {
+                address,
+                connection,
+            } => {
+                let mut connection = connection.lock().expect("syslog 连接锁已损坏");
+                if connection.stream.is_none() {
+                    if connection
+                        .retry_at
+                        .is_some_and(|retry_at| Instant::now() < retry_at)
+                    {
+                        return Err(io::Error::new(
+                            io::ErrorKind::NotConnected,
+                            "syslog 服务不可用",
+                        ));
+                    }
+                    match connect_tcp(address) {
+                        Ok(stream) => {
+                            connection.stream = Some(stream);
+                            connection.retry_at = None;
+                        }
+                        Err(e) => {
+                            connection.retry_at = Some(Instant::now() + TCP_RETRY_INTERVAL);
+                            return Err(e);
+                        }
+                    }
+                }
+                let stream = connection.stream.as_mut().expect("syslog 连接已建立");
+                let framed = [format!("{} ", message.len()).as_bytes(), message].concat();
+                let result = stream.write_all(&framed);
+                if result.is_err() {
+                    connection.stream = None;
+                }
+                result
+            }
+        }
+    }
+}
+
+/// syslog 写入器的共享状态
+#[derive(Debug)]
+struct Inner {
+    /// 到 syslog 服务的连接
+    transport: Transport,
+    /// 设施代码
+    facility: u8,
+    /// 应用名称
+    app_name: String,
+    /// 主机名
+    hostname: String,
+    /// 进程 ID
+    pid: u32,
+}
+
+/// syslog 写入器，每条日志作为一条 syslog 消息发送
+///
+/// # 示例
+/// ```rust
+/// let writer = SyslogWriter::connect(&config.syslog)?;
+/// tracing_subscriber::fmt().with_writer(writer).with_ansi(false).without_time().init();
+/// ```
+#[derive(Debug, Clone)]
+pub struct SyslogWriter {
+    /// 共享状态
+    inner: Arc<Inner>,
+}
+
+impl SyslogWriter {
+    /// 按配置连接 syslog 服务
+    ///
+    /// # 错误
+    /// 地址无效或连接失败时返回错误
+    pub fn connect(config: &SyslogConfig) -> Result<Self> {
+        let address = config.address.as_deref().unwrap_or(DEFAULT_SYSLOG_SOCKET);
+        Ok(Self {
+            inner: Arc::new(Inner {
+                transport: Transport::connect(address)?,
+                facility: config.facility.code(),
+                app_name: app_name(config),
+                hostname: hostname(),
+                pid: std::process::id(),
+            }),
+        })
+    }
+
+    /// 按传输方式格式化一条消息
+    fn format(&self, severity: u8, message: &str) -> String {
+        let inner = &self.inner;
+        let priority = inner.facility * 8 + severity;
+        if inner.transport.is_local() {
+            format!(
+                "<{}>{} {}[{}]: {}",
+                priority,
+                Local::now().format("%b %e %H:%M:%S"),
+                inner.app_name,
+                inner.pid,
+                message
+            )
+        } else {
+            format!(
+                "<{}>1 {} {} {} {} - - {}",
+                priority,
+                Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+                inner.hostname,
+                inner.app_name,
+                inner.pid,
+                message
+            )
+        }
+    }
+}
+
+impl<'a> MakeWriter<'a> for SyslogWriter {
+    type Writer = SyslogMessage<'a>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        SyslogMessage {
+            writer: self,
+            severity: severity(&Level::INFO),
+            buffer: Vec::new(),
+        }
+    }
+
+    fn make_writer_for(&'a self, meta: &Metadata<'_>) -> Self::Writer {
+        SyslogMessage {
+            writer: self,
+            severity: severity(meta.level()),
+            buffer: Vec::new(),
+        }
+    }
+}
+
+/// 一条 syslog 消息，写入的内容在丢弃时发送
+#[derive(Debug)]
+pub struct SyslogMessage<'a> {
+    /// 所属写入器
+    writer: &'a SyslogWriter,
+    /// 严重级别
+    severity: u8,
+    /// 已写入的内容
+    buffer: Vec<u8>,
+}
+
+impl Write for SyslogMessage<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for SyslogMessage<'_> {
+    fn drop(&mut self) {
+        let message = String::from_utf8_lossy(&self.buffer);
+        let message = message.trim_end();
+        if message.is_empty() {
+            return;
+        }
+        let formatted = self.writer.format(self.severity, message);
+        let _ = self.writer.inner.transport.send(formatted.as_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SyslogFacility;
+    use std::time::Duration;
+
+    /// 测试按 UDP 发送 RFC 5424 格式的消息，优先级由设施和日志级别计算
+    #[test]
+    fn test_syslog_writer() {
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        server
+            .set_read_timeout(Some(Duration::from_secs(2)))
+            .unwrap();
+        let config = SyslogConfig {
+            facility: SyslogFacility::Local0,
+            app_name: Some("orders".to_string()),
+            address: Some(format!("udp://{}", server.local_addr().unwrap())),
+        };
+        let writer = SyslogWriter::connect(&config).unwrap();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(writer)
+            .with_ansi(false)
+            .without_time()
+            .with_level(false)
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!("库存不足: {}", 42);
+        });
+
+        let mut buffer = [0u8; 1024];
+        let size = server.recv(&mut buffer).unwrap();
+        let message = String::from_utf8_lossy(&buffer[..size]);
+        // local0 (16) * 8 + warning (4)
+        assert!(message.starts_with("<132>1 "));
+        assert!(message.contains(&format!(" orders {} - - ", std::process::id())));
+        assert!(message.ends_with("库存不足: 42"));
+
+        // TCP 服务断开后重新连接失败，在重试间隔内直接丢弃日志
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let transport = Transport::connect(&format!("tcp://{}", address)).unwrap();
+        drop(listener);
+        if let Transport::Tcp { connection, .. } = &transport {
+            connection.lock().unwrap().stream = None;
+        }
+        assert!(transport.send(b"first").is_err());
+        let start = Instant::now();
+        let error = transport.send(b"second").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotConnected);
+        assert!(start.elapsed() < TCP_TIMEOUT);
+
+        let invalid = SyslogConfig {
+            address: Some("http://logs".to_string()),
+            ..config
+        };
+        assert!(SyslogWriter::connect(&invalid).is_err());
+    }
+}
@@ -0,0 +1,384 @@
+//! 日志限流模块
+//!
+//! 按 `[logging.throttle]` 配置限制高频输出的日志，如循环中反复输出的“处理记录失败”：
+//! - 以输出日志的代码位置计数，同一处代码的日志即使参数不同也视为相似日志
+//! - 一个时间窗口内超过限制的日志被丢弃，该处代码在之后的窗口再次输出日志时，
+//!   先以 `rspring::logging::throttle` 为目标输出一条被抑制数量的汇总
+//! - 规则按目标和级别设置不同的窗口和限制，按顺序使用第一条匹配的规则
+
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tracing::callsite::{Callsite, Identifier};
+use tracing::field::{display, FieldSet, Value};
+use tracing::metadata::Kind;
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::Interest;
+use tracing::{Event, Level, Metadata, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+use crate::config::{LogThrottleConfig, LogThrottleRule};
+use crate::error::{Error, Result};
+
+/// 汇总日志的目标
+pub const THROTTLE_TARGET: &str = "rspring::logging::throttle";
+
+/// 汇总日志的调用点
+struct SummaryCallsite;
+
+impl Callsite for SummaryCallsite {
+    fn set_interest(&self, _interest: Interest) {}
+
+    fn metadata(&self) -> &Metadata<'_> {
+        &SUMMARY_METADATA
+    }
+}
+
+static SUMMARY_CALLSITE: SummaryCallsite = SummaryCallsite;
+
+static SUMMARY_METADATA: Metadata<'static> = Metadata::new(
+    "log throttle summary",
+    THROTTLE_TARGET,
+    Level::WARN,
+    Some(file!()),
+    Some(line!()),
+    Some(module_path!()),
+    FieldSet::new(&["message"], Identifier(&SUMMARY_CALLSITE)),
+    Kind::EVENT,
+);
+
+/// 一条规则的窗口和限制
+#[derive(Debug, Clone, Copy)]
+struct Limit {
+    /// 时间窗口
+    window: Duration,
+    /// 窗口内最多输出的条数，0 表示不限流
+    limit: u32,
+}
+
+/// 解析后的限流规则
+#[derive(Debug)]
+struct Rule {
+    /// 匹配的目标
+    target: Option<String>,
+    /// 匹配的级别
+    level: Option<Level>,
+    /// 窗口和限制
+    limit: Limit,
+}
+
+impl Rule {
+    /// 判断规则是否匹配日志的目标和级别
+    fn matches(&self, metadata: &Metadata<'_>) -> bool {
+        let target_matches = self.target.as_deref().is_none_or(|target| {
+            metadata
+                .target()
+                .strip_prefix(target)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+        });
+        target_matches && self.level.is_none_or(|level| *metadata.level() == level)
+    }
+}
+
+/// 一处代码在当前窗口的计数
+#[derive(Debug)]
+struct Window {
+    /// 窗口和限制
+    limit: Limit,
+    /// 窗口开始时间
+    start: Instant,
+    /// 窗口内已输出的条数
+    count: u32,
+    /// 被抑制、尚未汇总的条数
+    suppressed: u64,
+}
+
+/// 对日志的处理结果
+enum Decision {
+    /// 输出日志，之前有被抑制的日志时先输出汇总
+    Pass { suppressed: u64 },
+    /// 丢弃日志
+    Drop,
+}
+
+/// 日志限流层，包装输出日志的层，被限流的日志不交给被包装的层
+///
+/// # 示例
+/// ```rust
+/// let layer = ThrottleLayer::new(&config.throttle, tracing_subscriber::fmt::layer())?;
+/// tracing_subscriber::registry().with(layer).init();
+/// ```
+pub struct ThrottleLayer<L> {
+    /// 被包装的层
+    inner: L,
+    /// 未匹配任何规则时的窗口和限制
+    default: Limit,
+    /// 限流规则
+    rules: Vec<Rule>,
+    /// 每处代码的计数
+    windows: Mutex<HashMap<Identifier, Window>>,
+}
+
+impl<L> ThrottleLayer<L> {
+    /// 按配置创建限流层
+    ///
+    /// # 错误
+    /// 规则中的级别无效时返回验证错误
+    pub fn new(config: &LogThrottleConfig, inner: L) -> Result<Self> {
+        let default = Limit {
+            window: config.window,
+            limit: config.limit,
+        };
+        let rules = config
+            .rules
+            .iter()
+            .map(|rule| parse_rule(rule, default))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            inner,
+            default,
+            rules,
+            windows: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// 计数并决定是否输出日志
+    fn decide(&self, metadata: &'static Metadata<'static>) -> Decision {
+        let now = Instant::now();
+        let mut windows = self.windows.lock().expect("日志限流锁已损坏");
+        let window = windows
+            .entry(metadata.callsite())
+            .or_insert_with(|| Window {
+                limit: self.limit_of(metadata),
+                start: now,
+                count: 0,
+                suppressed: 0,
+            });
+        if window.limit.limit == 0 {
+            return Decision::Pass { suppressed: 0 };
+        }
+        if now.duration_since(window.start) >= window.limit.window {
+            window.start = now;
+            window.count = 0;
+        }
+        if window.count >= window.limit.limit {
+            window.suppressed += 1;
+            return Decision::Drop;
+        }
+        window.count += 1;
+        // 新窗口的第一条日志带出上一个窗口抑制的数量
+        let suppressed = if window.count == 1 {
+            std::mem::take(&mut window.suppressed)
+        } else {
+            0
+        };
+        Decision::Pass { suppressed }
+    }
+
+    /// 获取日志适用的窗口和限制
+    fn limit_of(&self, metadata: &Metadata<'_>) -> Limit {
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(metadata))
+            .map_or(self.default, |rule| rule.limit)
+    }
+}
+
+impl<S, L> Layer<S> for ThrottleLayer<L>
+where
+    S: Subscriber,
+    L: Layer<S>,
+{
+    fn on_layer(&mut self, subscriber: &mut S) {
+        self.inner.on_layer(subscriber);
+    }
+
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        self.inner.register_callsite(metadata)
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, ctx: Context<'_, S>) -> bool {
+        self.inner.enabled(metadata, ctx)
+    }
+
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        self.inner.on_new_span(attrs, id, ctx);
+    }
+
+    fn max_level_hint(&self) -> Option<tracing::level_filters::LevelFilter> {
+        self.inner.max_level_hint()
+    }
+
+    fn on_record(&self, span: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        self.inner.on_record(span, values, ctx);
+    }
+
+    fn on_follows_from(&self, span: &Id, follows: &Id, ctx: Context<'_, S>) {
+        self.inner.on_follows_from(span, follows, ctx);
+    }
+
+    fn event_enabled(&self, event: &Event<'_>, ctx: Context<'_, S>) -> bool {
+        self.inner.event_enabled(event, ctx)
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        match self.decide(metadata) {
+            Decision::Drop => {}
+            Decision::Pass { suppressed } => {
+                if suppressed > 0 {
+                    let message = format!(
+                        "已抑制 {} 条相似日志，来源: {} {}",
+                        suppressed,
+                        metadata.target(),
+                        metadata.name()
+                    );
+                    let fields = SUMMARY_METADATA.fields();
+                    let field = fields.field("message").expect("汇总日志缺少 message 字段");
+                    let message = display(message);
+                    let values = [(&field, Some(&message as &dyn Value))];
+                    let values = fields.value_set(&values);
+                    self.inner
+                        .on_event(&Event::new(&SUMMARY_METADATA, &values), ctx.clone());
+                }
+                self.inner.on_event(event, ctx);
+            }
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        self.inner.on_enter(id, ctx);
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        self.inner.on_exit(id, ctx);
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        self.inner.on_close(id, ctx);
+    }
+
+    fn on_id_change(&self, old: &Id, new: &Id, ctx: Context<'_, S>) {
+        self.inner.on_id_change(old, new, ctx);
+    }
+
+    unsafe fn downcast_raw(&self, id: TypeId) -> Option<*const ()> {
+        if id == TypeId::of::<Self>() {
+            Some(self as *const Self as *const ())
+        } else {
+            self.inner.downcast_raw(id)
+        }
+    }
+}
+
+/// 解析限流规则，未设置的窗口和限制使用默认值
+fn parse_rule(rule: &LogThrottleRule, default: Limit) -> Result<Rule> {
+    let level = rule
+        .level
+        .as_deref()
+        .map(|level| {
+            Level::from_str(level.trim())
+                .map_err(|_| Error::validation(format!("无效的日志限流级别: {}", level)))
+        })
+        .transpose()?;
+    Ok(Rule {
+        target: rule.target.clone(),
+        level,
+        limit: Limit {
+            window: rule.window.unwrap_or(default.window),
+            limit: rule.limit.unwrap_or(default.limit),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::Arc;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    /// 收集日志输出的写入器
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// 测试超过限制的日志被抑制，下一个窗口输出汇总，规则可以关闭指定级别的限流
+    #[test]
+    fn test_log_throttle() {
+        let config = LogThrottleConfig {
+            enabled: true,
+            window: Duration::from_millis(200),
+            limit: 2,
+            rules: vec![LogThrottleRule {
+                target: None,
+                level: Some("error".to_string()),
+                window: None,
+                limit: Some(0),
+            }],
+        };
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let writer = output.clone();
+        let fmt = tracing_subscriber::fmt::layer()
+            .with_ansi(false)
+            .without_time()
+            .with_writer(move || Captured(writer.clone()));
+        let subscriber =
+            tracing_subscriber::registry().with(ThrottleLayer::new(&config, fmt).unwrap());
+        let _default = tracing::subscriber::set_default(subscriber);
+
+        let log = |id: u32| tracing::warn!("处理记录失败: {}", id);
+        for id in 0..5 {
+            log(id);
+            tracing::error!("连接断开: {}", id);
+        }
+        std::thread::sleep(Duration::from_millis(250));
+        log(5);
+
+        let output = String::from_utf8(output.lock().unwrap().clone()).unwrap();
+        let lines: Vec<_> = output.lines().collect();
+        let failures: Vec<_> = lines
+            .iter()
+            .filter(|line| line.contains("处理记录失败"))
+            .collect();
+        assert_eq!(failures.len(), 3);
+        assert!(failures[2].ends_with("处理记录失败: 5"));
+        assert_eq!(
+            lines
+                .iter()
+                .filter(|line| line.contains("连接断开"))
+                .count(),
+            5
+        );
+        let summary = lines
+            .iter()
+            .position(|line| line.contains("已抑制 3 条相似日志"))
+            .unwrap();
+        assert!(lines[summary].contains(THROTTLE_TARGET));
+        assert!(lines[summary + 1].ends_with("处理记录失败: 5"));
+
+        assert!(ThrottleLayer::new(
+            &LogThrottleConfig {
+                rules: vec![LogThrottleRule {
+                    level: Some("loud".to_string()),
+                    ..config.rules[0].clone()
+                }],
+                ..config
+            },
+            ()
+        )
+        .is_err());
+    }
+}
@@ -1,5 +1,5 @@
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{parse_macro_input, DeriveInput, ItemStruct};
 
 /// 应用程序入口注解
@@ -19,8 +19,60 @@ use syn::{parse_macro_input, DeriveInput, ItemStruct};
 ///     Application::run().await
 /// }
 /// ```
+/// 
+/// # 配置类
+/// 
+/// `configurations(...)` 中列出的 `#[derive(Configuration)]` 类型在自动装配前按配置前缀绑定，
+/// 并以 `Arc<T>` 单例注册到容器：
+/// 
+/// ```rust
+/// #[rspring_application(configurations(AppConfig, DatabaseConfig))]
+/// pub struct Application;
+/// ```
+/// 
+/// Web 应用通过生成的 `app()` 创建已登记这些配置类的 `RSpringApp`：
+/// 
+/// ```rust
+/// Application::app()?.run().await?;
+/// ```
+/// 
+/// # 组件扫描
+/// 
+/// `scan(...)` 中列出的组件在启动时通过 `component_scan!` 注册：
+/// 
+/// ```rust
+/// #[rspring_application(scan(UserService, UserRepository, advice(GlobalAdvice)))]
+/// pub struct Application;
+/// ```
+/// 
+/// # 框架路径
+/// 
+/// 核心注解生成的代码通过 `::rspring_core` 引用框架类型。通过门面 crate 重新导出框架时，
+/// 派生宏使用 `#[rspring(crate = "...")]`、属性宏使用 `crate = "..."` 参数指定框架路径：
+/// 
+/// ```rust
+/// #[rspring_application(crate = "my_platform::core")]
+/// pub struct Application;
+/// 
+/// #[derive(Service)]
+/// #[rspring(crate = "my_platform::core")]
+/// pub struct UserService;
+/// 
+/// #[Transactional(crate = "my_platform::core")]
+/// pub async fn transfer(&self) -> Result<()> {
+///     Ok(())
+/// }
+/// ```
 #[proc_macro_attribute]
-pub fn rspring_application(_args: TokenStream, input: TokenStream) -> TokenStream {
+pub fn rspring_application(args: TokenStream, input: TokenStream) -> TokenStream {
+    let ApplicationArgs { krate, configurations, scan } = match ApplicationArgs::parse(args.into()) {
+        Ok(args) => args,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let scan = match scan.map(|entries| scan_components(&krate, &entries)).transpose() {
+        Ok(scan) => scan.map(|scan| quote! { context.scan_components(#scan).await?; }),
+        Err(error) => return error.to_compile_error().into(),
+    };
     let input = parse_macro_input!(input as ItemStruct);
     let struct_name = &input.ident;
 
@@ -28,36 +80,47 @@ pub fn rspring_application(_args: TokenStream, input: TokenStream) -> TokenStrea
         #input
 
         impl #struct_name {
+            /// 创建登记了 `configurations(...)` 中配置类的 `RSpringApp`
+            pub fn app() -> #krate::Result<#krate::RSpringApp> {
+                Ok(#krate::RSpringApp::new()? #(.configuration::<#configurations>())*)
+            }
+
             /// 运行 RSpring 应用程序
-            pub async fn run() -> crate::Result<()> {
+            pub async fn run() -> #krate::Result<()> {
                 // 初始化日志系统
-                let logging_config = crate::config::LoggingConfig::default();
-                crate::logging::init_logging(&logging_config)?;
+                let logging_config = #krate::config::LoggingConfig::default();
+                #krate::logging::init_logging(&logging_config)?;
 
-                tracing::info!("启动 RSpring 应用程序");
+                #krate::__private::tracing::info!("启动 RSpring 应用程序");
 
                 // 创建应用上下文
-                let context = crate::ApplicationContext::new()?;
+                let context = #krate::ApplicationContext::new()?;
                 
-                tracing::info!("应用上下文初始化完成");
+                #krate::__private::tracing::info!("应用上下文初始化完成");
+
+                // 绑定配置类并注册到容器
+                #(context.bind_configuration::<#configurations>().await?;)*
+
+                // 注册列出的组件
+                #scan
 
                 // 执行自动装配
                 context.auto_wire().await?;
                 
-                tracing::info!("自动装配完成，应用程序运行中");
+                #krate::__private::tracing::info!("自动装配完成，应用程序运行中");
 
                 // 保持运行（非Web应用需要自定义实现）
-                tokio::signal::ctrl_c().await.map_err(|e| {
-                    crate::Error::runtime(format!("等待关闭信号失败: {}", e))
+                #krate::__private::tokio::signal::ctrl_c().await.map_err(|e| {
+                    #krate::Error::runtime(format!("等待关闭信号失败: {}", e))
                 })?;
 
-                tracing::info!("应用程序已停止");
+                #krate::__private::tracing::info!("应用程序已停止");
                 Ok(())
             }
         }
 
-        impl crate::RSpringApplication for #struct_name {
-            async fn run() -> crate::Result<()> {
+        impl #krate::RSpringApplication for #struct_name {
+            async fn run() -> #krate::Result<()> {
                 Self::run().await
             }
         }
@@ -66,131 +129,4153 @@ pub fn rspring_application(_args: TokenStream, input: TokenStream) -> TokenStrea
     TokenStream::from(expanded)
 }
 
+/// `#[rspring_application]` 的参数
+struct ApplicationArgs {
+    /// 框架路径
+    krate: syn::Path,
+    /// 启动时绑定的配置类
+    configurations: Vec<syn::Type>,
+    /// 启动时注册的组件
+    scan: Option<Vec<ScanEntry>>,
+}
+
+impl ApplicationArgs {
+    /// 解析 `crate = "..."`、`configurations(A, B)` 和 `scan(A, advice(B))` 参数
+    fn parse(args: proc_macro2::TokenStream) -> syn::Result<Self> {
+        let mut krate = default_core_path();
+        let mut configurations = Vec::new();
+        let mut scan = None;
+        let parser = syn::meta::parser(|meta| {
+            if meta.path.is_ident("crate") {
+                krate = parse_core_path(&meta)?;
+                Ok(())
+            } else if meta.path.is_ident("configurations") {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                configurations.extend(content.parse_terminated(<syn::Type as syn::parse::Parse>::parse, syn::Token![,])?);
+                Ok(())
+            } else if meta.path.is_ident("scan") {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                let entries = content.parse_terminated(<ScanEntry as syn::parse::Parse>::parse, syn::Token![,])?;
+                scan.get_or_insert_with(Vec::new).extend(entries);
+                Ok(())
+            } else {
+                Err(meta.error("不支持的参数，可用参数: crate, configurations, scan"))
+            }
+        });
+        syn::parse::Parser::parse2(parser, args)?;
+        Ok(Self { krate, configurations, scan })
+    }
+}
+
+/// 框架核心 crate 的默认路径
+fn default_core_path() -> syn::Path {
+    syn::parse_quote!(::rspring_core)
+}
+
+/// 解析 `crate = "..."` 参数的值
+fn parse_core_path(meta: &syn::meta::ParseNestedMeta) -> syn::Result<syn::Path> {
+    meta.value()?.parse::<syn::LitStr>()?.parse()
+}
+
+/// 读取派生宏的 `#[rspring(crate = "...")]` 属性，未指定时为 `::rspring_core`
+fn core_path(attrs: &[syn::Attribute]) -> syn::Result<syn::Path> {
+    let mut krate = default_core_path();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("rspring")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                krate = parse_core_path(&meta)?;
+                Ok(())
+            } else {
+                Err(meta.error("不支持的参数，可用参数: crate"))
+            }
+        })?;
+    }
+    Ok(krate)
+}
+
+/// 解析只接受 `crate = "..."` 参数的属性宏参数，未指定时为 `::rspring_core`
+fn core_path_args(args: proc_macro2::TokenStream) -> syn::Result<syn::Path> {
+    let mut krate = default_core_path();
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("crate") {
+            krate = parse_core_path(&meta)?;
+            Ok(())
+        } else {
+            Err(meta.error("不支持的参数，可用参数: crate"))
+        }
+    });
+    syn::parse::Parser::parse2(parser, args)?;
+    Ok(krate)
+}
+
 /// 组件注解
 /// 
 /// 标记一个结构体为通用组件，可以被依赖注入容器管理
 /// 
+/// 标注 `#[component(inject)]` 的 `Arc<T>` 字段作为组件依赖，注册到容器时写入依赖图，
+/// 自动装配时校验依赖已注册。未标注的 `Arc<T>` 字段（如共享的客户端、配置）不是依赖
+/// 
+/// 字段均为依赖、`#[Value]` 或 `#[component(default)]` 字段的非泛型组件可以通过 `component_scan!` 注册，
+/// 由容器在自动装配时创建，`#[component(default)]` 字段使用 `Default::default()`
+/// 
+/// 字段标注 `#[Value("${key:default}")]` 时生成构造函数 `from_config`，标注的字段按
+/// `ConfigurationManager::resolve` 从配置读取并转换为字段类型，其余字段按声明顺序作为参数：
+/// - `${key}` - 配置不存在时返回错误，`Option` 字段为 `None`
+/// - `${key:default}` - 配置不存在时使用默认值
+/// 
 /// # 示例
 /// 
 /// ```rust
 /// #[derive(Component)]
 /// pub struct MyComponent {
-///     // 组件字段
+///     // 依赖 UserRepository
+///     #[component(inject)]
+///     repository: Arc<UserRepository>,
+///     // 不作为依赖，通过 from_config 的参数传入
+///     settings: Arc<Settings>,
+///     #[Value("${mail.sender:noreply@example.com}")]
+///     sender: String,
+///     #[Value("${mail.retries:3}")]
+///     retries: u32,
 /// }
+/// 
+/// let component = MyComponent::from_config(context.config_manager(), repository, settings)?;
 /// ```
-#[proc_macro_derive(Component)]
+#[proc_macro_derive(Component, attributes(component, rspring, Value))]
 pub fn component_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
+
+    match core_path(&input.attrs).and_then(|krate| component_impl(&input, &krate, None)) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// 生成 `Component` 实现，`#[component(inject)]` 字段作为组件依赖，`marker` 为同时实现的标记特征
+fn component_impl(
+    input: &DeriveInput,
+    krate: &syn::Path,
+    marker: Option<proc_macro2::TokenStream>,
+) -> syn::Result<proc_macro2::TokenStream> {
     let name = &input.ident;
+    let dependencies = field_dependencies(&input.data)?;
 
-    let expanded = quote! {
-        impl crate::Component for #name {
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for dependency in &dependencies {
+        where_clause.predicates.push(syn::parse_quote!(#dependency: 'static));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let dependencies_fn = (!dependencies.is_empty()).then(|| {
+        quote! {
+            fn dependencies() -> Vec<std::any::TypeId> {
+                vec![#(std::any::TypeId::of::<#dependencies>()),*]
+            }
+        }
+    });
+    let marker = marker.map(|marker| {
+        quote! { impl #impl_generics #marker for #name #ty_generics #where_clause {} }
+    });
+    let constructor = value_constructor(input, krate)?;
+    let scan_definition = scan_definition(input, krate)?;
+
+    Ok(quote! {
+        impl #impl_generics #krate::Component for #name #ty_generics #where_clause {
             fn component_name(&self) -> &'static str {
                 stringify!(#name)
             }
+
+            #dependencies_fn
+        }
+
+        #marker
+
+        #constructor
+
+        #scan_definition
+    })
+}
+
+/// 生成 `from_config` 构造函数，`#[Value]` 字段从配置读取，其余字段作为参数；
+/// 没有 `#[Value]` 字段时不生成
+fn value_constructor(input: &DeriveInput, krate: &syn::Path) -> syn::Result<Option<proc_macro2::TokenStream>> {
+    let syn::Data::Struct(data) = &input.data else {
+        return Ok(None);
+    };
+
+    let mut params = Vec::new();
+    let mut fields = Vec::new();
+    let mut has_value = false;
+    for field in &data.fields {
+        let value = field.attrs.iter().find(|attr| attr.path().is_ident("Value"));
+        let Some(ident) = &field.ident else {
+            match value {
+                Some(attr) => return Err(syn::Error::new_spanned(attr, "#[Value] 只能标注在具名字段上")),
+                None => continue,
+            }
+        };
+        let ty = &field.ty;
+        match value {
+            Some(attr) => {
+                let expression: syn::LitStr = attr.parse_args()?;
+                validate_value_expression(&expression)?;
+                fields.push(quote! { #ident: __config.resolve::<#ty>(#expression)? });
+                has_value = true;
+            }
+            None => {
+                params.push(quote! { #ident: #ty });
+                fields.push(quote! { #ident });
+            }
+        }
+    }
+    if !has_value {
+        return Ok(None);
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(Some(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// 创建组件，`#[Value]` 字段从配置读取，其余字段按声明顺序传入
+            pub fn from_config(
+                __config: &#krate::ConfigurationManager,
+                #(#params),*
+            ) -> #krate::Result<Self> {
+                Ok(Self { #(#fields),* })
+            }
         }
+    }))
+}
+
+/// 在编译期检查 `#[Value]` 表达式的格式
+fn validate_value_expression(expression: &syn::LitStr) -> syn::Result<()> {
+    let value = expression.value();
+    let Some(placeholder) = value.strip_prefix("${") else {
+        return Ok(());
     };
+    let key = placeholder
+        .strip_suffix('}')
+        .ok_or_else(|| syn::Error::new_spanned(expression, "配置表达式缺少右括号，格式为 ${key} 或 ${key:default}"))?;
+    let key = key.split_once(':').map_or(key, |(key, _)| key);
+    if key.trim().is_empty() {
+        return Err(syn::Error::new_spanned(expression, "配置表达式缺少配置键"));
+    }
+    Ok(())
+}
 
-    TokenStream::from(expanded)
+/// 收集结构体中 `#[component(inject)]` 标注的 `Arc<T>` 字段的类型 `T`
+fn field_dependencies(data: &syn::Data) -> syn::Result<Vec<syn::Type>> {
+    let syn::Data::Struct(data) = data else {
+        return Ok(Vec::new());
+    };
+
+    let mut dependencies: Vec<syn::Type> = Vec::new();
+    for field in &data.fields {
+        let FieldSource::Dependency(ty) = field_source(field)? else {
+            continue;
+        };
+        // syn 未启用 extra-traits，按生成的代码比较类型
+        if !dependencies.iter().any(|dependency| quote!(#dependency).to_string() == quote!(#ty).to_string()) {
+            dependencies.push(*ty);
+        }
+    }
+    Ok(dependencies)
+}
+
+/// 组件字段的取值方式
+enum FieldSource {
+    /// 从容器获取的依赖，为 `Arc<T>` 中的 `T`
+    Dependency(Box<syn::Type>),
+    /// `#[Value]` 标注的配置表达式
+    Value(syn::LitStr),
+    /// `#[component(default)]` 标注，使用 `Default::default()`
+    Default,
+    /// 容器无法创建
+    Unsupported,
+}
+
+/// 按 `#[Value]`、`#[component(inject)]`、`#[component(default)]` 判断字段的取值方式
+fn field_source(field: &syn::Field) -> syn::Result<FieldSource> {
+    let mut inject = None;
+    let mut default = false;
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("component")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("inject") {
+                inject = Some(meta.path.clone());
+                Ok(())
+            } else if meta.path.is_ident("default") {
+                default = true;
+                Ok(())
+            } else {
+                Err(meta.error("不支持的参数，可用参数: inject, default"))
+            }
+        })?;
+    }
+
+    // 从配置读取的字段不是组件依赖
+    if let Some(attr) = field.attrs.iter().find(|attr| attr.path().is_ident("Value")) {
+        return attr.parse_args().map(FieldSource::Value);
+    }
+    match (inject, default) {
+        (Some(inject), true) => Err(syn::Error::new_spanned(inject, "inject 和 default 不能同时使用")),
+        (Some(inject), false) => match arc_component(&field.ty) {
+            Some(ty) => Ok(FieldSource::Dependency(Box::new(ty.clone()))),
+            None => Err(syn::Error::new_spanned(inject, "#[component(inject)] 只能标注在 Arc<T> 字段上")),
+        },
+        (None, true) => Ok(FieldSource::Default),
+        (None, false) => Ok(FieldSource::Unsupported),
+    }
+}
+
+/// 判断组件能否由 `component_scan!` 创建，不能创建时返回原因
+fn scan_unsupported(input: &DeriveInput) -> syn::Result<Option<String>> {
+    let syn::Data::Struct(data) = &input.data else {
+        return Ok(Some("只能扫描结构体组件".to_string()));
+    };
+    if !input.generics.params.is_empty() {
+        return Ok(Some("泛型组件需要手动注册".to_string()));
+    }
+    for (index, field) in data.fields.iter().enumerate() {
+        if let FieldSource::Unsupported = field_source(field)? {
+            let name = field.ident.as_ref().map_or_else(|| index.to_string(), ToString::to_string);
+            return Ok(Some(format!(
+                "字段 {} 不是组件依赖或配置值，请标注 #[component(inject)]、#[component(default)] \
+                 或手动注册组件",
+                name
+            )));
+        }
+    }
+    Ok(None)
+}
+
+/// 生成 `component_scan!` 使用的 Bean 定义函数：依赖从容器获取，`#[Value]` 字段在扫描时从配置读取，
+/// `#[component(default)]` 字段使用默认值；组件无法由容器创建时不生成
+fn scan_definition(input: &DeriveInput, krate: &syn::Path) -> syn::Result<Option<proc_macro2::TokenStream>> {
+    if scan_unsupported(input)?.is_some() {
+        return Ok(None);
+    }
+    let syn::Data::Struct(data) = &input.data else {
+        return Ok(None);
+    };
+
+    let mut values = Vec::new();
+    let mut fields = Vec::new();
+    for (index, field) in data.fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(index.into()),
+        };
+        let ty = &field.ty;
+        let init = match field_source(field)? {
+            FieldSource::Dependency(dependency) => quote! { __container.require::<#dependency>()? },
+            FieldSource::Value(expression) => {
+                let value = format_ident!("__value_{}", index);
+                values.push(quote! { let #value = __config.resolve::<#ty>(#expression)?; });
+                quote! { #value }
+            }
+            FieldSource::Default => quote! { ::std::default::Default::default() },
+            FieldSource::Unsupported => return Ok(None),
+        };
+        fields.push(quote! { #member: #init });
+    }
+    let dependencies = field_dependencies(&input.data)?;
+
+    let name = &input.ident;
+    Ok(Some(quote! {
+        impl #name {
+            /// `component_scan!` 使用的 Bean 定义
+            #[doc(hidden)]
+            #[allow(dead_code)]
+            pub fn __scan_definition(
+                __config: &#krate::ConfigurationManager,
+            ) -> #krate::Result<#krate::BeanDefinition> {
+                #(#values)*
+                Ok(#krate::BeanDefinition::new(
+                    stringify!(#name),
+                    vec![#(#krate::BeanDependency::of::<#dependencies>()),*],
+                    move |__container| Ok(Self { #(#fields),* }),
+                ))
+            }
+        }
+    }))
+}
+
+/// 获取 `Arc<T>` 中的类型 `T`
+fn arc_component(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Arc" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first() {
+        Some(syn::GenericArgument::Type(inner)) => Some(inner),
+        _ => None,
+    }
 }
 
 /// 服务组件注解
 /// 
-/// 标记一个结构体为服务组件，通常包含业务逻辑
+/// 标记一个结构体为服务组件，通常包含业务逻辑，字段的处理与 `#[derive(Component)]` 相同
 /// 
 /// # 示例
 /// 
 /// ```rust
 /// #[derive(Service)]
 /// pub struct UserService {
+///     #[component(inject)]
 ///     user_repository: Arc<UserRepository>,
 /// }
 /// ```
-#[proc_macro_derive(Service)]
+#[proc_macro_derive(Service, attributes(component, rspring, Value))]
 pub fn service_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let name = &input.ident;
 
-    let expanded = quote! {
-        impl crate::Component for #name {
-            fn component_name(&self) -> &'static str {
-                stringify!(#name)
-            }
-        }
-        
-        impl crate::Service for #name {}
-    };
-
-    TokenStream::from(expanded)
+    match core_path(&input.attrs)
+        .and_then(|krate| component_impl(&input, &krate, Some(quote! { #krate::Service })))
+    {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => error.to_compile_error().into(),
+    }
 }
 
 /// 仓储组件注解
 /// 
 /// 标记一个结构体为仓储组件，负责数据访问
 /// 
+/// 添加 `#[repository(...)]` 属性后会为仓储实现 `rspring_data_mysql::CrudRepository`，
+/// 生成 `find_by_id`、`find_all`、`find_page`、`save`、`update`、`delete_by_id` 方法，
+/// 并实现 `rspring_data_mysql::SpecRepository`，按 `Spec` 动态条件查询 `find_all` 的结果。
+/// 所有方法按 `[datasource.logging]` 记录 SQL 日志和慢查询，在 `#[Transactional]` 事务中时使用事务的连接：
+/// - `entity` - 实体类型，需实现 `sqlx::FromRow`（必填）
+/// - `table` - 表名（必填）
+/// - `columns` - 插入和更新的列，逗号分隔，列名即实体字段名（必填）
+/// - `id` - 主键列名，同时是实体的主键字段名，默认 `"id"`
+/// - `id_type` - 主键类型，默认 `i64`
+/// - `pool` - 连接池字段名，字段类型为 `MySqlPool`，默认 `"pool"`
+/// - `audited` - 实体实现了 `Auditable`（见 `#[derive(Audited)]`）时，`save` 额外写入
+///   `created_at`、`updated_at`、`created_by`、`updated_by` 列，`update` 额外写入
+///   `updated_at`、`updated_by` 列，时间取当前时间，操作人取当前认证主体
+/// - `soft_delete` - 启用软删除，可指定删除时间列名，默认 `"deleted_at"`：
+///   - `delete_by_id` 和派生的 `delete_by_` 方法改为将删除时间列更新为当前 UTC 时间
+///   - 生成的查询、`update` 和派生查询只匹配删除时间为 `NULL` 的记录
+///   - 同时实现 `rspring_data_mysql::SoftDeleteRepository`，提供包含已删除记录的查询、
+///     恢复和物理删除方法；派生查询方法名以 `_including_deleted` 结尾时不过滤已删除记录（删除方法为物理删除）
+/// - `find_by_id`、`find_all`、`save`、`update`、`delete_by_id` - 覆盖生成的 SQL，
+///   参数绑定顺序与生成的 SQL 相同；`find_page` 在 `find_all` 的 SQL 之后追加排序和分页
+/// - `queries(...)` - 派生查询方法签名，按方法名在编译期生成 SQL：
+///   - 前缀：`find_by_`（返回 `Option<T>`、`Vec<T>` 或 `T`）、`count_by_`、`exists_by_`、`delete_by_`
+///   - 条件以 `_and_`、`_or_` 连接，列名后可跟 `_greater_than`、`_greater_than_equal`、
+///     `_less_than`、`_less_than_equal`、`_after`、`_before`、`_between`、`_like`、`_not`、`_is_null`、
+///     `_is_not_null`；不支持 `_in`，可使用 `Spec::in_list`
+///   - 末尾可跟 `_order_by_列名[_asc|_desc]`，多个排序列以 `_and_` 连接
+///   - 参数按条件顺序绑定，`_between` 需要上下限两个参数，`_is_null`、`_is_not_null` 不需要参数
+/// 
 /// # 示例
 /// 
 /// ```rust
 /// #[derive(Repository)]
+/// #[repository(entity = User, table = "users", columns = "name, email")]
 /// pub struct UserRepository {
-///     db_pool: Arc<DbPool>,
+///     pool: MySqlPool,
+/// }
+/// 
+/// #[derive(Repository)]
+/// #[repository(
+///     entity = Order,
+///     table = "orders",
+///     columns = "user_id, amount",
+///     id_type = u64,
+///     find_all = "SELECT * FROM orders WHERE deleted = 0",
+///     queries(
+///         fn find_by_user_id_order_by_created_at_desc(user_id: u64) -> Vec<Order>;
+///         fn find_by_status_and_created_at_after(status: i32, after: NaiveDateTime) -> Vec<Order>;
+///         fn count_by_user_id(user_id: u64) -> u64;
+///         fn exists_by_user_id_and_deleted_at_is_null(user_id: u64) -> bool;
+///     ),
+/// )]
+/// pub struct OrderRepository {
+///     pool: MySqlPool,
+/// }
+/// 
+/// #[derive(Repository)]
+/// #[repository(
+///     entity = Article,
+///     table = "articles",
+///     columns = "title",
+///     soft_delete,
+///     queries(
+///         fn find_by_title(title: &str) -> Vec<Article>;
+///         fn find_by_title_including_deleted(title: &str) -> Vec<Article>;
+///     ),
+/// )]
+/// pub struct ArticleRepository {
+///     pool: MySqlPool,
 /// }
 /// ```
-#[proc_macro_derive(Repository)]
+#[proc_macro_derive(Repository, attributes(repository, component, rspring, Value))]
 pub fn repository_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
+    let krate = match core_path(&input.attrs) {
+        Ok(krate) => krate,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
+    let crud = match input.attrs.iter().find(|attr| attr.path().is_ident("repository")) {
+        Some(attr) => match RepositoryArgs::parse(attr) {
+            Ok(args) => args.expand(name, &krate),
+            Err(error) => return error.to_compile_error().into(),
+        },
+        None => quote! {},
+    };
+    let component = match component_impl(&input, &krate, Some(quote! { #krate::Repository })) {
+        Ok(component) => component,
+        Err(error) => return error.to_compile_error().into(),
+    };
 
     let expanded = quote! {
-        impl crate::Component for #name {
-            fn component_name(&self) -> &'static str {
-                stringify!(#name)
+        #component
+
+        #crud
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// `#[repository(...)]` 属性参数
+struct RepositoryArgs {
+    /// 实体类型
+    entity: syn::Type,
+    /// 表名
+    table: String,
+    /// 插入和更新的列
+    columns: Vec<String>,
+    /// 插入和更新的列对应的实体字段
+    fields: Vec<syn::Ident>,
+    /// 主键列名
+    id: String,
+    /// 主键列对应的实体字段
+    id_field: syn::Ident,
+    /// 主键类型
+    id_type: syn::Type,
+    /// 连接池字段名
+    pool: syn::Ident,
+    /// 是否自动写入审计列
+    audited: bool,
+    /// 软删除的删除时间列名，未启用时为 `None`
+    soft_delete: Option<String>,
+    /// 按方法名覆盖的 SQL
+    overrides: std::collections::HashMap<String, String>,
+    /// 派生查询方法签名
+    queries: Vec<syn::Signature>,
+}
+
+/// 将列名或连接池字段名解析为实体字段的标识符，名称无效或为关键字时返回指向参数的错误
+fn field_ident(name: &str, lit: &syn::LitStr) -> syn::Result<syn::Ident> {
+    syn::parse_str::<syn::Ident>(name)
+        .map_err(|_| syn::Error::new_spanned(lit, format!("\"{}\" 不是有效的字段名", name)))
+}
+
+impl RepositoryArgs {
+    /// 可覆盖 SQL 的方法名
+    const METHODS: [&'static str; 5] = ["find_by_id", "find_all", "save", "update", "delete_by_id"];
+
+    /// 解析属性参数
+    fn parse(attr: &syn::Attribute) -> syn::Result<Self> {
+        let mut entity = None;
+        let mut table = None;
+        let mut columns = None;
+        let mut id = "id".to_string();
+        let mut id_field: syn::Ident = syn::parse_quote!(id);
+        let mut id_type: syn::Type = syn::parse_quote!(i64);
+        let mut pool: syn::Ident = syn::parse_quote!(pool);
+        let mut audited = false;
+        let mut soft_delete = None;
+        let mut overrides = std::collections::HashMap::new();
+        let mut queries = Vec::new();
+
+        attr.parse_nested_meta(|meta| {
+            let key = meta
+                .path
+                .get_ident()
+                .map(|ident| ident.to_string())
+                .unwrap_or_default();
+            match key.as_str() {
+                "entity" => entity = Some(meta.value()?.parse::<syn::Type>()?),
+                "id_type" => id_type = meta.value()?.parse::<syn::Type>()?,
+                "table" => table = Some(meta.value()?.parse::<syn::LitStr>()?.value()),
+                "columns" => {
+                    let lit = meta.value()?.parse::<syn::LitStr>()?;
+                    columns = Some(
+                        lit.value()
+                            .split(',')
+                            .map(|column| column.trim().to_string())
+                            .filter(|column| !column.is_empty())
+                            .map(|column| Ok((field_ident(&column, &lit)?, column)))
+                            .collect::<syn::Result<Vec<_>>>()?,
+                    );
+                }
+                "id" => {
+                    let lit = meta.value()?.parse::<syn::LitStr>()?;
+                    id = lit.value();
+                    id_field = field_ident(&id, &lit)?;
+                }
+                "pool" => {
+                    let lit = meta.value()?.parse::<syn::LitStr>()?;
+                    pool = field_ident(&lit.value(), &lit)?;
+                }
+                "audited" => audited = true,
+                "soft_delete" => {
+                    soft_delete = Some(if meta.input.peek(syn::Token![=]) {
+                        meta.value()?.parse::<syn::LitStr>()?.value()
+                    } else {
+                        "deleted_at".to_string()
+                    });
+                }
+                "queries" => {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    while !content.is_empty() {
+                        queries.push(content.parse::<syn::Signature>()?);
+                        content.parse::<syn::Token![;]>()?;
+                    }
+                }
+                method if Self::METHODS.contains(&method) => {
+                    overrides.insert(key.clone(), meta.value()?.parse::<syn::LitStr>()?.value());
+                }
+                _ => return Err(meta.error(format!("未知的 repository 参数: {}", key))),
+            }
+            Ok(())
+        })?;
+
+        let missing = |name: &str| syn::Error::new_spanned(attr, format!("repository 缺少参数: {}", name));
+        let (fields, columns) = columns
+            .filter(|columns| !columns.is_empty())
+            .ok_or_else(|| missing("columns"))?
+            .into_iter()
+            .unzip();
+        Ok(Self {
+            entity: entity.ok_or_else(|| missing("entity"))?,
+            table: table.ok_or_else(|| missing("table"))?,
+            columns,
+            fields,
+            id,
+            id_field,
+            id_type,
+            pool,
+            audited,
+            soft_delete,
+            overrides,
+            queries,
+        })
+    }
+
+    /// 获取方法的 SQL，未覆盖时使用生成的 SQL
+    fn sql(&self, method: &str, generated: String) -> String {
+        self.overrides.get(method).cloned().unwrap_or(generated)
+    }
+
+    /// 生成 `CrudRepository` 实现
+    fn expand(&self, name: &syn::Ident, krate: &syn::Path) -> proc_macro2::TokenStream {
+        let entity = &self.entity;
+        let id_type = &self.id_type;
+        let table = &self.table;
+        let id = &self.id;
+        let pool = &self.pool;
+        let id_field = &self.id_field;
+        let fields = &self.fields;
+
+        // 审计列追加在实体列之后，覆盖的 SQL 须按相同顺序声明
+        let mut insert_columns = self.columns.clone();
+        let mut update_columns = self.columns.clone();
+        if self.audited {
+            insert_columns.extend(
+                ["created_at", "updated_at", "created_by", "updated_by"].map(String::from),
+            );
+            update_columns.extend(["updated_at", "updated_by"].map(String::from));
+        }
+
+        // 软删除时生成的 SQL 只匹配未删除的记录
+        let alive = self
+            .soft_delete
+            .as_ref()
+            .map(|column| format!(" AND {} IS NULL", column))
+            .unwrap_or_default();
+
+        let find_by_id = self.sql(
+            "find_by_id",
+            format!("SELECT * FROM {} WHERE {} = ?{}", table, id, alive),
+        );
+        let find_all = self.sql(
+            "find_all",
+            match &self.soft_delete {
+                Some(column) => format!("SELECT * FROM {} WHERE {} IS NULL", table, column),
+                None => format!("SELECT * FROM {}", table),
+            },
+        );
+        let save = self.sql(
+            "save",
+            format!(
+                "INSERT INTO {} ({}) VALUES ({})",
+                table,
+                insert_columns.join(", "),
+                vec!["?"; insert_columns.len()].join(", ")
+            ),
+        );
+        let update = self.sql(
+            "update",
+            format!(
+                "UPDATE {} SET {} WHERE {} = ?{}",
+                table,
+                update_columns
+                    .iter()
+                    .map(|column| format!("{} = ?", column))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                id,
+                alive
+            ),
+        );
+        let delete_by_id = self.sql(
+            "delete_by_id",
+            match &self.soft_delete {
+                Some(column) => format!(
+                    "UPDATE {} SET {} = UTC_TIMESTAMP(6) WHERE {} = ?{}",
+                    table, column, id, alive
+                ),
+                None => format!("DELETE FROM {} WHERE {} = ?", table, id),
+            },
+        );
+        let derived = self
+            .queries
+            .iter()
+            .map(|signature| {
+                derive_query(table, pool, self.soft_delete.as_deref(), signature, krate)
+                    .unwrap_or_else(|error| error.to_compile_error())
+            })
+            .collect::<Vec<_>>();
+        let soft_delete = self
+            .soft_delete
+            .as_ref()
+            .map(|column| self.expand_soft_delete(name, column, krate))
+            .unwrap_or_default();
+        let (audit_stamp, save_audit, update_audit, audit_check) = if self.audited {
+            (
+                quote! { let stamp = #krate::auditing::AuditStamp::now(); },
+                vec![quote! { stamp.at }, quote! { stamp.at }, quote! { stamp.by }, quote! { stamp.by }],
+                vec![quote! { stamp.at }, quote! { stamp.by }],
+                quote! {
+                    const _: fn() = || {
+                        fn assert_auditable<T: #krate::auditing::Auditable>() {}
+                        assert_auditable::<#entity>();
+                    };
+                },
+            )
+        } else {
+            (quote! {}, Vec::new(), Vec::new(), quote! {})
+        };
+
+        let entity_fields = fields.iter().map(|field| quote! { entity.#field }).collect::<Vec<_>>();
+        let save_params = [entity_fields.clone(), save_audit].concat();
+        let update_params = [entity_fields, update_audit, vec![quote! { entity.#id_field }]].concat();
+        let id_params = [quote! { id }];
+
+        let find_by_id_query = observe_query(
+            &find_by_id,
+            &id_params,
+            quote! { ::rspring_data_mysql::sqlx::query_as::<_, #entity>(#find_by_id) },
+            quote! { fetch_optional(&mut *__conn) },
+        );
+        let find_all_query = observe_query(
+            &find_all,
+            &[],
+            quote! { ::rspring_data_mysql::sqlx::query_as::<_, #entity>(#find_all) },
+            quote! { fetch_all(&mut *__conn) },
+        );
+        let save_query = observe_query(
+            &save,
+            &save_params,
+            quote! { ::rspring_data_mysql::sqlx::query(#save) },
+            quote! { execute(&mut *__conn) },
+        );
+        let update_query = observe_query(
+            &update,
+            &update_params,
+            quote! { ::rspring_data_mysql::sqlx::query(#update) },
+            quote! { execute(&mut *__conn) },
+        );
+        let delete_by_id_query = observe_query(
+            &delete_by_id,
+            &id_params,
+            quote! { ::rspring_data_mysql::sqlx::query(#delete_by_id) },
+            quote! { execute(&mut *__conn) },
+        );
+
+        quote! {
+            #audit_check
+
+            #[::rspring_data_mysql::async_trait]
+            impl ::rspring_data_mysql::CrudRepository for #name {
+                type Entity = #entity;
+                type Id = #id_type;
+
+                async fn find_by_id(&self, id: #id_type) -> #krate::Result<Option<#entity>> {
+                    let mut __conn = ::rspring_data_mysql::acquire_connection(&self.#pool).await?;
+                    #find_by_id_query
+                        .await
+                        .map_err(::rspring_data_mysql::map_sqlx_error)
+                }
+
+                async fn find_all(&self) -> #krate::Result<Vec<#entity>> {
+                    let mut __conn = ::rspring_data_mysql::acquire_connection(&self.#pool).await?;
+                    #find_all_query
+                        .await
+                        .map_err(::rspring_data_mysql::map_sqlx_error)
+                }
+
+                async fn find_page(
+                    &self,
+                    page: ::rspring_data_mysql::Page,
+                    sort: &::rspring_data_mysql::Sort,
+                ) -> #krate::Result<::rspring_data_mysql::PageResult<#entity>> {
+                    ::rspring_data_mysql::PageQuery::new(#find_all)
+                        .fetch(&self.#pool, page, sort)
+                        .await
+                }
+
+                async fn save(&self, entity: &#entity) -> #krate::Result<u64> {
+                    #audit_stamp
+                    let mut __conn = ::rspring_data_mysql::acquire_connection(&self.#pool).await?;
+                    #save_query
+                        .await
+                        .map(|result| result.last_insert_id())
+                        .map_err(::rspring_data_mysql::map_sqlx_error)
+                }
+
+                async fn update(&self, entity: &#entity) -> #krate::Result<bool> {
+                    #audit_stamp
+                    let mut __conn = ::rspring_data_mysql::acquire_connection(&self.#pool).await?;
+                    #update_query
+                        .await
+                        .map(|result| result.rows_affected() > 0)
+                        .map_err(::rspring_data_mysql::map_sqlx_error)
+                }
+
+                async fn delete_by_id(&self, id: #id_type) -> #krate::Result<bool> {
+                    let mut __conn = ::rspring_data_mysql::acquire_connection(&self.#pool).await?;
+                    #delete_by_id_query
+                        .await
+                        .map(|result| result.rows_affected() > 0)
+                        .map_err(::rspring_data_mysql::map_sqlx_error)
+                }
+            }
+
+            #[::rspring_data_mysql::async_trait]
+            impl ::rspring_data_mysql::SpecRepository for #name {
+                async fn find_all_by_spec(
+                    &self,
+                    spec: &::rspring_data_mysql::Spec,
+                ) -> #krate::Result<Vec<#entity>> {
+                    spec.fetch_all(&self.#pool, #find_all).await
+                }
+
+                async fn find_page_by_spec(
+                    &self,
+                    spec: &::rspring_data_mysql::Spec,
+                    page: ::rspring_data_mysql::Page,
+                    sort: &::rspring_data_mysql::Sort,
+                ) -> #krate::Result<::rspring_data_mysql::PageResult<#entity>> {
+                    spec.fetch_page(&self.#pool, #find_all, page, sort).await
+                }
+
+                async fn count_by_spec(&self, spec: &::rspring_data_mysql::Spec) -> #krate::Result<u64> {
+                    spec.count(&self.#pool, #find_all).await
+                }
+            }
+
+            #soft_delete
+
+            impl #name {
+                #(#derived)*
+            }
+        }
+    }
+
+    /// 生成 `SoftDeleteRepository` 实现
+    fn expand_soft_delete(
+        &self,
+        name: &syn::Ident,
+        column: &str,
+        krate: &syn::Path,
+    ) -> proc_macro2::TokenStream {
+        let entity = &self.entity;
+        let id_type = &self.id_type;
+        let table = &self.table;
+        let id = &self.id;
+        let pool = &self.pool;
+
+        let find_by_id = format!("SELECT * FROM {} WHERE {} = ?", table, id);
+        let find_all = format!("SELECT * FROM {}", table);
+        let restore = format!(
+            "UPDATE {} SET {} = NULL WHERE {} = ? AND {} IS NOT NULL",
+            table, column, id, column
+        );
+        let hard_delete = format!("DELETE FROM {} WHERE {} = ?", table, id);
+
+        let id_params = [quote! { id }];
+        let find_by_id_query = observe_query(
+            &find_by_id,
+            &id_params,
+            quote! { ::rspring_data_mysql::sqlx::query_as::<_, #entity>(#find_by_id) },
+            quote! { fetch_optional(&mut *__conn) },
+        );
+        let find_all_query = observe_query(
+            &find_all,
+            &[],
+            quote! { ::rspring_data_mysql::sqlx::query_as::<_, #entity>(#find_all) },
+            quote! { fetch_all(&mut *__conn) },
+        );
+        let restore_query = observe_query(
+            &restore,
+            &id_params,
+            quote! { ::rspring_data_mysql::sqlx::query(#restore) },
+            quote! { execute(&mut *__conn) },
+        );
+        let hard_delete_query = observe_query(
+            &hard_delete,
+            &id_params,
+            quote! { ::rspring_data_mysql::sqlx::query(#hard_delete) },
+            quote! { execute(&mut *__conn) },
+        );
+
+        quote! {
+            #[::rspring_data_mysql::async_trait]
+            impl ::rspring_data_mysql::SoftDeleteRepository for #name {
+                async fn find_by_id_including_deleted(&self, id: #id_type) -> #krate::Result<Option<#entity>> {
+                    let mut __conn = ::rspring_data_mysql::acquire_connection(&self.#pool).await?;
+                    #find_by_id_query
+                        .await
+                        .map_err(::rspring_data_mysql::map_sqlx_error)
+                }
+
+                async fn find_all_including_deleted(&self) -> #krate::Result<Vec<#entity>> {
+                    let mut __conn = ::rspring_data_mysql::acquire_connection(&self.#pool).await?;
+                    #find_all_query
+                        .await
+                        .map_err(::rspring_data_mysql::map_sqlx_error)
+                }
+
+                async fn restore_by_id(&self, id: #id_type) -> #krate::Result<bool> {
+                    let mut __conn = ::rspring_data_mysql::acquire_connection(&self.#pool).await?;
+                    #restore_query
+                        .await
+                        .map(|result| result.rows_affected() > 0)
+                        .map_err(::rspring_data_mysql::map_sqlx_error)
+                }
+
+                async fn hard_delete_by_id(&self, id: #id_type) -> #krate::Result<bool> {
+                    let mut __conn = ::rspring_data_mysql::acquire_connection(&self.#pool).await?;
+                    #hard_delete_query
+                        .await
+                        .map(|result| result.rows_affected() > 0)
+                        .map_err(::rspring_data_mysql::map_sqlx_error)
+                }
             }
         }
-        
-        impl crate::Repository for #name {}
+    }
+}
+
+/// 派生查询的类型，由方法名前缀决定
+enum QueryKind {
+    /// `find_by_`
+    Find,
+    /// `count_by_`
+    Count,
+    /// `exists_by_`
+    Exists,
+    /// `delete_by_`
+    Delete,
+}
+
+/// 根据方法签名生成派生查询方法
+///
+/// 方法名在编译期转换为 SQL，如 `find_by_status_and_age_greater_than` 转换为
+/// `SELECT * FROM users WHERE status = ? AND age > ?`
+///
+/// 启用软删除时追加未删除条件，删除改为更新删除时间列；方法名以 `_including_deleted`
+/// 结尾时不追加条件
+fn derive_query(
+    table: &str,
+    pool: &syn::Ident,
+    soft_delete: Option<&str>,
+    signature: &syn::Signature,
+    krate: &syn::Path,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let method = signature.ident.to_string();
+    let (kind, rest) = [
+        ("find_by_", QueryKind::Find),
+        ("count_by_", QueryKind::Count),
+        ("exists_by_", QueryKind::Exists),
+        ("delete_by_", QueryKind::Delete),
+    ]
+    .into_iter()
+    .find_map(|(prefix, kind)| method.strip_prefix(prefix).map(|rest| (kind, rest)))
+    .ok_or_else(|| {
+        syn::Error::new_spanned(
+            &signature.ident,
+            "派生查询方法须以 find_by_、count_by_、exists_by_ 或 delete_by_ 开头",
+        )
+    })?;
+
+    let (rest, soft_delete) = match (soft_delete, rest.strip_suffix("_including_deleted")) {
+        (Some(_), Some(rest)) => (rest, None),
+        _ => (rest, soft_delete),
     };
+    let (criteria, order_by) = match rest.split_once("_order_by_") {
+        Some((criteria, order_by)) => (criteria, Some(order_by)),
+        None => (rest, None),
+    };
+    let (conditions, bind_count) = parse_conditions(criteria).map_err(|error| {
+        syn::Error::new_spanned(&signature.ident, format!("无法解析查询条件 {}: {}", criteria, error))
+    })?;
 
-    TokenStream::from(expanded)
+    let conditions = match soft_delete {
+        Some(column) if conditions.contains(" OR ") => format!("({}) AND {} IS NULL", conditions, column),
+        Some(column) => format!("{} AND {} IS NULL", conditions, column),
+        None => conditions,
+    };
+
+    let mut sql = match kind {
+        QueryKind::Find => format!("SELECT * FROM {} WHERE {}", table, conditions),
+        QueryKind::Count => format!("SELECT COUNT(*) FROM {} WHERE {}", table, conditions),
+        QueryKind::Exists => format!("SELECT EXISTS(SELECT 1 FROM {} WHERE {})", table, conditions),
+        QueryKind::Delete => match soft_delete {
+            Some(column) => format!("UPDATE {} SET {} = UTC_TIMESTAMP(6) WHERE {}", table, column, conditions),
+            None => format!("DELETE FROM {} WHERE {}", table, conditions),
+        },
+    };
+    if let Some(order_by) = order_by {
+        let order_by = parse_order_by(order_by)
+            .ok_or_else(|| syn::Error::new_spanned(&signature.ident, format!("无法解析排序条件: {}", order_by)))?;
+        sql.push_str(" ORDER BY ");
+        sql.push_str(&order_by);
+    }
+
+    let params: Vec<&syn::PatType> = signature
+        .inputs
+        .iter()
+        .filter_map(|input| match input {
+            syn::FnArg::Typed(param) => Some(param),
+            syn::FnArg::Receiver(_) => None,
+        })
+        .collect();
+    if params.len() != bind_count {
+        return Err(syn::Error::new_spanned(
+            &signature.inputs,
+            format!("方法 {} 需要 {} 个参数，实际为 {} 个", method, bind_count, params.len()),
+        ));
+    }
+    let param_names = params
+        .iter()
+        .map(|param| {
+            let pat = &param.pat;
+            quote! { #pat }
+        })
+        .collect::<Vec<_>>();
+
+    let output = match &signature.output {
+        syn::ReturnType::Type(_, output) => output.as_ref(),
+        syn::ReturnType::Default => {
+            return Err(syn::Error::new_spanned(signature, "派生查询方法须声明返回类型"));
+        }
+    };
+
+    let body = match kind {
+        QueryKind::Find => {
+            let (entity, fetch) = if let Some(entity) = type_arg(output, "Option") {
+                (entity, quote! { fetch_optional })
+            } else if let Some(entity) = type_arg(output, "Vec") {
+                (entity, quote! { fetch_all })
+            } else {
+                (output, quote! { fetch_one })
+            };
+            let query = observe_query(
+                &sql,
+                &param_names,
+                quote! { ::rspring_data_mysql::sqlx::query_as::<_, #entity>(#sql) },
+                quote! { #fetch(&mut *__conn) },
+            );
+            quote! { #query.await }
+        }
+        QueryKind::Count => {
+            let query = observe_query(
+                &sql,
+                &param_names,
+                quote! { ::rspring_data_mysql::sqlx::query_scalar::<_, i64>(#sql) },
+                quote! { fetch_one(&mut *__conn) },
+            );
+            quote! { #query.await.map(|count| count as #output) }
+        }
+        QueryKind::Exists => {
+            let query = observe_query(
+                &sql,
+                &param_names,
+                quote! { ::rspring_data_mysql::sqlx::query_scalar::<_, i64>(#sql) },
+                quote! { fetch_one(&mut *__conn) },
+            );
+            quote! { #query.await.map(|exists| exists != 0) }
+        }
+        QueryKind::Delete => {
+            let query = observe_query(
+                &sql,
+                &param_names,
+                quote! { ::rspring_data_mysql::sqlx::query(#sql) },
+                quote! { execute(&mut *__conn) },
+            );
+            quote! { #query.await.map(|result| result.rows_affected() as #output) }
+        }
+    };
+
+    let ident = &signature.ident;
+    let generics = &signature.generics;
+    Ok(quote! {
+        #[doc = concat!("派生查询：`", #sql, "`")]
+        pub async fn #ident #generics(&self, #(#params),*) -> #krate::Result<#output> {
+            let mut __conn = ::rspring_data_mysql::acquire_connection(&self.#pool).await?;
+            #body.map_err(::rspring_data_mysql::map_sqlx_error)
+        }
+    })
 }
 
-/// 配置类注解
+/// 生成经过 SQL 日志记录的查询
+///
+/// 参数以引用绑定，日志中的参数值仅在需要时通过 `Debug` 格式化，未实现 `Debug` 的参数记录为 `<?>`
+fn observe_query(
+    sql: &str,
+    params: &[proc_macro2::TokenStream],
+    query: proc_macro2::TokenStream,
+    execute: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    quote! {
+        ::rspring_data_mysql::observe_query(
+            #sql,
+            || {
+                #[allow(unused_imports)]
+                use ::rspring_data_mysql::sql_log::{DescribeDebug as _, DescribeOpaque as _};
+                vec![#((&::rspring_data_mysql::sql_log::Param(&#params)).describe()),*]
+            },
+            #query #(.bind(&#params))*.#execute,
+        )
+    }
+}
+
+/// 解析方法名中的查询条件，返回 `WHERE` 子句和需要绑定的参数个数
+///
+/// 条件以 `and`、`or` 分隔，每个条件的结尾可以是比较运算符
+fn parse_conditions(criteria: &str) -> Result<(String, usize), String> {
+    // 按单词后缀匹配运算符和绑定参数个数，较长的后缀在前
+    const OPERATORS: [(&[&str], &str, usize); 11] = [
+        (&["greater", "than", "equal"], ">= ?", 1),
+        (&["less", "than", "equal"], "<= ?", 1),
+        (&["greater", "than"], "> ?", 1),
+        (&["less", "than"], "< ?", 1),
+        (&["is", "not", "null"], "IS NOT NULL", 0),
+        (&["is", "null"], "IS NULL", 0),
+        (&["between"], "BETWEEN ? AND ?", 2),
+        (&["after"], "> ?", 1),
+        (&["before"], "< ?", 1),
+        (&["like"], "LIKE ?", 1),
+        (&["not"], "<> ?", 1),
+    ];
+
+    let mut clause = String::new();
+    let mut bind_count = 0;
+    let mut words: Vec<&str> = Vec::new();
+    let mut connector = "";
+    // 末尾追加 `None`，用于结束最后一个条件
+    for token in criteria.split('_').map(Some).chain(std::iter::once(None)) {
+        let end = token.is_none();
+        let token = token.unwrap_or_default();
+        if !end && ((token != "and" && token != "or") || words.is_empty()) {
+            words.push(token);
+            continue;
+        }
+
+        if words.len() > 1 && words.ends_with(&["in"]) {
+            return Err(format!("不支持 in 条件 \"{}\"，请使用 Spec::in_list", words.join("_")));
+        }
+        let (column, operator, binds) = OPERATORS
+            .iter()
+            .find(|(suffix, _, _)| words.len() > suffix.len() && words.ends_with(suffix))
+            .map(|(suffix, operator, binds)| (&words[..words.len() - suffix.len()], *operator, *binds))
+            .unwrap_or((&words[..], "= ?", 1));
+        if column.is_empty() || column.iter().any(|word| word.is_empty()) {
+            return Err(format!("缺少属性名: \"{}\"", words.join("_")));
+        }
+
+        clause.push_str(connector);
+        clause.push_str(&format!("{} {}", column.join("_"), operator));
+        bind_count += binds;
+        connector = if token == "or" { " OR " } else { " AND " };
+        words.clear();
+        if end {
+            break;
+        }
+    }
+
+    Ok((clause, bind_count))
+}
+
+/// 解析方法名中的排序条件，多个排序列以 `and` 分隔，如 `name_and_created_at_desc`
+fn parse_order_by(order_by: &str) -> Option<String> {
+    let orders = order_by
+        .split("_and_")
+        .map(|order| {
+            let (column, direction) = match order.rsplit_once('_') {
+                Some((column, "asc")) => (column, "ASC"),
+                Some((column, "desc")) => (column, "DESC"),
+                _ => (order, "ASC"),
+            };
+            (!column.is_empty()).then(|| format!("{} {}", column, direction))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(orders.join(", "))
+}
+
+/// 获取形如 `Option<T>` 的类型的泛型参数
+fn type_arg<'a>(ty: &'a syn::Type, name: &str) -> Option<&'a syn::Type> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != name {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+/// 缓存注解
+/// 
+/// 标注在异步方法上，先按键读取缓存，未命中时执行方法并写入缓存：
+/// - `name` - 缓存名称，过期时间等按 `[cache.caches.<name>]` 配置（必填）
+/// - `key` - 缓存键表达式，结果需实现 `ToString`，默认为所有参数的 `Debug` 输出
 /// 
-/// 标记一个结构体为配置类，可以从配置文件中自动绑定值
+/// 返回 `Result` 时只缓存 `Ok` 的值，值类型需实现 `Serialize` 和 `DeserializeOwned`
 /// 
 /// # 示例
 /// 
 /// ```rust
-/// #[derive(Configuration, Deserialize)]
-/// pub struct DatabaseConfig {
-///     pub host: String,
-///     pub port: u16,
+/// #[Cacheable(name = "users", key = "id")]
+/// pub async fn find_user(&self, id: u64) -> Result<User> {
+///     self.user_repository.find_by_id(id).await?.ok_or_else(|| Error::not_found("用户"))
 /// }
 /// ```
-#[proc_macro_derive(Configuration)]
-pub fn configuration_derive(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
-    let name = &input.ident;
+#[allow(non_snake_case)]
+#[proc_macro_attribute]
+pub fn Cacheable(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut cache_args = CacheArgs::default();
+    let parser = syn::meta::parser(|meta| cache_args.parse(meta));
+    parse_macro_input!(args with parser);
+    let function = parse_macro_input!(input as syn::ItemFn);
 
-    let expanded = quote! {
-        impl crate::Configuration for #name {
-            fn config_prefix(&self) -> &'static str {
-                // 将驼峰命名转换为小写下划线命名
-                // DatabaseConfig -> database_config
-                stringify!(#name)
-                    .chars()
-                    .enumerate()
-                    .fold(String::new(), |mut acc, (i, c)| {
-                        if c.is_uppercase() && i > 0 {
-                            acc.push('_');
-                        }
-                        acc.push(c.to_lowercase().next().unwrap());
-                        acc
-                    })
-                    .leak()
-            }
+    match expand_cacheable(&cache_args, function) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// 缓存清除注解
+/// 
+/// 标注在异步方法上，方法执行成功后清除缓存：
+/// - `name` - 缓存名称（必填）
+/// - `key` - 缓存键表达式，规则同 `#[Cacheable]`
+/// - `all_entries` - 为 `true` 时清空整个缓存
+/// - `before_invocation` - 为 `true` 时在方法执行前清除，无论方法是否成功
+/// 
+/// # 示例
+/// 
+/// ```rust
+/// #[CacheEvict(name = "users", key = "user.id")]
+/// pub async fn update_user(&self, user: &User) -> Result<()> {
+///     self.user_repository.update(user).await.map(|_| ())
+/// }
+/// ```
+#[allow(non_snake_case)]
+#[proc_macro_attribute]
+pub fn CacheEvict(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut cache_args = CacheArgs::default();
+    let parser = syn::meta::parser(|meta| cache_args.parse(meta));
+    parse_macro_input!(args with parser);
+    let function = parse_macro_input!(input as syn::ItemFn);
+
+    match expand_cache_evict(&cache_args, function) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// 缓存注解参数
+#[derive(Default)]
+struct CacheArgs {
+    /// 缓存名称
+    name: Option<syn::LitStr>,
+    /// 缓存键表达式
+    key: Option<syn::Expr>,
+    /// 是否清空整个缓存
+    all_entries: bool,
+    /// 是否在方法执行前清除
+    before_invocation: bool,
+    /// 框架核心 crate 的路径
+    krate: Option<syn::Path>,
+}
+
+impl CacheArgs {
+    /// 解析单个参数
+    fn parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("name") || meta.path.is_ident("cache") {
+            self.name = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("key") {
+            self.key = Some(meta.value()?.parse::<syn::LitStr>()?.parse()?);
+        } else if meta.path.is_ident("all_entries") {
+            self.all_entries = meta.value()?.parse::<syn::LitBool>()?.value;
+        } else if meta.path.is_ident("before_invocation") {
+            self.before_invocation = meta.value()?.parse::<syn::LitBool>()?.value;
+        } else if meta.path.is_ident("crate") {
+            self.krate = Some(parse_core_path(&meta)?);
+        } else {
+            return Err(meta.error("未知的缓存注解参数"));
+        }
+        Ok(())
+    }
+
+    /// 框架核心 crate 的路径
+    fn krate(&self) -> syn::Path {
+        self.krate.clone().unwrap_or_else(default_core_path)
+    }
+
+    /// 缓存名称
+    fn name(&self, function: &syn::ItemFn) -> syn::Result<&syn::LitStr> {
+        self.name
+            .as_ref()
+            .ok_or_else(|| syn::Error::new_spanned(&function.sig.ident, "缓存注解缺少参数: name"))
+    }
+
+    /// 生成计算缓存键的表达式
+    fn key(&self, function: &syn::ItemFn) -> proc_macro2::TokenStream {
+        if let Some(key) = &self.key {
+            return quote! { (#key).to_string() };
         }
+        let args = function.sig.inputs.iter().filter_map(|input| match input {
+            syn::FnArg::Typed(param) => Some(&param.pat),
+            syn::FnArg::Receiver(_) => None,
+        });
+        quote! { format!("{:?}", (#(&#args,)*)) }
+    }
+}
+
+/// 检查缓存注解标注的方法并返回其返回类型
+fn cached_output(function: &syn::ItemFn) -> syn::Result<syn::Type> {
+    if function.sig.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(&function.sig, "缓存注解只能标注在异步方法上"));
+    }
+    Ok(match &function.sig.output {
+        syn::ReturnType::Type(_, output) => output.as_ref().clone(),
+        syn::ReturnType::Default => syn::parse_quote!(()),
+    })
+}
+
+/// 展开 `#[Cacheable]`
+fn expand_cacheable(args: &CacheArgs, function: syn::ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    let output = cached_output(&function)?;
+    let name = args.name(&function)?;
+    let key = args.key(&function);
+    let krate = args.krate();
+    let syn::ItemFn { attrs, vis, sig, block } = function;
+
+    // 返回 Result 时只缓存 Ok 的值
+    let (lookup, store) = match type_arg(&output, "Result") {
+        Some(value) => (
+            quote! {
+                if let Some(__cached) = #krate::cache::get_value::<#value>(&*__cache, &__cache_key).await {
+                    return Ok(__cached);
+                }
+            },
+            quote! {
+                if let Ok(__value) = &__result {
+                    #krate::cache::put_value(&*__cache, &__cache_key, __value).await;
+                }
+            },
+        ),
+        None => (
+            quote! {
+                if let Some(__cached) = #krate::cache::get_value::<#output>(&*__cache, &__cache_key).await {
+                    return __cached;
+                }
+            },
+            quote! {
+                #krate::cache::put_value(&*__cache, &__cache_key, &__result).await;
+            },
+        ),
     };
 
-    TokenStream::from(expanded)
-}
\ No newline at end of file
+    Ok(quote! {
+        #(#attrs)*
+        #vis #sig {
+            let __cache = #krate::cache::cache_manager().cache(#name);
+            let __cache_key: String = #key;
+            #lookup
+            let __result: #output = async move {
+                let __value: #output = #block;
+                __value
+            }
+            .await;
+            #store
+            __result
+        }
+    })
+}
+
+/// 展开 `#[CacheEvict]`
+fn expand_cache_evict(args: &CacheArgs, function: syn::ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    let output = cached_output(&function)?;
+    let name = args.name(&function)?;
+    let key = args.key(&function);
+    let krate = args.krate();
+    let syn::ItemFn { attrs, vis, sig, block } = function;
+
+    let evict = if args.all_entries {
+        quote! { #krate::cache::Cache::clear(&*__cache).await; }
+    } else {
+        quote! { #krate::cache::Cache::evict(&*__cache, &__cache_key).await; }
+    };
+    let (before, after) = if args.before_invocation {
+        (evict, quote! {})
+    } else if type_arg(&output, "Result").is_some() {
+        (quote! {}, quote! { if __result.is_ok() { #evict } })
+    } else {
+        (quote! {}, evict)
+    };
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis #sig {
+            let __cache = #krate::cache::cache_manager().cache(#name);
+            let __cache_key: String = #key;
+            #before
+            let __result: #output = async move {
+                let __value: #output = #block;
+                __value
+            }
+            .await;
+            #after
+            __result
+        }
+    })
+}
+
+/// 审计实体注解
+/// 
+/// 为实体实现 `Auditable`，实体须包含 `created_at`、`updated_at`（`Option<DateTime<Utc>>`）
+/// 和 `created_by`、`updated_by`（`Option<String>`）字段。配合仓储的
+/// `#[repository(..., audited)]` 在保存和更新时自动填充审计列
+/// 
+/// # 示例
+/// 
+/// ```rust
+/// #[derive(sqlx::FromRow, Audited)]
+/// pub struct User {
+///     pub id: i64,
+///     pub name: String,
+///     pub created_at: Option<DateTime<Utc>>,
+///     pub updated_at: Option<DateTime<Utc>>,
+///     pub created_by: Option<String>,
+///     pub updated_by: Option<String>,
+/// }
+/// ```
+#[proc_macro_derive(Audited, attributes(rspring))]
+pub fn audited_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let krate = match core_path(&input.attrs) {
+        Ok(krate) => krate,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let expanded = quote! {
+        impl #impl_generics #krate::auditing::Auditable for #name #ty_generics #where_clause {
+            fn created_at(&self) -> Option<#krate::auditing::DateTime<#krate::auditing::Utc>> {
+                self.created_at
+            }
+
+            fn updated_at(&self) -> Option<#krate::auditing::DateTime<#krate::auditing::Utc>> {
+                self.updated_at
+            }
+
+            fn created_by(&self) -> Option<&str> {
+                self.created_by.as_deref()
+            }
+
+            fn updated_by(&self) -> Option<&str> {
+                self.updated_by.as_deref()
+            }
+
+            fn mark_created(&mut self, stamp: &#krate::auditing::AuditStamp) {
+                self.created_at = Some(stamp.at);
+                self.created_by = stamp.by.clone();
+                self.mark_updated(stamp);
+            }
+
+            fn mark_updated(&mut self, stamp: &#krate::auditing::AuditStamp) {
+                self.updated_at = Some(stamp.at);
+                self.updated_by = stamp.by.clone();
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// 对象映射注解
+/// 
+/// 为实体和 DTO 生成 `From` 转换，目标类型在结构体上以 `#[map_to(...)]` 声明，可声明多个：
+/// - `#[map_to(UserResponse)]` - 生成 `impl From<Self> for UserResponse`，目标的每个字段都须有来源
+/// - `#[map_to(User, default)]` - 目标中未映射的字段取 `Default::default()`
+/// 
+/// 字段上的 `#[map_to(...)]`：
+/// - `rename = "name"` - 映射到目标的指定字段
+/// - `skip` - 不映射该字段
+/// - `with = "path::to::fn"` - 使用自定义转换函数 `fn(源字段类型) -> 目标字段类型`，
+///   未指定时使用 `Into` 转换
+/// - `target = Type` - 只对指定目标生效，未指定时对所有目标生效
+/// 
+/// # 示例
+/// 
+/// ```rust
+/// #[derive(MapTo)]
+/// #[map_to(UserResponse)]
+/// #[map_to(UserSummary, default)]
+/// pub struct User {
+///     pub id: i64,
+///     #[map_to(rename = "display_name")]
+///     pub name: String,
+///     #[map_to(skip)]
+///     pub password_hash: String,
+///     #[map_to(target = UserResponse, with = "format_time")]
+///     pub created_at: DateTime<Utc>,
+/// }
+/// 
+/// #[derive(MapTo)]
+/// #[map_to(User, default)]
+/// pub struct CreateUserRequest {
+///     pub name: String,
+/// }
+/// 
+/// let response: UserResponse = user.into();
+/// ```
+#[proc_macro_derive(MapTo, attributes(map_to))]
+pub fn map_to_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_map_to(&input) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// 结构体上的 `#[map_to(...)]`
+struct MapTarget {
+    /// 目标类型
+    ty: syn::Type,
+    /// 未映射的字段是否取默认值
+    default: bool,
+}
+
+/// 字段上的 `#[map_to(...)]`
+#[derive(Default)]
+struct MapField {
+    /// 只对该目标生效
+    target: Option<syn::Type>,
+    /// 目标字段名
+    rename: Option<syn::Ident>,
+    /// 是否跳过
+    skip: bool,
+    /// 自定义转换函数
+    with: Option<syn::Path>,
+}
+
+impl MapField {
+    /// 解析字段属性
+    fn parse(attr: &syn::Attribute) -> syn::Result<Self> {
+        let mut field = Self::default();
+        attr.parse_nested_meta(|meta| {
+            let key = meta
+                .path
+                .get_ident()
+                .map(|ident| ident.to_string())
+                .unwrap_or_default();
+            match key.as_str() {
+                "target" => field.target = Some(meta.value()?.parse()?),
+                "rename" => {
+                    let name = meta.value()?.parse::<syn::LitStr>()?;
+                    field.rename = Some(name.parse()?);
+                }
+                "skip" => field.skip = true,
+                "with" => field.with = Some(meta.value()?.parse::<syn::LitStr>()?.parse()?),
+                _ => return Err(meta.error(format!("未知的 map_to 参数: {}", key))),
+            }
+            Ok(())
+        })?;
+        Ok(field)
+    }
+
+    /// 是否对指定目标生效
+    fn applies_to(&self, target: &syn::Type) -> bool {
+        self.target
+            .as_ref()
+            .is_none_or(|ty| quote!(#ty).to_string() == quote!(#target).to_string())
+    }
+}
+
+/// 生成 `MapTo` 的所有 `From` 实现
+fn expand_map_to(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let fields = match &input.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => return Err(syn::Error::new_spanned(input, "MapTo 只支持具名字段的结构体")),
+    };
+
+    let mut targets = Vec::new();
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("map_to")) {
+        targets.push(attr.parse_args_with(|input: syn::parse::ParseStream| {
+            let ty = input.parse::<syn::Type>()?;
+            let mut default = false;
+            while input.parse::<Option<syn::Token![,]>>()?.is_some() && !input.is_empty() {
+                let flag = input.parse::<syn::Ident>()?;
+                if flag != "default" {
+                    return Err(syn::Error::new_spanned(&flag, format!("未知的 map_to 参数: {}", flag)));
+                }
+                default = true;
+            }
+            Ok(MapTarget { ty, default })
+        })?);
+    }
+    if targets.is_empty() {
+        return Err(syn::Error::new_spanned(name, "MapTo 缺少目标类型，如 #[map_to(UserResponse)]"));
+    }
+
+    let mut field_attrs = Vec::new();
+    for field in fields {
+        let attrs = field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("map_to"))
+            .map(MapField::parse)
+            .collect::<syn::Result<Vec<_>>>()?;
+        field_attrs.push((field, attrs));
+    }
+
+    let impls = targets.iter().map(|target| {
+        let ty = &target.ty;
+        let assignments = field_attrs.iter().filter_map(|(field, attrs)| {
+            let source = field.ident.as_ref()?;
+            let attrs: Vec<&MapField> = attrs.iter().filter(|attr| attr.applies_to(ty)).collect();
+            if attrs.iter().any(|attr| attr.skip) {
+                return None;
+            }
+
+            let target_field = attrs
+                .iter()
+                .find_map(|attr| attr.rename.clone())
+                .unwrap_or_else(|| source.clone());
+            let value = match attrs.iter().find_map(|attr| attr.with.as_ref()) {
+                Some(with) => quote! { #with(source.#source) },
+                None => quote! { ::std::convert::Into::into(source.#source) },
+            };
+            Some(quote! { #target_field: #value })
+        });
+        let rest = if target.default {
+            quote! { ..::std::default::Default::default() }
+        } else {
+            quote! {}
+        };
+
+        quote! {
+            impl ::std::convert::From<#name> for #ty {
+                fn from(source: #name) -> Self {
+                    Self {
+                        #(#assignments,)*
+                        #rest
+                    }
+                }
+            }
+        }
+    });
+
+    Ok(quote! { #(#impls)* })
+}
+
+/// 异步执行注解
+/// 
+/// 标注在异步方法或函数上，调用时将方法体提交到全局任务执行器后立即返回
+/// `JoinHandle`，并发数和停止时的等待时间按 `[task.execution]` 配置。
+/// 不关心结果时可以直接丢弃返回的句柄
+/// 
+/// 方法的接收者须为 `&self`，组件需实现 `Clone`，方法体在克隆的组件上执行；
+/// 参数须为拥有所有权的类型
+/// 
+/// # 示例
+/// 
+/// ```rust
+/// #[derive(Clone)]
+/// pub struct ReportService {
+///     mailer: Arc<Mailer>,
+/// }
+/// 
+/// impl ReportService {
+///     #[Async]
+///     pub async fn send_report(&self, user_id: u64) -> Result<()> {
+///         self.mailer.send(user_id, "report").await
+///     }
+/// }
+/// 
+/// // 立即返回，报表在后台发送
+/// let handle = report_service.send_report(42);
+/// ```
+#[allow(non_snake_case)]
+#[proc_macro_attribute]
+pub fn Async(args: TokenStream, input: TokenStream) -> TokenStream {
+    let function = parse_macro_input!(input as syn::ItemFn);
+
+    match core_path_args(args.into()).and_then(|krate| expand_async(function, &krate)) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// 展开 `#[Async]`
+fn expand_async(function: syn::ItemFn, krate: &syn::Path) -> syn::Result<proc_macro2::TokenStream> {
+    if function.sig.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(&function.sig, "#[Async] 只能标注在异步方法上"));
+    }
+
+    let mut has_receiver = false;
+    let mut args = Vec::new();
+    for input in &function.sig.inputs {
+        match input {
+            syn::FnArg::Receiver(value) => {
+                if value.reference.is_none() || value.mutability.is_some() {
+                    return Err(syn::Error::new_spanned(value, "#[Async] 方法的接收者须为 &self"));
+                }
+                has_receiver = true;
+            }
+            syn::FnArg::Typed(param) => match param.pat.as_ref() {
+                syn::Pat::Ident(pat) => args.push(pat.ident.clone()),
+                _ => return Err(syn::Error::new_spanned(&param.pat, "#[Async] 方法的参数须为标识符")),
+            },
+        }
+    }
+
+    let syn::ItemFn { attrs, vis, sig, block } = function;
+    let output = match &sig.output {
+        syn::ReturnType::Type(_, output) => output.as_ref().clone(),
+        syn::ReturnType::Default => syn::parse_quote!(()),
+    };
+    let name = &sig.ident;
+    let inner = format_ident!("__async_{}", name);
+
+    // 提交到执行器的方法不再是 async，返回任务句柄
+    let mut outer_sig = sig.clone();
+    outer_sig.asyncness = None;
+    outer_sig.output = syn::parse_quote!(-> #krate::task::JoinHandle<#output>);
+
+    let mut inner_sig = sig.clone();
+    inner_sig.ident = inner.clone();
+
+    if has_receiver {
+        // 方法体在按值接收的组件副本上执行
+        inner_sig.inputs = inner_sig
+            .inputs
+            .into_iter()
+            .map(|input| match input {
+                syn::FnArg::Receiver(_) => syn::parse_quote!(self),
+                other => other,
+            })
+            .collect();
+
+        Ok(quote! {
+            #[doc(hidden)]
+            #inner_sig #block
+
+            #(#attrs)*
+            #vis #outer_sig {
+                let __this = ::std::clone::Clone::clone(self);
+                #krate::task::task_executor().spawn(async move { __this.#inner(#(#args),*).await })
+            }
+        })
+    } else {
+        Ok(quote! {
+            #(#attrs)*
+            #vis #outer_sig {
+                #inner_sig #block
+
+                #krate::task::task_executor().spawn(#inner(#(#args),*))
+            }
+        })
+    }
+}
+
+/// 重试注解
+/// 
+/// 标注在返回 `Result` 的异步方法上，失败时按策略重试：
+/// - `max_attempts` - 最大尝试次数（包括第一次调用），默认 3
+/// - `delay` - 首次重试前的等待时间，如 `"200ms"`，默认 `"1s"`
+/// - `multiplier` - 每次重试等待时间的倍数，默认 1（固定间隔）
+/// - `max_delay` - 最长等待时间，默认 `"30s"`
+/// - `jitter` - 随机抖动比例（0 到 1），默认 0
+/// - `retry_on` - 判断错误是否可以重试的函数 `fn(&E) -> bool`，默认 `is_transient`
+/// - `recover` - 重试耗尽后调用的兜底方法名，该方法的参数为错误加上原方法的参数
+/// 
+/// 每次尝试都会克隆参数，参数须实现 `Clone`
+/// 
+/// # 示例
+/// 
+/// ```rust
+/// impl PriceClient {
+///     #[Retryable(max_attempts = 5, delay = "200ms", multiplier = 2.0, jitter = 0.2, recover = "cached_price")]
+///     pub async fn fetch_price(&self, sku: String) -> Result<Price> {
+///         self.http.get_price(&sku).await
+///     }
+/// 
+///     #[Recover]
+///     async fn cached_price(&self, error: Error, sku: String) -> Result<Price> {
+///         tracing::warn!("价格服务不可用，使用缓存价格: {}", error);
+///         self.cache.price(&sku).await
+///     }
+/// }
+/// ```
+#[allow(non_snake_case)]
+#[proc_macro_attribute]
+pub fn Retryable(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut retry_args = RetryArgs::default();
+    let parser = syn::meta::parser(|meta| retry_args.parse(meta));
+    parse_macro_input!(args with parser);
+    let function = parse_macro_input!(input as syn::ItemFn);
+
+    match expand_retryable(&retry_args, function) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// 重试兜底注解
+/// 
+/// 标记 `#[Retryable(recover = "...")]` 引用的兜底方法，方法须为异步，第一个参数接收
+/// 最后一次失败的错误，其余参数和返回类型与重试方法相同
+#[allow(non_snake_case)]
+#[proc_macro_attribute]
+pub fn Recover(_args: TokenStream, input: TokenStream) -> TokenStream {
+    let function = parse_macro_input!(input as syn::ItemFn);
+
+    let typed = function
+        .sig
+        .inputs
+        .iter()
+        .filter(|input| matches!(input, syn::FnArg::Typed(_)))
+        .count();
+    if function.sig.asyncness.is_none() || typed == 0 {
+        return syn::Error::new_spanned(&function.sig, "#[Recover] 方法须为异步，且第一个参数接收错误")
+            .to_compile_error()
+            .into();
+    }
+    quote! { #function }.into()
+}
+
+/// 重试注解参数
+#[derive(Default)]
+struct RetryArgs {
+    /// 最大尝试次数
+    max_attempts: Option<syn::LitInt>,
+    /// 首次重试前的等待时间（毫秒）
+    delay: Option<u64>,
+    /// 退避倍数
+    multiplier: Option<syn::LitFloat>,
+    /// 最长等待时间（毫秒）
+    max_delay: Option<u64>,
+    /// 随机抖动比例
+    jitter: Option<syn::LitFloat>,
+    /// 可重试错误判定函数
+    retry_on: Option<syn::Path>,
+    /// 兜底方法名
+    recover: Option<syn::Ident>,
+    /// 框架核心 crate 的路径
+    krate: Option<syn::Path>,
+}
+
+impl RetryArgs {
+    /// 解析单个参数
+    fn parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("max_attempts") {
+            self.max_attempts = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("delay") {
+            self.delay = Some(parse_duration_millis(&meta.value()?.parse()?)?);
+        } else if meta.path.is_ident("multiplier") {
+            self.multiplier = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("max_delay") {
+            self.max_delay = Some(parse_duration_millis(&meta.value()?.parse()?)?);
+        } else if meta.path.is_ident("jitter") {
+            self.jitter = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("retry_on") {
+            self.retry_on = Some(meta.value()?.parse::<syn::LitStr>()?.parse()?);
+        } else if meta.path.is_ident("recover") {
+            self.recover = Some(meta.value()?.parse::<syn::LitStr>()?.parse()?);
+        } else if meta.path.is_ident("crate") {
+            self.krate = Some(parse_core_path(&meta)?);
+        } else {
+            return Err(meta.error("未知的重试注解参数"));
+        }
+        Ok(())
+    }
+
+    /// 框架核心 crate 的路径
+    fn krate(&self) -> syn::Path {
+        self.krate.clone().unwrap_or_else(default_core_path)
+    }
+
+    /// 生成重试策略表达式
+    fn policy(&self) -> proc_macro2::TokenStream {
+        let krate = self.krate();
+        let mut policy = match &self.max_attempts {
+            Some(max_attempts) => quote! { #krate::retry::RetryPolicy::new(#max_attempts) },
+            None => quote! { #krate::retry::RetryPolicy::default() },
+        };
+        if let Some(delay) = self.delay {
+            policy = quote! { #policy.with_delay(::std::time::Duration::from_millis(#delay)) };
+        }
+        if let Some(multiplier) = &self.multiplier {
+            policy = quote! { #policy.with_multiplier(#multiplier) };
+        }
+        if let Some(max_delay) = self.max_delay {
+            policy = quote! { #policy.with_max_delay(::std::time::Duration::from_millis(#max_delay)) };
+        }
+        if let Some(jitter) = &self.jitter {
+            policy = quote! { #policy.with_jitter(#jitter) };
+        }
+        policy
+    }
+}
+
+/// 在编译期解析 `"500ms"`、`"2s"`、`"1m"` 形式的时长，返回毫秒数
+fn parse_duration_millis(value: &syn::LitStr) -> syn::Result<u64> {
+    let text = value.value();
+    let text = text.trim();
+    let split = text.find(|ch: char| !ch.is_ascii_digit()).unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: u64 = number
+        .parse()
+        .map_err(|_| syn::Error::new_spanned(value, "无效的时长"))?;
+
+    let factor = match unit.trim() {
+        "ms" => 1,
+        "" | "s" => 1000,
+        "m" => 60 * 1000,
+        "h" => 60 * 60 * 1000,
+        _ => return Err(syn::Error::new_spanned(value, "无效的时长单位，支持 ms/s/m/h")),
+    };
+    number
+        .checked_mul(factor)
+        .ok_or_else(|| syn::Error::new_spanned(value, "时长超出范围"))
+}
+
+/// 展开 `#[Retryable]`
+fn expand_retryable(args: &RetryArgs, function: syn::ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    if function.sig.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(&function.sig, "#[Retryable] 只能标注在异步方法上"));
+    }
+
+    let mut has_receiver = false;
+    let mut params = Vec::new();
+    for input in &function.sig.inputs {
+        match input {
+            syn::FnArg::Receiver(_) => has_receiver = true,
+            syn::FnArg::Typed(param) => match param.pat.as_ref() {
+                syn::Pat::Ident(pat) => params.push(pat.ident.clone()),
+                _ => return Err(syn::Error::new_spanned(&param.pat, "#[Retryable] 方法的参数须为标识符")),
+            },
+        }
+    }
+
+    let syn::ItemFn { attrs, vis, sig, block } = function;
+    let name = &sig.ident;
+    let label = name.to_string();
+    let inner = format_ident!("__retryable_{}", name);
+    let mut inner_sig = sig.clone();
+    inner_sig.ident = inner.clone();
+
+    let krate = args.krate();
+    let policy = args.policy();
+    let retry_on = match &args.retry_on {
+        Some(path) => quote! { #path },
+        None => quote! { #krate::retry::is_transient },
+    };
+    // 方法的重试体作为隐藏的兄弟方法，函数的重试体作为内部函数
+    let (call, sibling, nested) = if has_receiver {
+        (
+            quote! { self.#inner(#(::std::clone::Clone::clone(&#params)),*) },
+            quote! {
+                #[doc(hidden)]
+                #inner_sig #block
+            },
+            quote! {},
+        )
+    } else {
+        (
+            quote! { #inner(#(::std::clone::Clone::clone(&#params)),*) },
+            quote! {},
+            quote! { #inner_sig #block },
+        )
+    };
+
+    let finish = match &args.recover {
+        Some(recover) if has_receiver => quote! {
+            match __result {
+                Ok(__value) => Ok(__value),
+                Err(__error) => self.#recover(__error, #(#params),*).await,
+            }
+        },
+        Some(recover) => quote! {
+            match __result {
+                Ok(__value) => Ok(__value),
+                Err(__error) => #recover(__error, #(#params),*).await,
+            }
+        },
+        None => quote! { __result },
+    };
+
+    Ok(quote! {
+        #sibling
+
+        #(#attrs)*
+        #vis #sig {
+            #nested
+
+            let __policy = #policy;
+            let __result = #krate::retry::retry(&__policy, #label, #retry_on, || #call).await;
+            #finish
+        }
+    })
+}
+
+/// 断路器注解
+/// 
+/// 标注在返回 `Result` 的异步方法上，通过全局注册表中指定名称的断路器执行方法体：
+/// - `name` - 断路器名称，按 `[resilience.circuit_breakers.<name>]` 配置（必填）
+/// - `fallback` - 调用失败或断路器打开时调用的兜底方法名，参数为错误加上原方法的参数
+/// 
+/// 错误类型需实现 `From<rspring_core::Error>`；指定 `fallback` 时参数须实现 `Clone`
+/// 
+/// # 示例
+/// 
+/// ```rust
+/// impl PaymentClient {
+///     #[CircuitBreaker(name = "payments", fallback = "queue_payment")]
+///     pub async fn charge(&self, order_id: u64) -> Result<Receipt> {
+///         self.http.charge(order_id).await
+///     }
+/// 
+///     async fn queue_payment(&self, error: Error, order_id: u64) -> Result<Receipt> {
+///         tracing::warn!("支付服务不可用，稍后重试: {}", error);
+///         self.pending.push(order_id).await
+///     }
+/// }
+/// ```
+#[allow(non_snake_case)]
+#[proc_macro_attribute]
+pub fn CircuitBreaker(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut name: Option<syn::LitStr> = None;
+    let mut fallback: Option<syn::Ident> = None;
+    let mut krate = default_core_path();
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("name") {
+            name = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("fallback") {
+            fallback = Some(meta.value()?.parse::<syn::LitStr>()?.parse()?);
+        } else if meta.path.is_ident("crate") {
+            krate = parse_core_path(&meta)?;
+        } else {
+            return Err(meta.error("未知的断路器注解参数"));
+        }
+        Ok(())
+    });
+    parse_macro_input!(args with parser);
+    let function = parse_macro_input!(input as syn::ItemFn);
+
+    match expand_circuit_breaker(name, fallback, function, &krate) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// 展开 `#[CircuitBreaker]`
+fn expand_circuit_breaker(
+    name: Option<syn::LitStr>,
+    fallback: Option<syn::Ident>,
+    function: syn::ItemFn,
+    krate: &syn::Path,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if function.sig.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(&function.sig, "#[CircuitBreaker] 只能标注在异步方法上"));
+    }
+    let name = name.ok_or_else(|| syn::Error::new_spanned(&function.sig.ident, "断路器注解缺少参数: name"))?;
+    let output = match &function.sig.output {
+        syn::ReturnType::Type(_, output) => output.as_ref().clone(),
+        syn::ReturnType::Default => {
+            return Err(syn::Error::new_spanned(&function.sig, "#[CircuitBreaker] 方法须返回 Result"))
+        }
+    };
+
+    let mut has_receiver = false;
+    let mut params = Vec::new();
+    for input in &function.sig.inputs {
+        match input {
+            syn::FnArg::Receiver(_) => has_receiver = true,
+            syn::FnArg::Typed(param) => match param.pat.as_ref() {
+                syn::Pat::Ident(pat) => params.push(pat.ident.clone()),
+                _ => return Err(syn::Error::new_spanned(&param.pat, "#[CircuitBreaker] 方法的参数须为标识符")),
+            },
+        }
+    }
+
+    let syn::ItemFn { attrs, vis, sig, block } = function;
+    let call = quote! {
+        #krate::resilience::circuit_breaker(#name)
+            .call(async move {
+                let __value: #output = #block;
+                __value
+            })
+            .await
+    };
+
+    let body = match fallback {
+        Some(fallback) => {
+            // 方法体会移动参数，兜底方法使用调用前的副本
+            let copies: Vec<_> = params.iter().map(|param| format_ident!("__fallback_{}", param)).collect();
+            let target = if has_receiver { quote! { self.#fallback } } else { quote! { #fallback } };
+            quote! {
+                #(let #copies = ::std::clone::Clone::clone(&#params);)*
+                let __result: #output = #call;
+                match __result {
+                    Ok(__value) => Ok(__value),
+                    Err(__error) => #target(__error, #(#copies),*).await,
+                }
+            }
+        }
+        None => call,
+    };
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis #sig {
+            #body
+        }
+    })
+}
+
+/// 定时任务锁注解
+/// 
+/// 标注在异步任务方法上，通过全局锁提供者加锁执行，保证集群中同一时刻只有一个实例执行：
+/// - `name` - 锁名称（必填）
+/// - `lock_at_most_for` - 锁的租期，执行期间自动续期，实例崩溃后到期释放，默认 `"10m"`
+/// - `lock_at_least_for` - 任务完成后锁的最短保留时间，默认 `"0s"`
+/// 
+/// 方法须返回 `()` 或 `Result<(), E>`（`E` 实现 `From<rspring_core::Error>`）。
+/// 未获得锁时跳过本次执行；返回 `()` 时访问锁提供者失败只记录日志
+/// 
+/// # 示例
+/// 
+/// ```rust
+/// impl ReportJob {
+///     #[SchedulerLock(name = "daily-report", lock_at_most_for = "30m", lock_at_least_for = "1m")]
+///     pub async fn run(&self) -> Result<()> {
+///         self.report_service.generate().await
+///     }
+/// }
+/// ```
+#[allow(non_snake_case)]
+#[proc_macro_attribute]
+pub fn SchedulerLock(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut name: Option<syn::LitStr> = None;
+    let mut at_most: Option<u64> = None;
+    let mut at_least: Option<u64> = None;
+    let mut krate = default_core_path();
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("name") {
+            name = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("lock_at_most_for") {
+            at_most = Some(parse_duration_millis(&meta.value()?.parse()?)?);
+        } else if meta.path.is_ident("lock_at_least_for") {
+            at_least = Some(parse_duration_millis(&meta.value()?.parse()?)?);
+        } else if meta.path.is_ident("crate") {
+            krate = parse_core_path(&meta)?;
+        } else {
+            return Err(meta.error("未知的定时任务锁注解参数"));
+        }
+        Ok(())
+    });
+    parse_macro_input!(args with parser);
+    let function = parse_macro_input!(input as syn::ItemFn);
+
+    match expand_scheduler_lock(name, at_most, at_least, function, &krate) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// 展开 `#[SchedulerLock]`
+fn expand_scheduler_lock(
+    name: Option<syn::LitStr>,
+    at_most: Option<u64>,
+    at_least: Option<u64>,
+    function: syn::ItemFn,
+    krate: &syn::Path,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if function.sig.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(&function.sig, "#[SchedulerLock] 只能标注在异步方法上"));
+    }
+    let name = name.ok_or_else(|| syn::Error::new_spanned(&function.sig.ident, "定时任务锁注解缺少参数: name"))?;
+    let output = match &function.sig.output {
+        syn::ReturnType::Type(_, output) => output.as_ref().clone(),
+        syn::ReturnType::Default => syn::parse_quote!(()),
+    };
+
+    let at_most = at_most.unwrap_or(10 * 60 * 1000);
+    let at_least = at_least.unwrap_or(0);
+    let syn::ItemFn { attrs, vis, sig, block } = function;
+
+    let run = quote! {
+        #krate::lock::run_locked(
+            &*#krate::lock::lock_provider(),
+            #name,
+            #krate::lock::LockConfig {
+                lock_at_most_for: ::std::time::Duration::from_millis(#at_most),
+                lock_at_least_for: ::std::time::Duration::from_millis(#at_least),
+            },
+            async move {
+                let __value: #output = #block;
+                __value
+            },
+        )
+        .await
+    };
+
+    // 未获得锁时跳过本次执行
+    let body = if type_arg(&output, "Result").is_some() {
+        quote! {
+            match #run {
+                Ok(Some(__result)) => __result,
+                Ok(None) => Ok(()),
+                Err(__error) => Err(__error.into()),
+            }
+        }
+    } else {
+        quote! {
+            if let Err(__error) = #run {
+                #krate::lock::log_lock_failure(#name, &__error);
+            }
+        }
+    };
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis #sig {
+            #body
+        }
+    })
+}
+
+/// 定时任务注解
+/// 
+/// 标注在无参数的异步函数上，额外生成 `<函数名>_scheduled()`，返回 `rspring_jobs::ScheduledTask`，
+/// 调用 `start()` 后在当前进程中按以下方式之一执行：
+/// - `cron` - cron 表达式，按 UTC 计算，支持 6 段（含秒）和 5 段（不含秒）格式
+/// - `fixed_rate` - 固定频率，如 `"30s"`，上次未完成时推迟
+/// - `fixed_delay` - 上次执行完成后的等待时间
+/// 
+/// 其余参数：
+/// - `initial_delay` - 首次执行前的等待时间，不能与 `cron` 同时使用
+/// - `name` - 任务名称，默认为函数名
+/// - `crate`、`jobs_crate` - 重新导出时 `rspring_core`、`rspring_jobs` 的路径
+/// 
+/// 生成的代码引用 `rspring_jobs`，使用方须依赖该 crate，或通过 `jobs_crate` 指定重新导出的路径。
+/// cron 表达式和时长在编译期校验，无效或已没有下一次执行时间（如年份已过去）时编译错误指向出错的参数。
+/// 函数须返回 `()` 或 `Result<T, E>`（`E` 实现 `Into<rspring_core::Error>`），返回错误时只记录日志
+/// 
+/// # 示例
+/// 
+/// ```rust
+/// #[Scheduled(cron = "0 */5 * * * *")]
+/// async fn refresh_rates() -> Result<()> {
+///     RATES.refresh().await
+/// }
+/// 
+/// #[Scheduled(fixed_delay = "30s", initial_delay = "1m")]
+/// async fn purge_sessions() {
+///     SESSIONS.purge_expired().await;
+/// }
+/// 
+/// let running = refresh_rates_scheduled().start()?;
+/// ```
+#[allow(non_snake_case)]
+#[proc_macro_attribute]
+pub fn Scheduled(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut name: Option<syn::LitStr> = None;
+    let mut trigger: Option<(syn::Path, proc_macro2::TokenStream)> = None;
+    let mut initial_delay: Option<(syn::LitStr, u64)> = None;
+    let mut krate = default_core_path();
+    let mut jobs: syn::Path = syn::parse_quote!(::rspring_jobs);
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("cron") || meta.path.is_ident("fixed_rate") || meta.path.is_ident("fixed_delay") {
+            let value: syn::LitStr = meta.value()?.parse()?;
+            if trigger.is_some() {
+                return Err(meta.error("cron、fixed_rate 和 fixed_delay 只能指定其中一个"));
+            }
+            let expanded = if meta.path.is_ident("cron") {
+                validate_cron(&value)?;
+                quote! { Cron(::std::string::String::from(#value)) }
+            } else {
+                let millis = parse_interval_millis(&value)?;
+                if meta.path.is_ident("fixed_rate") {
+                    quote! { FixedRate(::std::time::Duration::from_millis(#millis)) }
+                } else {
+                    quote! { FixedDelay(::std::time::Duration::from_millis(#millis)) }
+                }
+            };
+            trigger = Some((meta.path.clone(), expanded));
+        } else if meta.path.is_ident("initial_delay") {
+            let value: syn::LitStr = meta.value()?.parse()?;
+            let millis = parse_duration_millis(&value)?;
+            initial_delay = Some((value, millis));
+        } else if meta.path.is_ident("name") {
+            name = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("crate") {
+            krate = parse_core_path(&meta)?;
+        } else if meta.path.is_ident("jobs_crate") {
+            jobs = parse_core_path(&meta)?;
+        } else {
+            return Err(meta.error("未知的定时任务注解参数"));
+        }
+        Ok(())
+    });
+    parse_macro_input!(args with parser);
+    let function = parse_macro_input!(input as syn::ItemFn);
+
+    match expand_scheduled(name, trigger, initial_delay, function, &krate, &jobs) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// 解析 `fixed_rate`、`fixed_delay` 的执行间隔，返回毫秒数，间隔不能为 0
+fn parse_interval_millis(value: &syn::LitStr) -> syn::Result<u64> {
+    match parse_duration_millis(value)? {
+        0 => Err(syn::Error::new_spanned(value, "执行间隔不能为 0")),
+        millis => Ok(millis),
+    }
+}
+
+/// 在编译期校验 cron 表达式，5 段格式补全秒为 0，与 `rspring_jobs` 运行时的解析方式一致
+///
+/// 没有下一次执行时间的表达式在 `start()` 时才会失败，因此同样在编译期拒绝
+fn validate_cron(value: &syn::LitStr) -> syn::Result<()> {
+    use std::str::FromStr;
+
+    const FIELDS: [&str; 7] = ["秒", "分", "时", "日", "月", "星期", "年"];
+    let expression = value.value();
+    let mut segments: Vec<&str> = expression.split_whitespace().collect();
+    if segments.len() == 5 {
+        segments.insert(0, "0");
+    }
+    if !(6..=7).contains(&segments.len()) {
+        return Err(syn::Error::new_spanned(
+            value,
+            format!("无效的 cron 表达式 \"{}\": 应为 5 段（分 时 日 月 星期）或 6 段（秒 分 时 日 月 星期）", expression),
+        ));
+    }
+
+    let parse = |segments: &[&str]| cron::Schedule::from_str(&segments.join(" "));
+    match parse(&segments) {
+        Ok(schedule) if schedule.upcoming(chrono::Utc).next().is_none() => {
+            Err(syn::Error::new_spanned(
+                value,
+                format!("无效的 cron 表达式 \"{}\": 没有下一次执行时间", expression),
+            ))
+        }
+        Ok(_) => Ok(()),
+        Err(error) => {
+            // 逐段替换为 `*`，找出导致解析失败的段
+            let invalid = (0..segments.len()).find(|&index| {
+                let mut replaced = segments.clone();
+                replaced[index] = "*";
+                parse(&replaced).is_ok()
+            });
+            let message = match invalid {
+                Some(index) => format!(
+                    "无效的 cron 表达式 \"{}\": {}字段 \"{}\" 无效",
+                    expression, FIELDS[index], segments[index]
+                ),
+                None => format!("无效的 cron 表达式 \"{}\": {}", expression, error),
+            };
+            Err(syn::Error::new_spanned(value, message))
+        }
+    }
+}
+
+/// 展开 `#[Scheduled]`
+fn expand_scheduled(
+    name: Option<syn::LitStr>,
+    trigger: Option<(syn::Path, proc_macro2::TokenStream)>,
+    initial_delay: Option<(syn::LitStr, u64)>,
+    function: syn::ItemFn,
+    krate: &syn::Path,
+    jobs: &syn::Path,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if function.sig.asyncness.is_none() || !function.sig.inputs.is_empty() {
+        return Err(syn::Error::new_spanned(&function.sig, "#[Scheduled] 只能标注在无参数的异步函数上"));
+    }
+    let (path, trigger) = trigger.ok_or_else(|| {
+        syn::Error::new_spanned(&function.sig.ident, "定时任务注解缺少参数: cron、fixed_rate 或 fixed_delay")
+    })?;
+    let initial_delay = match initial_delay {
+        Some((value, _)) if path.is_ident("cron") => {
+            return Err(syn::Error::new_spanned(value, "initial_delay 不能与 cron 同时使用"));
+        }
+        Some((_, millis)) => Some(quote! { .initial_delay(::std::time::Duration::from_millis(#millis)) }),
+        None => None,
+    };
+
+    let vis = &function.vis;
+    let ident = &function.sig.ident;
+    let scheduled = format_ident!("{}_scheduled", ident);
+    let name = name.map_or_else(|| ident.to_string(), |name| name.value());
+    let returns_result = match &function.sig.output {
+        syn::ReturnType::Type(_, output) => type_arg(output, "Result").is_some(),
+        syn::ReturnType::Default => false,
+    };
+    let call = if returns_result {
+        quote! {
+            let __result: ::std::result::Result<(), #krate::Error> =
+                #ident().await.map(|_| ()).map_err(::std::convert::Into::into);
+            __result
+        }
+    } else {
+        quote! {
+            #ident().await;
+            Ok(())
+        }
+    };
+
+    Ok(quote! {
+        #function
+
+        /// 创建按注解参数执行的定时任务
+        #vis fn #scheduled() -> #jobs::ScheduledTask {
+            #jobs::ScheduledTask::new(#name, #jobs::Trigger::#trigger, || async { #call })
+            #initial_delay
+        }
+    })
+}
+
+/// SQS 监听注解
+/// 
+/// 标注在只有一个参数的异步函数上，额外生成 `<函数名>_listener()`，返回绑定到指定队列的
+/// `rspring_sqs::SqsListenerEndpoint`，注册到 `SqsListenerContainer` 后开始接收消息。
+/// 参数类型为 `SqsMessage` 时直接传入原始消息，否则将 JSON 消息体反序列化为参数类型
+/// 
+/// 队列可以是队列 URL、`[aws.sqs.queues]` 中的别名或队列名。函数返回错误时消息不会被删除，
+/// 可见性超时后由 SQS 重新投递
+/// 
+/// 队列之后可以指定消费设置，`[aws.sqs.listeners.<队列>]` 中的配置优先：
+/// - `concurrency` - 并发消费者数，默认 1
+/// - `batch_size` - 单次接收的最大消息数，默认为 `[aws.sqs]` 中的 `max_messages`
+/// - `max_poll_interval` - 一批消息的最长处理时间，如 `"5m"`，超过后不再延长可见性超时
+/// 
+/// # 示例
+/// 
+/// ```rust
+/// #[SqsListener("orders", concurrency = 4, batch_size = 5, max_poll_interval = "5m")]
+/// async fn on_order(event: OrderCreated) -> Result<()> {
+///     tracing::info!("收到订单 {}", event.order_id);
+///     Ok(())
+/// }
+/// 
+/// SqsListenerContainer::new(sqs).listener(on_order_listener()).start();
+/// ```
+#[allow(non_snake_case)]
+#[proc_macro_attribute]
+pub fn SqsListener(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut settings = Vec::new();
+    let parser = |input: syn::parse::ParseStream| -> syn::Result<syn::LitStr> {
+        let queue: syn::LitStr = input.parse()?;
+        while !input.is_empty() {
+            input.parse::<syn::Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let name: syn::Ident = input.parse()?;
+            input.parse::<syn::Token![=]>()?;
+            let setting = match name.to_string().as_str() {
+                "concurrency" => {
+                    let value = input.parse::<syn::LitInt>()?.base10_parse::<usize>()?;
+                    quote! { .concurrency(#value) }
+                }
+                "batch_size" => {
+                    let value = input.parse::<syn::LitInt>()?.base10_parse::<u32>()?;
+                    quote! { .batch_size(#value) }
+                }
+                "max_poll_interval" => {
+                    let millis = parse_duration_millis(&input.parse()?)?;
+                    quote! { .max_poll_interval(::std::time::Duration::from_millis(#millis)) }
+                }
+                _ => return Err(syn::Error::new_spanned(&name, "未知的 SQS 监听注解参数")),
+            };
+            settings.push(setting);
+        }
+        Ok(queue)
+    };
+    let queue = parse_macro_input!(args with parser);
+    let function = parse_macro_input!(input as syn::ItemFn);
+
+    match expand_sqs_listener(&queue, &settings, function) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// 展开 `#[SqsListener]`
+fn expand_sqs_listener(
+    queue: &syn::LitStr,
+    settings: &[proc_macro2::TokenStream],
+    function: syn::ItemFn,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if function.sig.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(&function.sig, "SQS 监听注解只能标注在异步函数上"));
+    }
+    let param = match function.sig.inputs.iter().collect::<Vec<_>>().as_slice() {
+        [syn::FnArg::Typed(param)] => param.clone(),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &function.sig.inputs,
+                "SQS 监听函数必须有且只有一个消息参数",
+            ))
+        }
+    };
+
+    let vis = &function.vis;
+    let name = &function.sig.ident;
+    let listener = format_ident!("{}_listener", name);
+    let ty = &param.ty;
+
+    let is_raw_message = matches!(
+        ty.as_ref(),
+        syn::Type::Path(path) if path.path.segments.last().is_some_and(|segment| segment.ident == "SqsMessage")
+    );
+    let call = if is_raw_message {
+        quote! { #name(__message).await }
+    } else {
+        quote! {
+            let __payload: #ty = __message.payload()?;
+            #name(__payload).await
+        }
+    };
+
+    Ok(quote! {
+        #function
+
+        /// 创建绑定到 SQS 队列的监听端点
+        #vis fn #listener() -> ::rspring_sqs::SqsListenerEndpoint {
+            ::rspring_sqs::SqsListenerEndpoint::new(
+                #queue,
+                |__message: ::rspring_sqs::SqsMessage| async move { #call },
+            )
+            #(#settings)*
+        }
+    })
+}
+
+/// 事务注解
+/// 
+/// 标注在返回 `Result<T, E>`（`E` 实现 `From<rspring_core::Error>`）的异步方法上，通过全局事务管理器
+/// 在事务中执行方法体：返回 `Ok` 时提交，返回 `Err` 时回滚；已在事务中时加入当前事务
+/// 
+/// # 示例
+/// 
+/// ```rust
+/// impl OrderService {
+///     #[Transactional]
+///     pub async fn place_order(&self, order: Order) -> Result<Order> {
+///         let order = self.order_repository.save(order).await?;
+///         publish_event(OrderPlaced { id: order.id }).await?;
+///         Ok(order)
+///     }
+/// }
+/// ```
+#[allow(non_snake_case)]
+#[proc_macro_attribute]
+pub fn Transactional(args: TokenStream, input: TokenStream) -> TokenStream {
+    let function = parse_macro_input!(input as syn::ItemFn);
+
+    match core_path_args(args.into()).and_then(|krate| expand_transactional(function, &krate)) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// 展开 `#[Transactional]`
+fn expand_transactional(function: syn::ItemFn, krate: &syn::Path) -> syn::Result<proc_macro2::TokenStream> {
+    if function.sig.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(&function.sig, "#[Transactional] 只能标注在异步方法上"));
+    }
+    let output = match &function.sig.output {
+        syn::ReturnType::Type(_, output) if type_arg(output, "Result").is_some() => output.as_ref().clone(),
+        _ => return Err(syn::Error::new_spanned(&function.sig, "#[Transactional] 方法必须返回 Result")),
+    };
+    let syn::ItemFn { attrs, vis, sig, block } = function;
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis #sig {
+            #krate::transaction::transactional(async move {
+                let __value: #output = #block;
+                __value
+            })
+            .await
+        }
+    })
+}
+
+/// 事务事件监听注解
+/// 
+/// 标注在只有一个事件参数、返回 `Result<()>` 的异步函数上，额外生成 `<函数名>_listener()`，
+/// 返回绑定到事务阶段的 `ApplicationListener`，注册到事件发布器后生效：
+/// - `phase` - 事务阶段，`"before_commit"`、`"after_commit"`、`"after_rollback"` 或
+///   `"after_completion"`，默认 `"after_commit"`
+/// - `fallback_execution` - 不在事务中发布时是否立即执行，默认 `false`
+/// 
+/// # 示例
+/// 
+/// ```rust
+/// #[TransactionalEventListener(phase = "after_commit")]
+/// async fn send_confirmation(event: OrderPlaced) -> Result<()> {
+///     mail_service().send_order_confirmation(event.id).await
+/// }
+/// 
+/// event_publisher().register(send_confirmation_listener());
+/// ```
+#[allow(non_snake_case)]
+#[proc_macro_attribute]
+pub fn TransactionalEventListener(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut phase: Option<syn::LitStr> = None;
+    let mut fallback_execution = false;
+    let mut krate = default_core_path();
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("phase") {
+            phase = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("fallback_execution") {
+            fallback_execution = meta.value()?.parse::<syn::LitBool>()?.value;
+        } else if meta.path.is_ident("crate") {
+            krate = parse_core_path(&meta)?;
+        } else {
+            return Err(meta.error("未知的事务事件监听注解参数"));
+        }
+        Ok(())
+    });
+    parse_macro_input!(args with parser);
+    let function = parse_macro_input!(input as syn::ItemFn);
+
+    match expand_transactional_event_listener(phase, fallback_execution, function, &krate) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// 展开 `#[TransactionalEventListener]`
+fn expand_transactional_event_listener(
+    phase: Option<syn::LitStr>,
+    fallback_execution: bool,
+    function: syn::ItemFn,
+    krate: &syn::Path,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if function.sig.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(&function.sig, "事务事件监听注解只能标注在异步函数上"));
+    }
+    if !matches!(function.sig.inputs.iter().collect::<Vec<_>>().as_slice(), [syn::FnArg::Typed(_)]) {
+        return Err(syn::Error::new_spanned(
+            &function.sig.inputs,
+            "事务事件监听函数必须有且只有一个事件参数",
+        ));
+    }
+
+    let phase = match phase {
+        None => format_ident!("AfterCommit"),
+        Some(phase) => match phase.value().as_str() {
+            "before_commit" => format_ident!("BeforeCommit"),
+            "after_commit" => format_ident!("AfterCommit"),
+            "after_rollback" => format_ident!("AfterRollback"),
+            "after_completion" => format_ident!("AfterCompletion"),
+            _ => return Err(syn::Error::new_spanned(&phase, "无效的事务阶段")),
+        },
+    };
+
+    let vis = &function.vis;
+    let name = &function.sig.ident;
+    let listener = format_ident!("{}_listener", name);
+
+    Ok(quote! {
+        #function
+
+        /// 创建绑定到事务阶段的事件监听器
+        #vis fn #listener() -> #krate::event::ApplicationListener {
+            #krate::event::ApplicationListener::transactional(#krate::transaction::TransactionPhase::#phase, #name)
+                .fallback_execution(#fallback_execution)
+        }
+    })
+}
+
+/// 方法级权限注解
+///
+/// 标注在返回 `Result<T, E>`（`E` 实现 `From<rspring_core::Error>`）的方法上，执行方法体之前
+/// 按访问控制表达式检查当前认证主体：未认证时返回 `Error::Unauthorized`（401），
+/// 权限不足时返回 `Error::Forbidden`（403）。可用于控制器方法和服务方法，
+/// 表达式语法见 `AccessExpression`，在编译期解析，语法错误或不支持的函数导致编译失败
+///
+/// # 示例
+///
+/// ```rust
+/// #[RequestMapping("/api/users")]
+/// impl UserController {
+///     #[DeleteMapping("/{id}")]
+///     #[PreAuthorize("hasRole('ADMIN')")]
+///     pub async fn delete_user(&self, #[PathVariable] id: u64) -> WebResult<()> {
+///         self.user_service.delete(id).await?;
+///         Ok(())
+///     }
+/// }
+///
+/// impl ReportService {
+///     #[PreAuthorize("hasAnyRole('ADMIN', 'AUDITOR') or hasAuthority('reports:read')")]
+///     pub async fn monthly_report(&self) -> Result<Report> {
+///         // ...
+///     }
+/// }
+/// ```
+#[allow(non_snake_case)]
+#[proc_macro_attribute]
+pub fn PreAuthorize(args: TokenStream, input: TokenStream) -> TokenStream {
+    // 表达式之后可以跟 `crate = "..."`
+    let parser = |input: syn::parse::ParseStream| -> syn::Result<(syn::LitStr, syn::Path)> {
+        let expression = input.parse()?;
+        if !input.is_empty() {
+            input.parse::<syn::Token![,]>()?;
+        }
+        Ok((expression, core_path_args(input.parse()?)?))
+    };
+    let (expression, krate) = parse_macro_input!(args with parser);
+    let function = parse_macro_input!(input as syn::ItemFn);
+
+    match expand_pre_authorize(expression, function, &krate) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// 展开 `#[PreAuthorize]`
+fn expand_pre_authorize(
+    expression: syn::LitStr,
+    function: syn::ItemFn,
+    krate: &syn::Path,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if expression.value().trim().is_empty() {
+        return Err(syn::Error::new_spanned(&expression, "访问控制表达式不能为空"));
+    }
+    // 与运行时使用同一个解析器，表达式错误在编译期报告
+    if let Err(error) = crate::security::AccessExpression::parse(&expression.value()) {
+        return Err(syn::Error::new_spanned(&expression, format!("无效的访问控制表达式: {}", error)));
+    }
+    // 返回类型的最后一段以 `Result` 结尾即可，如 `Result`、`WebResult`
+    let returns_result = match &function.sig.output {
+        syn::ReturnType::Type(_, output) => matches!(
+            output.as_ref(),
+            syn::Type::Path(path) if path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident.to_string().ends_with("Result"))
+        ),
+        syn::ReturnType::Default => false,
+    };
+    if !returns_result {
+        return Err(syn::Error::new_spanned(&function.sig, "#[PreAuthorize] 方法必须返回 Result"));
+    }
+    let syn::ItemFn { attrs, vis, sig, block } = function;
+    let statements = &block.stmts;
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis #sig {
+            static __ACCESS_EXPRESSION: ::std::sync::OnceLock<#krate::security::AccessExpression> =
+                ::std::sync::OnceLock::new();
+            let __access_expression = __ACCESS_EXPRESSION.get_or_init(|| {
+                #krate::security::AccessExpression::parse(#expression).expect("访问控制表达式已在编译期校验")
+            });
+            if let Err(__error) = __access_expression.check() {
+                return Err(__error.into());
+            }
+            #(#statements)*
+        }
+    })
+}
+
+/// 以指定身份执行注解
+///
+/// 标注在定时任务、消息监听器等没有认证主体的异步入口方法上，方法体在指定的认证主体下执行，
+/// 其中调用的 `#[PreAuthorize]` 服务方法按该主体检查权限：
+/// - `name` - 主体名称（必填）
+/// - `roles` - 角色列表，自动添加 `ROLE_` 前缀
+/// - `authorities` - 其他权限列表
+///
+/// 注解作用于方法体，同一方法上的 `#[PreAuthorize]` 仍按调用方的主体检查
+///
+/// # 示例
+///
+/// ```rust
+/// impl ReportJob {
+///     #[SchedulerLock(name = "monthly-report")]
+///     #[RunAs(name = "report-scheduler", roles = ["AUDITOR"])]
+///     pub async fn run(&self) -> Result<()> {
+///         // monthly_report 标注了 #[PreAuthorize("hasRole('AUDITOR')")]
+///         let report = self.report_service.monthly_report().await?;
+///         self.mailer.send(report).await
+///     }
+/// }
+/// ```
+#[allow(non_snake_case)]
+#[proc_macro_attribute]
+pub fn RunAs(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut name: Option<syn::LitStr> = None;
+    let mut authorities: Vec<String> = Vec::new();
+    let mut krate = default_core_path();
+    let parser = syn::meta::parser(|meta| {
+        let is_roles = meta.path.is_ident("roles");
+        if meta.path.is_ident("name") {
+            name = Some(meta.value()?.parse()?);
+        } else if is_roles || meta.path.is_ident("authorities") {
+            let values: syn::ExprArray = meta.value()?.parse()?;
+            for value in values.elems {
+                let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(value), .. }) = value else {
+                    return Err(syn::Error::new_spanned(value, "角色和权限须为字符串字面量"));
+                };
+                let value = value.value();
+                if is_roles && !value.starts_with("ROLE_") {
+                    authorities.push(format!("ROLE_{}", value));
+                } else {
+                    authorities.push(value);
+                }
+            }
+        } else if meta.path.is_ident("crate") {
+            krate = parse_core_path(&meta)?;
+        } else {
+            return Err(meta.error("未知的 RunAs 注解参数"));
+        }
+        Ok(())
+    });
+    parse_macro_input!(args with parser);
+    let function = parse_macro_input!(input as syn::ItemFn);
+
+    match expand_run_as(name, authorities, function, &krate) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// 展开 `#[RunAs]`
+fn expand_run_as(
+    name: Option<syn::LitStr>,
+    authorities: Vec<String>,
+    function: syn::ItemFn,
+    krate: &syn::Path,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if function.sig.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(&function.sig, "#[RunAs] 只能标注在异步方法上"));
+    }
+    let name = name.ok_or_else(|| syn::Error::new_spanned(&function.sig.ident, "RunAs 注解缺少参数: name"))?;
+    let output = match &function.sig.output {
+        syn::ReturnType::Type(_, output) => output.as_ref().clone(),
+        syn::ReturnType::Default => syn::parse_quote!(()),
+    };
+    let count = authorities.len();
+    let syn::ItemFn { attrs, vis, sig, block } = function;
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis #sig {
+            let __authorities: [&str; #count] = [#(#authorities),*];
+            #krate::security::with_principal(
+                #krate::security::Principal::new(#name).with_authorities(__authorities),
+                async move {
+                    let __value: #output = #block;
+                    __value
+                },
+            )
+            .await
+        }
+    })
+}
+
+/// 配置类注解
+/// 
+/// 标记一个结构体为配置类，按配置前缀从配置文件中绑定。配置前缀默认由类型名去掉
+/// `Configuration`、`Config` 或 `Properties` 后缀并转换为小写下划线命名得到，
+/// 如 `DatabaseConfig` 为 `database`、`BatchJobProperties` 为 `batch_job`，
+/// 可以通过 `#[config(prefix = "app.batch")]` 指定
+/// 
+/// 在 `#[rspring_application(configurations(...))]` 中列出的配置类在启动时绑定，
+/// 并以 `Arc<T>` 单例注册到容器，组件可以通过 `#[component(inject)]` 注入；也可以通过
+/// `ApplicationContext::bind_configuration` 手动绑定
+/// 
+/// # 示例
+/// 
+/// ```rust
+/// #[derive(Configuration, Deserialize)]
+/// pub struct DatabaseConfig {
+///     pub host: String,
+///     pub port: u16,
+/// }
+/// 
+/// #[derive(Configuration, Deserialize)]
+/// #[config(prefix = "app.batch")]
+/// pub struct BatchSettings {
+///     pub size: usize,
+/// }
+/// 
+/// #[derive(Service)]
+/// pub struct ImportService {
+///     #[component(inject)]
+///     batch: Arc<BatchSettings>,
+/// }
+/// 
+/// #[rspring_application(configurations(DatabaseConfig, BatchSettings))]
+/// pub struct Application;
+/// ```
+#[proc_macro_derive(Configuration, attributes(rspring, config))]
+pub fn configuration_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let (krate, prefix) = match core_path(&input.attrs).and_then(|krate| Ok((krate, config_prefix(&input)?))) {
+        Ok(result) => result,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let expanded = quote! {
+        impl #impl_generics #krate::config::properties::Configuration for #name #ty_generics #where_clause {
+            fn prefix() -> &'static str {
+                #prefix
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// 读取 `#[config(prefix = "...")]` 指定的配置前缀，未指定时由类型名得到
+fn config_prefix(input: &DeriveInput) -> syn::Result<String> {
+    let mut prefix = None;
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("config")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("prefix") {
+                prefix = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+                Ok(())
+            } else {
+                Err(meta.error("不支持的参数，可用参数: prefix"))
+            }
+        })?;
+    }
+    if let Some(prefix) = prefix {
+        return Ok(prefix);
+    }
+
+    // DatabaseConfig -> database
+    let name = input.ident.to_string();
+    let name = ["Configuration", "Config", "Properties"]
+        .iter()
+        .find_map(|suffix| name.strip_suffix(suffix).filter(|name| !name.is_empty()))
+        .unwrap_or(&name);
+    Ok(name.chars().enumerate().fold(String::new(), |mut acc, (i, c)| {
+        if c.is_uppercase() && i > 0 {
+            acc.push('_');
+        }
+        acc.extend(c.to_lowercase());
+        acc
+    }))
+}
+
+/// 配置类注解
+/// 
+/// 标注在 impl 块上，为类型实现 `BeanConfiguration`，其中 `#[Bean]` 方法的返回值作为单例组件
+/// 注册到容器。通过 `Container::register_configuration` 注册配置类后，容器在自动装配时
+/// 按依赖顺序调用 Bean 方法：
+/// - 方法可以接收 `&self`，其余参数须为 `Arc<T>`，注入容器中的单例组件并写入依赖图
+/// - 返回 `Result<T, E>`（`E` 可转换为 `rspring_core::Error`）时 Bean 类型为 `T`，返回错误时自动装配失败
+/// - `#[Bean(name = "...")]` 指定组件名称，默认为方法名
+/// 
+/// # 示例
+/// 
+/// ```rust
+/// pub struct MailConfiguration;
+/// 
+/// #[BeanConfiguration]
+/// impl MailConfiguration {
+///     #[Bean]
+///     fn smtp_client(&self, settings: Arc<MailSettings>) -> Result<SmtpClient> {
+///         SmtpClient::connect(&settings.host)
+///     }
+/// 
+///     #[Bean(name = "mailer")]
+///     fn mailer(&self, client: Arc<SmtpClient>) -> Mailer {
+///         Mailer::new(client)
+///     }
+/// }
+/// 
+/// context.register_configuration(MailConfiguration).await;
+/// ```
+#[allow(non_snake_case)]
+#[proc_macro_attribute]
+pub fn BeanConfiguration(args: TokenStream, input: TokenStream) -> TokenStream {
+    let item_impl = parse_macro_input!(input as syn::ItemImpl);
+
+    match core_path_args(args.into()).and_then(|krate| expand_bean_configuration(item_impl, &krate)) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Bean 工厂方法注解
+/// 
+/// 只能用于 `#[BeanConfiguration]` 标注的 impl 块中，见 `#[BeanConfiguration]`
+#[allow(non_snake_case)]
+#[proc_macro_attribute]
+pub fn Bean(_args: TokenStream, input: TokenStream) -> TokenStream {
+    let input = proc_macro2::TokenStream::from(input);
+    let error = syn::Error::new(proc_macro2::Span::call_site(), "#[Bean] 只能用于 #[BeanConfiguration] 标注的 impl 块中")
+        .to_compile_error();
+    TokenStream::from(quote! { #error #input })
+}
+
+/// 展开 `#[BeanConfiguration]`，移除方法上的 `#[Bean]` 并生成 Bean 定义
+fn expand_bean_configuration(
+    mut item_impl: syn::ItemImpl,
+    krate: &syn::Path,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut definitions = Vec::new();
+    for item in &mut item_impl.items {
+        let syn::ImplItem::Fn(method) = item else {
+            continue;
+        };
+        let Some(index) = method.attrs.iter().position(|attr| attr.path().is_ident("Bean")) else {
+            continue;
+        };
+        let attr = method.attrs.remove(index);
+        definitions.push(bean_definition(&attr, &method.sig, krate)?);
+    }
+
+    let self_ty = &item_impl.self_ty;
+    let (impl_generics, _, where_clause) = item_impl.generics.split_for_impl();
+    Ok(quote! {
+        #item_impl
+
+        impl #impl_generics #krate::BeanConfiguration for #self_ty #where_clause {
+            fn bean_definitions(self: ::std::sync::Arc<Self>) -> Vec<#krate::BeanDefinition> {
+                vec![#(#definitions),*]
+            }
+        }
+    })
+}
+
+/// 生成 `#[Bean]` 方法的 Bean 定义
+fn bean_definition(
+    attr: &syn::Attribute,
+    sig: &syn::Signature,
+    krate: &syn::Path,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut name = syn::LitStr::new(&sig.ident.to_string(), sig.ident.span());
+    if !matches!(attr.meta, syn::Meta::Path(_)) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = meta.value()?.parse()?;
+                Ok(())
+            } else {
+                Err(meta.error("不支持的参数，可用参数: name"))
+            }
+        })?;
+    }
+    if sig.asyncness.is_some() {
+        return Err(syn::Error::new_spanned(sig, "Bean 方法不能是异步方法"));
+    }
+
+    let mut has_receiver = false;
+    let mut dependencies = Vec::new();
+    for input in &sig.inputs {
+        match input {
+            syn::FnArg::Receiver(receiver) => {
+                if receiver.reference.is_none() || receiver.mutability.is_some() {
+                    return Err(syn::Error::new_spanned(receiver, "Bean 方法的接收者须为 &self"));
+                }
+                has_receiver = true;
+            }
+            syn::FnArg::Typed(param) => match type_arg(&param.ty, "Arc") {
+                Some(dependency) => dependencies.push(dependency),
+                None => return Err(syn::Error::new_spanned(&param.ty, "Bean 方法的参数须为 Arc<T>")),
+            },
+        }
+    }
+
+    let output = match &sig.output {
+        syn::ReturnType::Type(_, output) => output.as_ref(),
+        syn::ReturnType::Default => return Err(syn::Error::new_spanned(sig, "Bean 方法须返回组件")),
+    };
+    let method = &sig.ident;
+    let args = quote! { #(__container.require::<#dependencies>()?),* };
+    let call = if has_receiver {
+        quote! { __this.#method(#args) }
+    } else {
+        quote! { Self::#method(#args) }
+    };
+    let (bean, body) = match type_arg(output, "Result") {
+        Some(bean) => (bean, quote! { Ok(#call?) }),
+        None => (output, quote! { Ok(#call) }),
+    };
+    let this = has_receiver.then(|| quote! { let __this = ::std::sync::Arc::clone(&self); });
+
+    Ok(quote! {
+        {
+            #this
+            #krate::BeanDefinition::new(
+                #name,
+                vec![#(#krate::BeanDependency::of::<#dependencies>()),*],
+                move |__container: &#krate::Container| -> #krate::Result<#bean> { #body },
+            )
+        }
+    })
+}
+
+/// 方法计时注解
+/// 
+/// 记录方法的耗时，方法可以是同步或异步的：
+/// - `name` - 指标名称，默认为 `method.timed`
+/// - 指标为直方图，单位为秒，标签为 `class`、`method` 和 `outcome`，返回 `Result` 的方法返回 `Err` 时 `outcome` 为 `error`
+/// - 有接收者的方法 `class` 为 `Self` 的类型名，其余为模块路径
+/// 
+/// 需要启用 `metrics` 特性，未启用时只执行方法
+/// 
+/// # 示例
+/// 
+/// ```rust
+/// impl OrderService {
+///     #[Timed]
+///     pub async fn place(&self, order: Order) -> Result<OrderId> {
+///         self.repository.save(order).await
+///     }
+/// 
+///     #[Timed(name = "orders.export")]
+///     pub fn export(&self) -> Vec<u8> {
+///         // ...
+///     }
+/// }
+/// ```
+#[allow(non_snake_case)]
+#[proc_macro_attribute]
+pub fn Timed(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut name: Option<syn::LitStr> = None;
+    let mut krate = default_core_path();
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("name") {
+            name = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("crate") {
+            krate = parse_core_path(&meta)?;
+        } else {
+            return Err(meta.error("不支持的参数，可用参数: name, crate"));
+        }
+        Ok(())
+    });
+    parse_macro_input!(args with parser);
+    let function = parse_macro_input!(input as syn::ItemFn);
+
+    let metric = match name {
+        Some(name) => quote! { #name },
+        None => quote! { #krate::instrumentation::METHOD_TIMED_METRIC },
+    };
+    let method = function.sig.ident.to_string();
+    let class = instrumented_class(&function.sig);
+    let call = instrumented_call(&function);
+    let success = if returns_result(&function.sig) {
+        quote! { __value.is_ok() }
+    } else {
+        quote! { true }
+    };
+
+    let syn::ItemFn { attrs, vis, sig, .. } = function;
+    TokenStream::from(quote! {
+        #(#attrs)*
+        #vis #sig {
+            let __start = ::std::time::Instant::now();
+            let __value = #call;
+            #krate::instrumentation::record_timed(
+                #metric,
+                #class,
+                #method,
+                #success,
+                __start.elapsed(),
+            );
+            __value
+        }
+    })
+}
+
+/// 方法日志注解
+/// 
+/// 在以方法名命名的 tracing span 中执行方法，记录调用参数和耗时，方法可以是同步或异步的：
+/// - `level` - 日志级别，可选 `trace`、`debug`、`info`、`warn`、`error`，默认为 `info`
+/// - `redact(password, token)` - 日志中显示为 `******` 的参数
+/// - `skip(conn)` - 不记录的参数，未实现 `Debug` 的参数需要跳过
+/// - `result` - 记录返回值，返回值需要实现 `Debug`
+/// 
+/// 参数须绑定到参数名，模式绑定的参数编译错误。
+/// 参数以 `Debug` 格式记录；返回 `Result` 的方法返回 `Err` 时以 `warn` 级别记录错误，错误类型需要实现 `Debug`
+/// 
+/// # 示例
+/// 
+/// ```rust
+/// impl AccountService {
+///     #[Logged(level = "debug", redact(password), skip(session))]
+///     pub async fn login(&self, username: String, password: String, session: &mut Session) -> Result<Token> {
+///         // 日志: 开始执行 login(username = "alice", password = ******)
+///         // 日志: login 执行完成，耗时 12 ms
+///     }
+/// }
+/// ```
+#[allow(non_snake_case)]
+#[proc_macro_attribute]
+pub fn Logged(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut level = syn::Ident::new("INFO", proc_macro2::Span::call_site());
+    let mut redact: Vec<syn::Ident> = Vec::new();
+    let mut skip: Vec<syn::Ident> = Vec::new();
+    let mut result = false;
+    let mut krate = default_core_path();
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("level") {
+            let lit = meta.value()?.parse::<syn::LitStr>()?;
+            level = match lit.value().to_lowercase().as_str() {
+                name @ ("trace" | "debug" | "info" | "warn" | "error") => {
+                    syn::Ident::new(&name.to_uppercase(), lit.span())
+                }
+                _ => return Err(syn::Error::new_spanned(lit, "无效的日志级别，可选值: trace, debug, info, warn, error")),
+            };
+        } else if meta.path.is_ident("redact") {
+            meta.parse_nested_meta(|param| {
+                redact.push(param.path.require_ident()?.clone());
+                Ok(())
+            })?;
+        } else if meta.path.is_ident("skip") {
+            meta.parse_nested_meta(|param| {
+                skip.push(param.path.require_ident()?.clone());
+                Ok(())
+            })?;
+        } else if meta.path.is_ident("result") {
+            result = true;
+        } else if meta.path.is_ident("crate") {
+            krate = parse_core_path(&meta)?;
+        } else {
+            return Err(meta.error("不支持的参数，可用参数: level, redact, skip, result, crate"));
+        }
+        Ok(())
+    });
+    parse_macro_input!(args with parser);
+    let function = parse_macro_input!(input as syn::ItemFn);
+
+    match expand_logged(function, &level, &redact, &skip, result, &krate) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// 展开 `#[Logged]`
+fn expand_logged(
+    function: syn::ItemFn,
+    level: &syn::Ident,
+    redact: &[syn::Ident],
+    skip: &[syn::Ident],
+    result: bool,
+    krate: &syn::Path,
+) -> syn::Result<proc_macro2::TokenStream> {
+    // 模式绑定的参数没有名称，无法记录或脱敏
+    let mut params = Vec::new();
+    for input in &function.sig.inputs {
+        if let syn::FnArg::Typed(param) = input {
+            match param.pat.as_ref() {
+                syn::Pat::Ident(pat) => params.push(pat.ident.clone()),
+                pattern => {
+                    return Err(syn::Error::new_spanned(
+                        pattern,
+                        "#[Logged] 不支持模式绑定的参数，请绑定到参数名后在方法中解构",
+                    ))
+                }
+            }
+        }
+    }
+    if let Some(unknown) = redact.iter().chain(skip).find(|name| !params.contains(name)) {
+        return Err(syn::Error::new_spanned(unknown, format!("{} 不是方法的参数", unknown)));
+    }
+
+    // 开始执行 login(username = "alice", password = ******)
+    let method = function.sig.ident.to_string();
+    let mut shown = Vec::new();
+    let mut values = Vec::new();
+    for param in params.iter().filter(|param| !skip.contains(param)) {
+        if redact.contains(param) {
+            shown.push(format!("{} = {{}}", param));
+            values.push(quote! { #krate::instrumentation::REDACTED });
+        } else {
+            shown.push(format!("{} = {{:?}}", param));
+            values.push(quote! { #param });
+        }
+    }
+    let enter = format!("开始执行 {}({})", method, shown.join(", "));
+    let done = format!("{} 执行完成，耗时 {{}} ms", method);
+    let done_value = format!("{} 执行完成，耗时 {{}} ms，返回 {{:?}}", method);
+    let failed = format!("{} 执行失败，耗时 {{}} ms: {{:?}}", method);
+
+    let tracing = quote! { #krate::__private::tracing };
+    let finish = match (returns_result(&function.sig), result) {
+        (true, show) => {
+            let ok = if show {
+                quote! { #tracing::event!(#tracing::Level::#level, #done_value, __elapsed, __ok) }
+            } else {
+                quote! { #tracing::event!(#tracing::Level::#level, #done, __elapsed) }
+            };
+            quote! {
+                match &__value {
+                    Ok(__ok) => { #ok; }
+                    Err(__error) => #tracing::warn!(#failed, __elapsed, __error),
+                }
+            }
+        }
+        (false, true) => quote! { #tracing::event!(#tracing::Level::#level, #done_value, __elapsed, &__value); },
+        (false, false) => quote! { #tracing::event!(#tracing::Level::#level, #done, __elapsed); },
+    };
+
+    let class = instrumented_class(&function.sig);
+    let call = instrumented_call(&function);
+    let logged = quote! {
+        #tracing::event!(#tracing::Level::#level, #enter, #(#values),*);
+        let __start = ::std::time::Instant::now();
+        let __value = #call;
+        let __elapsed = __start.elapsed().as_millis();
+        #finish
+        __value
+    };
+    let body = if function.sig.asyncness.is_some() {
+        quote! {
+            #tracing::Instrument::instrument(async move { #logged }, __span).await
+        }
+    } else {
+        quote! {
+            let __entered = __span.enter();
+            #logged
+        }
+    };
+
+    let syn::ItemFn { attrs, vis, sig, .. } = function;
+    Ok(quote! {
+        #(#attrs)*
+        #vis #sig {
+            let __span = #tracing::span!(#tracing::Level::#level, #method, class = #class);
+            #body
+        }
+    })
+}
+
+/// 监控指标和日志中的类名，有接收者时为 `Self` 的类型名，否则为模块路径
+fn instrumented_class(sig: &syn::Signature) -> proc_macro2::TokenStream {
+    if matches!(sig.inputs.first(), Some(syn::FnArg::Receiver(_))) {
+        quote! { ::std::any::type_name::<Self>() }
+    } else {
+        quote! { module_path!() }
+    }
+}
+
+/// 判断方法是否返回 `Result` 或以 `Result` 结尾的别名
+fn returns_result(sig: &syn::Signature) -> bool {
+    match &sig.output {
+        syn::ReturnType::Type(_, ty) => match ty.as_ref() {
+            syn::Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident.to_string().ends_with("Result")),
+            _ => false,
+        },
+        syn::ReturnType::Default => false,
+    }
+}
+
+/// 调用原方法体的表达式，方法体中的 `return` 和 `?` 只结束方法体，之后的监控代码仍会执行
+fn instrumented_call(function: &syn::ItemFn) -> proc_macro2::TokenStream {
+    let block = &function.block;
+    // `impl Trait` 不能用于变量和闭包的类型标注
+    let output = match &function.sig.output {
+        syn::ReturnType::Type(_, ty) if !quote!(#ty).to_string().contains("impl ") => Some(quote! { #ty }),
+        syn::ReturnType::Type(_, _) => None,
+        syn::ReturnType::Default => Some(quote! { () }),
+    };
+    match (function.sig.asyncness.is_some(), output) {
+        (true, Some(output)) => quote! {
+            async move {
+                let __value: #output = #block;
+                __value
+            }
+            .await
+        },
+        (true, None) => quote! { async move #block.await },
+        (false, Some(output)) => quote! { (move || -> #output #block)() },
+        (false, None) => quote! { (move || #block)() },
+    }
+}
+
+/// 业务错误注解
+/// 
+/// 为业务错误枚举实现 `BusinessError`、`Display` 和 `std::error::Error`，并生成到 `Error` 和
+/// `ErrorResponse` 的转换，枚举需要同时派生 `Debug`，不能与 `thiserror::Error` 同时使用。
+/// 每个变体通过 `#[error(...)]` 声明：
+/// - `code` - 错误码，默认为变体名的大写下划线形式，如 `UserExists` 为 `USER_EXISTS`
+/// - `status` - HTTP 状态码，默认为 400，转换为 `Error` 时随错误携带，Web 层返回该状态码
+/// - `message` - 错误描述，必填，可以通过 `{field}` 引用命名字段，通过 `{0}` 引用元组字段
+/// 
+/// # 示例
+/// 
+/// ```rust
+/// #[derive(Debug, BusinessError)]
+/// pub enum UserError {
+///     #[error(code = "USER_EXISTS", status = 409, message = "用户 {name} 已存在")]
+///     Exists { name: String },
+/// 
+///     #[error(status = 423, message = "用户已被锁定，{0} 分钟后重试")]
+///     Locked(u32),
+/// 
+///     #[error(code = "QUOTA_EXCEEDED", status = 429, message = "超出调用配额")]
+///     Quota,
+/// }
+/// 
+/// pub async fn register(&self, name: &str) -> Result<User> {
+///     if self.repository.exists(name).await? {
+///         return Err(UserError::Exists { name: name.to_string() }.into());
+///     }
+///     // ...
+/// }
+/// ```
+#[proc_macro_derive(BusinessError, attributes(rspring, error))]
+pub fn business_error_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_business_error(&input) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// 展开 `#[derive(BusinessError)]`
+fn expand_business_error(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let krate = core_path(&input.attrs)?;
+    let name = &input.ident;
+    let syn::Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(name, "BusinessError 只能用于枚举"));
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut codes = Vec::new();
+    let mut statuses = Vec::new();
+    let mut messages = Vec::new();
+    for variant in &data.variants {
+        let (code, status, message) = business_error_args(variant)?;
+        let ident = &variant.ident;
+        codes.push(quote! { Self::#ident { .. } => #code });
+        statuses.push(quote! { Self::#ident { .. } => #status });
+
+        let (format, used) = business_message(&message, &variant.fields)?;
+        let pattern = match &variant.fields {
+            syn::Fields::Named(_) => quote! { Self::#ident { #(#used,)* .. } },
+            syn::Fields::Unnamed(fields) => {
+                let bindings = (0..fields.unnamed.len()).map(|index| {
+                    let binding = format_ident!("__{}", index);
+                    if used.contains(&binding) {
+                        quote! { #binding }
+                    } else {
+                        quote! { _ }
+                    }
+                });
+                quote! { Self::#ident(#(#bindings),*) }
+            }
+            syn::Fields::Unit => quote! { Self::#ident },
+        };
+        messages.push(quote! { #pattern => format!(#format, #(#used = #used),*) });
+    }
+    if data.variants.is_empty() {
+        return Err(syn::Error::new_spanned(name, "BusinessError 枚举至少需要一个变体"));
+    }
+
+    Ok(quote! {
+        impl #impl_generics #krate::error::BusinessError for #name #ty_generics #where_clause {
+            fn code(&self) -> &'static str {
+                match self {
+                    #(#codes,)*
+                }
+            }
+
+            fn status(&self) -> u16 {
+                match self {
+                    #(#statuses,)*
+                }
+            }
+
+            fn message(&self) -> String {
+                match self {
+                    #(#messages,)*
+                }
+            }
+        }
+
+        impl #impl_generics ::std::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(&#krate::error::BusinessError::message(self))
+            }
+        }
+
+        impl #impl_generics ::std::error::Error for #name #ty_generics #where_clause {}
+
+        impl #impl_generics ::std::convert::From<#name #ty_generics> for #krate::Error #where_clause {
+            fn from(error: #name #ty_generics) -> Self {
+                #krate::error::BusinessError::into_error(error)
+            }
+        }
+
+        impl #impl_generics ::std::convert::From<#name #ty_generics> for #krate::error::ErrorResponse #where_clause {
+            fn from(error: #name #ty_generics) -> Self {
+                #krate::error::ErrorResponse::new(
+                    #krate::error::BusinessError::code(&error),
+                    #krate::error::BusinessError::message(&error),
+                )
+            }
+        }
+    })
+}
+
+/// 读取变体的 `#[error(code = "...", status = 409, message = "...")]`
+fn business_error_args(variant: &syn::Variant) -> syn::Result<(String, u16, syn::LitStr)> {
+    use syn::ext::IdentExt;
+
+    // UserExists -> USER_EXISTS
+    let mut code = variant.ident.unraw().to_string().chars().enumerate().fold(String::new(), |mut acc, (i, c)| {
+        if c.is_uppercase() && i > 0 {
+            acc.push('_');
+        }
+        acc.extend(c.to_uppercase());
+        acc
+    });
+    let mut status = 400;
+    let mut message = None;
+    for attr in variant.attrs.iter().filter(|attr| attr.path().is_ident("error")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("code") {
+                code = meta.value()?.parse::<syn::LitStr>()?.value();
+            } else if meta.path.is_ident("status") {
+                let lit = meta.value()?.parse::<syn::LitInt>()?;
+                status = lit.base10_parse::<u16>()?;
+                if !(100..=599).contains(&status) {
+                    return Err(syn::Error::new_spanned(lit, "HTTP 状态码须在 100 到 599 之间"));
+                }
+            } else if meta.path.is_ident("message") {
+                message = Some(meta.value()?.parse::<syn::LitStr>()?);
+            } else {
+                return Err(meta.error("不支持的参数，可用参数: code, status, message"));
+            }
+            Ok(())
+        })?;
+    }
+    let message = message.ok_or_else(|| {
+        syn::Error::new_spanned(&variant.ident, "缺少错误描述，如 #[error(code = \"USER_EXISTS\", message = \"用户已存在\")]")
+    })?;
+    Ok((code, status, message))
+}
+
+/// 将错误描述中的 `{field}` 和 `{0}` 改写为命名参数，返回格式字符串和引用的字段绑定
+fn business_message(message: &syn::LitStr, fields: &syn::Fields) -> syn::Result<(String, Vec<syn::Ident>)> {
+    use syn::ext::IdentExt;
+
+    let source = message.value();
+    let mut format = String::new();
+    let mut used: Vec<syn::Ident> = Vec::new();
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        format.push(c);
+        if c == '}' && chars.peek() == Some(&'}') {
+            format.push(chars.next().unwrap_or('}'));
+            continue;
+        }
+        if c != '{' {
+            continue;
+        }
+        if chars.peek() == Some(&'{') {
+            format.push(chars.next().unwrap_or('{'));
+            continue;
+        }
+
+        let mut name = String::new();
+        while let Some(&c) = chars.peek() {
+            if c == '}' || c == ':' {
+                break;
+            }
+            name.push(c);
+            chars.next();
+        }
+        let name = name.trim();
+        let binding = match fields {
+            syn::Fields::Named(named) => named
+                .named
+                .iter()
+                .filter_map(|field| field.ident.clone())
+                .find(|ident| ident.unraw() == name),
+            syn::Fields::Unnamed(unnamed) => name
+                .parse::<usize>()
+                .ok()
+                .filter(|index| *index < unnamed.unnamed.len())
+                .map(|index| format_ident!("__{}", index)),
+            syn::Fields::Unit => None,
+        };
+        let Some(binding) = binding else {
+            return Err(syn::Error::new_spanned(
+                message,
+                format!("错误描述中的 {{{}}} 不是变体的字段", name),
+            ));
+        };
+        format.push_str(&binding.unraw().to_string());
+        if !used.contains(&binding) {
+            used.push(binding);
+        }
+    }
+    Ok((format, used))
+}
+
+/// 组件扫描宏
+/// 
+/// 注册列出的 `#[derive(Component)]`、`#[derive(Service)]` 和 `#[derive(Repository)]` 组件。
+/// 展开为 `|config: &ConfigurationManager| -> Result<Vec<BeanDefinition>>` 闭包，
+/// 通过 `ApplicationContext::scan_components` 注册，或在 `#[rspring_application(scan(...))]` 中列出组件：
+/// - 组件在自动装配时按依赖顺序创建：`#[component(inject)]` 字段从容器获取，`#[Value]` 字段在扫描时从配置读取，
+///   `#[component(default)]` 字段使用 `Default::default()`
+/// - `rspring_web` 的 `#[HttpClient]` 标注的 trait 列出生成的 `Http<Trait>` 客户端，
+///   `#[RestControllerAdvice]` 标注的类型通过 `advice(Type)` 列出，在自动装配时注册到全局异常处理器
+/// - 泛型组件或有其他字段的组件无法由容器创建，不生成 Bean 定义，列出时编译报错，需要手动注册
+/// 
+/// # 示例
+/// 
+/// ```rust
+/// let scan = component_scan!(UserService, UserRepository, HttpUserClient, advice(GlobalAdvice));
+/// context.scan_components(scan).await?;
+/// context.auto_wire().await?;
+/// 
+/// // 通过门面 crate 使用时指定框架路径
+/// let scan = component_scan!(crate = "my_platform::core", UserService);
+/// container.register_beans(scan(&config)?);
+/// ```
+#[proc_macro]
+pub fn component_scan(input: TokenStream) -> TokenStream {
+    let parser = |input: syn::parse::ParseStream| {
+        let mut krate = default_core_path();
+        if input.peek(syn::Token![crate]) && input.peek2(syn::Token![=]) {
+            input.parse::<syn::Token![crate]>()?;
+            input.parse::<syn::Token![=]>()?;
+            krate = input.parse::<syn::LitStr>()?.parse()?;
+            if !input.is_empty() {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+        let entries = input.parse_terminated(ScanEntry::parse, syn::Token![,])?;
+        Ok((krate, entries.into_iter().collect::<Vec<_>>()))
+    };
+
+    match syn::parse::Parser::parse(parser, input).and_then(|(krate, entries)| scan_components(&krate, &entries)) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// `component_scan!` 中列出的组件
+enum ScanEntry {
+    /// 组件类型，使用派生宏生成的 `__scan_definition`
+    Component(syn::Path),
+    /// `advice(Type)` 列出的控制器增强，使用 `#[RestControllerAdvice]` 生成的 `__advice_definition`
+    Advice(syn::Path),
+}
+
+impl syn::parse::Parse for ScanEntry {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::Ident) && input.peek2(syn::token::Paren) {
+            let ident: syn::Ident = input.parse()?;
+            if ident != "advice" {
+                return Err(syn::Error::new_spanned(ident, "不支持的参数，列出组件类型或 advice(Type)"));
+            }
+            let content;
+            syn::parenthesized!(content in input);
+            return Ok(Self::Advice(content.parse()?));
+        }
+        Ok(Self::Component(input.parse()?))
+    }
+}
+
+/// 展开组件扫描，生成返回列出组件的 Bean 定义的闭包
+fn scan_components(krate: &syn::Path, entries: &[ScanEntry]) -> syn::Result<proc_macro2::TokenStream> {
+    if entries.is_empty() {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "请列出要注册的组件，如 component_scan!(UserService, advice(GlobalAdvice))",
+        ));
+    }
+
+    let definitions = entries.iter().map(|entry| match entry {
+        ScanEntry::Component(ty) => quote! {
+            __definitions.push(#ty::__scan_definition(__config)?);
+        },
+        ScanEntry::Advice(ty) => quote! {
+            __definitions.push(#ty::__advice_definition());
+        },
+    });
+    Ok(quote! {
+        |__config: &#krate::ConfigurationManager| -> #krate::Result<::std::vec::Vec<#krate::BeanDefinition>> {
+            let mut __definitions = ::std::vec::Vec::new();
+            #(#definitions)*
+            Ok(__definitions)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试 `#[PreAuthorize]` 在编译期校验表达式
+    #[test]
+    fn test_pre_authorize_expression() {
+        let function: syn::ItemFn = syn::parse_quote! {
+            async fn delete_user(&self, id: u64) -> Result<()> {
+                Ok(())
+            }
+        };
+        let krate = default_core_path();
+        let expand = |expression: &str| {
+            let expression = syn::LitStr::new(expression, proc_macro2::Span::call_site());
+            expand_pre_authorize(expression, function.clone(), &krate)
+        };
+
+        assert!(expand("hasRole('ADMIN') or hasAuthority('users:write')").is_ok());
+        assert!(expand("hasRol('ADMIN')").unwrap_err().to_string().contains("hasRol"));
+        assert!(expand("hasRole('ADMIN'").is_err());
+        assert!(expand(" ").is_err());
+    }
+
+    /// 测试 cron 表达式的编译期校验
+    #[test]
+    fn test_validate_cron() {
+        let validate = |expression: &str| {
+            validate_cron(&syn::LitStr::new(expression, proc_macro2::Span::call_site()))
+                .map_err(|error| error.to_string())
+        };
+
+        assert!(validate("0 */5 * * * *").is_ok());
+        assert!(validate("*/5 * * * *").is_ok());
+        assert!(validate("0 0 12 * * Mon-Fri 2099").is_ok());
+        assert!(validate("0 0 0 1 1 * 2000").unwrap_err().contains("没有下一次执行时间"));
+
+        assert!(validate("* * *").unwrap_err().contains("应为 5 段"));
+        assert!(validate("60 0 0 * * *").unwrap_err().contains("秒字段 \"60\" 无效"));
+        assert!(validate("0 61 * * * *").unwrap_err().contains("分字段 \"61\" 无效"));
+        assert!(validate("61 * * * *").unwrap_err().contains("分字段 \"61\" 无效"));
+        assert!(validate("0 0 25 * * *").unwrap_err().contains("时字段 \"25\" 无效"));
+        assert!(validate("0 0 0 32 * *").unwrap_err().contains("日字段 \"32\" 无效"));
+        assert!(validate("0 0 0 * 13 *").unwrap_err().contains("月字段 \"13\" 无效"));
+        assert!(validate("0 0 0 * * Funday").unwrap_err().contains("星期字段 \"Funday\" 无效"));
+    }
+
+    /// 测试时长解析和执行间隔不能为 0
+    #[test]
+    fn test_parse_duration_millis() {
+        let literal = |text: &str| syn::LitStr::new(text, proc_macro2::Span::call_site());
+
+        assert_eq!(parse_duration_millis(&literal("500ms")).unwrap(), 500);
+        assert_eq!(parse_duration_millis(&literal("2")).unwrap(), 2000);
+        assert_eq!(parse_duration_millis(&literal("1m")).unwrap(), 60_000);
+        assert_eq!(parse_duration_millis(&literal("0s")).unwrap(), 0);
+        assert!(parse_duration_millis(&literal("5d")).is_err());
+        assert!(parse_duration_millis(&literal("18446744073709551615h")).is_err());
+
+        assert_eq!(parse_interval_millis(&literal("30s")).unwrap(), 30_000);
+        assert!(parse_interval_millis(&literal("0s")).unwrap_err().to_string().contains("不能为 0"));
+        assert!(parse_interval_millis(&literal("0ms")).is_err());
+    }
+
+    /// 测试 `jobs_crate` 参数替换生成代码中的 `rspring_jobs` 路径
+    #[test]
+    fn test_scheduled_jobs_path() {
+        let function: syn::ItemFn = syn::parse_quote! {
+            async fn refresh() {}
+        };
+        let trigger = (
+            syn::parse_quote!(fixed_rate),
+            quote! { FixedRate(::std::time::Duration::from_millis(1000)) },
+        );
+        let jobs: syn::Path = syn::parse_quote!(my_platform::jobs);
+        let expanded = expand_scheduled(None, Some(trigger), None, function, &default_core_path(), &jobs)
+            .unwrap()
+            .to_string();
+
+        assert!(expanded.contains("my_platform :: jobs :: ScheduledTask :: new"));
+        assert!(expanded.contains("my_platform :: jobs :: Trigger :: FixedRate"));
+        assert!(!expanded.contains("rspring_jobs"));
+    }
+
+    /// 测试 `#[Logged]` 脱敏和跳过参数、记录返回值和 `Result` 错误
+    #[test]
+    fn test_expand_logged() {
+        let function: syn::ItemFn = syn::parse_quote! {
+            async fn login(
+                &self,
+                username: String,
+                password: String,
+                session: &mut Session,
+            ) -> Result<Token> {
+                self.authenticate(username, password, session).await
+            }
+        };
+        let info = syn::Ident::new("INFO", proc_macro2::Span::call_site());
+        let redact: Vec<syn::Ident> = vec![syn::parse_quote!(password)];
+        let skip: Vec<syn::Ident> = vec![syn::parse_quote!(session)];
+        let krate = default_core_path();
+
+        let expanded = expand_logged(function.clone(), &info, &redact, &skip, false, &krate)
+            .unwrap()
+            .to_string();
+        assert!(expanded.contains(
+            "\"开始执行 login(username = {:?}, password = {})\" , username , \
+             :: rspring_core :: instrumentation :: REDACTED)"
+        ));
+        assert!(!expanded.contains("session ="));
+        assert!(expanded.contains("\"login 执行完成，耗时 {} ms\" , __elapsed)"));
+        assert!(expanded.contains(
+            "Err (__error) => :: rspring_core :: __private :: tracing :: warn ! \
+             (\"login 执行失败，耗时 {} ms: {:?}\" , __elapsed , __error)"
+        ));
+
+        let expanded = expand_logged(function, &info, &[], &[], true, &krate).unwrap().to_string();
+        assert!(expanded.contains("password = {:?}, session = {:?}"));
+        assert!(expanded.contains("\"login 执行完成，耗时 {} ms，返回 {:?}\" , __elapsed , __ok)"));
+    }
+
+    /// 测试 `#[Logged]` 拒绝未知参数和模式绑定的参数
+    #[test]
+    fn test_expand_logged_params() {
+        let info = syn::Ident::new("INFO", proc_macro2::Span::call_site());
+        let krate = default_core_path();
+        let function: syn::ItemFn = syn::parse_quote! {
+            fn total(count: u32, (price, discount): (u32, u32)) -> u32 {
+                count * (price - discount)
+            }
+        };
+        let error = expand_logged(function, &info, &[], &[], false, &krate).unwrap_err();
+        assert!(error.to_string().contains("不支持模式绑定的参数"));
+
+        let function: syn::ItemFn = syn::parse_quote! {
+            fn total(count: u32) -> u32 {
+                count
+            }
+        };
+        let redact: Vec<syn::Ident> = vec![syn::parse_quote!(price)];
+        let error = expand_logged(function, &info, &redact, &[], false, &krate).unwrap_err();
+        assert_eq!(error.to_string(), "price 不是方法的参数");
+    }
+
+    /// 测试 repository 的列名、主键和连接池字段须为有效的字段名
+    #[test]
+    fn test_repository_field_names() {
+        let parse = |attr: syn::Attribute| RepositoryArgs::parse(&attr).map_err(|e| e.to_string());
+
+        let args = parse(syn::parse_quote! {
+            #[repository(entity = User, table = "users", columns = "name, email", pool = "db")]
+        })
+        .unwrap();
+        assert_eq!(args.columns, ["name", "email"]);
+        assert_eq!(args.fields, ["name", "email"]);
+        assert_eq!(args.id_field, "id");
+        assert_eq!(args.pool, "db");
+
+        let error = parse(syn::parse_quote! {
+            #[repository(entity = User, table = "users", columns = "name, type")]
+        });
+        assert_eq!(error.err().unwrap(), "\"type\" 不是有效的字段名");
+        let error = parse(syn::parse_quote! {
+            #[repository(entity = User, table = "users", columns = "name", id = "user-id")]
+        });
+        assert_eq!(error.err().unwrap(), "\"user-id\" 不是有效的字段名");
+        let error = parse(syn::parse_quote! {
+            #[repository(entity = User, table = "users", columns = "name", pool = "1pool")]
+        });
+        assert_eq!(error.err().unwrap(), "\"1pool\" 不是有效的字段名");
+    }
+
+    /// 测试派生查询条件解析
+    #[test]
+    fn test_parse_conditions() {
+        assert_eq!(
+            parse_conditions("status_and_age_greater_than_equal").unwrap(),
+            ("status = ? AND age >= ?".to_string(), 2)
+        );
+        assert_eq!(
+            parse_conditions("created_at_between_or_deleted_at_is_null").unwrap(),
+            ("created_at BETWEEN ? AND ? OR deleted_at IS NULL".to_string(), 2)
+        );
+        assert_eq!(
+            parse_conditions("email_is_not_null_and_name_like").unwrap(),
+            ("email IS NOT NULL AND name LIKE ?".to_string(), 1)
+        );
+
+        assert!(parse_conditions("status_in").unwrap_err().contains("Spec::in_list"));
+        assert!(parse_conditions("_is_null").is_err());
+        assert!(parse_conditions("status_and").is_err());
+        assert!(parse_conditions("status__name").is_err());
+        assert!(parse_conditions("").is_err());
+    }
+
+    /// 测试派生查询排序解析
+    #[test]
+    fn test_parse_order_by() {
+        assert_eq!(parse_order_by("created_at_desc").as_deref(), Some("created_at DESC"));
+        assert_eq!(parse_order_by("name_and_id_asc").as_deref(), Some("name ASC, id ASC"));
+        assert!(parse_order_by("_desc").is_none());
+        assert!(parse_order_by("name_and_").is_none());
+    }
+
+    /// 测试按方法名生成派生查询及错误提示
+    #[test]
+    fn test_derive_query() {
+        let pool = format_ident!("pool");
+        let krate = default_core_path();
+        let derive = |signature: syn::Signature, soft_delete: Option<&str>| {
+            derive_query("users", &pool, soft_delete, &signature, &krate).map(|tokens| tokens.to_string())
+        };
+
+        let expanded = derive(
+            syn::parse_quote! {
+                fn find_by_status_and_age_greater_than_order_by_created_at_desc(status: &str, age: i32) -> Vec<User>
+            },
+            None,
+        )
+        .unwrap();
+        assert!(expanded.contains(
+            "\"SELECT * FROM users WHERE status = ? AND age > ? ORDER BY created_at DESC\""
+        ));
+
+        let expanded = derive(
+            syn::parse_quote! { fn count_by_created_at_between(from: DateTime<Utc>, to: DateTime<Utc>) -> u64 },
+            Some("deleted_at"),
+        )
+        .unwrap();
+        assert!(expanded.contains(
+            "\"SELECT COUNT(*) FROM users WHERE created_at BETWEEN ? AND ? AND deleted_at IS NULL\""
+        ));
+
+        let expanded = derive(syn::parse_quote! { fn delete_by_email_is_null() -> u64 }, None).unwrap();
+        assert!(expanded.contains("\"DELETE FROM users WHERE email IS NULL\""));
+
+        let error = |signature| derive(signature, None).unwrap_err().to_string();
+        assert!(error(syn::parse_quote! { fn search_by_name(name: &str) -> Vec<User> }).contains("find_by_"));
+        assert!(error(syn::parse_quote! { fn find_by_status_in(status: Vec<String>) -> Vec<User> })
+            .contains("Spec::in_list"));
+        assert!(error(syn::parse_quote! { fn find_by__is_null() -> Vec<User> }).contains("缺少属性名"));
+        assert!(error(syn::parse_quote! { fn find_by_name_order_by__desc(name: &str) -> Vec<User> })
+            .contains("无法解析排序条件"));
+        assert!(error(syn::parse_quote! { fn find_by_name_and_email(name: &str) -> Option<User> })
+            .contains("需要 2 个参数"));
+        assert!(error(syn::parse_quote! { fn find_by_name(name: &str) }).contains("返回类型"));
+    }
+
+    /// 测试 `MapTo` 的字段重命名、跳过、自定义转换和默认值
+    #[test]
+    fn test_expand_map_to() {
+        let input: DeriveInput = syn::parse_quote! {
+            #[map_to(UserResponse)]
+            #[map_to(UserSummary, default)]
+            pub struct User {
+                pub id: i64,
+                #[map_to(rename = "display_name")]
+                pub name: String,
+                #[map_to(skip)]
+                pub password_hash: String,
+                #[map_to(target = UserResponse, with = "format_time")]
+                pub created_at: DateTime<Utc>,
+            }
+        };
+        let expanded = expand_map_to(&input).unwrap().to_string();
+
+        let response = quote! {
+            impl ::std::convert::From<User> for UserResponse {
+                fn from(source: User) -> Self {
+                    Self {
+                        id: ::std::convert::Into::into(source.id),
+                        display_name: ::std::convert::Into::into(source.name),
+                        created_at: format_time(source.created_at),
+                    }
+                }
+            }
+        };
+        let summary = quote! {
+            impl ::std::convert::From<User> for UserSummary {
+                fn from(source: User) -> Self {
+                    Self {
+                        id: ::std::convert::Into::into(source.id),
+                        display_name: ::std::convert::Into::into(source.name),
+                        created_at: ::std::convert::Into::into(source.created_at),
+                        ..::std::default::Default::default()
+                    }
+                }
+            }
+        };
+        assert!(expanded.contains(&response.to_string()));
+        assert!(expanded.contains(&summary.to_string()));
+        assert!(!expanded.contains("password_hash"));
+
+        let input: DeriveInput = syn::parse_quote! {
+            #[map_to(UserResponse)]
+            pub struct User {
+                #[map_to(renamed = "display_name")]
+                pub name: String,
+            }
+        };
+        assert!(expand_map_to(&input).unwrap_err().to_string().contains("renamed"));
+
+        let input: DeriveInput = syn::parse_quote! {
+            pub struct User(i64);
+        };
+        assert!(expand_map_to(&input).is_err());
+    }
+
+    /// 测试业务错误变体的错误码、状态码和错误描述
+    #[test]
+    fn test_business_error_args() {
+        let variant: syn::Variant = syn::parse_quote! {
+            #[error(status = 423, message = "用户已被锁定，{0} 分钟后重试")]
+            UserLocked(u32)
+        };
+        let (code, status, message) = business_error_args(&variant).unwrap();
+        assert_eq!((code.as_str(), status), ("USER_LOCKED", 423));
+        let (format, used) = business_message(&message, &variant.fields).unwrap();
+        assert_eq!(format, "用户已被锁定，{__0} 分钟后重试");
+        assert_eq!(used, vec![format_ident!("__0")]);
+
+        let variant: syn::Variant = syn::parse_quote! {
+            #[error(code = "USER_EXISTS", message = "用户 {name} 已存在 {{}}")]
+            Exists { name: String, id: u64 }
+        };
+        let (code, status, message) = business_error_args(&variant).unwrap();
+        assert_eq!((code.as_str(), status), ("USER_EXISTS", 400));
+        let (format, used) = business_message(&message, &variant.fields).unwrap();
+        assert_eq!(format, "用户 {name} 已存在 {{}}");
+        assert_eq!(used, vec![format_ident!("name")]);
+
+        let variant: syn::Variant = syn::parse_quote! {
+            #[error(message = "用户 {email} 已存在")]
+            Exists { name: String }
+        };
+        let (_, _, message) = business_error_args(&variant).unwrap();
+        let error = business_message(&message, &variant.fields).unwrap_err();
+        assert!(error.to_string().contains("{email} 不是变体的字段"));
+
+        let variant: syn::Variant = syn::parse_quote! {
+            #[error(status = 700, message = "无效")]
+            Invalid
+        };
+        assert!(business_error_args(&variant).is_err());
+        let variant: syn::Variant = syn::parse_quote! {
+            #[error(code = "MISSING")]
+            Missing
+        };
+        assert!(business_error_args(&variant).is_err());
+    }
+
+    /// 测试 `component_scan!` 只注册列出的组件和控制器增强
+    #[test]
+    fn test_scan_components() {
+        let parse = |input: &str| {
+            let parser = syn::punctuated::Punctuated::<ScanEntry, syn::Token![,]>::parse_terminated;
+            syn::parse::Parser::parse_str(parser, input).map(|entries| entries.into_iter().collect::<Vec<_>>())
+        };
+
+        let entries = parse("crate::services::UserService, advice(GlobalAdvice)").unwrap();
+        let expanded = scan_components(&default_core_path(), &entries).unwrap().to_string();
+        let component = quote! { __definitions.push(crate::services::UserService::__scan_definition(__config)?); };
+        let advice = quote! { __definitions.push(GlobalAdvice::__advice_definition()); };
+        assert!(expanded.contains(&component.to_string()));
+        assert!(expanded.contains(&advice.to_string()));
+
+        assert!(parse("handler(GlobalAdvice)").is_err_and(|error| error.to_string().contains("advice(Type)")));
+        assert!(scan_components(&default_core_path(), &[]).is_err());
+    }
+    /// 测试只有 `#[component(inject)]` 标注的 `Arc<T>` 字段作为组件依赖
+    #[test]
+    fn test_field_dependencies() {
+        let input: DeriveInput = syn::parse_quote! {
+            struct OrderService {
+                #[component(inject)]
+                repository: Arc<OrderRepository>,
+                client: Arc<reqwest::Client>,
+                #[component(default)]
+                cache: Arc<Cache>,
+            }
+        };
+        let dependencies = field_dependencies(&input.data).unwrap();
+        assert_eq!(dependencies.len(), 1);
+        let dependency = &dependencies[0];
+        assert_eq!(quote!(#dependency).to_string(), "OrderRepository");
+
+        let invalid = |input: DeriveInput| {
+            field_dependencies(&input.data).err().unwrap().to_string()
+        };
+        let not_arc = syn::parse_quote! { struct A { #[component(inject)] name: String } };
+        assert!(invalid(not_arc).contains("Arc<T>"));
+        let both = syn::parse_quote! { struct A { #[component(inject, default)] b: Arc<B> } };
+        assert!(invalid(both).contains("default"));
+        let unknown = syn::parse_quote! { struct A { #[component(skip)] b: Arc<B> } };
+        assert!(invalid(unknown).contains("inject"));
+    }
+}
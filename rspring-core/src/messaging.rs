@@ -0,0 +1,251 @@
+//! 消息模块
+//!
+//! 各消息启动器共用的消息抽象：
+//! - [`converter`] 提供可插拔的消息体编解码，默认 JSON，可选 Protobuf 和 Avro
+//! - [`listener`] 提供监听器的并发消费者数、批量大小等消费设置
+//!
+//! 以及统一的死信处理：
+//! - [`DeadLetterPolicy`] 描述最大投递次数和死信目标的命名规则
+//! - 消息投递失败达到上限后，启动器将消息连同错误信息转发到死信目标，并调用
+//!   [`publish_dead_letter`] 通知已注册的 [`DeadLetterListener`]
+//! - 启用 `metrics` 特性时记录 `messaging.dead_letters` 指标
+
+pub mod converter;
+pub mod listener;
+
+use std::sync::{Arc, RwLock};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+pub use converter::{
+    from_message, message_converter, set_message_converter, to_message, JsonMessageConverter,
+    MessageConverter, MessagingConfig,
+};
+pub use listener::ListenerSettings;
+
+/// 死信次数指标
+#[cfg(feature = "metrics")]
+pub const DEAD_LETTER_METRIC: &str = "messaging.dead_letters";
+
+/// 死信消息头：失败原因
+pub const DEAD_LETTER_ERROR: &str = "x-dead-letter-error";
+/// 死信消息头：原始目标
+pub const DEAD_LETTER_SOURCE: &str = "x-dead-letter-source";
+/// 死信消息头：投递次数
+pub const DEAD_LETTER_DELIVERIES: &str = "x-dead-letter-deliveries";
+/// 死信消息头：原始消息 ID
+pub const DEAD_LETTER_MESSAGE_ID: &str = "x-dead-letter-message-id";
+
+/// 死信策略
+///
+/// 未单独指定死信目标时，使用原始目标名加上 `suffix`，如 `orders` 对应 `orders-dlq`
+///
+/// # 示例
+/// ```toml
+/// [aws.sqs.dead_letter]
+/// enabled = true
+/// max_deliveries = 5
+/// suffix = "-dlq"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct DeadLetterPolicy {
+    /// 是否启用
+    ///
+    /// # 默认值
+    /// `false`
+    #[serde(default)]
+    pub enabled: bool,
+    /// 最大投递次数，达到后转发到死信目标
+    ///
+    /// # 默认值
+    /// `5`
+    #[serde(default = "default_max_deliveries")]
+    pub max_deliveries: u32,
+    /// 死信目标名的后缀
+    ///
+    /// # 默认值
+    /// `"-dlq"`
+    #[serde(default = "default_suffix")]
+    pub suffix: String,
+}
+
+impl Default for DeadLetterPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_deliveries: default_max_deliveries(),
+            suffix: default_suffix(),
+        }
+    }
+}
+
+impl DeadLetterPolicy {
+    /// 已投递 `deliveries` 次仍失败的消息是否应转发到死信目标
+    pub fn is_exhausted(&self, deliveries: u32) -> bool {
+        self.enabled && deliveries >= self.max_deliveries.max(1)
+    }
+
+    /// 原始目标对应的默认死信目标
+    pub fn destination_for(&self, source: &str) -> String {
+        format!("{}{}", source, self.suffix)
+    }
+}
+
+/// 死信记录
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct DeadLetter {
+    /// 来源，如 `sqs`、`outbox`
+    pub origin: String,
+    /// 原始目标
+    pub source: String,
+    /// 死信目标，未转发时为 `None`
+    pub destination: Option<String>,
+    /// 原始消息 ID
+    pub message_id: String,
+    /// 已投递次数
+    pub deliveries: u32,
+    /// 最后一次失败的原因
+    pub error: String,
+    /// 进入死信的时间
+    pub failed_at: DateTime<Utc>,
+}
+
+impl DeadLetter {
+    /// 创建死信记录
+    pub fn new(
+        origin: impl Into<String>,
+        source: impl Into<String>,
+        message_id: impl Into<String>,
+        deliveries: u32,
+        error: impl ToString,
+    ) -> Self {
+        Self {
+            origin: origin.into(),
+            source: source.into(),
+            destination: None,
+            message_id: message_id.into(),
+            deliveries,
+            error: error.to_string(),
+            failed_at: crate::clock::now(),
+        }
+    }
+
+    /// 设置死信目标
+    pub fn with_destination(mut self, destination: impl Into<String>) -> Self {
+        self.destination = Some(destination.into());
+        self
+    }
+
+    /// 转发到死信目标时附带的消息头
+    pub fn headers(&self) -> Vec<(&'static str, String)> {
+        vec![
+            (DEAD_LETTER_ERROR, self.error.clone()),
+            (DEAD_LETTER_SOURCE, self.source.clone()),
+            (DEAD_LETTER_DELIVERIES, self.deliveries.to_string()),
+            (DEAD_LETTER_MESSAGE_ID, self.message_id.clone()),
+        ]
+    }
+}
+
+/// 死信监听器特征
+///
+/// # 示例
+/// ```rust
+/// struct AlertOnDeadLetter;
+///
+/// impl DeadLetterListener for AlertOnDeadLetter {
+///     fn on_dead_letter(&self, dead_letter: &DeadLetter) {
+///         alert(format!("{} 的消息 {} 进入死信", dead_letter.source, dead_letter.message_id));
+///     }
+/// }
+///
+/// register_dead_letter_listener(Arc::new(AlertOnDeadLetter));
+/// ```
+pub trait DeadLetterListener: Send + Sync {
+    /// 消息进入死信后调用
+    fn on_dead_letter(&self, dead_letter: &DeadLetter);
+}
+
+/// 已注册的死信监听器
+static DEAD_LETTER_LISTENERS: RwLock<Vec<Arc<dyn DeadLetterListener>>> = RwLock::new(Vec::new());
+
+/// 注册死信监听器
+pub fn register_dead_letter_listener(listener: Arc<dyn DeadLetterListener>) {
+    DEAD_LETTER_LISTENERS
+        .write()
+        .expect("死信监听器锁已损坏")
+        .push(listener);
+}
+
+/// 发布死信事件：记录日志和指标，并通知所有死信监听器
+pub fn publish_dead_letter(dead_letter: &DeadLetter) {
+    tracing::error!(
+        "{} 消息 {} 投递 {} 次后进入死信（{} -> {}）: {}",
+        dead_letter.origin,
+        dead_letter.message_id,
+        dead_letter.deliveries,
+        dead_letter.source,
+        dead_letter.destination.as_deref().unwrap_or("-"),
+        dead_letter.error
+    );
+
+    #[cfg(feature = "metrics")]
+    metrics::counter!(
+        DEAD_LETTER_METRIC,
+        "origin" => dead_letter.origin.clone(),
+        "source" => dead_letter.source.clone()
+    )
+    .increment(1);
+
+    let listeners = DEAD_LETTER_LISTENERS
+        .read()
+        .expect("死信监听器锁已损坏")
+        .clone();
+    for listener in listeners {
+        listener.on_dead_letter(dead_letter);
+    }
+}
+
+// 默认值函数
+
+fn default_max_deliveries() -> u32 {
+    5
+}
+
+fn default_suffix() -> String {
+    "-dlq".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct Collect(Mutex<Vec<String>>);
+
+    impl DeadLetterListener for Collect {
+        fn on_dead_letter(&self, dead_letter: &DeadLetter) {
+            self.0.lock().unwrap().push(dead_letter.message_id.clone());
+        }
+    }
+
+    /// 测试死信策略和监听器通知
+    #[test]
+    fn test_dead_letter() {
+        let policy: DeadLetterPolicy =
+            serde_json::from_str(r#"{ "enabled": true, "max_deliveries": 3 }"#).unwrap();
+        assert!(!policy.is_exhausted(2));
+        assert!(policy.is_exhausted(3));
+        assert_eq!(policy.destination_for("orders"), "orders-dlq");
+        assert!(!DeadLetterPolicy::default().is_exhausted(100));
+
+        let listener = Arc::new(Collect(Mutex::new(Vec::new())));
+        register_dead_letter_listener(listener.clone());
+        let dead_letter = DeadLetter::new("sqs", "orders", "m-1", 3, "boom").with_destination("orders-dlq");
+        publish_dead_letter(&dead_letter);
+
+        assert_eq!(*listener.0.lock().unwrap(), vec!["m-1"]);
+        assert_eq!(dead_letter.headers()[0], (DEAD_LETTER_ERROR, "boom".to_string()));
+    }
+}
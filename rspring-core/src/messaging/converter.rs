@@ -0,0 +1,340 @@
+//! 消息转换模块
+//!
+//! 各消息启动器共用的消息体编解码 SPI。消息先转换为 JSON 值，再由 [`MessageConverter`]
+//! 编码为字节，编码格式在 `[messaging]` 中统一配置：
+//! - `json`（默认）：UTF-8 JSON 文本
+//! - `protobuf`（`protobuf` 特性）：编码为 `google.protobuf.Value`
+//! - `avro`（`avro` 特性）：按 Avro 模式编码，配置了 `schema_id` 时使用 Schema Registry 的
+//!   线格式（魔数 `0` + 4 字节模式 ID + 数据）
+
+use std::sync::{Arc, RwLock};
+
+use once_cell::sync::Lazy;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config::properties::Configuration;
+use crate::error::{Error, Result};
+
+/// 消息转换器特征
+pub trait MessageConverter: Send + Sync {
+    /// 内容类型，如 `application/json`，随消息一起发送
+    fn content_type(&self) -> &str;
+
+    /// 编码结果是否为 UTF-8 文本，只支持文本消息体的中间件需对非文本结果做 Base64 编码
+    fn is_text(&self) -> bool {
+        false
+    }
+
+    /// 将 JSON 值编码为消息体
+    fn encode(&self, value: &Value) -> Result<Vec<u8>>;
+
+    /// 将消息体解码为 JSON 值
+    fn decode(&self, bytes: &[u8]) -> Result<Value>;
+}
+
+/// 使用转换器编码消息
+///
+/// # 错误
+/// 值无法序列化或编码失败时返回验证错误
+pub fn to_message<T: Serialize + ?Sized>(converter: &dyn MessageConverter, payload: &T) -> Result<Vec<u8>> {
+    let value = serde_json::to_value(payload)
+        .map_err(|e| Error::validation(format!("消息序列化失败: {}", e)))?;
+    converter.encode(&value)
+}
+
+/// 使用转换器解码消息
+///
+/// # 错误
+/// 消息体无法解码或与类型不匹配时返回验证错误
+pub fn from_message<T: DeserializeOwned>(converter: &dyn MessageConverter, bytes: &[u8]) -> Result<T> {
+    let value = converter.decode(bytes)?;
+    serde_json::from_value(value).map_err(|e| Error::validation(format!("消息反序列化失败: {}", e)))
+}
+
+/// 全局消息转换器
+static MESSAGE_CONVERTER: Lazy<RwLock<Arc<dyn MessageConverter>>> =
+    Lazy::new(|| RwLock::new(Arc::new(JsonMessageConverter)));
+
+/// 设置全局消息转换器，各消息启动器收发消息时使用
+///
+/// 应用启动时会按 `[messaging]` 配置设置，未配置时使用 [`JsonMessageConverter`]
+///
+/// 在作用域中调用时的行为见[全局组件作用域](crate::scope)
+pub fn set_message_converter(converter: Arc<dyn MessageConverter>) {
+    if let Some(converter) = crate::scope::set_scoped(converter) {
+        *MESSAGE_CONVERTER.write().expect("消息转换器锁已损坏") = converter;
+    }
+}
+
+/// 获取全局消息转换器
+pub fn message_converter() -> Arc<dyn MessageConverter> {
+    crate::scope::scoped()
+        .unwrap_or_else(|| MESSAGE_CONVERTER.read().expect("消息转换器锁已损坏").clone())
+}
+
+/// 消息编码格式
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageFormat {
+    /// JSON
+    #[default]
+    Json,
+    /// Protobuf
+    Protobuf,
+    /// Avro
+    Avro,
+}
+
+/// 消息配置
+///
+/// # 示例
+/// ```toml
+/// [messaging]
+/// format = "avro"
+///
+/// [messaging.avro]
+/// schema = "schemas/events.avsc"
+/// schema_id = 42
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct MessagingConfig {
+    /// 消息编码格式
+    ///
+    /// # 默认值
+    /// `"json"`
+    #[serde(default)]
+    pub format: MessageFormat,
+    /// Avro 配置，`format = "avro"` 时必填
+    #[serde(default)]
+    pub avro: Option<AvroConfig>,
+}
+
+impl Configuration for MessagingConfig {}
+
+/// Avro 配置
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct AvroConfig {
+    /// Avro 模式文件路径
+    pub schema: String,
+    /// Schema Registry 中的模式 ID（可选）
+    #[serde(default)]
+    pub schema_id: Option<u32>,
+}
+
+impl MessagingConfig {
+    /// 按配置创建消息转换器
+    ///
+    /// # 错误
+    /// 未启用对应特性，或 Avro 模式缺失、无法解析时返回错误
+    pub fn converter(&self) -> Result<Arc<dyn MessageConverter>> {
+        match self.format {
+            MessageFormat::Json => Ok(Arc::new(JsonMessageConverter)),
+            #[cfg(feature = "protobuf")]
+            MessageFormat::Protobuf => Ok(Arc::new(ProtobufMessageConverter)),
+            #[cfg(feature = "avro")]
+            MessageFormat::Avro => {
+                let avro = self
+                    .avro
+                    .as_ref()
+                    .ok_or_else(|| Error::validation("使用 Avro 格式时须配置 [messaging.avro]"))?;
+                let schema = std::fs::read_to_string(&avro.schema).map_err(|e| {
+                    Error::application(format!("读取 Avro 模式失败 ({}): {}", avro.schema, e))
+                })?;
+                Ok(Arc::new(AvroMessageConverter::new(&schema, avro.schema_id)?))
+            }
+            #[allow(unreachable_patterns)]
+            format => Err(Error::validation(format!(
+                "消息格式 {:?} 需要启用 rspring-core 的对应特性",
+                format
+            ))),
+        }
+    }
+}
+
+/// JSON 消息转换器
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonMessageConverter;
+
+impl MessageConverter for JsonMessageConverter {
+    fn content_type(&self) -> &str {
+        "application/json"
+    }
+
+    fn is_text(&self) -> bool {
+        true
+    }
+
+    fn encode(&self, value: &Value) -> Result<Vec<u8>> {
+        serde_json::to_vec(value).map_err(|e| Error::validation(format!("JSON 编码失败: {}", e)))
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Value> {
+        serde_json::from_slice(bytes).map_err(|e| Error::validation(format!("JSON 解码失败: {}", e)))
+    }
+}
+
+/// Protobuf 消息转换器，消息体为 `google.protobuf.Value`
+#[cfg(feature = "protobuf")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProtobufMessageConverter;
+
+#[cfg(feature = "protobuf")]
+impl MessageConverter for ProtobufMessageConverter {
+    fn content_type(&self) -> &str {
+        "application/x-protobuf"
+    }
+
+    fn encode(&self, value: &Value) -> Result<Vec<u8>> {
+        Ok(prost::Message::encode_to_vec(&protobuf::to_proto(value)))
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Value> {
+        let value: prost_types::Value = prost::Message::decode(bytes)
+            .map_err(|e| Error::validation(format!("Protobuf 解码失败: {}", e)))?;
+        Ok(protobuf::from_proto(value))
+    }
+}
+
+/// JSON 值与 `google.protobuf.Value` 的互相转换
+#[cfg(feature = "protobuf")]
+mod protobuf {
+    use prost_types::value::Kind;
+    use prost_types::{ListValue, Struct};
+    use serde_json::Value;
+
+    /// JSON 值转换为 Protobuf 值
+    pub fn to_proto(value: &Value) -> prost_types::Value {
+        let kind = match value {
+            Value::Null => Kind::NullValue(0),
+            Value::Bool(value) => Kind::BoolValue(*value),
+            Value::Number(value) => Kind::NumberValue(value.as_f64().unwrap_or_default()),
+            Value::String(value) => Kind::StringValue(value.clone()),
+            Value::Array(values) => Kind::ListValue(ListValue {
+                values: values.iter().map(to_proto).collect(),
+            }),
+            Value::Object(fields) => Kind::StructValue(Struct {
+                fields: fields
+                    .iter()
+                    .map(|(key, value)| (key.clone(), to_proto(value)))
+                    .collect(),
+            }),
+        };
+        prost_types::Value { kind: Some(kind) }
+    }
+
+    /// Protobuf 值转换为 JSON 值，整数值还原为整数
+    pub fn from_proto(value: prost_types::Value) -> Value {
+        match value.kind {
+            None | Some(Kind::NullValue(_)) => Value::Null,
+            Some(Kind::BoolValue(value)) => Value::Bool(value),
+            Some(Kind::NumberValue(value)) if value.fract() == 0.0 && value.abs() < 9.0e15 => {
+                Value::from(value as i64)
+            }
+            Some(Kind::NumberValue(value)) => Value::from(value),
+            Some(Kind::StringValue(value)) => Value::String(value),
+            Some(Kind::ListValue(list)) => Value::Array(list.values.into_iter().map(from_proto).collect()),
+            Some(Kind::StructValue(object)) => Value::Object(
+                object
+                    .fields
+                    .into_iter()
+                    .map(|(key, value)| (key, from_proto(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Avro 消息转换器
+#[cfg(feature = "avro")]
+#[derive(Debug, Clone)]
+pub struct AvroMessageConverter {
+    /// Avro 模式
+    schema: apache_avro::Schema,
+    /// Schema Registry 中的模式 ID
+    schema_id: Option<u32>,
+}
+
+#[cfg(feature = "avro")]
+impl AvroMessageConverter {
+    /// Schema Registry 线格式的魔数
+    const MAGIC_BYTE: u8 = 0;
+
+    /// 使用 JSON 格式的 Avro 模式创建
+    ///
+    /// # 错误
+    /// 模式无法解析时返回验证错误
+    pub fn new(schema: &str, schema_id: Option<u32>) -> Result<Self> {
+        let schema = apache_avro::Schema::parse_str(schema)
+            .map_err(|e| Error::validation(format!("Avro 模式解析失败: {}", e)))?;
+        Ok(Self { schema, schema_id })
+    }
+}
+
+#[cfg(feature = "avro")]
+impl MessageConverter for AvroMessageConverter {
+    fn content_type(&self) -> &str {
+        "application/avro"
+    }
+
+    fn encode(&self, value: &Value) -> Result<Vec<u8>> {
+        let value = apache_avro::types::Value::from(value.clone())
+            .resolve(&self.schema)
+            .map_err(|e| Error::validation(format!("消息与 Avro 模式不匹配: {}", e)))?;
+        let datum = apache_avro::to_avro_datum(&self.schema, value)
+            .map_err(|e| Error::validation(format!("Avro 编码失败: {}", e)))?;
+
+        let Some(schema_id) = self.schema_id else {
+            return Ok(datum);
+        };
+        let mut bytes = Vec::with_capacity(datum.len() + 5);
+        bytes.push(Self::MAGIC_BYTE);
+        bytes.extend_from_slice(&schema_id.to_be_bytes());
+        bytes.extend_from_slice(&datum);
+        Ok(bytes)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Value> {
+        let mut datum = bytes;
+        if self.schema_id.is_some() {
+            if bytes.len() < 5 || bytes[0] != Self::MAGIC_BYTE {
+                return Err(Error::validation("消息不是 Schema Registry 线格式"));
+            }
+            datum = &bytes[5..];
+        }
+
+        let value = apache_avro::from_avro_datum(&self.schema, &mut datum, None)
+            .map_err(|e| Error::validation(format!("Avro 解码失败: {}", e)))?;
+        Value::try_from(value).map_err(|e| Error::validation(format!("Avro 值无法转换为 JSON: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct OrderCreated {
+        order_id: u64,
+        items: Vec<String>,
+    }
+
+    /// 测试默认的 JSON 转换器和未启用特性时的错误
+    #[test]
+    fn test_json_converter() {
+        let converter = MessagingConfig::default().converter().unwrap();
+        let event = OrderCreated {
+            order_id: 42,
+            items: vec!["book".to_string()],
+        };
+
+        let bytes = to_message(&*converter, &event).unwrap();
+        assert_eq!(bytes, br#"{"items":["book"],"order_id":42}"#);
+        assert_eq!(from_message::<OrderCreated>(&*converter, &bytes).unwrap(), event);
+        assert!(from_message::<OrderCreated>(&*converter, b"{").is_err());
+
+        let config: MessagingConfig = serde_json::from_str(r#"{ "format": "avro" }"#).unwrap();
+        assert!(config.converter().is_err());
+    }
+}
@@ -0,0 +1,107 @@
+//! 消息监听器设置模块
+//!
+//! 各消息启动器的监听器共用的消费设置：并发消费者数、单次拉取（预取）的消息数和
+//! 最长处理时间。设置可以来自监听注解的参数，也可以来自启动器配置中按监听器的章节，
+//! 启动器通过 [`ListenerSettings::merge`] 按优先级合并后，用自己的默认值补全未设置的项
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+
+/// 消息监听器的消费设置
+///
+/// 所有项都是可选的，未设置时使用启动器的默认值
+///
+/// # 示例
+/// ```toml
+/// [aws.sqs.listeners.orders]
+/// concurrency = 4
+/// batch_size = 5
+/// max_poll_interval = "5m"
+/// ```
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ListenerSettings {
+    /// 并发消费者数，每个消费者独立拉取和处理消息
+    #[serde(default)]
+    pub concurrency: Option<usize>,
+    /// 单次拉取或预取的最大消息数，同一批消息并发处理
+    #[serde(default)]
+    pub batch_size: Option<u32>,
+    /// 一批消息的最长处理时间，超过后启动器不再为这批消息续期，由消息中间件重新投递
+    #[serde(default, with = "crate::config::duration::option")]
+    pub max_poll_interval: Option<Duration>,
+}
+
+impl ListenerSettings {
+    /// 创建未设置任何项的设置
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置并发消费者数
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = Some(concurrency);
+        self
+    }
+
+    /// 设置单次拉取的最大消息数
+    pub fn batch_size(mut self, batch_size: u32) -> Self {
+        self.batch_size = Some(batch_size);
+        self
+    }
+
+    /// 设置一批消息的最长处理时间
+    pub fn max_poll_interval(mut self, max_poll_interval: Duration) -> Self {
+        self.max_poll_interval = Some(max_poll_interval);
+        self
+    }
+
+    /// 用 `overrides` 中已设置的项覆盖当前设置
+    pub fn merge(self, overrides: &ListenerSettings) -> Self {
+        Self {
+            concurrency: overrides.concurrency.or(self.concurrency),
+            batch_size: overrides.batch_size.or(self.batch_size),
+            max_poll_interval: overrides.max_poll_interval.or(self.max_poll_interval),
+        }
+    }
+
+    /// 检查已设置的项
+    ///
+    /// # 错误
+    /// 并发消费者数或单次拉取的消息数为 0、最长处理时间为 0 时返回验证错误
+    pub fn validate(&self) -> Result<()> {
+        if self.concurrency == Some(0) {
+            return Err(Error::validation("监听器的并发消费者数必须大于 0"));
+        }
+        if self.batch_size == Some(0) {
+            return Err(Error::validation("监听器单次拉取的消息数必须大于 0"));
+        }
+        if self.max_poll_interval == Some(Duration::ZERO) {
+            return Err(Error::validation("监听器的最长处理时间必须大于 0"));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试按优先级合并和校验
+    #[test]
+    fn test_merge_listener_settings() {
+        let configured: ListenerSettings = serde_json::from_str(r#"{ "batch_size": 5, "max_poll_interval": "5m" }"#).unwrap();
+        let declared = ListenerSettings::new().concurrency(4).batch_size(10);
+
+        let settings = declared.merge(&configured);
+        assert_eq!(settings.concurrency, Some(4));
+        assert_eq!(settings.batch_size, Some(5));
+        assert_eq!(settings.max_poll_interval, Some(Duration::from_secs(300)));
+        assert!(settings.validate().is_ok());
+
+        assert_eq!(ListenerSettings::new().merge(&ListenerSettings::new()), ListenerSettings::default());
+        assert!(ListenerSettings::new().concurrency(0).validate().is_err());
+    }
+}
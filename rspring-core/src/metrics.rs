@@ -0,0 +1,249 @@
+//! 指标模块
+//!
+//! 基于 `metrics` 门面记录计数器、仪表和直方图：
+//! - 框架和应用代码通过 [`counter!`]、[`gauge!`]、[`histogram!`] 记录指标，未安装记录器时为空操作
+//! - 启用 `prometheus` 特性后，应用启动时按 `[metrics]` 配置安装 Prometheus 记录器，
+//!   Web 层通过 [`render_prometheus`] 暴露文本格式的抓取端点
+//! - `[metrics.tags]` 中的公共标签附加到所有指标，`common_tags` 自动添加应用名称、环境和实例标签
+//! - `prefix`、`rename` 和 `disabled` 按组织的命名约定改写或禁用指标，见 [`naming`] 模块
+//! - `[metrics.runtime]` 控制 Tokio 运行时指标的采集，见 [`runtime`] 模块
+
+pub mod naming;
+pub mod runtime;
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::properties::Configuration;
+use crate::config::{AppConfig, ConfigurationManager};
+use crate::error::{Error, Result};
+use crate::logging::syslog::hostname;
+
+pub use ::metrics::{counter, gauge, histogram, Counter, Gauge, Histogram};
+pub use naming::{MetricNaming, NamingRecorder};
+pub use runtime::{spawn_runtime_metrics, RuntimeMetricsConfig};
+#[cfg(feature = "prometheus")]
+pub use metrics_exporter_prometheus::{PrometheusHandle, PrometheusRecorder};
+
+/// 直方图数据的清理间隔，清理前的样本会一直占用内存
+#[cfg(feature = "prometheus")]
+const UPKEEP_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// 已安装的 Prometheus 记录器句柄
+#[cfg(feature = "prometheus")]
+static PROMETHEUS_HANDLE: once_cell::sync::OnceCell<PrometheusHandle> = once_cell::sync::OnceCell::new();
+
+/// 指标配置
+///
+/// # 示例
+/// ```toml
+/// [metrics]
+/// path = "/metrics"
+/// buckets = [0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0]
+/// prefix = "acme"
+/// common_tags = ["app", "env", "instance"]
+/// disabled = ["tokio.*"]
+///
+/// [metrics.rename]
+/// "http.server.requests" = "http.requests"
+///
+/// [metrics.tags]
+/// team = "payments"
+///
+/// [metrics.runtime]
+/// interval = "30s"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct MetricsConfig {
+    /// 是否启用
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Prometheus 抓取端点的路径
+    ///
+    /// # 默认值
+    /// `"/metrics"`
+    #[serde(default = "default_path")]
+    pub path: String,
+    /// 附加到所有指标的公共标签
+    #[serde(default)]
+    pub tags: BTreeMap<String, String>,
+    /// 自动添加的公共标签，可选 `"app"`（`[app]` 中的名称）、`"env"`（当前环境）和
+    /// `"instance"`（主机名），`tags` 中已配置的同名标签优先
+    #[serde(default)]
+    pub common_tags: Vec<String>,
+    /// 添加到所有指标名称之前的前缀，如 `"acme"` 使 `http.server.requests` 导出为
+    /// `acme_http_server_requests`
+    #[serde(default)]
+    pub prefix: Option<String>,
+    /// 按原名称修改指标名称
+    #[serde(default)]
+    pub rename: BTreeMap<String, String>,
+    /// 不再记录的指标，`*` 结尾的项按前缀匹配
+    #[serde(default)]
+    pub disabled: Vec<String>,
+    /// 直方图的桶上界，为空时直方图导出为分位数摘要
+    #[serde(default)]
+    pub buckets: Vec<f64>,
+    /// Tokio 运行时指标配置
+    #[serde(default)]
+    pub runtime: RuntimeMetricsConfig,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            path: default_path(),
+            tags: BTreeMap::new(),
+            common_tags: Vec::new(),
+            prefix: None,
+            rename: BTreeMap::new(),
+            disabled: Vec::new(),
+            buckets: Vec::new(),
+            runtime: RuntimeMetricsConfig::default(),
+        }
+    }
+}
+
+impl MetricsConfig {
+    /// 从配置管理器读取 `[metrics]` 章节，并按 `common_tags` 添加公共标签
+    ///
+    /// 未配置时使用默认值
+    ///
+    /// # 错误
+    /// 配置格式错误或 `common_tags` 中有不支持的标签时返回错误
+    pub fn load(config: &ConfigurationManager) -> Result<Self> {
+        let mut metrics: Self = if config.contains_key("metrics") {
+            config.get_section("metrics")?
+        } else {
+            Self::default()
+        };
+        let app = config
+            .get_string("app.name")
+            .unwrap_or_else(|_| AppConfig::default().name);
+        metrics.resolve_common_tags(&app, config.profile())?;
+        Ok(metrics)
+    }
+
+    /// 按 `common_tags` 添加公共标签，`tags` 中已配置的同名标签不变
+    ///
+    /// # 错误
+    /// 有不支持的标签时返回验证错误
+    pub fn resolve_common_tags(&mut self, app: &str, env: &str) -> Result<()> {
+        for tag in &self.common_tags {
+            let value = match tag.as_str() {
+                "app" => app.to_string(),
+                "env" => env.to_string(),
+                "instance" => hostname(),
+                other => {
+                    return Err(Error::validation(format!(
+                        "不支持的公共标签: {}，可选 app、env、instance",
+                        other
+                    )))
+                }
+            };
+            self.tags.entry(tag.clone()).or_insert(value);
+        }
+        Ok(())
+    }
+}
+
+impl Configuration for MetricsConfig {}
+
+/// 按配置创建 Prometheus 记录器
+///
+/// # 错误
+/// 直方图桶无效时返回验证错误
+#[cfg(feature = "prometheus")]
+pub fn build_prometheus_recorder(config: &MetricsConfig) -> Result<PrometheusRecorder> {
+    let mut builder = metrics_exporter_prometheus::PrometheusBuilder::new();
+    for (key, value) in &config.tags {
+        builder = builder.add_global_label(key, value);
+    }
+    if !config.buckets.is_empty() {
+        builder = builder
+            .set_buckets(&config.buckets)
+            .map_err(|e| Error::validation(format!("无效的 metrics.buckets: {}", e)))?;
+    }
+    Ok(builder.build_recorder())
+}
+
+/// 按配置安装全局 Prometheus 记录器，指标按 [`MetricNaming`] 规则改写
+///
+/// 在 Tokio 运行时中调用时启动后台任务定期清理直方图数据
+///
+/// # 错误
+/// 配置无效或已安装其他全局记录器时返回错误
+#[cfg(feature = "prometheus")]
+pub fn install_prometheus(config: &MetricsConfig) -> Result<PrometheusHandle> {
+    let recorder = build_prometheus_recorder(config)?;
+    let handle = recorder.handle();
+    let recorder = NamingRecorder::new(MetricNaming::from_config(config), recorder);
+    ::metrics::set_global_recorder(recorder)
+        .map_err(|e| Error::internal(format!("安装指标记录器失败: {}", e)))?;
+    let _ = PROMETHEUS_HANDLE.set(handle.clone());
+
+    if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+        let upkeep = handle.clone();
+        runtime.spawn(async move {
+            let mut ticker = tokio::time::interval(UPKEEP_INTERVAL);
+            loop {
+                ticker.tick().await;
+                upkeep.run_upkeep();
+            }
+        });
+    }
+    Ok(handle)
+}
+
+/// 获取已安装的 Prometheus 记录器句柄
+#[cfg(feature = "prometheus")]
+pub fn prometheus_handle() -> Option<PrometheusHandle> {
+    PROMETHEUS_HANDLE.get().cloned()
+}
+
+/// 以 Prometheus 文本格式导出所有指标，未安装记录器时返回 `None`
+#[cfg(feature = "prometheus")]
+pub fn render_prometheus() -> Option<String> {
+    PROMETHEUS_HANDLE.get().map(PrometheusHandle::render)
+}
+
+// 默认值函数
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_path() -> String {
+    "/metrics".to_string()
+}
+
+#[cfg(all(test, feature = "prometheus"))]
+mod tests {
+    use super::*;
+
+    /// 测试公共标签和直方图桶
+    #[test]
+    fn test_prometheus_recorder() {
+        let config: MetricsConfig = serde_json::from_value(serde_json::json!({
+            "tags": { "app": "orders" },
+            "buckets": [0.1, 1.0],
+        }))
+        .unwrap();
+        let recorder = build_prometheus_recorder(&config).unwrap();
+        let handle = recorder.handle();
+        ::metrics::with_local_recorder(&recorder, || {
+            counter!("orders.created", "channel" => "web").increment(2);
+            histogram!("orders.latency").record(0.5);
+        });
+
+        let output = handle.render();
+        assert!(output.contains("orders_created{app=\"orders\",channel=\"web\"} 2"));
+        assert!(output.contains("orders_latency_bucket{app=\"orders\",le=\"1\"} 1"));
+        assert!(output.contains("orders_latency_bucket{app=\"orders\",le=\"0.1\"} 0"));
+    }
+}
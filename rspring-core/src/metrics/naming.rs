@@ -0,0 +1,178 @@
+//! 指标命名规则模块
+//!
+//! 按 `[metrics]` 中的命名规则改写框架和应用记录的指标，使导出的指标符合组织的命名约定：
+//! - `disabled` 中的指标不再记录，`*` 结尾的项按前缀匹配，如 `"tokio.*"`
+//! - `rename` 按原名称修改指标名称
+//! - `prefix` 添加到所有指标名称之前，以 `.` 连接，导出到 Prometheus 时转换为 `_`
+//!
+//! 规则按原名称匹配，即框架文档中的指标名称，如 `http.server.requests`
+
+use std::collections::BTreeMap;
+
+use metrics::{
+    Counter, Gauge, Histogram, Key, KeyName, Label, Metadata, Recorder, SharedString, Unit,
+};
+
+use crate::metrics::MetricsConfig;
+
+/// 指标命名规则
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MetricNaming {
+    /// 名称前缀
+    prefix: Option<String>,
+    /// 原名称到新名称的映射
+    rename: BTreeMap<String, String>,
+    /// 不再记录的指标
+    disabled: Vec<String>,
+}
+
+impl MetricNaming {
+    /// 按 `[metrics]` 配置创建命名规则
+    pub fn from_config(config: &MetricsConfig) -> Self {
+        Self {
+            prefix: config
+                .prefix
+                .as_deref()
+                .map(|prefix| prefix.trim_end_matches('.').to_string())
+                .filter(|prefix| !prefix.is_empty()),
+            rename: config.rename.clone(),
+            disabled: config.disabled.clone(),
+        }
+    }
+
+    /// 获取指标改写后的名称，指标被禁用时返回 `None`
+    pub fn name(&self, name: &str) -> Option<String> {
+        let disabled = self
+            .disabled
+            .iter()
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => name.starts_with(prefix),
+                None => name == pattern,
+            });
+        if disabled {
+            return None;
+        }
+        let name = self.rename.get(name).map_or(name, String::as_str);
+        Some(match &self.prefix {
+            Some(prefix) => format!("{}.{}", prefix, name),
+            None => name.to_string(),
+        })
+    }
+
+    /// 改写指标键，保留标签
+    fn key(&self, key: &Key) -> Option<Key> {
+        let name = self.name(key.name())?;
+        Some(Key::from_parts(
+            name,
+            key.labels().cloned().collect::<Vec<Label>>(),
+        ))
+    }
+}
+
+/// 按命名规则改写指标后交给被包装的记录器
+///
+/// # 示例
+/// ```rust
+/// let naming = MetricNaming::from_config(&config);
+/// metrics::set_global_recorder(NamingRecorder::new(naming, build_prometheus_recorder(&config)?))?;
+/// ```
+#[derive(Debug)]
+pub struct NamingRecorder<R> {
+    /// 命名规则
+    naming: MetricNaming,
+    /// 被包装的记录器
+    inner: R,
+}
+
+impl<R> NamingRecorder<R> {
+    /// 包装记录器
+    pub fn new(naming: MetricNaming, inner: R) -> Self {
+        Self { naming, inner }
+    }
+}
+
+impl<R: Recorder> Recorder for NamingRecorder<R> {
+    fn describe_counter(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        if let Some(name) = self.naming.name(key.as_str()) {
+            self.inner.describe_counter(name.into(), unit, description);
+        }
+    }
+
+    fn describe_gauge(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        if let Some(name) = self.naming.name(key.as_str()) {
+            self.inner.describe_gauge(name.into(), unit, description);
+        }
+    }
+
+    fn describe_histogram(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        if let Some(name) = self.naming.name(key.as_str()) {
+            self.inner
+                .describe_histogram(name.into(), unit, description);
+        }
+    }
+
+    fn register_counter(&self, key: &Key, metadata: &Metadata<'_>) -> Counter {
+        match self.naming.key(key) {
+            Some(key) => self.inner.register_counter(&key, metadata),
+            None => Counter::noop(),
+        }
+    }
+
+    fn register_gauge(&self, key: &Key, metadata: &Metadata<'_>) -> Gauge {
+        match self.naming.key(key) {
+            Some(key) => self.inner.register_gauge(&key, metadata),
+            None => Gauge::noop(),
+        }
+    }
+
+    fn register_histogram(&self, key: &Key, metadata: &Metadata<'_>) -> Histogram {
+        match self.naming.key(key) {
+            Some(key) => self.inner.register_histogram(&key, metadata),
+            None => Histogram::noop(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "prometheus"))]
+mod tests {
+    use super::*;
+    use crate::metrics::{build_prometheus_recorder, counter, gauge};
+
+    /// 测试前缀、重命名、禁用规则和公共标签
+    #[test]
+    fn test_metric_naming() {
+        let mut config: MetricsConfig = serde_json::from_value(serde_json::json!({
+            "prefix": "acme",
+            "common_tags": ["app", "env"],
+            "tags": { "env": "staging" },
+            "rename": { "http.server.requests": "http.requests" },
+            "disabled": ["tokio.*", "jobs.queue.depth"],
+        }))
+        .unwrap();
+        config.resolve_common_tags("orders", "prod").unwrap();
+        assert_eq!(config.tags["app"], "orders");
+        // 显式配置的标签优先
+        assert_eq!(config.tags["env"], "staging");
+
+        let prometheus = build_prometheus_recorder(&config).unwrap();
+        let handle = prometheus.handle();
+        let recorder = NamingRecorder::new(MetricNaming::from_config(&config), prometheus);
+        metrics::with_local_recorder(&recorder, || {
+            counter!("http.server.requests", "status" => "200").increment(3);
+            counter!("orders.created").increment(1);
+            gauge!("tokio.workers").set(4.0);
+            gauge!("jobs.queue.depth").set(7.0);
+        });
+
+        let output = handle.render();
+        assert!(
+            output.contains("acme_http_requests{app=\"orders\",env=\"staging\",status=\"200\"} 3")
+        );
+        assert!(output.contains("acme_orders_created{app=\"orders\",env=\"staging\"} 1"));
+        assert!(!output.contains("tokio"));
+        assert!(!output.contains("jobs_queue_depth"));
+
+        config.common_tags = vec!["region".to_string()];
+        assert!(config.resolve_common_tags("orders", "prod").is_err());
+    }
+}
@@ -0,0 +1,198 @@
+//! Tokio 运行时指标模块
+//!
+//! 按 `[metrics.runtime]` 配置定期采集 Tokio 运行时的状态，通过 `metrics` 门面导出：
+//! - `tokio.workers` - 工作线程数
+//! - `tokio.workers.utilization` - 采样间隔内工作线程的平均忙碌比例（0 到 1）
+//! - `tokio.workers.parks` - 工作线程累计休眠次数
+//! - `tokio.tasks.alive` - 存活的任务数
+//! - `tokio.queue.global.depth` - 全局队列中等待调度的任务数
+//!
+//! 以 `--cfg tokio_unstable` 编译时还导出：
+//! - `tokio.queue.local.depth` - 所有工作线程本地队列中的任务数
+//! - `tokio.tasks.spawned` - 累计创建的任务数
+//! - `tokio.blocking.threads`、`tokio.blocking.threads.idle` - 阻塞线程池的线程数和空闲线程数
+//! - `tokio.blocking.queue.depth` - 等待阻塞线程的任务数
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tokio::runtime::{Handle, RuntimeMetrics};
+use tokio::task::JoinHandle;
+
+/// 工作线程数指标
+pub const WORKERS_METRIC: &str = "tokio.workers";
+
+/// 工作线程忙碌比例指标
+pub const WORKER_UTILIZATION_METRIC: &str = "tokio.workers.utilization";
+
+/// 工作线程休眠次数指标
+pub const WORKER_PARKS_METRIC: &str = "tokio.workers.parks";
+
+/// 存活任务数指标
+pub const ALIVE_TASKS_METRIC: &str = "tokio.tasks.alive";
+
+/// 全局队列深度指标
+pub const GLOBAL_QUEUE_DEPTH_METRIC: &str = "tokio.queue.global.depth";
+
+/// 运行时指标配置
+///
+/// # 示例
+/// ```toml
+/// [metrics.runtime]
+/// enabled = true
+/// interval = "15s"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RuntimeMetricsConfig {
+    /// 是否采集运行时指标
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 采样间隔
+    ///
+    /// # 默认值
+    /// `"10s"`
+    #[serde(default = "default_interval", with = "crate::config::duration")]
+    pub interval: Duration,
+}
+
+impl Default for RuntimeMetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            interval: default_interval(),
+        }
+    }
+}
+
+/// 运行时指标采样器
+///
+/// 忙碌比例由相邻两次采样之间的忙碌时间计算，第一次采样不记录该指标
+#[derive(Debug)]
+pub struct RuntimeSampler {
+    /// 运行时指标
+    metrics: RuntimeMetrics,
+    /// 上一次采样的时间和所有工作线程的累计忙碌时间
+    last: Option<(Instant, Duration)>,
+}
+
+impl RuntimeSampler {
+    /// 创建运行时的采样器
+    pub fn new(handle: &Handle) -> Self {
+        Self {
+            metrics: handle.metrics(),
+            last: None,
+        }
+    }
+
+    /// 采样一次并记录指标
+    pub fn record(&mut self) {
+        let metrics = &self.metrics;
+        let workers = metrics.num_workers();
+        ::metrics::gauge!(WORKERS_METRIC).set(workers as f64);
+        ::metrics::gauge!(ALIVE_TASKS_METRIC).set(metrics.num_alive_tasks() as f64);
+        ::metrics::gauge!(GLOBAL_QUEUE_DEPTH_METRIC).set(metrics.global_queue_depth() as f64);
+
+        #[cfg(target_has_atomic = "64")]
+        {
+            let now = Instant::now();
+            let busy: Duration = (0..workers)
+                .map(|worker| metrics.worker_total_busy_duration(worker))
+                .sum();
+            if let Some((last_time, last_busy)) = self.last {
+                let elapsed = now.duration_since(last_time).as_secs_f64() * workers as f64;
+                if elapsed > 0.0 {
+                    let utilization = busy.saturating_sub(last_busy).as_secs_f64() / elapsed;
+                    ::metrics::gauge!(WORKER_UTILIZATION_METRIC).set(utilization.min(1.0));
+                }
+            }
+            self.last = Some((now, busy));
+
+            let parks: u64 = (0..workers)
+                .map(|worker| metrics.worker_park_count(worker))
+                .sum();
+            ::metrics::counter!(WORKER_PARKS_METRIC).absolute(parks);
+        }
+
+        #[cfg(tokio_unstable)]
+        {
+            let local_depth: usize = (0..workers)
+                .map(|worker| metrics.worker_local_queue_depth(worker))
+                .sum();
+            ::metrics::gauge!("tokio.queue.local.depth").set(local_depth as f64);
+            ::metrics::counter!("tokio.tasks.spawned").absolute(metrics.spawned_tasks_count());
+            ::metrics::gauge!("tokio.blocking.threads").set(metrics.num_blocking_threads() as f64);
+            ::metrics::gauge!("tokio.blocking.threads.idle")
+                .set(metrics.num_idle_blocking_threads() as f64);
+            ::metrics::gauge!("tokio.blocking.queue.depth")
+                .set(metrics.blocking_queue_depth() as f64);
+        }
+    }
+}
+
+/// 在当前运行时中启动后台任务，按配置的间隔记录运行时指标
+///
+/// 不在 Tokio 运行时中调用时返回 `None`
+///
+/// # 示例
+/// ```rust
+/// let config = MetricsConfig::load(&config_manager)?;
+/// spawn_runtime_metrics(&config.runtime);
+/// ```
+pub fn spawn_runtime_metrics(config: &RuntimeMetricsConfig) -> Option<JoinHandle<()>> {
+    let handle = Handle::try_current().ok()?;
+    let mut sampler = RuntimeSampler::new(&handle);
+    let interval = config.interval;
+    Some(handle.spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            sampler.record();
+        }
+    }))
+}
+
+// 默认值函数
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_interval() -> Duration {
+    Duration::from_secs(10)
+}
+
+#[cfg(all(test, feature = "prometheus"))]
+mod tests {
+    use super::*;
+    use crate::metrics::{build_prometheus_recorder, MetricsConfig};
+
+    /// 测试采样记录的运行时指标
+    #[test]
+    fn test_runtime_sampler() {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .enable_all()
+            .build()
+            .unwrap();
+        let recorder = build_prometheus_recorder(&MetricsConfig::default()).unwrap();
+        let handle = recorder.handle();
+        let mut sampler = RuntimeSampler::new(runtime.handle());
+
+        let task = runtime.spawn(std::future::pending::<()>());
+        ::metrics::with_local_recorder(&recorder, || {
+            sampler.record();
+            std::thread::sleep(Duration::from_millis(10));
+            sampler.record();
+        });
+        task.abort();
+
+        let output = handle.render();
+        assert!(output.contains("tokio_workers 2"));
+        assert!(output.contains("tokio_tasks_alive 1"));
+        assert!(output.contains("tokio_workers_utilization "));
+        assert!(output.contains("tokio_workers_parks "));
+    }
+}
@@ -0,0 +1,278 @@
+//! 分页与排序模块
+//!
+//! 定义 Web 层和数据访问层共用的分页参数、排序条件和分页结果，
+//! 控制器中解析的分页请求可以直接传给仓储生成 `LIMIT`/`OFFSET` 和 `ORDER BY` 子句
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+
+/// 分页参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Page {
+    /// 页码（从0开始）
+    pub page: u64,
+    /// 每页大小
+    pub size: u64,
+}
+
+impl Default for Page {
+    fn default() -> Self {
+        Self { page: 0, size: 20 }
+    }
+}
+
+impl Page {
+    /// 创建分页参数
+    pub fn new(page: u64, size: u64) -> Self {
+        Self { page, size }
+    }
+    
+    /// 计算偏移量，溢出时返回 `u64::MAX`
+    pub fn offset(&self) -> u64 {
+        self.page.saturating_mul(self.size)
+    }
+}
+
+/// 分页结果
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PageResult<T> {
+    /// 数据列表
+    pub content: Vec<T>,
+    /// 当前页码
+    pub page: u64,
+    /// 每页大小
+    pub size: u64,
+    /// 总记录数
+    pub total: u64,
+    /// 总页数
+    pub total_pages: u64,
+    /// 是否第一页
+    pub first: bool,
+    /// 是否最后一页
+    pub last: bool,
+    /// 是否为空
+    pub empty: bool,
+}
+
+impl<T> PageResult<T> {
+    /// 创建分页结果
+    pub fn new(content: Vec<T>, page: u64, size: u64, total: u64) -> Self {
+        let total_pages = if size > 0 { (total + size - 1) / size } else { 0 };
+        let first = page == 0;
+        let last = page >= total_pages.saturating_sub(1);
+        let empty = content.is_empty();
+        
+        Self {
+            content,
+            page,
+            size,
+            total,
+            total_pages,
+            first,
+            last,
+            empty,
+        }
+    }
+    
+    /// 创建空分页结果
+    pub fn empty(page: u64, size: u64) -> Self {
+        Self::new(Vec::new(), page, size, 0)
+    }
+    
+    /// 是否有下一页
+    pub fn has_next(&self) -> bool {
+        !self.last && self.page + 1 < self.total_pages
+    }
+    
+    /// 是否有上一页
+    pub fn has_previous(&self) -> bool {
+        !self.first && self.page > 0
+    }
+    
+    /// 获取下一页页码
+    pub fn next_page(&self) -> Option<u64> {
+        if self.has_next() {
+            Some(self.page + 1)
+        } else {
+            None
+        }
+    }
+    
+    /// 获取上一页页码
+    pub fn previous_page(&self) -> Option<u64> {
+        if self.has_previous() {
+            Some(self.page - 1)
+        } else {
+            None
+        }
+    }
+}
+
+/// 排序方向
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    /// 升序
+    #[default]
+    Asc,
+    /// 降序
+    Desc,
+}
+
+impl Direction {
+    /// 解析排序方向，忽略大小写
+    fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "asc" => Some(Self::Asc),
+            "desc" => Some(Self::Desc),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Asc => f.write_str("ASC"),
+            Self::Desc => f.write_str("DESC"),
+        }
+    }
+}
+
+/// 单个字段的排序
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Order {
+    /// 排序字段
+    pub property: String,
+    /// 排序方向
+    pub direction: Direction,
+}
+
+impl Order {
+    /// 创建升序排序
+    pub fn asc(property: impl Into<String>) -> Self {
+        Self {
+            property: property.into(),
+            direction: Direction::Asc,
+        }
+    }
+
+    /// 创建降序排序
+    pub fn desc(property: impl Into<String>) -> Self {
+        Self {
+            property: property.into(),
+            direction: Direction::Desc,
+        }
+    }
+}
+
+/// 排序条件，按添加顺序生效
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Sort {
+    /// 排序字段列表
+    pub orders: Vec<Order>,
+}
+
+impl Sort {
+    /// 创建空排序
+    pub fn unsorted() -> Self {
+        Self::default()
+    }
+
+    /// 按指定字段排序
+    pub fn by(orders: Vec<Order>) -> Self {
+        Self { orders }
+    }
+
+    /// 追加排序字段
+    pub fn and(mut self, order: Order) -> Self {
+        self.orders.push(order);
+        self
+    }
+
+    /// 是否没有排序条件
+    pub fn is_unsorted(&self) -> bool {
+        self.orders.is_empty()
+    }
+
+    /// 解析 `sort` 参数
+    ///
+    /// 每个参数的格式为 `字段[,字段...][,asc|desc]`，方向省略时为升序
+    ///
+    /// # 错误
+    /// 字段名包含非法字符时返回错误
+    pub fn parse<'a>(params: impl IntoIterator<Item = &'a str>) -> Result<Self> {
+        let mut orders = Vec::new();
+        for param in params {
+            let mut parts: Vec<&str> = param.split(',').map(str::trim).filter(|part| !part.is_empty()).collect();
+            let direction = match parts.last().and_then(|last| Direction::parse(last)) {
+                Some(direction) => {
+                    parts.pop();
+                    direction
+                }
+                None => Direction::Asc,
+            };
+
+            for property in parts {
+                if !is_valid_property(property) {
+                    return Err(Error::validation(format!("无效的排序字段: {}", property)));
+                }
+                orders.push(Order {
+                    property: property.to_string(),
+                    direction,
+                });
+            }
+        }
+        Ok(Self { orders })
+    }
+
+    /// 生成 `ORDER BY` 之后的排序子句，如 `name DESC, id ASC`
+    ///
+    /// 没有排序条件时返回 `None`
+    pub fn to_sql(&self) -> Option<String> {
+        if self.orders.is_empty() {
+            return None;
+        }
+        Some(
+            self.orders
+                .iter()
+                .map(|order| format!("{} {}", order.property, order.direction))
+                .collect::<Vec<_>>()
+                .join(", "),
+        )
+    }
+}
+
+/// 校验排序字段，只允许字母、数字、下划线和点
+pub fn is_valid_property(property: &str) -> bool {
+    property
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试排序参数解析和排序子句生成
+    #[test]
+    fn test_sort() {
+        let sort = Sort::parse(["name,email,desc", "id"]).unwrap();
+        assert_eq!(sort.to_sql().as_deref(), Some("name DESC, email DESC, id ASC"));
+        assert!(Sort::unsorted().to_sql().is_none());
+
+        let error = Sort::parse(["name;drop table"]).unwrap_err();
+        assert!(matches!(error, Error::Validation { .. }));
+        assert!(is_valid_property("user.created_at"));
+    }
+
+    /// 测试偏移量计算溢出时饱和
+    #[test]
+    fn test_page_offset() {
+        assert_eq!(Page::new(2, 10).offset(), 20);
+        assert_eq!(Page::new(u64::MAX, 20).offset(), u64::MAX);
+    }
+}
@@ -0,0 +1,509 @@
+//! 容错模块
+//!
+//! 提供类似 Resilience4j 的断路器：
+//! - 按最近 `sliding_window_size` 次调用统计失败率，达到阈值后打开，拒绝调用
+//! - 打开 `wait_duration` 后进入半开状态，放行少量试探调用，全部成功则关闭，任一失败则重新打开
+//! - 每个断路器按名称在 `[resilience.circuit_breakers.<name>]` 中配置，可通过
+//!   `#[CircuitBreaker]` 注解或 [`CircuitBreaker::call`] 包装调用
+
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use crate::config::properties::Configuration;
+use crate::error::Error;
+
+/// 容错配置
+///
+/// # 示例
+/// ```toml
+/// [resilience.circuit_breakers.payments]
+/// failure_rate_threshold = 50
+/// sliding_window_size = 20
+/// minimum_calls = 10
+/// wait_duration = "30s"
+/// half_open_calls = 3
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct ResilienceConfig {
+    /// 按名称的断路器配置，未配置的名称使用默认值
+    #[serde(default)]
+    pub circuit_breakers: HashMap<String, CircuitBreakerConfig>,
+}
+
+impl Configuration for ResilienceConfig {}
+
+/// 断路器配置
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct CircuitBreakerConfig {
+    /// 打开断路器的失败率阈值（百分比）
+    ///
+    /// # 默认值
+    /// `50`
+    #[serde(default = "default_failure_rate_threshold")]
+    pub failure_rate_threshold: f64,
+    /// 统计失败率的最近调用次数
+    ///
+    /// # 默认值
+    /// `20`
+    #[serde(default = "default_sliding_window_size")]
+    pub sliding_window_size: usize,
+    /// 开始计算失败率所需的最少调用次数
+    ///
+    /// # 默认值
+    /// `10`
+    #[serde(default = "default_minimum_calls")]
+    pub minimum_calls: usize,
+    /// 打开状态的持续时间，之后进入半开状态
+    ///
+    /// # 默认值
+    /// `"60s"`
+    #[serde(default = "default_wait_duration", with = "crate::config::duration")]
+    pub wait_duration: Duration,
+    /// 半开状态放行的试探调用次数
+    ///
+    /// # 默认值
+    /// `3`
+    #[serde(default = "default_half_open_calls")]
+    pub half_open_calls: usize,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_rate_threshold: default_failure_rate_threshold(),
+            sliding_window_size: default_sliding_window_size(),
+            minimum_calls: default_minimum_calls(),
+            wait_duration: default_wait_duration(),
+            half_open_calls: default_half_open_calls(),
+        }
+    }
+}
+
+/// 断路器状态
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CircuitState {
+    /// 关闭，正常放行
+    Closed,
+    /// 打开，拒绝调用
+    Open,
+    /// 半开，放行试探调用
+    HalfOpen,
+}
+
+/// 断路器指标快照
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct CircuitBreakerMetrics {
+    /// 当前状态
+    pub state: CircuitState,
+    /// 滑动窗口内的失败率（百分比），调用次数不足时为 `None`
+    pub failure_rate: Option<f64>,
+    /// 滑动窗口内的调用次数
+    pub buffered_calls: usize,
+    /// 滑动窗口内的失败次数
+    pub failed_calls: usize,
+    /// 累计被拒绝的调用次数
+    pub not_permitted_calls: u64,
+}
+
+/// 断路器内部状态
+#[derive(Debug)]
+struct CircuitInner {
+    /// 当前状态
+    state: CircuitState,
+    /// 最近调用的结果，`true` 表示失败
+    window: VecDeque<bool>,
+    /// 进入打开状态的时间
+    opened_at: Option<Instant>,
+    /// 半开状态已放行的调用次数
+    half_open_permitted: usize,
+    /// 半开状态已成功的调用次数
+    half_open_succeeded: usize,
+    /// 状态变更次数，用于判断许可是否在当前状态下申请
+    generation: u64,
+    /// 累计被拒绝的调用次数
+    not_permitted: u64,
+}
+
+/// 断路器
+///
+/// # 示例
+/// ```rust
+/// let breaker = circuit_breaker("payments");
+/// let receipt = breaker.call(payment_client.charge(order_id, amount)).await?;
+/// ```
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    /// 断路器名称
+    name: String,
+    /// 断路器配置
+    config: CircuitBreakerConfig,
+    /// 内部状态
+    inner: Mutex<CircuitInner>,
+}
+
+impl CircuitBreaker {
+    /// 创建关闭状态的断路器
+    pub fn new(name: impl Into<String>, config: CircuitBreakerConfig) -> Self {
+        Self {
+            name: name.into(),
+            config,
+            inner: Mutex::new(CircuitInner {
+                state: CircuitState::Closed,
+                window: VecDeque::new(),
+                opened_at: None,
+                half_open_permitted: 0,
+                half_open_succeeded: 0,
+                generation: 0,
+                not_permitted: 0,
+            }),
+        }
+    }
+
+    /// 断路器名称
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 当前状态，打开时间已到时返回半开
+    pub fn state(&self) -> CircuitState {
+        let mut inner = self.lock();
+        self.refresh(&mut inner);
+        inner.state
+    }
+
+    /// 获取指标快照
+    pub fn metrics(&self) -> CircuitBreakerMetrics {
+        let mut inner = self.lock();
+        self.refresh(&mut inner);
+        let failed_calls = inner.window.iter().filter(|failed| **failed).count();
+        CircuitBreakerMetrics {
+            state: inner.state,
+            failure_rate: self.failure_rate(&inner.window),
+            buffered_calls: inner.window.len(),
+            failed_calls,
+            not_permitted_calls: inner.not_permitted,
+        }
+    }
+
+    /// 通过断路器执行调用
+    ///
+    /// 断路器打开时不执行调用，直接返回运行时错误；调用返回 `Err` 时记为失败，
+    /// 调用被取消或发生 panic 时不记录结果，归还半开状态的试探名额
+    ///
+    /// # 错误
+    /// 断路器拒绝调用或调用本身失败时返回错误
+    pub async fn call<T, E, Fut>(&self, call: Fut) -> std::result::Result<T, E>
+    where
+        Fut: Future<Output = std::result::Result<T, E>>,
+        E: From<Error>,
+    {
+        let permit = self.acquire()?;
+        let result = call.await;
+        permit.record(result.is_ok());
+        result
+    }
+
+    /// 申请调用许可
+    ///
+    /// 调用完成后通过 [`CircuitPermit::record`] 记录结果；许可未记录结果就被释放时，
+    /// 归还半开状态的试探名额
+    ///
+    /// # 错误
+    /// 断路器打开或半开状态的试探名额已用完时返回运行时错误
+    pub fn acquire(&self) -> crate::error::Result<CircuitPermit<'_>> {
+        let mut inner = self.lock();
+        self.refresh(&mut inner);
+
+        let permitted = match inner.state {
+            CircuitState::Closed => true,
+            CircuitState::Open => false,
+            CircuitState::HalfOpen => {
+                if inner.half_open_permitted < self.config.half_open_calls.max(1) {
+                    inner.half_open_permitted += 1;
+                    true
+                } else {
+                    false
+                }
+            }
+        };
+
+        if permitted {
+            Ok(CircuitPermit {
+                breaker: self,
+                generation: inner.generation,
+                recorded: false,
+            })
+        } else {
+            inner.not_permitted += 1;
+            Err(Error::runtime(format!("断路器 {} 已打开，拒绝调用", self.name)))
+        }
+    }
+
+    /// 记录调用结果
+    fn record(&self, success: bool) {
+        let mut inner = self.lock();
+        match inner.state {
+            CircuitState::Closed => {
+                inner.window.push_back(!success);
+                while inner.window.len() > self.config.sliding_window_size.max(1) {
+                    inner.window.pop_front();
+                }
+                let tripped = self
+                    .failure_rate(&inner.window)
+                    .is_some_and(|rate| rate >= self.config.failure_rate_threshold);
+                if tripped {
+                    self.transition(&mut inner, CircuitState::Open);
+                }
+            }
+            CircuitState::HalfOpen if !success => self.transition(&mut inner, CircuitState::Open),
+            CircuitState::HalfOpen => {
+                inner.half_open_succeeded += 1;
+                if inner.half_open_succeeded >= self.config.half_open_calls.max(1) {
+                    self.transition(&mut inner, CircuitState::Closed);
+                }
+            }
+            // 打开前已放行的调用，结果不再影响状态
+            CircuitState::Open => {}
+        }
+    }
+
+    /// 强制重置为关闭状态
+    pub fn reset(&self) {
+        let mut inner = self.lock();
+        self.transition(&mut inner, CircuitState::Closed);
+    }
+
+    /// 释放未记录结果的许可，仍处于申请时的半开状态时归还试探名额
+    fn release(&self, generation: u64) {
+        let mut inner = self.lock();
+        if inner.state == CircuitState::HalfOpen && inner.generation == generation {
+            inner.half_open_permitted = inner.half_open_permitted.saturating_sub(1);
+        }
+    }
+
+    /// 打开时间已到时进入半开状态
+    fn refresh(&self, inner: &mut CircuitInner) {
+        let expired = inner
+            .opened_at
+            .is_some_and(|opened_at| opened_at.elapsed() >= self.config.wait_duration);
+        if inner.state == CircuitState::Open && expired {
+            self.transition(inner, CircuitState::HalfOpen);
+        }
+    }
+
+    /// 切换状态并清空统计
+    fn transition(&self, inner: &mut CircuitInner, state: CircuitState) {
+        if inner.state != state {
+            tracing::warn!("断路器 {} 状态变更: {:?} -> {:?}", self.name, inner.state, state);
+        }
+        inner.state = state;
+        inner.window.clear();
+        inner.opened_at = (state == CircuitState::Open).then(Instant::now);
+        inner.half_open_permitted = 0;
+        inner.half_open_succeeded = 0;
+        inner.generation += 1;
+    }
+
+    /// 计算失败率，调用次数不足时返回 `None`
+    fn failure_rate(&self, window: &VecDeque<bool>) -> Option<f64> {
+        if window.is_empty() || window.len() < self.config.minimum_calls {
+            return None;
+        }
+        let failed = window.iter().filter(|failed| **failed).count();
+        Some(failed as f64 * 100.0 / window.len() as f64)
+    }
+
+    /// 获取内部状态锁
+    fn lock(&self) -> std::sync::MutexGuard<'_, CircuitInner> {
+        self.inner.lock().expect("断路器状态锁已损坏")
+    }
+}
+
+/// 断路器的调用许可
+///
+/// 由 [`CircuitBreaker::acquire`] 申请，调用完成后通过 [`record`](Self::record) 记录结果；
+/// 调用被取消或发生 panic 导致许可未记录结果就被释放时，归还半开状态的试探名额，
+/// 避免断路器一直停留在半开状态拒绝调用
+#[derive(Debug)]
+#[must_use = "许可须在调用完成后记录结果"]
+pub struct CircuitPermit<'a> {
+    /// 所属断路器
+    breaker: &'a CircuitBreaker,
+    /// 申请时的状态变更次数
+    generation: u64,
+    /// 是否已记录结果
+    recorded: bool,
+}
+
+impl CircuitPermit<'_> {
+    /// 记录调用结果
+    pub fn record(mut self, success: bool) {
+        self.recorded = true;
+        self.breaker.record(success);
+    }
+}
+
+impl Drop for CircuitPermit<'_> {
+    fn drop(&mut self) {
+        if !self.recorded {
+            self.breaker.release(self.generation);
+        }
+    }
+}
+
+/// 断路器注册表
+#[derive(Debug, Default)]
+pub struct CircuitBreakerRegistry {
+    /// 容错配置
+    config: ResilienceConfig,
+    /// 已创建的断路器
+    breakers: RwLock<HashMap<String, Arc<CircuitBreaker>>>,
+}
+
+impl CircuitBreakerRegistry {
+    /// 根据容错配置创建
+    pub fn new(config: ResilienceConfig) -> Self {
+        Self {
+            config,
+            breakers: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// 获取指定名称的断路器，不存在时按配置创建
+    pub fn circuit_breaker(&self, name: &str) -> Arc<CircuitBreaker> {
+        if let Some(breaker) = self.breakers.read().expect("断路器注册表锁已损坏").get(name) {
+            return breaker.clone();
+        }
+
+        self.breakers
+            .write()
+            .expect("断路器注册表锁已损坏")
+            .entry(name.to_string())
+            .or_insert_with(|| {
+                let config = self.config.circuit_breakers.get(name).cloned().unwrap_or_default();
+                Arc::new(CircuitBreaker::new(name, config))
+            })
+            .clone()
+    }
+
+    /// 获取所有断路器的指标，按名称排序
+    pub fn metrics(&self) -> Vec<(String, CircuitBreakerMetrics)> {
+        let mut metrics: Vec<_> = self
+            .breakers
+            .read()
+            .expect("断路器注册表锁已损坏")
+            .iter()
+            .map(|(name, breaker)| (name.clone(), breaker.metrics()))
+            .collect();
+        metrics.sort_by(|a, b| a.0.cmp(&b.0));
+        metrics
+    }
+}
+
+/// 全局断路器注册表
+static CIRCUIT_BREAKERS: Lazy<RwLock<Arc<CircuitBreakerRegistry>>> =
+    Lazy::new(|| RwLock::new(Arc::new(CircuitBreakerRegistry::default())));
+
+/// 设置全局断路器注册表，供 `#[CircuitBreaker]` 使用
+///
+/// 在作用域中调用时的行为见[全局组件作用域](crate::scope)
+pub fn set_circuit_breaker_registry(registry: Arc<CircuitBreakerRegistry>) {
+    if let Some(registry) = crate::scope::set_scoped(registry) {
+        *CIRCUIT_BREAKERS.write().expect("断路器注册表锁已损坏") = registry;
+    }
+}
+
+/// 获取全局断路器注册表
+pub fn circuit_breaker_registry() -> Arc<CircuitBreakerRegistry> {
+    crate::scope::scoped()
+        .unwrap_or_else(|| CIRCUIT_BREAKERS.read().expect("断路器注册表锁已损坏").clone())
+}
+
+/// 从全局注册表获取指定名称的断路器
+pub fn circuit_breaker(name: &str) -> Arc<CircuitBreaker> {
+    circuit_breaker_registry().circuit_breaker(name)
+}
+
+// 默认值函数
+
+fn default_failure_rate_threshold() -> f64 {
+    50.0
+}
+
+fn default_sliding_window_size() -> usize {
+    20
+}
+
+fn default_minimum_calls() -> usize {
+    10
+}
+
+fn default_wait_duration() -> Duration {
+    Duration::from_secs(60)
+}
+
+fn default_half_open_calls() -> usize {
+    3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试关闭、打开、半开状态的转换
+    #[tokio::test]
+    async fn test_circuit_breaker() {
+        let config: ResilienceConfig = serde_json::from_str(
+            r#"{ "circuit_breakers": { "payments": { "minimum_calls": 4, "sliding_window_size": 4, "wait_duration": "50ms", "half_open_calls": 2 } } }"#,
+        )
+        .unwrap();
+        let registry = CircuitBreakerRegistry::new(config);
+        let breaker = registry.circuit_breaker("payments");
+
+        for success in [true, false, true, false] {
+            let _ = breaker
+                .call(async { if success { Ok(()) } else { Err(Error::internal("timeout")) } })
+                .await;
+        }
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(breaker.call(async { Ok::<_, Error>(()) }).await.is_err());
+        assert_eq!(breaker.metrics().not_permitted_calls, 1);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        for _ in 0..2 {
+            breaker.call(async { Ok::<_, Error>(()) }).await.unwrap();
+        }
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(registry.metrics()[0].0, "payments");
+    }
+
+    /// 测试半开状态的调用被取消后归还试探名额
+    #[tokio::test]
+    async fn test_cancelled_half_open_call() {
+        let config = CircuitBreakerConfig {
+            minimum_calls: 1,
+            sliding_window_size: 1,
+            wait_duration: Duration::from_millis(20),
+            half_open_calls: 1,
+            ..CircuitBreakerConfig::default()
+        };
+        let breaker = CircuitBreaker::new("inventory", config);
+        let _ = breaker.call(async { Err::<(), _>(Error::internal("timeout")) }).await;
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        let pending = breaker.call(std::future::pending::<Result<(), Error>>());
+        assert!(tokio::time::timeout(Duration::from_millis(10), pending).await.is_err());
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+        breaker.call(async { Ok::<_, Error>(()) }).await.unwrap();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+}
@@ -0,0 +1,231 @@
+//! 重试模块
+//!
+//! 提供类似 Spring Retry 的重试支持：
+//! - [`RetryPolicy`] 描述最大尝试次数和指数退避（可带随机抖动）
+//! - [`retry`] 按策略重复执行异步操作，只重试被判定为可重试的错误
+//! - `#[Retryable]` 注解按策略重试方法，重试耗尽后可调用 `#[Recover]` 标注的兜底方法
+
+use std::collections::hash_map::RandomState;
+use std::fmt::Display;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use crate::error::Error;
+
+/// 重试策略
+///
+/// 第 `n` 次重试前等待 `delay * multiplier^(n-1)`，不超过 `max_delay`，
+/// 再按 `jitter` 比例随机增减
+///
+/// # 示例
+/// ```rust
+/// let policy = RetryPolicy::new(5)
+///     .with_delay(Duration::from_millis(200))
+///     .with_multiplier(2.0)
+///     .with_max_delay(Duration::from_secs(10))
+///     .with_jitter(0.2);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// 最大尝试次数（包括第一次调用）
+    pub max_attempts: u32,
+    /// 首次重试前的等待时间
+    pub delay: Duration,
+    /// 每次重试等待时间的倍数，为 1 时固定间隔
+    pub multiplier: f64,
+    /// 最长等待时间
+    pub max_delay: Duration,
+    /// 随机抖动比例，取值 0 到 1
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            delay: Duration::from_secs(1),
+            multiplier: 1.0,
+            max_delay: Duration::from_secs(30),
+            jitter: 0.0,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 创建指定最大尝试次数的策略，其余使用默认值（间隔 1 秒，不退避）
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            ..Self::default()
+        }
+    }
+
+    /// 设置首次重试前的等待时间
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// 设置退避倍数
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// 设置最长等待时间
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// 设置随机抖动比例
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// 第 `retry` 次重试（从 1 开始）前的等待时间，不含抖动
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.max(1.0).powi(retry.saturating_sub(1) as i32);
+        let delay = self.delay.as_secs_f64() * factor;
+        Duration::from_secs_f64(delay.min(self.max_delay.as_secs_f64()))
+    }
+
+    /// 第 `retry` 次重试前的等待时间，含抖动
+    pub fn delay_for(&self, retry: u32) -> Duration {
+        let backoff = self.backoff(retry);
+        if self.jitter <= 0.0 {
+            return backoff;
+        }
+        // 在 [1 - jitter, 1 + jitter] 范围内随机缩放
+        let scale = 1.0 + self.jitter * (random_unit() * 2.0 - 1.0);
+        backoff.mul_f64(scale.max(0.0))
+    }
+}
+
+/// 默认的可重试错误判定
+///
+/// IO、内部、应用和运行时错误视为临时故障可以重试；验证、业务、未找到、未授权等
+/// 重试也不会成功的错误不重试
+pub fn is_transient(error: &Error) -> bool {
+    matches!(
+        error,
+        Error::Io(_) | Error::Internal { .. } | Error::Application { .. } | Error::Runtime { .. }
+    )
+}
+
+/// 按策略执行异步操作
+///
+/// 操作失败且 `retry_if` 返回 `true` 时等待后重试，直到成功、遇到不可重试的错误或
+/// 达到最大尝试次数，返回最后一次的结果
+///
+/// # 参数
+/// * `policy` - 重试策略
+/// * `name` - 操作名称，用于日志
+/// * `retry_if` - 判断错误是否可以重试
+/// * `operation` - 每次尝试时调用，返回新的 Future
+///
+/// # 示例
+/// ```rust
+/// let user = retry(&RetryPolicy::new(3), "fetch_user", is_transient, || client.fetch_user(id)).await?;
+/// ```
+pub async fn retry<T, E, F, Fut>(
+    policy: &RetryPolicy,
+    name: &str,
+    retry_if: impl Fn(&E) -> bool,
+    mut operation: F,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: Display,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(error) if attempt < max_attempts && retry_if(&error) => {
+                let delay = policy.delay_for(attempt);
+                tracing::warn!(
+                    "{} 第 {}/{} 次执行失败，{:?} 后重试: {}",
+                    name,
+                    attempt,
+                    max_attempts,
+                    delay,
+                    error
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(error) => {
+                if attempt > 1 {
+                    tracing::warn!("{} 重试 {} 次后仍然失败: {}", name, attempt - 1, error);
+                }
+                return Err(error);
+            }
+        }
+    }
+}
+
+/// 生成 [0, 1) 范围内的随机数
+fn random_unit() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// 测试指数退避、上限和抖动范围
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy::new(5)
+            .with_delay(Duration::from_millis(100))
+            .with_multiplier(2.0)
+            .with_max_delay(Duration::from_millis(300));
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(300));
+
+        let policy = policy.with_jitter(0.5);
+        for _ in 0..20 {
+            let delay = policy.delay_for(1);
+            assert!(delay >= Duration::from_millis(50) && delay <= Duration::from_millis(150));
+        }
+    }
+
+    /// 测试只重试可重试的错误
+    #[tokio::test]
+    async fn test_retry() {
+        let policy = RetryPolicy::new(3).with_delay(Duration::from_millis(1));
+        let calls = AtomicU32::new(0);
+
+        let result = retry(&policy, "flaky", is_transient, || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => Err(Error::internal("timeout")),
+                n => Ok(n),
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 2);
+
+        calls.store(0, Ordering::SeqCst);
+        let result: Result<(), Error> = retry(&policy, "invalid", is_transient, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(Error::validation("bad"))
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,151 @@
+//! 全局组件作用域模块
+//!
+//! 事件发布器、缓存管理器、事务管理器等全局组件默认在进程内共享，[`GlobalScope`] 为一组任务提供独立的副本：
+//! - 在作用域中执行时，`event_publisher()` 等获取函数优先返回作用域中设置的组件，未设置时返回全局组件
+//! - 在作用域中调用 `set_event_publisher()` 等设置函数只修改当前作用域，不影响其他作用域和全局组件
+//! - 作用域是任务本地的，`tokio::spawn` 启动的任务不继承作用域，[`TaskExecutor`](crate::TaskExecutor)
+//!   提交的任务会继承提交时的作用域
+//!
+//! 遵循以上规则的设置函数及对应的获取函数：
+//! - [`set_event_publisher`](crate::event::set_event_publisher)、`event_publisher()`
+//! - [`set_cache_manager`](crate::cache::set_cache_manager)、`cache_manager()`
+//! - [`set_transaction_manager`](crate::transaction::set_transaction_manager)、`transaction_manager()`
+//! - [`set_lock_provider`](crate::lock::set_lock_provider)、`lock_provider()`
+//! - [`set_task_executor`](crate::task::set_task_executor)、`task_executor()`
+//! - [`set_clock`](crate::clock::set_clock)、`clock()`
+//! - [`set_error_reporting`](crate::error::set_error_reporting)、`error_reporting()`
+//! - [`set_circuit_breaker_registry`](crate::resilience::set_circuit_breaker_registry)、`circuit_breaker_registry()`
+//! - [`set_message_converter`](crate::messaging::set_message_converter)、`message_converter()`
+//! - [`set_audit_recorder`](crate::auditing::set_audit_recorder)、`audit_recorder()`
+//!
+//! 测试支持用它为每个测试隔离全局组件，使并行的测试互不影响
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+
+tokio::task_local! {
+    /// 当前的全局组件作用域
+    static CURRENT_SCOPE: Arc<GlobalScope>;
+}
+
+/// 全局组件作用域
+///
+/// # 示例
+/// ```rust
+/// let scope = Arc::new(GlobalScope::new());
+/// scope.insert(Arc::new(EventPublisher::new()));
+///
+/// scope
+///     .run(async {
+///         // 只有本作用域的监听器收到事件
+///         event_publisher().register(ApplicationListener::new(on_order_created));
+///         publish_event(OrderCreated { id: 1 }).await
+///     })
+///     .await?;
+/// ```
+#[derive(Default)]
+pub struct GlobalScope {
+    /// 按类型保存的组件
+    values: RwLock<HashMap<TypeId, Box<dyn Any + Send + Sync>>>,
+}
+
+impl GlobalScope {
+    /// 创建空作用域，所有组件都使用全局组件
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置作用域中的组件，按值的类型区分，如 `Arc<EventPublisher>`、`Arc<dyn CacheManager>`
+    pub fn insert<T: Clone + Send + Sync + 'static>(&self, value: T) {
+        self.values
+            .write()
+            .expect("全局组件作用域锁已损坏")
+            .insert(TypeId::of::<T>(), Box::new(value));
+    }
+
+    /// 获取作用域中的组件，未设置时返回 `None`
+    pub fn get<T: Clone + Send + Sync + 'static>(&self) -> Option<T> {
+        self.values
+            .read()
+            .expect("全局组件作用域锁已损坏")
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref::<T>())
+            .cloned()
+    }
+
+    /// 在作用域中执行
+    pub async fn run<F: Future>(self: &Arc<Self>, future: F) -> F::Output {
+        CURRENT_SCOPE.scope(self.clone(), future).await
+    }
+}
+
+impl std::fmt::Debug for GlobalScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GlobalScope")
+            .field(
+                "values",
+                &self.values.read().expect("全局组件作用域锁已损坏").len(),
+            )
+            .finish()
+    }
+}
+
+/// 获取当前的全局组件作用域，不在作用域中时返回 `None`
+pub fn current_scope() -> Option<Arc<GlobalScope>> {
+    CURRENT_SCOPE.try_with(Arc::clone).ok()
+}
+
+/// 获取当前作用域中的组件，不在作用域中或未设置时返回 `None`
+pub(crate) fn scoped<T: Clone + Send + Sync + 'static>() -> Option<T> {
+    CURRENT_SCOPE
+        .try_with(|scope| scope.get::<T>())
+        .ok()
+        .flatten()
+}
+
+/// 在当前作用域中设置组件，不在作用域中时原样返回，由调用方设置全局组件
+pub(crate) fn set_scoped<T: Clone + Send + Sync + 'static>(value: T) -> Option<T> {
+    match current_scope() {
+        Some(scope) => {
+            scope.insert(value);
+            None
+        }
+        None => Some(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{event_publisher, set_event_publisher, EventPublisher};
+
+    /// 测试作用域中设置的组件不影响全局组件和其他作用域
+    #[tokio::test]
+    async fn test_global_scope() {
+        let global = event_publisher();
+        let first = Arc::new(GlobalScope::new());
+        let second = Arc::new(GlobalScope::new());
+
+        let scoped = Arc::new(EventPublisher::new());
+        first.insert(scoped.clone());
+        first
+            .run(async {
+                assert!(Arc::ptr_eq(&event_publisher(), &scoped));
+                assert!(current_scope().is_some());
+            })
+            .await;
+
+        let replaced = second
+            .run(async {
+                assert!(Arc::ptr_eq(&event_publisher(), &global));
+                set_event_publisher(Arc::new(EventPublisher::new()));
+                event_publisher()
+            })
+            .await;
+        assert!(!Arc::ptr_eq(&replaced, &global));
+        assert!(Arc::ptr_eq(&event_publisher(), &global));
+        assert!(current_scope().is_none());
+    }
+}
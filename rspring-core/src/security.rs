@@ -0,0 +1,588 @@
+//! 安全上下文模块
+//!
+//! 在任务本地上下文中保存当前的认证主体，由 Web 层的认证中间件设置，
+//! 供审计字段填充、方法级权限检查等在任意位置读取。
+//!
+//! `#[PreAuthorize("hasRole('ADMIN')")]` 在方法执行前按 [`AccessExpression`] 检查当前主体的权限，
+//! 检查基于任务本地上下文而非 HTTP 请求，因此同样保护定时任务和消息监听器调用的服务方法：
+//! 这些入口没有认证主体，需用 `#[RunAs]` 或 [`with_principal`] 以指定身份执行；
+//! `#[Async]` 提交的任务继承提交者的认证主体。
+//! 启用 `password` 特性后提供密码编码器，见 [`password`] 模块
+
+#[cfg(feature = "password")]
+pub mod password;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::container::Component;
+use crate::error::{Error, Result};
+use crate::logging::{with_log_context, USER_ID_FIELD};
+
+/// 角色权限的前缀
+pub const ROLE_PREFIX: &str = "ROLE_";
+
+tokio::task_local! {
+    /// 当前的认证主体
+    static CURRENT_PRINCIPAL: Principal;
+}
+
+/// 认证主体
+///
+/// # 示例
+/// ```rust
+/// let principal = Principal::new("alice").with_authorities(["ROLE_ADMIN", "user:write"]);
+/// assert!(principal.has_authority("ROLE_ADMIN"));
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Principal {
+    /// 主体名称，如用户名或用户 ID
+    pub name: String,
+    /// 拥有的权限，角色以 `ROLE_` 开头
+    #[serde(default)]
+    pub authorities: Vec<String>,
+}
+
+impl Principal {
+    /// 创建没有任何权限的主体
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            authorities: Vec::new(),
+        }
+    }
+
+    /// 设置权限
+    pub fn with_authorities<I, S>(mut self, authorities: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.authorities = authorities.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// 添加角色，角色名未以 `ROLE_` 开头时自动添加前缀
+    pub fn with_roles<I, S>(mut self, roles: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.authorities.extend(roles.into_iter().map(|role| role_authority(&role.into())));
+        self
+    }
+
+    /// 是否拥有指定权限
+    pub fn has_authority(&self, authority: &str) -> bool {
+        self.authorities.iter().any(|owned| owned == authority)
+    }
+
+    /// 是否拥有任一权限
+    pub fn has_any_authority(&self, authorities: &[&str]) -> bool {
+        authorities.iter().any(|authority| self.has_authority(authority))
+    }
+
+    /// 是否拥有指定角色，角色名可以带或不带 `ROLE_` 前缀
+    pub fn has_role(&self, role: &str) -> bool {
+        self.has_authority(&role_authority(role))
+    }
+
+    /// 是否拥有任一角色
+    pub fn has_any_role(&self, roles: &[&str]) -> bool {
+        roles.iter().any(|role| self.has_role(role))
+    }
+
+    /// 获取拥有的角色名，不含 `ROLE_` 前缀
+    pub fn roles(&self) -> impl Iterator<Item = &str> {
+        self.authorities
+            .iter()
+            .filter_map(|authority| authority.strip_prefix(ROLE_PREFIX))
+    }
+}
+
+/// 角色对应的权限名
+fn role_authority(role: &str) -> String {
+    if role.starts_with(ROLE_PREFIX) {
+        role.to_string()
+    } else {
+        format!("{}{}", ROLE_PREFIX, role)
+    }
+}
+
+/// 获取当前的认证主体
+///
+/// 不在认证上下文中时返回 `None`
+pub fn current_principal() -> Option<Principal> {
+    CURRENT_PRINCIPAL.try_with(Principal::clone).ok()
+}
+
+/// 获取当前的认证主体
+///
+/// # 错误
+/// 不在认证上下文中时返回 `Error::Unauthorized`
+pub fn require_principal() -> Result<Principal> {
+    current_principal().ok_or(Error::Unauthorized)
+}
+
+/// 从认证主体映射为应用的用户类型
+///
+/// 用于 Web 层的 `CurrentUser<T>` 提取器和 [`SecurityContext::current_user`]
+///
+/// # 示例
+/// ```rust
+/// pub struct AppUser {
+///     pub id: u64,
+///     pub name: String,
+/// }
+///
+/// #[async_trait]
+/// impl FromPrincipal for AppUser {
+///     async fn from_principal(principal: &Principal) -> Result<Self> {
+///         user_repository()
+///             .find_by_name(&principal.name)
+///             .await?
+///             .ok_or_else(|| Error::not_found(format!("用户 {}", principal.name)))
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait FromPrincipal: Sized + Send {
+    /// 从认证主体映射
+    ///
+    /// # 错误
+    /// 映射失败时返回错误，Web 层按错误类型转换为响应
+    async fn from_principal(principal: &Principal) -> Result<Self>;
+}
+
+#[async_trait]
+impl FromPrincipal for Principal {
+    async fn from_principal(principal: &Principal) -> Result<Self> {
+        Ok(principal.clone())
+    }
+}
+
+/// 安全上下文访问器
+///
+/// 可作为组件注入到服务中，读取当前任务的认证主体
+///
+/// # 示例
+/// ```rust
+/// #[derive(Service)]
+/// pub struct OrderService {
+///     security: SecurityContext,
+/// }
+///
+/// impl OrderService {
+///     pub async fn my_orders(&self) -> Result<Vec<Order>> {
+///         let user: AppUser = self.security.current_user().await?;
+///         self.repository.find_by_owner(user.id).await
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SecurityContext;
+
+impl SecurityContext {
+    /// 获取当前的认证主体，不在认证上下文中时返回 `None`
+    pub fn principal(&self) -> Option<Principal> {
+        current_principal()
+    }
+
+    /// 获取当前的认证主体
+    ///
+    /// # 错误
+    /// 不在认证上下文中时返回 `Error::Unauthorized`
+    pub fn require_principal(&self) -> Result<Principal> {
+        require_principal()
+    }
+
+    /// 是否在认证上下文中
+    pub fn is_authenticated(&self) -> bool {
+        CURRENT_PRINCIPAL.try_with(|_| ()).is_ok()
+    }
+
+    /// 将当前的认证主体映射为应用的用户类型
+    ///
+    /// # 错误
+    /// 不在认证上下文中时返回 `Error::Unauthorized`，映射失败时返回映射的错误
+    pub async fn current_user<T: FromPrincipal>(&self) -> Result<T> {
+        T::from_principal(&require_principal()?).await
+    }
+}
+
+impl Component for SecurityContext {
+    fn component_name(&self) -> &'static str {
+        "SecurityContext"
+    }
+}
+
+/// 以指定主体执行异步任务
+///
+/// 认证中间件用它包裹后续处理；也可用于将主体传递到 `tokio::spawn` 的后台任务中。
+/// 执行期间主体名称作为 `user_id` 字段附加到日志
+///
+/// # 示例
+/// ```rust
+/// let principal = current_principal();
+/// tokio::spawn(async move {
+///     match principal {
+///         Some(principal) => with_principal(principal, job()).await,
+///         None => job().await,
+///     }
+/// });
+/// ```
+pub async fn with_principal<F: std::future::Future>(principal: Principal, future: F) -> F::Output {
+    let user_id = principal.name.clone();
+    CURRENT_PRINCIPAL
+        .scope(principal, with_log_context([(USER_ID_FIELD, user_id)], future))
+        .await
+}
+
+/// 访问控制表达式
+///
+/// 支持的语法与 Spring Security 的常用表达式一致：
+/// - `hasRole('ADMIN')`、`hasAnyRole('ADMIN', 'OPS')`
+/// - `hasAuthority('orders:write')`、`hasAnyAuthority('orders:write', 'orders:admin')`
+/// - `isAuthenticated()`、`isAnonymous()`、`permitAll()`、`denyAll()`
+/// - `and`、`or`、`not`（或 `&&`、`||`、`!`）以及括号
+///
+/// # 示例
+/// ```rust
+/// let expression = AccessExpression::parse("hasRole('ADMIN') or hasAuthority('orders:write')")?;
+/// assert!(expression.evaluate(Some(&Principal::new("alice").with_roles(["ADMIN"]))));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccessExpression {
+    /// 允许所有访问
+    PermitAll,
+    /// 拒绝所有访问
+    DenyAll,
+    /// 已认证
+    Authenticated,
+    /// 未认证
+    Anonymous,
+    /// 拥有任一角色
+    HasAnyRole(Vec<String>),
+    /// 拥有任一权限
+    HasAnyAuthority(Vec<String>),
+    /// 取反
+    Not(Box<AccessExpression>),
+    /// 同时满足
+    And(Box<AccessExpression>, Box<AccessExpression>),
+    /// 满足任一
+    Or(Box<AccessExpression>, Box<AccessExpression>),
+}
+
+impl AccessExpression {
+    /// 解析表达式
+    ///
+    /// # 错误
+    /// 表达式语法错误或使用了不支持的函数时返回验证错误
+    pub fn parse(expression: &str) -> Result<Self> {
+        let tokens = tokenize(expression)?;
+        let mut parser = ExpressionParser { tokens, position: 0 };
+        let parsed = parser.parse_or()?;
+        match parser.tokens.get(parser.position) {
+            None => Ok(parsed),
+            Some(token) => Err(Error::validation(format!(
+                "访问控制表达式 {} 中存在多余的内容: {:?}",
+                expression, token
+            ))),
+        }
+    }
+
+    /// 对认证主体求值，未认证时 `principal` 为 `None`
+    pub fn evaluate(&self, principal: Option<&Principal>) -> bool {
+        match self {
+            Self::PermitAll => true,
+            Self::DenyAll => false,
+            Self::Authenticated => principal.is_some(),
+            Self::Anonymous => principal.is_none(),
+            Self::HasAnyRole(roles) => principal
+                .is_some_and(|principal| roles.iter().any(|role| principal.has_role(role))),
+            Self::HasAnyAuthority(authorities) => principal.is_some_and(|principal| {
+                authorities
+                    .iter()
+                    .any(|authority| principal.has_authority(authority))
+            }),
+            Self::Not(inner) => !inner.evaluate(principal),
+            Self::And(left, right) => left.evaluate(principal) && right.evaluate(principal),
+            Self::Or(left, right) => left.evaluate(principal) || right.evaluate(principal),
+        }
+    }
+
+    /// 检查当前主体的权限，供 `#[PreAuthorize]` 使用，表达式在编译期解析并缓存
+    ///
+    /// # 错误
+    /// - 未认证且表达式不允许匿名访问时返回 [`Error::Unauthorized`]
+    /// - 已认证但权限不足时返回 [`Error::Forbidden`]
+    pub fn check(&self) -> Result<()> {
+        let principal = current_principal();
+        if self.evaluate(principal.as_ref()) {
+            return Ok(());
+        }
+        match principal {
+            Some(principal) => {
+                tracing::debug!("主体 {} 不满足访问控制表达式 {:?}", principal.name, self);
+                Err(Error::forbidden("权限不足"))
+            }
+            None => Err(Error::Unauthorized),
+        }
+    }
+}
+
+/// 解析访问控制表达式并检查当前主体的权限
+///
+/// # 错误
+/// - 未认证且表达式不允许匿名访问时返回 [`Error::Unauthorized`]
+/// - 已认证但权限不足时返回 [`Error::Forbidden`]
+/// - 表达式无效时返回内部错误
+pub fn check_access(expression: &str) -> Result<()> {
+    AccessExpression::parse(expression)
+        .map_err(|e| Error::internal(format!("无效的访问控制表达式: {}", e)))?
+        .check()
+}
+
+/// 表达式的词法单元
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    /// 标识符或关键字
+    Ident(String),
+    /// 字符串字面量
+    Str(String),
+    /// 左括号
+    Open,
+    /// 右括号
+    Close,
+    /// 逗号
+    Comma,
+    /// `and` / `&&`
+    And,
+    /// `or` / `||`
+    Or,
+    /// `not` / `!`
+    Not,
+}
+
+/// 将表达式拆分为词法单元
+fn tokenize(expression: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' | ',' | '!' => {
+                chars.next();
+                tokens.push(match c {
+                    '(' => Token::Open,
+                    ')' => Token::Close,
+                    ',' => Token::Comma,
+                    _ => Token::Not,
+                });
+            }
+            '&' | '|' => {
+                chars.next();
+                if chars.next() != Some(c) {
+                    return Err(Error::validation(format!("访问控制表达式 {} 中的 {} 应为 {}{}", expression, c, c, c)));
+                }
+                tokens.push(if c == '&' { Token::And } else { Token::Or });
+            }
+            '\'' | '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some(next) if next == c => break,
+                        Some(next) => value.push(next),
+                        None => {
+                            return Err(Error::validation(format!("访问控制表达式 {} 中的字符串未闭合", expression)))
+                        }
+                    }
+                }
+                tokens.push(Token::Str(value));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut ident = String::new();
+                while let Some(&next) = chars.peek() {
+                    if !(next.is_alphanumeric() || next == '_') {
+                        break;
+                    }
+                    ident.push(next);
+                    chars.next();
+                }
+                tokens.push(match ident.as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    _ => Token::Ident(ident),
+                });
+            }
+            _ => {
+                return Err(Error::validation(format!("访问控制表达式 {} 中存在无效字符: {}", expression, c)));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// 递归下降解析器，优先级从低到高为 `or`、`and`、`not`
+struct ExpressionParser {
+    /// 词法单元
+    tokens: Vec<Token>,
+    /// 当前位置
+    position: usize,
+}
+
+impl ExpressionParser {
+    /// 读取下一个词法单元
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    /// 下一个词法单元是指定单元时跳过并返回 `true`
+    fn eat(&mut self, expected: &Token) -> bool {
+        if self.tokens.get(self.position) == Some(expected) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// 要求下一个词法单元是指定单元
+    fn expect(&mut self, expected: Token) -> Result<()> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            token => Err(Error::validation(format!("访问控制表达式应为 {:?}，实际为 {:?}", expected, token))),
+        }
+    }
+
+    /// `or` 表达式
+    fn parse_or(&mut self) -> Result<AccessExpression> {
+        let mut expression = self.parse_and()?;
+        while self.eat(&Token::Or) {
+            expression = AccessExpression::Or(Box::new(expression), Box::new(self.parse_and()?));
+        }
+        Ok(expression)
+    }
+
+    /// `and` 表达式
+    fn parse_and(&mut self) -> Result<AccessExpression> {
+        let mut expression = self.parse_unary()?;
+        while self.eat(&Token::And) {
+            expression = AccessExpression::And(Box::new(expression), Box::new(self.parse_unary()?));
+        }
+        Ok(expression)
+    }
+
+    /// `not` 表达式
+    fn parse_unary(&mut self) -> Result<AccessExpression> {
+        if self.eat(&Token::Not) {
+            return Ok(AccessExpression::Not(Box::new(self.parse_unary()?)));
+        }
+        self.parse_primary()
+    }
+
+    /// 括号表达式或函数调用
+    fn parse_primary(&mut self) -> Result<AccessExpression> {
+        let name = match self.next() {
+            Some(Token::Open) => {
+                let expression = self.parse_or()?;
+                self.expect(Token::Close)?;
+                return Ok(expression);
+            }
+            Some(Token::Ident(name)) => name,
+            token => return Err(Error::validation(format!("访问控制表达式应为函数调用，实际为 {:?}", token))),
+        };
+
+        self.expect(Token::Open)?;
+        let mut args = Vec::new();
+        if !self.eat(&Token::Close) {
+            loop {
+                match self.next() {
+                    Some(Token::Str(arg)) => args.push(arg),
+                    token => {
+                        return Err(Error::validation(format!("{} 的参数应为字符串，实际为 {:?}", name, token)))
+                    }
+                }
+                if self.eat(&Token::Close) {
+                    break;
+                }
+                self.expect(Token::Comma)?;
+            }
+        }
+
+        let expression = match (name.as_str(), args.len()) {
+            ("permitAll", 0) => AccessExpression::PermitAll,
+            ("denyAll", 0) => AccessExpression::DenyAll,
+            ("isAuthenticated", 0) => AccessExpression::Authenticated,
+            ("isAnonymous", 0) => AccessExpression::Anonymous,
+            ("hasRole", 1) | ("hasAnyRole", 1..) => AccessExpression::HasAnyRole(args),
+            ("hasAuthority", 1) | ("hasAnyAuthority", 1..) => AccessExpression::HasAnyAuthority(args),
+            _ => {
+                return Err(Error::validation(format!(
+                    "不支持的访问控制函数 {}（{} 个参数）",
+                    name,
+                    args.len()
+                )))
+            }
+        };
+        Ok(expression)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试主体只在作用域内可见
+    #[tokio::test]
+    async fn test_principal_scope() {
+        assert!(current_principal().is_none());
+
+        let principal = Principal::new("alice").with_authorities(["ROLE_ADMIN"]);
+        let name = with_principal(principal, async {
+            let principal = current_principal().unwrap();
+            assert!(principal.has_authority("ROLE_ADMIN"));
+            assert!(!principal.has_authority("ROLE_USER"));
+            principal.name
+        })
+        .await;
+
+        assert_eq!(name, "alice");
+        assert!(current_principal().is_none());
+    }
+
+    /// 测试访问控制表达式的解析和检查
+    #[tokio::test]
+    async fn test_access_expression() {
+        let admin = Principal::new("alice").with_authorities(["orders:read"]).with_roles(["ADMIN"]);
+        assert!(admin.has_role("ROLE_ADMIN") && admin.has_role("ADMIN"));
+        assert_eq!(admin.roles().collect::<Vec<_>>(), vec!["ADMIN"]);
+
+        let expression = AccessExpression::parse(
+            "hasRole('ADMIN') and (hasAuthority(\"orders:write\") || !hasAnyRole('OPS', 'AUDITOR'))",
+        )
+        .unwrap();
+        assert!(expression.evaluate(Some(&admin)));
+        assert!(!expression.evaluate(Some(&Principal::new("bob").with_roles(["ADMIN", "OPS"]))));
+        assert!(!expression.evaluate(None));
+
+        assert!(AccessExpression::parse("hasRole('ADMIN'").is_err());
+        assert!(AccessExpression::parse("hasRole(ADMIN)").is_err());
+        assert!(AccessExpression::parse("hasPermission('x', 'y')").is_err());
+
+        assert!(matches!(check_access("hasRole('ADMIN')"), Err(Error::Unauthorized)));
+        assert!(check_access("permitAll()").is_ok());
+        with_principal(admin, async {
+            assert!(check_access("isAuthenticated() and hasRole('ADMIN')").is_ok());
+            assert!(matches!(check_access("hasRole('OPS')"), Err(Error::Forbidden { .. })));
+            assert!(matches!(check_access("hasRole("), Err(Error::Internal { .. })));
+        })
+        .await;
+    }
+}
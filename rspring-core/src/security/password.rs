@@ -0,0 +1,540 @@
+//! 密码编码模块
+//!
+//! 提供类似 Spring Security `PasswordEncoder` 的密码哈希抽象：
+//! - [`Argon2PasswordEncoder`]、[`BcryptPasswordEncoder`] 和 [`Pbkdf2PasswordEncoder`]
+//!   输出自描述的哈希字符串（PHC 或 Modular Crypt 格式），算法和参数随哈希一起保存
+//! - [`DelegatingPasswordEncoder`] 用当前算法编码，按哈希格式选择算法校验，
+//!   并在校验成功后按需用当前算法和强度重新编码（upgrade-on-verify）
+//! - 算法和强度在 `[security.password]` 中配置
+//!
+//! 哈希计算是有意设计得很慢的 CPU 密集操作，在异步代码中应通过 `tokio::task::spawn_blocking` 调用
+
+use std::sync::Arc;
+
+use argon2::Argon2;
+use password_hash::rand_core::OsRng;
+use password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use serde::{Deserialize, Serialize};
+
+use crate::config::properties::Configuration;
+use crate::config::ConfigurationManager;
+use crate::container::Component;
+use crate::error::{Error, Result};
+
+/// 密码编码器特征
+pub trait PasswordEncoder: Send + Sync {
+    /// 编码原始密码，每次使用新的随机盐
+    ///
+    /// # 错误
+    /// 哈希计算失败时返回错误
+    fn encode(&self, raw: &str) -> Result<String>;
+
+    /// 校验原始密码与编码结果是否匹配，编码结果格式错误时返回 `false`
+    fn matches(&self, raw: &str, encoded: &str) -> bool;
+
+    /// 是否能识别该编码结果的格式
+    fn supports(&self, encoded: &str) -> bool;
+
+    /// 编码结果是否弱于当前设置，需要重新编码
+    fn upgrade_encoding(&self, _encoded: &str) -> bool {
+        false
+    }
+}
+
+/// 密码校验结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PasswordVerification {
+    /// 密码不匹配
+    Mismatch,
+    /// 密码匹配，编码结果符合当前设置
+    Matched,
+    /// 密码匹配，已按当前设置重新编码，调用方应保存新的编码结果
+    Upgraded(String),
+}
+
+impl PasswordVerification {
+    /// 密码是否匹配
+    pub fn is_match(&self) -> bool {
+        !matches!(self, Self::Mismatch)
+    }
+}
+
+/// 密码哈希算法
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PasswordAlgorithm {
+    /// Argon2id
+    #[default]
+    Argon2,
+    /// bcrypt
+    Bcrypt,
+    /// PBKDF2-HMAC-SHA256
+    Pbkdf2,
+}
+
+/// 密码编码配置
+///
+/// 默认强度参考 OWASP 密码存储建议
+///
+/// # 示例
+/// ```toml
+/// [security.password]
+/// algorithm = "argon2"
+///
+/// [security.password.argon2]
+/// memory_kib = 65536
+/// iterations = 3
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PasswordEncoderConfig {
+    /// 编码新密码使用的算法，其他算法的编码结果仍可校验，并在校验成功后升级
+    ///
+    /// # 默认值
+    /// `"argon2"`
+    #[serde(default)]
+    pub algorithm: PasswordAlgorithm,
+    /// Argon2 参数，对应 `[security.password.argon2]`
+    #[serde(default)]
+    pub argon2: Argon2Config,
+    /// bcrypt 参数，对应 `[security.password.bcrypt]`
+    #[serde(default)]
+    pub bcrypt: BcryptConfig,
+    /// PBKDF2 参数，对应 `[security.password.pbkdf2]`
+    #[serde(default)]
+    pub pbkdf2: Pbkdf2Config,
+}
+
+impl PasswordEncoderConfig {
+    /// 从配置管理器读取 `[security.password]` 章节
+    ///
+    /// 未配置时使用默认值
+    pub fn load(config: &ConfigurationManager) -> Result<Self> {
+        if config.contains_key("security.password") {
+            config.get_section("security.password")
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// 创建编码器：用配置的算法编码，三种算法的编码结果都可校验
+    ///
+    /// # 错误
+    /// 参数超出算法允许的范围时返回验证错误
+    pub fn build(&self) -> Result<DelegatingPasswordEncoder> {
+        let argon2 = Arc::new(Argon2PasswordEncoder::new(
+            self.argon2.memory_kib,
+            self.argon2.iterations,
+            self.argon2.parallelism,
+        )?);
+        let bcrypt = Arc::new(BcryptPasswordEncoder::new(self.bcrypt.cost)?);
+        let pbkdf2 = Arc::new(Pbkdf2PasswordEncoder::new(self.pbkdf2.iterations)?);
+
+        let encoders: [Arc<dyn PasswordEncoder>; 3] = match self.algorithm {
+            PasswordAlgorithm::Argon2 => [argon2, bcrypt, pbkdf2],
+            PasswordAlgorithm::Bcrypt => [bcrypt, argon2, pbkdf2],
+            PasswordAlgorithm::Pbkdf2 => [pbkdf2, argon2, bcrypt],
+        };
+        let [current, legacy @ ..] = encoders;
+        Ok(legacy
+            .into_iter()
+            .fold(DelegatingPasswordEncoder::new(current), DelegatingPasswordEncoder::with_encoder))
+    }
+}
+
+impl Configuration for PasswordEncoderConfig {}
+
+/// Argon2 参数
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Argon2Config {
+    /// 内存开销（KiB）
+    ///
+    /// # 默认值
+    /// `19456`（19 MiB）
+    #[serde(default = "default_argon2_memory_kib")]
+    pub memory_kib: u32,
+    /// 迭代次数
+    ///
+    /// # 默认值
+    /// `2`
+    #[serde(default = "default_argon2_iterations")]
+    pub iterations: u32,
+    /// 并行度
+    ///
+    /// # 默认值
+    /// `1`
+    #[serde(default = "default_argon2_parallelism")]
+    pub parallelism: u32,
+}
+
+impl Default for Argon2Config {
+    fn default() -> Self {
+        Self {
+            memory_kib: default_argon2_memory_kib(),
+            iterations: default_argon2_iterations(),
+            parallelism: default_argon2_parallelism(),
+        }
+    }
+}
+
+/// bcrypt 参数
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub struct BcryptConfig {
+    /// 成本因子，迭代次数为 2 的 `cost` 次方，取值 4 到 31
+    ///
+    /// # 默认值
+    /// `12`
+    #[serde(default = "default_bcrypt_cost")]
+    pub cost: u32,
+}
+
+impl Default for BcryptConfig {
+    fn default() -> Self {
+        Self {
+            cost: default_bcrypt_cost(),
+        }
+    }
+}
+
+/// PBKDF2 参数
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Pbkdf2Config {
+    /// 迭代次数
+    ///
+    /// # 默认值
+    /// `600000`
+    #[serde(default = "default_pbkdf2_iterations")]
+    pub iterations: u32,
+}
+
+impl Default for Pbkdf2Config {
+    fn default() -> Self {
+        Self {
+            iterations: default_pbkdf2_iterations(),
+        }
+    }
+}
+
+/// Argon2id 密码编码器
+#[derive(Debug, Clone)]
+pub struct Argon2PasswordEncoder {
+    /// 哈希参数
+    params: argon2::Params,
+}
+
+impl Argon2PasswordEncoder {
+    /// 创建编码器
+    ///
+    /// # 错误
+    /// 参数超出 Argon2 允许的范围时返回验证错误
+    pub fn new(memory_kib: u32, iterations: u32, parallelism: u32) -> Result<Self> {
+        let params = argon2::Params::new(memory_kib, iterations, parallelism, None)
+            .map_err(|e| Error::validation(format!("无效的 Argon2 参数: {}", e)))?;
+        Ok(Self { params })
+    }
+
+    /// 按当前参数创建哈希器
+    fn hasher(&self) -> Argon2<'static> {
+        Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, self.params.clone())
+    }
+}
+
+impl PasswordEncoder for Argon2PasswordEncoder {
+    fn encode(&self, raw: &str) -> Result<String> {
+        let salt = SaltString::generate(&mut OsRng);
+        self.hasher()
+            .hash_password(raw.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| Error::internal(format!("Argon2 哈希计算失败: {}", e)))
+    }
+
+    fn matches(&self, raw: &str, encoded: &str) -> bool {
+        // 校验使用编码结果中记录的算法和参数
+        PasswordHash::new(encoded)
+            .is_ok_and(|hash| Argon2::default().verify_password(raw.as_bytes(), &hash).is_ok())
+    }
+
+    fn supports(&self, encoded: &str) -> bool {
+        encoded.starts_with("$argon2")
+    }
+
+    fn upgrade_encoding(&self, encoded: &str) -> bool {
+        let Ok(hash) = PasswordHash::new(encoded) else {
+            return false;
+        };
+        if hash.algorithm != argon2::Algorithm::Argon2id.ident() {
+            return true;
+        }
+        argon2::Params::try_from(&hash).is_ok_and(|params| {
+            params.m_cost() < self.params.m_cost()
+                || params.t_cost() < self.params.t_cost()
+                || params.p_cost() < self.params.p_cost()
+        })
+    }
+}
+
+/// bcrypt 密码编码器
+///
+/// bcrypt 只使用密码的前 72 个字节
+#[derive(Debug, Clone, Copy)]
+pub struct BcryptPasswordEncoder {
+    /// 成本因子
+    cost: u32,
+}
+
+impl BcryptPasswordEncoder {
+    /// 创建编码器
+    ///
+    /// # 错误
+    /// 成本因子不在 4 到 31 之间时返回验证错误
+    pub fn new(cost: u32) -> Result<Self> {
+        if !(4..=31).contains(&cost) {
+            return Err(Error::validation(format!("bcrypt 成本因子必须在 4 到 31 之间: {}", cost)));
+        }
+        Ok(Self { cost })
+    }
+}
+
+impl PasswordEncoder for BcryptPasswordEncoder {
+    fn encode(&self, raw: &str) -> Result<String> {
+        bcrypt::hash(raw, self.cost).map_err(|e| Error::internal(format!("bcrypt 哈希计算失败: {}", e)))
+    }
+
+    fn matches(&self, raw: &str, encoded: &str) -> bool {
+        bcrypt::verify(raw, encoded).unwrap_or(false)
+    }
+
+    fn supports(&self, encoded: &str) -> bool {
+        encoded.starts_with("$2")
+    }
+
+    fn upgrade_encoding(&self, encoded: &str) -> bool {
+        // 格式为 `$2b$<cost>$<salt+hash>`
+        encoded
+            .get(4..6)
+            .and_then(|cost| cost.parse::<u32>().ok())
+            .is_some_and(|cost| cost < self.cost)
+    }
+}
+
+/// PBKDF2-HMAC-SHA256 密码编码器
+#[derive(Debug, Clone, Copy)]
+pub struct Pbkdf2PasswordEncoder {
+    /// 哈希参数
+    params: pbkdf2::Params,
+}
+
+impl Pbkdf2PasswordEncoder {
+    /// 创建编码器
+    ///
+    /// # 错误
+    /// 迭代次数为 0 时返回验证错误
+    pub fn new(iterations: u32) -> Result<Self> {
+        if iterations == 0 {
+            return Err(Error::validation("PBKDF2 迭代次数必须大于 0"));
+        }
+        Ok(Self {
+            params: pbkdf2::Params {
+                rounds: iterations,
+                output_length: 32,
+            },
+        })
+    }
+}
+
+impl PasswordEncoder for Pbkdf2PasswordEncoder {
+    fn encode(&self, raw: &str) -> Result<String> {
+        let salt = SaltString::generate(&mut OsRng);
+        pbkdf2::Pbkdf2
+            .hash_password_customized(
+                raw.as_bytes(),
+                Some(pbkdf2::Algorithm::Pbkdf2Sha256.ident()),
+                None,
+                self.params,
+                &salt,
+            )
+            .map(|hash| hash.to_string())
+            .map_err(|e| Error::internal(format!("PBKDF2 哈希计算失败: {}", e)))
+    }
+
+    fn matches(&self, raw: &str, encoded: &str) -> bool {
+        PasswordHash::new(encoded)
+            .is_ok_and(|hash| pbkdf2::Pbkdf2.verify_password(raw.as_bytes(), &hash).is_ok())
+    }
+
+    fn supports(&self, encoded: &str) -> bool {
+        encoded.starts_with("$pbkdf2")
+    }
+
+    fn upgrade_encoding(&self, encoded: &str) -> bool {
+        let Ok(hash) = PasswordHash::new(encoded) else {
+            return false;
+        };
+        if hash.algorithm != pbkdf2::Algorithm::Pbkdf2Sha256.ident() {
+            return true;
+        }
+        pbkdf2::Params::try_from(&hash).is_ok_and(|params| params.rounds < self.params.rounds)
+    }
+}
+
+/// 委托密码编码器
+///
+/// 用当前编码器编码新密码，按编码结果的格式选择编码器校验；
+/// 编码结果不是当前算法或弱于当前设置时，校验成功后重新编码
+///
+/// # 示例
+/// ```rust
+/// let encoder = PasswordEncoderConfig::load(&config)?.build()?;
+/// container.register_singleton(encoder)?;
+///
+/// match encoder.verify_and_upgrade(&form.password, &user.password_hash)? {
+///     PasswordVerification::Mismatch => return Err(Error::Unauthorized),
+///     PasswordVerification::Upgraded(hash) => user_repository.update_password(user.id, &hash).await?,
+///     PasswordVerification::Matched => {}
+/// }
+/// ```
+#[derive(Clone)]
+pub struct DelegatingPasswordEncoder {
+    /// 编码新密码使用的编码器
+    current: Arc<dyn PasswordEncoder>,
+    /// 只用于校验的编码器
+    legacy: Vec<Arc<dyn PasswordEncoder>>,
+}
+
+impl DelegatingPasswordEncoder {
+    /// 创建只包含当前编码器的委托编码器
+    pub fn new(current: Arc<dyn PasswordEncoder>) -> Self {
+        Self {
+            current,
+            legacy: Vec::new(),
+        }
+    }
+
+    /// 添加只用于校验的编码器
+    pub fn with_encoder(mut self, encoder: Arc<dyn PasswordEncoder>) -> Self {
+        self.legacy.push(encoder);
+        self
+    }
+
+    /// 校验密码，匹配且编码结果需要升级时用当前设置重新编码
+    ///
+    /// # 错误
+    /// 重新编码失败时返回错误
+    pub fn verify_and_upgrade(&self, raw: &str, encoded: &str) -> Result<PasswordVerification> {
+        if !self.matches(raw, encoded) {
+            return Ok(PasswordVerification::Mismatch);
+        }
+        if !self.upgrade_encoding(encoded) {
+            return Ok(PasswordVerification::Matched);
+        }
+        Ok(PasswordVerification::Upgraded(self.current.encode(raw)?))
+    }
+
+    /// 按格式选择编码器
+    fn encoder_for(&self, encoded: &str) -> Option<&Arc<dyn PasswordEncoder>> {
+        std::iter::once(&self.current)
+            .chain(self.legacy.iter())
+            .find(|encoder| encoder.supports(encoded))
+    }
+}
+
+impl PasswordEncoder for DelegatingPasswordEncoder {
+    fn encode(&self, raw: &str) -> Result<String> {
+        self.current.encode(raw)
+    }
+
+    fn matches(&self, raw: &str, encoded: &str) -> bool {
+        match self.encoder_for(encoded) {
+            Some(encoder) => encoder.matches(raw, encoded),
+            None => {
+                tracing::warn!("无法识别的密码编码格式");
+                false
+            }
+        }
+    }
+
+    fn supports(&self, encoded: &str) -> bool {
+        self.encoder_for(encoded).is_some()
+    }
+
+    fn upgrade_encoding(&self, encoded: &str) -> bool {
+        !self.current.supports(encoded) || self.current.upgrade_encoding(encoded)
+    }
+}
+
+impl std::fmt::Debug for DelegatingPasswordEncoder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DelegatingPasswordEncoder")
+            .field("legacy", &self.legacy.len())
+            .finish()
+    }
+}
+
+impl Component for DelegatingPasswordEncoder {
+    fn component_name(&self) -> &'static str {
+        "PasswordEncoder"
+    }
+}
+
+// 默认值函数
+
+fn default_argon2_memory_kib() -> u32 {
+    19 * 1024
+}
+
+fn default_argon2_iterations() -> u32 {
+    2
+}
+
+fn default_argon2_parallelism() -> u32 {
+    1
+}
+
+fn default_bcrypt_cost() -> u32 {
+    12
+}
+
+fn default_pbkdf2_iterations() -> u32 {
+    600_000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试切换算法和提高强度后，旧的编码结果校验成功时升级
+    #[test]
+    fn test_upgrade_on_verify() {
+        let mut config = PasswordEncoderConfig {
+            algorithm: PasswordAlgorithm::Bcrypt,
+            argon2: Argon2Config {
+                memory_kib: 1024,
+                iterations: 1,
+                parallelism: 1,
+            },
+            bcrypt: BcryptConfig { cost: 4 },
+            pbkdf2: Pbkdf2Config { iterations: 1000 },
+        };
+        let bcrypt_hash = config.build().unwrap().encode("s3cret").unwrap();
+        assert!(bcrypt_hash.starts_with("$2"));
+
+        config.algorithm = PasswordAlgorithm::Argon2;
+        let encoder = config.build().unwrap();
+        assert_eq!(encoder.verify_and_upgrade("wrong", &bcrypt_hash).unwrap(), PasswordVerification::Mismatch);
+        let PasswordVerification::Upgraded(argon2_hash) = encoder.verify_and_upgrade("s3cret", &bcrypt_hash).unwrap() else {
+            panic!("bcrypt 编码结果应升级为 Argon2");
+        };
+        assert!(argon2_hash.starts_with("$argon2id$"));
+        assert_eq!(encoder.verify_and_upgrade("s3cret", &argon2_hash).unwrap(), PasswordVerification::Matched);
+
+        // 同一算法提高强度
+        config.argon2.iterations = 2;
+        assert!(config.build().unwrap().upgrade_encoding(&argon2_hash));
+
+        config.algorithm = PasswordAlgorithm::Pbkdf2;
+        let pbkdf2_hash = config.build().unwrap().encode("s3cret").unwrap();
+        assert!(pbkdf2_hash.starts_with("$pbkdf2-sha256$"));
+        assert!(encoder.matches("s3cret", &pbkdf2_hash));
+        assert!(!encoder.matches("s3cret", "plain"));
+        assert!(BcryptPasswordEncoder::new(3).is_err());
+    }
+}
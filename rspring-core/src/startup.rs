@@ -0,0 +1,345 @@
+//! 启动报告模块
+//!
+//! 记录应用启动过程，便于排查启动缓慢或不符合预期的问题：
+//! - `RSpringApp` 记录每个启动阶段的耗时，完成后发布 [`ApplicationReady`] 事件并输出启动报告
+//! - 容器记录每个 Bean 工厂方法的耗时，创建耗时较长的其他组件通过
+//!   [`StartupRecorder::time_component`] 记录耗时，如 MySQL 连接池
+//! - Web 服务器等监听端口的组件通过 [`StartupRecorder::listener`] 记录绑定的地址，
+//!   并通过 [`StartupRecorder::hold`] 推迟启动报告，直到地址绑定完成
+//!
+//! Web 启动器的 actuator `startup` 端点返回 [`startup_report`] 的内容
+
+use std::future::Future;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+
+use crate::config::ConfigurationManager;
+
+/// 一个启动阶段或组件的耗时
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct StartupStep {
+    /// 阶段或组件名称
+    pub name: String,
+    /// 耗时
+    #[serde(with = "crate::config::duration")]
+    pub duration: Duration,
+}
+
+/// 已启动的监听器
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct StartupListener {
+    /// 监听器名称，如 `"http"`
+    pub name: String,
+    /// 绑定的地址，如 `"0.0.0.0:8080"`
+    pub address: String,
+}
+
+/// 启动报告
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct StartupReport {
+    /// 开始启动的时间
+    pub started_at: DateTime<Utc>,
+    /// 启动完成的时间，尚未完成时为 `None`
+    pub ready_at: Option<DateTime<Utc>>,
+    /// 启动总耗时，尚未完成时为 `None`
+    #[serde(default, with = "crate::config::duration::option")]
+    pub duration: Option<Duration>,
+    /// 当前环境
+    pub profile: Option<String>,
+    /// 已加载的配置来源，按优先级从低到高
+    pub config_sources: Vec<String>,
+    /// 启动阶段的耗时，按执行顺序
+    pub steps: Vec<StartupStep>,
+    /// 组件的创建耗时，按完成顺序
+    pub components: Vec<StartupStep>,
+    /// 已启动的监听器
+    pub listeners: Vec<StartupListener>,
+}
+
+impl StartupReport {
+    /// 生成用于日志输出的多行摘要，阶段和组件按耗时从长到短排列
+    pub fn summary(&self) -> String {
+        let mut lines = vec![format!(
+            "启动耗时 {}，环境: {}",
+            self.duration.map_or_else(|| "-".to_string(), format_millis),
+            self.profile.as_deref().unwrap_or("-")
+        )];
+        lines.push(format!("  配置来源: {}", self.config_sources.join(", ")));
+        for (title, steps) in [("启动阶段", &self.steps), ("组件", &self.components)] {
+            let mut steps: Vec<_> = steps.iter().collect();
+            steps.sort_by_key(|step| std::cmp::Reverse(step.duration));
+            for step in steps {
+                lines.push(format!(
+                    "  {} {}: {}",
+                    title,
+                    step.name,
+                    format_millis(step.duration)
+                ));
+            }
+        }
+        for listener in &self.listeners {
+            lines.push(format!("  已启动 {}: {}", listener.name, listener.address));
+        }
+        lines.join("\n")
+    }
+}
+
+/// 启动完成事件，`RSpringApp` 完成所有启动阶段后发布
+///
+/// # 示例
+/// ```rust
+/// event_publisher().register(ApplicationListener::new(|event: ApplicationReady| async move {
+///     warm_up_cache().await;
+///     tracing::info!("启动耗时 {:?}", event.report.duration);
+///     Ok(())
+/// }));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ApplicationReady {
+    /// 启动报告
+    pub report: StartupReport,
+}
+
+/// 启动过程记录器
+#[derive(Debug)]
+pub struct StartupRecorder {
+    /// 开始启动的时刻
+    started: Instant,
+    /// 当前的报告
+    report: Mutex<StartupReport>,
+    /// 未释放的 [`StartupHold`] 数量
+    holds: AtomicUsize,
+    /// 所有 [`StartupHold`] 释放时通知
+    released: Notify,
+}
+
+impl StartupRecorder {
+    /// 从当前时刻开始记录
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            report: Mutex::new(StartupReport {
+                started_at: Utc::now(),
+                ready_at: None,
+                duration: None,
+                profile: None,
+                config_sources: Vec::new(),
+                steps: Vec::new(),
+                components: Vec::new(),
+                listeners: Vec::new(),
+            }),
+            holds: AtomicUsize::new(0),
+            released: Notify::new(),
+        }
+    }
+
+    /// 记录配置来源：存在的配置文件和环境变量
+    pub fn config_sources(&self, config: &ConfigurationManager) {
+        let mut sources: Vec<String> = config
+            .config_paths()
+            .iter()
+            .filter(|path| Path::new(path).exists())
+            .cloned()
+            .collect();
+        sources.push(format!("环境变量 {}_*", config.env_prefix()));
+        let mut report = self.lock();
+        report.profile = Some(config.profile().to_string());
+        report.config_sources = sources;
+    }
+
+    /// 记录启动阶段的耗时
+    pub fn step(&self, name: impl Into<String>, duration: Duration) {
+        self.lock().steps.push(StartupStep {
+            name: name.into(),
+            duration,
+        });
+    }
+
+    /// 记录组件的创建耗时
+    pub fn component(&self, name: impl Into<String>, duration: Duration) {
+        self.lock().components.push(StartupStep {
+            name: name.into(),
+            duration,
+        });
+    }
+
+    /// 执行异步任务并记录为启动阶段的耗时
+    pub async fn time_step<F: Future>(&self, name: impl Into<String>, future: F) -> F::Output {
+        let start = Instant::now();
+        let output = future.await;
+        self.step(name, start.elapsed());
+        output
+    }
+
+    /// 执行异步任务并记录为组件的创建耗时
+    ///
+    /// # 示例
+    /// ```rust
+    /// let client = startup_recorder()
+    ///     .time_component("search.client", SearchClient::connect(&config))
+    ///     .await?;
+    /// ```
+    pub async fn time_component<F: Future>(&self, name: impl Into<String>, future: F) -> F::Output {
+        let start = Instant::now();
+        let output = future.await;
+        self.component(name, start.elapsed());
+        output
+    }
+
+    /// 记录已启动的监听器
+    pub fn listener(&self, name: impl Into<String>, address: impl Into<String>) {
+        self.lock().listeners.push(StartupListener {
+            name: name.into(),
+            address: address.into(),
+        });
+    }
+
+    /// 推迟启动完成，直到返回的守卫释放
+    ///
+    /// 监听端口的组件在创建时调用，所有地址绑定后释放守卫，启动报告因此包含这些监听器
+    ///
+    /// # 示例
+    /// ```rust
+    /// let hold = startup_recorder().hold();
+    /// let listener = std::net::TcpListener::bind(addr)?;
+    /// startup_recorder().listener("http", format!("http://{}", addr));
+    /// drop(hold);
+    /// ```
+    pub fn hold(&self) -> StartupHold<'_> {
+        self.holds.fetch_add(1, Ordering::SeqCst);
+        StartupHold { recorder: self }
+    }
+
+    /// 等待所有 [`StartupHold`] 释放
+    pub async fn released(&self) {
+        loop {
+            let notified = self.released.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if self.holds.load(Ordering::SeqCst) == 0 {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// 标记启动完成，返回此时的报告
+    pub fn ready(&self) -> StartupReport {
+        let duration = self.started.elapsed();
+        let mut report = self.lock();
+        report.ready_at = Some(Utc::now());
+        report.duration = Some(duration);
+        report.clone()
+    }
+
+    /// 获取当前的报告
+    pub fn report(&self) -> StartupReport {
+        self.lock().clone()
+    }
+
+    /// 锁定报告
+    fn lock(&self) -> std::sync::MutexGuard<'_, StartupReport> {
+        self.report.lock().expect("启动记录锁已损坏")
+    }
+}
+
+impl Default for StartupRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 推迟启动完成的守卫，由 [`StartupRecorder::hold`] 创建，释放时不再推迟
+#[derive(Debug)]
+pub struct StartupHold<'a> {
+    /// 所属的记录器
+    recorder: &'a StartupRecorder,
+}
+
+impl Drop for StartupHold<'_> {
+    fn drop(&mut self) {
+        if self.recorder.holds.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.recorder.released.notify_waiters();
+        }
+    }
+}
+
+/// 全局启动记录器，从首次使用时开始计时，`ApplicationContext::new` 会初始化它
+static STARTUP_RECORDER: Lazy<StartupRecorder> = Lazy::new(StartupRecorder::new);
+
+/// 获取全局启动记录器
+pub fn startup_recorder() -> &'static StartupRecorder {
+    &STARTUP_RECORDER
+}
+
+/// 获取当前的启动报告
+pub fn startup_report() -> StartupReport {
+    STARTUP_RECORDER.report()
+}
+
+/// 将时长格式化为毫秒
+fn format_millis(duration: Duration) -> String {
+    format!("{}ms", duration.as_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试记录阶段、组件和监听器，完成时计算总耗时
+    #[tokio::test]
+    async fn test_startup_report() {
+        let recorder = StartupRecorder::new();
+        recorder
+            .time_step("configuration", async {
+                tokio::time::sleep(Duration::from_millis(3)).await;
+            })
+            .await;
+        recorder.step("auto_wire", Duration::from_millis(12));
+        let value = recorder
+            .time_component("mysql.datasource", async {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                42
+            })
+            .await;
+        assert_eq!(value, 42);
+        recorder.listener("http", "0.0.0.0:8080");
+
+        let report = recorder.ready();
+        assert!(report.duration.unwrap() >= Duration::from_millis(5));
+        assert!(report.components[0].duration >= Duration::from_millis(5));
+        assert_eq!(report.listeners[0].address, "0.0.0.0:8080");
+        let summary = report.summary();
+        let lines: Vec<_> = summary.lines().collect();
+        assert_eq!(lines[2], "  启动阶段 auto_wire: 12ms");
+        assert!(lines[3].starts_with("  启动阶段 configuration: "));
+        assert!(lines[4].starts_with("  组件 mysql.datasource: "));
+        assert_eq!(lines[5], "  已启动 http: 0.0.0.0:8080");
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["steps"][1]["duration"], "12ms");
+    }
+
+    /// 测试所有守卫释放后才完成等待
+    #[tokio::test]
+    async fn test_hold() {
+        let recorder = StartupRecorder::new();
+        recorder.released().await;
+
+        let first = recorder.hold();
+        let second = recorder.hold();
+        let released = recorder.released();
+        tokio::pin!(released);
+        drop(first);
+        assert!(futures::poll!(released.as_mut()).is_pending());
+        drop(second);
+        tokio::time::timeout(Duration::from_secs(1), released).await.unwrap();
+    }
+}
@@ -0,0 +1,292 @@
+//! 异步任务执行模块
+//!
+//! 提供类似 Spring `@Async` 的任务执行器：
+//! - [`TaskExecutor`] 按 `[task.execution]` 配置限制同时执行的任务数，超出的任务排队等待
+//! - `#[Async]` 注解的方法提交到全局任务执行器后立即返回 [`JoinHandle`]
+//! - 应用停止时等待已提交的任务执行完成，超过 `shutdown_timeout` 后不再等待
+//! - 提交时的认证主体传递到任务中，任务中的方法级权限检查按提交者的身份进行
+//! - 提交时的日志上下文传递到任务中，任务中输出的日志带有提交时的请求 ID 等字段
+//! - 提交时的链路追踪上下文传递到任务中，任务中的出站请求和消息属于同一链路
+//! - 提交时的全局组件作用域传递到任务中，任务中发布的事件等使用同一作用域的组件
+//! - 未结束的任务登记在 [`dump::task_registry`] 中，可以通过 [`dump::task_dump`] 查看
+
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Notify, Semaphore};
+
+pub use tokio::task::JoinHandle;
+
+pub mod dump;
+
+use crate::config::properties::Configuration;
+use crate::task::dump::task_registry;
+use crate::logging::{log_context, with_log_context};
+use crate::scope::current_scope;
+use crate::security::{current_principal, with_principal};
+use crate::trace::{current_trace_context, with_trace_context};
+
+/// 任务执行配置
+///
+/// # 示例
+/// ```toml
+/// [task.execution]
+/// pool_size = 16
+/// shutdown_timeout = "30s"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TaskExecutionConfig {
+    /// 同时执行的最大任务数
+    ///
+    /// # 默认值
+    /// CPU 核心数
+    #[serde(default = "default_pool_size")]
+    pub pool_size: usize,
+    /// 应用停止时等待任务完成的最长时间
+    ///
+    /// # 默认值
+    /// `"30s"`
+    #[serde(default = "default_shutdown_timeout", with = "crate::config::duration")]
+    pub shutdown_timeout: Duration,
+}
+
+impl Default for TaskExecutionConfig {
+    fn default() -> Self {
+        Self {
+            pool_size: default_pool_size(),
+            shutdown_timeout: default_shutdown_timeout(),
+        }
+    }
+}
+
+impl Configuration for TaskExecutionConfig {}
+
+/// 全局任务执行器
+static TASK_EXECUTOR: Lazy<RwLock<Arc<TaskExecutor>>> =
+    Lazy::new(|| RwLock::new(Arc::new(TaskExecutor::new(TaskExecutionConfig::default()))));
+
+/// 设置全局任务执行器，供 `#[Async]` 使用
+///
+/// 应用启动时会按 `[task.execution]` 配置设置，之前已提交的任务仍由原执行器执行
+///
+/// 在作用域中调用时的行为见[全局组件作用域](crate::scope)
+pub fn set_task_executor(executor: Arc<TaskExecutor>) {
+    if let Some(executor) = crate::scope::set_scoped(executor) {
+        *TASK_EXECUTOR.write().expect("任务执行器锁已损坏") = executor;
+    }
+}
+
+/// 获取全局任务执行器
+pub fn task_executor() -> Arc<TaskExecutor> {
+    crate::scope::scoped()
+        .unwrap_or_else(|| TASK_EXECUTOR.read().expect("任务执行器锁已损坏").clone())
+}
+
+/// 任务执行器
+///
+/// # 示例
+/// ```rust
+/// let executor = TaskExecutor::new(TaskExecutionConfig::default());
+/// let handle = executor.spawn(async { send_report().await });
+///
+/// executor.shutdown().await;
+/// ```
+#[derive(Debug)]
+pub struct TaskExecutor {
+    /// 任务执行配置
+    config: TaskExecutionConfig,
+    /// 并发许可
+    permits: Arc<Semaphore>,
+    /// 已提交但未完成的任务数
+    pending: Arc<AtomicUsize>,
+    /// 任务完成通知
+    completed: Arc<Notify>,
+    /// 是否已停止
+    shutdown: AtomicBool,
+}
+
+impl TaskExecutor {
+    /// 根据任务执行配置创建，`pool_size` 为 0 时按 1 处理
+    pub fn new(config: TaskExecutionConfig) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(config.pool_size.max(1))),
+            config,
+            pending: Arc::new(AtomicUsize::new(0)),
+            completed: Arc::new(Notify::new()),
+            shutdown: AtomicBool::new(false),
+        }
+    }
+
+    /// 获取任务执行配置
+    pub fn config(&self) -> &TaskExecutionConfig {
+        &self.config
+    }
+
+    /// 获取已提交但未完成的任务数
+    pub fn pending(&self) -> usize {
+        self.pending.load(Ordering::SeqCst)
+    }
+
+    /// 提交任务，立即返回任务句柄
+    ///
+    /// 任务在获得执行许可后才开始执行，并在提交时的认证主体、日志上下文、链路追踪上下文和全局组件作用域下执行。
+    /// 执行器停止后提交的任务仍会执行，但不再被等待
+    pub fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        self.spawn_named(std::any::type_name::<F>(), future)
+    }
+
+    /// 提交任务并指定在任务转储中显示的名称，立即返回任务句柄
+    ///
+    /// # 示例
+    /// ```rust
+    /// executor.spawn_named(format!("导出报表 {}", report_id), export(report_id));
+    /// ```
+    pub fn spawn_named<F>(&self, name: impl Into<String>, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        if self.shutdown.load(Ordering::SeqCst) {
+            tracing::warn!("任务执行器已停止，新提交的任务不会在停止时被等待");
+        }
+
+        let permits = self.permits.clone();
+        let guard = PendingGuard::new(self.pending.clone(), self.completed.clone());
+        let principal = current_principal();
+        let context = log_context();
+        let trace = current_trace_context();
+        let scope = current_scope();
+        let tracked = task_registry().track("async", name);
+        tokio::spawn(with_log_context(context, async move {
+            let _guard = guard;
+            let _permit = permits.acquire_owned().await.expect("任务执行器信号量已关闭");
+            tracked.running();
+            let future = async move {
+                match principal {
+                    Some(principal) => with_principal(principal, future).await,
+                    None => future.await,
+                }
+            };
+            let future = async move {
+                match trace {
+                    Some(trace) => with_trace_context(trace, future).await,
+                    None => future.await,
+                }
+            };
+            match scope {
+                Some(scope) => scope.run(future).await,
+                None => future.await,
+            }
+        }))
+    }
+
+    /// 停止执行器并等待已提交的任务完成
+    ///
+    /// 超过 `shutdown_timeout` 仍未完成时返回 `false`，未完成的任务不会被取消
+    pub async fn shutdown(&self) -> bool {
+        self.shutdown.store(true, Ordering::SeqCst);
+
+        let drained = tokio::time::timeout(self.config.shutdown_timeout, async {
+            loop {
+                let completed = self.completed.notified();
+                if self.pending() == 0 {
+                    break;
+                }
+                completed.await;
+            }
+        })
+        .await
+        .is_ok();
+
+        if !drained {
+            tracing::warn!("等待异步任务完成超时，仍有 {} 个任务未完成", self.pending());
+        }
+        drained
+    }
+}
+
+/// 任务计数守卫，任务结束（包括被取消或 panic）时减少计数
+struct PendingGuard {
+    /// 未完成的任务数
+    pending: Arc<AtomicUsize>,
+    /// 任务完成通知
+    completed: Arc<Notify>,
+}
+
+impl PendingGuard {
+    /// 增加计数并创建守卫
+    fn new(pending: Arc<AtomicUsize>, completed: Arc<Notify>) -> Self {
+        pending.fetch_add(1, Ordering::SeqCst);
+        Self { pending, completed }
+    }
+}
+
+impl Drop for PendingGuard {
+    fn drop(&mut self) {
+        self.pending.fetch_sub(1, Ordering::SeqCst);
+        self.completed.notify_waiters();
+    }
+}
+
+// 默认值函数
+
+fn default_pool_size() -> usize {
+    num_cpus::get()
+}
+
+fn default_shutdown_timeout() -> Duration {
+    Duration::from_secs(30)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::Principal;
+
+    /// 测试并发上限、停止时等待任务完成和认证主体传递
+    #[tokio::test]
+    async fn test_task_executor() {
+        let executor = TaskExecutor::new(TaskExecutionConfig {
+            pool_size: 2,
+            shutdown_timeout: Duration::from_secs(5),
+        });
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..6)
+            .map(|i| {
+                let running = running.clone();
+                let max_running = max_running.clone();
+                executor.spawn(async move {
+                    let current = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(current, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    i
+                })
+            })
+            .collect();
+
+        assert!(executor.shutdown().await);
+        assert_eq!(executor.pending(), 0);
+        assert_eq!(max_running.load(Ordering::SeqCst), 2);
+        assert_eq!(handles.into_iter().last().unwrap().await.unwrap(), 5);
+
+        let executor = TaskExecutor::new(TaskExecutionConfig::default());
+        let name = with_principal(Principal::new("alice"), async {
+            let handle = executor.spawn(async { current_principal().map(|principal| principal.name) });
+            handle.await.unwrap()
+        })
+        .await;
+        assert_eq!(name.as_deref(), Some("alice"));
+        assert!(executor.spawn(async { current_principal() }).await.unwrap().is_none());
+    }
+}
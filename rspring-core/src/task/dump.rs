@@ -0,0 +1,347 @@
+//! 任务转储模块
+//!
+//! 生产环境中排查卡住的请求或任务时，[`task_dump`] 返回当前的任务快照：
+//! - 托管任务：通过 [`TaskExecutor`](super::TaskExecutor) 提交的异步任务、任务队列正在执行的任务等，
+//!   包括状态、已运行时长和提交时的日志上下文
+//! - 各组件通过 [`TaskDumpContributor`] 提供的附加信息，如周期任务的下次执行时间
+//! - Tokio 运行时的工作线程数和存活任务数；以 `--cfg tokio_unstable --cfg tokio_taskdump`
+//!   编译时还包括每个任务的异步调用栈
+//!
+//! Web 启动器的 actuator `tasks` 端点返回转储内容
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::Result;
+use crate::logging::log_context;
+
+/// 托管任务的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskState {
+    /// 等待执行许可
+    Queued,
+    /// 执行中
+    Running,
+}
+
+/// 托管任务信息
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TaskInfo {
+    /// 任务 ID，进程内唯一
+    pub id: u64,
+    /// 任务类别，如 `"async"`、`"job"`
+    pub kind: String,
+    /// 任务名称
+    pub name: String,
+    /// 状态
+    pub state: TaskState,
+    /// 提交时间
+    pub submitted_at: DateTime<Utc>,
+    /// 提交后经过的时长
+    #[serde(with = "crate::config::duration")]
+    pub elapsed: Duration,
+    /// 提交时的日志上下文，如请求 ID
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub context: BTreeMap<String, String>,
+}
+
+/// 托管任务登记
+#[derive(Debug)]
+struct TaskEntry {
+    /// 任务信息，`elapsed` 在生成快照时计算
+    info: TaskInfo,
+    /// 提交时刻
+    submitted: Instant,
+}
+
+/// 托管任务登记表
+#[derive(Debug, Default)]
+pub struct TaskRegistry {
+    /// 下一个任务 ID
+    next_id: AtomicU64,
+    /// 未结束的任务
+    tasks: Mutex<HashMap<u64, TaskEntry>>,
+}
+
+impl TaskRegistry {
+    /// 创建空的登记表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 登记任务，返回的守卫被丢弃时移除登记
+    ///
+    /// 任务处于 [`TaskState::Queued`] 状态，开始执行时调用 [`TaskGuard::running`]
+    ///
+    /// # 示例
+    /// ```rust
+    /// let guard = task_registry().track("import", format!("导入文件 {}", file));
+    /// guard.running();
+    /// import(file).await?;
+    /// ```
+    pub fn track(self: &Arc<Self>, kind: impl Into<String>, name: impl Into<String>) -> TaskGuard {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let entry = TaskEntry {
+            info: TaskInfo {
+                id,
+                kind: kind.into(),
+                name: name.into(),
+                state: TaskState::Queued,
+                submitted_at: Utc::now(),
+                elapsed: Duration::ZERO,
+                context: log_context(),
+            },
+            submitted: Instant::now(),
+        };
+        self.lock().insert(id, entry);
+        TaskGuard {
+            registry: self.clone(),
+            id,
+        }
+    }
+
+    /// 获取未结束的任务，按提交顺序排列
+    pub fn snapshot(&self) -> Vec<TaskInfo> {
+        let mut tasks: Vec<TaskInfo> = self
+            .lock()
+            .values()
+            .map(|entry| TaskInfo {
+                elapsed: entry.submitted.elapsed(),
+                ..entry.info.clone()
+            })
+            .collect();
+        tasks.sort_by_key(|task| task.id);
+        tasks
+    }
+
+    /// 锁定登记
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<u64, TaskEntry>> {
+        self.tasks.lock().expect("任务登记锁已损坏")
+    }
+}
+
+/// 任务登记守卫，丢弃时移除登记
+#[derive(Debug)]
+pub struct TaskGuard {
+    /// 所属登记表
+    registry: Arc<TaskRegistry>,
+    /// 任务 ID
+    id: u64,
+}
+
+impl TaskGuard {
+    /// 获取任务 ID
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// 标记任务开始执行
+    pub fn running(&self) {
+        if let Some(entry) = self.registry.lock().get_mut(&self.id) {
+            entry.info.state = TaskState::Running;
+        }
+    }
+}
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        self.registry.lock().remove(&self.id);
+    }
+}
+
+/// 任务转储的附加信息提供者
+///
+/// # 示例
+/// ```rust
+/// struct ConsumerDump { consumers: Arc<ConsumerPool> }
+///
+/// #[async_trait]
+/// impl TaskDumpContributor for ConsumerDump {
+///     async fn dump(&self) -> Result<Value> {
+///         Ok(json!({ "active": self.consumers.active().await }))
+///     }
+/// }
+///
+/// register_task_dump_contributor("kafka_consumers", Arc::new(ConsumerDump { consumers }));
+/// ```
+#[async_trait]
+pub trait TaskDumpContributor: Send + Sync {
+    /// 生成附加信息
+    async fn dump(&self) -> Result<Value>;
+}
+
+/// Tokio 运行时信息
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RuntimeDump {
+    /// 工作线程数
+    pub workers: usize,
+    /// 存活的任务数，包括非托管任务
+    pub alive_tasks: usize,
+    /// 每个任务的异步调用栈，需要以 `--cfg tokio_unstable --cfg tokio_taskdump` 编译
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub traces: Option<Vec<String>>,
+}
+
+/// 任务转储
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TaskDump {
+    /// 生成时间
+    pub generated_at: DateTime<Utc>,
+    /// 未结束的托管任务
+    pub tasks: Vec<TaskInfo>,
+    /// 各提供者的附加信息，生成失败时为错误信息
+    pub sections: BTreeMap<String, Value>,
+    /// Tokio 运行时信息，不在运行时中时为 `None`
+    pub runtime: Option<RuntimeDump>,
+}
+
+/// 全局托管任务登记表
+static TASK_REGISTRY: Lazy<Arc<TaskRegistry>> = Lazy::new(|| Arc::new(TaskRegistry::new()));
+
+/// 全局附加信息提供者
+static CONTRIBUTORS: Lazy<RwLock<BTreeMap<String, Arc<dyn TaskDumpContributor>>>> =
+    Lazy::new(|| RwLock::new(BTreeMap::new()));
+
+/// 获取全局托管任务登记表
+pub fn task_registry() -> Arc<TaskRegistry> {
+    TASK_REGISTRY.clone()
+}
+
+/// 注册附加信息提供者，名称相同时替换
+pub fn register_task_dump_contributor(
+    name: impl Into<String>,
+    contributor: Arc<dyn TaskDumpContributor>,
+) {
+    CONTRIBUTORS
+        .write()
+        .expect("任务转储提供者锁已损坏")
+        .insert(name.into(), contributor);
+}
+
+/// 移除附加信息提供者
+pub fn unregister_task_dump_contributor(name: &str) {
+    CONTRIBUTORS
+        .write()
+        .expect("任务转储提供者锁已损坏")
+        .remove(name);
+}
+
+/// 生成任务转储
+pub async fn task_dump() -> TaskDump {
+    let contributors: Vec<_> = CONTRIBUTORS
+        .read()
+        .expect("任务转储提供者锁已损坏")
+        .iter()
+        .map(|(name, contributor)| (name.clone(), contributor.clone()))
+        .collect();
+    let mut sections = BTreeMap::new();
+    for (name, contributor) in contributors {
+        let section = contributor
+            .dump()
+            .await
+            .unwrap_or_else(|e| serde_json::json!({ "error": e.to_string() }));
+        sections.insert(name, section);
+    }
+
+    TaskDump {
+        generated_at: Utc::now(),
+        tasks: TASK_REGISTRY.snapshot(),
+        sections,
+        runtime: runtime_dump().await,
+    }
+}
+
+/// 获取当前 Tokio 运行时的信息
+async fn runtime_dump() -> Option<RuntimeDump> {
+    let handle = tokio::runtime::Handle::try_current().ok()?;
+    let metrics = handle.metrics();
+    #[cfg(all(tokio_unstable, tokio_taskdump))]
+    let traces = {
+        let dump = handle.dump().await;
+        Some(
+            dump.tasks()
+                .iter()
+                .map(|task| task.trace().to_string())
+                .collect(),
+        )
+    };
+    #[cfg(not(all(tokio_unstable, tokio_taskdump)))]
+    let traces = None;
+    Some(RuntimeDump {
+        workers: metrics.num_workers(),
+        alive_tasks: metrics.num_alive_tasks(),
+        traces,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logging::context::{with_log_context, REQUEST_ID_FIELD};
+    use crate::task::{TaskExecutionConfig, TaskExecutor};
+
+    struct Failing;
+
+    #[async_trait]
+    impl TaskDumpContributor for Failing {
+        async fn dump(&self) -> Result<Value> {
+            Err(crate::Error::internal("存储不可用"))
+        }
+    }
+
+    /// 测试转储包含执行中和排队的托管任务，任务结束后移除
+    #[tokio::test]
+    async fn test_task_dump() {
+        let executor = TaskExecutor::new(TaskExecutionConfig {
+            pool_size: 1,
+            ..Default::default()
+        });
+        let (release, released) = tokio::sync::oneshot::channel::<()>();
+        let (first, second) = with_log_context([(REQUEST_ID_FIELD, "req-1")], async {
+            let first = executor.spawn(async move {
+                let _ = released.await;
+            });
+            (first, executor.spawn(async {}))
+        })
+        .await;
+        register_task_dump_contributor("failing", Arc::new(Failing));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let dump = task_dump().await;
+        let mine: Vec<_> = dump
+            .tasks
+            .iter()
+            .filter(|task| task.context.get(REQUEST_ID_FIELD).map(String::as_str) == Some("req-1"))
+            .collect();
+        assert_eq!(mine.len(), 2);
+        assert_eq!(mine[0].kind, "async");
+        assert_eq!(mine[0].state, TaskState::Running);
+        assert_eq!(mine[1].state, TaskState::Queued);
+        assert_eq!(
+            dump.sections["failing"]["error"],
+            "内部服务器错误: 存储不可用"
+        );
+        assert!(dump.runtime.unwrap().alive_tasks >= 1);
+        unregister_task_dump_contributor("failing");
+
+        release.send(()).unwrap();
+        first.await.unwrap();
+        second.await.unwrap();
+        let remaining = task_registry()
+            .snapshot()
+            .into_iter()
+            .filter(|task| task.context.contains_key(REQUEST_ID_FIELD))
+            .filter(|task| task.context[REQUEST_ID_FIELD] == "req-1")
+            .count();
+        assert_eq!(remaining, 0);
+    }
+}
@@ -0,0 +1,237 @@
+//! 链路追踪上下文模块
+//!
+//! 按 [W3C Trace Context](https://www.w3.org/TR/trace-context/) 在服务之间传递链路信息：
+//! - 入站请求和消息通过 [`TraceContext::extract`] 读取 `traceparent`、`tracestate`，
+//!   再由 [`continue_trace`] 在新的跨度中处理，没有上游链路时开始新的链路
+//! - 出站请求和消息通过 [`outbound_trace_headers`] 取得需要附加的头，调用方不需要手动传递
+//! - 处理期间链路 ID 和跨度 ID 作为日志上下文的 `trace_id`、`span_id` 字段附加到每一条日志
+//!
+//! Web 启动器的 `[web.trace_context]` 中间件处理入站 HTTP 请求，SQS 启动器在收发消息时自动传递
+
+use std::future::Future;
+
+use crate::logging::context::{with_log_context, SPAN_ID_FIELD, TRACE_ID_FIELD};
+
+/// 链路信息请求头
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// 厂商扩展信息请求头
+pub const TRACESTATE_HEADER: &str = "tracestate";
+
+/// 支持的 `traceparent` 版本
+const VERSION: &str = "00";
+
+/// 采样标志位
+const FLAG_SAMPLED: u8 = 0x01;
+
+tokio::task_local! {
+    /// 当前的链路上下文
+    static CURRENT_TRACE: TraceContext;
+}
+
+/// 链路上下文
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    /// 链路 ID，32 位小写十六进制
+    pub trace_id: String,
+    /// 当前跨度 ID，16 位小写十六进制；从请求头解析时为上游的跨度 ID
+    pub span_id: String,
+    /// 是否被采样
+    pub sampled: bool,
+    /// 厂商扩展信息，原样传递
+    pub trace_state: Option<String>,
+}
+
+impl TraceContext {
+    /// 开始新的链路
+    pub fn new_root() -> Self {
+        Self {
+            trace_id: uuid::Uuid::new_v4().simple().to_string(),
+            span_id: new_span_id(),
+            sampled: true,
+            trace_state: None,
+        }
+    }
+
+    /// 解析 `traceparent` 和 `tracestate` 的值，格式无效时返回 `None`
+    ///
+    /// 高于 `00` 的版本只读取前四个字段，`ff` 版本和全零的 ID 视为无效
+    pub fn parse(traceparent: &str, tracestate: Option<&str>) -> Option<Self> {
+        let mut parts = traceparent.trim().split('-');
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let span_id = parts.next()?;
+        let flags = parts.next()?;
+        let valid_version =
+            is_hex(version, 2) && version != "ff" && (version != VERSION || parts.next().is_none());
+        if !valid_version || !is_id(trace_id, 32) || !is_id(span_id, 16) || !is_hex(flags, 2) {
+            return None;
+        }
+        let flags = u8::from_str_radix(flags, 16).ok()?;
+        let trace_state = tracestate
+            .map(str::trim)
+            .filter(|state| !state.is_empty())
+            .map(str::to_string);
+        Some(Self {
+            trace_id: trace_id.to_string(),
+            span_id: span_id.to_string(),
+            sampled: flags & FLAG_SAMPLED != 0,
+            trace_state,
+        })
+    }
+
+    /// 通过名称读取头的值并解析链路上下文
+    ///
+    /// # 示例
+    /// ```rust
+    /// let parent = TraceContext::extract(|name| message.attributes.get(name).map(String::as_str));
+    /// ```
+    pub fn extract<'a>(get: impl Fn(&str) -> Option<&'a str>) -> Option<Self> {
+        Self::parse(get(TRACEPARENT_HEADER)?, get(TRACESTATE_HEADER))
+    }
+
+    /// 在同一链路中创建子跨度
+    pub fn child(&self) -> Self {
+        Self {
+            span_id: new_span_id(),
+            ..self.clone()
+        }
+    }
+
+    /// 生成 `traceparent` 的值
+    pub fn traceparent(&self) -> String {
+        let flags = if self.sampled { FLAG_SAMPLED } else { 0 };
+        format!(
+            "{}-{}-{}-{:02x}",
+            VERSION, self.trace_id, self.span_id, flags
+        )
+    }
+
+    /// 生成需要传递给下游的头
+    pub fn headers(&self) -> Vec<(&'static str, String)> {
+        let mut headers = vec![(TRACEPARENT_HEADER, self.traceparent())];
+        if let Some(state) = &self.trace_state {
+            headers.push((TRACESTATE_HEADER, state.clone()));
+        }
+        headers
+    }
+}
+
+/// 获取当前的链路上下文
+///
+/// 不在链路上下文中时返回 `None`
+pub fn current_trace_context() -> Option<TraceContext> {
+    CURRENT_TRACE.try_with(Clone::clone).ok()
+}
+
+/// 在指定的链路上下文中执行异步任务
+///
+/// 链路 ID 和跨度 ID 同时加入日志上下文
+pub async fn with_trace_context<F: Future>(context: TraceContext, future: F) -> F::Output {
+    let fields = [
+        (TRACE_ID_FIELD, context.trace_id.clone()),
+        (SPAN_ID_FIELD, context.span_id.clone()),
+    ];
+    CURRENT_TRACE
+        .scope(context, with_log_context(fields, future))
+        .await
+}
+
+/// 在上游链路的子跨度中执行异步任务，没有上游链路时开始新的链路
+///
+/// # 示例
+/// ```rust
+/// let parent = TraceContext::extract(|name| headers.get(name).and_then(|value| value.to_str().ok()));
+/// continue_trace(parent, handle(request)).await
+/// ```
+pub async fn continue_trace<F: Future>(parent: Option<TraceContext>, future: F) -> F::Output {
+    let context = parent.map_or_else(TraceContext::new_root, |parent| parent.child());
+    with_trace_context(context, future).await
+}
+
+/// 获取出站请求或消息需要附加的头
+///
+/// 不在链路上下文中时返回空列表
+///
+/// # 示例
+/// ```rust
+/// let mut request = http.post(url).json(&order);
+/// for (name, value) in outbound_trace_headers() {
+///     request = request.header(name, value);
+/// }
+/// ```
+pub fn outbound_trace_headers() -> Vec<(&'static str, String)> {
+    current_trace_context()
+        .map(|context| context.headers())
+        .unwrap_or_default()
+}
+
+/// 生成新的跨度 ID
+fn new_span_id() -> String {
+    let id = uuid::Uuid::new_v4().simple().to_string();
+    id[..16].to_string()
+}
+
+/// 判断是否为指定长度的小写十六进制字符串
+fn is_hex(value: &str, len: usize) -> bool {
+    value.len() == len
+        && value
+            .bytes()
+            .all(|byte| byte.is_ascii_digit() || (b'a'..=b'f').contains(&byte))
+}
+
+/// 判断是否为有效的链路或跨度 ID，全零无效
+fn is_id(value: &str, len: usize) -> bool {
+    is_hex(value, len) && value.bytes().any(|byte| byte != b'0')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logging::context::log_context;
+
+    /// 测试解析、延续和传递链路上下文
+    #[tokio::test]
+    async fn test_trace_context() {
+        let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let parent = TraceContext::parse(traceparent, Some("congo=t61rcWkgMzE")).unwrap();
+        assert_eq!(parent.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert!(parent.sampled);
+        assert_eq!(parent.traceparent(), traceparent);
+        for invalid in [
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+        ] {
+            assert_eq!(TraceContext::parse(invalid, None), None, "{}", invalid);
+        }
+        assert!(TraceContext::parse(
+            "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00-extra",
+            None
+        )
+        .is_some());
+
+        assert!(outbound_trace_headers().is_empty());
+        continue_trace(Some(parent.clone()), async {
+            let current = current_trace_context().unwrap();
+            assert_eq!(current.trace_id, parent.trace_id);
+            assert_ne!(current.span_id, parent.span_id);
+            assert_eq!(log_context()[TRACE_ID_FIELD], parent.trace_id);
+
+            let headers = outbound_trace_headers();
+            assert_eq!(headers[0], (TRACEPARENT_HEADER, current.traceparent()));
+            assert_eq!(
+                headers[1],
+                (TRACESTATE_HEADER, "congo=t61rcWkgMzE".to_string())
+            );
+        })
+        .await;
+
+        let root = continue_trace(None, async { current_trace_context().unwrap() }).await;
+        assert_eq!(root.trace_id.len(), 32);
+        assert_eq!(root.span_id.len(), 16);
+        assert_eq!(TraceContext::parse(&root.traceparent(), None), Some(root));
+    }
+}
@@ -0,0 +1,363 @@
+//! 事务模块
+//!
+//! 提供类似 Spring 的声明式事务抽象：
+//! - [`TransactionManager`] 负责开启事务，数据访问启动器提供具体实现，如 MySQL 的连接事务
+//! - [`transactional`] 在事务中执行异步任务，返回 `Ok` 时提交，返回 `Err` 时回滚；
+//!   已在事务中时加入当前事务
+//! - 事务同步回调按 [`TransactionPhase`] 在提交前、提交后、回滚后或完成后执行，
+//!   事务事件监听器基于它实现
+//! - `#[Transactional]` 注解在事务中执行方法体
+//!
+//! 当前事务保存在任务本地上下文中，`tokio::spawn` 的后台任务不在事务中
+
+use std::any::Any;
+use std::future::Future;
+use std::sync::{Arc, Mutex, RwLock};
+
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+
+tokio::task_local! {
+    /// 当前事务
+    static CURRENT_TRANSACTION: Arc<TransactionContext>;
+}
+
+/// 事务阶段
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionPhase {
+    /// 提交前，仍在事务中执行，返回错误时事务回滚
+    BeforeCommit,
+    /// 提交成功后
+    #[default]
+    AfterCommit,
+    /// 回滚后，包括提交失败
+    AfterRollback,
+    /// 提交或回滚后
+    AfterCompletion,
+}
+
+impl TransactionPhase {
+    /// 获取阶段名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::BeforeCommit => "before_commit",
+            Self::AfterCommit => "after_commit",
+            Self::AfterRollback => "after_rollback",
+            Self::AfterCompletion => "after_completion",
+        }
+    }
+
+    /// 解析阶段名称
+    ///
+    /// # 错误
+    /// 名称无效时返回验证错误
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "before_commit" => Ok(Self::BeforeCommit),
+            "after_commit" => Ok(Self::AfterCommit),
+            "after_rollback" => Ok(Self::AfterRollback),
+            "after_completion" => Ok(Self::AfterCompletion),
+            _ => Err(Error::validation(format!("无效的事务阶段: {}", value))),
+        }
+    }
+}
+
+impl std::fmt::Display for TransactionPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 已开启的事务
+#[async_trait]
+pub trait Transaction: Send {
+    /// 事务持有的资源，如数据库连接，事务中可通过 [`transaction_resource`] 获取
+    fn resource(&self) -> Option<Arc<dyn Any + Send + Sync>> {
+        None
+    }
+
+    /// 提交事务
+    async fn commit(self: Box<Self>) -> Result<()>;
+
+    /// 回滚事务
+    async fn rollback(self: Box<Self>) -> Result<()>;
+}
+
+/// 事务管理器特征
+#[async_trait]
+pub trait TransactionManager: Send + Sync {
+    /// 开启事务
+    async fn begin(&self) -> Result<Box<dyn Transaction>>;
+}
+
+/// 不管理任何资源的事务管理器，只划定事务边界并执行同步回调
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoopTransactionManager;
+
+/// [`NoopTransactionManager`] 开启的事务
+struct NoopTransaction;
+
+#[async_trait]
+impl Transaction for NoopTransaction {
+    async fn commit(self: Box<Self>) -> Result<()> {
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TransactionManager for NoopTransactionManager {
+    async fn begin(&self) -> Result<Box<dyn Transaction>> {
+        Ok(Box::new(NoopTransaction))
+    }
+}
+
+/// 全局事务管理器
+static TRANSACTION_MANAGER: Lazy<RwLock<Arc<dyn TransactionManager>>> =
+    Lazy::new(|| RwLock::new(Arc::new(NoopTransactionManager)));
+
+/// 设置全局事务管理器，供 [`transactional`] 和 `#[Transactional]` 使用
+///
+/// 未设置时使用 [`NoopTransactionManager`]
+///
+/// 在作用域中调用时的行为见[全局组件作用域](crate::scope)
+pub fn set_transaction_manager(manager: Arc<dyn TransactionManager>) {
+    if let Some(manager) = crate::scope::set_scoped(manager) {
+        *TRANSACTION_MANAGER.write().expect("事务管理器锁已损坏") = manager;
+    }
+}
+
+/// 获取全局事务管理器
+pub fn transaction_manager() -> Arc<dyn TransactionManager> {
+    crate::scope::scoped()
+        .unwrap_or_else(|| TRANSACTION_MANAGER.read().expect("事务管理器锁已损坏").clone())
+}
+
+/// 事务同步回调
+type Synchronization = Box<dyn FnOnce() -> BoxFuture<'static, Result<()>> + Send>;
+
+/// 事务上下文
+struct TransactionContext {
+    /// 事务持有的资源
+    resource: Option<Arc<dyn Any + Send + Sync>>,
+    /// 已注册的同步回调
+    synchronizations: Mutex<Vec<(TransactionPhase, Synchronization)>>,
+}
+
+impl TransactionContext {
+    /// 按注册顺序执行指定阶段的回调，执行期间新注册的回调也会被执行
+    ///
+    /// 返回第一个错误，其余回调仍会执行
+    async fn run(&self, phase: TransactionPhase) -> Result<()> {
+        let mut first_error = None;
+        loop {
+            let callbacks: Vec<Synchronization> = {
+                let mut synchronizations = self.synchronizations.lock().expect("事务同步锁已损坏");
+                let (matched, rest) = std::mem::take(&mut *synchronizations)
+                    .into_iter()
+                    .partition(|(registered, _)| *registered == phase);
+                *synchronizations = rest;
+                matched.into_iter().map(|(_, callback)| callback).collect()
+            };
+            if callbacks.is_empty() {
+                break;
+            }
+            for callback in callbacks {
+                if let Err(e) = callback().await {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    /// 执行事务完成后的回调，错误只记录日志
+    async fn complete(&self, committed: bool) {
+        let phases = if committed {
+            [TransactionPhase::AfterCommit, TransactionPhase::AfterCompletion]
+        } else {
+            [TransactionPhase::AfterRollback, TransactionPhase::AfterCompletion]
+        };
+        for phase in phases {
+            if let Err(e) = self.run(phase).await {
+                tracing::warn!("事务同步回调执行失败 ({}): {}", phase, e);
+            }
+        }
+    }
+}
+
+/// 当前是否在事务中
+pub fn is_transaction_active() -> bool {
+    CURRENT_TRANSACTION.try_with(|_| ()).is_ok()
+}
+
+/// 获取当前事务持有的资源
+///
+/// 不在事务中或资源类型不是 `R` 时返回 `None`
+pub fn transaction_resource<R: Any + Send + Sync>() -> Option<Arc<R>> {
+    CURRENT_TRANSACTION
+        .try_with(|context| context.resource.clone())
+        .ok()
+        .flatten()
+        .and_then(|resource| resource.downcast::<R>().ok())
+}
+
+/// 向当前事务注册同步回调，在指定阶段执行
+///
+/// 不在事务中时不注册，返回 `false`
+///
+/// # 示例
+/// ```rust
+/// register_synchronization(TransactionPhase::AfterCommit, || async move {
+///     cache.evict(&key).await;
+///     Ok(())
+/// });
+/// ```
+pub fn register_synchronization<F, Fut>(phase: TransactionPhase, callback: F) -> bool
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<()>> + Send + 'static,
+{
+    CURRENT_TRANSACTION
+        .try_with(|context| {
+            context
+                .synchronizations
+                .lock()
+                .expect("事务同步锁已损坏")
+                .push((phase, Box::new(move || callback().boxed())));
+        })
+        .is_ok()
+}
+
+/// 在事务中执行异步任务
+///
+/// 任务返回 `Ok` 时依次执行提交前回调、提交事务、执行提交后回调；
+/// 返回 `Err`、提交前回调失败或提交失败时回滚事务并执行回滚后回调。
+/// 已在事务中时直接加入当前事务，由最外层负责提交或回滚
+///
+/// # 错误
+/// 开启、提交事务或提交前回调失败时返回错误，任务的错误原样返回
+///
+/// # 示例
+/// ```rust
+/// let order = transactional(async {
+///     let order = repository.save(order).await?;
+///     publish_event(OrderCreated { id: order.id }).await?;
+///     Ok::<_, Error>(order)
+/// })
+/// .await?;
+/// ```
+pub async fn transactional<T, E, F>(task: F) -> std::result::Result<T, E>
+where
+    F: Future<Output = std::result::Result<T, E>>,
+    E: From<Error>,
+{
+    if is_transaction_active() {
+        return task.await;
+    }
+
+    let transaction = transaction_manager().begin().await?;
+    let context = Arc::new(TransactionContext {
+        resource: transaction.resource(),
+        synchronizations: Mutex::new(Vec::new()),
+    });
+
+    let result = CURRENT_TRANSACTION.scope(context.clone(), task).await;
+    let value = match result {
+        Ok(value) => value,
+        Err(error) => {
+            rollback(transaction, &context).await;
+            return Err(error);
+        }
+    };
+
+    // 提交前回调仍在事务中执行，可以访问事务资源
+    let before_commit = CURRENT_TRANSACTION
+        .scope(context.clone(), context.run(TransactionPhase::BeforeCommit))
+        .await;
+    if let Err(error) = before_commit {
+        rollback(transaction, &context).await;
+        return Err(error.into());
+    }
+
+    if let Err(error) = transaction.commit().await {
+        context.complete(false).await;
+        return Err(error.into());
+    }
+    context.complete(true).await;
+    Ok(value)
+}
+
+/// 回滚事务并执行回滚后回调
+async fn rollback(transaction: Box<dyn Transaction>, context: &TransactionContext) {
+    if let Err(e) = transaction.rollback().await {
+        tracing::warn!("事务回滚失败: {}", e);
+    }
+    context.complete(false).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试提交和回滚时执行对应阶段的回调
+    #[tokio::test]
+    async fn test_transaction_synchronization() {
+        let phases = Arc::new(Mutex::new(Vec::new()));
+        let record = |phases: &Arc<Mutex<Vec<TransactionPhase>>>, phase| {
+            let phases = phases.clone();
+            assert!(register_synchronization(phase, move || async move {
+                phases.lock().unwrap().push(phase);
+                Ok(())
+            }));
+        };
+
+        assert!(!is_transaction_active());
+        let value = transactional(async {
+            assert!(is_transaction_active());
+            for phase in [
+                TransactionPhase::AfterCompletion,
+                TransactionPhase::AfterRollback,
+                TransactionPhase::AfterCommit,
+                TransactionPhase::BeforeCommit,
+            ] {
+                record(&phases, phase);
+            }
+            // 嵌套调用加入当前事务
+            transactional(async { Ok::<_, Error>(is_transaction_active()) }).await
+        })
+        .await
+        .unwrap();
+        assert!(value);
+        assert_eq!(
+            *phases.lock().unwrap(),
+            vec![
+                TransactionPhase::BeforeCommit,
+                TransactionPhase::AfterCommit,
+                TransactionPhase::AfterCompletion,
+            ]
+        );
+
+        phases.lock().unwrap().clear();
+        let result: Result<()> = transactional(async {
+            record(&phases, TransactionPhase::AfterCommit);
+            record(&phases, TransactionPhase::AfterRollback);
+            Err(Error::validation("余额不足"))
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(*phases.lock().unwrap(), vec![TransactionPhase::AfterRollback]);
+
+        assert!(!register_synchronization(TransactionPhase::AfterCommit, || async { Ok(()) }));
+        assert_eq!(TransactionPhase::parse("after_rollback").unwrap(), TransactionPhase::AfterRollback);
+    }
+}
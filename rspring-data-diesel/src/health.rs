@@ -0,0 +1,102 @@
+//! 数据源健康检查模块
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use diesel::r2d2::R2D2Connection;
+use diesel::RunQueryDsl;
+use rspring_core::{Health, HealthIndicator};
+
+use crate::pool::DieselPool;
+
+/// 健康检查的默认超时时间
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Diesel 数据源健康指示器
+///
+/// 执行 `SELECT 1` 检查数据库是否可用，并报告连接池状态
+///
+/// # 示例
+/// ```rust
+/// registry.register(DieselHealthIndicator::new("primary", pool.clone()));
+/// ```
+pub struct DieselHealthIndicator<C>
+where
+    C: R2D2Connection + 'static,
+{
+    /// 数据源名称
+    name: String,
+    /// 连接池
+    pool: DieselPool<C>,
+    /// 检查超时时间
+    timeout: Duration,
+}
+
+impl<C> DieselHealthIndicator<C>
+where
+    C: R2D2Connection + Send + 'static,
+{
+    /// 创建健康指示器
+    pub fn new(name: impl Into<String>, pool: DieselPool<C>) -> Self {
+        Self {
+            name: name.into(),
+            pool,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// 设置检查超时时间
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+#[async_trait]
+impl<C> HealthIndicator for DieselHealthIndicator<C>
+where
+    C: R2D2Connection + Send + 'static,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn health(&self) -> Health {
+        let query = self
+            .pool
+            .run(|conn| diesel::sql_query("SELECT 1").execute(conn));
+        let health = match tokio::time::timeout(self.timeout, query).await {
+            Ok(Ok(_)) => Health::up(),
+            Ok(Err(e)) => Health::down(e),
+            Err(_) => Health::down(format!("检查超时 ({:?})", self.timeout)),
+        };
+
+        let state = self.pool.state();
+        health
+            .with_detail("active", state.connections.saturating_sub(state.idle_connections))
+            .with_detail("idle", state.idle_connections)
+            .with_detail("max", self.pool.pool().max_size())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel::sqlite::SqliteConnection;
+    use rspring_core::config::DataSourceConfig;
+    use rspring_core::HealthStatus;
+
+    /// 测试数据库可用时报告 UP 和连接池状态
+    #[tokio::test]
+    async fn test_health_up() {
+        let config = DataSourceConfig {
+            max_connections: 2,
+            ..DataSourceConfig::new(":memory:")
+        };
+        let pool = DieselPool::<SqliteConnection>::new(&config).unwrap();
+
+        let health = DieselHealthIndicator::new("primary", pool).health().await;
+        assert_eq!(health.status, HealthStatus::Up);
+        assert_eq!(health.details["max"], 2);
+    }
+}
@@ -0,0 +1,27 @@
+//! RSpring Diesel 启动器
+//!
+//! 根据 `[datasource]` 配置管理 r2d2 连接池，并提供基于 `spawn_blocking` 的异步门面，
+//! 已有的 Diesel 同步数据访问代码无需改写即可在异步处理器中使用
+//!
+//! 通过 `mysql`、`postgres`、`sqlite` 特性启用对应的数据库后端，`metrics` 特性导出连接池指标
+//!
+//! # 示例
+//! ```rust
+//! let pool = DieselPool::<MysqlConnection>::init(&context).await?;
+//!
+//! let users = pool
+//!     .run(|conn| users::table.filter(users::active.eq(true)).load::<User>(conn))
+//!     .await?;
+//! ```
+
+pub mod health;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod pool;
+
+pub use health::*;
+#[cfg(feature = "metrics")]
+pub use crate::metrics::*;
+pub use pool::*;
+
+pub use diesel;
@@ -0,0 +1,137 @@
+//! 连接池指标模块
+//!
+//! 启用 `metrics` 特性后，通过 `metrics` 门面导出连接池指标，均带有 `pool` 标签：
+//! - `datasource.connections.active` - 使用中的连接数
+//! - `datasource.connections.idle` - 空闲连接数
+//! - `datasource.connections.max` - 最大连接数
+//! - `datasource.connections.wait` - 获取连接的等待时间（秒），每次取出连接时记录
+//! - `datasource.connections.timeouts` - 获取连接超时的次数
+//!
+//! 以及 [`DieselPool::run`] 每次执行记录的查询指标：
+//! - `datasource.query.duration` - 执行耗时（秒），事务按一次执行记录
+//! - `datasource.queries.errors` - 执行失败的次数，`NotFound` 不计入
+//!
+//! [`DieselPool::new`] 按 `[datasource.metrics]` 配置自动开始记录
+
+use std::time::Duration;
+
+use diesel::r2d2::event::{CheckoutEvent, HandleEvent, TimeoutEvent};
+use diesel::r2d2::R2D2Connection;
+use diesel::result::Error as DieselError;
+use rspring_core::database::{
+    CONNECTIONS_ACTIVE_METRIC, CONNECTIONS_IDLE_METRIC, CONNECTIONS_MAX_METRIC,
+    CONNECTIONS_TIMEOUTS_METRIC, CONNECTIONS_WAIT_METRIC, QUERY_DURATION_METRIC,
+    QUERY_ERRORS_METRIC,
+};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::pool::DieselPool;
+
+/// 记录一次连接池状态
+pub fn record_pool_metrics<C>(name: &str, pool: &DieselPool<C>)
+where
+    C: R2D2Connection + Send + 'static,
+{
+    let state = pool.state();
+    let pool_name = name.to_string();
+    ::metrics::gauge!(CONNECTIONS_ACTIVE_METRIC, "pool" => pool_name.clone())
+        .set(state.connections.saturating_sub(state.idle_connections) as f64);
+    ::metrics::gauge!(CONNECTIONS_IDLE_METRIC, "pool" => pool_name.clone())
+        .set(state.idle_connections as f64);
+    ::metrics::gauge!(CONNECTIONS_MAX_METRIC, "pool" => pool_name)
+        .set(pool.pool().max_size() as f64);
+}
+
+/// 记录一次执行的耗时和结果
+pub fn record_query_metrics<R>(elapsed: Duration, result: &Result<R, DieselError>) {
+    ::metrics::histogram!(QUERY_DURATION_METRIC).record(elapsed.as_secs_f64());
+    if matches!(result, Err(error) if !matches!(error, DieselError::NotFound)) {
+        ::metrics::counter!(QUERY_ERRORS_METRIC).increment(1);
+    }
+}
+
+/// 启动后台任务，按固定间隔记录连接池状态
+///
+/// `cancel` 取消后任务退出
+///
+/// # 示例
+/// ```rust
+/// let cancel = CancellationToken::new();
+/// spawn_pool_metrics("primary", pool.clone(), Duration::from_secs(10), cancel.clone());
+/// ```
+pub fn spawn_pool_metrics<C>(
+    name: impl Into<String>,
+    pool: DieselPool<C>,
+    interval: Duration,
+    cancel: CancellationToken,
+) -> JoinHandle<()>
+where
+    C: R2D2Connection + Send + 'static,
+{
+    let name = name.into();
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => record_pool_metrics(&name, &pool),
+                _ = cancel.cancelled() => break,
+            }
+        }
+    })
+}
+
+/// 记录获取连接等待时间的 r2d2 事件处理器
+///
+/// # 示例
+/// ```rust
+/// let pool = Pool::builder()
+///     .event_handler(Box::new(PoolMetricsHandler::new("primary")))
+///     .build(manager)?;
+/// let pool = DieselPool::from_pool(pool);
+/// ```
+#[derive(Debug)]
+pub struct PoolMetricsHandler {
+    /// 数据源名称
+    name: String,
+}
+
+impl PoolMetricsHandler {
+    /// 创建事件处理器
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+impl HandleEvent for PoolMetricsHandler {
+    fn handle_checkout(&self, event: CheckoutEvent) {
+        ::metrics::histogram!(CONNECTIONS_WAIT_METRIC, "pool" => self.name.clone())
+            .record(event.duration().as_secs_f64());
+    }
+
+    fn handle_timeout(&self, _event: TimeoutEvent) {
+        ::metrics::counter!(CONNECTIONS_TIMEOUTS_METRIC, "pool" => self.name.clone()).increment(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel::sqlite::SqliteConnection;
+    use rspring_core::config::DataSourceConfig;
+
+    /// 测试取消后连接池指标任务退出
+    #[tokio::test]
+    async fn test_spawn_pool_metrics_cancel() {
+        let config = DataSourceConfig {
+            max_connections: 1,
+            ..DataSourceConfig::new(":memory:")
+        };
+        let pool = DieselPool::<SqliteConnection>::new(&config).unwrap();
+        let cancel = CancellationToken::new();
+        let handle = spawn_pool_metrics("test", pool, Duration::from_millis(10), cancel.clone());
+
+        cancel.cancel();
+        tokio::time::timeout(Duration::from_secs(1), handle).await.unwrap().unwrap();
+    }
+}
@@ -0,0 +1,362 @@
+//! Diesel 连接池模块
+
+#[cfg(feature = "metrics")]
+use std::sync::Arc;
+
+use diesel::r2d2::{ConnectionManager, Pool, PooledConnection, R2D2Connection, State};
+use diesel::result::Error as DieselError;
+use diesel::connection::SimpleConnection;
+use diesel::Connection;
+use rspring_core::config::{ConfigurationManager, DataSourceConfig};
+use rspring_core::database::load_init_scripts;
+use rspring_core::{ApplicationContext, Component, Error, HealthRegistry, Result};
+#[cfg(feature = "metrics")]
+use tokio_util::sync::{CancellationToken, DropGuard};
+
+use crate::health::DieselHealthIndicator;
+
+/// Diesel 连接池
+///
+/// 克隆开销很小，克隆后共享同一个连接池。所有数据库操作在 tokio 的阻塞线程池中执行，
+/// 不会阻塞异步运行时
+pub struct DieselPool<C>
+where
+    C: R2D2Connection + 'static,
+{
+    /// r2d2 连接池
+    pool: Pool<ConnectionManager<C>>,
+    /// 连接池指标任务的取消守卫，所有克隆释放后停止任务
+    #[cfg(feature = "metrics")]
+    metrics_guard: Option<Arc<DropGuard>>,
+}
+
+impl<C> Clone for DieselPool<C>
+where
+    C: R2D2Connection + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            #[cfg(feature = "metrics")]
+            metrics_guard: self.metrics_guard.clone(),
+        }
+    }
+}
+
+impl<C> DieselPool<C>
+where
+    C: R2D2Connection + Send + 'static,
+{
+    /// 根据数据源配置创建连接池
+    ///
+    /// 创建时会建立 `min_idle` 个连接，数据库不可用时返回错误。启用 `metrics` 特性时按
+    /// `[datasource.metrics]` 记录连接池指标，在 Tokio 运行时中创建时还会定期记录连接池状态
+    pub fn new(config: &DataSourceConfig) -> Result<Self> {
+        let manager = ConnectionManager::<C>::new(config.url.as_str());
+        let builder = Pool::builder()
+            .max_size(config.max_connections)
+            .min_idle(config.min_idle)
+            .connection_timeout(config.connect_timeout)
+            .idle_timeout(config.idle_timeout)
+            .max_lifetime(config.max_lifetime)
+            .test_on_check_out(true);
+        #[cfg(feature = "metrics")]
+        let builder = if config.metrics.enabled {
+            builder.event_handler(Box::new(crate::metrics::PoolMetricsHandler::new(
+                config.metrics.name.clone(),
+            )))
+        } else {
+            builder
+        };
+        let pool = builder
+            .build(manager)
+            .map_err(|e| Error::application(format!("创建 Diesel 连接池失败: {}", e)))?;
+        let pool = Self::from_pool(pool);
+
+        #[cfg(feature = "metrics")]
+        let pool = if config.metrics.enabled && tokio::runtime::Handle::try_current().is_ok() {
+            // 任务持有不带守卫的连接池，避免自身阻止任务退出
+            let cancel = CancellationToken::new();
+            crate::metrics::spawn_pool_metrics(
+                config.metrics.name.clone(),
+                pool.clone(),
+                config.metrics.interval,
+                cancel.clone(),
+            );
+            Self {
+                metrics_guard: Some(Arc::new(cancel.drop_guard())),
+                ..pool
+            }
+        } else {
+            pool
+        };
+
+        tracing::info!("Diesel 连接池已创建，最大连接数: {}", config.max_connections);
+        Ok(pool)
+    }
+
+    /// 使用配置文件中的 `[datasource]` 章节创建连接池，并按 `[datasource.init]`
+    /// 执行当前环境的初始化脚本
+    ///
+    /// # 错误
+    /// 未配置数据源、连接池创建失败或初始化脚本执行失败时返回错误
+    pub fn from_config(config: &ConfigurationManager) -> Result<Self> {
+        let datasource: DataSourceConfig = config.get_section("datasource")?;
+        let pool = Self::new(&datasource)?;
+        pool.initialize(&datasource, config.profile())?;
+        Ok(pool)
+    }
+
+    /// 使用配置文件中的 `[datasource]` 章节创建连接池并注册到应用上下文
+    ///
+    /// 上下文中存在 [`HealthRegistry`] 时同时注册以 `[datasource.metrics]` 的 `name`
+    /// 命名的 [`DieselHealthIndicator`]
+    ///
+    /// # 错误
+    /// 未配置数据源、连接池创建失败或初始化脚本执行失败时返回错误
+    pub async fn init(context: &ApplicationContext) -> Result<Self> {
+        let datasource: DataSourceConfig = context.config_manager().get_section("datasource")?;
+        let pool = Self::from_config(context.config_manager())?;
+        context.register_singleton(pool.clone()).await;
+
+        if let Some(registry) = context.get::<HealthRegistry>().await {
+            registry.register(DieselHealthIndicator::new(datasource.metrics.name, pool.clone()));
+        }
+        Ok(pool)
+    }
+
+    /// 同步执行 `schema.sql`、`data.sql` 等初始化脚本
+    ///
+    /// 默认只对 SQLite 执行，其他数据库需配置 `mode = "always"`
+    ///
+    /// # 错误
+    /// 脚本无法读取，或语句执行失败且未开启 `continue_on_error` 时返回错误
+    pub fn initialize(&self, config: &DataSourceConfig, profile: &str) -> Result<()> {
+        let scripts = load_init_scripts(config, profile)?;
+        if scripts.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = self.get()?;
+        for script in scripts {
+            for statement in &script.statements {
+                if let Err(e) = conn.batch_execute(statement) {
+                    let message = format!("执行初始化脚本失败 ({}): {}", script.path.display(), e);
+                    if !config.init.continue_on_error {
+                        return Err(Error::application(message));
+                    }
+                    tracing::warn!("{}", message);
+                }
+            }
+            tracing::info!(
+                "已执行初始化脚本 {}（{} 条语句）",
+                script.path.display(),
+                script.statements.len()
+            );
+        }
+        Ok(())
+    }
+
+    /// 使用已有的 r2d2 连接池创建
+    pub fn from_pool(pool: Pool<ConnectionManager<C>>) -> Self {
+        Self {
+            pool,
+            #[cfg(feature = "metrics")]
+            metrics_guard: None,
+        }
+    }
+
+    /// 获取底层的 r2d2 连接池
+    pub fn pool(&self) -> &Pool<ConnectionManager<C>> {
+        &self.pool
+    }
+
+    /// 获取连接池状态（总连接数和空闲连接数）
+    pub fn state(&self) -> State {
+        self.pool.state()
+    }
+
+    /// 同步获取连接，只能在阻塞上下文中调用
+    pub fn get(&self) -> Result<PooledConnection<ConnectionManager<C>>> {
+        self.pool
+            .get()
+            .map_err(|e| Error::application(format!("获取数据库连接失败: {}", e)))
+    }
+
+    /// 在阻塞线程池中获取连接并执行 Diesel 操作
+    ///
+    /// # 示例
+    /// ```rust
+    /// let user = pool
+    ///     .run(move |conn| users::table.find(id).first::<User>(conn))
+    ///     .await?;
+    /// ```
+    ///
+    /// # 错误
+    /// 获取连接失败或查询失败时返回错误，`NotFound` 转换为 [`Error::NotFound`]
+    pub async fn run<F, R>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&mut C) -> std::result::Result<R, DieselError> + Send + 'static,
+        R: Send + 'static,
+    {
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool
+                .get()
+                .map_err(|e| Error::application(format!("获取数据库连接失败: {}", e)))?;
+            #[cfg(feature = "metrics")]
+            let start = std::time::Instant::now();
+            let result = f(&mut *conn);
+            #[cfg(feature = "metrics")]
+            crate::metrics::record_query_metrics(start.elapsed(), &result);
+            result.map_err(map_diesel_error)
+        })
+        .await
+        .map_err(|e| Error::internal(format!("数据库任务执行失败: {}", e)))?
+    }
+
+    /// 在事务中执行 Diesel 操作，闭包返回错误时回滚
+    ///
+    /// # 示例
+    /// ```rust
+    /// pool.transaction(move |conn| {
+    ///     diesel::insert_into(orders::table).values(&order).execute(conn)?;
+    ///     diesel::update(stock::table.find(item_id))
+    ///         .set(stock::quantity.eq(stock::quantity - 1))
+    ///         .execute(conn)
+    /// })
+    /// .await?;
+    /// ```
+    pub async fn transaction<F, R>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&mut C) -> std::result::Result<R, DieselError> + Send + 'static,
+        R: Send + 'static,
+    {
+        self.run(move |conn| conn.transaction(f)).await
+    }
+}
+
+impl<C> Component for DieselPool<C>
+where
+    C: R2D2Connection + Send + 'static,
+{
+    fn component_name(&self) -> &'static str {
+        "DieselPool"
+    }
+}
+
+/// 将 Diesel 错误转换为框架错误
+fn map_diesel_error(error: DieselError) -> Error {
+    match error {
+        DieselError::NotFound => Error::not_found("记录"),
+        other => Error::internal(format!("数据库操作失败: {}", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel::prelude::*;
+    use diesel::sql_types::Integer;
+    use diesel::sqlite::SqliteConnection;
+
+    #[derive(QueryableByName)]
+    struct Count {
+        #[diesel(sql_type = Integer)]
+        count: i32,
+    }
+
+    /// 创建内存数据库连接池
+    fn memory_pool() -> DieselPool<SqliteConnection> {
+        let config = DataSourceConfig {
+            max_connections: 1,
+            ..DataSourceConfig::new(":memory:")
+        };
+        DieselPool::new(&config).unwrap()
+    }
+
+    /// 测试在阻塞线程池中执行查询和事务回滚
+    #[tokio::test]
+    async fn test_run_and_transaction() {
+        let pool = memory_pool();
+        pool.run(|conn| {
+            diesel::sql_query("CREATE TABLE items (id INTEGER PRIMARY KEY)").execute(conn)
+        })
+        .await
+        .unwrap();
+
+        let result = pool
+            .transaction(|conn| {
+                diesel::sql_query("INSERT INTO items (id) VALUES (1)").execute(conn)?;
+                Err::<(), _>(DieselError::RollbackTransaction)
+            })
+            .await;
+        assert!(result.is_err());
+
+        let count = pool
+            .run(|conn| {
+                diesel::sql_query("SELECT COUNT(*) AS count FROM items").get_result::<Count>(conn)
+            })
+            .await
+            .unwrap();
+        assert_eq!(count.count, 0);
+    }
+
+    /// 测试内存数据库默认执行初始化脚本
+    #[tokio::test]
+    async fn test_initialize() {
+        let dir = tempfile::tempdir().unwrap();
+        let schema = dir.path().join("schema.sql");
+        std::fs::write(&schema, "CREATE TABLE items (id INTEGER PRIMARY KEY);\n").unwrap();
+        std::fs::write(dir.path().join("schema-test.sql"), "INSERT INTO items (id) VALUES (1), (2);").unwrap();
+
+        let pool = memory_pool();
+        let mut config = DataSourceConfig::new(":memory:");
+        config.init.schema = vec![schema.to_string_lossy().to_string()];
+        pool.initialize(&config, "test").unwrap();
+
+        let count = pool
+            .run(|conn| {
+                diesel::sql_query("SELECT COUNT(*) AS count FROM items").get_result::<Count>(conn)
+            })
+            .await
+            .unwrap();
+        assert_eq!(count.count, 2);
+    }
+
+    /// 测试初始化时注册连接池和健康指示器
+    #[tokio::test]
+    async fn test_init() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("application.toml"),
+            "[datasource]\nurl = \":memory:\"\nmax_connections = 1\n",
+        )
+        .unwrap();
+        let config = ConfigurationManager::from_dirs(
+            &[dir.path().to_path_buf()],
+            "test",
+            "RSPRING_DIESEL_INIT_TEST",
+        )
+        .unwrap();
+        let context = ApplicationContext::with_config(config);
+        context.register_singleton(HealthRegistry::new()).await;
+
+        DieselPool::<SqliteConnection>::init(&context).await.unwrap();
+        assert!(context.get::<DieselPool<SqliteConnection>>().await.is_some());
+        let registry = context.get::<HealthRegistry>().await.unwrap();
+        assert_eq!(registry.names(), vec!["default".to_string()]);
+    }
+
+    /// 测试 NotFound 转换为未找到错误
+    #[tokio::test]
+    async fn test_not_found() {
+        let pool = memory_pool();
+        let result = pool
+            .run(|conn| {
+                diesel::sql_query("SELECT 1 AS count WHERE 1 = 0").get_result::<Count>(conn)
+            })
+            .await;
+        assert!(matches!(result, Err(Error::NotFound { .. })));
+    }
+}
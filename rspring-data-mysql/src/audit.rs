@@ -0,0 +1,130 @@
+//! MySQL 审计事件存储模块
+//!
+//! 将审计事件写入审计表，每个事件一行，附加信息以 JSON 保存在 `metadata` 列
+
+use async_trait::async_trait;
+use rspring_core::auditing::{AuditEvent, AuditSink};
+use rspring_core::page::is_valid_property;
+use rspring_core::{Error, Result};
+use sqlx::mysql::MySqlPool;
+use sqlx::Executor;
+
+use crate::datasource::map_sqlx_error;
+
+/// MySQL 审计事件存储
+///
+/// # 示例
+/// ```rust
+/// let sink = MySqlAuditSink::new(pool.clone(), "audit_events")?;
+/// sink.create_table().await?;
+/// rspring_core::auditing::audit_recorder().add_sink(Arc::new(sink));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MySqlAuditSink {
+    /// 连接池
+    pool: MySqlPool,
+    /// 审计表名
+    table: String,
+}
+
+impl MySqlAuditSink {
+    /// 创建审计事件存储
+    ///
+    /// # 错误
+    /// 表名包含非法字符时返回错误
+    pub fn new(pool: MySqlPool, table: impl Into<String>) -> Result<Self> {
+        let table = table.into();
+        if table.is_empty() || !is_valid_property(&table) {
+            return Err(Error::validation(format!("无效的审计表名: {}", table)));
+        }
+        Ok(Self { pool, table })
+    }
+
+    /// 创建审计表（不存在时）
+    ///
+    /// # 错误
+    /// 执行失败时返回错误
+    pub async fn create_table(&self) -> Result<()> {
+        self.pool
+            .execute(create_table_sql(&self.table).as_str())
+            .await
+            .map_err(map_sqlx_error)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AuditSink for MySqlAuditSink {
+    async fn record(&self, event: &AuditEvent) -> Result<()> {
+        let metadata = if event.metadata.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&event.metadata)?)
+        };
+        sqlx::query(&insert_sql(&self.table))
+            .bind(event.timestamp)
+            .bind(&event.actor)
+            .bind(&event.action)
+            .bind(&event.resource)
+            .bind(event.outcome.as_str())
+            .bind(&event.request_id)
+            .bind(metadata)
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+        Ok(())
+    }
+}
+
+/// 生成创建审计表的语句
+///
+/// 索引名不能包含 `.`，带库名的表名以 `_` 连接后作为索引名的一部分
+fn create_table_sql(table: &str) -> String {
+    let index = table.replace('.', "_");
+    format!(
+        "CREATE TABLE IF NOT EXISTS {} (\
+         id BIGINT AUTO_INCREMENT PRIMARY KEY, \
+         occurred_at DATETIME(6) NOT NULL, \
+         actor VARCHAR(255) NULL, \
+         action VARCHAR(128) NOT NULL, \
+         resource VARCHAR(512) NULL, \
+         outcome VARCHAR(16) NOT NULL, \
+         request_id VARCHAR(64) NULL, \
+         metadata JSON NULL, \
+         INDEX idx_{}_occurred_at (occurred_at), \
+         INDEX idx_{}_actor (actor))",
+        table, index, index
+    )
+}
+
+/// 生成写入审计事件的语句
+fn insert_sql(table: &str) -> String {
+    format!(
+        "INSERT INTO {} (occurred_at, actor, action, resource, outcome, request_id, metadata) \
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+        table
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试表名校验和写入语句
+    #[tokio::test]
+    async fn test_audit_sink() {
+        let pool = MySqlPool::connect_lazy("mysql://localhost/test").unwrap();
+        assert!(MySqlAuditSink::new(pool.clone(), "audit_events").is_ok());
+        assert!(MySqlAuditSink::new(pool, "audit; DROP TABLE users").is_err());
+        assert_eq!(
+            insert_sql("audit_events"),
+            "INSERT INTO audit_events (occurred_at, actor, action, resource, outcome, request_id, metadata) \
+             VALUES (?, ?, ?, ?, ?, ?, ?)"
+        );
+
+        let sql = create_table_sql("audit.audit_events");
+        assert!(sql.starts_with("CREATE TABLE IF NOT EXISTS audit.audit_events ("));
+        assert!(sql.contains("INDEX idx_audit_audit_events_occurred_at (occurred_at)"));
+        assert!(sql.contains("INDEX idx_audit_audit_events_actor (actor))"));
+    }
+}
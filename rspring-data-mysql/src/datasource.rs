@@ -0,0 +1,148 @@
+//! 数据源模块
+
+use rspring_core::config::{ConfigurationManager, DataSourceConfig};
+use rspring_core::database::load_init_scripts;
+use rspring_core::startup::startup_recorder;
+use rspring_core::{ApplicationContext, Error, HealthRegistry, Result};
+use sqlx::mysql::{MySqlPool, MySqlPoolOptions};
+use sqlx::Executor;
+
+use crate::health::DataSourceHealthIndicator;
+
+use crate::sql_log::set_sql_log_config;
+
+/// 根据数据源配置创建 MySQL 连接池
+///
+/// 创建时会建立 `min_idle` 个连接并验证连接可用，同时应用 `[datasource.logging]` 配置；
+/// 启用 `metrics` 特性时按 `[datasource.metrics]` 开始记录连接池指标
+///
+/// # 错误
+/// 数据库不可用时返回错误
+pub async fn connect(config: &DataSourceConfig) -> Result<MySqlPool> {
+    set_sql_log_config(config.logging.clone());
+    let pool = pool_options(config)
+        .connect(&config.url)
+        .await
+        .map_err(|e| Error::application(format!("创建 MySQL 连接池失败: {}", e)))?;
+
+    #[cfg(feature = "metrics")]
+    if config.metrics.enabled {
+        crate::metrics::spawn_pool_metrics(
+            &config.metrics.name,
+            pool.clone(),
+            config.metrics.interval,
+            tokio_util::sync::CancellationToken::new(),
+        );
+    }
+
+    tracing::info!("MySQL 连接池已创建，最大连接数: {}", config.max_connections);
+    Ok(pool)
+}
+
+/// 使用配置文件中的 `[datasource]` 章节创建 MySQL 连接池，并按 `[datasource.init]`
+/// 执行当前环境的初始化脚本，耗时记录到启动报告的 `mysql.datasource` 组件
+///
+/// # 错误
+/// 未配置数据源、数据库不可用或初始化脚本执行失败时返回错误
+pub async fn connect_from_config(config: &ConfigurationManager) -> Result<MySqlPool> {
+    let datasource: DataSourceConfig = config.get_section("datasource")?;
+    let pool = startup_recorder()
+        .time_component("mysql.datasource", async {
+            let pool = connect(&datasource).await?;
+            initialize(&pool, &datasource, config.profile()).await?;
+            Ok::<_, Error>(pool)
+        })
+        .await?;
+    Ok(pool)
+}
+
+/// 使用配置文件中的 `[datasource]` 章节创建 MySQL 连接池并执行初始化脚本
+///
+/// 上下文中存在 [`HealthRegistry`] 时同时注册以 `[datasource.metrics]` 的 `name`
+/// 命名的 [`DataSourceHealthIndicator`]
+///
+/// # 错误
+/// 未配置数据源、数据库不可用或初始化脚本执行失败时返回错误
+pub async fn init_datasource(context: &ApplicationContext) -> Result<MySqlPool> {
+    let datasource: DataSourceConfig = context.config_manager().get_section("datasource")?;
+    let pool = connect_from_config(context.config_manager()).await?;
+
+    if let Some(registry) = context.get::<HealthRegistry>().await {
+        registry.register(DataSourceHealthIndicator::new(datasource.metrics.name, pool.clone()));
+    }
+    Ok(pool)
+}
+
+/// 执行 `schema.sql`、`data.sql` 等初始化脚本
+///
+/// MySQL 不是嵌入式数据库，只有 `mode = "always"` 时才会执行
+///
+/// # 错误
+/// 脚本无法读取，或语句执行失败且未开启 `continue_on_error` 时返回错误
+pub async fn initialize(pool: &MySqlPool, config: &DataSourceConfig, profile: &str) -> Result<()> {
+    for script in load_init_scripts(config, profile)? {
+        for statement in &script.statements {
+            if let Err(e) = pool.execute(statement.as_str()).await {
+                let message = format!("执行初始化脚本失败 ({}): {}", script.path.display(), e);
+                if !config.init.continue_on_error {
+                    return Err(Error::application(message));
+                }
+                tracing::warn!("{}", message);
+            }
+        }
+        tracing::info!(
+            "已执行初始化脚本 {}（{} 条语句）",
+            script.path.display(),
+            script.statements.len()
+        );
+    }
+    Ok(())
+}
+
+/// 根据数据源配置生成连接池参数
+fn pool_options(config: &DataSourceConfig) -> MySqlPoolOptions {
+    MySqlPoolOptions::new()
+        .max_connections(config.max_connections)
+        .min_connections(config.min_idle.unwrap_or(0))
+        .acquire_timeout(config.connect_timeout)
+        .idle_timeout(config.idle_timeout)
+        .max_lifetime(config.max_lifetime)
+}
+
+/// 将 sqlx 错误转换为框架错误
+///
+/// `RowNotFound` 转换为 [`Error::NotFound`]，其余转换为内部错误
+pub fn map_sqlx_error(error: sqlx::Error) -> Error {
+    match error {
+        sqlx::Error::RowNotFound => Error::not_found("记录"),
+        other => Error::internal(format!("数据库操作失败: {}", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// 测试连接池参数取自数据源配置
+    #[test]
+    fn test_pool_options() {
+        let config = DataSourceConfig {
+            max_connections: 20,
+            min_idle: Some(5),
+            connect_timeout: Duration::from_secs(5),
+            ..DataSourceConfig::new("mysql://localhost/app")
+        };
+        let options = pool_options(&config);
+        assert_eq!(options.get_max_connections(), 20);
+        assert_eq!(options.get_min_connections(), 5);
+        assert_eq!(options.get_acquire_timeout(), Duration::from_secs(5));
+    }
+
+    /// 测试错误转换
+    #[test]
+    fn test_map_sqlx_error() {
+        assert!(matches!(map_sqlx_error(sqlx::Error::RowNotFound), Error::NotFound { .. }));
+        assert!(matches!(map_sqlx_error(sqlx::Error::PoolTimedOut), Error::Internal { .. }));
+    }
+}
@@ -0,0 +1,89 @@
+//! 数据源健康检查模块
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rspring_core::{Health, HealthIndicator};
+use sqlx::mysql::MySqlPool;
+
+/// 健康检查的默认超时时间
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// MySQL 数据源健康指示器
+///
+/// 执行 `SELECT 1` 检查数据库是否可用，并报告连接池状态
+///
+/// # 示例
+/// ```rust
+/// registry.register(DataSourceHealthIndicator::new("primary", pool.clone()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct DataSourceHealthIndicator {
+    /// 数据源名称
+    name: String,
+    /// 连接池
+    pool: MySqlPool,
+    /// 检查超时时间
+    timeout: Duration,
+}
+
+impl DataSourceHealthIndicator {
+    /// 创建健康指示器
+    pub fn new(name: impl Into<String>, pool: MySqlPool) -> Self {
+        Self {
+            name: name.into(),
+            pool,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// 设置检查超时时间
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+#[async_trait]
+impl HealthIndicator for DataSourceHealthIndicator {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn health(&self) -> Health {
+        let query = sqlx::query("SELECT 1").execute(&self.pool);
+        let health = match tokio::time::timeout(self.timeout, query).await {
+            Ok(Ok(_)) => Health::up(),
+            Ok(Err(e)) => Health::down(e),
+            Err(_) => Health::down(format!("检查超时 ({:?})", self.timeout)),
+        };
+
+        let size = self.pool.size();
+        let idle = self.pool.num_idle() as u32;
+        health
+            .with_detail("database", "MySQL")
+            .with_detail("active", size.saturating_sub(idle))
+            .with_detail("idle", idle)
+            .with_detail("max", self.pool.options().get_max_connections())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rspring_core::HealthStatus;
+
+    /// 测试数据库不可用时报告 DOWN 和连接池状态
+    #[tokio::test]
+    async fn test_health_down() {
+        let pool = MySqlPool::connect_lazy("mysql://root@127.0.0.1:1/app").unwrap();
+        let indicator =
+            DataSourceHealthIndicator::new("primary", pool).timeout(Duration::from_millis(500));
+
+        let health = indicator.health().await;
+        assert_eq!(health.status, HealthStatus::Down);
+        assert!(health.details.contains_key("error"));
+        assert_eq!(health.details["database"], "MySQL");
+        assert_eq!(health.details["max"], 10);
+    }
+}
@@ -1,4 +1,47 @@
-// Placeholder for MySQL functionality
-pub fn init_mysql() {
-    println!("MySQL starter initialized");
-}
\ No newline at end of file
+//! RSpring MySQL 启动器
+//!
+//! 根据 `[datasource]` 配置创建 sqlx MySQL 连接池，并为 `#[derive(Repository)]`
+//! 生成的 CRUD 方法提供运行时支持，同时提供 SQL 日志、慢查询检测、数据源健康指示器、
+//! 声明式事务、事务发件箱、定时任务锁、审计事件存储和连接池指标（`metrics` 特性）
+//!
+//! # 示例
+//! ```rust
+//! let pool = rspring_data_mysql::init_datasource(&context).await?;
+//! context.register_singleton(UserRepository { pool }).await;
+//! ```
+
+// 测试中使用 `#[derive(Repository)]`，生成的代码通过 `::rspring_data_mysql` 引用本 crate
+#[cfg(test)]
+extern crate self as rspring_data_mysql;
+
+pub mod audit;
+pub mod datasource;
+pub mod health;
+pub mod lock;
+pub mod outbox;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod paging;
+pub mod repository;
+pub mod spec;
+pub mod sql_log;
+pub mod transaction;
+
+pub use audit::MySqlAuditSink;
+pub use datasource::*;
+pub use health::*;
+pub use lock::MySqlLockProvider;
+pub use outbox::*;
+#[cfg(feature = "metrics")]
+pub use crate::metrics::*;
+pub use paging::PageQuery;
+pub use rspring_core::page::{Page, PageResult, Sort};
+pub use repository::*;
+pub use spec::{Spec, SpecValue};
+pub use sql_log::{observe_query, set_sql_log_config};
+pub use transaction::{
+    acquire_connection, current_transaction, MySqlConnectionGuard, MySqlTransaction, MySqlTransactionManager,
+};
+
+pub use async_trait::async_trait;
+pub use sqlx;
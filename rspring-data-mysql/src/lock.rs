@@ -0,0 +1,164 @@
+//! MySQL 分布式锁模块
+//!
+//! 基于锁表实现定时任务锁，每个锁一行，`lock_until` 之前由 `locked_by` 持有。
+//! 时间统一使用数据库的 `UTC_TIMESTAMP(6)`，不受各实例时钟偏差影响
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rspring_core::lock::{LockProvider, LockToken};
+use rspring_core::page::is_valid_property;
+use rspring_core::{Error, Result};
+use sqlx::mysql::MySqlPool;
+use sqlx::Executor;
+
+use crate::datasource::map_sqlx_error;
+
+/// MySQL 锁提供者
+///
+/// # 示例
+/// ```rust
+/// let provider = MySqlLockProvider::new(pool.clone(), "scheduler_locks")?;
+/// provider.create_table().await?;
+/// rspring_core::lock::set_lock_provider(Arc::new(provider));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MySqlLockProvider {
+    /// 连接池
+    pool: MySqlPool,
+    /// 锁表名
+    table: String,
+}
+
+impl MySqlLockProvider {
+    /// 创建锁提供者
+    ///
+    /// # 错误
+    /// 表名包含非法字符时返回错误
+    pub fn new(pool: MySqlPool, table: impl Into<String>) -> Result<Self> {
+        let table = table.into();
+        if table.is_empty() || !is_valid_property(&table) {
+            return Err(Error::validation(format!("无效的锁表名: {}", table)));
+        }
+        Ok(Self { pool, table })
+    }
+
+    /// 创建锁表（不存在时）
+    ///
+    /// # 错误
+    /// 执行失败时返回错误
+    pub async fn create_table(&self) -> Result<()> {
+        let sql = format!(
+            "CREATE TABLE IF NOT EXISTS {} (\
+             name VARCHAR(64) NOT NULL PRIMARY KEY, \
+             lock_until DATETIME(6) NOT NULL, \
+             locked_at DATETIME(6) NOT NULL, \
+             locked_by VARCHAR(64) NOT NULL)",
+            self.table
+        );
+        self.pool.execute(sql.as_str()).await.map_err(map_sqlx_error)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl LockProvider for MySqlLockProvider {
+    async fn try_lock(&self, name: &str, lease: Duration) -> Result<Option<LockToken>> {
+        let lock = LockToken::new(name);
+        let lease = lease.as_micros() as u64;
+
+        // 首次使用时插入，否则只在已过期时抢占
+        let inserted = sqlx::query(&insert_sql(&self.table))
+            .bind(name)
+            .bind(lease)
+            .bind(&lock.owner)
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+        if inserted.rows_affected() == 1 {
+            return Ok(Some(lock));
+        }
+
+        let updated = sqlx::query(&acquire_sql(&self.table))
+            .bind(lease)
+            .bind(&lock.owner)
+            .bind(name)
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+        Ok((updated.rows_affected() == 1).then_some(lock))
+    }
+
+    async fn extend(&self, lock: &LockToken, lease: Duration) -> Result<bool> {
+        let updated = sqlx::query(&extend_sql(&self.table))
+            .bind(lease.as_micros() as u64)
+            .bind(&lock.name)
+            .bind(&lock.owner)
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+        Ok(updated.rows_affected() == 1)
+    }
+
+    async fn unlock(&self, lock: &LockToken, keep_for: Duration) -> Result<()> {
+        sqlx::query(&unlock_sql(&self.table))
+            .bind(keep_for.as_micros() as u64)
+            .bind(&lock.name)
+            .bind(&lock.owner)
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+        Ok(())
+    }
+}
+
+/// 生成首次加锁的语句
+fn insert_sql(table: &str) -> String {
+    format!(
+        "INSERT IGNORE INTO {} (name, lock_until, locked_at, locked_by) \
+         VALUES (?, UTC_TIMESTAMP(6) + INTERVAL ? MICROSECOND, UTC_TIMESTAMP(6), ?)",
+        table
+    )
+}
+
+/// 生成抢占过期锁的语句
+fn acquire_sql(table: &str) -> String {
+    format!(
+        "UPDATE {} SET lock_until = UTC_TIMESTAMP(6) + INTERVAL ? MICROSECOND, \
+         locked_at = UTC_TIMESTAMP(6), locked_by = ? \
+         WHERE name = ? AND lock_until <= UTC_TIMESTAMP(6)",
+        table
+    )
+}
+
+/// 生成续期语句
+fn extend_sql(table: &str) -> String {
+    format!(
+        "UPDATE {} SET lock_until = UTC_TIMESTAMP(6) + INTERVAL ? MICROSECOND \
+         WHERE name = ? AND locked_by = ? AND lock_until > UTC_TIMESTAMP(6)",
+        table
+    )
+}
+
+/// 生成释放语句，锁至少保留到加锁后的指定时间
+fn unlock_sql(table: &str) -> String {
+    format!(
+        "UPDATE {} SET lock_until = GREATEST(UTC_TIMESTAMP(6), locked_at + INTERVAL ? MICROSECOND) \
+         WHERE name = ? AND locked_by = ?",
+        table
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试生成的加锁语句只抢占已过期的锁
+    #[test]
+    fn test_lock_sql() {
+        assert!(insert_sql("scheduler_locks").starts_with("INSERT IGNORE INTO scheduler_locks"));
+        assert!(acquire_sql("scheduler_locks").ends_with("WHERE name = ? AND lock_until <= UTC_TIMESTAMP(6)"));
+        assert!(extend_sql("scheduler_locks").contains("AND locked_by = ?"));
+        assert!(unlock_sql("scheduler_locks").contains("GREATEST(UTC_TIMESTAMP(6), locked_at"));
+    }
+}
@@ -0,0 +1,210 @@
+//! 连接池指标模块
+//!
+//! 启用 `metrics` 特性后，通过 `metrics` 门面导出连接池指标，均带有 `pool` 标签：
+//! - `datasource.connections.active` - 使用中的连接数
+//! - `datasource.connections.idle` - 空闲连接数
+//! - `datasource.connections.max` - 最大连接数
+//! - `datasource.connections.wait` - 获取连接的等待时间（秒），每次取出连接时记录
+//! - `datasource.connections.timeouts` - 获取连接超时的次数
+//!
+//! sqlx 连接池不提供取出连接的事件，等待时间和超时次数在
+//! [`acquire_connection`](crate::acquire_connection) 和事务管理器取出连接时记录，
+//! 只记录已通过 [`spawn_pool_metrics`] 开始记录指标的连接池
+//!
+//! [`connect`](crate::connect) 按 `[datasource.metrics]` 配置自动开始记录，
+//! 查询耗时和失败次数由 [`observe_query`](crate::observe_query) 记录
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use rspring_core::database::{
+    CONNECTIONS_ACTIVE_METRIC, CONNECTIONS_IDLE_METRIC, CONNECTIONS_MAX_METRIC,
+    CONNECTIONS_TIMEOUTS_METRIC, CONNECTIONS_WAIT_METRIC,
+};
+use sqlx::mysql::MySqlPool;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+/// 正在记录指标的连接池，键为连接池地址
+static METERED_POOLS: Lazy<RwLock<HashMap<usize, MeteredPool>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// 正在记录指标的连接池信息
+struct MeteredPool {
+    /// 数据源名称
+    name: String,
+    /// 指标任务的取消令牌
+    cancel: CancellationToken,
+}
+
+/// 连接池的标识，同一个连接池的所有克隆共享
+fn pool_key(pool: &MySqlPool) -> usize {
+    pool.options() as *const _ as usize
+}
+
+/// 获取正在记录指标的连接池的数据源名称
+fn metered_name(pool: &MySqlPool) -> Option<String> {
+    let pools = METERED_POOLS.read().unwrap_or_else(|e| e.into_inner());
+    pools
+        .get(&pool_key(pool))
+        .map(|metered| metered.name.clone())
+}
+
+/// 记录一次连接池状态
+pub fn record_pool_metrics(name: &str, pool: &MySqlPool) {
+    let size = pool.size();
+    let idle = pool.num_idle() as u32;
+    let pool_name = name.to_string();
+    ::metrics::gauge!(CONNECTIONS_ACTIVE_METRIC, "pool" => pool_name.clone())
+        .set(size.saturating_sub(idle) as f64);
+    ::metrics::gauge!(CONNECTIONS_IDLE_METRIC, "pool" => pool_name.clone()).set(idle as f64);
+    ::metrics::gauge!(CONNECTIONS_MAX_METRIC, "pool" => pool_name)
+        .set(pool.options().get_max_connections() as f64);
+}
+
+/// 记录一次获取连接的等待时间，超时时增加超时次数
+///
+/// 连接池未通过 [`spawn_pool_metrics`] 开始记录指标时不记录
+pub fn record_acquire_metrics<T>(
+    pool: &MySqlPool,
+    elapsed: Duration,
+    result: &Result<T, sqlx::Error>,
+) {
+    let Some(name) = metered_name(pool) else {
+        return;
+    };
+    ::metrics::histogram!(CONNECTIONS_WAIT_METRIC, "pool" => name.clone())
+        .record(elapsed.as_secs_f64());
+    if matches!(result, Err(sqlx::Error::PoolTimedOut)) {
+        ::metrics::counter!(CONNECTIONS_TIMEOUTS_METRIC, "pool" => name).increment(1);
+    }
+}
+
+/// 启动后台任务，按固定间隔记录连接池状态，并开始记录获取连接的等待时间和超时次数
+///
+/// `cancel` 取消、调用 [`stop_pool_metrics`] 或连接池关闭后任务退出
+///
+/// # 示例
+/// ```rust
+/// let cancel = CancellationToken::new();
+/// spawn_pool_metrics("primary", pool.clone(), Duration::from_secs(10), cancel.clone());
+/// ```
+pub fn spawn_pool_metrics(
+    name: impl Into<String>,
+    pool: MySqlPool,
+    interval: Duration,
+    cancel: CancellationToken,
+) -> JoinHandle<()> {
+    let name = name.into();
+    let key = pool_key(&pool);
+    let previous = METERED_POOLS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(
+            key,
+            MeteredPool {
+                name: name.clone(),
+                cancel: cancel.clone(),
+            },
+        );
+    if let Some(previous) = previous {
+        previous.cancel.cancel();
+    }
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        let closed = pool.close_event();
+        tokio::pin!(closed);
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => record_pool_metrics(&name, &pool),
+                _ = cancel.cancelled() => break,
+                _ = &mut closed => break,
+            }
+        }
+
+        let mut pools = METERED_POOLS.write().unwrap_or_else(|e| e.into_inner());
+        if pools
+            .get(&key)
+            .is_some_and(|metered| metered.cancel.is_cancelled() || pool.is_closed())
+        {
+            pools.remove(&key);
+        }
+    })
+}
+
+/// 停止记录连接池指标
+///
+/// # 示例
+/// ```rust
+/// stop_pool_metrics(&pool);
+/// pool.close().await;
+/// ```
+pub fn stop_pool_metrics(pool: &MySqlPool) {
+    let metered = METERED_POOLS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&pool_key(pool));
+    if let Some(metered) = metered {
+        metered.cancel.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试取消后连接池指标任务退出并停止记录获取连接的指标
+    #[tokio::test]
+    async fn test_spawn_pool_metrics_cancel() {
+        let pool = MySqlPool::connect_lazy("mysql://localhost/test").unwrap();
+        let cancel = CancellationToken::new();
+        let handle = spawn_pool_metrics(
+            "test",
+            pool.clone(),
+            Duration::from_millis(10),
+            cancel.clone(),
+        );
+        assert_eq!(metered_name(&pool).as_deref(), Some("test"));
+
+        cancel.cancel();
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(metered_name(&pool).is_none());
+    }
+
+    /// 测试停止记录和关闭连接池后任务退出
+    #[tokio::test]
+    async fn test_stop_pool_metrics() {
+        let pool = MySqlPool::connect_lazy("mysql://localhost/test").unwrap();
+        let handle = spawn_pool_metrics(
+            "test",
+            pool.clone(),
+            Duration::from_millis(10),
+            CancellationToken::new(),
+        );
+        stop_pool_metrics(&pool);
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(metered_name(&pool).is_none());
+
+        let handle = spawn_pool_metrics(
+            "test",
+            pool.clone(),
+            Duration::from_millis(10),
+            CancellationToken::new(),
+        );
+        pool.close().await;
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(metered_name(&pool).is_none());
+    }
+}
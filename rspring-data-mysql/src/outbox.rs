@@ -0,0 +1,697 @@
+//! 事务发件箱模块
+//!
+//! 在业务事务中将事件写入发件箱表，由后台的 [`OutboxRelay`] 按写入顺序投递给
+//! [`OutboxPublisher`]（如 Kafka、RabbitMQ 的生产者），保证事件与业务数据同时提交：
+//! - 投递成功后标记为已发布，失败时记录错误并在下一轮重试，即至少一次投递
+//! - 每轮通过 `FOR UPDATE SKIP LOCKED` 领取一批事件并记录租约后立即提交，投递期间不持有行锁，
+//!   多个实例同时运行时不会重复领取；实例异常退出后事件在租约到期时被重新领取
+//! - 达到最大投递次数的事件不再重试，并发布死信事件
+//! - 已发布的事件超过保留时间后定期清理
+//!
+//! 消费端需按事件 ID 做幂等处理
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rspring_core::config::properties::Configuration;
+use rspring_core::config::ConfigurationManager;
+use rspring_core::messaging::{publish_dead_letter, DeadLetter};
+use rspring_core::page::is_valid_property;
+use rspring_core::{Error, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sqlx::mysql::{MySql, MySqlPool};
+use sqlx::types::chrono::{DateTime, Utc};
+use sqlx::{Executor, FromRow};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::datasource::map_sqlx_error;
+use crate::sql_log::observe_query;
+use crate::transaction::{acquire_connection, observe_acquire};
+
+/// 发件箱配置
+///
+/// # 示例
+/// ```toml
+/// [outbox]
+/// table = "outbox_events"
+/// poll_interval = "500ms"
+/// batch_size = 100
+/// max_attempts = 10
+/// lease = "1m"
+/// retention = "7d"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct OutboxConfig {
+    /// 发件箱表名
+    ///
+    /// # 默认值
+    /// `"outbox_events"`
+    #[serde(default = "default_table")]
+    pub table: String,
+    /// 轮询间隔，上一轮投递了完整的一批时立即开始下一轮
+    ///
+    /// # 默认值
+    /// `"1s"`
+    #[serde(default = "default_poll_interval", with = "rspring_core::config::duration")]
+    pub poll_interval: Duration,
+    /// 每轮最多投递的事件数
+    ///
+    /// # 默认值
+    /// `100`
+    #[serde(default = "default_batch_size")]
+    pub batch_size: u32,
+    /// 最大投递次数，超过后不再重试，需人工处理
+    ///
+    /// # 默认值
+    /// `10`
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    /// 领取事件的租约时长，应大于投递一批事件的耗时，投递器异常退出后事件在租约到期时被重新领取
+    ///
+    /// # 默认值
+    /// `"1m"`
+    #[serde(default = "default_lease", with = "rspring_core::config::duration")]
+    pub lease: Duration,
+    /// 已发布事件的保留时间
+    ///
+    /// # 默认值
+    /// `"7d"`
+    #[serde(default = "default_retention", with = "rspring_core::config::duration")]
+    pub retention: Duration,
+    /// 清理已发布事件的间隔
+    ///
+    /// # 默认值
+    /// `"1h"`
+    #[serde(default = "default_cleanup_interval", with = "rspring_core::config::duration")]
+    pub cleanup_interval: Duration,
+}
+
+impl Default for OutboxConfig {
+    fn default() -> Self {
+        Self {
+            table: default_table(),
+            poll_interval: default_poll_interval(),
+            batch_size: default_batch_size(),
+            max_attempts: default_max_attempts(),
+            lease: default_lease(),
+            retention: default_retention(),
+            cleanup_interval: default_cleanup_interval(),
+        }
+    }
+}
+
+impl Configuration for OutboxConfig {}
+
+/// 待写入发件箱的消息
+///
+/// # 示例
+/// ```rust
+/// let message = OutboxMessage::new("orders", "OrderCreated", &event)?.with_key(order.id);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutboxMessage {
+    /// 投递目标，如 Kafka 主题或 RabbitMQ 交换机
+    pub destination: String,
+    /// 消息键，如聚合根 ID，用于分区
+    pub key: Option<String>,
+    /// 事件类型
+    pub event_type: String,
+    /// JSON 格式的消息体
+    pub payload: String,
+}
+
+impl OutboxMessage {
+    /// 以 JSON 序列化事件创建消息
+    ///
+    /// # 错误
+    /// 事件无法序列化时返回验证错误
+    pub fn new<T: Serialize + ?Sized>(
+        destination: impl Into<String>,
+        event_type: impl Into<String>,
+        payload: &T,
+    ) -> Result<Self> {
+        let payload = serde_json::to_string(payload)
+            .map_err(|e| Error::validation(format!("发件箱消息序列化失败: {}", e)))?;
+        Ok(Self {
+            destination: destination.into(),
+            key: None,
+            event_type: event_type.into(),
+            payload,
+        })
+    }
+
+    /// 设置消息键
+    pub fn with_key(mut self, key: impl ToString) -> Self {
+        self.key = Some(key.to_string());
+        self
+    }
+}
+
+/// 发件箱中待投递的事件
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct OutboxEvent {
+    /// 事件 ID，按写入顺序递增
+    pub id: i64,
+    /// 投递目标
+    pub destination: String,
+    /// 消息键
+    pub message_key: Option<String>,
+    /// 事件类型
+    pub event_type: String,
+    /// JSON 格式的消息体
+    pub payload: String,
+    /// 写入时间
+    pub created_at: DateTime<Utc>,
+    /// 已投递次数
+    pub attempts: i32,
+}
+
+impl OutboxEvent {
+    /// 将消息体反序列化为指定类型
+    ///
+    /// # 错误
+    /// 消息体与类型不匹配时返回验证错误
+    pub fn payload<T: DeserializeOwned>(&self) -> Result<T> {
+        serde_json::from_str(&self.payload)
+            .map_err(|e| Error::validation(format!("发件箱事件 {} 反序列化失败: {}", self.id, e)))
+    }
+}
+
+/// 发件箱事件发布器特征
+///
+/// 将事件发送到消息中间件，返回 `Ok` 时事件被标记为已发布
+///
+/// # 示例
+/// ```rust
+/// pub struct KafkaOutboxPublisher {
+///     producer: FutureProducer,
+/// }
+///
+/// #[async_trait]
+/// impl OutboxPublisher for KafkaOutboxPublisher {
+///     async fn publish(&self, event: &OutboxEvent) -> Result<()> {
+///         let record = FutureRecord::to(&event.destination)
+///             .key(event.message_key.as_deref().unwrap_or_default())
+///             .payload(&event.payload);
+///         self.producer.send(record, Duration::from_secs(5)).await
+///             .map(|_| ())
+///             .map_err(|(e, _)| Error::application(e.to_string()))
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait OutboxPublisher: Send + Sync {
+    /// 发布一个事件
+    async fn publish(&self, event: &OutboxEvent) -> Result<()>;
+}
+
+/// 发件箱
+///
+/// # 示例
+/// ```rust
+/// let mut tx = pool.begin().await?;
+/// sqlx::query("INSERT INTO orders (id, amount) VALUES (?, ?)")
+///     .bind(order.id)
+///     .bind(order.amount)
+///     .execute(&mut *tx)
+///     .await?;
+/// outbox.append(&mut *tx, &OutboxMessage::new("orders", "OrderCreated", &order)?).await?;
+/// tx.commit().await?;
+///
+/// // 在 #[Transactional] 方法中
+/// outbox.append_current(&OutboxMessage::new("orders", "OrderCreated", &order)?).await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct Outbox {
+    /// 连接池
+    pool: MySqlPool,
+    /// 发件箱配置
+    config: Arc<OutboxConfig>,
+    /// 领取事件时记录的投递者标识
+    owner: Arc<str>,
+}
+
+impl Outbox {
+    /// 创建发件箱
+    ///
+    /// # 错误
+    /// 表名包含非法字符时返回错误
+    pub fn new(pool: MySqlPool, config: OutboxConfig) -> Result<Self> {
+        if config.table.is_empty() || !is_valid_property(&config.table) {
+            return Err(Error::validation(format!("无效的发件箱表名: {}", config.table)));
+        }
+        let host = std::env::var("HOSTNAME")
+            .ok()
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "outbox".to_string());
+        Ok(Self {
+            pool,
+            config: Arc::new(config),
+            owner: format!("{}-{}", host, uuid::Uuid::new_v4().simple()).into(),
+        })
+    }
+
+    /// 使用配置文件中的 `[outbox]` 章节创建，未配置时使用默认值
+    ///
+    /// # 错误
+    /// 配置格式错误或表名无效时返回错误
+    pub fn from_config(pool: MySqlPool, config: &ConfigurationManager) -> Result<Self> {
+        let outbox = if config.contains_key("outbox") {
+            config.get_section("outbox")?
+        } else {
+            OutboxConfig::default()
+        };
+        Self::new(pool, outbox)
+    }
+
+    /// 获取发件箱配置
+    pub fn config(&self) -> &OutboxConfig {
+        &self.config
+    }
+
+    /// 创建发件箱表（不存在时）
+    ///
+    /// 已有的表需要添加 `locked_by VARCHAR(255) NULL` 和 `locked_until DATETIME(6) NULL` 列
+    ///
+    /// # 错误
+    /// 执行失败时返回错误
+    pub async fn create_table(&self) -> Result<()> {
+        self.pool
+            .execute(create_table_sql(&self.config.table).as_str())
+            .await
+            .map_err(map_sqlx_error)?;
+        Ok(())
+    }
+
+    /// 在当前事务中写入消息，返回事件 ID
+    ///
+    /// 应传入业务事务的连接（`&mut *tx`），使事件与业务数据一起提交或回滚
+    ///
+    /// # 错误
+    /// 写入失败时返回错误
+    pub async fn append<'e, E>(&self, executor: E, message: &OutboxMessage) -> Result<i64>
+    where
+        E: Executor<'e, Database = MySql>,
+    {
+        let sql = insert_sql(&self.config.table);
+        let result = observe_query(
+            &sql,
+            || {
+                vec![
+                    format!("{:?}", message.destination),
+                    format!("{:?}", message.key),
+                    format!("{:?}", message.event_type),
+                    format!("{:?}", message.payload),
+                ]
+            },
+            sqlx::query(&sql)
+                .bind(&message.destination)
+                .bind(&message.key)
+                .bind(&message.event_type)
+                .bind(&message.payload)
+                .execute(executor),
+        )
+        .await
+        .map_err(map_sqlx_error)?;
+        Ok(result.last_insert_id() as i64)
+    }
+
+    /// 在 `#[Transactional]` 的当前事务中写入消息，返回事件 ID
+    ///
+    /// 不在事务中时直接写入连接池
+    ///
+    /// # 错误
+    /// 写入失败时返回错误
+    pub async fn append_current(&self, message: &OutboxMessage) -> Result<i64> {
+        let mut conn = acquire_connection(&self.pool).await?;
+        self.append(&mut *conn, message).await
+    }
+
+    /// 投递一批待发布的事件，返回成功投递的数量
+    ///
+    /// 领取事件后在事务外按 ID 顺序投递，遇到失败时停止本轮投递并释放剩余事件，
+    /// 保证同一目标的事件不乱序
+    ///
+    /// # 错误
+    /// 数据库操作失败时返回错误，发布失败只记录在事件上
+    pub async fn relay(&self, publisher: &dyn OutboxPublisher) -> Result<usize> {
+        let table = &self.config.table;
+        let max_attempts = self.config.max_attempts as i32;
+        let events = self.claim().await?;
+
+        let mut published = Vec::with_capacity(events.len());
+        let mut failed = None;
+        for event in &events {
+            match publisher.publish(event).await {
+                Ok(()) => published.push(event.id),
+                Err(e) => {
+                    tracing::warn!(
+                        "发件箱事件 {}（{} -> {}）第 {} 次投递失败: {}",
+                        event.id,
+                        event.event_type,
+                        event.destination,
+                        event.attempts + 1,
+                        e
+                    );
+                    // 达到最大投递次数的事件留在发件箱表中，由死信监听器告警后人工处理
+                    if event.attempts + 1 >= max_attempts {
+                        publish_dead_letter(&DeadLetter::new(
+                            "outbox",
+                            event.destination.as_str(),
+                            event.id.to_string(),
+                            (event.attempts + 1) as u32,
+                            &e,
+                        ));
+                    }
+                    failed = Some((event.id, e.to_string()));
+                    break;
+                }
+            }
+        }
+
+        if !published.is_empty() {
+            self.update_claimed(published_sql(table, published.len()), &published).await?;
+        }
+        if let Some((id, error)) = &failed {
+            sqlx::query(&failed_sql(table))
+                .bind(error)
+                .bind(*id)
+                .bind(&*self.owner)
+                .execute(&self.pool)
+                .await
+                .map_err(map_sqlx_error)?;
+        }
+
+        // 释放本轮未投递的事件，不必等待租约到期
+        let attempted = published.len() + usize::from(failed.is_some());
+        let unsent: Vec<i64> = events[attempted..].iter().map(|event| event.id).collect();
+        if !unsent.is_empty() {
+            self.update_claimed(release_sql(table, unsent.len()), &unsent).await?;
+        }
+
+        Ok(published.len())
+    }
+
+    /// 领取一批待发布的事件并记录租约，领取后立即提交
+    async fn claim(&self) -> Result<Vec<OutboxEvent>> {
+        let table = &self.config.table;
+        let mut tx = observe_acquire(&self.pool, self.pool.begin()).await?;
+
+        let sql = pending_sql(table);
+        let max_attempts = self.config.max_attempts as i32;
+        let batch_size = self.config.batch_size;
+        let events: Vec<OutboxEvent> = observe_query(
+            &sql,
+            || vec![max_attempts.to_string(), batch_size.to_string()],
+            sqlx::query_as(&sql)
+                .bind(max_attempts)
+                .bind(batch_size)
+                .fetch_all(&mut *tx),
+        )
+        .await
+        .map_err(map_sqlx_error)?;
+
+        if !events.is_empty() {
+            let sql = claim_sql(table, events.len());
+            let lease = self.config.lease.as_micros() as u64;
+            let mut query = sqlx::query(&sql).bind(&*self.owner).bind(lease);
+            for event in &events {
+                query = query.bind(event.id);
+            }
+            query.execute(&mut *tx).await.map_err(map_sqlx_error)?;
+        }
+        tx.commit().await.map_err(map_sqlx_error)?;
+
+        Ok(events)
+    }
+
+    /// 更新本投递者领取的事件，语句的第一个参数为投递者标识，其后为事件 ID
+    async fn update_claimed(&self, sql: String, ids: &[i64]) -> Result<()> {
+        let mut query = sqlx::query(&sql).bind(&*self.owner);
+        for id in ids {
+            query = query.bind(id);
+        }
+        query.execute(&self.pool).await.map_err(map_sqlx_error)?;
+        Ok(())
+    }
+
+    /// 删除超过保留时间的已发布事件，返回删除的数量
+    ///
+    /// # 错误
+    /// 执行失败时返回错误
+    pub async fn cleanup(&self) -> Result<u64> {
+        let sql = cleanup_sql(&self.config.table);
+        let retention = self.config.retention.as_secs();
+        let result = observe_query(
+            &sql,
+            || vec![retention.to_string()],
+            sqlx::query(&sql).bind(retention).execute(&self.pool),
+        )
+        .await
+        .map_err(map_sqlx_error)?;
+        Ok(result.rows_affected())
+    }
+}
+
+/// 发件箱投递器
+///
+/// # 示例
+/// ```rust
+/// let relay = OutboxRelay::new(outbox.clone(), KafkaOutboxPublisher::new(producer)).start();
+///
+/// // 应用退出时
+/// relay.stop().await;
+/// ```
+pub struct OutboxRelay {
+    /// 发件箱
+    outbox: Outbox,
+    /// 事件发布器
+    publisher: Arc<dyn OutboxPublisher>,
+}
+
+impl OutboxRelay {
+    /// 创建投递器
+    pub fn new(outbox: Outbox, publisher: impl OutboxPublisher + 'static) -> Self {
+        Self {
+            outbox,
+            publisher: Arc::new(publisher),
+        }
+    }
+
+    /// 启动后台投递和清理任务
+    pub fn start(self) -> RunningOutboxRelay {
+        let token = CancellationToken::new();
+        let task = tokio::spawn(run(self.outbox, self.publisher, token.clone()));
+        RunningOutboxRelay { token, task }
+    }
+}
+
+/// 运行中的发件箱投递器
+#[derive(Debug)]
+pub struct RunningOutboxRelay {
+    /// 停止信号
+    token: CancellationToken,
+    /// 后台任务
+    task: JoinHandle<()>,
+}
+
+impl RunningOutboxRelay {
+    /// 停止投递，等待当前一轮投递完成
+    pub async fn stop(self) {
+        self.token.cancel();
+        let _ = self.task.await;
+    }
+}
+
+/// 投递循环
+async fn run(outbox: Outbox, publisher: Arc<dyn OutboxPublisher>, token: CancellationToken) {
+    let config = outbox.config().clone();
+    let mut cleanup = tokio::time::interval(config.cleanup_interval);
+    tracing::info!("发件箱投递器已启动，表: {}", config.table);
+
+    loop {
+        let wait = match outbox.relay(&*publisher).await {
+            Ok(count) if count >= config.batch_size as usize => Duration::ZERO,
+            Ok(_) => config.poll_interval,
+            Err(e) => {
+                tracing::error!("投递发件箱事件失败: {}", e);
+                config.poll_interval
+            }
+        };
+
+        tokio::select! {
+            _ = token.cancelled() => break,
+            _ = cleanup.tick() => match outbox.cleanup().await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("已清理 {} 个已发布的发件箱事件", count),
+                Err(e) => tracing::warn!("清理发件箱事件失败: {}", e),
+            },
+            _ = tokio::time::sleep(wait) => {}
+        }
+    }
+    tracing::info!("发件箱投递器已停止");
+}
+
+/// 生成建表语句
+pub fn create_table_sql(table: &str) -> String {
+    format!(
+        "CREATE TABLE IF NOT EXISTS {} (\
+         id BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY, \
+         destination VARCHAR(255) NOT NULL, \
+         message_key VARCHAR(255) NULL, \
+         event_type VARCHAR(255) NOT NULL, \
+         payload LONGTEXT NOT NULL, \
+         created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6), \
+         published_at DATETIME(6) NULL, \
+         attempts INT NOT NULL DEFAULT 0, \
+         last_error TEXT NULL, \
+         locked_by VARCHAR(255) NULL, \
+         locked_until DATETIME(6) NULL, \
+         INDEX idx_{}_pending (published_at, id))",
+        table, table
+    )
+}
+
+/// 生成写入语句
+fn insert_sql(table: &str) -> String {
+    format!(
+        "INSERT INTO {} (destination, message_key, event_type, payload) VALUES (?, ?, ?, ?)",
+        table
+    )
+}
+
+/// 生成查询待发布且未被领取（或租约已到期）事件的语句
+fn pending_sql(table: &str) -> String {
+    format!(
+        "SELECT id, destination, message_key, event_type, payload, created_at, attempts FROM {} \
+         WHERE published_at IS NULL AND attempts < ? \
+         AND (locked_until IS NULL OR locked_until < UTC_TIMESTAMP(6)) \
+         ORDER BY id LIMIT ? FOR UPDATE SKIP LOCKED",
+        table
+    )
+}
+
+/// 生成记录领取者和租约到期时间的语句
+fn claim_sql(table: &str, count: usize) -> String {
+    format!(
+        "UPDATE {} SET locked_by = ?, locked_until = UTC_TIMESTAMP(6) + INTERVAL ? MICROSECOND \
+         WHERE id IN ({})",
+        table,
+        placeholders(count)
+    )
+}
+
+/// 生成标记已发布的语句，只更新本投递者仍持有租约的事件
+fn published_sql(table: &str, count: usize) -> String {
+    format!(
+        "UPDATE {} SET published_at = UTC_TIMESTAMP(6), attempts = attempts + 1, last_error = NULL, \
+         locked_by = NULL, locked_until = NULL WHERE locked_by = ? AND id IN ({})",
+        table,
+        placeholders(count)
+    )
+}
+
+/// 生成记录投递失败并释放租约的语句
+fn failed_sql(table: &str) -> String {
+    format!(
+        "UPDATE {} SET attempts = attempts + 1, last_error = ?, locked_by = NULL, locked_until = NULL \
+         WHERE id = ? AND locked_by = ?",
+        table
+    )
+}
+
+/// 生成释放未投递事件租约的语句
+fn release_sql(table: &str, count: usize) -> String {
+    format!(
+        "UPDATE {} SET locked_by = NULL, locked_until = NULL WHERE locked_by = ? AND id IN ({})",
+        table,
+        placeholders(count)
+    )
+}
+
+/// 生成 `IN` 子句的占位符
+fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
+
+/// 生成清理已发布事件的语句
+fn cleanup_sql(table: &str) -> String {
+    format!(
+        "DELETE FROM {} WHERE published_at IS NOT NULL \
+         AND published_at < UTC_TIMESTAMP(6) - INTERVAL ? SECOND",
+        table
+    )
+}
+
+// 默认值函数
+
+fn default_table() -> String {
+    "outbox_events".to_string()
+}
+
+fn default_poll_interval() -> Duration {
+    Duration::from_secs(1)
+}
+
+fn default_batch_size() -> u32 {
+    100
+}
+
+fn default_max_attempts() -> u32 {
+    10
+}
+
+fn default_lease() -> Duration {
+    Duration::from_secs(60)
+}
+
+fn default_retention() -> Duration {
+    Duration::from_secs(7 * 24 * 60 * 60)
+}
+
+fn default_cleanup_interval() -> Duration {
+    Duration::from_secs(60 * 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试配置默认值和生成的语句
+    #[test]
+    fn test_outbox_sql() {
+        let config: OutboxConfig =
+            serde_json::from_str(r#"{ "table": "order_outbox", "retention": "1d" }"#).unwrap();
+        assert_eq!(config.retention, Duration::from_secs(86400));
+        assert_eq!(config.batch_size, 100);
+
+        assert!(pending_sql(&config.table).starts_with(
+            "SELECT id, destination, message_key, event_type, payload, created_at, attempts FROM order_outbox"
+        ));
+        assert!(pending_sql(&config.table).ends_with("FOR UPDATE SKIP LOCKED"));
+        assert_eq!(config.lease, Duration::from_secs(60));
+        assert_eq!(
+            claim_sql("order_outbox", 2),
+            "UPDATE order_outbox SET locked_by = ?, locked_until = UTC_TIMESTAMP(6) + INTERVAL ? MICROSECOND \
+             WHERE id IN (?, ?)"
+        );
+        assert_eq!(
+            published_sql("order_outbox", 3),
+            "UPDATE order_outbox SET published_at = UTC_TIMESTAMP(6), attempts = attempts + 1, \
+             last_error = NULL, locked_by = NULL, locked_until = NULL WHERE locked_by = ? AND id IN (?, ?, ?)"
+        );
+        assert_eq!(
+            release_sql("order_outbox", 1),
+            "UPDATE order_outbox SET locked_by = NULL, locked_until = NULL WHERE locked_by = ? AND id IN (?)"
+        );
+
+        let message = OutboxMessage::new("orders", "OrderCreated", &serde_json::json!({ "id": 1 }))
+            .unwrap()
+            .with_key(1);
+        assert_eq!(message.payload, r#"{"id":1}"#);
+        assert_eq!(message.key.as_deref(), Some("1"));
+    }
+}
@@ -0,0 +1,149 @@
+//! 分页查询模块
+//!
+//! 根据 [`Page`] 和 [`Sort`] 为查询语句追加 `ORDER BY`、`LIMIT`/`OFFSET`，
+//! 同时生成 `COUNT` 查询统计总数，返回 [`PageResult`]
+
+use rspring_core::page::{is_valid_property, Page, PageResult, Sort};
+use rspring_core::{Error, Result};
+use sqlx::mysql::{MySql, MySqlArguments, MySqlPool, MySqlRow};
+use sqlx::{Arguments, Encode, FromRow, Type};
+
+use crate::datasource::map_sqlx_error;
+use crate::sql_log::observe_query;
+use crate::transaction::acquire_connection;
+
+/// 分页查询
+///
+/// 查询语句不能包含 `ORDER BY` 和 `LIMIT`，由分页参数生成
+///
+/// # 示例
+/// ```rust
+/// let users: PageResult<User> = PageQuery::new("SELECT * FROM users WHERE status = ?")
+///     .bind(status)
+///     .fetch(&pool, pageable.page, &pageable.sort)
+///     .await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct PageQuery<'q> {
+    /// 查询语句
+    sql: &'q str,
+    /// 查询参数，数据查询和统计查询共用
+    arguments: MySqlArguments,
+}
+
+impl<'q> PageQuery<'q> {
+    /// 创建分页查询
+    pub fn new(sql: &'q str) -> Self {
+        Self {
+            sql,
+            arguments: MySqlArguments::default(),
+        }
+    }
+
+    /// 绑定查询参数
+    pub fn bind<T>(mut self, value: T) -> Self
+    where
+        T: 'q + Send + Encode<'q, MySql> + Type<MySql>,
+    {
+        self.arguments.add(value);
+        self
+    }
+
+    /// 执行分页查询和统计查询
+    ///
+    /// 在 `#[Transactional]` 事务中时使用事务的连接
+    ///
+    /// # 错误
+    /// 排序字段包含非法字符或查询失败时返回错误
+    pub async fn fetch<T>(self, pool: &MySqlPool, page: Page, sort: &Sort) -> Result<PageResult<T>>
+    where
+        T: for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+    {
+        let mut conn = acquire_connection(pool).await?;
+        let count_sql = count_sql(self.sql);
+        let total: i64 = observe_query(
+            &count_sql,
+            Vec::new,
+            sqlx::query_scalar_with(&count_sql, self.arguments.clone()).fetch_one(&mut *conn),
+        )
+        .await
+        .map_err(map_sqlx_error)?;
+
+        let total = total.max(0) as u64;
+        if total == 0 || page.offset() >= total {
+            return Ok(PageResult::new(Vec::new(), page.page, page.size, total));
+        }
+
+        let page_sql = page_sql(self.sql, page, sort)?;
+        let content = observe_query(
+            &page_sql,
+            Vec::new,
+            sqlx::query_as_with::<_, T, _>(&page_sql, self.arguments).fetch_all(&mut *conn),
+        )
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(PageResult::new(content, page.page, page.size, total))
+    }
+}
+
+/// 生成统计总数的查询语句
+pub fn count_sql(sql: &str) -> String {
+    format!("SELECT COUNT(*) FROM ({}) AS page_count", sql.trim())
+}
+
+/// 生成带排序和分页的查询语句
+///
+/// # 错误
+/// 排序字段包含非法字符时返回错误
+pub fn page_sql(sql: &str, page: Page, sort: &Sort) -> Result<String> {
+    if let Some(order) = sort
+        .orders
+        .iter()
+        .find(|order| !is_valid_property(&order.property))
+    {
+        return Err(Error::validation(format!("无效的排序字段: {}", order.property)));
+    }
+
+    let mut page_sql = sql.trim().to_string();
+    if let Some(order_by) = sort.to_sql() {
+        page_sql.push_str(" ORDER BY ");
+        page_sql.push_str(&order_by);
+    }
+    page_sql.push_str(&format!(" LIMIT {} OFFSET {}", page.size, page.offset()));
+    Ok(page_sql)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rspring_core::page::Order;
+
+    /// 测试生成分页和统计查询语句
+    #[test]
+    fn test_page_sql() {
+        let sql = "SELECT * FROM users WHERE status = ?";
+        let sort = Sort::by(vec![Order::desc("created_at"), Order::asc("id")]);
+
+        assert_eq!(
+            page_sql(sql, Page::new(2, 10), &sort).unwrap(),
+            "SELECT * FROM users WHERE status = ? ORDER BY created_at DESC, id ASC LIMIT 10 OFFSET 20"
+        );
+        assert_eq!(
+            page_sql(sql, Page::new(0, 20), &Sort::unsorted()).unwrap(),
+            "SELECT * FROM users WHERE status = ? LIMIT 20 OFFSET 0"
+        );
+        assert_eq!(
+            count_sql(sql),
+            "SELECT COUNT(*) FROM (SELECT * FROM users WHERE status = ?) AS page_count"
+        );
+
+        let sort = Sort::by(vec![Order::asc("id; DROP TABLE users")]);
+        assert!(page_sql(sql, Page::default(), &sort).is_err());
+
+        assert_eq!(
+            page_sql(sql, Page::new(u64::MAX, 20), &Sort::unsorted()).unwrap(),
+            format!("SELECT * FROM users WHERE status = ? LIMIT 20 OFFSET {}", u64::MAX)
+        );
+    }
+}
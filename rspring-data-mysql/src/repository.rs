@@ -0,0 +1,100 @@
+//! 仓储模块
+//!
+//! 定义通用的 CRUD 仓储特征、软删除仓储特征和动态条件查询特征，由 `#[derive(Repository)]` 根据实体和表名自动实现
+
+use async_trait::async_trait;
+use rspring_core::page::{Page, PageResult, Sort};
+use rspring_core::Result;
+
+use crate::spec::Spec;
+
+/// CRUD 仓储特征
+///
+/// # 示例
+/// ```rust
+/// #[derive(Repository)]
+/// #[repository(entity = User, table = "users", columns = "name, email")]
+/// pub struct UserRepository {
+///     pool: MySqlPool,
+/// }
+///
+/// let user = repository.find_by_id(1).await?;
+/// ```
+#[async_trait]
+pub trait CrudRepository: Send + Sync {
+    /// 实体类型
+    type Entity: Send + Sync;
+    /// 主键类型
+    type Id: Send + Sync;
+
+    /// 按主键查询
+    async fn find_by_id(&self, id: Self::Id) -> Result<Option<Self::Entity>>;
+
+    /// 查询全部记录
+    async fn find_all(&self) -> Result<Vec<Self::Entity>>;
+
+    /// 分页查询，排序和分页条件追加在 `find_all` 的查询语句之后
+    async fn find_page(&self, page: Page, sort: &Sort) -> Result<PageResult<Self::Entity>>;
+
+    /// 插入实体，返回自增主键
+    async fn save(&self, entity: &Self::Entity) -> Result<u64>;
+
+    /// 按主键更新实体，返回是否有记录被更新
+    async fn update(&self, entity: &Self::Entity) -> Result<bool>;
+
+    /// 按主键删除，返回是否有记录被删除
+    async fn delete_by_id(&self, id: Self::Id) -> Result<bool>;
+}
+
+/// 软删除仓储特征
+///
+/// 由 `#[repository(..., soft_delete)]` 自动实现，用于绕过软删除过滤
+///
+/// # 示例
+/// ```rust
+/// let article = repository.find_by_id_including_deleted(1).await?;
+/// if article.is_some_and(|article| article.deleted_at.is_some()) {
+///     repository.restore_by_id(1).await?;
+/// }
+/// ```
+#[async_trait]
+pub trait SoftDeleteRepository: CrudRepository {
+    /// 按主键查询，包含已删除的记录
+    async fn find_by_id_including_deleted(&self, id: Self::Id) -> Result<Option<Self::Entity>>;
+
+    /// 查询全部记录，包含已删除的记录
+    async fn find_all_including_deleted(&self) -> Result<Vec<Self::Entity>>;
+
+    /// 恢复已删除的记录，返回是否有记录被恢复
+    async fn restore_by_id(&self, id: Self::Id) -> Result<bool>;
+
+    /// 按主键物理删除，返回是否有记录被删除
+    async fn hard_delete_by_id(&self, id: Self::Id) -> Result<bool>;
+}
+
+/// 动态条件查询仓储特征
+///
+/// 由 `#[derive(Repository)]` 自动实现，条件作用于 `find_all` 的查询语句，
+/// 因此同样遵循软删除过滤和覆盖的 SQL
+///
+/// # 示例
+/// ```rust
+/// let spec = Spec::eq("status", 1).and(keyword.map(|keyword| Spec::contains("name", keyword)));
+/// let users = repository.find_page_by_spec(&spec, pageable.page, &pageable.sort).await?;
+/// ```
+#[async_trait]
+pub trait SpecRepository: CrudRepository {
+    /// 查询满足条件的全部记录
+    async fn find_all_by_spec(&self, spec: &Spec) -> Result<Vec<Self::Entity>>;
+
+    /// 分页查询满足条件的记录
+    async fn find_page_by_spec(
+        &self,
+        spec: &Spec,
+        page: Page,
+        sort: &Sort,
+    ) -> Result<PageResult<Self::Entity>>;
+
+    /// 统计满足条件的记录数
+    async fn count_by_spec(&self, spec: &Spec) -> Result<u64>;
+}
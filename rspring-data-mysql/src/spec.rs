@@ -0,0 +1,377 @@
+//! 动态查询条件模块
+//!
+//! 以 [`Spec`] 组合查询条件，字段名经过校验、取值通过参数绑定，
+//! 适合管理后台按可选过滤条件查询的场景，无需手工拼接 SQL
+
+use std::fmt::Debug;
+use std::ops::Not;
+use std::sync::Arc;
+
+use rspring_core::page::{is_valid_property, Page, PageResult, Sort};
+use rspring_core::{Error, Result};
+use sqlx::mysql::{MySql, MySqlArguments, MySqlPool, MySqlRow};
+use sqlx::{Arguments, Encode, FromRow, Type};
+
+use crate::datasource::map_sqlx_error;
+use crate::paging::{count_sql, page_sql};
+use crate::sql_log::observe_query;
+use crate::transaction::acquire_connection;
+
+/// 参数绑定函数
+type Binder = Arc<dyn Fn(&mut MySqlArguments) + Send + Sync>;
+
+/// 查询条件
+///
+/// # 示例
+/// ```rust
+/// let spec = Spec::eq("status", status)
+///     .and(name.map(|name| Spec::contains("name", name)))
+///     .and(Spec::ge("created_at", since).or(Spec::is_null("created_at")));
+///
+/// let users = repository.find_page_by_spec(&spec, page, &sort).await?;
+/// ```
+#[derive(Clone, Default)]
+pub struct Spec {
+    /// 条件子句，为空时匹配所有记录
+    sql: String,
+    /// 按占位符顺序排列的参数
+    binders: Vec<Binder>,
+    /// 用于日志的参数值
+    params: Vec<String>,
+    /// 非法的字段名
+    invalid: Option<String>,
+}
+
+impl Spec {
+    /// 匹配所有记录的空条件
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// `field = value`
+    pub fn eq<T: SpecValue>(field: &str, value: T) -> Self {
+        Self::compare(field, "=", value)
+    }
+
+    /// `field <> value`
+    pub fn ne<T: SpecValue>(field: &str, value: T) -> Self {
+        Self::compare(field, "<>", value)
+    }
+
+    /// `field > value`
+    pub fn gt<T: SpecValue>(field: &str, value: T) -> Self {
+        Self::compare(field, ">", value)
+    }
+
+    /// `field >= value`
+    pub fn ge<T: SpecValue>(field: &str, value: T) -> Self {
+        Self::compare(field, ">=", value)
+    }
+
+    /// `field < value`
+    pub fn lt<T: SpecValue>(field: &str, value: T) -> Self {
+        Self::compare(field, "<", value)
+    }
+
+    /// `field <= value`
+    pub fn le<T: SpecValue>(field: &str, value: T) -> Self {
+        Self::compare(field, "<=", value)
+    }
+
+    /// `field LIKE pattern`，通配符由调用方提供
+    pub fn like(field: &str, pattern: impl Into<String>) -> Self {
+        Self::compare(field, "LIKE", pattern.into())
+    }
+
+    /// `field LIKE '%value%'`，`value` 中的通配符会被转义
+    pub fn contains(field: &str, value: &str) -> Self {
+        Self::like(field, format!("%{}%", escape_like(value)))
+    }
+
+    /// `field LIKE 'value%'`，`value` 中的通配符会被转义
+    pub fn starts_with(field: &str, value: &str) -> Self {
+        Self::like(field, format!("{}%", escape_like(value)))
+    }
+
+    /// `field IN (...)`，列表为空时不匹配任何记录
+    pub fn in_list<T: SpecValue>(field: &str, values: impl IntoIterator<Item = T>) -> Self {
+        let mut spec = Self::field(field);
+        let values: Vec<T> = values.into_iter().collect();
+        if values.is_empty() {
+            spec.sql = "1 = 0".to_string();
+            return spec;
+        }
+
+        spec.sql = format!("{} IN ({})", field, vec!["?"; values.len()].join(", "));
+        for value in values {
+            spec.push(value);
+        }
+        spec
+    }
+
+    /// `field BETWEEN low AND high`
+    pub fn between<T: SpecValue>(field: &str, low: T, high: T) -> Self {
+        let mut spec = Self::field(field);
+        spec.sql = format!("{} BETWEEN ? AND ?", field);
+        spec.push(low);
+        spec.push(high);
+        spec
+    }
+
+    /// `field IS NULL`
+    pub fn is_null(field: &str) -> Self {
+        let mut spec = Self::field(field);
+        spec.sql = format!("{} IS NULL", field);
+        spec
+    }
+
+    /// `field IS NOT NULL`
+    pub fn is_not_null(field: &str) -> Self {
+        let mut spec = Self::field(field);
+        spec.sql = format!("{} IS NOT NULL", field);
+        spec
+    }
+
+    /// 与另一个条件同时成立，`None` 时保持不变
+    pub fn and(self, other: impl Into<Option<Spec>>) -> Self {
+        self.combine("AND", other.into())
+    }
+
+    /// 与另一个条件任一成立，`None` 时保持不变
+    pub fn or(self, other: impl Into<Option<Spec>>) -> Self {
+        self.combine("OR", other.into())
+    }
+
+    /// 是否为匹配所有记录的空条件
+    pub fn is_empty(&self) -> bool {
+        self.sql.is_empty()
+    }
+
+    /// 条件子句，空条件返回 `None`
+    pub fn to_sql(&self) -> Option<&str> {
+        (!self.sql.is_empty()).then_some(self.sql.as_str())
+    }
+
+    /// 在基础查询上应用条件
+    ///
+    /// 基础查询作为派生表，因此可以包含自己的 `WHERE` 子句
+    ///
+    /// # 错误
+    /// 条件中包含非法字段名时返回错误
+    pub fn apply(&self, base_sql: &str) -> Result<String> {
+        if let Some(field) = &self.invalid {
+            return Err(Error::validation(format!("无效的查询字段: {}", field)));
+        }
+
+        Ok(match self.to_sql() {
+            Some(condition) => format!("SELECT * FROM ({}) AS spec WHERE {}", base_sql.trim(), condition),
+            None => base_sql.trim().to_string(),
+        })
+    }
+
+    /// 生成绑定参数
+    pub fn arguments(&self) -> MySqlArguments {
+        let mut arguments = MySqlArguments::default();
+        for binder in &self.binders {
+            binder(&mut arguments);
+        }
+        arguments
+    }
+
+    /// 查询满足条件的全部记录
+    ///
+    /// 在 `#[Transactional]` 事务中时使用事务的连接，下同
+    ///
+    /// # 错误
+    /// 条件中包含非法字段名或查询失败时返回错误
+    pub async fn fetch_all<T>(&self, pool: &MySqlPool, base_sql: &str) -> Result<Vec<T>>
+    where
+        T: for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+    {
+        let sql = self.apply(base_sql)?;
+        let mut conn = acquire_connection(pool).await?;
+        observe_query(
+            &sql,
+            || self.params.clone(),
+            sqlx::query_as_with::<_, T, _>(&sql, self.arguments()).fetch_all(&mut *conn),
+        )
+        .await
+        .map_err(map_sqlx_error)
+    }
+
+    /// 统计满足条件的记录数
+    ///
+    /// # 错误
+    /// 条件中包含非法字段名或查询失败时返回错误
+    pub async fn count(&self, pool: &MySqlPool, base_sql: &str) -> Result<u64> {
+        let sql = count_sql(&self.apply(base_sql)?);
+        let mut conn = acquire_connection(pool).await?;
+        let total: i64 = observe_query(
+            &sql,
+            || self.params.clone(),
+            sqlx::query_scalar_with(&sql, self.arguments()).fetch_one(&mut *conn),
+        )
+        .await
+        .map_err(map_sqlx_error)?;
+        Ok(total.max(0) as u64)
+    }
+
+    /// 分页查询满足条件的记录
+    ///
+    /// # 错误
+    /// 条件或排序中包含非法字段名，或查询失败时返回错误
+    pub async fn fetch_page<T>(
+        &self,
+        pool: &MySqlPool,
+        base_sql: &str,
+        page: Page,
+        sort: &Sort,
+    ) -> Result<PageResult<T>>
+    where
+        T: for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+    {
+        let total = self.count(pool, base_sql).await?;
+        if total == 0 || page.offset() >= total {
+            return Ok(PageResult::new(Vec::new(), page.page, page.size, total));
+        }
+
+        let sql = page_sql(&self.apply(base_sql)?, page, sort)?;
+        let mut conn = acquire_connection(pool).await?;
+        let content = observe_query(
+            &sql,
+            || self.params.clone(),
+            sqlx::query_as_with::<_, T, _>(&sql, self.arguments()).fetch_all(&mut *conn),
+        )
+        .await
+        .map_err(map_sqlx_error)?;
+        Ok(PageResult::new(content, page.page, page.size, total))
+    }
+
+    /// 只含字段校验结果的条件
+    fn field(field: &str) -> Self {
+        Self {
+            invalid: (field.is_empty() || !is_valid_property(field)).then(|| field.to_string()),
+            ..Self::default()
+        }
+    }
+
+    /// 比较条件
+    fn compare<T: SpecValue>(field: &str, operator: &str, value: T) -> Self {
+        let mut spec = Self::field(field);
+        spec.sql = format!("{} {} ?", field, operator);
+        spec.push(value);
+        spec
+    }
+
+    /// 追加一个参数
+    fn push<T: SpecValue>(&mut self, value: T) {
+        self.params.push(format!("{:?}", value));
+        self.binders
+            .push(Arc::new(move |arguments: &mut MySqlArguments| arguments.add(value.clone())));
+    }
+
+    /// 以逻辑运算符组合两个条件
+    fn combine(mut self, operator: &str, other: Option<Spec>) -> Self {
+        let Some(other) = other else {
+            return self;
+        };
+        if other.is_empty() && other.invalid.is_none() {
+            return self;
+        }
+        if self.is_empty() && self.invalid.is_none() {
+            return other;
+        }
+
+        self.sql = format!("({}) {} ({})", self.sql, operator, other.sql);
+        self.binders.extend(other.binders);
+        self.params.extend(other.params);
+        self.invalid = self.invalid.or(other.invalid);
+        self
+    }
+}
+
+/// 取反，如 `!Spec::eq("status", 0)`，空条件取反后仍匹配所有记录
+impl Not for Spec {
+    type Output = Spec;
+
+    fn not(mut self) -> Self {
+        if !self.sql.is_empty() {
+            self.sql = format!("NOT ({})", self.sql);
+        }
+        self
+    }
+}
+
+impl Debug for Spec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Spec")
+            .field("sql", &self.sql)
+            .field("params", &self.params)
+            .finish()
+    }
+}
+
+/// 可作为查询条件取值的类型
+pub trait SpecValue:
+    for<'q> Encode<'q, MySql> + Type<MySql> + Debug + Clone + Send + Sync + 'static
+{
+}
+
+impl<T> SpecValue for T where
+    T: for<'q> Encode<'q, MySql> + Type<MySql> + Debug + Clone + Send + Sync + 'static
+{
+}
+
+/// 转义 `LIKE` 通配符
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试条件组合和参数顺序
+    #[test]
+    fn test_spec_sql() {
+        let name: Option<&str> = Some("a_b");
+        let spec = Spec::eq("status", 1)
+            .and(name.map(|name| Spec::contains("name", name)))
+            .and(Spec::in_list("role", ["admin", "user"]).or(Spec::is_null("role")));
+
+        assert_eq!(
+            spec.to_sql(),
+            Some("((status = ?) AND (name LIKE ?)) AND ((role IN (?, ?)) OR (role IS NULL))")
+        );
+        assert_eq!(spec.params, vec!["1", "\"%a\\\\_b%\"", "\"admin\"", "\"user\""]);
+        assert_eq!(spec.binders.len(), 4);
+
+        assert_eq!(
+            spec.apply("SELECT * FROM users WHERE deleted_at IS NULL").unwrap(),
+            format!(
+                "SELECT * FROM (SELECT * FROM users WHERE deleted_at IS NULL) AS spec WHERE {}",
+                spec.to_sql().unwrap()
+            )
+        );
+    }
+
+    /// 测试空条件和非法字段
+    #[test]
+    fn test_spec_edge_cases() {
+        let spec = !Spec::all().and(None).or(Spec::all());
+        assert!(spec.is_empty());
+        assert_eq!(spec.apply("SELECT * FROM users").unwrap(), "SELECT * FROM users");
+
+        let spec = Spec::all().and(Spec::between("age", 18, 30));
+        assert_eq!(spec.to_sql(), Some("age BETWEEN ? AND ?"));
+        assert_eq!(Spec::in_list::<i64>("id", []).to_sql(), Some("1 = 0"));
+        assert_eq!((!Spec::eq("status", 0)).to_sql(), Some("NOT (status = ?)"));
+
+        let spec = Spec::eq("id", 1).and(Spec::eq("id = 1 OR 1", 1));
+        assert!(spec.apply("SELECT * FROM users").is_err());
+        assert!(Spec::is_null("").apply("SELECT * FROM users").is_err());
+    }
+}
@@ -0,0 +1,202 @@
+//! SQL 日志模块
+//!
+//! 按 `[datasource.logging]` 配置记录语句、参数和耗时，超过阈值的慢查询输出警告，
+//! 启用 `metrics` 特性时同时记录查询耗时、失败次数和慢查询次数。`#[derive(Repository)]` 生成的方法
+//! 和 [`PageQuery`](crate::PageQuery) 均通过 [`observe_query`] 执行
+
+use std::fmt::{self, Debug, Display};
+use std::future::Future;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use rspring_core::config::SqlLogConfig;
+
+/// 日志目标
+pub const SQL_LOG_TARGET: &str = "rspring::sql";
+
+#[cfg(feature = "metrics")]
+pub use rspring_core::database::{QUERY_DURATION_METRIC, QUERY_ERRORS_METRIC, SLOW_QUERY_METRIC};
+
+/// 全局 SQL 日志配置，未设置时使用默认配置
+static SQL_LOG_CONFIG: RwLock<Option<SqlLogConfig>> = RwLock::new(None);
+
+/// 设置全局 SQL 日志配置
+///
+/// [`connect`](crate::connect) 创建连接池时会使用 `[datasource.logging]` 调用此函数
+pub fn set_sql_log_config(config: SqlLogConfig) {
+    *SQL_LOG_CONFIG.write().expect("SQL 日志配置锁已损坏") = Some(config);
+}
+
+/// 获取全局 SQL 日志配置
+pub fn sql_log_config() -> SqlLogConfig {
+    SQL_LOG_CONFIG
+        .read()
+        .expect("SQL 日志配置锁已损坏")
+        .clone()
+        .unwrap_or_default()
+}
+
+/// 执行查询并记录语句、参数和耗时
+///
+/// 参数只在需要输出日志时才格式化
+///
+/// # 示例
+/// ```rust
+/// let users = observe_query(sql, || vec![format!("{:?}", status)], {
+///     sqlx::query_as::<_, User>(sql).bind(status).fetch_all(&pool)
+/// })
+/// .await?;
+/// ```
+pub async fn observe_query<F, T, E>(
+    sql: &str,
+    params: impl FnOnce() -> Vec<String>,
+    query: F,
+) -> Result<T, E>
+where
+    F: Future<Output = Result<T, E>>,
+    E: Display,
+{
+    let start = Instant::now();
+    let result = query.await;
+    let elapsed = start.elapsed();
+
+    let config = sql_log_config();
+    let slow = config
+        .slow_threshold
+        .is_some_and(|threshold| elapsed >= threshold);
+
+    #[cfg(feature = "metrics")]
+    {
+        metrics::histogram!(QUERY_DURATION_METRIC).record(elapsed.as_secs_f64());
+        if slow {
+            metrics::counter!(SLOW_QUERY_METRIC).increment(1);
+        }
+        if result.is_err() {
+            metrics::counter!(QUERY_ERRORS_METRIC).increment(1);
+        }
+    }
+
+    if slow || config.log_statements || result.is_err() {
+        let entry = SqlLogEntry {
+            sql,
+            params: if config.log_params {
+                params()
+            } else {
+                Vec::new()
+            },
+            redacted: !config.log_params,
+            elapsed,
+        };
+        match &result {
+            Err(e) => tracing::warn!(target: SQL_LOG_TARGET, "SQL 执行失败: {} | {}", entry, e),
+            Ok(_) if slow => tracing::warn!(target: SQL_LOG_TARGET, "慢查询: {}", entry),
+            Ok(_) => tracing::debug!(target: SQL_LOG_TARGET, "{}", entry),
+        }
+    }
+    result
+}
+
+/// 一条 SQL 日志
+struct SqlLogEntry<'a> {
+    /// 语句
+    sql: &'a str,
+    /// 参数值
+    params: Vec<String>,
+    /// 参数是否已脱敏
+    redacted: bool,
+    /// 耗时
+    elapsed: Duration,
+}
+
+impl Display for SqlLogEntry<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.sql)?;
+        if self.redacted {
+            let count = self.sql.matches('?').count();
+            if count > 0 {
+                write!(f, " | params: [{}]", vec!["***"; count].join(", "))?;
+            }
+        } else if !self.params.is_empty() {
+            write!(f, " | params: [{}]", self.params.join(", "))?;
+        }
+        write!(f, " | {:.3}ms", self.elapsed.as_secs_f64() * 1000.0)
+    }
+}
+
+/// 待记录的参数
+///
+/// 与 [`DescribeDebug`]、[`DescribeOpaque`] 配合：实现了 `Debug` 的参数按 `Debug` 格式化，
+/// 其余记录为 `<?>`。用法为 `(&Param(&value)).describe()`，供生成的代码使用
+#[doc(hidden)]
+pub struct Param<'a, T: ?Sized>(pub &'a T);
+
+/// 格式化实现了 `Debug` 的参数
+#[doc(hidden)]
+pub trait DescribeDebug {
+    fn describe(&self) -> String;
+}
+
+impl<T: Debug + ?Sized> DescribeDebug for Param<'_, T> {
+    fn describe(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+/// 未实现 `Debug` 的参数
+#[doc(hidden)]
+pub trait DescribeOpaque {
+    fn describe(&self) -> String;
+}
+
+impl<T: ?Sized> DescribeOpaque for &Param<'_, T> {
+    fn describe(&self) -> String {
+        "<?>".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Opaque;
+
+    /// 测试参数格式化和脱敏
+    #[test]
+    fn test_log_entry() {
+        let id = 7_i64;
+        let params = vec![Param(&id).describe(), (&Param(&Opaque)).describe()];
+        assert_eq!(params, vec!["7".to_string(), "<?>".to_string()]);
+
+        let entry = SqlLogEntry {
+            sql: "SELECT * FROM users WHERE id = ? AND status = ?",
+            params,
+            redacted: false,
+            elapsed: Duration::from_micros(1500),
+        };
+        assert_eq!(
+            entry.to_string(),
+            "SELECT * FROM users WHERE id = ? AND status = ? | params: [7, <?>] | 1.500ms"
+        );
+
+        let entry = SqlLogEntry {
+            params: Vec::new(),
+            redacted: true,
+            ..entry
+        };
+        assert!(entry.to_string().contains("params: [***, ***]"));
+    }
+
+    /// 测试查询结果原样返回
+    #[tokio::test]
+    async fn test_observe_query() {
+        let result: Result<i32, String> = observe_query("SELECT 1", Vec::new, async { Ok(1) }).await;
+        assert_eq!(result, Ok(1));
+
+        let result: Result<i32, String> =
+            observe_query("SELECT x", || panic!("参数已脱敏，不应格式化"), async {
+                Err("unknown column".to_string())
+            })
+            .await;
+        assert!(result.is_err());
+    }
+}
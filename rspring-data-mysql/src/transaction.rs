@@ -0,0 +1,245 @@
+//! MySQL 事务管理模块
+//!
+//! 为 `#[Transactional]` 提供基于连接的事务：开启事务时从连接池获取一个连接并执行 `BEGIN`，
+//! 事务中的查询通过 [`current_transaction`] 获取同一个连接执行
+//!
+//! `#[derive(Repository)]` 生成的方法、[`PageQuery`](crate::PageQuery)、[`Spec`](crate::Spec)
+//! 的查询和 [`Outbox::append_current`](crate::Outbox::append_current) 通过 [`acquire_connection`]
+//! 获取连接，在事务中时自动使用事务的连接，随事务一起提交或回滚
+
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use rspring_core::transaction::{transaction_resource, Transaction, TransactionManager};
+use rspring_core::{Error, Result};
+use sqlx::mysql::{MySql, MySqlConnection, MySqlPool};
+use sqlx::pool::PoolConnection;
+use tokio::sync::{MappedMutexGuard, Mutex, MutexGuard, OwnedMappedMutexGuard, OwnedMutexGuard};
+
+use crate::datasource::map_sqlx_error;
+
+/// MySQL 事务管理器
+///
+/// # 示例
+/// ```rust
+/// rspring_core::transaction::set_transaction_manager(Arc::new(MySqlTransactionManager::new(pool.clone())));
+///
+/// #[Transactional]
+/// pub async fn transfer(&self, from: u64, to: u64, amount: i64) -> Result<()> {
+///     let tx = current_transaction().ok_or_else(|| Error::internal("不在事务中"))?;
+///     let mut conn = tx.connection().await?;
+///     sqlx::query("UPDATE accounts SET balance = balance - ? WHERE id = ?")
+///         .bind(amount)
+///         .bind(from)
+///         .execute(&mut *conn)
+///         .await
+///         .map_err(map_sqlx_error)?;
+///     // ...
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MySqlTransactionManager {
+    /// 连接池
+    pool: MySqlPool,
+}
+
+impl MySqlTransactionManager {
+    /// 创建事务管理器
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TransactionManager for MySqlTransactionManager {
+    async fn begin(&self) -> Result<Box<dyn Transaction>> {
+        let transaction = observe_acquire(&self.pool, self.pool.begin()).await?;
+        Ok(Box::new(MySqlTransactionHandle(Arc::new(MySqlTransaction {
+            inner: Arc::new(Mutex::new(Some(transaction))),
+        }))))
+    }
+}
+
+/// 事务中的 MySQL 连接
+#[derive(Debug)]
+pub struct MySqlTransaction {
+    /// 未完成的事务，提交或回滚后为 `None`
+    inner: Arc<Mutex<Option<sqlx::Transaction<'static, MySql>>>>,
+}
+
+impl MySqlTransaction {
+    /// 获取事务的连接，持有期间同一事务中的其他查询等待
+    ///
+    /// # 错误
+    /// 事务已提交或回滚时返回错误
+    pub async fn connection(&self) -> Result<MappedMutexGuard<'_, MySqlConnection>> {
+        MutexGuard::try_map(self.inner.lock().await, |transaction| {
+            transaction.as_deref_mut()
+        })
+        .map_err(|_| Error::internal("MySQL 事务已结束"))
+    }
+
+    /// 获取事务的连接，不借用事务本身，持有期间同一事务中的其他查询等待
+    async fn owned_connection(&self) -> Result<OwnedMappedMutexGuard<Option<sqlx::Transaction<'static, MySql>>, MySqlConnection>> {
+        OwnedMutexGuard::try_map(self.inner.clone().lock_owned().await, |transaction| {
+            transaction.as_deref_mut()
+        })
+        .map_err(|_| Error::internal("MySQL 事务已结束"))
+    }
+
+    /// 取出未完成的事务
+    async fn take(&self) -> Result<sqlx::Transaction<'static, MySql>> {
+        self.inner
+            .lock()
+            .await
+            .take()
+            .ok_or_else(|| Error::internal("MySQL 事务已结束"))
+    }
+}
+
+/// 获取当前事务的 MySQL 连接
+///
+/// 不在 [`MySqlTransactionManager`] 开启的事务中时返回 `None`
+pub fn current_transaction() -> Option<Arc<MySqlTransaction>> {
+    transaction_resource::<MySqlTransaction>()
+}
+
+/// 获取执行查询的连接
+///
+/// 在 [`MySqlTransactionManager`] 开启的事务中时返回事务的连接，否则从 `pool` 获取连接。
+/// 事务的连接同一时间只能被一个查询持有，持有期间不要在同一事务中执行其他查询
+///
+/// # 错误
+/// 事务已结束或从连接池获取连接失败时返回错误
+///
+/// # 示例
+/// ```rust
+/// let mut conn = acquire_connection(&self.pool).await?;
+/// sqlx::query("UPDATE accounts SET balance = balance - ? WHERE id = ?")
+///     .bind(amount)
+///     .bind(from)
+///     .execute(&mut *conn)
+///     .await
+///     .map_err(map_sqlx_error)?;
+/// ```
+pub async fn acquire_connection(pool: &MySqlPool) -> Result<MySqlConnectionGuard> {
+    match current_transaction() {
+        Some(transaction) => Ok(MySqlConnectionGuard::Transaction(transaction.owned_connection().await?)),
+        None => Ok(MySqlConnectionGuard::Pool(observe_acquire(pool, pool.acquire()).await?)),
+    }
+}
+
+/// 等待从连接池取出连接，启用 `metrics` 特性时记录等待时间和超时次数
+#[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+pub(crate) async fn observe_acquire<T>(
+    pool: &MySqlPool,
+    acquire: impl Future<Output = std::result::Result<T, sqlx::Error>>,
+) -> Result<T> {
+    #[cfg(feature = "metrics")]
+    let start = std::time::Instant::now();
+    let result = acquire.await;
+    #[cfg(feature = "metrics")]
+    crate::metrics::record_acquire_metrics(pool, start.elapsed(), &result);
+    result.map_err(map_sqlx_error)
+}
+
+/// 由 [`acquire_connection`] 获取的连接，释放时归还给事务或连接池
+#[derive(Debug)]
+pub enum MySqlConnectionGuard {
+    /// 当前事务的连接
+    Transaction(OwnedMappedMutexGuard<Option<sqlx::Transaction<'static, MySql>>, MySqlConnection>),
+    /// 从连接池获取的连接
+    Pool(PoolConnection<MySql>),
+}
+
+impl Deref for MySqlConnectionGuard {
+    type Target = MySqlConnection;
+
+    fn deref(&self) -> &MySqlConnection {
+        match self {
+            MySqlConnectionGuard::Transaction(connection) => connection,
+            MySqlConnectionGuard::Pool(connection) => connection,
+        }
+    }
+}
+
+impl DerefMut for MySqlConnectionGuard {
+    fn deref_mut(&mut self) -> &mut MySqlConnection {
+        match self {
+            MySqlConnectionGuard::Transaction(connection) => connection,
+            MySqlConnectionGuard::Pool(connection) => connection,
+        }
+    }
+}
+
+/// [`MySqlTransactionManager`] 开启的事务
+struct MySqlTransactionHandle(Arc<MySqlTransaction>);
+
+#[async_trait]
+impl Transaction for MySqlTransactionHandle {
+    fn resource(&self) -> Option<Arc<dyn std::any::Any + Send + Sync>> {
+        Some(self.0.clone())
+    }
+
+    async fn commit(self: Box<Self>) -> Result<()> {
+        self.0.take().await?.commit().await.map_err(map_sqlx_error)
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<()> {
+        self.0.take().await?.rollback().await.map_err(map_sqlx_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rspring_core::transaction::{set_transaction_manager, transactional};
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct Note {
+        id: i64,
+        title: String,
+    }
+
+    #[derive(rspring_core::Repository)]
+    #[repository(entity = Note, table = "transaction_notes", columns = "title")]
+    struct NoteRepository {
+        pool: MySqlPool,
+    }
+
+    /// 测试事务回滚时撤销仓储的写入，需要通过 `RSPRING_TEST_MYSQL_URL` 指定测试数据库
+    #[tokio::test]
+    async fn test_repository_rollback() {
+        use crate::CrudRepository;
+
+        let Ok(url) = std::env::var("RSPRING_TEST_MYSQL_URL") else {
+            return;
+        };
+        let pool = MySqlPool::connect(&url).await.unwrap();
+        sqlx::query("CREATE TABLE IF NOT EXISTS transaction_notes (id BIGINT AUTO_INCREMENT PRIMARY KEY, title VARCHAR(64) NOT NULL)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        set_transaction_manager(Arc::new(MySqlTransactionManager::new(pool.clone())));
+        let repository = NoteRepository { pool: pool.clone() };
+
+        let note = Note { id: 0, title: "rolled back".to_string() };
+        let result = transactional(async {
+            let id = repository.save(&note).await?;
+            assert_eq!(repository.find_by_id(id as i64).await?.unwrap().title, note.title);
+            Err::<(), _>(Error::internal("回滚"))
+        })
+        .await;
+        assert!(result.is_err());
+
+        let saved: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM transaction_notes WHERE title = ?")
+            .bind(&note.title)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(saved, 0);
+    }
+}
@@ -0,0 +1,210 @@
+//! Redis 缓存模块
+//!
+//! 基于 Redis 实现缓存 SPI，多个实例共享缓存。键的格式为 `<缓存名称>::<键>`
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rspring_core::cache::{Cache, CacheConfig, CacheManager};
+use serde_json::Value;
+
+use crate::client::RedisClient;
+
+/// Redis 缓存管理器
+///
+/// # 示例
+/// ```rust
+/// let cache_config: CacheConfig = config.get_section("cache")?;
+/// rspring_core::cache::set_cache_manager(Arc::new(RedisCacheManager::new(redis.clone(), cache_config)));
+/// ```
+pub struct RedisCacheManager {
+    /// Redis 客户端
+    client: RedisClient,
+    /// 缓存配置，`max_entries` 对 Redis 缓存无效
+    config: CacheConfig,
+    /// 已创建的缓存
+    caches: RwLock<HashMap<String, Arc<RedisCache>>>,
+}
+
+impl RedisCacheManager {
+    /// 创建 Redis 缓存管理器
+    pub fn new(client: RedisClient, config: CacheConfig) -> Self {
+        Self {
+            client,
+            config,
+            caches: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl CacheManager for RedisCacheManager {
+    fn cache(&self, name: &str) -> Arc<dyn Cache> {
+        if let Some(cache) = self.caches.read().expect("缓存锁已损坏").get(name) {
+            return cache.clone();
+        }
+
+        self.caches
+            .write()
+            .expect("缓存锁已损坏")
+            .entry(name.to_string())
+            .or_insert_with(|| {
+                Arc::new(RedisCache {
+                    name: name.to_string(),
+                    client: self.client.clone(),
+                    ttl: self.config.ttl(name),
+                })
+            })
+            .clone()
+    }
+
+    fn cache_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .caches
+            .read()
+            .expect("缓存锁已损坏")
+            .keys()
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+/// Redis 缓存
+///
+/// 读写失败时记录警告并按未命中处理，不影响业务方法执行
+pub struct RedisCache {
+    /// 缓存名称
+    name: String,
+    /// Redis 客户端
+    client: RedisClient,
+    /// 过期时间
+    ttl: Option<Duration>,
+}
+
+impl RedisCache {
+    /// 缓存项在 Redis 中的键
+    fn redis_key(&self, key: &str) -> String {
+        format!("{}::{}", self.name, key)
+    }
+
+    /// 使用 `SCAN` 删除该缓存的所有键，集群模式下依次扫描每个主节点
+    async fn clear_keys(&self) -> rspring_core::Result<()> {
+        if !self.client.is_cluster() {
+            return self.scan_delete(None).await;
+        }
+        for slot in self.client.primary_slots().await? {
+            self.scan_delete(Some(slot)).await?;
+        }
+        Ok(())
+    }
+
+    /// 扫描并删除单个节点上该缓存的键，`slot` 为集群中目标主节点负责的哈希槽
+    async fn scan_delete(&self, slot: Option<u16>) -> rspring_core::Result<()> {
+        let pattern = format!("{}::*", self.name);
+        let mut cursor = 0u64;
+        loop {
+            let mut cmd = redis::cmd("SCAN");
+            cmd.arg(cursor)
+                .arg("MATCH")
+                .arg(&pattern)
+                .arg("COUNT")
+                .arg(100);
+            let (next, keys): (u64, Vec<String>) = match slot {
+                Some(slot) => self.client.query_slot(&cmd, slot).await?,
+                None => self.client.query(&cmd).await?,
+            };
+            if !keys.is_empty() {
+                self.delete_keys(keys).await?;
+            }
+            if next == 0 {
+                return Ok(());
+            }
+            cursor = next;
+        }
+    }
+
+    /// 删除一批键，集群模式下按哈希槽分批，避免跨槽的 `DEL` 返回 `CROSSSLOT` 错误
+    async fn delete_keys(&self, keys: Vec<String>) -> rspring_core::Result<()> {
+        let batches = if self.client.is_cluster() {
+            group_by_slot(keys)
+        } else {
+            vec![keys]
+        };
+        for batch in batches {
+            self.client.query::<i64>(redis::cmd("DEL").arg(&batch)).await?;
+        }
+        Ok(())
+    }
+}
+
+/// 按集群哈希槽对键分组
+fn group_by_slot(keys: Vec<String>) -> Vec<Vec<String>> {
+    let mut slots: HashMap<u16, Vec<String>> = HashMap::new();
+    for key in keys {
+        slots
+            .entry(redis::cluster_routing::get_slot(key.as_bytes()))
+            .or_default()
+            .push(key);
+    }
+    slots.into_values().collect()
+}
+
+#[async_trait]
+impl Cache for RedisCache {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn get(&self, key: &str) -> Option<Value> {
+        match self.client.get::<Value>(&self.redis_key(key)).await {
+            Ok(value) => value,
+            Err(e) => {
+                tracing::warn!("读取 Redis 缓存 {} 失败: {}", self.name, e);
+                None
+            }
+        }
+    }
+
+    async fn put(&self, key: &str, value: Value) {
+        if let Err(e) = self.client.set(&self.redis_key(key), &value, self.ttl).await {
+            tracing::warn!("写入 Redis 缓存 {} 失败: {}", self.name, e);
+        }
+    }
+
+    async fn evict(&self, key: &str) {
+        if let Err(e) = self.client.delete(&self.redis_key(key)).await {
+            tracing::warn!("删除 Redis 缓存 {} 失败: {}", self.name, e);
+        }
+    }
+
+    /// 使用 `SCAN` 删除该缓存的所有键，集群模式下依次扫描每个主节点
+    async fn clear(&self) {
+        if let Err(e) = self.clear_keys().await {
+            tracing::warn!("清空 Redis 缓存 {} 失败: {}", self.name, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试按哈希槽分组，相同哈希标签的键在同一组
+    #[test]
+    fn test_group_by_slot() {
+        let keys = vec![
+            "{users}::1".to_string(),
+            "{users}::2".to_string(),
+            "orders::1".to_string(),
+        ];
+        let mut groups = group_by_slot(keys);
+        groups.sort_by_key(|group| group.len());
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0], vec!["orders::1".to_string()]);
+        assert_eq!(groups[1], vec!["{users}::1".to_string(), "{users}::2".to_string()]);
+    }
+}
@@ -0,0 +1,336 @@
+//! Redis 客户端模块
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use deadpool_redis::{cluster, PoolConfig, Runtime, Timeouts};
+use redis::cluster_routing::{Route, RoutingInfo, SingleNodeRoutingInfo, SlotAddr};
+use redis::{Cmd, FromRedisValue};
+use rspring_core::config::{ConfigValidator, ConfigurationManager, RedisConfig};
+use rspring_core::{Component, Error, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Redis 连接池，单机或集群
+#[derive(Clone)]
+enum RedisPool {
+    /// 单机连接池
+    Single(deadpool_redis::Pool),
+    /// 集群连接池
+    Cluster(cluster::Pool),
+}
+
+/// Redis 客户端
+///
+/// 克隆开销很小，克隆后共享同一个连接池。值以 JSON 格式存储
+///
+/// # 示例
+/// ```rust
+/// let client = RedisClient::new(&RedisConfig::default())?;
+///
+/// client.set("token:abc", &session, Some(Duration::from_secs(1800))).await?;
+/// let count: i64 = client.query(redis::cmd("INCR").arg("visits")).await?;
+/// ```
+#[derive(Clone)]
+pub struct RedisClient {
+    /// 连接池
+    pool: RedisPool,
+}
+
+impl RedisClient {
+    /// 根据 Redis 配置创建客户端
+    ///
+    /// 连接按需创建，创建客户端时不会连接 Redis
+    ///
+    /// # 错误
+    /// URL 无效或连接池创建失败时返回错误
+    pub fn new(config: &RedisConfig) -> Result<Self> {
+        let validator = ConfigValidator::new();
+        let mut pool_config = PoolConfig::new(config.pool_size);
+        pool_config.timeouts = Timeouts {
+            wait: Some(config.timeout),
+            create: Some(config.timeout),
+            recycle: Some(config.timeout),
+        };
+
+        let pool = if config.cluster {
+            let nodes = config.cluster_nodes();
+            for node in &nodes {
+                validator.validate_redis_url(node)?;
+            }
+            let mut cluster_config = cluster::Config::from_urls(nodes);
+            cluster_config.pool = Some(pool_config);
+            let pool = cluster_config
+                .create_pool(Some(Runtime::Tokio1))
+                .map_err(|e| Error::application(format!("创建 Redis 集群连接池失败: {}", e)))?;
+            RedisPool::Cluster(pool)
+        } else {
+            validator.validate_redis_url(&config.url)?;
+            let mut single_config = deadpool_redis::Config::from_url(config.url.as_str());
+            single_config.pool = Some(pool_config);
+            let pool = single_config
+                .create_pool(Some(Runtime::Tokio1))
+                .map_err(|e| Error::application(format!("创建 Redis 连接池失败: {}", e)))?;
+            RedisPool::Single(pool)
+        };
+
+        tracing::info!(
+            "Redis 连接池已创建，模式: {}，连接池大小: {}",
+            if config.cluster { "集群" } else { "单机" },
+            config.pool_size
+        );
+        Ok(Self { pool })
+    }
+
+    /// 使用配置文件中的 `[redis]` 章节创建客户端
+    ///
+    /// 未配置时使用默认值
+    pub fn from_config(config: &ConfigurationManager) -> Result<Self> {
+        let redis_config = if config.contains_key("redis") {
+            config.get_section("redis")?
+        } else {
+            RedisConfig::default()
+        };
+        Self::new(&redis_config)
+    }
+
+    /// 执行任意 Redis 命令
+    ///
+    /// # 错误
+    /// 获取连接或命令执行失败时返回错误
+    pub async fn query<T: FromRedisValue>(&self, cmd: &Cmd) -> Result<T> {
+        match &self.pool {
+            RedisPool::Single(pool) => {
+                let mut conn = pool
+                    .get()
+                    .await
+                    .map_err(|e| Error::internal(format!("获取 Redis 连接失败: {}", e)))?;
+                cmd.query_async(&mut conn).await.map_err(map_redis_error)
+            }
+            RedisPool::Cluster(pool) => {
+                let mut conn = pool
+                    .get()
+                    .await
+                    .map_err(|e| Error::internal(format!("获取 Redis 连接失败: {}", e)))?;
+                cmd.query_async(&mut conn).await.map_err(map_redis_error)
+            }
+        }
+    }
+
+    /// 读取 JSON 值，键不存在时返回 `None`
+    pub async fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        let value: Option<String> = self.query(redis::cmd("GET").arg(key)).await?;
+        value
+            .map(|value| serde_json::from_str(&value))
+            .transpose()
+            .map_err(Error::from)
+    }
+
+    /// 以 JSON 格式写入值，`ttl` 为 `None` 时不过期
+    pub async fn set<T: Serialize + ?Sized>(
+        &self,
+        key: &str,
+        value: &T,
+        ttl: Option<Duration>,
+    ) -> Result<()> {
+        let value = serde_json::to_string(value)?;
+        let mut cmd = redis::cmd("SET");
+        cmd.arg(key).arg(value);
+        if let Some(ttl) = ttl {
+            cmd.arg("PX").arg(ttl.as_millis() as u64);
+        }
+        self.query(&cmd).await
+    }
+
+    /// 键不存在时以 JSON 格式写入值，返回是否写入成功
+    pub async fn set_if_absent<T: Serialize + ?Sized>(
+        &self,
+        key: &str,
+        value: &T,
+        ttl: Option<Duration>,
+    ) -> Result<bool> {
+        let value = serde_json::to_string(value)?;
+        let mut cmd = redis::cmd("SET");
+        cmd.arg(key).arg(value).arg("NX");
+        if let Some(ttl) = ttl {
+            cmd.arg("PX").arg(ttl.as_millis() as u64);
+        }
+        let result: Option<String> = self.query(&cmd).await?;
+        Ok(result.is_some())
+    }
+
+    /// 删除键，返回键是否存在
+    pub async fn delete(&self, key: &str) -> Result<bool> {
+        let count: i64 = self.query(redis::cmd("DEL").arg(key)).await?;
+        Ok(count > 0)
+    }
+
+    /// 判断键是否存在
+    pub async fn exists(&self, key: &str) -> Result<bool> {
+        let count: i64 = self.query(redis::cmd("EXISTS").arg(key)).await?;
+        Ok(count > 0)
+    }
+
+    /// 设置过期时间，返回键是否存在
+    pub async fn expire(&self, key: &str, ttl: Duration) -> Result<bool> {
+        let updated: i64 = self
+            .query(redis::cmd("PEXPIRE").arg(key).arg(ttl.as_millis() as u64))
+            .await?;
+        Ok(updated > 0)
+    }
+
+    /// 发送 PING 命令
+    pub async fn ping(&self) -> Result<()> {
+        let _: String = self.query(&redis::cmd("PING")).await?;
+        Ok(())
+    }
+
+    /// 获取连接池状态，返回（当前连接数，空闲连接数，最大连接数）
+    pub fn pool_status(&self) -> (usize, usize, usize) {
+        let status = match &self.pool {
+            RedisPool::Single(pool) => pool.status(),
+            RedisPool::Cluster(pool) => pool.status(),
+        };
+        (status.size, status.available, status.max_size)
+    }
+
+    /// 是否为集群模式
+    pub fn is_cluster(&self) -> bool {
+        matches!(self.pool, RedisPool::Cluster(_))
+    }
+
+    /// 集群中每个主节点负责的一个哈希槽，单机模式返回空列表
+    ///
+    /// 配合 [`query_slot`](Self::query_slot) 将 `SCAN` 等只作用于单个节点的命令依次发送到每个主节点
+    ///
+    /// # 错误
+    /// 获取连接或 `CLUSTER SLOTS` 执行失败时返回错误
+    pub async fn primary_slots(&self) -> Result<Vec<u16>> {
+        if !self.is_cluster() {
+            return Ok(Vec::new());
+        }
+        let ranges: Vec<Vec<redis::Value>> = self.query(redis::cmd("CLUSTER").arg("SLOTS")).await?;
+        primary_slots(&ranges)
+    }
+
+    /// 将命令发送到负责指定哈希槽的主节点，单机模式下直接执行
+    ///
+    /// # 错误
+    /// 获取连接或命令执行失败时返回错误
+    pub async fn query_slot<T: FromRedisValue>(&self, cmd: &Cmd, slot: u16) -> Result<T> {
+        let RedisPool::Cluster(pool) = &self.pool else {
+            return self.query(cmd).await;
+        };
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| Error::internal(format!("获取 Redis 连接失败: {}", e)))?;
+        let routing = RoutingInfo::SingleNode(SingleNodeRoutingInfo::SpecificNode(Route::new(
+            slot,
+            SlotAddr::Master,
+        )));
+        let value = conn
+            .route_command(cmd, routing)
+            .await
+            .map_err(map_redis_error)?;
+        T::from_redis_value(&value).map_err(map_redis_error)
+    }
+}
+
+/// 从 `CLUSTER SLOTS` 的结果中为每个主节点选出一个哈希槽
+///
+/// 每项为 `[起始槽, 结束槽, [主节点地址, 端口, ...], 副本...]`
+fn primary_slots(ranges: &[Vec<redis::Value>]) -> Result<Vec<u16>> {
+    let mut primaries: BTreeMap<(String, i64), u16> = BTreeMap::new();
+    for range in ranges {
+        let (Some(start), Some(redis::Value::Bulk(node))) = (range.first(), range.get(2)) else {
+            return Err(Error::internal("CLUSTER SLOTS 返回格式无效"));
+        };
+        let (Some(host), Some(port)) = (node.first(), node.get(1)) else {
+            return Err(Error::internal("CLUSTER SLOTS 返回格式无效"));
+        };
+        let start: u16 = redis::from_redis_value(start).map_err(map_redis_error)?;
+        let address = (
+            redis::from_redis_value(host).map_err(map_redis_error)?,
+            redis::from_redis_value(port).map_err(map_redis_error)?,
+        );
+        primaries
+            .entry(address)
+            .and_modify(|slot| *slot = (*slot).min(start))
+            .or_insert(start);
+    }
+    Ok(primaries.into_values().collect())
+}
+
+impl std::fmt::Debug for RedisClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisClient")
+            .field("cluster", &self.is_cluster())
+            .finish()
+    }
+}
+
+impl Component for RedisClient {
+    fn component_name(&self) -> &'static str {
+        "RedisClient"
+    }
+}
+
+/// 将 Redis 错误转换为框架错误
+fn map_redis_error(error: redis::RedisError) -> Error {
+    Error::internal(format!("Redis 操作失败: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试创建单机和集群客户端，创建时不连接 Redis
+    #[tokio::test]
+    async fn test_create_client() {
+        let client = RedisClient::new(&RedisConfig::default()).unwrap();
+        assert!(!client.is_cluster());
+        assert_eq!(client.pool_status(), (0, 0, 10));
+
+        let config = RedisConfig {
+            cluster: true,
+            nodes: vec!["redis://10.0.0.1:6379".to_string()],
+            ..RedisConfig::default()
+        };
+        assert!(RedisClient::new(&config).unwrap().is_cluster());
+
+        let config = RedisConfig {
+            url: "http://localhost:6379".to_string(),
+            ..RedisConfig::default()
+        };
+        assert!(RedisClient::new(&config).is_err());
+    }
+
+    /// 测试从 `CLUSTER SLOTS` 的结果中为每个主节点选出一个哈希槽
+    #[test]
+    fn test_primary_slots() {
+        let node = |host: &str, port: i64| {
+            redis::Value::Bulk(vec![
+                redis::Value::Data(host.as_bytes().to_vec()),
+                redis::Value::Int(port),
+                redis::Value::Data(b"node-id".to_vec()),
+            ])
+        };
+        let range = |start: i64, end: i64, host: &str| {
+            vec![
+                redis::Value::Int(start),
+                redis::Value::Int(end),
+                node(host, 6379),
+                node("10.0.0.9", 6379),
+            ]
+        };
+        let ranges = vec![
+            range(10923, 16383, "10.0.0.3"),
+            range(5461, 10922, "10.0.0.2"),
+            range(100, 5460, "10.0.0.1"),
+            range(0, 99, "10.0.0.1"),
+        ];
+        assert_eq!(primary_slots(&ranges).unwrap(), vec![0, 5461, 10923]);
+        assert!(primary_slots(&[vec![redis::Value::Int(0)]]).is_err());
+    }
+}
@@ -0,0 +1,60 @@
+//! Redis 健康检查模块
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rspring_core::{Health, HealthIndicator};
+
+use crate::client::RedisClient;
+
+/// 健康检查的默认超时时间
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Redis 健康指示器
+///
+/// 发送 `PING` 检查 Redis 是否可用，并报告连接池状态
+#[derive(Debug, Clone)]
+pub struct RedisHealthIndicator {
+    /// Redis 客户端
+    client: RedisClient,
+    /// 检查超时时间
+    timeout: Duration,
+}
+
+impl RedisHealthIndicator {
+    /// 创建健康指示器
+    pub fn new(client: RedisClient) -> Self {
+        Self {
+            client,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// 设置检查超时时间
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+#[async_trait]
+impl HealthIndicator for RedisHealthIndicator {
+    fn name(&self) -> &str {
+        "redis"
+    }
+
+    async fn health(&self) -> Health {
+        let health = match tokio::time::timeout(self.timeout, self.client.ping()).await {
+            Ok(Ok(())) => Health::up(),
+            Ok(Err(e)) => Health::down(e),
+            Err(_) => Health::down(format!("检查超时 ({:?})", self.timeout)),
+        };
+
+        let (size, idle, max) = self.client.pool_status();
+        health
+            .with_detail("mode", if self.client.is_cluster() { "cluster" } else { "standalone" })
+            .with_detail("active", size.saturating_sub(idle))
+            .with_detail("idle", idle)
+            .with_detail("max", max)
+    }
+}
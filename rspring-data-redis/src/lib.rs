@@ -1,4 +1,44 @@
-// Placeholder for Redis functionality
-pub fn init_redis() {
-    println!("Redis starter initialized");
-}
\ No newline at end of file
+//! RSpring Redis 启动器
+//!
+//! 根据 `[redis]` 配置创建基于 deadpool 的 Redis 连接池（支持集群模式），
+//! 注册为容器组件和健康指示器，并提供基于 serde 的类型化读写方法、Redis 缓存实现和定时任务锁
+//!
+//! # 示例
+//! ```rust
+//! let redis = rspring_data_redis::init_redis(&context).await?;
+//!
+//! redis.set("user:1", &user, Some(Duration::from_secs(600))).await?;
+//! let user: Option<User> = redis.get("user:1").await?;
+//! ```
+
+pub mod cache;
+pub mod client;
+pub mod health;
+pub mod lock;
+
+pub use cache::*;
+pub use client::*;
+pub use health::*;
+pub use lock::*;
+
+pub use redis;
+
+use rspring_core::{ApplicationContext, HealthRegistry, Result};
+
+/// 根据配置创建 Redis 客户端并注册到应用上下文
+///
+/// 上下文中存在 [`HealthRegistry`] 时同时注册 Redis 健康指示器
+///
+/// # 错误
+/// 配置无效或连接池创建失败时返回错误
+pub async fn init_redis(context: &ApplicationContext) -> Result<RedisClient> {
+    let client = RedisClient::from_config(context.config_manager())?;
+    context.register_singleton(client.clone()).await;
+
+    if let Some(registry) = context.get::<HealthRegistry>().await {
+        registry.register(RedisHealthIndicator::new(client.clone()));
+    }
+
+    tracing::info!("Redis 启动器初始化完成");
+    Ok(client)
+}
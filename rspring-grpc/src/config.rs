@@ -0,0 +1,111 @@
+//! gRPC 配置模块
+
+use axum::Router;
+use rspring_core::config::SslConfig;
+use serde::{Deserialize, Serialize};
+
+use crate::services::GrpcServices;
+
+/// gRPC 配置
+///
+/// # 示例
+/// ```toml
+/// [grpc]
+/// enabled = true
+/// host = "0.0.0.0"
+/// port = 50051
+/// ```
+///
+/// 与 HTTP 接口共用端口时需要 HTTP/2，即开启 `server.ssl` 或 `server.http2.h2c`：
+/// ```toml
+/// [grpc]
+/// shared = true
+///
+/// [server.http2]
+/// h2c = true
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct GrpcConfig {
+    /// 是否启用
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 监听地址
+    ///
+    /// # 默认值
+    /// `"0.0.0.0"`
+    #[serde(default = "default_host")]
+    pub host: String,
+    /// 监听端口
+    ///
+    /// # 默认值
+    /// `50051`
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// 是否与 HTTP 接口共用 `[server]` 端口，为 `true` 时不启动独立的 gRPC 服务器
+    ///
+    /// # 默认值
+    /// `false`
+    #[serde(default)]
+    pub shared: bool,
+    /// 独立端口的 TLS 配置
+    #[serde(default)]
+    pub ssl: Option<SslConfig>,
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            host: default_host(),
+            port: default_port(),
+            shared: false,
+            ssl: None,
+        }
+    }
+}
+
+impl GrpcConfig {
+    /// 共用端口时将 gRPC 路由合并到 HTTP 路由，否则原样返回
+    ///
+    /// 合并后 HTTP 路由上的中间件同样作用于 gRPC 请求
+    pub fn apply(&self, services: &GrpcServices, router: Router) -> Router {
+        if self.enabled && self.shared {
+            services.clone().merge_into(router)
+        } else {
+            router
+        }
+    }
+}
+
+// 默认值函数
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_host() -> String {
+    "0.0.0.0".to_string()
+}
+
+fn default_port() -> u16 {
+    50051
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试配置反序列化和默认值
+    #[test]
+    fn test_grpc_config() {
+        let config: GrpcConfig = toml::from_str("port = 9090\nshared = true").unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.host, "0.0.0.0");
+        assert_eq!(config.port, 9090);
+        assert!(config.shared);
+        assert!(config.ssl.is_none());
+    }
+}
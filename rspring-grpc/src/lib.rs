@@ -0,0 +1,28 @@
+//! RSpring gRPC 启动器
+//!
+//! 基于 tonic 提供 gRPC 服务：
+//! - 通过 [`GrpcServices`] 注册 tonic 生成的服务，服务实现可以是容器中的组件
+//! - 根据 `[grpc]` 配置在独立端口上自动启动 gRPC 服务器
+//! - gRPC 路由本质上是 axum 路由，可复用 Web 栈的拦截器、请求 ID、访问日志等中间件
+//! - `grpc.shared = true` 时与 HTTP 接口共用 `[server]` 端口
+//!
+//! # 示例
+//! ```rust
+//! let services = GrpcServices::new()
+//!     .add_component(context, GreeterServer::from_arc)
+//!     .await?;
+//!
+//! GrpcServer::from_config(context.config_manager(), services)?
+//!     .run()
+//!     .await?;
+//! ```
+
+pub mod config;
+pub mod server;
+pub mod services;
+
+pub use config::*;
+pub use server::*;
+pub use services::*;
+
+pub use tonic;
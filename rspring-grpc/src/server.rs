@@ -0,0 +1,128 @@
+//! gRPC 服务器模块
+//!
+//! 在独立端口上启动 gRPC 服务，底层复用 Web 服务器，因此 TLS、优雅关闭等行为与 HTTP 一致
+
+use std::future::Future;
+
+use axum::Router;
+use rspring_core::config::{ConfigurationManager, Http2Config, ServerConfig};
+use rspring_core::Result;
+use rspring_web::WebServer;
+use tower_http::trace::TraceLayer;
+
+use crate::config::GrpcConfig;
+use crate::services::GrpcServices;
+
+/// gRPC 服务器
+///
+/// # 示例
+/// ```rust
+/// GrpcServer::from_config(&config, services)?
+///     // 复用 Web 栈的中间件
+///     .map_router(|router| interceptors.apply(router))
+///     .run()
+///     .await?;
+/// ```
+pub struct GrpcServer {
+    /// gRPC 配置
+    config: GrpcConfig,
+    /// gRPC 路由
+    router: Router,
+}
+
+impl GrpcServer {
+    /// 创建 gRPC 服务器
+    pub fn new(config: GrpcConfig, services: GrpcServices) -> Self {
+        Self {
+            config,
+            router: services.into_router().layer(TraceLayer::new_for_grpc()),
+        }
+    }
+
+    /// 使用配置文件中的 `[grpc]` 章节创建 gRPC 服务器
+    ///
+    /// 未配置时使用默认值
+    pub fn from_config(config: &ConfigurationManager, services: GrpcServices) -> Result<Self> {
+        let grpc_config = if config.contains_key("grpc") {
+            config.get_section("grpc")?
+        } else {
+            GrpcConfig::default()
+        };
+        Ok(Self::new(grpc_config, services))
+    }
+
+    /// 对 gRPC 路由应用中间件，如拦截器、请求 ID 和访问日志
+    pub fn map_router(mut self, f: impl FnOnce(Router) -> Router) -> Self {
+        self.router = f(self.router);
+        self
+    }
+
+    /// 获取 gRPC 配置
+    pub fn config(&self) -> &GrpcConfig {
+        &self.config
+    }
+
+    /// 启动服务器，收到 Ctrl+C 后优雅关闭
+    pub async fn run(self) -> Result<()> {
+        self.run_until(async {
+            let _ = tokio::signal::ctrl_c().await;
+            tracing::info!("收到停止信号，正在关闭 gRPC 服务器");
+        })
+        .await
+    }
+
+    /// 启动服务器，`shutdown` 完成后优雅关闭
+    ///
+    /// 未启用或与 HTTP 共用端口时直接返回
+    pub async fn run_until<F>(self, shutdown: F) -> Result<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        if !self.config.enabled {
+            tracing::info!("gRPC 服务器未启用");
+            return Ok(());
+        }
+        if self.config.shared {
+            tracing::info!("gRPC 服务与 HTTP 共用端口，不启动独立的 gRPC 服务器");
+            return Ok(());
+        }
+
+        WebServer::new(self.server_config(), self.router)
+            .run_until(shutdown)
+            .await
+    }
+
+    /// 转换为 Web 服务器配置，明文连接使用 h2c
+    fn server_config(&self) -> ServerConfig {
+        ServerConfig {
+            host: self.config.host.clone(),
+            port: self.config.port,
+            ssl: self.config.ssl.clone(),
+            http2: Http2Config {
+                enabled: true,
+                h2c: true,
+                ..Http2Config::default()
+            },
+            ..ServerConfig::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试独立端口使用 h2c 启动
+    #[test]
+    fn test_server_config() {
+        let config = GrpcConfig {
+            port: 9090,
+            ..GrpcConfig::default()
+        };
+        let server = GrpcServer::new(config, GrpcServices::new());
+        let server_config = server.server_config();
+        assert_eq!(server_config.port, 9090);
+        assert!(server_config.http2.h2c);
+        assert!(server_config.listeners.is_empty());
+    }
+}
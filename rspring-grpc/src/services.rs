@@ -0,0 +1,105 @@
+//! gRPC 服务注册模块
+
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use axum::extract::Request;
+use axum::response::IntoResponse;
+use axum::Router;
+use rspring_core::{ApplicationContext, Component, Error, Result};
+use tonic::body::BoxBody;
+use tonic::server::NamedService;
+use tonic::service::RoutesBuilder;
+use tower::Service;
+
+/// gRPC 服务集合
+///
+/// 收集 tonic 生成的 `XxxServer` 服务，最终转换为 axum 路由，
+/// 由 [`GrpcServer`](crate::GrpcServer) 在独立端口启动或合并到 HTTP 路由
+///
+/// # 示例
+/// ```rust
+/// let services = GrpcServices::new()
+///     .add_service(GreeterServer::new(MyGreeter::default()))
+///     .add_component(context, HealthServer::from_arc)
+///     .await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GrpcServices {
+    /// tonic 路由构建器
+    routes: RoutesBuilder,
+    /// 已注册的服务名称
+    names: Vec<&'static str>,
+}
+
+impl GrpcServices {
+    /// 创建空的服务集合
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册服务
+    pub fn add_service<S>(mut self, service: S) -> Self
+    where
+        S: Service<Request<BoxBody>, Error = Infallible> + NamedService + Clone + Send + 'static,
+        S::Response: IntoResponse,
+        S::Future: Send + 'static,
+    {
+        tracing::debug!("注册 gRPC 服务: {}", S::NAME);
+        self.names.push(S::NAME);
+        self.routes.add_service(service);
+        self
+    }
+
+    /// 从容器中获取服务实现组件并注册
+    ///
+    /// `wrap` 通常为生成代码中的 `XxxServer::from_arc`
+    ///
+    /// # 错误
+    /// 容器中不存在 `T` 时返回错误
+    pub async fn add_component<T, S, F>(self, context: &ApplicationContext, wrap: F) -> Result<Self>
+    where
+        T: 'static,
+        F: FnOnce(Arc<T>) -> S,
+        S: Service<Request<BoxBody>, Error = Infallible> + NamedService + Clone + Send + 'static,
+        S::Response: IntoResponse,
+        S::Future: Send + 'static,
+    {
+        let component = context.get::<T>().await.ok_or_else(|| {
+            Error::not_found(format!(
+                "gRPC 服务 {} 的实现组件未注册: {}",
+                S::NAME,
+                std::any::type_name::<T>()
+            ))
+        })?;
+        Ok(self.add_service(wrap(component)))
+    }
+
+    /// 获取已注册的服务名称
+    pub fn service_names(&self) -> &[&'static str] {
+        &self.names
+    }
+
+    /// 是否没有注册任何服务
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// 转换为 axum 路由
+    pub fn into_router(self) -> Router {
+        self.routes.routes().into_axum_router()
+    }
+
+    /// 将 gRPC 路由合并到 HTTP 路由，使 gRPC 和 HTTP 接口在同一端口提供服务
+    ///
+    /// 服务端口需要支持 HTTP/2（HTTPS 或 h2c）
+    pub fn merge_into(self, router: Router) -> Router {
+        router.merge(self.into_router())
+    }
+}
+
+impl Component for GrpcServices {
+    fn component_name(&self) -> &'static str {
+        "GrpcServices"
+    }
+}
@@ -0,0 +1,155 @@
+//! 任务定义模块
+
+use chrono::{DateTime, Utc};
+use rspring_core::clock;
+use rspring_core::{Error, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// 默认队列
+pub const DEFAULT_QUEUE: &str = "default";
+
+/// 类型化任务特征
+///
+/// 任务以 JSON 持久化，`NAME` 用于在队列中区分任务类型，重命名任务类型时应保留原名称
+///
+/// # 示例
+/// ```rust
+/// #[derive(Serialize, Deserialize)]
+/// struct SendWelcomeEmail {
+///     user_id: u64,
+/// }
+///
+/// impl Job for SendWelcomeEmail {
+///     const NAME: &'static str = "send_welcome_email";
+///     const QUEUE: &'static str = "mailers";
+/// }
+/// ```
+pub trait Job: Serialize + DeserializeOwned + Send + Sync + 'static {
+    /// 任务名称
+    const NAME: &'static str;
+
+    /// 默认投递的队列
+    const QUEUE: &'static str = DEFAULT_QUEUE;
+
+    /// 最大尝试次数，未指定时使用 `[jobs]` 中的 `max_attempts`
+    const MAX_ATTEMPTS: Option<u32> = None;
+}
+
+/// 任务状态
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    /// 等待执行，包括等待重试和尚未到执行时间的任务
+    Pending,
+    /// 执行中
+    Running,
+    /// 执行成功
+    Succeeded,
+    /// 达到最大尝试次数后失败
+    Dead,
+}
+
+impl JobStatus {
+    /// 状态名称，与序列化结果一致
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Pending => "pending",
+            JobStatus::Running => "running",
+            JobStatus::Succeeded => "succeeded",
+            JobStatus::Dead => "dead",
+        }
+    }
+
+    /// 解析状态名称
+    ///
+    /// # 错误
+    /// 名称无效时返回验证错误
+    pub fn parse(status: &str) -> Result<Self> {
+        match status {
+            "pending" => Ok(JobStatus::Pending),
+            "running" => Ok(JobStatus::Running),
+            "succeeded" => Ok(JobStatus::Succeeded),
+            "dead" => Ok(JobStatus::Dead),
+            _ => Err(Error::validation(format!("无效的任务状态: {}", status))),
+        }
+    }
+}
+
+impl std::fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 持久化的任务记录
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct JobRecord {
+    /// 任务 ID
+    pub id: String,
+    /// 所属队列
+    pub queue: String,
+    /// 任务名称
+    pub name: String,
+    /// 任务参数
+    pub payload: Value,
+    /// 任务状态
+    pub status: JobStatus,
+    /// 已尝试次数，每次被工作者领取时加 1
+    pub attempts: u32,
+    /// 最大尝试次数
+    pub max_attempts: u32,
+    /// 最早执行时间
+    pub run_at: DateTime<Utc>,
+    /// 领取任务的工作者
+    #[serde(default)]
+    pub locked_by: Option<String>,
+    /// 领取的租约到期时间，到期未续约的任务可被其他工作者重新领取
+    #[serde(default)]
+    pub locked_until: Option<DateTime<Utc>>,
+    /// 最后一次失败的原因
+    #[serde(default)]
+    pub last_error: Option<String>,
+    /// 创建时间
+    pub created_at: DateTime<Utc>,
+    /// 更新时间
+    pub updated_at: DateTime<Utc>,
+}
+
+impl JobRecord {
+    /// 创建立即执行的任务记录
+    pub fn new(queue: impl Into<String>, name: impl Into<String>, payload: Value, max_attempts: u32) -> Self {
+        let now = clock::now();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            queue: queue.into(),
+            name: name.into(),
+            payload,
+            status: JobStatus::Pending,
+            attempts: 0,
+            max_attempts: max_attempts.max(1),
+            run_at: now,
+            locked_by: None,
+            locked_until: None,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// 将任务参数反序列化为指定类型
+    ///
+    /// # 错误
+    /// 参数与类型不匹配时返回验证错误
+    pub fn payload<T: DeserializeOwned>(&self) -> Result<T> {
+        serde_json::from_value(self.payload.clone()).map_err(|e| {
+            Error::validation(format!("任务 {} ({}) 的参数反序列化失败: {}", self.id, self.name, e))
+        })
+    }
+
+    /// 是否已用完尝试次数
+    pub fn is_exhausted(&self) -> bool {
+        self.attempts >= self.max_attempts
+    }
+}
@@ -0,0 +1,101 @@
+//! RSpring 任务队列
+//!
+//! 将类型化的后台任务持久化到 PostgreSQL 或 Redis，由工作者组件领取执行：
+//! - [`JobQueue`]：投递任务，并提供队列统计、分页查询、手动重试等管理方法
+//! - [`JobWorker`]：按队列轮询领取任务，限制并发数，失败后按退避策略重试，
+//!   达到最大尝试次数后进入死信状态
+//! - [`JobScheduler`]：按 cron 表达式定期投递周期任务，定义持久化在任务存储中
+//! - [`ScheduledTask`]：`#[Scheduled]` 标注的函数在当前进程中按 cron 表达式、固定频率或固定延迟执行
+//! - [`JobStore`]：任务存储 SPI，内置内存、PostgreSQL（`postgres` 特性）和 Redis（`redis` 特性）实现
+//!
+//! # 示例
+//! ```rust
+//! #[derive(Serialize, Deserialize)]
+//! struct SendWelcomeEmail {
+//!     user_id: u64,
+//! }
+//!
+//! impl Job for SendWelcomeEmail {
+//!     const NAME: &'static str = "send_welcome_email";
+//! }
+//!
+//! let jobs = rspring_jobs::init_jobs(&context).await?;
+//! jobs.enqueue(&SendWelcomeEmail { user_id: 42 }).await?;
+//!
+//! let worker = JobWorker::new(jobs.clone())
+//!     .handle(|job: SendWelcomeEmail| async move { send_welcome(job.user_id).await })
+//!     .start();
+//! ```
+
+pub mod config;
+pub mod job;
+#[cfg(feature = "postgres")]
+pub mod postgres;
+pub mod queue;
+pub mod recurring;
+#[cfg(feature = "redis")]
+pub mod redis;
+pub mod scheduled;
+pub mod store;
+pub mod worker;
+
+pub use config::*;
+pub use job::*;
+#[cfg(feature = "postgres")]
+pub use postgres::PostgresJobStore;
+pub use queue::JobQueue;
+pub use recurring::*;
+#[cfg(feature = "redis")]
+pub use redis::RedisJobStore;
+pub use scheduled::*;
+pub use store::*;
+pub use worker::*;
+
+use std::sync::Arc;
+
+use rspring_core::{ApplicationContext, Error, Result};
+
+/// 根据 `[jobs]` 配置创建任务队列并注册到应用上下文
+///
+/// 使用 Redis 存储时优先复用上下文中已注册的 `RedisClient`
+///
+/// # 错误
+/// 配置格式错误、未启用所选存储的特性或存储初始化失败时返回错误
+pub async fn init_jobs(context: &ApplicationContext) -> Result<JobQueue> {
+    let config_manager = context.config_manager();
+    let config: JobsConfig = if config_manager.contains_key("jobs") {
+        config_manager.get_section("jobs")?
+    } else {
+        JobsConfig::default()
+    };
+
+    let store: Arc<dyn JobStore> = match config.backend {
+        JobBackend::Memory => {
+            tracing::warn!("任务存储在内存中，应用重启后未执行的任务将丢失");
+            Arc::new(InMemoryJobStore::new())
+        }
+        #[cfg(feature = "postgres")]
+        JobBackend::Postgres => Arc::new(PostgresJobStore::connect(&config).await?),
+        #[cfg(feature = "redis")]
+        JobBackend::Redis => {
+            let client = match context.get::<rspring_data_redis::RedisClient>().await {
+                Some(client) => (*client).clone(),
+                None => rspring_data_redis::RedisClient::from_config(config_manager)?,
+            };
+            Arc::new(RedisJobStore::new(client).with_prefix(config.key_prefix.as_str()))
+        }
+        #[allow(unreachable_patterns)]
+        backend => {
+            return Err(Error::validation(format!(
+                "任务存储 {:?} 需要启用 rspring-jobs 的对应特性",
+                backend
+            )))
+        }
+    };
+
+    let queue = JobQueue::new(store, config);
+    context.register_singleton(queue.clone()).await;
+
+    tracing::info!("任务队列初始化完成，存储: {:?}", queue.config().backend);
+    Ok(queue)
+}
@@ -0,0 +1,506 @@
+//! PostgreSQL 任务存储模块
+//!
+//! 所有任务保存在一张表中，领取任务使用 `FOR UPDATE SKIP LOCKED`，
+//! 多个工作者并发领取时互不阻塞。周期任务定义保存在 `<表名>_recurring` 表中
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rspring_core::clock;
+use rspring_core::page::{is_valid_property, Page, PageResult};
+use rspring_core::{Error, Result};
+use sqlx::postgres::{PgPool, PgPoolOptions, PgRow};
+use sqlx::Row;
+
+use crate::config::JobsConfig;
+use crate::job::{JobRecord, JobStatus};
+use crate::recurring::{MisfirePolicy, RecurringJob};
+use crate::store::{lease_until, JobStore, QueueStats};
+
+/// PostgreSQL 任务存储
+///
+/// # 示例
+/// ```rust
+/// let store = PostgresJobStore::new(pool, "rspring_jobs")?;
+/// store.create_table().await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct PostgresJobStore {
+    /// 连接池
+    pool: PgPool,
+    /// 任务表名
+    table: String,
+}
+
+impl PostgresJobStore {
+    /// 创建任务存储
+    ///
+    /// # 错误
+    /// 表名包含非法字符时返回错误
+    pub fn new(pool: PgPool, table: impl Into<String>) -> Result<Self> {
+        let table = table.into();
+        if table.is_empty() || !is_valid_property(&table) {
+            return Err(Error::validation(format!("无效的任务表名: {}", table)));
+        }
+        Ok(Self { pool, table })
+    }
+
+    /// 按 `[jobs]` 配置连接数据库并创建任务表
+    ///
+    /// # 错误
+    /// 未配置 `url`、连接或建表失败时返回错误
+    pub async fn connect(config: &JobsConfig) -> Result<Self> {
+        let url = config
+            .url
+            .as_deref()
+            .ok_or_else(|| Error::validation("使用 PostgreSQL 存储任务时须配置 jobs.url"))?;
+        let pool = PgPoolOptions::new()
+            .max_connections(config.max_connections)
+            .connect(url)
+            .await
+            .map_err(|e| Error::application(format!("连接任务数据库失败: {}", e)))?;
+
+        let store = Self::new(pool, config.table.as_str())?;
+        store.create_table().await?;
+        Ok(store)
+    }
+
+    /// 获取连接池
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
+    /// 创建任务表、周期任务表和索引（不存在时）
+    ///
+    /// # 错误
+    /// 执行失败时返回错误
+    pub async fn create_table(&self) -> Result<()> {
+        for sql in create_table_sql(&self.table) {
+            sqlx::query(&sql)
+                .execute(&self.pool)
+                .await
+                .map_err(map_error)?;
+        }
+        Ok(())
+    }
+
+    /// 执行更新语句，返回影响的行数
+    async fn execute(
+        &self,
+        query: sqlx::query::Query<'_, sqlx::Postgres, sqlx::postgres::PgArguments>,
+    ) -> Result<u64> {
+        Ok(query
+            .execute(&self.pool)
+            .await
+            .map_err(map_error)?
+            .rows_affected())
+    }
+}
+
+#[async_trait]
+impl JobStore for PostgresJobStore {
+    async fn enqueue(&self, job: &JobRecord) -> Result<()> {
+        let sql = format!(
+            "INSERT INTO {} (id, queue, name, payload, status, attempts, max_attempts, run_at, created_at, updated_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+            self.table
+        );
+        self.execute(
+            sqlx::query(&sql)
+                .bind(&job.id)
+                .bind(&job.queue)
+                .bind(&job.name)
+                .bind(&job.payload)
+                .bind(job.status.as_str())
+                .bind(job.attempts as i32)
+                .bind(job.max_attempts as i32)
+                .bind(job.run_at)
+                .bind(job.created_at)
+                .bind(job.updated_at),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn fetch(
+        &self,
+        queue: &str,
+        names: &[String],
+        worker: &str,
+        limit: usize,
+        lease: Duration,
+    ) -> Result<Vec<JobRecord>> {
+        let now = clock::now();
+        let rows = sqlx::query(&fetch_sql(&self.table))
+            .bind(queue)
+            .bind(now)
+            .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+            .bind(worker)
+            .bind(lease_until(now, lease))
+            .bind(names)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_error)?;
+        rows.iter().map(from_row).collect()
+    }
+
+    async fn heartbeat(&self, job: &JobRecord, lease: Duration) -> Result<bool> {
+        let sql = format!(
+            "UPDATE {} SET locked_until = $3, updated_at = $4 \
+             WHERE id = $1 AND status = 'running' AND locked_by = $2",
+            self.table
+        );
+        let now = clock::now();
+        let updated = self
+            .execute(
+                sqlx::query(&sql)
+                    .bind(&job.id)
+                    .bind(&job.locked_by)
+                    .bind(lease_until(now, lease))
+                    .bind(now),
+            )
+            .await?;
+        Ok(updated == 1)
+    }
+
+    async fn complete(&self, job: &JobRecord) -> Result<()> {
+        let sql = format!(
+            "UPDATE {} SET status = 'succeeded', locked_by = NULL, locked_until = NULL, updated_at = $3 \
+             WHERE id = $1 AND status = 'running' AND locked_by = $2",
+            self.table
+        );
+        self.execute(
+            sqlx::query(&sql)
+                .bind(&job.id)
+                .bind(&job.locked_by)
+                .bind(clock::now()),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn fail(
+        &self,
+        job: &JobRecord,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let sql = format!(
+            "UPDATE {} SET status = $3, run_at = COALESCE($4, run_at), last_error = $5, \
+             locked_by = NULL, locked_until = NULL, updated_at = $6 \
+             WHERE id = $1 AND status = 'running' AND locked_by = $2",
+            self.table
+        );
+        let status = if retry_at.is_some() {
+            JobStatus::Pending
+        } else {
+            JobStatus::Dead
+        };
+        self.execute(
+            sqlx::query(&sql)
+                .bind(&job.id)
+                .bind(&job.locked_by)
+                .bind(status.as_str())
+                .bind(retry_at)
+                .bind(error)
+                .bind(clock::now()),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn queues(&self) -> Result<Vec<String>> {
+        let sql = format!("SELECT DISTINCT queue FROM {} ORDER BY queue", self.table);
+        sqlx::query_scalar(&sql)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_error)
+    }
+
+    async fn stats(&self, queue: &str) -> Result<QueueStats> {
+        let sql = format!(
+            "SELECT \
+             COUNT(*) FILTER (WHERE status = 'pending' AND run_at <= $2), \
+             COUNT(*) FILTER (WHERE status = 'pending' AND run_at > $2), \
+             COUNT(*) FILTER (WHERE status = 'running'), \
+             COUNT(*) FILTER (WHERE status = 'succeeded'), \
+             COUNT(*) FILTER (WHERE status = 'dead') \
+             FROM {} WHERE queue = $1",
+            self.table
+        );
+        let row = sqlx::query(&sql)
+            .bind(queue)
+            .bind(clock::now())
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_error)?;
+        let count = |index: usize| -> Result<u64> {
+            Ok(row.try_get::<i64, _>(index).map_err(map_error)?.max(0) as u64)
+        };
+        Ok(QueueStats {
+            queue: queue.to_string(),
+            pending: count(0)?,
+            scheduled: count(1)?,
+            running: count(2)?,
+            succeeded: count(3)?,
+            dead: count(4)?,
+        })
+    }
+
+    async fn list(
+        &self,
+        queue: &str,
+        status: JobStatus,
+        page: Page,
+    ) -> Result<PageResult<JobRecord>> {
+        let count_sql = format!(
+            "SELECT COUNT(*) FROM {} WHERE queue = $1 AND status = $2",
+            self.table
+        );
+        let total: i64 = sqlx::query_scalar(&count_sql)
+            .bind(queue)
+            .bind(status.as_str())
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_error)?;
+
+        let order = if status == JobStatus::Pending {
+            "run_at ASC"
+        } else {
+            "updated_at DESC"
+        };
+        let sql = format!(
+            "SELECT * FROM {} WHERE queue = $1 AND status = $2 ORDER BY {}, id LIMIT $3 OFFSET $4",
+            self.table, order
+        );
+        let rows = sqlx::query(&sql)
+            .bind(queue)
+            .bind(status.as_str())
+            .bind(i64::try_from(page.size).unwrap_or(i64::MAX))
+            .bind(i64::try_from(page.offset()).unwrap_or(i64::MAX))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_error)?;
+        let content = rows.iter().map(from_row).collect::<Result<Vec<_>>>()?;
+        Ok(PageResult::new(
+            content,
+            page.page,
+            page.size,
+            total.max(0) as u64,
+        ))
+    }
+
+    async fn get(&self, queue: &str, id: &str) -> Result<Option<JobRecord>> {
+        let sql = format!("SELECT * FROM {} WHERE id = $1 AND queue = $2", self.table);
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .bind(queue)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_error)?;
+        row.as_ref().map(from_row).transpose()
+    }
+
+    async fn retry(&self, queue: &str, id: &str) -> Result<bool> {
+        let sql = format!(
+            "UPDATE {} SET status = 'pending', attempts = 0, run_at = $3, updated_at = $3 \
+             WHERE id = $1 AND queue = $2 AND status = 'dead'",
+            self.table
+        );
+        let updated = self
+            .execute(sqlx::query(&sql).bind(id).bind(queue).bind(clock::now()))
+            .await?;
+        Ok(updated == 1)
+    }
+
+    async fn delete(&self, queue: &str, id: &str) -> Result<bool> {
+        let sql = format!("DELETE FROM {} WHERE id = $1 AND queue = $2", self.table);
+        let deleted = self.execute(sqlx::query(&sql).bind(id).bind(queue)).await?;
+        Ok(deleted == 1)
+    }
+
+    async fn purge(&self, queue: &str, before: DateTime<Utc>) -> Result<u64> {
+        let sql = format!(
+            "DELETE FROM {} WHERE queue = $1 AND status IN ('succeeded', 'dead') AND updated_at < $2",
+            self.table
+        );
+        self.execute(sqlx::query(&sql).bind(queue).bind(before))
+            .await
+    }
+
+    async fn save_recurring(&self, job: &RecurringJob) -> Result<()> {
+        let sql = format!(
+            "INSERT INTO {}_recurring (name, queue, job_name, payload, cron, misfire, max_attempts, next_run_at, last_run_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) \
+             ON CONFLICT (name) DO UPDATE SET queue = EXCLUDED.queue, job_name = EXCLUDED.job_name, \
+             payload = EXCLUDED.payload, cron = EXCLUDED.cron, misfire = EXCLUDED.misfire, \
+             max_attempts = EXCLUDED.max_attempts, next_run_at = EXCLUDED.next_run_at, \
+             last_run_at = EXCLUDED.last_run_at",
+            self.table
+        );
+        self.execute(
+            sqlx::query(&sql)
+                .bind(&job.name)
+                .bind(&job.queue)
+                .bind(&job.job_name)
+                .bind(&job.payload)
+                .bind(&job.cron)
+                .bind(job.misfire.as_str())
+                .bind(job.max_attempts.map(|attempts| attempts as i32))
+                .bind(job.next_run_at)
+                .bind(job.last_run_at),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn recurring_jobs(&self) -> Result<Vec<RecurringJob>> {
+        let sql = format!("SELECT * FROM {}_recurring ORDER BY name", self.table);
+        let rows = sqlx::query(&sql)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_error)?;
+        rows.iter().map(recurring_from_row).collect()
+    }
+
+    async fn remove_recurring(&self, name: &str) -> Result<bool> {
+        let sql = format!("DELETE FROM {}_recurring WHERE name = $1", self.table);
+        let deleted = self.execute(sqlx::query(&sql).bind(name)).await?;
+        Ok(deleted == 1)
+    }
+
+    async fn advance_recurring(
+        &self,
+        name: &str,
+        expected: DateTime<Utc>,
+        next: DateTime<Utc>,
+    ) -> Result<bool> {
+        let sql = format!(
+            "UPDATE {}_recurring SET next_run_at = $3, last_run_at = $4 WHERE name = $1 AND next_run_at = $2",
+            self.table
+        );
+        let updated = self
+            .execute(
+                sqlx::query(&sql)
+                    .bind(name)
+                    .bind(expected)
+                    .bind(next)
+                    .bind(clock::now()),
+            )
+            .await?;
+        Ok(updated == 1)
+    }
+}
+
+/// 生成建表和索引语句
+pub fn create_table_sql(table: &str) -> Vec<String> {
+    vec![
+        format!(
+            "CREATE TABLE IF NOT EXISTS {} (\
+             id VARCHAR(36) PRIMARY KEY, \
+             queue VARCHAR(255) NOT NULL, \
+             name VARCHAR(255) NOT NULL, \
+             payload JSONB NOT NULL, \
+             status VARCHAR(16) NOT NULL, \
+             attempts INT NOT NULL DEFAULT 0, \
+             max_attempts INT NOT NULL, \
+             run_at TIMESTAMPTZ NOT NULL, \
+             locked_by VARCHAR(255) NULL, \
+             locked_until TIMESTAMPTZ NULL, \
+             last_error TEXT NULL, \
+             created_at TIMESTAMPTZ NOT NULL, \
+             updated_at TIMESTAMPTZ NOT NULL)",
+            table
+        ),
+        format!(
+            "CREATE INDEX IF NOT EXISTS idx_{}_fetch ON {} (queue, status, run_at)",
+            table, table
+        ),
+        format!(
+            "CREATE TABLE IF NOT EXISTS {}_recurring (\
+             name VARCHAR(255) PRIMARY KEY, \
+             queue VARCHAR(255) NOT NULL, \
+             job_name VARCHAR(255) NOT NULL, \
+             payload JSONB NOT NULL, \
+             cron VARCHAR(255) NOT NULL, \
+             misfire VARCHAR(16) NOT NULL, \
+             max_attempts INT NULL, \
+             next_run_at TIMESTAMPTZ NOT NULL, \
+             last_run_at TIMESTAMPTZ NULL)",
+            table
+        ),
+    ]
+}
+
+/// 生成领取任务的语句：到期的待执行任务，以及租约已过期的执行中任务
+fn fetch_sql(table: &str) -> String {
+    format!(
+        "UPDATE {table} SET status = 'running', attempts = attempts + 1, locked_by = $4, \
+         locked_until = $5, updated_at = $2 \
+         WHERE id IN (SELECT id FROM {table} WHERE queue = $1 AND name = ANY($6) \
+         AND ((status = 'pending' AND run_at <= $2) OR (status = 'running' AND locked_until < $2)) \
+         ORDER BY run_at LIMIT $3 FOR UPDATE SKIP LOCKED) \
+         RETURNING *",
+        table = table
+    )
+}
+
+/// 将查询结果转换为任务记录
+fn from_row(row: &PgRow) -> Result<JobRecord> {
+    let status: String = row.try_get("status").map_err(map_error)?;
+    let attempts: i32 = row.try_get("attempts").map_err(map_error)?;
+    let max_attempts: i32 = row.try_get("max_attempts").map_err(map_error)?;
+    Ok(JobRecord {
+        id: row.try_get("id").map_err(map_error)?,
+        queue: row.try_get("queue").map_err(map_error)?,
+        name: row.try_get("name").map_err(map_error)?,
+        payload: row.try_get("payload").map_err(map_error)?,
+        status: JobStatus::parse(&status)?,
+        attempts: attempts.max(0) as u32,
+        max_attempts: max_attempts.max(0) as u32,
+        run_at: row.try_get("run_at").map_err(map_error)?,
+        locked_by: row.try_get("locked_by").map_err(map_error)?,
+        locked_until: row.try_get("locked_until").map_err(map_error)?,
+        last_error: row.try_get("last_error").map_err(map_error)?,
+        created_at: row.try_get("created_at").map_err(map_error)?,
+        updated_at: row.try_get("updated_at").map_err(map_error)?,
+    })
+}
+
+/// 将查询结果转换为周期任务定义
+fn recurring_from_row(row: &PgRow) -> Result<RecurringJob> {
+    let misfire: String = row.try_get("misfire").map_err(map_error)?;
+    let max_attempts: Option<i32> = row.try_get("max_attempts").map_err(map_error)?;
+    Ok(RecurringJob {
+        name: row.try_get("name").map_err(map_error)?,
+        queue: row.try_get("queue").map_err(map_error)?,
+        job_name: row.try_get("job_name").map_err(map_error)?,
+        payload: row.try_get("payload").map_err(map_error)?,
+        cron: row.try_get("cron").map_err(map_error)?,
+        misfire: MisfirePolicy::parse(&misfire)?,
+        max_attempts: max_attempts.map(|attempts| attempts.max(1) as u32),
+        next_run_at: row.try_get("next_run_at").map_err(map_error)?,
+        last_run_at: row.try_get("last_run_at").map_err(map_error)?,
+    })
+}
+
+/// 将 SQLx 错误转换为框架错误
+fn map_error(error: sqlx::Error) -> Error {
+    Error::application(format!("任务数据库操作失败: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试生成的领取语句
+    #[test]
+    fn test_fetch_sql() {
+        let sql = fetch_sql("rspring_jobs");
+        assert!(sql.starts_with("UPDATE rspring_jobs SET status = 'running'"));
+        assert!(sql.contains("FROM rspring_jobs WHERE queue = $1 AND name = ANY($6)"));
+        assert!(sql.ends_with("FOR UPDATE SKIP LOCKED) RETURNING *"));
+        assert!(create_table_sql("rspring_jobs")[2].contains("rspring_jobs_recurring"));
+    }
+}
@@ -0,0 +1,223 @@
+//! 任务入队模块
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rspring_core::clock;
+use rspring_core::page::{Page, PageResult};
+use rspring_core::{Component, Error, Result};
+
+use crate::config::JobsConfig;
+use crate::job::{Job, JobRecord, JobStatus};
+use crate::recurring::{next_fire, RecurringJob};
+use crate::store::{JobStore, QueueStats};
+
+/// 任务队列
+///
+/// 克隆开销很小，克隆后共享同一个存储。除入队外还提供队列统计、分页查询、
+/// 手动重试和删除等管理方法，可直接暴露为任务看板的接口
+///
+/// # 示例
+/// ```rust
+/// let jobs = rspring_jobs::init_jobs(&context).await?;
+/// jobs.enqueue(&SendWelcomeEmail { user_id: 42 }).await?;
+///
+/// for stats in jobs.overview().await? {
+///     tracing::info!("{}: {} 个待执行, {} 个死信", stats.queue, stats.pending, stats.dead);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct JobQueue {
+    /// 任务存储
+    store: Arc<dyn JobStore>,
+    /// 任务队列配置
+    config: Arc<JobsConfig>,
+}
+
+impl JobQueue {
+    /// 使用指定的存储创建
+    pub fn new(store: Arc<dyn JobStore>, config: JobsConfig) -> Self {
+        Self {
+            store,
+            config: Arc::new(config),
+        }
+    }
+
+    /// 获取任务存储
+    pub fn store(&self) -> &Arc<dyn JobStore> {
+        &self.store
+    }
+
+    /// 获取任务队列配置
+    pub fn config(&self) -> &JobsConfig {
+        &self.config
+    }
+
+    /// 将任务投递到默认队列，返回任务 ID
+    ///
+    /// # 错误
+    /// 序列化或保存失败时返回错误
+    pub async fn enqueue<J: Job>(&self, job: &J) -> Result<String> {
+        self.enqueue_to(J::QUEUE, job).await
+    }
+
+    /// 将任务投递到指定队列，返回任务 ID
+    ///
+    /// # 错误
+    /// 序列化或保存失败时返回错误
+    pub async fn enqueue_to<J: Job>(&self, queue: &str, job: &J) -> Result<String> {
+        let record = self.record(queue, job)?;
+        self.store.enqueue(&record).await?;
+        tracing::debug!("任务 {} ({}) 已投递到队列 {}", record.id, record.name, queue);
+        Ok(record.id)
+    }
+
+    /// 延迟指定时间后执行任务，返回任务 ID
+    ///
+    /// # 错误
+    /// 序列化或保存失败时返回错误
+    pub async fn enqueue_in<J: Job>(&self, job: &J, delay: Duration) -> Result<String> {
+        let delay = chrono::Duration::from_std(delay)
+            .map_err(|_| Error::validation(format!("任务延迟时间过长: {:?}", delay)))?;
+        self.enqueue_at(job, clock::now() + delay).await
+    }
+
+    /// 在指定时间执行任务，时间已过时立即执行，返回任务 ID
+    ///
+    /// # 错误
+    /// 序列化或保存失败时返回错误
+    pub async fn enqueue_at<J: Job>(&self, job: &J, run_at: DateTime<Utc>) -> Result<String> {
+        let mut record = self.record(J::QUEUE, job)?;
+        record.run_at = run_at;
+        self.store.enqueue(&record).await?;
+        tracing::debug!("任务 {} ({}) 已投递到队列 {}，执行时间 {}", record.id, record.name, J::QUEUE, run_at);
+        Ok(record.id)
+    }
+
+    /// 注册周期任务定义，由 [`JobScheduler`](crate::JobScheduler) 按时投递
+    ///
+    /// 已存在同名且 cron 表达式相同的定义时保留其下次执行时间，使重启期间错过的执行按错过策略处理
+    ///
+    /// # 错误
+    /// cron 表达式无效或保存失败时返回错误
+    pub async fn schedule(&self, mut recurring: RecurringJob) -> Result<RecurringJob> {
+        let schedule = recurring.schedule()?;
+        let existing = self
+            .store
+            .recurring_jobs()
+            .await?
+            .into_iter()
+            .find(|job| job.name == recurring.name);
+        match existing {
+            Some(existing) if existing.cron == recurring.cron => {
+                recurring.next_run_at = existing.next_run_at;
+                recurring.last_run_at = existing.last_run_at;
+            }
+            _ => recurring.next_run_at = next_fire(&schedule, clock::now())?,
+        }
+
+        self.store.save_recurring(&recurring).await?;
+        tracing::info!("周期任务 {} ({}) 已注册，下次执行时间 {}", recurring.name, recurring.cron, recurring.next_run_at);
+        Ok(recurring)
+    }
+
+    /// 删除周期任务定义
+    ///
+    /// # 错误
+    /// 定义不存在时返回未找到错误
+    pub async fn unschedule(&self, name: &str) -> Result<()> {
+        if !self.store.remove_recurring(name).await? {
+            return Err(Error::not_found(format!("周期任务 {}", name)));
+        }
+        Ok(())
+    }
+
+    /// 所有周期任务定义
+    ///
+    /// # 错误
+    /// 查询失败时返回错误
+    pub async fn recurring_jobs(&self) -> Result<Vec<RecurringJob>> {
+        self.store.recurring_jobs().await
+    }
+
+    /// 创建任务记录
+    pub(crate) fn record<J: Job>(&self, queue: &str, job: &J) -> Result<JobRecord> {
+        let payload = serde_json::to_value(job)
+            .map_err(|e| Error::validation(format!("任务 {} 序列化失败: {}", J::NAME, e)))?;
+        let max_attempts = J::MAX_ATTEMPTS.unwrap_or(self.config.max_attempts);
+        Ok(JobRecord::new(queue, J::NAME, payload, max_attempts))
+    }
+
+    /// 所有队列的统计
+    ///
+    /// # 错误
+    /// 查询失败时返回错误
+    pub async fn overview(&self) -> Result<Vec<QueueStats>> {
+        let mut overview = Vec::new();
+        for queue in self.store.queues().await? {
+            overview.push(self.store.stats(&queue).await?);
+        }
+        Ok(overview)
+    }
+
+    /// 队列统计
+    ///
+    /// # 错误
+    /// 查询失败时返回错误
+    pub async fn stats(&self, queue: &str) -> Result<QueueStats> {
+        self.store.stats(queue).await
+    }
+
+    /// 分页查询指定状态的任务
+    ///
+    /// # 错误
+    /// 查询失败时返回错误
+    pub async fn list(&self, queue: &str, status: JobStatus, page: Page) -> Result<PageResult<JobRecord>> {
+        self.store.list(queue, status, page).await
+    }
+
+    /// 查询任务
+    ///
+    /// # 错误
+    /// 查询失败时返回错误
+    pub async fn get(&self, queue: &str, id: &str) -> Result<Option<JobRecord>> {
+        self.store.get(queue, id).await
+    }
+
+    /// 立即重新执行死信任务
+    ///
+    /// # 错误
+    /// 任务不存在或不是死信状态时返回未找到错误
+    pub async fn retry(&self, queue: &str, id: &str) -> Result<()> {
+        if !self.store.retry(queue, id).await? {
+            return Err(Error::not_found(format!("队列 {} 中的死信任务 {}", queue, id)));
+        }
+        Ok(())
+    }
+
+    /// 删除任务
+    ///
+    /// # 错误
+    /// 任务不存在时返回未找到错误
+    pub async fn delete(&self, queue: &str, id: &str) -> Result<()> {
+        if !self.store.delete(queue, id).await? {
+            return Err(Error::not_found(format!("队列 {} 中的任务 {}", queue, id)));
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for JobQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JobQueue")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl Component for JobQueue {
+    fn component_name(&self) -> &'static str {
+        "JobQueue"
+    }
+}
@@ -0,0 +1,441 @@
+//! 周期任务模块
+//!
+//! 周期任务按 cron 表达式定期投递到任务队列，定义持久化在任务存储中，应用重启后继续按原计划执行：
+//! - 重新注册同名且表达式不变的定义时保留原有的下次执行时间，停机期间错过的执行按错过策略处理
+//! - 多实例部署时通过比较并更新下次执行时间保证同一次执行只投递一次
+//!
+//! 调度器运行期间，任务转储的 `scheduled_jobs.<序号>` 部分列出周期任务的下次执行时间，
+//! 同一进程中的多个调度器使用不同的序号
+//!
+//! cron 表达式按 UTC 计算，支持 6 段（含秒）和 5 段（不含秒）格式
+
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use cron::Schedule;
+use rspring_core::clock;
+use rspring_core::task::dump::{
+    register_task_dump_contributor, unregister_task_dump_contributor, TaskDumpContributor,
+};
+use rspring_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::job::{Job, JobRecord};
+use crate::queue::JobQueue;
+
+/// 任务转储中周期任务部分的名称前缀，后接调度器的序号
+pub const SCHEDULED_JOBS_DUMP: &str = "scheduled_jobs";
+
+/// 下一个启动的调度器的序号
+static NEXT_SCHEDULER: AtomicUsize = AtomicUsize::new(1);
+
+/// 一次补偿投递的最大次数，避免长时间停机后瞬间投递大量任务
+const MAX_MISFIRES: usize = 100;
+
+/// 错过执行时间的处理策略
+///
+/// 实际执行时间晚于计划时间超过 `misfire_threshold` 时视为错过
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MisfirePolicy {
+    /// 无论错过多少次只补投一次
+    #[default]
+    FireOnce,
+    /// 每次错过的执行都补投，最多 100 次
+    FireAll,
+    /// 不补投，等待下一次执行时间
+    Skip,
+}
+
+impl MisfirePolicy {
+    /// 策略名称，与序列化结果一致
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MisfirePolicy::FireOnce => "fire_once",
+            MisfirePolicy::FireAll => "fire_all",
+            MisfirePolicy::Skip => "skip",
+        }
+    }
+
+    /// 解析策略名称
+    ///
+    /// # 错误
+    /// 名称无效时返回验证错误
+    pub fn parse(policy: &str) -> Result<Self> {
+        match policy {
+            "fire_once" => Ok(MisfirePolicy::FireOnce),
+            "fire_all" => Ok(MisfirePolicy::FireAll),
+            "skip" => Ok(MisfirePolicy::Skip),
+            _ => Err(Error::validation(format!("无效的错过执行策略: {}", policy))),
+        }
+    }
+}
+
+/// 周期任务定义
+///
+/// # 示例
+/// ```rust
+/// let report = RecurringJob::new("nightly-report", "0 0 2 * * *", &BuildReport { days: 1 })?
+///     .misfire(MisfirePolicy::Skip);
+/// jobs.schedule(report).await?;
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct RecurringJob {
+    /// 定义名称，全局唯一
+    pub name: String,
+    /// 投递的队列
+    pub queue: String,
+    /// 任务名称
+    pub job_name: String,
+    /// 任务参数
+    pub payload: Value,
+    /// cron 表达式
+    pub cron: String,
+    /// 错过执行时间的处理策略
+    #[serde(default)]
+    pub misfire: MisfirePolicy,
+    /// 最大尝试次数，未指定时使用 `[jobs]` 中的 `max_attempts`
+    #[serde(default)]
+    pub max_attempts: Option<u32>,
+    /// 下次执行时间
+    pub next_run_at: DateTime<Utc>,
+    /// 上次投递时间
+    #[serde(default)]
+    pub last_run_at: Option<DateTime<Utc>>,
+}
+
+impl RecurringJob {
+    /// 创建周期任务定义，投递到任务类型的默认队列
+    ///
+    /// # 错误
+    /// cron 表达式无效、没有下次执行时间或任务序列化失败时返回验证错误
+    pub fn new<J: Job>(name: impl Into<String>, cron: impl Into<String>, job: &J) -> Result<Self> {
+        let cron = cron.into();
+        let next_run_at = next_fire(&parse_cron(&cron)?, clock::now())?;
+        let payload = serde_json::to_value(job)
+            .map_err(|e| Error::validation(format!("任务 {} 序列化失败: {}", J::NAME, e)))?;
+        Ok(Self {
+            name: name.into(),
+            queue: J::QUEUE.to_string(),
+            job_name: J::NAME.to_string(),
+            payload,
+            cron,
+            misfire: MisfirePolicy::default(),
+            max_attempts: J::MAX_ATTEMPTS,
+            next_run_at,
+            last_run_at: None,
+        })
+    }
+
+    /// 设置错过执行时间的处理策略
+    pub fn misfire(mut self, misfire: MisfirePolicy) -> Self {
+        self.misfire = misfire;
+        self
+    }
+
+    /// 设置投递的队列
+    pub fn queue(mut self, queue: impl Into<String>) -> Self {
+        self.queue = queue.into();
+        self
+    }
+
+    /// 解析 cron 表达式
+    ///
+    /// # 错误
+    /// 表达式无效时返回验证错误
+    pub fn schedule(&self) -> Result<Schedule> {
+        parse_cron(&self.cron)
+    }
+
+    /// 计算截至 `now` 应投递的次数
+    ///
+    /// 返回值按错过策略计算：按时执行时为 1，错过时 `FireOnce` 为 1、`FireAll` 为错过的次数、`Skip` 为 0
+    pub fn due_count(&self, schedule: &Schedule, now: DateTime<Utc>, misfire_threshold: Duration) -> usize {
+        if self.next_run_at > now {
+            return 0;
+        }
+
+        let fires: Vec<DateTime<Utc>> = std::iter::once(self.next_run_at)
+            .chain(schedule.after(&self.next_run_at).take_while(|fire| *fire <= now))
+            .take(MAX_MISFIRES)
+            .collect();
+        let threshold = chrono::Duration::from_std(misfire_threshold).unwrap_or(chrono::Duration::MAX);
+        let missed = fires.len() > 1 || now - self.next_run_at > threshold;
+        if !missed {
+            return 1;
+        }
+
+        match self.misfire {
+            MisfirePolicy::FireOnce => 1,
+            MisfirePolicy::FireAll => fires.len(),
+            MisfirePolicy::Skip => 0,
+        }
+    }
+}
+
+/// 解析 cron 表达式，5 段格式补全秒为 0
+pub(crate) fn parse_cron(cron: &str) -> Result<Schedule> {
+    let expression = if cron.split_whitespace().count() == 5 {
+        format!("0 {}", cron.trim())
+    } else {
+        cron.trim().to_string()
+    };
+    Schedule::from_str(&expression)
+        .map_err(|e| Error::validation(format!("无效的 cron 表达式 {}: {}", cron, e)))
+}
+
+/// 计算 `after` 之后的下次执行时间
+pub(crate) fn next_fire(schedule: &Schedule, after: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    schedule
+        .after(&after)
+        .next()
+        .ok_or_else(|| Error::validation(format!("cron 表达式 {} 没有下次执行时间", schedule)))
+}
+
+/// 周期任务调度器
+///
+/// 按 `poll_interval` 检查到期的周期任务并投递到任务队列，任务的执行仍由 [`JobWorker`](crate::JobWorker) 负责
+///
+/// # 示例
+/// ```rust
+/// let scheduler = JobScheduler::new(jobs.clone()).start();
+///
+/// // 应用退出时
+/// scheduler.stop().await;
+/// ```
+#[derive(Debug)]
+pub struct JobScheduler {
+    /// 任务队列
+    queue: JobQueue,
+}
+
+impl JobScheduler {
+    /// 创建调度器
+    pub fn new(queue: JobQueue) -> Self {
+        Self { queue }
+    }
+
+    /// 投递所有到期的周期任务，返回投递的任务数
+    ///
+    /// # 错误
+    /// 读取周期任务定义失败时返回错误，单个定义投递失败只记录日志
+    pub async fn tick(&self) -> Result<usize> {
+        let store = self.queue.store();
+        let config = self.queue.config();
+        let now = clock::now();
+        let mut enqueued = 0;
+
+        for recurring in store.recurring_jobs().await? {
+            if recurring.next_run_at > now {
+                continue;
+            }
+            let schedule = match recurring.schedule() {
+                Ok(schedule) => schedule,
+                Err(e) => {
+                    tracing::error!("周期任务 {} 的 {}", recurring.name, e);
+                    continue;
+                }
+            };
+            let next = match next_fire(&schedule, now) {
+                Ok(next) => next,
+                Err(e) => {
+                    tracing::warn!("周期任务 {} 不再执行: {}", recurring.name, e);
+                    continue;
+                }
+            };
+
+            // 其他实例已推进下次执行时间时跳过
+            match store.advance_recurring(&recurring.name, recurring.next_run_at, next).await {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(e) => {
+                    tracing::error!("更新周期任务 {} 的下次执行时间失败: {}", recurring.name, e);
+                    continue;
+                }
+            }
+
+            let count = recurring.due_count(&schedule, now, config.misfire_threshold);
+            if count == 0 {
+                tracing::warn!("周期任务 {} 错过了 {} 的执行，按策略跳过", recurring.name, recurring.next_run_at);
+            }
+            let max_attempts = recurring.max_attempts.unwrap_or(config.max_attempts);
+            for _ in 0..count {
+                let job = JobRecord::new(&recurring.queue, &recurring.job_name, recurring.payload.clone(), max_attempts);
+                match store.enqueue(&job).await {
+                    Ok(()) => enqueued += 1,
+                    Err(e) => tracing::error!("投递周期任务 {} 失败: {}", recurring.name, e),
+                }
+            }
+        }
+        Ok(enqueued)
+    }
+
+    /// 启动后台调度任务，并在任务转储中列出周期任务
+    pub fn start(self) -> RunningJobScheduler {
+        let dump_section = format!(
+            "{}.{}",
+            SCHEDULED_JOBS_DUMP,
+            NEXT_SCHEDULER.fetch_add(1, Ordering::Relaxed)
+        );
+        let dump = ScheduledJobsDump { queue: self.queue.clone() };
+        register_task_dump_contributor(dump_section.clone(), Arc::new(dump));
+        let token = CancellationToken::new();
+        let cancelled = token.clone();
+        let task = tokio::spawn(async move {
+            let interval = self.queue.config().poll_interval;
+            tracing::info!("周期任务调度器已启动");
+            loop {
+                if let Err(e) = self.tick().await {
+                    tracing::error!("调度周期任务失败: {}", e);
+                }
+                tokio::select! {
+                    _ = cancelled.cancelled() => break,
+                    _ = tokio::time::sleep(interval) => {}
+                }
+            }
+            tracing::info!("周期任务调度器已停止");
+        });
+        RunningJobScheduler {
+            token,
+            task,
+            dump_section,
+        }
+    }
+}
+
+/// 运行中的周期任务调度器
+#[derive(Debug)]
+pub struct RunningJobScheduler {
+    /// 停止信号
+    token: CancellationToken,
+    /// 后台任务
+    task: JoinHandle<()>,
+    /// 任务转储中列出周期任务的部分名称
+    dump_section: String,
+}
+
+impl RunningJobScheduler {
+    /// 任务转储中列出本调度器周期任务的部分名称，如 `scheduled_jobs.1`
+    pub fn dump_section(&self) -> &str {
+        &self.dump_section
+    }
+
+    /// 停止调度，等待当前一轮投递完成
+    pub async fn stop(self) {
+        unregister_task_dump_contributor(&self.dump_section);
+        self.token.cancel();
+        let _ = self.task.await;
+    }
+}
+
+/// 在任务转储中列出周期任务
+struct ScheduledJobsDump {
+    /// 任务队列
+    queue: JobQueue,
+}
+
+#[async_trait]
+impl TaskDumpContributor for ScheduledJobsDump {
+    async fn dump(&self) -> Result<Value> {
+        let jobs: Vec<Value> = self
+            .queue
+            .store()
+            .recurring_jobs()
+            .await?
+            .into_iter()
+            .map(|recurring| {
+                json!({
+                    "name": recurring.name,
+                    "job_name": recurring.job_name,
+                    "queue": recurring.queue,
+                    "cron": recurring.cron,
+                    "next_run_at": recurring.next_run_at,
+                    "last_run_at": recurring.last_run_at,
+                })
+            })
+            .collect();
+        Ok(Value::Array(jobs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::JobsConfig;
+    use crate::store::InMemoryJobStore;
+    use std::sync::Arc;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Cleanup;
+
+    impl Job for Cleanup {
+        const NAME: &'static str = "cleanup";
+    }
+
+    /// 测试错过执行时间的处理策略
+    #[test]
+    fn test_due_count() {
+        let schedule = parse_cron("*/10 * * * *").unwrap();
+        let now: DateTime<Utc> = "2024-10-10T12:35:00Z".parse().unwrap();
+        let mut recurring = RecurringJob::new("cleanup", "*/10 * * * *", &Cleanup).unwrap();
+        let threshold = Duration::from_secs(60);
+
+        recurring.next_run_at = "2024-10-10T12:34:30Z".parse().unwrap();
+        assert_eq!(recurring.due_count(&schedule, now, threshold), 1);
+        recurring.next_run_at = "2024-10-10T12:00:00Z".parse().unwrap();
+        assert_eq!(recurring.due_count(&schedule, now, threshold), 1);
+        assert_eq!(recurring.clone().misfire(MisfirePolicy::FireAll).due_count(&schedule, now, threshold), 4);
+        assert_eq!(recurring.clone().misfire(MisfirePolicy::Skip).due_count(&schedule, now, threshold), 0);
+
+        assert!(parse_cron("every minute").is_err());
+    }
+
+    /// 测试重新注册保留下次执行时间，到期后只投递一次，后台调度按轮询间隔投递
+    #[tokio::test(start_paused = true)]
+    async fn test_scheduler_tick() {
+        let jobs = JobQueue::new(Arc::new(InMemoryJobStore::new()), JobsConfig::default());
+        let mut recurring = RecurringJob::new("cleanup", "0 * * * * *", &Cleanup).unwrap();
+        recurring.next_run_at = Utc::now() - chrono::Duration::seconds(1);
+        jobs.store().save_recurring(&recurring).await.unwrap();
+
+        let registered = jobs
+            .schedule(RecurringJob::new("cleanup", "0 * * * * *", &Cleanup).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(registered.next_run_at, recurring.next_run_at);
+
+        let scheduler = JobScheduler::new(jobs.clone());
+        assert_eq!(scheduler.tick().await.unwrap(), 1);
+        assert_eq!(scheduler.tick().await.unwrap(), 0);
+        assert_eq!(jobs.stats("default").await.unwrap().pending, 1);
+
+        let stored = jobs.recurring_jobs().await.unwrap();
+        assert!(stored[0].next_run_at > Utc::now());
+        assert!(stored[0].last_run_at.is_some());
+
+        let running = scheduler.start();
+        let other = JobScheduler::new(jobs.clone()).start();
+        assert_ne!(running.dump_section(), other.dump_section());
+        other.stop().await;
+        let dump = rspring_core::task_dump().await;
+        assert_eq!(dump.sections[running.dump_section()][0]["job_name"], "cleanup");
+
+        // 暂停的时钟在空闲时自动推进，等待超过一个轮询间隔后调度器已再次检查
+        let mut report = RecurringJob::new("report", "0 * * * * *", &Cleanup).unwrap();
+        report.next_run_at = Utc::now() - chrono::Duration::seconds(1);
+        jobs.store().save_recurring(&report).await.unwrap();
+        tokio::time::sleep(jobs.config().poll_interval + Duration::from_millis(1)).await;
+        assert_eq!(jobs.stats("default").await.unwrap().pending, 2);
+
+        let section = running.dump_section().to_string();
+        running.stop().await;
+        assert!(!rspring_core::task_dump().await.sections.contains_key(&section));
+    }
+}
@@ -0,0 +1,615 @@
+//! Redis 任务存储模块
+//!
+//! 每个队列使用以下键，键名中的 `{<队列>}` 为哈希标签，保证集群模式下同一队列的键位于同一槽：
+//! - `<前缀>{<队列>}:jobs`：任务 ID 到任务 JSON 的哈希
+//! - `<前缀>{<队列>}:pending`：待执行任务，分数为执行时间
+//! - `<前缀>{<队列>}:running`：执行中任务，分数为租约到期时间
+//! - `<前缀>{<队列>}:succeeded`、`<前缀>{<队列>}:dead`：已结束任务，分数为结束时间
+//! - `<前缀>{<队列>}:owners`：执行中任务 ID 到工作者的哈希
+//!
+//! 状态流转通过 Lua 脚本原子完成，队列名称记录在 `<前缀>queues` 集合中。
+//! 周期任务定义保存在 `<前缀>{recurring}:definitions` 哈希中，下次执行时间单独保存在
+//! `<前缀>{recurring}:next_runs` 哈希中用于比较并更新。
+//! 执行中任务的租约以 `running` 的分数为准，任务 JSON 中的 `locked_until` 只记录领取时的值
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rspring_core::clock;
+use rspring_core::page::{Page, PageResult};
+use rspring_core::{Error, Result};
+use rspring_data_redis::redis;
+use rspring_data_redis::RedisClient;
+
+use crate::job::{JobRecord, JobStatus};
+use crate::recurring::RecurringJob;
+use crate::store::{claim, lease_until, reset, JobStore, QueueStats};
+
+/// 领取到期的待执行任务和租约过期的执行中任务，只领取名称在 `ARGV[5..]` 中的任务，返回任务 ID
+const FETCH_SCRIPT: &str = r#"
+local limit = tonumber(ARGV[2])
+local names = {}
+for i = 5, #ARGV do
+    names[ARGV[i]] = true
+end
+local ids = {}
+local function collect(key, max)
+    local found = {}
+    local offset = 0
+    while #ids < limit do
+        local batch = redis.call('ZRANGEBYSCORE', key, '-inf', max, 'LIMIT', offset, 100)
+        if #batch == 0 then
+            break
+        end
+        for _, id in ipairs(batch) do
+            local job = redis.call('HGET', KEYS[4], id)
+            if job and names[cjson.decode(job).name] then
+                table.insert(ids, id)
+                table.insert(found, id)
+                if #ids >= limit then
+                    break
+                end
+            end
+        end
+        offset = offset + #batch
+    end
+    return found
+end
+collect(KEYS[2], '(' .. ARGV[1])
+for _, id in ipairs(collect(KEYS[1], ARGV[1])) do
+    redis.call('ZREM', KEYS[1], id)
+end
+for _, id in ipairs(ids) do
+    redis.call('ZADD', KEYS[2], ARGV[4], id)
+    redis.call('HSET', KEYS[3], id, ARGV[3])
+end
+return ids
+"#;
+
+/// 领取者一致时续约
+const HEARTBEAT_SCRIPT: &str = r#"
+if redis.call('HGET', KEYS[2], ARGV[1]) == ARGV[2] then
+    redis.call('ZADD', KEYS[1], ARGV[3], ARGV[1])
+    return 1
+end
+return 0
+"#;
+
+/// 领取者一致时结束执行，将任务移入目标集合并更新任务 JSON
+const FINISH_SCRIPT: &str = r#"
+if redis.call('HGET', KEYS[2], ARGV[1]) ~= ARGV[2] then
+    return 0
+end
+redis.call('ZREM', KEYS[1], ARGV[1])
+redis.call('HDEL', KEYS[2], ARGV[1])
+redis.call('ZADD', KEYS[3], ARGV[3], ARGV[1])
+redis.call('HSET', KEYS[4], ARGV[1], ARGV[4])
+return 1
+"#;
+
+/// 将死信任务移回待执行集合
+const RETRY_SCRIPT: &str = r#"
+if redis.call('ZREM', KEYS[1], ARGV[1]) == 0 then
+    return 0
+end
+redis.call('ZADD', KEYS[2], ARGV[2], ARGV[1])
+redis.call('HSET', KEYS[3], ARGV[1], ARGV[3])
+return 1
+"#;
+
+/// 删除任务
+const DELETE_SCRIPT: &str = r#"
+for i = 1, 4 do
+    redis.call('ZREM', KEYS[i], ARGV[1])
+end
+redis.call('HDEL', KEYS[5], ARGV[1])
+return redis.call('HDEL', KEYS[6], ARGV[1])
+"#;
+
+/// 删除在指定时间之前结束的任务
+const PURGE_SCRIPT: &str = r#"
+local count = 0
+for i = 1, 2 do
+    for _, id in ipairs(redis.call('ZRANGEBYSCORE', KEYS[i], '-inf', '(' .. ARGV[1])) do
+        redis.call('HDEL', KEYS[3], id)
+        count = count + 1
+    end
+    redis.call('ZREMRANGEBYSCORE', KEYS[i], '-inf', '(' .. ARGV[1])
+end
+return count
+"#;
+
+/// 保存周期任务定义
+const SAVE_RECURRING_SCRIPT: &str = r#"
+redis.call('HSET', KEYS[1], ARGV[1], ARGV[2])
+redis.call('HSET', KEYS[2], ARGV[1], ARGV[3])
+return 1
+"#;
+
+/// 下次执行时间一致时推进周期任务
+const ADVANCE_RECURRING_SCRIPT: &str = r#"
+if redis.call('HGET', KEYS[2], ARGV[1]) ~= ARGV[2] then
+    return 0
+end
+redis.call('HSET', KEYS[1], ARGV[1], ARGV[3])
+redis.call('HSET', KEYS[2], ARGV[1], ARGV[4])
+return 1
+"#;
+
+/// 队列统计
+const STATS_SCRIPT: &str = r#"
+return {
+    redis.call('ZCOUNT', KEYS[1], '-inf', ARGV[1]),
+    redis.call('ZCOUNT', KEYS[1], '(' .. ARGV[1], '+inf'),
+    redis.call('ZCARD', KEYS[2]),
+    redis.call('ZCARD', KEYS[3]),
+    redis.call('ZCARD', KEYS[4])
+}
+"#;
+
+/// Redis 任务存储
+///
+/// # 示例
+/// ```rust
+/// let store = RedisJobStore::new(redis.clone()).with_prefix("myapp:jobs:");
+/// ```
+#[derive(Clone)]
+pub struct RedisJobStore {
+    /// Redis 客户端
+    client: RedisClient,
+    /// 键前缀
+    prefix: String,
+}
+
+impl RedisJobStore {
+    /// 创建任务存储，键前缀为 `jobs:`
+    pub fn new(client: RedisClient) -> Self {
+        Self {
+            client,
+            prefix: "jobs:".to_string(),
+        }
+    }
+
+    /// 设置键前缀
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// 队列的键
+    fn key(&self, queue: &str, suffix: &str) -> String {
+        format!("{}{{{}}}:{}", self.prefix, queue, suffix)
+    }
+
+    /// 周期任务的键，后缀与队列的键不同，不会与名为 `recurring` 的队列冲突
+    fn recurring_key(&self, suffix: &str) -> String {
+        self.key("recurring", suffix)
+    }
+
+    /// 状态对应的有序集合
+    fn status_key(&self, queue: &str, status: JobStatus) -> String {
+        self.key(queue, status.as_str())
+    }
+
+    /// 执行 Lua 脚本
+    async fn eval<T: redis::FromRedisValue>(
+        &self,
+        script: &str,
+        keys: &[String],
+        args: &[String],
+    ) -> Result<T> {
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(script).arg(keys.len()).arg(keys).arg(args);
+        self.client.query(&cmd).await
+    }
+
+    /// 结束执行中的任务
+    async fn finish(
+        &self,
+        job: &JobRecord,
+        target: JobStatus,
+        score: DateTime<Utc>,
+        record: &JobRecord,
+    ) -> Result<()> {
+        let queue = &job.queue;
+        let _: i64 = self
+            .eval(
+                FINISH_SCRIPT,
+                &[
+                    self.status_key(queue, JobStatus::Running),
+                    self.key(queue, "owners"),
+                    self.status_key(queue, target),
+                    self.key(queue, "jobs"),
+                ],
+                &[
+                    job.id.clone(),
+                    job.locked_by.clone().unwrap_or_default(),
+                    score.timestamp_millis().to_string(),
+                    to_json(record)?,
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// 批量读取任务 JSON，忽略已删除的任务
+    async fn load(&self, queue: &str, ids: &[String]) -> Result<Vec<JobRecord>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let values: Vec<Option<String>> = self
+            .client
+            .query(redis::cmd("HMGET").arg(self.key(queue, "jobs")).arg(ids))
+            .await?;
+        values
+            .into_iter()
+            .flatten()
+            .map(|value| from_json(&value))
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for RedisJobStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisJobStore")
+            .field("prefix", &self.prefix)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl JobStore for RedisJobStore {
+    async fn enqueue(&self, job: &JobRecord) -> Result<()> {
+        let mut cmd = redis::cmd("HSET");
+        cmd.arg(self.key(&job.queue, "jobs"))
+            .arg(&job.id)
+            .arg(to_json(job)?);
+        let _: i64 = self.client.query(&cmd).await?;
+
+        let mut cmd = redis::cmd("ZADD");
+        cmd.arg(self.status_key(&job.queue, JobStatus::Pending))
+            .arg(job.run_at.timestamp_millis())
+            .arg(&job.id);
+        let _: i64 = self.client.query(&cmd).await?;
+
+        let _: i64 = self
+            .client
+            .query(
+                redis::cmd("SADD")
+                    .arg(format!("{}queues", self.prefix))
+                    .arg(&job.queue),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn fetch(
+        &self,
+        queue: &str,
+        names: &[String],
+        worker: &str,
+        limit: usize,
+        lease: Duration,
+    ) -> Result<Vec<JobRecord>> {
+        if names.is_empty() || limit == 0 {
+            return Ok(Vec::new());
+        }
+        let now = clock::now();
+        let until = lease_until(now, lease);
+        let mut args = vec![
+            now.timestamp_millis().to_string(),
+            limit.to_string(),
+            worker.to_string(),
+            until.timestamp_millis().to_string(),
+        ];
+        args.extend(names.iter().cloned());
+        let ids: Vec<String> = self
+            .eval(
+                FETCH_SCRIPT,
+                &[
+                    self.status_key(queue, JobStatus::Pending),
+                    self.status_key(queue, JobStatus::Running),
+                    self.key(queue, "owners"),
+                    self.key(queue, "jobs"),
+                ],
+                &args,
+            )
+            .await?;
+
+        let mut jobs = self.load(queue, &ids).await?;
+        if jobs.is_empty() {
+            return Ok(jobs);
+        }
+        let mut cmd = redis::cmd("HSET");
+        cmd.arg(self.key(queue, "jobs"));
+        for job in &mut jobs {
+            claim(job, worker, now, lease);
+            cmd.arg(&job.id).arg(to_json(job)?);
+        }
+        let _: i64 = self.client.query(&cmd).await?;
+        Ok(jobs)
+    }
+
+    async fn heartbeat(&self, job: &JobRecord, lease: Duration) -> Result<bool> {
+        let extended: i64 = self
+            .eval(
+                HEARTBEAT_SCRIPT,
+                &[
+                    self.status_key(&job.queue, JobStatus::Running),
+                    self.key(&job.queue, "owners"),
+                ],
+                &[
+                    job.id.clone(),
+                    job.locked_by.clone().unwrap_or_default(),
+                    lease_until(clock::now(), lease)
+                        .timestamp_millis()
+                        .to_string(),
+                ],
+            )
+            .await?;
+        Ok(extended == 1)
+    }
+
+    async fn complete(&self, job: &JobRecord) -> Result<()> {
+        let now = clock::now();
+        let mut record = job.clone();
+        record.status = JobStatus::Succeeded;
+        record.locked_by = None;
+        record.locked_until = None;
+        record.updated_at = now;
+        self.finish(job, JobStatus::Succeeded, now, &record).await
+    }
+
+    async fn fail(
+        &self,
+        job: &JobRecord,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let now = clock::now();
+        let mut record = job.clone();
+        record.status = if retry_at.is_some() {
+            JobStatus::Pending
+        } else {
+            JobStatus::Dead
+        };
+        record.run_at = retry_at.unwrap_or(record.run_at);
+        record.last_error = Some(error.to_string());
+        record.locked_by = None;
+        record.locked_until = None;
+        record.updated_at = now;
+        self.finish(job, record.status, retry_at.unwrap_or(now), &record)
+            .await
+    }
+
+    async fn queues(&self) -> Result<Vec<String>> {
+        let mut queues: Vec<String> = self
+            .client
+            .query(redis::cmd("SMEMBERS").arg(format!("{}queues", self.prefix)))
+            .await?;
+        queues.sort();
+        Ok(queues)
+    }
+
+    async fn stats(&self, queue: &str) -> Result<QueueStats> {
+        let counts: Vec<u64> = self
+            .eval(
+                STATS_SCRIPT,
+                &[
+                    self.status_key(queue, JobStatus::Pending),
+                    self.status_key(queue, JobStatus::Running),
+                    self.status_key(queue, JobStatus::Succeeded),
+                    self.status_key(queue, JobStatus::Dead),
+                ],
+                &[clock::now().timestamp_millis().to_string()],
+            )
+            .await?;
+        let count = |index: usize| counts.get(index).copied().unwrap_or_default();
+        Ok(QueueStats {
+            queue: queue.to_string(),
+            pending: count(0),
+            scheduled: count(1),
+            running: count(2),
+            succeeded: count(3),
+            dead: count(4),
+        })
+    }
+
+    async fn list(
+        &self,
+        queue: &str,
+        status: JobStatus,
+        page: Page,
+    ) -> Result<PageResult<JobRecord>> {
+        let key = self.status_key(queue, status);
+        let total: u64 = self.client.query(redis::cmd("ZCARD").arg(&key)).await?;
+        if page.size == 0 || page.offset() >= total {
+            return Ok(PageResult::new(Vec::new(), page.page, page.size, total));
+        }
+
+        // 待执行任务按执行时间升序，其他按结束时间降序
+        let command = if status == JobStatus::Pending {
+            "ZRANGE"
+        } else {
+            "ZREVRANGE"
+        };
+        let ids: Vec<String> = self
+            .client
+            .query(
+                redis::cmd(command)
+                    .arg(&key)
+                    .arg(page.offset())
+                    .arg(page.offset().saturating_add(page.size - 1)),
+            )
+            .await?;
+        let content = self.load(queue, &ids).await?;
+        Ok(PageResult::new(content, page.page, page.size, total))
+    }
+
+    async fn get(&self, queue: &str, id: &str) -> Result<Option<JobRecord>> {
+        let value: Option<String> = self
+            .client
+            .query(redis::cmd("HGET").arg(self.key(queue, "jobs")).arg(id))
+            .await?;
+        value.as_deref().map(from_json).transpose()
+    }
+
+    async fn retry(&self, queue: &str, id: &str) -> Result<bool> {
+        let Some(mut job) = self.get(queue, id).await? else {
+            return Ok(false);
+        };
+        let now = clock::now();
+        reset(&mut job, now);
+        let retried: i64 = self
+            .eval(
+                RETRY_SCRIPT,
+                &[
+                    self.status_key(queue, JobStatus::Dead),
+                    self.status_key(queue, JobStatus::Pending),
+                    self.key(queue, "jobs"),
+                ],
+                &[
+                    id.to_string(),
+                    now.timestamp_millis().to_string(),
+                    to_json(&job)?,
+                ],
+            )
+            .await?;
+        Ok(retried == 1)
+    }
+
+    async fn delete(&self, queue: &str, id: &str) -> Result<bool> {
+        let deleted: i64 = self
+            .eval(
+                DELETE_SCRIPT,
+                &[
+                    self.status_key(queue, JobStatus::Pending),
+                    self.status_key(queue, JobStatus::Running),
+                    self.status_key(queue, JobStatus::Succeeded),
+                    self.status_key(queue, JobStatus::Dead),
+                    self.key(queue, "owners"),
+                    self.key(queue, "jobs"),
+                ],
+                &[id.to_string()],
+            )
+            .await?;
+        Ok(deleted == 1)
+    }
+
+    async fn purge(&self, queue: &str, before: DateTime<Utc>) -> Result<u64> {
+        self.eval(
+            PURGE_SCRIPT,
+            &[
+                self.status_key(queue, JobStatus::Succeeded),
+                self.status_key(queue, JobStatus::Dead),
+                self.key(queue, "jobs"),
+            ],
+            &[before.timestamp_millis().to_string()],
+        )
+        .await
+    }
+
+    async fn save_recurring(&self, job: &RecurringJob) -> Result<()> {
+        let _: i64 = self
+            .eval(
+                SAVE_RECURRING_SCRIPT,
+                &[
+                    self.recurring_key("definitions"),
+                    self.recurring_key("next_runs"),
+                ],
+                &[
+                    job.name.clone(),
+                    to_json(job)?,
+                    job.next_run_at.timestamp_millis().to_string(),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn recurring_jobs(&self) -> Result<Vec<RecurringJob>> {
+        let values: Vec<String> = self
+            .client
+            .query(redis::cmd("HVALS").arg(self.recurring_key("definitions")))
+            .await?;
+        let mut jobs = values
+            .iter()
+            .map(|value| from_json::<RecurringJob>(value))
+            .collect::<Result<Vec<_>>>()?;
+        jobs.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(jobs)
+    }
+
+    async fn remove_recurring(&self, name: &str) -> Result<bool> {
+        let _: i64 = self
+            .client
+            .query(
+                redis::cmd("HDEL")
+                    .arg(self.recurring_key("next_runs"))
+                    .arg(name),
+            )
+            .await?;
+        let deleted: i64 = self
+            .client
+            .query(
+                redis::cmd("HDEL")
+                    .arg(self.recurring_key("definitions"))
+                    .arg(name),
+            )
+            .await?;
+        Ok(deleted == 1)
+    }
+
+    async fn advance_recurring(
+        &self,
+        name: &str,
+        expected: DateTime<Utc>,
+        next: DateTime<Utc>,
+    ) -> Result<bool> {
+        let value: Option<String> = self
+            .client
+            .query(
+                redis::cmd("HGET")
+                    .arg(self.recurring_key("definitions"))
+                    .arg(name),
+            )
+            .await?;
+        let Some(mut job) = value
+            .as_deref()
+            .map(from_json::<RecurringJob>)
+            .transpose()?
+        else {
+            return Ok(false);
+        };
+        job.next_run_at = next;
+        job.last_run_at = Some(clock::now());
+
+        let advanced: i64 = self
+            .eval(
+                ADVANCE_RECURRING_SCRIPT,
+                &[
+                    self.recurring_key("definitions"),
+                    self.recurring_key("next_runs"),
+                ],
+                &[
+                    name.to_string(),
+                    expected.timestamp_millis().to_string(),
+                    to_json(&job)?,
+                    next.timestamp_millis().to_string(),
+                ],
+            )
+            .await?;
+        Ok(advanced == 1)
+    }
+}
+
+/// 序列化任务记录或周期任务定义
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String> {
+    serde_json::to_string(value).map_err(|e| Error::internal(format!("任务序列化失败: {}", e)))
+}
+
+/// 反序列化任务记录或周期任务定义
+fn from_json<T: serde::de::DeserializeOwned>(value: &str) -> Result<T> {
+    serde_json::from_str(value).map_err(|e| Error::internal(format!("任务反序列化失败: {}", e)))
+}
@@ -0,0 +1,230 @@
+//! 定时任务模块
+//!
+//! `#[Scheduled]` 标注的异步函数在当前进程中按 cron 表达式、固定频率或固定延迟执行，与周期任务不同：
+//! - 不经过任务队列，每个实例各自执行，集群中只需执行一次时配合 `#[SchedulerLock]` 使用
+//! - 注解中的 cron 表达式和时长在编译期校验，无效时编译失败并指出出错的参数
+//! - 执行失败只记录日志，不影响后续执行
+//!
+//! cron 表达式按 UTC 计算，支持 6 段（含秒）和 5 段（不含秒）格式
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use rspring_core::{clock, Error, Result};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+
+use crate::recurring::{next_fire, parse_cron};
+
+/// 定时任务的触发方式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Trigger {
+    /// 按 cron 表达式执行
+    Cron(String),
+    /// 按固定频率执行，以上次开始时间计算下次执行时间，上次未完成时推迟
+    FixedRate(Duration),
+    /// 上次执行完成后等待固定时间再执行
+    FixedDelay(Duration),
+}
+
+/// 定时任务
+///
+/// 通常由 `#[Scheduled]` 生成的 `<函数名>_scheduled()` 创建
+///
+/// # 示例
+/// ```rust
+/// #[Scheduled(cron = "0 0 2 * * *")]
+/// async fn purge_sessions() -> Result<()> {
+///     SESSIONS.purge_expired().await
+/// }
+///
+/// let running = purge_sessions_scheduled().start()?;
+///
+/// // 应用退出时
+/// running.stop().await;
+/// ```
+#[derive(Clone)]
+pub struct ScheduledTask {
+    /// 任务名称
+    name: String,
+    /// 触发方式
+    trigger: Trigger,
+    /// 首次执行前的等待时间，cron 触发时忽略
+    initial_delay: Duration,
+    /// 任务函数
+    task: Arc<dyn Fn() -> BoxFuture<'static, Result<()>> + Send + Sync>,
+}
+
+impl ScheduledTask {
+    /// 创建定时任务
+    pub fn new<F, Fut>(name: impl Into<String>, trigger: Trigger, task: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        Self {
+            name: name.into(),
+            trigger,
+            initial_delay: Duration::ZERO,
+            task: Arc::new(move || Box::pin(task())),
+        }
+    }
+
+    /// 设置首次执行前的等待时间
+    ///
+    /// # 默认值
+    /// `0`
+    pub fn initial_delay(mut self, initial_delay: Duration) -> Self {
+        self.initial_delay = initial_delay;
+        self
+    }
+
+    /// 获取任务名称
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 获取触发方式
+    pub fn trigger(&self) -> &Trigger {
+        &self.trigger
+    }
+
+    /// 启动后台执行
+    ///
+    /// # 错误
+    /// cron 表达式无效或没有下次执行时间、固定频率或固定延迟为 0 时返回验证错误
+    pub fn start(self) -> Result<RunningScheduledTask> {
+        let schedule = match &self.trigger {
+            Trigger::Cron(cron) => {
+                let schedule = parse_cron(cron)?;
+                next_fire(&schedule, clock::now())?;
+                Some(schedule)
+            }
+            Trigger::FixedRate(period) | Trigger::FixedDelay(period) if period.is_zero() => {
+                return Err(Error::validation(format!("定时任务 {} 的执行间隔不能为 0", self.name)));
+            }
+            _ => None,
+        };
+
+        let token = CancellationToken::new();
+        let cancelled = token.clone();
+        let task = tokio::spawn(async move {
+            tracing::info!("定时任务 {} 已启动: {:?}", self.name, self.trigger);
+            match (&self.trigger, schedule) {
+                (Trigger::Cron(_), Some(schedule)) => loop {
+                    let wait = match next_fire(&schedule, clock::now()) {
+                        Ok(next) => (next - clock::now()).to_std().unwrap_or_default(),
+                        Err(e) => {
+                            tracing::warn!("定时任务 {} 不再执行: {}", self.name, e);
+                            break;
+                        }
+                    };
+                    tokio::select! {
+                        _ = cancelled.cancelled() => break,
+                        _ = tokio::time::sleep(wait) => self.run_once().await,
+                    }
+                },
+                (Trigger::FixedRate(period), _) => {
+                    let start = tokio::time::Instant::now() + self.initial_delay;
+                    let mut interval = tokio::time::interval_at(start, *period);
+                    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                    loop {
+                        tokio::select! {
+                            _ = cancelled.cancelled() => break,
+                            _ = interval.tick() => self.run_once().await,
+                        }
+                    }
+                }
+                (Trigger::FixedDelay(delay), _) => {
+                    let mut wait = self.initial_delay;
+                    loop {
+                        tokio::select! {
+                            _ = cancelled.cancelled() => break,
+                            _ = tokio::time::sleep(wait) => self.run_once().await,
+                        }
+                        wait = *delay;
+                    }
+                }
+                _ => {}
+            }
+            tracing::info!("定时任务 {} 已停止", self.name);
+        });
+        Ok(RunningScheduledTask { token, task })
+    }
+
+    /// 执行一次，失败只记录日志
+    async fn run_once(&self) {
+        if let Err(e) = (self.task)().await {
+            tracing::error!("定时任务 {} 执行失败: {}", self.name, e);
+        }
+    }
+}
+
+impl std::fmt::Debug for ScheduledTask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScheduledTask")
+            .field("name", &self.name)
+            .field("trigger", &self.trigger)
+            .field("initial_delay", &self.initial_delay)
+            .finish()
+    }
+}
+
+/// 运行中的定时任务
+#[derive(Debug)]
+pub struct RunningScheduledTask {
+    /// 停止信号
+    token: CancellationToken,
+    /// 后台任务
+    task: JoinHandle<()>,
+}
+
+impl RunningScheduledTask {
+    /// 停止执行，等待当前一次执行完成
+    pub async fn stop(self) {
+        self.token.cancel();
+        let _ = self.task.await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// 测试按固定频率执行，停止后不再执行
+    #[tokio::test]
+    async fn test_fixed_rate() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let counter = count.clone();
+        let task = ScheduledTask::new("tick", Trigger::FixedRate(Duration::from_millis(10)), move || {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            }
+        });
+
+        let running = task.start().unwrap();
+        tokio::time::sleep(Duration::from_millis(55)).await;
+        running.stop().await;
+        let executed = count.load(Ordering::SeqCst);
+        assert!(executed >= 3, "{}", executed);
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(count.load(Ordering::SeqCst), executed);
+    }
+
+    /// 测试手动创建的任务在启动时校验 cron 表达式和执行间隔
+    #[tokio::test]
+    async fn test_invalid_trigger() {
+        let task = ScheduledTask::new("report", Trigger::Cron("0 25 * * *".to_string()), || async { Ok(()) });
+        assert!(task.start().unwrap_err().is_validation_error());
+
+        let task = ScheduledTask::new("poll", Trigger::FixedDelay(Duration::ZERO), || async { Ok(()) });
+        assert!(task.start().unwrap_err().is_validation_error());
+    }
+}
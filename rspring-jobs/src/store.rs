@@ -0,0 +1,466 @@
+//! 任务存储模块
+//!
+//! [`JobStore`] 抽象任务的持久化和领取，同一任务同一时间只会被一个工作者领取：
+//! - 工作者领取到期的待执行任务，以及租约已过期的执行中任务（原工作者异常退出）
+//! - 完成、失败和续约都校验领取者，租约过期后被其他工作者重新领取的任务不会被原工作者改写
+//!
+//! 除执行相关的方法外，还提供队列统计、分页查询、手动重试和清理等管理方法，便于实现任务看板，
+//! 以及周期任务定义的持久化
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rspring_core::clock;
+use rspring_core::page::{Page, PageResult};
+use rspring_core::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::job::{JobRecord, JobStatus};
+use crate::recurring::RecurringJob;
+
+/// 任务存储特征
+#[async_trait]
+pub trait JobStore: Send + Sync {
+    /// 保存新任务
+    async fn enqueue(&self, job: &JobRecord) -> Result<()>;
+
+    /// 领取最多 `limit` 个可执行且名称在 `names` 中的任务，领取后状态为执行中，尝试次数加 1
+    ///
+    /// 工作者只传入注册了处理器的任务名称，其他任务留给能处理它们的工作者
+    async fn fetch(
+        &self,
+        queue: &str,
+        names: &[String],
+        worker: &str,
+        limit: usize,
+        lease: Duration,
+    ) -> Result<Vec<JobRecord>>;
+
+    /// 延长租约，任务已不属于该工作者时返回 `false`
+    async fn heartbeat(&self, job: &JobRecord, lease: Duration) -> Result<bool>;
+
+    /// 标记任务执行成功
+    async fn complete(&self, job: &JobRecord) -> Result<()>;
+
+    /// 记录任务失败，`retry_at` 为 `None` 时任务进入死信状态
+    async fn fail(
+        &self,
+        job: &JobRecord,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<()>;
+
+    /// 已有任务的队列名称
+    async fn queues(&self) -> Result<Vec<String>>;
+
+    /// 队列统计
+    async fn stats(&self, queue: &str) -> Result<QueueStats>;
+
+    /// 分页查询指定状态的任务，待执行任务按执行时间升序，其他按更新时间降序
+    async fn list(
+        &self,
+        queue: &str,
+        status: JobStatus,
+        page: Page,
+    ) -> Result<PageResult<JobRecord>>;
+
+    /// 查询任务
+    async fn get(&self, queue: &str, id: &str) -> Result<Option<JobRecord>>;
+
+    /// 立即重新执行死信任务，尝试次数清零，任务不存在或不是死信状态时返回 `false`
+    async fn retry(&self, queue: &str, id: &str) -> Result<bool>;
+
+    /// 删除任务，任务不存在时返回 `false`
+    async fn delete(&self, queue: &str, id: &str) -> Result<bool>;
+
+    /// 删除在 `before` 之前结束的成功和死信任务，返回删除的数量
+    async fn purge(&self, queue: &str, before: DateTime<Utc>) -> Result<u64>;
+
+    /// 保存周期任务定义，同名定义存在时覆盖
+    async fn save_recurring(&self, job: &RecurringJob) -> Result<()>;
+
+    /// 所有周期任务定义，按名称排序
+    async fn recurring_jobs(&self) -> Result<Vec<RecurringJob>>;
+
+    /// 删除周期任务定义，不存在时返回 `false`
+    async fn remove_recurring(&self, name: &str) -> Result<bool>;
+
+    /// 下次执行时间仍为 `expected` 时推进到 `next` 并记录投递时间，返回是否推进成功
+    ///
+    /// 多个实例同时调度时只有一个实例推进成功并负责投递
+    async fn advance_recurring(
+        &self,
+        name: &str,
+        expected: DateTime<Utc>,
+        next: DateTime<Utc>,
+    ) -> Result<bool>;
+}
+
+/// 队列统计
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct QueueStats {
+    /// 队列名称
+    pub queue: String,
+    /// 已到执行时间、等待领取的任务数
+    pub pending: u64,
+    /// 尚未到执行时间的任务数，包括等待重试的任务
+    pub scheduled: u64,
+    /// 执行中的任务数
+    pub running: u64,
+    /// 执行成功的任务数
+    pub succeeded: u64,
+    /// 死信任务数
+    pub dead: u64,
+}
+
+/// 内存任务存储，仅用于开发和测试
+#[derive(Debug, Default)]
+pub struct InMemoryJobStore {
+    /// 任务 ID 到任务的映射
+    jobs: Mutex<HashMap<String, JobRecord>>,
+    /// 名称到周期任务定义的映射
+    recurring: Mutex<HashMap<String, RecurringJob>>,
+}
+
+impl InMemoryJobStore {
+    /// 创建空的内存存储
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 在锁内修改属于该工作者的执行中任务
+    fn update_owned(&self, job: &JobRecord, update: impl FnOnce(&mut JobRecord)) -> bool {
+        let mut jobs = self.jobs.lock().expect("任务存储锁已损坏");
+        match jobs.get_mut(&job.id) {
+            Some(stored)
+                if stored.status == JobStatus::Running && stored.locked_by == job.locked_by =>
+            {
+                update(stored);
+                stored.updated_at = clock::now();
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[async_trait]
+impl JobStore for InMemoryJobStore {
+    async fn enqueue(&self, job: &JobRecord) -> Result<()> {
+        self.jobs
+            .lock()
+            .expect("任务存储锁已损坏")
+            .insert(job.id.clone(), job.clone());
+        Ok(())
+    }
+
+    async fn fetch(
+        &self,
+        queue: &str,
+        names: &[String],
+        worker: &str,
+        limit: usize,
+        lease: Duration,
+    ) -> Result<Vec<JobRecord>> {
+        let now = clock::now();
+        let mut jobs = self.jobs.lock().expect("任务存储锁已损坏");
+        let mut due: Vec<&mut JobRecord> = jobs
+            .values_mut()
+            .filter(|job| job.queue == queue && names.contains(&job.name) && is_claimable(job, now))
+            .collect();
+        due.sort_by_key(|job| job.run_at);
+
+        Ok(due
+            .into_iter()
+            .take(limit)
+            .map(|job| {
+                claim(job, worker, now, lease);
+                job.clone()
+            })
+            .collect())
+    }
+
+    async fn heartbeat(&self, job: &JobRecord, lease: Duration) -> Result<bool> {
+        Ok(self.update_owned(job, |stored| {
+            stored.locked_until = Some(lease_until(clock::now(), lease))
+        }))
+    }
+
+    async fn complete(&self, job: &JobRecord) -> Result<()> {
+        self.update_owned(job, |stored| {
+            stored.status = JobStatus::Succeeded;
+            stored.locked_by = None;
+            stored.locked_until = None;
+        });
+        Ok(())
+    }
+
+    async fn fail(
+        &self,
+        job: &JobRecord,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        self.update_owned(job, |stored| {
+            stored.status = if retry_at.is_some() {
+                JobStatus::Pending
+            } else {
+                JobStatus::Dead
+            };
+            stored.run_at = retry_at.unwrap_or(stored.run_at);
+            stored.last_error = Some(error.to_string());
+            stored.locked_by = None;
+            stored.locked_until = None;
+        });
+        Ok(())
+    }
+
+    async fn queues(&self) -> Result<Vec<String>> {
+        let jobs = self.jobs.lock().expect("任务存储锁已损坏");
+        let mut queues: Vec<String> = jobs.values().map(|job| job.queue.clone()).collect();
+        queues.sort();
+        queues.dedup();
+        Ok(queues)
+    }
+
+    async fn stats(&self, queue: &str) -> Result<QueueStats> {
+        let now = clock::now();
+        let jobs = self.jobs.lock().expect("任务存储锁已损坏");
+        let mut stats = QueueStats {
+            queue: queue.to_string(),
+            ..QueueStats::default()
+        };
+        for job in jobs.values().filter(|job| job.queue == queue) {
+            match job.status {
+                JobStatus::Pending if job.run_at <= now => stats.pending += 1,
+                JobStatus::Pending => stats.scheduled += 1,
+                JobStatus::Running => stats.running += 1,
+                JobStatus::Succeeded => stats.succeeded += 1,
+                JobStatus::Dead => stats.dead += 1,
+            }
+        }
+        Ok(stats)
+    }
+
+    async fn list(
+        &self,
+        queue: &str,
+        status: JobStatus,
+        page: Page,
+    ) -> Result<PageResult<JobRecord>> {
+        let jobs = self.jobs.lock().expect("任务存储锁已损坏");
+        let mut matched: Vec<&JobRecord> = jobs
+            .values()
+            .filter(|job| job.queue == queue && job.status == status)
+            .collect();
+        if status == JobStatus::Pending {
+            matched.sort_by_key(|job| job.run_at);
+        } else {
+            matched.sort_by_key(|job| std::cmp::Reverse(job.updated_at));
+        }
+
+        let total = matched.len() as u64;
+        let content = matched
+            .into_iter()
+            .skip(page.offset() as usize)
+            .take(page.size as usize)
+            .cloned()
+            .collect();
+        Ok(PageResult::new(content, page.page, page.size, total))
+    }
+
+    async fn get(&self, queue: &str, id: &str) -> Result<Option<JobRecord>> {
+        let jobs = self.jobs.lock().expect("任务存储锁已损坏");
+        Ok(jobs.get(id).filter(|job| job.queue == queue).cloned())
+    }
+
+    async fn retry(&self, queue: &str, id: &str) -> Result<bool> {
+        let mut jobs = self.jobs.lock().expect("任务存储锁已损坏");
+        match jobs.get_mut(id) {
+            Some(job) if job.queue == queue && job.status == JobStatus::Dead => {
+                reset(job, clock::now());
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn delete(&self, queue: &str, id: &str) -> Result<bool> {
+        let mut jobs = self.jobs.lock().expect("任务存储锁已损坏");
+        if jobs.get(id).is_some_and(|job| job.queue == queue) {
+            jobs.remove(id);
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    async fn purge(&self, queue: &str, before: DateTime<Utc>) -> Result<u64> {
+        let mut jobs = self.jobs.lock().expect("任务存储锁已损坏");
+        let count = jobs.len();
+        jobs.retain(|_, job| {
+            !(job.queue == queue
+                && matches!(job.status, JobStatus::Succeeded | JobStatus::Dead)
+                && job.updated_at < before)
+        });
+        Ok((count - jobs.len()) as u64)
+    }
+
+    async fn save_recurring(&self, job: &RecurringJob) -> Result<()> {
+        self.recurring
+            .lock()
+            .expect("任务存储锁已损坏")
+            .insert(job.name.clone(), job.clone());
+        Ok(())
+    }
+
+    async fn recurring_jobs(&self) -> Result<Vec<RecurringJob>> {
+        let recurring = self.recurring.lock().expect("任务存储锁已损坏");
+        let mut jobs: Vec<RecurringJob> = recurring.values().cloned().collect();
+        jobs.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(jobs)
+    }
+
+    async fn remove_recurring(&self, name: &str) -> Result<bool> {
+        Ok(self
+            .recurring
+            .lock()
+            .expect("任务存储锁已损坏")
+            .remove(name)
+            .is_some())
+    }
+
+    async fn advance_recurring(
+        &self,
+        name: &str,
+        expected: DateTime<Utc>,
+        next: DateTime<Utc>,
+    ) -> Result<bool> {
+        let mut recurring = self.recurring.lock().expect("任务存储锁已损坏");
+        match recurring.get_mut(name) {
+            Some(job) if job.next_run_at == expected => {
+                job.next_run_at = next;
+                job.last_run_at = Some(clock::now());
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+/// 任务是否可以被领取：已到执行时间的待执行任务，或租约已过期的执行中任务
+pub(crate) fn is_claimable(job: &JobRecord, now: DateTime<Utc>) -> bool {
+    match job.status {
+        JobStatus::Pending => job.run_at <= now,
+        JobStatus::Running => job.locked_until.is_some_and(|until| until < now),
+        _ => false,
+    }
+}
+
+/// 将任务标记为被工作者领取
+pub(crate) fn claim(job: &mut JobRecord, worker: &str, now: DateTime<Utc>, lease: Duration) {
+    job.status = JobStatus::Running;
+    job.attempts += 1;
+    job.locked_by = Some(worker.to_string());
+    job.locked_until = Some(lease_until(now, lease));
+    job.updated_at = now;
+}
+
+/// 将死信任务重置为立即执行
+pub(crate) fn reset(job: &mut JobRecord, now: DateTime<Utc>) {
+    job.status = JobStatus::Pending;
+    job.attempts = 0;
+    job.run_at = now;
+    job.updated_at = now;
+}
+
+/// 租约到期时间
+pub(crate) fn lease_until(now: DateTime<Utc>, lease: Duration) -> DateTime<Utc> {
+    chrono::Duration::from_std(lease)
+        .ok()
+        .and_then(|lease| now.checked_add_signed(lease))
+        .unwrap_or(DateTime::<Utc>::MAX_UTC)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// 测试领取、租约过期后重新领取、失败重试和死信
+    #[tokio::test]
+    async fn test_in_memory_store() {
+        let store = InMemoryJobStore::new();
+        let job = JobRecord::new("mailers", "welcome", json!({ "user_id": 1 }), 2);
+        store.enqueue(&job).await.unwrap();
+        let mut later = JobRecord::new("mailers", "welcome", json!({ "user_id": 2 }), 2);
+        later.run_at = Utc::now() + chrono::Duration::hours(1);
+        store.enqueue(&later).await.unwrap();
+
+        let stats = store.stats("mailers").await.unwrap();
+        assert_eq!((stats.pending, stats.scheduled), (1, 1));
+
+        // 租约过期后被其他工作者重新领取，原工作者无法再改写
+        let names = ["welcome".to_string()];
+        assert!(store
+            .fetch("mailers", &["digest".to_string()], "a", 10, Duration::ZERO)
+            .await
+            .unwrap()
+            .is_empty());
+        let first = store
+            .fetch("mailers", &names, "a", 10, Duration::ZERO)
+            .await
+            .unwrap();
+        assert_eq!(first.len(), 1);
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let second = store
+            .fetch("mailers", &names, "b", 10, Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(second[0].attempts, 2);
+        assert!(!store
+            .heartbeat(&first[0], Duration::from_secs(60))
+            .await
+            .unwrap());
+        store.complete(&first[0]).await.unwrap();
+        assert_eq!(store.stats("mailers").await.unwrap().running, 1);
+
+        store.fail(&second[0], "smtp timeout", None).await.unwrap();
+        let dead = store
+            .list("mailers", JobStatus::Dead, Page::new(0, 10))
+            .await
+            .unwrap();
+        assert_eq!(dead.total, 1);
+        assert_eq!(dead.content[0].last_error.as_deref(), Some("smtp timeout"));
+
+        assert!(store.retry("mailers", &job.id).await.unwrap());
+        let retried = store
+            .fetch("mailers", &names, "a", 10, Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(retried[0].attempts, 1);
+        store.complete(&retried[0]).await.unwrap();
+
+        assert_eq!(
+            store
+                .purge("mailers", Utc::now() + chrono::Duration::seconds(1))
+                .await
+                .unwrap(),
+            1
+        );
+        assert!(store.delete("mailers", &later.id).await.unwrap());
+        assert_eq!(
+            lease_until(Utc::now(), Duration::MAX),
+            DateTime::<Utc>::MAX_UTC
+        );
+        assert_eq!(
+            store.stats("mailers").await.unwrap(),
+            QueueStats {
+                queue: "mailers".to_string(),
+                ..QueueStats::default()
+            }
+        );
+    }
+}
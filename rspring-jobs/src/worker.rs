@@ -0,0 +1,433 @@
+//! 任务工作者模块
+//!
+//! 每个队列由一个后台任务轮询领取任务，同时执行的任务数不超过 `concurrency`：
+//! - 执行期间每半个租约续约一次，续约失败说明任务已被其他工作者领取
+//! - 执行失败的任务按退避策略等待后重试，达到最大尝试次数后进入死信状态并发布死信事件
+//! - 停止时不再领取新任务，并等待正在执行的任务完成
+//! - 执行期间输出的日志带有 `job_id` 字段
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rspring_core::clock;
+use rspring_core::logging::{with_log_context, JOB_ID_FIELD};
+use rspring_core::messaging::{publish_dead_letter, DeadLetter};
+use rspring_core::task::dump::task_registry;
+use rspring_core::{Error, Result};
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::job::{Job, JobRecord};
+use crate::queue::JobQueue;
+
+/// 任务处理器特征
+#[async_trait]
+pub trait JobHandler: Send + Sync {
+    /// 执行任务
+    async fn handle(&self, job: &JobRecord) -> Result<()>;
+}
+
+/// 将类型化的处理函数适配为 [`JobHandler`]
+struct TypedJobHandler<J, F> {
+    /// 处理函数
+    handler: F,
+    /// 任务类型
+    job: PhantomData<fn() -> J>,
+}
+
+#[async_trait]
+impl<J, F, Fut> JobHandler for TypedJobHandler<J, F>
+where
+    J: Job,
+    F: Fn(J) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<()>> + Send,
+{
+    async fn handle(&self, job: &JobRecord) -> Result<()> {
+        (self.handler)(job.payload()?).await
+    }
+}
+
+/// 任务工作者
+///
+/// # 示例
+/// ```rust
+/// let worker = JobWorker::new(jobs.clone())
+///     .handle(|job: SendWelcomeEmail| async move { mailer.send_welcome(job.user_id).await })
+///     .concurrency("mailers", 4)
+///     .start();
+///
+/// // 应用退出时
+/// worker.stop().await;
+/// ```
+pub struct JobWorker {
+    /// 任务队列
+    queue: JobQueue,
+    /// 按队列和任务名称注册的处理器
+    handlers: HashMap<String, HashMap<String, Arc<dyn JobHandler>>>,
+    /// 按队列指定的并发数
+    concurrency: HashMap<String, usize>,
+}
+
+impl JobWorker {
+    /// 创建工作者
+    pub fn new(queue: JobQueue) -> Self {
+        Self {
+            queue,
+            handlers: HashMap::new(),
+            concurrency: HashMap::new(),
+        }
+    }
+
+    /// 注册任务类型的处理函数，处理任务默认队列中的该类型任务
+    pub fn handle<J, F, Fut>(self, handler: F) -> Self
+    where
+        J: Job,
+        F: Fn(J) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.handle_queue(J::QUEUE, handler)
+    }
+
+    /// 注册任务类型在指定队列中的处理函数
+    pub fn handle_queue<J, F, Fut>(self, queue: &str, handler: F) -> Self
+    where
+        J: Job,
+        F: Fn(J) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        let handler = TypedJobHandler {
+            handler,
+            job: PhantomData,
+        };
+        self.handler(queue, J::NAME, Arc::new(handler))
+    }
+
+    /// 注册指定队列和任务名称的处理器
+    pub fn handler(mut self, queue: &str, name: &str, handler: Arc<dyn JobHandler>) -> Self {
+        self.handlers
+            .entry(queue.to_string())
+            .or_default()
+            .insert(name.to_string(), handler);
+        self
+    }
+
+    /// 设置队列的并发数，未设置时使用 `[jobs]` 中的 `concurrency`
+    pub fn concurrency(mut self, queue: &str, concurrency: usize) -> Self {
+        self.concurrency
+            .insert(queue.to_string(), concurrency.max(1));
+        self
+    }
+
+    /// 为每个注册了处理器的队列启动轮询任务
+    pub fn start(self) -> RunningJobWorker {
+        let token = CancellationToken::new();
+        let worker_id = format!("{}-{}", hostname(), uuid::Uuid::new_v4().simple());
+        let tasks = self
+            .handlers
+            .into_iter()
+            .map(|(queue, handlers)| {
+                let concurrency = self
+                    .concurrency
+                    .get(&queue)
+                    .copied()
+                    .unwrap_or(self.queue.config().concurrency.max(1));
+                let poller = QueuePoller {
+                    queue: self.queue.clone(),
+                    name: queue,
+                    job_names: handlers.keys().cloned().collect(),
+                    worker_id: worker_id.clone(),
+                    handlers: Arc::new(handlers),
+                    permits: Arc::new(Semaphore::new(concurrency)),
+                    concurrency,
+                };
+                tokio::spawn(poller.run(token.clone()))
+            })
+            .collect();
+        RunningJobWorker { token, tasks }
+    }
+}
+
+impl std::fmt::Debug for JobWorker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let handlers: HashMap<&String, Vec<&String>> = self
+            .handlers
+            .iter()
+            .map(|(queue, handlers)| (queue, handlers.keys().collect()))
+            .collect();
+        f.debug_struct("JobWorker")
+            .field("handlers", &handlers)
+            .field("concurrency", &self.concurrency)
+            .finish()
+    }
+}
+
+/// 运行中的工作者
+#[derive(Debug)]
+pub struct RunningJobWorker {
+    /// 停止信号
+    token: CancellationToken,
+    /// 轮询任务
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl RunningJobWorker {
+    /// 停止领取新任务，并等待正在执行的任务完成
+    pub async fn stop(self) {
+        self.token.cancel();
+        for task in self.tasks {
+            let _ = task.await;
+        }
+    }
+}
+
+/// 单个队列的轮询器
+struct QueuePoller {
+    /// 任务队列
+    queue: JobQueue,
+    /// 队列名称
+    name: String,
+    /// 注册了处理器的任务名称，只领取这些任务
+    job_names: Vec<String>,
+    /// 工作者标识
+    worker_id: String,
+    /// 任务名称到处理器的映射
+    handlers: Arc<HashMap<String, Arc<dyn JobHandler>>>,
+    /// 并发许可
+    permits: Arc<Semaphore>,
+    /// 并发数
+    concurrency: usize,
+}
+
+impl QueuePoller {
+    /// 轮询循环
+    async fn run(self, token: CancellationToken) {
+        tracing::info!(
+            "开始处理任务队列 {}，并发数 {}",
+            self.name,
+            self.concurrency
+        );
+        let config = self.queue.config().clone();
+
+        while !token.is_cancelled() {
+            // 至少有一个空闲许可时才领取任务
+            let permit = tokio::select! {
+                _ = token.cancelled() => break,
+                permit = self.permits.clone().acquire_owned() => permit.expect("任务许可已关闭"),
+            };
+            let limit = self.permits.available_permits() + 1;
+
+            let jobs = match self
+                .queue
+                .store()
+                .fetch(
+                    &self.name,
+                    &self.job_names,
+                    &self.worker_id,
+                    limit,
+                    config.lease,
+                )
+                .await
+            {
+                Ok(jobs) => jobs,
+                Err(e) => {
+                    tracing::error!("领取任务队列 {} 的任务失败: {}", self.name, e);
+                    Vec::new()
+                }
+            };
+            if jobs.is_empty() {
+                drop(permit);
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    _ = tokio::time::sleep(config.poll_interval) => continue,
+                }
+            }
+
+            let mut permit = Some(permit);
+            for job in jobs {
+                let permit = match permit.take() {
+                    Some(permit) => permit,
+                    None => self
+                        .permits
+                        .clone()
+                        .acquire_owned()
+                        .await
+                        .expect("任务许可已关闭"),
+                };
+                let queue = self.queue.clone();
+                let handlers = self.handlers.clone();
+                tokio::spawn(async move {
+                    let fields = [(JOB_ID_FIELD, job.id.clone())];
+                    with_log_context(fields, execute(&queue, &handlers, job)).await;
+                    drop(permit);
+                });
+            }
+        }
+
+        // 等待正在执行的任务完成
+        let _ = self.permits.acquire_many(self.concurrency as u32).await;
+        tracing::info!("停止处理任务队列 {}", self.name);
+    }
+}
+
+/// 执行一个任务，执行期间定期续约
+async fn execute(
+    queue: &JobQueue,
+    handlers: &HashMap<String, Arc<dyn JobHandler>>,
+    job: JobRecord,
+) {
+    let store = queue.store();
+    let config = queue.config();
+    let tracked = task_registry().track("job", format!("{} {}", job.name, job.id));
+    tracked.running();
+
+    // 租约过期后被重新领取时尝试次数可能已超过上限
+    let result = if job.attempts > job.max_attempts {
+        Err(Error::runtime("执行超时或工作者异常退出"))
+    } else {
+        match handlers.get(&job.name) {
+            Some(handler) => {
+                let handling = handler.handle(&job);
+                tokio::pin!(handling);
+                let interval = (config.lease / 2).max(Duration::from_millis(100));
+                let mut ticker =
+                    tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+                loop {
+                    tokio::select! {
+                        result = &mut handling => break result,
+                        _ = ticker.tick() => match store.heartbeat(&job, config.lease).await {
+                            Ok(true) => {}
+                            Ok(false) => tracing::warn!("任务 {} 的租约已被其他工作者取得", job.id),
+                            Err(e) => tracing::warn!("任务 {} 续约失败: {}", job.id, e),
+                        },
+                    }
+                }
+            }
+            None => Err(Error::runtime(format!("未注册任务 {} 的处理器", job.name))),
+        }
+    };
+
+    let error = match result {
+        Ok(()) => {
+            if let Err(e) = store.complete(&job).await {
+                tracing::error!("标记任务 {} 执行成功失败: {}", job.id, e);
+            }
+            return;
+        }
+        Err(e) => e.to_string(),
+    };
+
+    if job.is_exhausted() {
+        if let Err(e) = store.fail(&job, &error, None).await {
+            tracing::error!("标记任务 {} 进入死信失败: {}", job.id, e);
+            return;
+        }
+        publish_dead_letter(&DeadLetter::new(
+            "jobs",
+            &job.queue,
+            &job.id,
+            job.attempts,
+            &error,
+        ));
+        return;
+    }
+
+    let delay = config.retry_policy().delay_for(job.attempts);
+    let retry_at =
+        clock::now() + chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::MAX);
+    tracing::warn!(
+        "任务 {} ({}) 第 {} 次执行失败，{:?} 后重试: {}",
+        job.id,
+        job.name,
+        job.attempts,
+        delay,
+        error
+    );
+    if let Err(e) = store.fail(&job, &error, Some(retry_at)).await {
+        tracing::error!("记录任务 {} 执行失败出错: {}", job.id, e);
+    }
+}
+
+/// 当前主机名，用于标识工作者
+fn hostname() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "worker".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::JobsConfig;
+    use crate::job::JobStatus;
+    use crate::store::InMemoryJobStore;
+    use rspring_core::page::Page;
+    use serde::{Deserialize, Serialize};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Charge {
+        amount: u32,
+    }
+
+    impl Job for Charge {
+        const NAME: &'static str = "charge";
+        const MAX_ATTEMPTS: Option<u32> = Some(2);
+    }
+
+    /// 测试执行成功、失败重试和进入死信
+    #[tokio::test]
+    async fn test_job_worker() {
+        let config = JobsConfig {
+            poll_interval: Duration::from_millis(10),
+            retry_delay: Duration::from_millis(10),
+            ..JobsConfig::default()
+        };
+        let jobs = JobQueue::new(Arc::new(InMemoryJobStore::new()), config);
+        jobs.enqueue(&Charge { amount: 10 }).await.unwrap();
+        jobs.enqueue(&Charge { amount: 0 }).await.unwrap();
+
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = calls.clone();
+        let worker = JobWorker::new(jobs.clone())
+            .handle(move |job: Charge| {
+                let counter = counter.clone();
+                async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    if job.amount == 0 {
+                        return Err(Error::validation("金额无效"));
+                    }
+                    Ok(())
+                }
+            })
+            .start();
+
+        let mut stats = jobs.stats("default").await.unwrap();
+        for _ in 0..200 {
+            if stats.succeeded == 1 && stats.dead == 1 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            stats = jobs.stats("default").await.unwrap();
+        }
+        worker.stop().await;
+
+        assert_eq!((stats.succeeded, stats.dead), (1, 1));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        let dead = jobs
+            .list("default", JobStatus::Dead, Page::new(0, 10))
+            .await
+            .unwrap();
+        assert_eq!(dead.content[0].attempts, 2);
+        assert!(dead.content[0]
+            .last_error
+            .as_deref()
+            .unwrap()
+            .contains("金额无效"));
+    }
+}
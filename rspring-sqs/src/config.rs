@@ -0,0 +1,201 @@
+//! SQS 配置模块
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use rspring_core::config::properties::Configuration;
+use rspring_core::messaging::{DeadLetterPolicy, ListenerSettings};
+use rspring_core::Result;
+use serde::{Deserialize, Serialize};
+
+/// SQS 单次接收的最大消息数
+pub const MAX_BATCH_SIZE: i32 = 10;
+
+/// SQS 长轮询的最大等待时间
+pub const MAX_WAIT_TIME: Duration = Duration::from_secs(20);
+
+/// SQS 配置
+///
+/// 对应配置文件中的 `[aws.sqs]` 章节，未配置 `region` 时使用 AWS 默认凭证链中的区域
+///
+/// # 示例
+/// ```toml
+/// [aws.sqs]
+/// region = "ap-northeast-1"
+/// # 本地开发时指向 LocalStack
+/// # endpoint = "http://localhost:4566"
+/// wait_time = "20s"
+/// max_messages = 10
+/// visibility_timeout = "30s"
+///
+/// [aws.sqs.queues]
+/// orders = "https://sqs.ap-northeast-1.amazonaws.com/123456789012/orders"
+///
+/// [aws.sqs.dead_letter]
+/// enabled = true
+/// max_deliveries = 5
+///
+/// [aws.sqs.listeners.orders]
+/// concurrency = 4
+/// batch_size = 5
+/// max_poll_interval = "5m"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct SqsConfig {
+    /// AWS 区域（可选）
+    #[serde(default)]
+    pub region: Option<String>,
+    /// 自定义服务地址，如 LocalStack（可选）
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// 长轮询等待时间，最大 20 秒
+    ///
+    /// # 默认值
+    /// `"20s"`
+    #[serde(default = "default_wait_time", with = "rspring_core::config::duration")]
+    pub wait_time: Duration,
+    /// 单次接收的最大消息数，取值 1 到 10，可按监听器覆盖
+    ///
+    /// # 默认值
+    /// `10`
+    #[serde(default = "default_max_messages")]
+    pub max_messages: i32,
+    /// 消息的可见性超时，处理时间超过一半时自动延长
+    ///
+    /// # 默认值
+    /// `"30s"`
+    #[serde(default = "default_visibility_timeout", with = "rspring_core::config::duration")]
+    pub visibility_timeout: Duration,
+    /// 队列别名到队列 URL 的映射，未配置的队列名通过 `GetQueueUrl` 查询
+    #[serde(default)]
+    pub queues: HashMap<String, String>,
+    /// 死信策略，处理失败达到最大投递次数的消息转发到 `<队列名>-dlq`
+    #[serde(default)]
+    pub dead_letter: DeadLetterPolicy,
+    /// 按队列的监听器设置，优先于 `#[SqsListener]` 注解的参数
+    #[serde(default)]
+    pub listeners: HashMap<String, ListenerSettings>,
+}
+
+impl Default for SqsConfig {
+    fn default() -> Self {
+        Self {
+            region: None,
+            endpoint: None,
+            wait_time: default_wait_time(),
+            max_messages: default_max_messages(),
+            visibility_timeout: default_visibility_timeout(),
+            queues: HashMap::new(),
+            dead_letter: DeadLetterPolicy::default(),
+            listeners: HashMap::new(),
+        }
+    }
+}
+
+impl SqsConfig {
+    /// 长轮询等待秒数，限制在 SQS 允许的范围内
+    pub fn wait_time_seconds(&self) -> i32 {
+        self.wait_time.min(MAX_WAIT_TIME).as_secs() as i32
+    }
+
+    /// 单次接收的消息数，限制在 SQS 允许的范围内
+    pub fn batch_size(&self) -> i32 {
+        self.max_messages.clamp(1, MAX_BATCH_SIZE)
+    }
+
+    /// 可见性超时秒数，至少 1 秒
+    pub fn visibility_timeout_seconds(&self) -> i32 {
+        self.visibility_timeout.as_secs().clamp(1, i32::MAX as u64) as i32
+    }
+
+    /// 获取队列监听器的消费设置
+    ///
+    /// 优先级从高到低为 `[aws.sqs.listeners.<队列>]`、注解参数、全局的 `max_messages`，
+    /// 并发消费者数默认为 1
+    ///
+    /// # 参数
+    /// * `queue` - 监听端点的队列，即注解中的队列 URL、别名或队列名
+    /// * `declared` - 注解参数中的设置
+    pub fn listener_settings(&self, queue: &str, declared: &ListenerSettings) -> ListenerSettings {
+        let defaults = ListenerSettings::new()
+            .concurrency(1)
+            .batch_size(self.batch_size() as u32);
+        match self.listeners.get(queue) {
+            Some(configured) => defaults.merge(declared).merge(configured),
+            None => defaults.merge(declared),
+        }
+    }
+
+    /// 检查监听器设置
+    ///
+    /// # 错误
+    /// 监听器设置无效时返回验证错误
+    pub fn validate(&self) -> Result<()> {
+        self.listeners.values().try_for_each(ListenerSettings::validate)
+    }
+}
+
+impl Configuration for SqsConfig {}
+
+// 默认值函数
+
+fn default_wait_time() -> Duration {
+    MAX_WAIT_TIME
+}
+
+fn default_max_messages() -> i32 {
+    MAX_BATCH_SIZE
+}
+
+fn default_visibility_timeout() -> Duration {
+    Duration::from_secs(30)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试默认值和取值范围限制
+    #[test]
+    fn test_sqs_config() {
+        let config: SqsConfig = toml::from_str("").unwrap();
+        assert_eq!(config, SqsConfig::default());
+        assert_eq!(config.wait_time_seconds(), 20);
+
+        let config: SqsConfig = toml::from_str(
+            r#"
+            wait_time = "1m"
+            max_messages = 50
+            visibility_timeout = "500ms"
+
+            [queues]
+            orders = "http://localhost:4566/000000000000/orders"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.wait_time_seconds(), 20);
+        assert_eq!(config.batch_size(), 10);
+        assert_eq!(config.visibility_timeout_seconds(), 1);
+        assert!(config.queues.contains_key("orders"));
+
+        let config: SqsConfig = toml::from_str(
+            r#"
+            max_messages = 8
+
+            [listeners.orders]
+            batch_size = 5
+            max_poll_interval = "5m"
+            "#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+        let declared = ListenerSettings::new().concurrency(4).batch_size(2);
+        let settings = config.listener_settings("orders", &declared);
+        assert_eq!(settings.concurrency, Some(4));
+        assert_eq!(settings.batch_size, Some(5));
+        assert_eq!(settings.max_poll_interval, Some(Duration::from_secs(300)));
+        let settings = config.listener_settings("payments", &ListenerSettings::new());
+        assert_eq!(settings.concurrency, Some(1));
+        assert_eq!(settings.batch_size, Some(8));
+    }
+}
@@ -0,0 +1,49 @@
+//! RSpring AWS SQS 启动器
+//!
+//! 根据 `[aws.sqs]` 配置创建 SQS 客户端，提供：
+//! - [`SqsTemplate`]：以 JSON 发送单条、延迟和批量消息
+//! - [`SqsListenerContainer`]：长轮询批量接收消息，处理期间自动延长可见性超时，
+//!   可按监听器设置并发消费者数、批量大小和最长处理时间
+//! - `#[SqsListener]` 注解：将异步函数声明为队列的消息处理器
+//!
+//! # 示例
+//! ```rust
+//! #[SqsListener("orders")]
+//! async fn on_order(event: OrderCreated) -> Result<()> {
+//!     tracing::info!("收到订单 {}", event.order_id);
+//!     Ok(())
+//! }
+//!
+//! let sqs = rspring_sqs::init_sqs(&context).await?;
+//! sqs.send("orders", &OrderCreated { order_id: 42 }).await?;
+//!
+//! let listeners = SqsListenerContainer::new(sqs)
+//!     .listener(on_order_listener())
+//!     .start();
+//! ```
+
+pub mod config;
+pub mod listener;
+pub mod message;
+pub mod template;
+
+pub use config::*;
+pub use listener::*;
+pub use message::*;
+pub use template::SqsTemplate;
+
+pub use aws_sdk_sqs;
+
+use rspring_core::{ApplicationContext, Result};
+
+/// 根据配置创建 SQS 客户端模板并注册到应用上下文
+///
+/// # 错误
+/// 配置格式错误时返回错误
+pub async fn init_sqs(context: &ApplicationContext) -> Result<SqsTemplate> {
+    let template = SqsTemplate::from_config(context.config_manager()).await?;
+    context.register_singleton(template.clone()).await;
+
+    tracing::info!("SQS 启动器初始化完成");
+    Ok(template)
+}
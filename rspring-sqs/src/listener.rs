@@ -0,0 +1,395 @@
+//! SQS 监听容器模块
+//!
+//! 每个监听的队列由 `concurrency` 个后台任务长轮询接收消息：
+//! - 一次最多接收 `batch_size` 条消息，同一批消息并发处理
+//! - 处理时间较长时定期延长消息的可见性超时，避免被重复投递；超过 `max_poll_interval`
+//!   后不再延长，消息在可见性超时后重新投递
+//! - 处理成功的消息批量删除，失败的消息保留，可见性超时后由 SQS 重新投递
+//! - 启用死信策略时，失败次数达到上限的消息转发到死信队列后删除
+//! - 消息带有 `traceparent` 属性时在发送方链路的子跨度中处理，否则开始新的链路
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use aws_sdk_sqs::types::{DeleteMessageBatchRequestEntry, MessageSystemAttributeName};
+use rspring_core::messaging::{publish_dead_letter, DeadLetter, ListenerSettings};
+use rspring_core::trace::{continue_trace, TraceContext};
+use rspring_core::Result;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::MAX_BATCH_SIZE;
+use crate::message::SqsMessage;
+use crate::template::{sqs_error, SqsTemplate};
+
+/// 接收失败后的重试间隔
+const RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// SQS 消息处理器特征
+///
+/// 返回 `Ok` 时删除消息，返回错误时消息在可见性超时后重新投递
+#[async_trait]
+pub trait SqsMessageHandler: Send + Sync {
+    /// 处理一条消息
+    async fn handle(&self, message: SqsMessage) -> Result<()>;
+}
+
+#[async_trait]
+impl<F, Fut> SqsMessageHandler for F
+where
+    F: Fn(SqsMessage) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<()>> + Send,
+{
+    async fn handle(&self, message: SqsMessage) -> Result<()> {
+        self(message).await
+    }
+}
+
+/// 监听端点，即队列与处理器的绑定
+///
+/// 通常由 `#[SqsListener]` 生成的 `<函数名>_listener()` 创建
+#[derive(Clone)]
+pub struct SqsListenerEndpoint {
+    /// 队列 URL、别名或队列名
+    queue: String,
+    /// 消息处理器
+    handler: Arc<dyn SqsMessageHandler>,
+    /// 死信队列，未指定时为 `<队列名>-dlq`
+    dead_letter_queue: Option<String>,
+    /// 注解参数中的消费设置，`[aws.sqs.listeners.<队列>]` 中的配置优先
+    settings: ListenerSettings,
+}
+
+impl SqsListenerEndpoint {
+    /// 创建监听端点
+    pub fn new(queue: impl Into<String>, handler: impl SqsMessageHandler + 'static) -> Self {
+        Self {
+            queue: queue.into(),
+            handler: Arc::new(handler),
+            dead_letter_queue: None,
+            settings: ListenerSettings::default(),
+        }
+    }
+
+    /// 指定死信队列，仍需在 `[aws.sqs.dead_letter]` 中启用死信策略
+    pub fn dead_letter_queue(mut self, queue: impl Into<String>) -> Self {
+        self.dead_letter_queue = Some(queue.into());
+        self
+    }
+
+    /// 设置并发消费者数
+    ///
+    /// # 默认值
+    /// `1`
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.settings.concurrency = Some(concurrency);
+        self
+    }
+
+    /// 设置单次接收的最大消息数，取值 1 到 10
+    ///
+    /// # 默认值
+    /// `[aws.sqs]` 中的 `max_messages`
+    pub fn batch_size(mut self, batch_size: u32) -> Self {
+        self.settings.batch_size = Some(batch_size);
+        self
+    }
+
+    /// 设置一批消息的最长处理时间，超过后不再延长可见性超时
+    ///
+    /// # 默认值
+    /// 不限制
+    pub fn max_poll_interval(mut self, max_poll_interval: Duration) -> Self {
+        self.settings.max_poll_interval = Some(max_poll_interval);
+        self
+    }
+
+    /// 获取监听的队列
+    pub fn queue(&self) -> &str {
+        &self.queue
+    }
+
+    /// 获取注解参数中的消费设置
+    pub fn settings(&self) -> &ListenerSettings {
+        &self.settings
+    }
+}
+
+impl std::fmt::Debug for SqsListenerEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SqsListenerEndpoint")
+            .field("queue", &self.queue)
+            .field("dead_letter_queue", &self.dead_letter_queue)
+            .field("settings", &self.settings)
+            .finish()
+    }
+}
+
+/// SQS 监听容器
+///
+/// # 示例
+/// ```rust
+/// #[SqsListener("orders")]
+/// async fn on_order(event: OrderCreated) -> Result<()> {
+///     tracing::info!("收到订单 {}", event.order_id);
+///     Ok(())
+/// }
+///
+/// let container = SqsListenerContainer::new(sqs.clone())
+///     .listener(on_order_listener())
+///     .start();
+///
+/// // 应用退出时
+/// container.stop().await;
+/// ```
+pub struct SqsListenerContainer {
+    /// SQS 客户端模板
+    template: SqsTemplate,
+    /// 监听端点
+    endpoints: Vec<SqsListenerEndpoint>,
+}
+
+impl SqsListenerContainer {
+    /// 创建监听容器
+    pub fn new(template: SqsTemplate) -> Self {
+        Self {
+            template,
+            endpoints: Vec::new(),
+        }
+    }
+
+    /// 添加监听端点
+    pub fn listener(mut self, endpoint: SqsListenerEndpoint) -> Self {
+        self.endpoints.push(endpoint);
+        self
+    }
+
+    /// 按每个端点的并发消费者数启动轮询任务
+    pub fn start(self) -> RunningSqsListeners {
+        let token = CancellationToken::new();
+        let mut tasks = Vec::new();
+        for endpoint in self.endpoints {
+            let settings = self
+                .template
+                .config()
+                .listener_settings(endpoint.queue(), &endpoint.settings);
+            for _ in 0..settings.concurrency.unwrap_or(1).max(1) {
+                let template = self.template.clone();
+                let endpoint = endpoint.clone();
+                let token = token.clone();
+                tasks.push(tokio::spawn(async move { poll(template, endpoint, settings, token).await }));
+            }
+        }
+        RunningSqsListeners { token, tasks }
+    }
+}
+
+/// 运行中的监听任务
+#[derive(Debug)]
+pub struct RunningSqsListeners {
+    /// 停止信号
+    token: CancellationToken,
+    /// 轮询任务
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl RunningSqsListeners {
+    /// 停止接收新消息，并等待正在处理的消息完成
+    pub async fn stop(self) {
+        self.token.cancel();
+        for task in self.tasks {
+            let _ = task.await;
+        }
+    }
+}
+
+/// 单个消费者的轮询循环
+async fn poll(
+    template: SqsTemplate,
+    endpoint: SqsListenerEndpoint,
+    settings: ListenerSettings,
+    token: CancellationToken,
+) {
+    let queue_url = loop {
+        match template.queue_url(endpoint.queue()).await {
+            Ok(url) => break url,
+            Err(e) => {
+                tracing::error!("解析 SQS 队列 {} 失败: {}", endpoint.queue(), e);
+                tokio::select! {
+                    _ = token.cancelled() => return,
+                    _ = tokio::time::sleep(RETRY_INTERVAL) => {}
+                }
+            }
+        }
+    };
+    tracing::info!("开始监听 SQS 队列 {}", endpoint.queue());
+
+    let config = template.config();
+    let batch_size = settings
+        .batch_size
+        .map_or(config.batch_size(), |size| size.clamp(1, MAX_BATCH_SIZE as u32) as i32);
+    while !token.is_cancelled() {
+        let request = template
+            .client()
+            .receive_message()
+            .queue_url(&queue_url)
+            .wait_time_seconds(config.wait_time_seconds())
+            .max_number_of_messages(batch_size)
+            .visibility_timeout(config.visibility_timeout_seconds())
+            .message_system_attribute_names(MessageSystemAttributeName::ApproximateReceiveCount)
+            .message_attribute_names("All")
+            .send();
+
+        let output = tokio::select! {
+            _ = token.cancelled() => break,
+            output = request => output,
+        };
+        let messages = match output {
+            Ok(output) => output.messages.unwrap_or_default(),
+            Err(e) => {
+                tracing::error!("{}", sqs_error(format!("接收 SQS 队列 {} 的消息失败", endpoint.queue()), e));
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    _ = tokio::time::sleep(RETRY_INTERVAL) => continue,
+                }
+            }
+        };
+
+        let messages: Vec<SqsMessage> = messages.iter().filter_map(SqsMessage::from_sdk).collect();
+        if messages.is_empty() {
+            continue;
+        }
+
+        let results = futures::future::join_all(
+            messages
+                .iter()
+                .map(|message| {
+                    handle(&template, &queue_url, &endpoint, settings.max_poll_interval, message.clone())
+                }),
+        )
+        .await;
+
+        let handled: Vec<&SqsMessage> = messages
+            .iter()
+            .zip(results)
+            .filter_map(|(message, handled)| handled.then_some(message))
+            .collect();
+        delete(&template, &queue_url, endpoint.queue(), &handled).await;
+    }
+    tracing::info!("停止监听 SQS 队列 {}", endpoint.queue());
+}
+
+/// 处理一条消息，处理期间定期延长可见性超时，返回是否处理成功
+///
+/// 处理时间超过 `max_poll_interval` 后不再延长可见性超时
+async fn handle(
+    template: &SqsTemplate,
+    queue_url: &str,
+    endpoint: &SqsListenerEndpoint,
+    max_poll_interval: Option<Duration>,
+    message: SqsMessage,
+) -> bool {
+    let message_id = message.message_id.clone();
+    let receipt_handle = message.receipt_handle.clone();
+    let receive_count = message.receive_count;
+    let body = message.body.clone();
+    let timeout = template.config().visibility_timeout_seconds();
+    let interval = Duration::from_secs((timeout as u64 / 2).max(1));
+    let deadline = max_poll_interval.map(|max| tokio::time::Instant::now() + max);
+
+    let parent = TraceContext::extract(|name| message.attributes.get(name).map(String::as_str));
+    let handling = continue_trace(parent, endpoint.handler.handle(message));
+    tokio::pin!(handling);
+    let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+
+    let mut extending = true;
+    let result = loop {
+        tokio::select! {
+            result = &mut handling => break result,
+            _ = ticker.tick(), if extending => {
+                if deadline.is_some_and(|deadline| tokio::time::Instant::now() >= deadline) {
+                    tracing::warn!("SQS 消息 {} 的处理时间超过最长处理时间，不再延长可见性超时", message_id);
+                    extending = false;
+                    continue;
+                }
+                let extended = template
+                    .client()
+                    .change_message_visibility()
+                    .queue_url(queue_url)
+                    .receipt_handle(&receipt_handle)
+                    .visibility_timeout(timeout)
+                    .send()
+                    .await;
+                if let Err(e) = extended {
+                    tracing::warn!("{}", sqs_error(format!("延长 SQS 消息 {} 的可见性超时失败", message_id), e));
+                }
+            }
+        }
+    };
+
+    let error = match result {
+        Ok(()) => return true,
+        Err(e) => e,
+    };
+    tracing::error!("处理 SQS 队列 {} 的消息 {} 失败: {}", endpoint.queue(), message_id, error);
+
+    let policy = &template.config().dead_letter;
+    if !policy.is_exhausted(receive_count) {
+        return false;
+    }
+    let destination = endpoint
+        .dead_letter_queue
+        .clone()
+        .unwrap_or_else(|| policy.destination_for(endpoint.queue()));
+    let dead_letter = DeadLetter::new("sqs", endpoint.queue(), message_id, receive_count, error)
+        .with_destination(destination.as_str());
+
+    // 转发成功后删除原消息，转发失败时保留，等待下次投递
+    match template.send_raw(&destination, body, &dead_letter.headers()).await {
+        Ok(_) => {
+            publish_dead_letter(&dead_letter);
+            true
+        }
+        Err(e) => {
+            tracing::error!("转发消息 {} 到死信队列 {} 失败: {}", dead_letter.message_id, destination, e);
+            false
+        }
+    }
+}
+
+/// 批量删除处理成功的消息
+async fn delete(template: &SqsTemplate, queue_url: &str, queue: &str, messages: &[&SqsMessage]) {
+    if messages.is_empty() {
+        return;
+    }
+
+    let entries = messages
+        .iter()
+        .enumerate()
+        .filter_map(|(index, message)| {
+            DeleteMessageBatchRequestEntry::builder()
+                .id(index.to_string())
+                .receipt_handle(&message.receipt_handle)
+                .build()
+                .ok()
+        })
+        .collect();
+
+    match template
+        .client()
+        .delete_message_batch()
+        .queue_url(queue_url)
+        .set_entries(Some(entries))
+        .send()
+        .await
+    {
+        Ok(output) => {
+            for failed in output.failed() {
+                tracing::warn!("删除 SQS 队列 {} 的消息失败: {} {}", queue, failed.code(), failed.message().unwrap_or_default());
+            }
+        }
+        Err(e) => tracing::warn!("{}", sqs_error(format!("删除 SQS 队列 {} 的消息失败", queue), e)),
+    }
+}
@@ -0,0 +1,112 @@
+//! SQS 消息模块
+
+use std::collections::HashMap;
+
+use aws_sdk_sqs::types::{Message, MessageSystemAttributeName};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use rspring_core::messaging::{from_message, message_converter};
+use rspring_core::{Error, Result};
+use serde::de::DeserializeOwned;
+
+/// 记录消息内容类型的消息属性
+pub const CONTENT_TYPE_ATTRIBUTE: &str = "contentType";
+
+/// 接收到的 SQS 消息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqsMessage {
+    /// 消息 ID
+    pub message_id: String,
+    /// 接收句柄，删除消息和修改可见性时使用
+    pub receipt_handle: String,
+    /// 消息体
+    pub body: String,
+    /// 字符串类型的消息属性
+    pub attributes: HashMap<String, String>,
+    /// 接收次数，首次接收为 1
+    pub receive_count: u32,
+}
+
+impl SqsMessage {
+    /// 从 SDK 消息转换，缺少消息 ID 或接收句柄时返回 `None`
+    pub fn from_sdk(message: &Message) -> Option<Self> {
+        let attributes = message
+            .message_attributes()
+            .map(|attributes| {
+                attributes
+                    .iter()
+                    .filter_map(|(name, value)| {
+                        value.string_value().map(|value| (name.clone(), value.to_string()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let receive_count = message
+            .attributes()
+            .and_then(|attributes| attributes.get(&MessageSystemAttributeName::ApproximateReceiveCount))
+            .and_then(|count| count.parse().ok())
+            .unwrap_or(1);
+
+        Some(Self {
+            message_id: message.message_id()?.to_string(),
+            receipt_handle: message.receipt_handle()?.to_string(),
+            body: message.body().unwrap_or_default().to_string(),
+            attributes,
+            receive_count,
+        })
+    }
+
+    /// 使用全局消息转换器将消息体反序列化为指定类型
+    ///
+    /// # 错误
+    /// 消息体无法解码或与类型不匹配时返回验证错误
+    pub fn payload<T: DeserializeOwned>(&self) -> Result<T> {
+        let converter = message_converter();
+        let payload = if converter.is_text() {
+            from_message(&*converter, self.body.as_bytes())
+        } else {
+            let bytes = BASE64
+                .decode(&self.body)
+                .map_err(|e| Error::validation(format!("消息体不是合法的 Base64: {}", e)))?;
+            from_message(&*converter, &bytes)
+        };
+        payload.map_err(|e| {
+            Error::validation(format!("SQS 消息 {} 反序列化失败: {}", self.message_id, e))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_sqs::types::MessageAttributeValue;
+
+    /// 测试从 SDK 消息转换并反序列化消息体
+    #[test]
+    fn test_from_sdk() {
+        let message = Message::builder()
+            .message_id("m-1")
+            .receipt_handle("r-1")
+            .body(r#"{"order_id": 42}"#)
+            .attributes(MessageSystemAttributeName::ApproximateReceiveCount, "3")
+            .message_attributes(
+                "tenant",
+                MessageAttributeValue::builder()
+                    .data_type("String")
+                    .string_value("acme")
+                    .build()
+                    .unwrap(),
+            )
+            .build();
+
+        let message = SqsMessage::from_sdk(&message).unwrap();
+        assert_eq!(message.receive_count, 3);
+        assert_eq!(message.attributes["tenant"], "acme");
+
+        let payload: serde_json::Value = message.payload().unwrap();
+        assert_eq!(payload["order_id"], 42);
+        assert!(message.payload::<Vec<u8>>().is_err());
+
+        assert!(SqsMessage::from_sdk(&Message::builder().body("x").build()).is_none());
+    }
+}
@@ -0,0 +1,307 @@
+//! SQS 发送模块
+//!
+//! 在链路上下文中发送的消息附加 `traceparent`、`tracestate` 消息属性，监听容器处理消息时延续该链路
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use aws_config::{BehaviorVersion, Region};
+use aws_sdk_sqs::error::DisplayErrorContext;
+use aws_sdk_sqs::types::{MessageAttributeValue, SendMessageBatchRequestEntry};
+use aws_sdk_sqs::Client;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use rspring_core::config::ConfigurationManager;
+use rspring_core::messaging::{message_converter, to_message};
+use rspring_core::trace::outbound_trace_headers;
+use rspring_core::{Component, Error, Result};
+use serde::Serialize;
+
+use crate::config::{SqsConfig, MAX_BATCH_SIZE};
+use crate::message::CONTENT_TYPE_ATTRIBUTE;
+
+/// SQS 客户端模板
+///
+/// 克隆开销很小，克隆后共享同一个 SDK 客户端和队列 URL 缓存。
+/// 队列可以是完整的 URL、`[aws.sqs.queues]` 中的别名或队列名
+///
+/// # 示例
+/// ```rust
+/// let sqs = SqsTemplate::from_config(context.config_manager()).await?;
+/// sqs.send("orders", &OrderCreated { order_id: 42 }).await?;
+/// ```
+#[derive(Clone)]
+pub struct SqsTemplate {
+    /// SDK 客户端
+    client: Client,
+    /// SQS 配置
+    config: Arc<SqsConfig>,
+    /// 队列名到 URL 的缓存
+    queue_urls: Arc<RwLock<HashMap<String, String>>>,
+}
+
+impl SqsTemplate {
+    /// 使用已有的 SDK 客户端创建
+    pub fn new(client: Client, config: SqsConfig) -> Self {
+        Self {
+            client,
+            config: Arc::new(config),
+            queue_urls: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// 根据 SQS 配置和 AWS 默认凭证链创建
+    pub async fn connect(config: SqsConfig) -> Self {
+        let mut loader = aws_config::defaults(BehaviorVersion::latest());
+        if let Some(region) = &config.region {
+            loader = loader.region(Region::new(region.clone()));
+        }
+        let sdk_config = loader.load().await;
+
+        let mut builder = aws_sdk_sqs::config::Builder::from(&sdk_config);
+        if let Some(endpoint) = &config.endpoint {
+            builder = builder.endpoint_url(endpoint);
+        }
+        Self::new(Client::from_conf(builder.build()), config)
+    }
+
+    /// 使用配置文件中的 `[aws.sqs]` 章节创建，未配置时使用默认值
+    ///
+    /// # 错误
+    /// 配置格式错误或监听器设置无效时返回错误
+    pub async fn from_config(config: &ConfigurationManager) -> Result<Self> {
+        let sqs: SqsConfig = if config.contains_key("aws.sqs") {
+            config.get_section("aws.sqs")?
+        } else {
+            SqsConfig::default()
+        };
+        sqs.validate()?;
+        Ok(Self::connect(sqs).await)
+    }
+
+    /// 获取 SDK 客户端
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// 获取 SQS 配置
+    pub fn config(&self) -> &SqsConfig {
+        &self.config
+    }
+
+    /// 解析队列 URL
+    ///
+    /// # 错误
+    /// 队列不存在或查询失败时返回错误
+    pub async fn queue_url(&self, queue: &str) -> Result<String> {
+        if queue.starts_with("https://") || queue.starts_with("http://") {
+            return Ok(queue.to_string());
+        }
+        if let Some(url) = self.config.queues.get(queue) {
+            return Ok(url.clone());
+        }
+        if let Some(url) = self.queue_urls.read().expect("队列 URL 缓存锁已损坏").get(queue) {
+            return Ok(url.clone());
+        }
+
+        let output = self
+            .client
+            .get_queue_url()
+            .queue_name(queue)
+            .send()
+            .await
+            .map_err(|e| sqs_error(format!("查询队列 {} 失败", queue), e))?;
+        let url = output
+            .queue_url()
+            .ok_or_else(|| Error::not_found(format!("SQS 队列 {}", queue)))?
+            .to_string();
+
+        self.queue_urls
+            .write()
+            .expect("队列 URL 缓存锁已损坏")
+            .insert(queue.to_string(), url.clone());
+        Ok(url)
+    }
+
+    /// 使用全局消息转换器编码并发送消息，返回消息 ID
+    ///
+    /// 消息的内容类型记录在 `contentType` 消息属性中，非文本格式的消息体以 Base64 编码
+    ///
+    /// # 错误
+    /// 序列化或发送失败时返回错误
+    pub async fn send<T: Serialize + ?Sized>(&self, queue: &str, payload: &T) -> Result<String> {
+        self.send_delayed(queue, payload, Duration::ZERO).await
+    }
+
+    /// 使用全局消息转换器编码并发送延迟消息，延迟最长 15 分钟
+    ///
+    /// # 错误
+    /// 序列化或发送失败时返回错误
+    pub async fn send_delayed<T: Serialize + ?Sized>(
+        &self,
+        queue: &str,
+        payload: &T,
+        delay: Duration,
+    ) -> Result<String> {
+        let url = self.queue_url(queue).await?;
+        let (body, content_type) = to_body(payload)?;
+
+        let mut request = self
+            .client
+            .send_message()
+            .queue_url(url)
+            .message_body(body)
+            .message_attributes(CONTENT_TYPE_ATTRIBUTE, content_type)
+            .delay_seconds(delay.as_secs() as i32);
+        for (name, value) in trace_attributes()? {
+            request = request.message_attributes(name, value);
+        }
+
+        let output = request
+            .send()
+            .await
+            .map_err(|e| sqs_error(format!("发送消息到队列 {} 失败", queue), e))?;
+        Ok(output.message_id().unwrap_or_default().to_string())
+    }
+
+    /// 发送原始消息体和字符串类型的消息属性，返回消息 ID
+    ///
+    /// 属性中没有链路头时附加当前链路的链路头
+    ///
+    /// # 错误
+    /// 发送失败时返回错误
+    pub async fn send_raw(
+        &self,
+        queue: &str,
+        body: impl Into<String>,
+        attributes: &[(&str, String)],
+    ) -> Result<String> {
+        let url = self.queue_url(queue).await?;
+        let mut request = self.client.send_message().queue_url(url).message_body(body);
+        for (name, value) in attributes {
+            request = request.message_attributes(*name, string_attribute(value)?);
+        }
+        for (name, value) in trace_attributes()? {
+            if !attributes.iter().any(|(existing, _)| *existing == name) {
+                request = request.message_attributes(name, value);
+            }
+        }
+
+        let output = request
+            .send()
+            .await
+            .map_err(|e| sqs_error(format!("发送消息到队列 {} 失败", queue), e))?;
+        Ok(output.message_id().unwrap_or_default().to_string())
+    }
+
+    /// 批量发送消息，每 10 条一批，返回成功发送的消息 ID
+    ///
+    /// # 错误
+    /// 任一消息发送失败时返回错误，之前的批次已经发送
+    pub async fn send_batch<T: Serialize>(&self, queue: &str, payloads: &[T]) -> Result<Vec<String>> {
+        let url = self.queue_url(queue).await?;
+        let mut message_ids = Vec::with_capacity(payloads.len());
+        let trace_attributes = trace_attributes()?;
+
+        for chunk in payloads.chunks(MAX_BATCH_SIZE as usize) {
+            let entries = chunk
+                .iter()
+                .enumerate()
+                .map(|(index, payload)| {
+                    let (body, content_type) = to_body(payload)?;
+                    let mut entry = SendMessageBatchRequestEntry::builder()
+                        .id(index.to_string())
+                        .message_body(body)
+                        .message_attributes(CONTENT_TYPE_ATTRIBUTE, content_type);
+                    for (name, value) in &trace_attributes {
+                        entry = entry.message_attributes(*name, value.clone());
+                    }
+                    entry
+                        .build()
+                        .map_err(|e| Error::internal(format!("构造批量消息失败: {}", e)))
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let output = self
+                .client
+                .send_message_batch()
+                .queue_url(&url)
+                .set_entries(Some(entries))
+                .send()
+                .await
+                .map_err(|e| sqs_error(format!("批量发送消息到队列 {} 失败", queue), e))?;
+
+            if let Some(failed) = output.failed().first() {
+                return Err(Error::application(format!(
+                    "批量发送消息到队列 {} 失败: {} {}",
+                    queue,
+                    failed.code(),
+                    failed.message().unwrap_or_default()
+                )));
+            }
+            message_ids.extend(
+                output
+                    .successful()
+                    .iter()
+                    .map(|entry| entry.message_id().to_string()),
+            );
+        }
+        Ok(message_ids)
+    }
+}
+
+impl std::fmt::Debug for SqsTemplate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SqsTemplate")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl Component for SqsTemplate {
+    fn component_name(&self) -> &'static str {
+        "SqsTemplate"
+    }
+}
+
+/// 使用全局消息转换器编码消息，返回消息体和内容类型属性
+///
+/// SQS 消息体只能是文本，非文本格式的编码结果以 Base64 编码
+fn to_body<T: Serialize + ?Sized>(payload: &T) -> Result<(String, MessageAttributeValue)> {
+    let converter = message_converter();
+    let bytes = to_message(&*converter, payload)?;
+    let body = if converter.is_text() {
+        String::from_utf8(bytes)
+            .map_err(|e| Error::validation(format!("SQS 消息体不是合法的 UTF-8: {}", e)))?
+    } else {
+        BASE64.encode(bytes)
+    };
+
+    Ok((body, string_attribute(converter.content_type())?))
+}
+
+/// 构造字符串类型的消息属性
+fn string_attribute(value: impl Into<String>) -> Result<MessageAttributeValue> {
+    MessageAttributeValue::builder()
+        .data_type("String")
+        .string_value(value)
+        .build()
+        .map_err(|e| Error::internal(format!("构造消息属性失败: {}", e)))
+}
+
+/// 当前链路的链路头，不在链路上下文中时为空
+fn trace_attributes() -> Result<Vec<(&'static str, MessageAttributeValue)>> {
+    outbound_trace_headers()
+        .into_iter()
+        .map(|(name, value)| Ok((name, string_attribute(value)?)))
+        .collect()
+}
+
+/// 将 SDK 错误转换为框架错误
+pub(crate) fn sqs_error<E>(context: String, error: E) -> Error
+where
+    E: std::error::Error + 'static,
+{
+    Error::application(format!("{}: {}", context, DisplayErrorContext(error)))
+}
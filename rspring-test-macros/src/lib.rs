@@ -0,0 +1,277 @@
+//! RSpring 测试注解
+//!
+//! 提供 `#[rspring_test]`，由 `rspring-test` 重新导出，生成的代码引用 `::rspring_test` 中的类型
+
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::parse_macro_input;
+
+/// 集成测试注解
+///
+/// 标注在异步测试函数上，为每个测试创建独立的 `rspring_test::TestContext`：
+/// 1. 以 `profile` 指定的环境（默认为 `test`）加载项目目录中的 `application.*` 和
+///    `application-{profile}.*`，`config` 中的内联 TOML 配置覆盖配置文件，
+///    `properties` 中 `key=value` 格式的配置项再覆盖内联配置，值按 TOML 解析
+/// 2. `containers` 中的服务（`postgres`、`redis`、`kafka`）在创建上下文前启动，连接地址覆盖配置文件，
+///    需要启用 `containers` 特性和本机可用的 Docker。只有 Redis 会自动初始化启动器（`data-redis` 特性），
+///    PostgreSQL 和 Kafka 只写入连接地址，需在 `setup` 中按配置创建客户端
+/// 3. 调用 `setup` 指定的函数注册组件，函数签名为 `async fn(&TestContext) -> Result<()>`
+/// 4. 执行自动装配，按参数类型注入组件：`Arc<T>` 为容器中的单例组件，`&TestContext` 为测试上下文，
+///    `&PublishedEvents` 为测试期间发布的事件
+/// 5. 测试函数返回后清空容器，删除服务容器和临时配置目录
+///
+/// 测试函数可以返回 `Result`，测试在 Tokio 单线程运行时中执行。初始化和测试函数在测试上下文的
+/// 全局组件作用域中执行，日志输出到测试框架捕获的输出中，并行的测试互不影响
+///
+/// # 示例
+///
+/// ```rust
+/// async fn register(context: &TestContext) -> Result<()> {
+///     context.register_singleton(OrderRepository::new()).await;
+///     context.register_singleton(OrderService::new()).await;
+///     Ok(())
+/// }
+///
+/// #[rspring_test(profile = "it", config = "[orders]\nmax_items = 3", setup = "register")]
+/// async fn creates_order(service: Arc<OrderService>, context: &TestContext) -> Result<()> {
+///     let order = service.create(vec![1, 2]).await?;
+///     assert_eq!(context.component::<OrderRepository>().await.count(), 1);
+///     Ok(())
+/// }
+///
+/// #[rspring_test(properties("server.port=0", "app.debug=true"))]
+/// async fn debug_enabled(context: &TestContext) {
+///     assert!(context.config.get::<bool>("app.debug").unwrap());
+/// }
+///
+/// #[rspring_test(containers(postgres, redis))]
+/// async fn caches_user(cache: Arc<RedisClient>) {
+///     cache.set("user:1", "Alice").await.unwrap();
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn rspring_test(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut test_args = TestArgs::default();
+    let parser = syn::meta::parser(|meta| test_args.parse(meta));
+    parse_macro_input!(args with parser);
+    let function = parse_macro_input!(input as syn::ItemFn);
+
+    match expand_rspring_test(&test_args, function) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// `#[rspring_test]` 的参数
+#[derive(Default)]
+struct TestArgs {
+    /// 激活的环境
+    profile: Option<syn::LitStr>,
+    /// 内联 TOML 配置
+    config: Option<syn::LitStr>,
+    /// `key=value` 格式的配置项
+    properties: Vec<syn::LitStr>,
+    /// 注册组件的函数
+    setup: Option<syn::Path>,
+    /// 启动的服务容器
+    containers: Vec<syn::Ident>,
+}
+
+impl TestArgs {
+    /// 解析单个参数
+    fn parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("profile") {
+            self.profile = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("config") {
+            self.config = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("properties") {
+            let content;
+            syn::parenthesized!(content in meta.input);
+            let properties =
+                syn::punctuated::Punctuated::<syn::LitStr, syn::Token![,]>::parse_terminated(
+                    &content,
+                )?;
+            for property in properties {
+                if !property.value().contains('=') {
+                    return Err(syn::Error::new_spanned(
+                        property,
+                        "配置项须为 key=value 格式",
+                    ));
+                }
+                self.properties.push(property);
+            }
+        } else if meta.path.is_ident("setup") {
+            self.setup = Some(meta.value()?.parse::<syn::LitStr>()?.parse()?);
+        } else if meta.path.is_ident("containers") {
+            meta.parse_nested_meta(|service| {
+                let ident = service
+                    .path
+                    .get_ident()
+                    .filter(|ident| SERVICES.iter().any(|(name, _)| ident == name))
+                    .ok_or_else(|| service.error("未知的服务容器，可选 postgres、redis、kafka"))?;
+                self.containers.push(ident.clone());
+                Ok(())
+            })?;
+        } else {
+            return Err(meta.error("未知的测试注解参数"));
+        }
+        Ok(())
+    }
+
+    /// 生成创建测试上下文的表达式
+    fn builder(&self) -> proc_macro2::TokenStream {
+        let mut builder = quote! {
+            ::rspring_test::TestContext::builder().project_dir(env!("CARGO_MANIFEST_DIR"))
+        };
+        if let Some(profile) = &self.profile {
+            builder = quote! { #builder.profile(#profile) };
+        }
+        if let Some(config) = &self.config {
+            builder = quote! { #builder.config(#config) };
+        }
+        if !self.properties.is_empty() {
+            let properties = &self.properties;
+            builder = quote! { #builder.properties([#(#properties),*]) };
+        }
+        if !self.containers.is_empty() {
+            let services = self.containers.iter().map(|ident| {
+                let (_, variant) = SERVICES.iter().find(|(name, _)| ident == name).unwrap();
+                let variant = format_ident!("{}", variant, span = ident.span());
+                quote! { ::rspring_test::Service::#variant }
+            });
+            builder = quote! {
+                #builder.containers(
+                    ::rspring_test::ServiceContainers::start(&[#(#services),*])
+                        .await
+                        .expect("启动测试容器失败"),
+                )
+            };
+        }
+        builder
+    }
+}
+
+/// `containers` 参数可选的服务及对应的 `Service` 变体
+const SERVICES: &[(&str, &str)] = &[
+    ("postgres", "Postgres"),
+    ("redis", "Redis"),
+    ("kafka", "Kafka"),
+];
+
+/// 展开 `#[rspring_test]`
+fn expand_rspring_test(
+    args: &TestArgs,
+    function: syn::ItemFn,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if function.sig.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(
+            &function.sig,
+            "#[rspring_test] 只能标注在异步函数上",
+        ));
+    }
+
+    // 按参数类型生成注入表达式
+    let mut injections = Vec::new();
+    for input in &function.sig.inputs {
+        let syn::FnArg::Typed(param) = input else {
+            return Err(syn::Error::new_spanned(
+                input,
+                "#[rspring_test] 只能标注在自由函数上",
+            ));
+        };
+        injections.push(injection(&param.ty)?);
+    }
+
+    let syn::ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = function;
+    let name = &sig.ident;
+    let output = &sig.output;
+    let body = format_ident!("__rspring_test_{}", name);
+    let mut body_sig = sig.clone();
+    body_sig.ident = body.clone();
+
+    let builder = args.builder();
+    let wire_containers = (!args.containers.is_empty()).then(|| {
+        quote! {
+            if let Err(e) = __context.wire_containers().await {
+                panic!("初始化测试容器的启动器失败: {}", e);
+            }
+        }
+    });
+    let setup = args.setup.as_ref().map(|setup| {
+        quote! {
+            if let Err(e) = #setup(&__context).await {
+                panic!("初始化测试上下文失败: {}", e);
+            }
+        }
+    });
+
+    Ok(quote! {
+        #[test]
+        #(#attrs)*
+        #vis fn #name() #output {
+            #body_sig #block
+
+            ::rspring_test::block_on(async {
+                let __context = #builder.build().expect("创建测试上下文失败");
+                let __result = __context
+                    .run(async {
+                        #wire_containers
+                        #setup
+                        if let Err(e) = __context.auto_wire().await {
+                            panic!("测试上下文自动装配失败: {}", e);
+                        }
+                        #body(#(#injections),*).await
+                    })
+                    .await;
+                __context.close().await;
+                __result
+            })
+        }
+    })
+}
+
+/// 生成测试函数参数的注入表达式
+fn injection(ty: &syn::Type) -> syn::Result<proc_macro2::TokenStream> {
+    let invalid = || {
+        syn::Error::new_spanned(
+            ty,
+            "#[rspring_test] 的参数须为 Arc<T> 组件、&TestContext 或 &PublishedEvents",
+        )
+    };
+    if let syn::Type::Reference(reference) = ty {
+        return match last_segment(&reference.elem) {
+            _ if reference.mutability.is_some() => Err(invalid()),
+            Some(segment) if segment.ident == "TestContext" => Ok(quote! { &__context }),
+            Some(segment) if segment.ident == "PublishedEvents" => {
+                Ok(quote! { __context.events() })
+            }
+            _ => Err(invalid()),
+        };
+    }
+
+    let segment = last_segment(ty)
+        .filter(|segment| segment.ident == "Arc")
+        .ok_or_else(invalid)?;
+    let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return Err(invalid());
+    };
+    match arguments.args.first() {
+        Some(syn::GenericArgument::Type(component)) if arguments.args.len() == 1 => {
+            Ok(quote! { __context.component::<#component>().await })
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// 获取类型路径的最后一段
+fn last_segment(ty: &syn::Type) -> Option<&syn::PathSegment> {
+    match ty {
+        syn::Type::Path(path) => path.path.segments.last(),
+        _ => None,
+    }
+}
@@ -0,0 +1,374 @@
+//! Web 测试客户端模块
+//!
+//! [`TestClient`] 在进程内调用完整的应用路由，不监听端口：
+//! - 请求带有回环地址的 `ConnectInfo`，与真实连接一样经过所有中间件
+//! - `get_json`、`post_json` 等方法以 JSON 收发请求和响应
+//! - [`TestResponse`] 提供状态码、响应头和 JSON 响应体的断言，失败时输出请求和响应体
+
+use std::net::{Ipv4Addr, SocketAddr};
+
+use axum::body::{Body, Bytes};
+use axum::extract::ConnectInfo;
+use axum::http::{header, HeaderMap, HeaderName, HeaderValue, Method, Request, StatusCode};
+use axum::Router;
+use rspring_web::WebServer;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use tower::ServiceExt;
+
+/// Web 测试客户端
+///
+/// # 示例
+/// ```rust
+/// let client = TestClient::new(UserController::routes()).bearer_auth("test-token");
+///
+/// let user = client.get_json::<ApiResponse<User>>("/api/users/1").await;
+/// assert_eq!(user.data.unwrap().name, "Alice");
+///
+/// client
+///     .post_json("/api/users", &json!({ "name": "" }))
+///     .await
+///     .assert_status(StatusCode::BAD_REQUEST)
+///     .assert_json_includes(json!({ "code": 400 }));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TestClient {
+    /// 应用路由
+    router: Router,
+    /// 每个请求都带有的请求头
+    headers: HeaderMap,
+}
+
+impl TestClient {
+    /// 使用应用路由创建客户端
+    pub fn new(router: Router) -> Self {
+        Self {
+            router,
+            headers: HeaderMap::new(),
+        }
+    }
+
+    /// 使用 Web 服务器的应用路由创建客户端
+    pub fn from_server(server: &WebServer) -> Self {
+        Self::new(server.router().clone())
+    }
+
+    /// 为每个请求添加请求头
+    ///
+    /// # Panics
+    /// 请求头名称或值无效时 panic
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        let name = HeaderName::try_from(name)
+            .unwrap_or_else(|e| panic!("无效的请求头名称 {}: {}", name, e));
+        let value = HeaderValue::try_from(value)
+            .unwrap_or_else(|e| panic!("无效的请求头值 {}: {}", value, e));
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// 为每个请求添加 `Authorization: Bearer` 请求头
+    pub fn bearer_auth(self, token: &str) -> Self {
+        self.header(header::AUTHORIZATION.as_str(), &format!("Bearer {}", token))
+    }
+
+    /// 发送请求
+    ///
+    /// # Panics
+    /// 路由处理失败时 panic
+    pub async fn send(&self, mut request: Request<Body>) -> TestResponse {
+        for (name, value) in &self.headers {
+            if !request.headers().contains_key(name) {
+                request.headers_mut().insert(name, value.clone());
+            }
+        }
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from((Ipv4Addr::LOCALHOST, 0))));
+        let description = format!("{} {}", request.method(), request.uri());
+
+        let response = self
+            .router
+            .clone()
+            .oneshot(request)
+            .await
+            .unwrap_or_else(|e| panic!("{} 处理失败: {}", description, e));
+        let (parts, body) = response.into_parts();
+        let body = axum::body::to_bytes(body, usize::MAX)
+            .await
+            .unwrap_or_else(|e| panic!("{} 读取响应体失败: {}", description, e));
+        TestResponse {
+            request: description,
+            status: parts.status,
+            headers: parts.headers,
+            body,
+        }
+    }
+
+    /// 发送不带请求体的请求
+    pub async fn request(&self, method: Method, uri: &str) -> TestResponse {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap_or_else(|e| panic!("无效的请求 {}: {}", uri, e));
+        self.send(request).await
+    }
+
+    /// 发送 JSON 请求体的请求
+    pub async fn request_json<B: Serialize + ?Sized>(
+        &self,
+        method: Method,
+        uri: &str,
+        body: &B,
+    ) -> TestResponse {
+        let body = serde_json::to_vec(body).expect("序列化请求体失败");
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .unwrap_or_else(|e| panic!("无效的请求 {}: {}", uri, e));
+        self.send(request).await
+    }
+
+    /// 发送 GET 请求
+    pub async fn get(&self, uri: &str) -> TestResponse {
+        self.request(Method::GET, uri).await
+    }
+
+    /// 发送 DELETE 请求
+    pub async fn delete(&self, uri: &str) -> TestResponse {
+        self.request(Method::DELETE, uri).await
+    }
+
+    /// 以 JSON 请求体发送 POST 请求
+    pub async fn post_json<B: Serialize + ?Sized>(&self, uri: &str, body: &B) -> TestResponse {
+        self.request_json(Method::POST, uri, body).await
+    }
+
+    /// 以 JSON 请求体发送 PUT 请求
+    pub async fn put_json<B: Serialize + ?Sized>(&self, uri: &str, body: &B) -> TestResponse {
+        self.request_json(Method::PUT, uri, body).await
+    }
+
+    /// 以 JSON 请求体发送 PATCH 请求
+    pub async fn patch_json<B: Serialize + ?Sized>(&self, uri: &str, body: &B) -> TestResponse {
+        self.request_json(Method::PATCH, uri, body).await
+    }
+
+    /// 发送 GET 请求并解析 JSON 响应体
+    ///
+    /// # Panics
+    /// 响应状态码不是 2xx 或响应体无法解析时 panic
+    pub async fn get_json<T: DeserializeOwned>(&self, uri: &str) -> T {
+        self.get(uri).await.assert_success().json()
+    }
+}
+
+/// 测试响应
+///
+/// 断言方法返回自身，可以连续调用
+#[derive(Debug, Clone)]
+pub struct TestResponse {
+    /// 请求方法和地址，用于断言失败信息
+    request: String,
+    /// 状态码
+    status: StatusCode,
+    /// 响应头
+    headers: HeaderMap,
+    /// 响应体
+    body: Bytes,
+}
+
+impl TestResponse {
+    /// 获取状态码
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// 获取响应头
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// 获取响应头的值，不存在或不是可见 ASCII 时返回 `None`
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name)?.to_str().ok()
+    }
+
+    /// 获取响应体
+    pub fn bytes(&self) -> &Bytes {
+        &self.body
+    }
+
+    /// 以文本获取响应体
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    /// 解析 JSON 响应体
+    ///
+    /// # Panics
+    /// 响应体无法解析为 `T` 时 panic
+    pub fn json<T: DeserializeOwned>(&self) -> T {
+        serde_json::from_slice(&self.body).unwrap_or_else(|e| {
+            panic!(
+                "{} 的响应体无法解析为 {}: {}\n响应体: {}",
+                self.request,
+                std::any::type_name::<T>(),
+                e,
+                self.text()
+            )
+        })
+    }
+
+    /// 断言状态码
+    pub fn assert_status(&self, status: StatusCode) -> &Self {
+        assert_eq!(
+            self.status,
+            status,
+            "{} 的状态码不符\n响应体: {}",
+            self.request,
+            self.text()
+        );
+        self
+    }
+
+    /// 断言状态码为 2xx
+    pub fn assert_success(&self) -> &Self {
+        assert!(
+            self.status.is_success(),
+            "{} 的状态码为 {}\n响应体: {}",
+            self.request,
+            self.status,
+            self.text()
+        );
+        self
+    }
+
+    /// 断言响应头的值
+    pub fn assert_header(&self, name: &str, value: &str) -> &Self {
+        assert_eq!(
+            self.header(name),
+            Some(value),
+            "{} 的响应头 {} 不符",
+            self.request,
+            name
+        );
+        self
+    }
+
+    /// 断言 JSON 响应体与期望值相等
+    pub fn assert_json<T: Serialize>(&self, expected: T) -> &Self {
+        let expected = serde_json::to_value(expected).expect("序列化期望值失败");
+        assert_eq!(
+            self.json::<Value>(),
+            expected,
+            "{} 的响应体不符",
+            self.request
+        );
+        self
+    }
+
+    /// 断言 JSON 响应体包含期望值
+    ///
+    /// 对象只比较期望值中的字段，数组按元素逐个比较，其他值须相等，
+    /// 可以忽略时间戳等每次不同的字段
+    pub fn assert_json_includes<T: Serialize>(&self, expected: T) -> &Self {
+        let expected = serde_json::to_value(expected).expect("序列化期望值失败");
+        let actual = self.json::<Value>();
+        assert!(
+            json_includes(&actual, &expected),
+            "{} 的响应体不包含期望值\n期望: {}\n实际: {}",
+            self.request,
+            expected,
+            actual
+        );
+        self
+    }
+}
+
+/// 判断 JSON 值是否包含期望值
+fn json_includes(actual: &Value, expected: &Value) -> bool {
+    match (actual, expected) {
+        (Value::Object(actual), Value::Object(expected)) => expected.iter().all(|(key, value)| {
+            actual
+                .get(key)
+                .is_some_and(|actual| json_includes(actual, value))
+        }),
+        (Value::Array(actual), Value::Array(expected)) => {
+            actual.len() == expected.len()
+                && actual
+                    .iter()
+                    .zip(expected)
+                    .all(|(actual, expected)| json_includes(actual, expected))
+        }
+        _ => actual == expected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::Path;
+    use axum::routing::{get, post};
+    use axum::Json;
+    use rspring_web::ApiResponse;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct User {
+        id: u64,
+        name: String,
+    }
+
+    /// 测试进程内调用路由并断言状态码和 JSON 响应体
+    #[tokio::test]
+    async fn test_client() {
+        let router = Router::new()
+            .route(
+                "/api/users/:id",
+                get(|Path(id): Path<u64>| async move {
+                    Json(ApiResponse::success(User {
+                        id,
+                        name: "Alice".to_string(),
+                    }))
+                }),
+            )
+            .route(
+                "/api/users",
+                post(|headers: HeaderMap, Json(user): Json<User>| async move {
+                    let authorized = headers.contains_key(header::AUTHORIZATION);
+                    (
+                        StatusCode::CREATED,
+                        Json(json!({ "user": user, "authorized": authorized })),
+                    )
+                }),
+            );
+        let client = TestClient::new(router).bearer_auth("token");
+
+        let response = client.get_json::<ApiResponse<User>>("/api/users/7").await;
+        assert_eq!(response.data.unwrap().name, "Alice");
+
+        let user = User {
+            id: 8,
+            name: "Bob".to_string(),
+        };
+        client
+            .post_json("/api/users", &user)
+            .await
+            .assert_status(StatusCode::CREATED)
+            .assert_header("content-type", "application/json")
+            .assert_json(json!({ "user": { "id": 8, "name": "Bob" }, "authorized": true }));
+        client
+            .get("/api/users/9")
+            .await
+            .assert_json_includes(json!({ "code": 200, "data": { "id": 9 } }));
+        client
+            .get("/missing")
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+    }
+}
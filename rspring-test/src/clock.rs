@@ -0,0 +1,118 @@
+//! 模拟时钟模块
+//!
+//! [`MockClock`] 是只在测试代码中前进的时钟，替换框架的全局时钟后，
+//! 缓存过期、审计时间、任务调度等依赖当前时间的逻辑可以确定地测试：
+//! - [`TestContext::mock_clock`](crate::TestContext::mock_clock) 在当前测试的作用域中替换全局时钟，
+//!   并替换容器中的 [`ApplicationClock`](rspring_core::ApplicationClock)
+//! - [`MockClock::advance`] 让时间和单调时间一起前进，[`MockClock::set`] 设置为指定时间
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use rspring_core::Clock;
+
+/// 模拟时钟
+///
+/// # 示例
+/// ```rust
+/// #[rspring_test]
+/// async fn expires_cached_user(context: &TestContext) {
+///     let clock = context.mock_clock(Utc::now()).await;
+///     let cache = InMemoryCache::new("users", Some(Duration::from_secs(60)), None);
+///
+///     cache.put("alice", json!("Alice")).await;
+///     clock.advance(Duration::from_secs(61));
+///     assert!(cache.get("alice").await.is_none());
+/// }
+/// ```
+#[derive(Debug)]
+pub struct MockClock {
+    /// 当前时间
+    now: Mutex<DateTime<Utc>>,
+    /// 当前的单调时间，只随 [`MockClock::advance`] 前进
+    monotonic: Mutex<Instant>,
+}
+
+impl MockClock {
+    /// 创建停在指定时间的时钟
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Mutex::new(now),
+            monotonic: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// 设置当前时间，可以回拨，与系统时间调整一样不影响单调时间
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.lock() = now;
+    }
+
+    /// 时间前进指定时长
+    ///
+    /// # Panics
+    /// 时长超出 `chrono` 的表示范围时 panic
+    pub fn advance(&self, duration: Duration) {
+        let elapsed = chrono::Duration::from_std(duration).expect("时钟前进的时长过长");
+        *self.lock() += elapsed;
+        let mut monotonic = self.monotonic.lock().unwrap_or_else(|e| e.into_inner());
+        *monotonic = monotonic.checked_add(duration).expect("时钟前进的时长过长");
+    }
+
+    /// 锁定当前时间
+    fn lock(&self) -> std::sync::MutexGuard<'_, DateTime<Utc>> {
+        self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for MockClock {
+    /// 创建停在创建时刻的时钟
+    fn default() -> Self {
+        Self::new(Utc::now())
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.lock()
+    }
+
+    fn monotonic(&self) -> Instant {
+        *self.monotonic.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestContext;
+    use rspring_core::cache::{Cache, InMemoryCache};
+    use rspring_core::ApplicationClock;
+
+    /// 测试模拟时钟控制缓存过期，只影响测试上下文的作用域
+    #[tokio::test]
+    async fn test_mock_clock() {
+        let context = TestContext::builder().build().unwrap();
+        let start = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let clock = context.mock_clock(start).await;
+        assert_eq!(context.component::<ApplicationClock>().await.now(), start);
+
+        context
+            .run(async {
+                assert_eq!(rspring_core::clock::now(), start);
+                let cache = InMemoryCache::new("users", Some(Duration::from_secs(60)), None);
+                cache.put("alice", serde_json::json!("Alice")).await;
+
+                clock.advance(Duration::from_secs(59));
+                assert!(cache.get("alice").await.is_some());
+                // 回拨时间不影响缓存过期
+                clock.set(start - chrono::Duration::hours(1));
+                assert!(cache.get("alice").await.is_some());
+                clock.advance(Duration::from_secs(1));
+                assert!(cache.get("alice").await.is_none());
+            })
+            .await;
+        assert!(rspring_core::clock::now() > start);
+        context.close().await;
+    }
+}
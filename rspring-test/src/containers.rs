@@ -0,0 +1,177 @@
+//! 测试容器模块
+//!
+//! 通过 testcontainers 为集成测试启动依赖的服务，需要本机可用的 Docker：
+//! - PostgreSQL：连接地址写入 `datasource.url` 和 `jobs.url`，不初始化启动器
+//! - Redis：连接地址写入 `redis.url`，并自动初始化 Redis 启动器（`data-redis` 特性）
+//! - Kafka：地址写入 `kafka.bootstrap_servers`，框架没有 Kafka 启动器，需自行创建客户端
+//!
+//! 容器随 [`TestContext`](crate::TestContext) 关闭而删除
+
+use std::collections::BTreeMap;
+
+use rspring_core::{ApplicationContext, Error, Result};
+use testcontainers::core::ContainerPort;
+use testcontainers::runners::AsyncRunner;
+use testcontainers::{ContainerAsync, Image};
+use testcontainers_modules::kafka::{Kafka, KAFKA_PORT};
+use testcontainers_modules::postgres::Postgres;
+use testcontainers_modules::redis::{Redis, REDIS_PORT};
+
+use crate::context::set_property;
+
+/// PostgreSQL 容器端口
+const POSTGRES_PORT: ContainerPort = ContainerPort::Tcp(5432);
+
+/// 可启动的服务
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Service {
+    /// PostgreSQL 数据库
+    Postgres,
+    /// Redis
+    Redis,
+    /// Kafka
+    Kafka,
+}
+
+impl Service {
+    /// 获取服务名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Service::Postgres => "postgres",
+            Service::Redis => "redis",
+            Service::Kafka => "kafka",
+        }
+    }
+}
+
+/// 测试依赖的服务容器
+///
+/// # 示例
+/// ```rust
+/// let containers = ServiceContainers::start(&[Service::Postgres, Service::Redis]).await?;
+/// let context = TestContext::builder().containers(containers).build()?;
+/// context.wire_containers().await?;
+/// ```
+#[derive(Debug, Default)]
+pub struct ServiceContainers {
+    /// PostgreSQL 容器
+    postgres: Option<ContainerAsync<Postgres>>,
+    /// Redis 容器
+    redis: Option<ContainerAsync<Redis>>,
+    /// Kafka 容器
+    kafka: Option<ContainerAsync<Kafka>>,
+    /// 各服务的连接地址
+    urls: BTreeMap<Service, String>,
+    /// 写入测试配置的连接地址
+    config: toml::Table,
+}
+
+impl ServiceContainers {
+    /// 启动指定的服务容器，重复的服务只启动一次
+    ///
+    /// # 错误
+    /// Docker 不可用或容器启动失败时返回错误
+    pub async fn start(services: &[Service]) -> Result<Self> {
+        let mut containers = Self::default();
+        for service in services {
+            if containers.urls.contains_key(service) {
+                continue;
+            }
+            match service {
+                Service::Postgres => {
+                    let container = run(*service, Postgres::default()).await?;
+                    let address = address(*service, &container, POSTGRES_PORT).await?;
+                    let url = format!("postgres://postgres:postgres@{}/postgres", address);
+                    containers.set(*service, "datasource.url", &url)?;
+                    containers.set(*service, "jobs.url", &url)?;
+                    containers.postgres = Some(container);
+                }
+                Service::Redis => {
+                    let container = run(*service, Redis::default()).await?;
+                    let address = address(*service, &container, REDIS_PORT).await?;
+                    containers.set(*service, "redis.url", &format!("redis://{}", address))?;
+                    containers.redis = Some(container);
+                }
+                Service::Kafka => {
+                    let container = run(*service, Kafka::default()).await?;
+                    let address = address(*service, &container, KAFKA_PORT).await?;
+                    containers.set(*service, "kafka.bootstrap_servers", &address)?;
+                    containers.kafka = Some(container);
+                }
+            }
+        }
+        Ok(containers)
+    }
+
+    /// 获取服务的连接地址，服务未启动时返回 `None`
+    pub fn url(&self, service: Service) -> Option<&str> {
+        self.urls.get(&service).map(String::as_str)
+    }
+
+    /// 获取写入测试配置的连接地址
+    pub fn config(&self) -> &toml::Table {
+        &self.config
+    }
+
+    /// 初始化服务对应的启动器，目前只有 Redis 有启动器
+    pub(crate) async fn wire(&self, context: &ApplicationContext) -> Result<()> {
+        #[cfg(feature = "data-redis")]
+        if self.redis.is_some() {
+            rspring_data_redis::init_redis(context).await?;
+        }
+        #[cfg(not(feature = "data-redis"))]
+        let _ = context;
+        Ok(())
+    }
+
+    /// 停止并删除所有容器
+    pub async fn remove(self) {
+        let results = [
+            remove(Service::Postgres, self.postgres).await,
+            remove(Service::Redis, self.redis).await,
+            remove(Service::Kafka, self.kafka).await,
+        ];
+        for (service, error) in results.into_iter().flatten() {
+            tracing::warn!("删除 {} 测试容器失败: {}", service.name(), error);
+        }
+    }
+
+    /// 记录服务的连接地址并写入配置，首次写入的地址作为服务的连接地址
+    fn set(&mut self, service: Service, key: &str, url: &str) -> Result<()> {
+        self.urls.entry(service).or_insert_with(|| url.to_string());
+        set_property(&mut self.config, key, toml::Value::String(url.to_string()))
+    }
+}
+
+/// 启动容器
+async fn run<I: Image>(service: Service, image: I) -> Result<ContainerAsync<I>> {
+    let container = image
+        .start()
+        .await
+        .map_err(|e| Error::application(format!("启动 {} 测试容器失败: {}", service.name(), e)))?;
+    tracing::info!("{} 测试容器已启动: {}", service.name(), container.id());
+    Ok(container)
+}
+
+/// 获取容器端口映射到本机的地址
+async fn address<I: Image>(
+    service: Service,
+    container: &ContainerAsync<I>,
+    port: impl Into<ContainerPort>,
+) -> Result<String> {
+    let error = |e: testcontainers::TestcontainersError| {
+        Error::application(format!("获取 {} 测试容器地址失败: {}", service.name(), e))
+    };
+    let host = container.get_host().await.map_err(error)?;
+    let port = container.get_host_port_ipv4(port).await.map_err(error)?;
+    Ok(format!("{}:{}", host, port))
+}
+
+/// 删除容器，失败时返回服务和错误信息
+async fn remove<I: Image>(
+    service: Service,
+    container: Option<ContainerAsync<I>>,
+) -> Option<(Service, String)> {
+    let error = container?.rm().await.err()?;
+    Some((service, error.to_string()))
+}
@@ -0,0 +1,735 @@
+//! 测试上下文模块
+//!
+//! [`TestContext`] 为单个测试创建独立的应用上下文：
+//! - 默认激活 `test` 环境，不读取 `PROFILE` 环境变量，项目中的 `application-test.{toml,yaml,json}`
+//!   覆盖 `application.*`，可在其中为测试配置随机端口、内存存储等
+//! - 配置从项目目录和测试专用的临时目录加载，内联配置和 `key=value` 配置项写入临时目录，
+//!   覆盖项目中的配置文件，不修改进程的环境变量，并行的测试互不影响
+//! - 启用 `containers` 特性时可以为测试启动依赖的服务容器，连接地址写入临时目录中的配置
+//! - 每个上下文有独立的 [`GlobalScope`]，在 [`TestContext::run`] 中发布事件、设置缓存管理器等全局组件
+//!   只影响当前测试，并行的测试互不影响
+//! - 记录测试期间通过作用域中的事件发布器发布的事件，可用 [`TestContext::events`] 断言
+//! - 启用 `data-mysql` 特性时可以通过仓储插入测试数据，测试结束时删除
+//! - [`TestContext::mock_clock`] 以模拟时钟替换全局时钟，时间只在测试中前进
+//! - 组件在自动装配前注册，测试中按类型获取，可以用 [`TestContext::mock`] 替换为模拟实现
+//! - 测试结束时清空容器并删除临时目录
+//!
+//! `#[rspring_test]` 生成的测试函数通过 [`TestContext::builder`] 创建上下文
+
+use std::future::Future;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use rspring_core::container::Container;
+use rspring_core::{
+    ApplicationClock, ApplicationContext, Clock, Component, ConfigurationManager, Error,
+    EventPublisher, GlobalScope, Result,
+};
+use tempfile::TempDir;
+
+use crate::clock::MockClock;
+#[cfg(feature = "containers")]
+use crate::containers::ServiceContainers;
+use crate::events::PublishedEvents;
+#[cfg(feature = "data-mysql")]
+use crate::fixtures::Fixtures;
+
+/// 未指定环境时激活的环境
+pub const DEFAULT_TEST_PROFILE: &str = "test";
+
+/// 读取配置覆盖的环境变量前缀
+const ENV_PREFIX: &str = "RSPRING";
+
+/// 恢复被替换组件的操作
+type Restore = Box<dyn FnOnce(&mut Container) -> Result<()> + Send>;
+
+/// 测试上下文
+///
+/// 通过 [`Deref`] 提供 [`ApplicationContext`] 的方法
+///
+/// # 示例
+/// ```rust
+/// let context = TestContext::builder()
+///     .profile("it")
+///     .config("[app]\nname = \"orders\"")
+///     .build()?;
+/// context.register_singleton(OrderService::new()).await;
+/// context.auto_wire().await?;
+///
+/// let service = context.component::<OrderService>().await;
+/// assert_eq!(service.count().await?, 0);
+/// context.close().await;
+/// ```
+pub struct TestContext {
+    /// 应用上下文
+    context: ApplicationContext,
+    /// 临时配置目录，上下文丢弃时删除
+    config_dir: TempDir,
+    /// 恢复被替换组件的操作，按替换顺序排列
+    restores: Mutex<Vec<Restore>>,
+    /// 全局组件作用域
+    scope: Arc<GlobalScope>,
+    /// 测试期间发布的事件
+    events: PublishedEvents,
+    /// 测试依赖的服务容器
+    #[cfg(feature = "containers")]
+    containers: Option<ServiceContainers>,
+    /// 测试数据
+    #[cfg(feature = "data-mysql")]
+    fixtures: Fixtures,
+}
+
+impl TestContext {
+    /// 创建测试上下文构建器
+    pub fn builder() -> TestContextBuilder {
+        TestContextBuilder::default()
+    }
+
+    /// 获取应用上下文
+    pub fn context(&self) -> &ApplicationContext {
+        &self.context
+    }
+
+    /// 获取临时配置目录
+    pub fn config_dir(&self) -> &Path {
+        self.config_dir.path()
+    }
+
+    /// 获取全局组件作用域
+    pub fn scope(&self) -> &Arc<GlobalScope> {
+        &self.scope
+    }
+
+    /// 在测试的全局组件作用域中执行，`#[rspring_test]` 在其中执行初始化和测试函数
+    ///
+    /// # 示例
+    /// ```rust
+    /// context
+    ///     .run(async {
+    ///         service.place_order(1).await.unwrap();
+    ///         context.events().assert_published::<OrderPlaced>(|e| e.id == 1);
+    ///     })
+    ///     .await;
+    /// ```
+    pub async fn run<F: Future>(&self, future: F) -> F::Output {
+        self.scope.run(future).await
+    }
+
+    /// 获取测试期间在作用域中发布的事件
+    pub fn events(&self) -> &PublishedEvents {
+        &self.events
+    }
+
+    /// 获取单例组件
+    ///
+    /// # Panics
+    /// 容器中没有该类型的单例组件时 panic
+    pub async fn component<T: 'static>(&self) -> Arc<T> {
+        self.context
+            .get::<T>()
+            .await
+            .unwrap_or_else(|| panic!("测试上下文中没有组件: {}", std::any::type_name::<T>()))
+    }
+
+    /// 以模拟实现替换单例组件，返回替换后的组件
+    ///
+    /// 原组件的注册（包括非单例组件和尚未创建的 Bean 定义）在 [`reset_mocks`](Self::reset_mocks)
+    /// 或 [`close`](Self::close) 时恢复；原来没有该组件时恢复为未注册。
+    /// 已经持有原组件的其他组件不受影响，替换应在依赖它的组件创建之前进行
+    ///
+    /// # Panics
+    /// 组件注册失败时 panic
+    ///
+    /// # 示例
+    /// ```rust
+    /// let repository = context.mock(UserRepository::with_users(vec![alice()])).await;
+    /// let service = UserService::new(context.component::<UserRepository>().await);
+    /// assert_eq!(service.find("alice").await?.name, "Alice");
+    /// ```
+    pub async fn mock<T: Component + 'static>(&self, mock: T) -> Arc<T> {
+        let mut container = self.context.container.write().await;
+        let original = container.injector_mut().registry_mut().take::<T>();
+        let beans = container.take_beans::<T>();
+        let name = original
+            .as_ref()
+            .and_then(|entry| entry.name())
+            .map(str::to_string);
+
+        let mock = Arc::new(mock);
+        container
+            .register_singleton_arc(mock.clone(), name)
+            .unwrap_or_else(|e| panic!("替换组件失败: {}", e));
+        self.lock_restores()
+            .push(Box::new(move |container: &mut Container| {
+                container.remove::<T>();
+                if let Some(original) = original {
+                    container.injector_mut().registry_mut().restore(original);
+                }
+                container.register_beans(beans);
+                Ok(())
+            }));
+        mock
+    }
+
+    /// 以停在指定时间的模拟时钟替换全局时钟和容器中的 [`ApplicationClock`]，返回模拟时钟
+    ///
+    /// 全局时钟只在当前上下文的作用域中替换，见 [`run`](Self::run)
+    pub async fn mock_clock(&self, now: DateTime<Utc>) -> Arc<MockClock> {
+        let clock = Arc::new(MockClock::new(now));
+        self.scope.insert::<Arc<dyn Clock>>(clock.clone());
+        self.mock(ApplicationClock::new(clock.clone())).await;
+        clock
+    }
+
+    /// 恢复所有被 [`mock`](Self::mock) 替换的组件
+    ///
+    /// # Panics
+    /// 组件恢复失败时 panic
+    pub async fn reset_mocks(&self) {
+        let restores = std::mem::take(&mut *self.lock_restores());
+        let mut container = self.context.container.write().await;
+        for restore in restores.into_iter().rev() {
+            restore(&mut container).unwrap_or_else(|e| panic!("恢复组件失败: {}", e));
+        }
+    }
+
+    /// 获取测试依赖的服务容器
+    #[cfg(feature = "containers")]
+    pub fn containers(&self) -> Option<&ServiceContainers> {
+        self.containers.as_ref()
+    }
+
+    /// 初始化服务容器对应的启动器，即为 Redis 容器创建 `RedisClient` 并注册到容器
+    ///
+    /// PostgreSQL 和 Kafka 容器只将连接地址写入配置，不初始化启动器
+    ///
+    /// # 错误
+    /// 启动器初始化失败时返回错误
+    #[cfg(feature = "containers")]
+    pub async fn wire_containers(&self) -> Result<()> {
+        match &self.containers {
+            Some(containers) => containers.wire(&self.context).await,
+            None => Ok(()),
+        }
+    }
+
+    /// 获取测试数据，插入的记录在 [`close`](Self::close) 时删除
+    #[cfg(feature = "data-mysql")]
+    pub fn fixtures(&self) -> &Fixtures {
+        &self.fixtures
+    }
+
+    /// 通过容器中的仓储插入已加载的数据集，返回插入记录的主键
+    ///
+    /// # 错误
+    /// 数据集不存在、记录无法转换为实体或插入失败时返回错误
+    ///
+    /// # Panics
+    /// 容器中没有该仓储时 panic
+    #[cfg(feature = "data-mysql")]
+    pub async fn insert_fixtures<R>(&self, name: &str) -> Result<Vec<serde_json::Value>>
+    where
+        R: rspring_data_mysql::CrudRepository + 'static,
+        R::Entity: serde::de::DeserializeOwned,
+        R::Id: serde::de::DeserializeOwned,
+    {
+        let repository = self.component::<R>().await;
+        self.fixtures.insert(name, repository).await
+    }
+
+    /// 锁定恢复操作列表
+    fn lock_restores(&self) -> std::sync::MutexGuard<'_, Vec<Restore>> {
+        self.restores.lock().expect("测试上下文锁已损坏")
+    }
+
+    /// 关闭测试上下文，删除插入的测试数据，恢复被替换的组件后清空容器中的组件，
+    /// 删除服务容器和临时配置目录
+    pub async fn close(self) {
+        #[cfg(feature = "data-mysql")]
+        if let Err(e) = self.fixtures.cleanup().await {
+            tracing::warn!("清理测试数据失败: {}", e);
+        }
+        self.reset_mocks().await;
+        self.context
+            .container
+            .write()
+            .await
+            .injector_mut()
+            .registry_mut()
+            .clear();
+        #[cfg(feature = "containers")]
+        if let Some(containers) = self.containers {
+            containers.remove().await;
+        }
+        if let Err(e) = self.config_dir.close() {
+            tracing::warn!("删除测试配置目录失败: {}", e);
+        }
+    }
+}
+
+impl std::fmt::Debug for TestContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TestContext")
+            .field("context", &self.context)
+            .field("config_dir", &self.config_dir)
+            .field("mocks", &self.lock_restores().len())
+            .finish()
+    }
+}
+
+impl Deref for TestContext {
+    type Target = ApplicationContext;
+
+    fn deref(&self) -> &Self::Target {
+        &self.context
+    }
+}
+
+/// 测试上下文构建器
+#[derive(Debug, Default)]
+pub struct TestContextBuilder {
+    /// 激活的环境
+    profile: Option<String>,
+    /// 项目配置文件所在目录
+    project_dir: Option<PathBuf>,
+    /// 内联配置，TOML 格式
+    config: Option<String>,
+    /// `key=value` 格式的配置项
+    properties: Vec<String>,
+    /// 测试依赖的服务容器
+    #[cfg(feature = "containers")]
+    containers: Option<ServiceContainers>,
+}
+
+impl TestContextBuilder {
+    /// 设置激活的环境，默认为 [`DEFAULT_TEST_PROFILE`]
+    pub fn profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// 设置项目配置文件所在目录，默认为当前目录
+    ///
+    /// 从中加载 `application.*` 和 `application-{profile}.*`
+    pub fn project_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.project_dir = Some(dir.into());
+        self
+    }
+
+    /// 设置内联配置，TOML 格式，覆盖项目配置文件中的同名配置
+    pub fn config(mut self, config: impl Into<String>) -> Self {
+        self.config = Some(config.into());
+        self
+    }
+
+    /// 添加配置项，覆盖配置文件和内联配置中的同名配置
+    ///
+    /// 值按 TOML 解析，如 `0`、`true`、`["a", "b"]`，无法解析时作为字符串
+    pub fn property(mut self, key: &str, value: &str) -> Self {
+        self.properties.push(format!("{}={}", key, value));
+        self
+    }
+
+    /// 添加 `key=value` 格式的配置项，后添加的配置项优先
+    ///
+    /// # 示例
+    /// ```rust
+    /// let context = TestContext::builder()
+    ///     .properties(["server.port=0", "app.debug=true", "app.name=orders"])
+    ///     .build()?;
+    /// ```
+    pub fn properties<I, S>(mut self, properties: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.properties
+            .extend(properties.into_iter().map(Into::into));
+        self
+    }
+
+    /// 设置测试依赖的服务容器，连接地址覆盖项目配置文件，内联配置仍然优先
+    ///
+    /// # 示例
+    /// ```rust
+    /// let context = TestContext::builder()
+    ///     .containers(ServiceContainers::start(&[Service::Postgres]).await?)
+    ///     .build()?;
+    /// ```
+    #[cfg(feature = "containers")]
+    pub fn containers(mut self, containers: ServiceContainers) -> Self {
+        self.containers = Some(containers);
+        self
+    }
+
+    /// 创建测试上下文，不执行自动装配
+    ///
+    /// # 错误
+    /// 临时配置目录创建失败、内联配置或配置项格式错误或配置加载失败时返回错误
+    pub fn build(self) -> Result<TestContext> {
+        let profile = self
+            .profile
+            .unwrap_or_else(|| DEFAULT_TEST_PROFILE.to_string());
+        let config_dir = tempfile::Builder::new().prefix("rspring-test-").tempdir()?;
+
+        // 依次为服务容器的连接地址、内联配置和配置项，后者覆盖前者
+        let mut overrides = toml::Table::new();
+        #[cfg(feature = "containers")]
+        if let Some(containers) = &self.containers {
+            merge_table(&mut overrides, containers.config().clone());
+        }
+        if let Some(config) = &self.config {
+            merge_table(&mut overrides, config.parse()?);
+        }
+        for property in &self.properties {
+            let (key, value) = property
+                .split_once('=')
+                .ok_or_else(|| Error::validation(format!("无效的配置项: {}", property)))?;
+            set_property(&mut overrides, key.trim(), parse_value(value.trim()))?;
+        }
+        if !overrides.is_empty() {
+            // 作为环境配置写入临时目录，在项目的环境配置之后加载
+            let path = config_dir
+                .path()
+                .join(format!("application-{}.toml", profile));
+            std::fs::write(path, overrides.to_string())?;
+        }
+
+        let dirs = [
+            self.project_dir.unwrap_or_default(),
+            config_dir.path().to_path_buf(),
+        ];
+        let config = ConfigurationManager::from_dirs(&dirs, &profile, ENV_PREFIX)?;
+
+        // 事件发布器不继承全局的监听器，只记录本测试发布的事件
+        let scope = Arc::new(GlobalScope::new());
+        let publisher = Arc::new(EventPublisher::new());
+        scope.insert(publisher.clone());
+        Ok(TestContext {
+            context: ApplicationContext::with_config(config),
+            config_dir,
+            restores: Mutex::new(Vec::new()),
+            scope,
+            events: PublishedEvents::record(publisher),
+            #[cfg(feature = "containers")]
+            containers: self.containers,
+            #[cfg(feature = "data-mysql")]
+            fixtures: Fixtures::new(),
+        })
+    }
+}
+
+/// 将 `source` 合并到 `target`，同名的表递归合并，其他值被替换
+pub(crate) fn merge_table(target: &mut toml::Table, source: toml::Table) {
+    for (key, value) in source {
+        match (target.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(table)) => {
+                merge_table(existing, table)
+            }
+            (_, value) => {
+                target.insert(key, value);
+            }
+        }
+    }
+}
+
+/// 按点分隔的配置键设置配置值，如 `"redis.url"`
+///
+/// # 错误
+/// 配置键为空或路径上已有非表的值时返回错误
+pub(crate) fn set_property(table: &mut toml::Table, key: &str, value: toml::Value) -> Result<()> {
+    if key.split('.').any(str::is_empty) {
+        return Err(Error::validation(format!("无效的配置键: {}", key)));
+    }
+    let (parents, last) = key
+        .rsplit_once('.')
+        .map_or((None, key), |(parents, last)| (Some(parents), last));
+
+    let mut current = table;
+    for part in parents.into_iter().flat_map(|parents| parents.split('.')) {
+        let entry = current
+            .entry(part)
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        current = match entry {
+            toml::Value::Table(next) => next,
+            _ => return Err(Error::validation(format!("配置键 {} 的上级不是表", key))),
+        };
+    }
+    current.insert(last.to_string(), value);
+    Ok(())
+}
+
+/// 按 TOML 值解析配置项的值，无法解析时作为字符串
+fn parse_value(value: &str) -> toml::Value {
+    format!("value = {}", value)
+        .parse::<toml::Table>()
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rspring_core::container::BeanDefinition;
+    use rspring_core::Component;
+    use serde::Deserialize;
+
+    struct Greeter {
+        greeting: String,
+    }
+
+    impl Component for Greeter {
+        fn component_name(&self) -> &'static str {
+            "Greeter"
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct GreeterConfig {
+        greeting: String,
+        name: String,
+    }
+
+    impl Component for GreeterConfig {
+        fn component_name(&self) -> &'static str {
+            "GreeterConfig"
+        }
+    }
+
+    /// 测试内联配置覆盖项目配置，关闭后删除临时配置目录
+    #[tokio::test]
+    async fn test_context_lifecycle() {
+        let project = tempfile::tempdir().unwrap();
+        std::fs::write(
+            project.path().join("application.toml"),
+            "[greeter]\ngreeting = \"hello\"\nname = \"rspring\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            project.path().join("application-it.toml"),
+            "[greeter]\ngreeting = \"hi\"\n",
+        )
+        .unwrap();
+
+        let context = TestContext::builder()
+            .profile("it")
+            .project_dir(project.path())
+            .config("[greeter]\nname = \"test\"\n")
+            .build()
+            .unwrap();
+        assert_eq!(context.config.profile(), "it");
+        let config: GreeterConfig = context.config.get_section("greeter").unwrap();
+        assert_eq!(config.greeting, "hi");
+        assert_eq!(config.name, "test");
+
+        context
+            .register_singleton(Greeter {
+                greeting: config.greeting,
+            })
+            .await;
+        context.auto_wire().await.unwrap();
+        assert_eq!(context.component::<Greeter>().await.greeting, "hi");
+
+        let config_dir = context.config_dir().to_path_buf();
+        assert!(config_dir.exists());
+        context.close().await;
+        assert!(!config_dir.exists());
+    }
+
+    /// 测试默认激活 test 环境并加载 application-test.toml
+    #[test]
+    fn test_default_profile() {
+        let project = tempfile::tempdir().unwrap();
+        std::fs::write(
+            project.path().join("application.toml"),
+            "[server]\nport = 8080\n",
+        )
+        .unwrap();
+        std::fs::write(
+            project.path().join("application-test.toml"),
+            "[server]\nport = 0\n",
+        )
+        .unwrap();
+
+        let context = TestContext::builder()
+            .project_dir(project.path())
+            .build()
+            .unwrap();
+        assert_eq!(context.config.profile(), DEFAULT_TEST_PROFILE);
+        assert_eq!(context.config.get::<u16>("server.port").unwrap(), 0);
+    }
+
+    /// 测试替换组件后恢复原组件，原来没有的组件恢复为未注册
+    #[tokio::test]
+    async fn test_mock_component() {
+        let context = TestContext::builder().build().unwrap();
+        context
+            .register_singleton(Greeter {
+                greeting: "hello".to_string(),
+            })
+            .await;
+        let original = context.component::<Greeter>().await;
+
+        let mock = context
+            .mock(Greeter {
+                greeting: "mocked".to_string(),
+            })
+            .await;
+        assert!(Arc::ptr_eq(&context.component::<Greeter>().await, &mock));
+        context
+            .mock(Greeter {
+                greeting: "again".to_string(),
+            })
+            .await;
+        context
+            .mock(GreeterConfig {
+                greeting: String::new(),
+                name: String::new(),
+            })
+            .await;
+        assert!(!context.container.read().await.allow_override());
+
+        context.reset_mocks().await;
+        assert!(Arc::ptr_eq(
+            &context.component::<Greeter>().await,
+            &original
+        ));
+        assert!(context.get::<GreeterConfig>().await.is_none());
+        let names: Vec<_> = context
+            .container
+            .read()
+            .await
+            .describe()
+            .into_iter()
+            .map(|component| component.name)
+            .collect();
+        assert_eq!(names, ["Greeter"]);
+        context.close().await;
+    }
+
+    /// 测试替换非单例组件和尚未创建的 Bean 后恢复原来的注册
+    #[tokio::test]
+    async fn test_mock_restores_registration() {
+        let context = TestContext::builder().build().unwrap();
+        {
+            let mut container = context.container.write().await;
+            container
+                .register(Greeter {
+                    greeting: "hello".to_string(),
+                })
+                .unwrap();
+            container.register_beans(vec![BeanDefinition::new(
+                "greeter_config",
+                Vec::new(),
+                |_| {
+                    Ok(GreeterConfig {
+                        greeting: "hi".to_string(),
+                        name: "bean".to_string(),
+                    })
+                },
+            )]);
+        }
+
+        context
+            .mock(Greeter {
+                greeting: "mocked".to_string(),
+            })
+            .await;
+        context
+            .mock(GreeterConfig {
+                greeting: String::new(),
+                name: "mocked".to_string(),
+            })
+            .await;
+        assert_eq!(context.component::<GreeterConfig>().await.name, "mocked");
+
+        context.reset_mocks().await;
+        let greeting = context
+            .container
+            .read()
+            .await
+            .injector()
+            .registry()
+            .get::<Greeter>()
+            .map(|greeter| greeter.greeting.clone());
+        assert_eq!(greeting.as_deref(), Some("hello"));
+        context.auto_wire().await.unwrap();
+        assert_eq!(context.component::<GreeterConfig>().await.name, "bean");
+        context.close().await;
+    }
+
+    /// 测试配置项按类型解析并覆盖配置文件和内联配置，不修改环境变量
+    #[test]
+    fn test_properties() {
+        let project = tempfile::tempdir().unwrap();
+        std::fs::write(
+            project.path().join("application.toml"),
+            "[server]\nport = 8080\nhost = \"0.0.0.0\"\n",
+        )
+        .unwrap();
+
+        let context = TestContext::builder()
+            .project_dir(project.path())
+            .config("[app]\ndebug = false\nname = \"inline\"\n")
+            .properties([
+                "server.port=0",
+                "app.debug = true",
+                "app.tags=[\"a\", \"b\"]",
+            ])
+            .property("app.name", "orders")
+            .build()
+            .unwrap();
+        let config = &context.config;
+        assert_eq!(config.get::<u16>("server.port").unwrap(), 0);
+        assert_eq!(config.get::<String>("server.host").unwrap(), "0.0.0.0");
+        assert!(config.get::<bool>("app.debug").unwrap());
+        assert_eq!(config.get::<String>("app.name").unwrap(), "orders");
+        assert_eq!(config.get::<Vec<String>>("app.tags").unwrap(), ["a", "b"]);
+        assert!(std::env::var("RSPRING_SERVER_PORT").is_err());
+
+        assert!(TestContext::builder()
+            .properties(["server.port"])
+            .build()
+            .is_err());
+        assert!(TestContext::builder()
+            .properties(["server..port=0"])
+            .build()
+            .is_err());
+    }
+
+    /// 测试每个上下文只记录在自己的作用域中发布的事件
+    #[tokio::test]
+    async fn test_isolated_events() {
+        let first = TestContext::builder().build().unwrap();
+        let second = TestContext::builder().build().unwrap();
+
+        first
+            .run(rspring_core::publish_event("first"))
+            .await
+            .unwrap();
+        rspring_core::publish_event("global").await.unwrap();
+        first.events().assert_count::<&str>(1);
+        second.events().assert_count::<&str>(0);
+        assert!(!Arc::ptr_eq(first.scope(), second.scope()));
+    }
+
+    /// 测试合并配置时递归合并同名的表，其他值被替换
+    #[test]
+    fn test_merge_table() {
+        let mut target: toml::Table = "[redis]\nurl = \"redis://container\"\npool_size = 4\n"
+            .parse()
+            .unwrap();
+        merge_table(
+            &mut target,
+            "redis = { url = \"redis://inline\" }\n[app]\ndebug = true\n"
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(target["redis"]["url"].as_str(), Some("redis://inline"));
+        assert_eq!(target["redis"]["pool_size"].as_integer(), Some(4));
+        assert_eq!(target["app"]["debug"].as_bool(), Some(true));
+    }
+}
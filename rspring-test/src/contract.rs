@@ -0,0 +1,275 @@
+//! 接口契约快照模块
+//!
+//! [`ContractSnapshot`] 将控制器生成的路由和 OpenAPI 描述保存为 JSON 快照，
+//! 之后的测试与快照比较，接口被意外删除或修改时测试失败：
+//! - 快照默认保存在 `tests/snapshots/{控制器名称}.json`，与测试代码一起提交
+//! - 快照不存在时写入快照；设置了 `CI` 环境变量时不写入，测试失败
+//! - 确认变更后设置 `RSPRING_UPDATE_SNAPSHOTS=1` 重新运行测试更新快照
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use rspring_web::{OpenApi, OperationInfo, RestRoutes};
+use serde_json::{json, Value};
+
+/// 设置后更新已有快照的环境变量
+pub const UPDATE_SNAPSHOTS_ENV: &str = "RSPRING_UPDATE_SNAPSHOTS";
+
+/// 快照的默认目录，相对于 crate 根目录
+const DEFAULT_SNAPSHOT_DIR: &str = "tests/snapshots";
+
+/// 控制器的接口契约快照
+///
+/// # 示例
+/// ```rust
+/// // 与 tests/snapshots/UserController.json 比较
+/// ContractSnapshot::of::<UserController>().assert_unchanged();
+///
+/// // 与 tests/snapshots/users_v2.json 比较
+/// ContractSnapshot::of::<UserV2Controller>()
+///     .name("users_v2")
+///     .assert_unchanged();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ContractSnapshot {
+    /// 快照名称，作为文件名
+    name: String,
+    /// 快照目录
+    dir: PathBuf,
+    /// 控制器基础路径
+    base_path: String,
+    /// 接口操作元数据
+    operations: Vec<OperationInfo>,
+}
+
+impl ContractSnapshot {
+    /// 创建控制器的快照，名称默认为控制器的类型名
+    pub fn of<C: RestRoutes>() -> Self {
+        let name = std::any::type_name::<C>()
+            .rsplit("::")
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        Self {
+            name,
+            dir: crate::manifest_dir().join(DEFAULT_SNAPSHOT_DIR),
+            base_path: C::base_path().to_string(),
+            operations: C::operations(),
+        }
+    }
+
+    /// 设置快照名称
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// 设置快照目录
+    pub fn dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = dir.into();
+        self
+    }
+
+    /// 获取快照文件路径
+    pub fn path(&self) -> PathBuf {
+        self.dir.join(format!("{}.json", self.name))
+    }
+
+    /// 生成当前的接口契约，包括路由列表和 OpenAPI 路径描述
+    pub fn to_json(&self) -> Value {
+        let openapi = self
+            .operations
+            .iter()
+            .cloned()
+            .fold(OpenApi::new(&self.name, "snapshot"), OpenApi::operation)
+            .to_json();
+        json!({
+            "controller": self.name,
+            "base_path": self.base_path,
+            "routes": routes(&self.operations),
+            "paths": openapi["paths"],
+        })
+    }
+
+    /// 断言接口契约与快照一致
+    ///
+    /// # Panics
+    /// 契约与快照不一致，或快照不存在且运行在 CI 环境中时 panic，输出变更的接口
+    pub fn assert_unchanged(&self) {
+        let path = self.path();
+        let actual = self.to_json();
+
+        let expected = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str::<Value>(&content)
+                .unwrap_or_else(|e| panic!("接口契约快照 {} 格式错误: {}", path.display(), e)),
+            Err(_) if std::env::var_os("CI").is_some() => {
+                panic!(
+                    "接口契约快照 {} 不存在，请在本地运行测试生成快照并提交",
+                    path.display()
+                )
+            }
+            Err(_) => {
+                write(&path, &actual);
+                tracing::info!("已写入接口契约快照: {}", path.display());
+                return;
+            }
+        };
+
+        if expected == actual {
+            return;
+        }
+        if std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some() {
+            write(&path, &actual);
+            tracing::info!("已更新接口契约快照: {}", path.display());
+            return;
+        }
+        panic!(
+            "{} 的接口契约与快照 {} 不一致:\n{}\n确认变更后设置 {}=1 重新运行测试以更新快照",
+            self.name,
+            path.display(),
+            changes(&expected, &actual).join("\n"),
+            UPDATE_SNAPSHOTS_ENV
+        );
+    }
+}
+
+/// 生成排序后的路由列表，如 `GET /api/users/{id}`
+fn routes(operations: &[OperationInfo]) -> Vec<String> {
+    let routes: BTreeSet<String> = operations
+        .iter()
+        .map(|operation| format!("{} {}", operation.method.to_uppercase(), operation.path))
+        .collect();
+    routes.into_iter().collect()
+}
+
+/// 写入快照
+fn write(path: &Path, snapshot: &Value) {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .unwrap_or_else(|e| panic!("创建快照目录 {} 失败: {}", dir.display(), e));
+    }
+    let content = serde_json::to_string_pretty(snapshot).expect("序列化接口契约失败") + "\n";
+    std::fs::write(path, content)
+        .unwrap_or_else(|e| panic!("写入接口契约快照 {} 失败: {}", path.display(), e));
+}
+
+/// 比较快照和当前契约，列出删除、新增和修改的接口
+fn changes(expected: &Value, actual: &Value) -> Vec<String> {
+    let route_set = |snapshot: &Value| -> BTreeSet<String> {
+        snapshot["routes"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|route| route.as_str().map(str::to_string))
+            .collect()
+    };
+    // 路由 `GET /path` 对应 OpenAPI 中的 paths./path.get
+    let operation = |snapshot: &Value, route: &str| -> Value {
+        let (method, path) = route.split_once(' ').unwrap_or_default();
+        snapshot["paths"][path][method.to_lowercase()].clone()
+    };
+
+    let before = route_set(expected);
+    let after = route_set(actual);
+    let mut changes = Vec::new();
+    changes.extend(
+        before
+            .difference(&after)
+            .map(|route| format!("  - 删除接口 {}", route)),
+    );
+    changes.extend(
+        after
+            .difference(&before)
+            .map(|route| format!("  + 新增接口 {}", route)),
+    );
+    changes.extend(
+        before
+            .intersection(&after)
+            .filter(|route| operation(expected, route) != operation(actual, route))
+            .map(|route| format!("  ~ 修改接口 {}", route)),
+    );
+    if expected["base_path"] != actual["base_path"] {
+        changes.push(format!(
+            "  ~ 基础路径 {} -> {}",
+            expected["base_path"], actual["base_path"]
+        ));
+    }
+    if changes.is_empty() {
+        changes.push("  ~ 快照内容不同".to_string());
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use axum::Router;
+    use rspring_web::{ParameterInfo, ParameterLocation};
+
+    struct UserController;
+
+    impl RestRoutes for UserController {
+        fn base_path() -> &'static str {
+            "/api/users"
+        }
+
+        fn operations() -> Vec<OperationInfo> {
+            let operation = |method, path: &str, operation_id: &str| OperationInfo {
+                method,
+                path: path.to_string(),
+                operation_id: operation_id.to_string(),
+                tag: "UserController".to_string(),
+                summary: None,
+                parameters: Vec::new(),
+                request_body: None,
+                request_content_types: Vec::new(),
+                response: None,
+            };
+            vec![
+                operation("get", "/api/users/{id}", "get_user"),
+                operation("delete", "/api/users/{id}", "delete_user"),
+            ]
+        }
+
+        fn router(self: Arc<Self>) -> Router {
+            Router::new()
+        }
+    }
+
+    /// 测试契约与快照比较，契约变更时列出变更的接口
+    #[test]
+    fn test_contract_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let snapshot = ContractSnapshot::of::<UserController>().dir(dir.path());
+        assert_eq!(snapshot.path(), dir.path().join("UserController.json"));
+
+        write(&snapshot.path(), &snapshot.to_json());
+        snapshot.assert_unchanged();
+
+        let mut changed = snapshot.clone();
+        changed.operations.remove(1);
+        changed.operations[0]
+            .parameters
+            .push(ParameterInfo::of::<u64>("id", ParameterLocation::Path));
+        changed
+            .operations
+            .push(UserController::operations().remove(0));
+        changed.operations[1].method = "put";
+
+        let expected: Value =
+            serde_json::from_str(&std::fs::read_to_string(snapshot.path()).unwrap()).unwrap();
+        assert_eq!(
+            changes(&expected, &changed.to_json()),
+            [
+                "  - 删除接口 DELETE /api/users/{id}",
+                "  + 新增接口 PUT /api/users/{id}",
+                "  ~ 修改接口 GET /api/users/{id}",
+            ]
+        );
+        if std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_none() {
+            assert!(std::panic::catch_unwind(|| changed.assert_unchanged()).is_err());
+        }
+    }
+}
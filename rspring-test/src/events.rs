@@ -0,0 +1,183 @@
+//! 事件记录模块
+//!
+//! [`PublishedEvents`] 记录测试期间通过应用事件发布器发布的所有事件：
+//! - 在监听器执行前记录，不论是否有监听器、是否在事务中
+//! - 按类型取出事件，断言事件是否发布、发布次数
+//! - 断言失败时输出已发布的事件类型
+
+use std::any::Any;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use rspring_core::EventPublisher;
+
+/// 已记录的事件，包括类型名称和事件
+type Recorded = (&'static str, Arc<dyn Any + Send + Sync>);
+
+/// 测试期间发布的事件
+///
+/// # 示例
+/// ```rust
+/// #[rspring_test(setup = "register")]
+/// async fn publishes_order_created(service: Arc<OrderService>, events: &PublishedEvents) {
+///     service.create(1).await.unwrap();
+///
+///     events
+///         .assert_published::<OrderCreated>(|e| e.id == 1)
+///         .assert_not_published::<OrderCancelled>(|_| true);
+/// }
+/// ```
+pub struct PublishedEvents {
+    /// 记录事件的发布器
+    publisher: Arc<EventPublisher>,
+    /// 观察函数编号
+    observer: u64,
+    /// 按发布顺序记录的事件
+    events: Arc<Mutex<Vec<Recorded>>>,
+}
+
+impl PublishedEvents {
+    /// 开始记录发布器发布的事件，记录在值被丢弃时停止
+    pub fn record(publisher: Arc<EventPublisher>) -> Self {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        let observer = publisher.observe(move |name, event| {
+            lock(&recorded).push((name, event.clone()));
+        });
+        Self {
+            publisher,
+            observer,
+            events,
+        }
+    }
+
+    /// 按发布顺序获取指定类型的事件
+    pub fn published<E: Clone + 'static>(&self) -> Vec<E> {
+        lock(&self.events)
+            .iter()
+            .filter_map(|(_, event)| event.downcast_ref::<E>().cloned())
+            .collect()
+    }
+
+    /// 获取指定类型的事件数量
+    pub fn count<E: 'static>(&self) -> usize {
+        lock(&self.events)
+            .iter()
+            .filter(|(_, event)| event.is::<E>())
+            .count()
+    }
+
+    /// 按发布顺序获取所有事件的类型名称
+    pub fn names(&self) -> Vec<&'static str> {
+        lock(&self.events).iter().map(|(name, _)| *name).collect()
+    }
+
+    /// 清空已记录的事件
+    pub fn clear(&self) {
+        lock(&self.events).clear();
+    }
+
+    /// 断言发布了满足条件的 `E` 类型事件
+    pub fn assert_published<E: 'static>(&self, predicate: impl Fn(&E) -> bool) -> &Self {
+        assert!(
+            self.matches(&predicate) > 0,
+            "没有发布满足条件的 {} 事件\n已发布: {:?}",
+            std::any::type_name::<E>(),
+            self.names()
+        );
+        self
+    }
+
+    /// 断言没有发布满足条件的 `E` 类型事件
+    pub fn assert_not_published<E: 'static>(&self, predicate: impl Fn(&E) -> bool) -> &Self {
+        assert_eq!(
+            self.matches(&predicate),
+            0,
+            "发布了满足条件的 {} 事件\n已发布: {:?}",
+            std::any::type_name::<E>(),
+            self.names()
+        );
+        self
+    }
+
+    /// 断言 `E` 类型事件的发布次数
+    pub fn assert_count<E: 'static>(&self, count: usize) -> &Self {
+        assert_eq!(
+            self.count::<E>(),
+            count,
+            "{} 事件的发布次数不符\n已发布: {:?}",
+            std::any::type_name::<E>(),
+            self.names()
+        );
+        self
+    }
+
+    /// 统计满足条件的 `E` 类型事件
+    fn matches<E: 'static>(&self, predicate: &impl Fn(&E) -> bool) -> usize {
+        lock(&self.events)
+            .iter()
+            .filter_map(|(_, event)| event.downcast_ref::<E>())
+            .filter(|event| predicate(event))
+            .count()
+    }
+}
+
+impl Drop for PublishedEvents {
+    fn drop(&mut self) {
+        self.publisher.remove_observer(self.observer);
+    }
+}
+
+impl std::fmt::Debug for PublishedEvents {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PublishedEvents")
+            .field("events", &self.names())
+            .finish()
+    }
+}
+
+/// 锁定事件列表，测试中断言失败不影响后续读取
+fn lock(events: &Mutex<Vec<Recorded>>) -> MutexGuard<'_, Vec<Recorded>> {
+    events.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct OrderCreated {
+        id: u32,
+    }
+
+    #[derive(Debug, Clone)]
+    struct OrderCancelled;
+
+    /// 测试记录发布的事件并断言，丢弃后停止记录
+    #[tokio::test]
+    async fn test_published_events() {
+        let publisher = Arc::new(EventPublisher::new());
+        let events = PublishedEvents::record(publisher.clone());
+
+        publisher.publish(OrderCreated { id: 1 }).await.unwrap();
+        publisher.publish(OrderCreated { id: 2 }).await.unwrap();
+        events
+            .assert_published::<OrderCreated>(|e| e.id == 1)
+            .assert_not_published::<OrderCreated>(|e| e.id == 3)
+            .assert_not_published::<OrderCancelled>(|_| true)
+            .assert_count::<OrderCreated>(2);
+        assert_eq!(
+            events.published::<OrderCreated>(),
+            [OrderCreated { id: 1 }, OrderCreated { id: 2 }]
+        );
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            events.assert_published::<OrderCancelled>(|_| true);
+        }));
+        assert!(result.is_err());
+
+        events.clear();
+        assert!(events.names().is_empty());
+        drop(events);
+        assert!(format!("{:?}", publisher).contains("observers: 0"));
+    }
+}
@@ -0,0 +1,290 @@
+//! 测试数据模块
+//!
+//! [`Fixtures`] 从 YAML/JSON 文件读取测试数据，通过仓储插入并在测试结束时删除：
+//! - 文件顶层为数据集名称到记录列表的映射，记录按实体的 serde 格式书写
+//! - 相对路径相对于 crate 根目录，可以多次加载，同名数据集被后加载的文件替换
+//! - [`Fixtures::insert`] 通过 [`CrudRepository::save`] 插入数据集，记录插入的主键
+//! - [`Fixtures::cleanup`] 按插入的相反顺序删除记录，[`TestContext`](crate::TestContext)
+//!   关闭时自动调用
+//!
+//! 与 MySQL 自增主键一致，`save` 返回的主键非零时以它为准，否则使用记录中的 `id` 字段
+//!
+//! ```yaml
+//! # tests/fixtures/users.yaml
+//! users:
+//!   - { name: alice, email: alice@example.com }
+//!   - { name: bob, email: bob@example.com }
+//! orders:
+//!   - { user_id: 1, amount: 100 }
+//! ```
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use rspring_core::{Error, Result};
+use rspring_data_mysql::CrudRepository;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// 删除已插入记录的操作
+type Cleanup = Box<dyn FnOnce() -> Pin<Box<dyn Future<Output = Result<()>> + Send>> + Send>;
+
+/// 记录中的主键字段
+const ID_PROPERTY: &str = "id";
+
+/// 测试数据
+///
+/// # 示例
+/// ```rust
+/// async fn fixtures(context: &TestContext) -> Result<()> {
+///     context.fixtures().load("tests/fixtures/users.yaml")?;
+///     context.insert_fixtures::<UserRepository>("users").await?;
+///     Ok(())
+/// }
+///
+/// #[rspring_test(setup = "fixtures")]
+/// async fn lists_users(service: Arc<UserService>) {
+///     assert_eq!(service.list().await.unwrap().len(), 2);
+/// }
+/// ```
+#[derive(Default)]
+pub struct Fixtures {
+    /// 按名称保存的数据集
+    sets: Mutex<BTreeMap<String, Vec<Value>>>,
+    /// 删除已插入记录的操作，按插入顺序排列
+    cleanups: Mutex<Vec<Cleanup>>,
+}
+
+impl Fixtures {
+    /// 创建空的测试数据
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 加载 YAML（`.yaml`、`.yml`）或 JSON（`.json`）文件中的数据集
+    ///
+    /// # 错误
+    /// 文件无法读取、格式不支持或内容不是数据集映射时返回错误
+    pub fn load(&self, path: impl AsRef<Path>) -> Result<&Self> {
+        let path = crate::manifest_dir().join(path);
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            Error::validation(format!("读取测试数据文件 {} 失败: {}", path.display(), e))
+        })?;
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default();
+        self.parse(&content, extension)
+            .map_err(|e| Error::validation(format!("测试数据文件 {} {}", path.display(), e)))
+    }
+
+    /// 添加 YAML 格式的数据集
+    ///
+    /// # 错误
+    /// 内容不是数据集映射时返回错误
+    pub fn add_yaml(&self, content: &str) -> Result<&Self> {
+        self.parse(content, "yaml").map_err(Error::validation)
+    }
+
+    /// 添加 JSON 格式的数据集
+    ///
+    /// # 错误
+    /// 内容不是数据集映射时返回错误
+    pub fn add_json(&self, content: &str) -> Result<&Self> {
+        self.parse(content, "json").map_err(Error::validation)
+    }
+
+    /// 获取数据集的名称
+    pub fn names(&self) -> Vec<String> {
+        self.lock_sets().keys().cloned().collect()
+    }
+
+    /// 按实体类型读取数据集
+    ///
+    /// # 错误
+    /// 数据集不存在或记录无法转换为实体时返回错误
+    pub fn records<E: DeserializeOwned>(&self, name: &str) -> Result<Vec<E>> {
+        self.set(name)?
+            .into_iter()
+            .map(|record| entity(name, record))
+            .collect()
+    }
+
+    /// 通过仓储插入数据集，返回插入记录的主键
+    ///
+    /// # 错误
+    /// 数据集不存在、记录无法转换为实体、记录的主键无效或插入失败时返回错误，
+    /// 已插入的记录仍会被清理
+    pub async fn insert<R>(&self, name: &str, repository: Arc<R>) -> Result<Vec<Value>>
+    where
+        R: CrudRepository + 'static,
+        R::Entity: DeserializeOwned,
+        R::Id: DeserializeOwned,
+    {
+        let mut ids = Vec::new();
+        for record in self.set(name)? {
+            let id = record.get(ID_PROPERTY).cloned().unwrap_or(Value::Null);
+            if !id.is_null() {
+                primary_key::<R::Id>(name, &id)?;
+            }
+            let saved = repository.save(&entity(name, record)?).await?;
+            let id = if saved > 0 { Value::from(saved) } else { id };
+
+            // 先登记清理再检查生成的主键，主键无效时清理同样返回错误，不会遗漏已插入的记录
+            let repository = repository.clone();
+            let (set, key) = (name.to_string(), id.clone());
+            self.lock_cleanups().push(Box::new(move || {
+                Box::pin(async move {
+                    let key = primary_key::<R::Id>(&set, &key)?;
+                    repository.delete_by_id(key).await.map(|_| ())
+                })
+            }));
+            primary_key::<R::Id>(name, &id)?;
+            ids.push(id);
+        }
+        tracing::debug!("已插入测试数据 {}: {} 条", name, ids.len());
+        Ok(ids)
+    }
+
+    /// 按插入的相反顺序删除已插入的记录
+    ///
+    /// # 错误
+    /// 删除失败时继续删除其余记录，返回第一个错误
+    pub async fn cleanup(&self) -> Result<()> {
+        let cleanups = std::mem::take(&mut *self.lock_cleanups());
+        let mut result = Ok(());
+        for cleanup in cleanups.into_iter().rev() {
+            if let Err(e) = cleanup().await {
+                tracing::warn!("删除测试数据失败: {}", e);
+                result = result.and(Err(e));
+            }
+        }
+        result
+    }
+
+    /// 解析数据集并替换同名数据集
+    fn parse(&self, content: &str, format: &str) -> std::result::Result<&Self, String> {
+        let document: Value = match format {
+            "yaml" | "yml" => serde_yaml::from_str(content).map_err(|e| e.to_string()),
+            "json" => serde_json::from_str(content).map_err(|e| e.to_string()),
+            _ => return Err(format!("格式不支持: {}", format)),
+        }
+        .map_err(|e| format!("解析失败: {}", e))?;
+
+        let Value::Object(document) = document else {
+            return Err("顶层应为数据集名称到记录列表的映射".to_string());
+        };
+        let mut sets = BTreeMap::new();
+        for (name, records) in document {
+            match records {
+                Value::Array(records) => sets.insert(name, records),
+                _ => return Err(format!("数据集 {} 应为记录列表", name)),
+            };
+        }
+        self.lock_sets().extend(sets);
+        Ok(self)
+    }
+
+    /// 获取数据集的记录
+    fn set(&self, name: &str) -> Result<Vec<Value>> {
+        self.lock_sets()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| Error::validation(format!("测试数据集不存在: {}", name)))
+    }
+
+    /// 锁定数据集
+    fn lock_sets(&self) -> MutexGuard<'_, BTreeMap<String, Vec<Value>>> {
+        self.sets.lock().expect("测试数据锁已损坏")
+    }
+
+    /// 锁定删除操作列表
+    fn lock_cleanups(&self) -> MutexGuard<'_, Vec<Cleanup>> {
+        self.cleanups.lock().expect("测试数据锁已损坏")
+    }
+}
+
+impl std::fmt::Debug for Fixtures {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Fixtures")
+            .field("sets", &self.names())
+            .field("inserted", &self.lock_cleanups().len())
+            .finish()
+    }
+}
+
+/// 将主键转换为仓储的主键类型
+fn primary_key<K: DeserializeOwned>(name: &str, id: &Value) -> Result<K> {
+    serde_json::from_value(id.clone())
+        .map_err(|e| Error::validation(format!("测试数据 {} 的主键 {} 无效: {}", name, id, e)))
+}
+
+/// 将记录转换为实体
+fn entity<E: DeserializeOwned>(name: &str, record: Value) -> Result<E> {
+    serde_json::from_value(record)
+        .map_err(|e| Error::validation(format!("测试数据 {} 无法转换为实体: {}", name, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InMemoryRepository;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct User {
+        #[serde(default)]
+        id: u64,
+        name: String,
+    }
+
+    /// 测试加载数据集，通过仓储插入并清理
+    #[tokio::test]
+    async fn test_fixtures() {
+        let fixtures = Fixtures::new();
+        fixtures
+            .add_yaml("users:\n  - { name: alice }\n  - { name: bob }\n")
+            .unwrap()
+            .add_json(r#"{ "orders": [] }"#)
+            .unwrap();
+        assert_eq!(fixtures.names(), ["orders", "users"]);
+        assert!(fixtures.add_yaml("users: alice").is_err());
+        assert!(fixtures.records::<User>("missing").is_err());
+
+        let repository = Arc::new(InMemoryRepository::<User>::new());
+        repository
+            .insert(User {
+                id: 10,
+                name: "carol".to_string(),
+            })
+            .unwrap();
+        let ids = fixtures.insert("users", repository.clone()).await.unwrap();
+        assert_eq!(ids, [Value::from(11), Value::from(12)]);
+        assert_eq!(repository.len(), 3);
+
+        fixtures.cleanup().await.unwrap();
+        assert_eq!(repository.entities()[0].name, "carol");
+        assert_eq!(repository.len(), 1);
+    }
+
+    /// 测试记录的主键无效时不插入
+    #[tokio::test]
+    async fn test_invalid_primary_key() {
+        let fixtures = Fixtures::new();
+        fixtures
+            .add_json(r#"{ "users": [{ "id": -1, "name": "alice" }] }"#)
+            .unwrap();
+
+        let repository = Arc::new(InMemoryRepository::<User>::new());
+        let error = fixtures
+            .insert("users", repository.clone())
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("主键"), "{}", error);
+        assert_eq!(repository.len(), 0);
+        fixtures.cleanup().await.unwrap();
+    }
+}
@@ -0,0 +1,107 @@
+//! RSpring 测试支持
+//!
+//! 为集成测试创建独立的应用上下文：
+//! - [`TestContext`]：按指定环境（默认为 `test`）和临时配置创建应用上下文，测试结束时清理，
+//!   全局组件和日志订阅者按测试隔离，测试可以并行执行
+//! - `#[rspring_test]`：在测试函数中创建上下文、执行自动装配并按参数类型注入组件
+//! - [`MockClock`]：只在测试中前进的时钟，确定地测试缓存过期、任务调度等依赖时间的逻辑
+//! - [`PublishedEvents`]：记录测试期间发布的应用事件，断言事件是否发布
+//! - [`InMemoryRepository`]（`data-mysql` 特性）：以 `HashMap` 实现的仓储，支持分页和排序，
+//!   替换服务依赖的数据库仓储
+//! - [`Fixtures`]（`data-mysql` 特性）：从 YAML/JSON 文件读取测试数据，通过仓储插入，
+//!   测试结束时自动删除
+//! - [`TestClient`]（`web` 特性）：在进程内调用应用路由，以 JSON 收发请求并断言响应
+//! - [`ContractSnapshot`]（`web` 特性）：将控制器的路由和 OpenAPI 描述保存为快照，
+//!   接口契约意外变更时测试失败
+//! - [`ServiceContainers`]（`containers` 特性）：启动 PostgreSQL、Redis、Kafka 容器，
+//!   连接地址写入测试配置，Redis 容器同时自动初始化 Redis 启动器（`data-redis` 特性）
+//!
+//! # 示例
+//! ```toml
+//! # application-test.toml
+//! [server]
+//! port = 0
+//!
+//! [jobs]
+//! backend = "memory"
+//! ```
+//!
+//! ```rust
+//! async fn register(context: &TestContext) -> Result<()> {
+//!     context.register_singleton(UserService::new()).await;
+//!     Ok(())
+//! }
+//!
+//! #[rspring_test(config = "[app]\nname = \"users\"", setup = "register")]
+//! async fn finds_user(service: Arc<UserService>) {
+//!     assert!(service.find(1).await.unwrap().is_none());
+//! }
+//! ```
+
+#[cfg(feature = "web")]
+pub mod client;
+pub mod clock;
+#[cfg(feature = "containers")]
+pub mod containers;
+pub mod context;
+#[cfg(feature = "web")]
+pub mod contract;
+pub mod events;
+#[cfg(feature = "data-mysql")]
+pub mod fixtures;
+#[cfg(feature = "data-mysql")]
+pub mod repository;
+
+#[cfg(feature = "web")]
+pub use client::{TestClient, TestResponse};
+pub use clock::MockClock;
+#[cfg(feature = "containers")]
+pub use containers::{Service, ServiceContainers};
+pub use context::{TestContext, TestContextBuilder, DEFAULT_TEST_PROFILE};
+#[cfg(feature = "web")]
+pub use contract::ContractSnapshot;
+pub use events::PublishedEvents;
+#[cfg(feature = "data-mysql")]
+pub use fixtures::Fixtures;
+#[cfg(feature = "data-mysql")]
+pub use repository::InMemoryRepository;
+pub use rspring_test_macros::rspring_test;
+
+use std::future::Future;
+#[cfg(any(feature = "web", feature = "data-mysql"))]
+use std::path::PathBuf;
+
+use tracing_subscriber::EnvFilter;
+
+/// 未设置 `RUST_LOG` 时测试日志的级别
+const DEFAULT_TEST_LOG_FILTER: &str = "info";
+
+/// 在 Tokio 单线程运行时中执行测试，供 `#[rspring_test]` 生成的代码使用
+///
+/// 测试期间在当前线程使用输出到测试框架的日志订阅者，不设置全局订阅者
+#[doc(hidden)]
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(DEFAULT_TEST_LOG_FILTER));
+    let subscriber = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_test_writer()
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("创建测试运行时失败")
+        .block_on(future)
+}
+
+/// 获取 crate 根目录，测试数据和快照的相对路径相对于它解析
+///
+/// 测试运行时由 cargo 设置 `CARGO_MANIFEST_DIR`，未设置时为当前目录
+#[cfg(any(feature = "web", feature = "data-mysql"))]
+pub(crate) fn manifest_dir() -> PathBuf {
+    std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+}
@@ -0,0 +1,415 @@
+//! 内存仓储模块
+//!
+//! [`InMemoryRepository`] 以 `HashMap` 实现 [`CrudRepository`]，服务测试无需数据库：
+//! - 实体通过 serde 读取主键和排序字段，默认主键字段为 `id`
+//! - `save` 与 MySQL 自增主键一致，忽略实体中的数值主键并分配新主键
+//! - `find_all` 按保存顺序返回，`find_page` 按 [`Sort`] 排序后分页，`NULL` 排在升序最前
+//! - 可以通过 [`TestContext::mock`](crate::TestContext::mock) 替换服务依赖的仓储
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use rspring_core::page::{is_valid_property, Direction, Page, PageResult, Sort};
+use rspring_core::{Component, Error, Result};
+use rspring_data_mysql::{async_trait, CrudRepository};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+/// 内存仓储
+///
+/// # 示例
+/// ```rust
+/// pub struct UserService<R: CrudRepository<Entity = User, Id = u64> = UserRepository> {
+///     repository: Arc<R>,
+/// }
+///
+/// #[rspring_test]
+/// async fn registers_user(context: &TestContext) {
+///     let repository = context.mock(InMemoryRepository::<User>::new()).await;
+///     let service = UserService::new(repository.clone());
+///
+///     service.register("alice").await.unwrap();
+///     assert_eq!(repository.len(), 1);
+/// }
+/// ```
+pub struct InMemoryRepository<E, Id = u64> {
+    /// 主键字段名
+    id_property: String,
+    /// 实体数据
+    state: RwLock<State<E>>,
+    /// 主键类型
+    id: PhantomData<fn(Id)>,
+}
+
+/// 内存仓储的数据
+struct State<E> {
+    /// 按主键保存的实体及保存顺序
+    entities: HashMap<String, (u64, E)>,
+    /// 下一个保存顺序
+    next_sequence: u64,
+    /// 下一个自增主键
+    next_id: u64,
+}
+
+impl<E, Id> InMemoryRepository<E, Id>
+where
+    E: Clone + Serialize + DeserializeOwned + Send + Sync,
+    Id: Serialize,
+{
+    /// 创建主键字段为 `id` 的空仓储
+    pub fn new() -> Self {
+        Self::with_id_property("id")
+    }
+
+    /// 创建指定主键字段的空仓储
+    pub fn with_id_property(id_property: impl Into<String>) -> Self {
+        Self {
+            id_property: id_property.into(),
+            state: RwLock::new(State {
+                entities: HashMap::new(),
+                next_sequence: 0,
+                next_id: 1,
+            }),
+            id: PhantomData,
+        }
+    }
+
+    /// 保存实体并保留实体中的主键，已存在时替换，用于准备测试数据
+    ///
+    /// # 错误
+    /// 实体无法序列化或没有主键字段时返回错误
+    pub fn insert(&self, entity: E) -> Result<()> {
+        let value = to_value(&entity)?;
+        let id = self.entity_id(&value)?;
+        let mut state = self.write();
+        if let Some(id) = id.as_u64() {
+            state.next_id = state.next_id.max(id + 1);
+        }
+        state.put(key(&id), entity);
+        Ok(())
+    }
+
+    /// 批量保存实体并保留实体中的主键
+    ///
+    /// # 错误
+    /// 实体无法序列化或没有主键字段时返回错误
+    pub fn insert_all(&self, entities: impl IntoIterator<Item = E>) -> Result<()> {
+        entities
+            .into_iter()
+            .try_for_each(|entity| self.insert(entity))
+    }
+
+    /// 按保存顺序获取所有实体
+    pub fn entities(&self) -> Vec<E> {
+        let state = self.read();
+        let mut entities: Vec<_> = state.entities.values().collect();
+        entities.sort_by_key(|(sequence, _)| *sequence);
+        entities
+            .into_iter()
+            .map(|(_, entity)| entity.clone())
+            .collect()
+    }
+
+    /// 获取实体数量
+    pub fn len(&self) -> usize {
+        self.read().entities.len()
+    }
+
+    /// 是否没有实体
+    pub fn is_empty(&self) -> bool {
+        self.read().entities.is_empty()
+    }
+
+    /// 清空实体，自增主键从 1 重新开始
+    pub fn clear(&self) {
+        let mut state = self.write();
+        state.entities.clear();
+        state.next_id = 1;
+    }
+
+    /// 读取实体的主键
+    fn entity_id(&self, value: &Value) -> Result<Value> {
+        match value.get(&self.id_property) {
+            Some(id) if !id.is_null() => Ok(id.clone()),
+            _ => Err(Error::validation(format!(
+                "实体没有主键字段 {}",
+                self.id_property
+            ))),
+        }
+    }
+
+    /// 锁定数据用于读取
+    fn read(&self) -> RwLockReadGuard<'_, State<E>> {
+        self.state.read().expect("内存仓储锁已损坏")
+    }
+
+    /// 锁定数据用于修改
+    fn write(&self) -> RwLockWriteGuard<'_, State<E>> {
+        self.state.write().expect("内存仓储锁已损坏")
+    }
+}
+
+impl<E, Id> Default for InMemoryRepository<E, Id>
+where
+    E: Clone + Serialize + DeserializeOwned + Send + Sync,
+    Id: Serialize,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> State<E> {
+    /// 保存实体，替换时保留原来的保存顺序
+    fn put(&mut self, key: String, entity: E) {
+        let sequence = match self.entities.get(&key) {
+            Some((sequence, _)) => *sequence,
+            None => {
+                self.next_sequence += 1;
+                self.next_sequence
+            }
+        };
+        self.entities.insert(key, (sequence, entity));
+    }
+}
+
+#[async_trait]
+impl<E, Id> CrudRepository for InMemoryRepository<E, Id>
+where
+    E: Clone + Serialize + DeserializeOwned + Send + Sync,
+    Id: Serialize + Send + Sync,
+{
+    type Entity = E;
+    type Id = Id;
+
+    async fn find_by_id(&self, id: Id) -> Result<Option<E>> {
+        let key = key(&to_value(&id)?);
+        Ok(self
+            .read()
+            .entities
+            .get(&key)
+            .map(|(_, entity)| entity.clone()))
+    }
+
+    async fn find_all(&self) -> Result<Vec<E>> {
+        Ok(self.entities())
+    }
+
+    async fn find_page(&self, page: Page, sort: &Sort) -> Result<PageResult<E>> {
+        if let Some(order) = sort
+            .orders
+            .iter()
+            .find(|order| !is_valid_property(&order.property))
+        {
+            return Err(Error::validation(format!(
+                "无效的排序字段: {}",
+                order.property
+            )));
+        }
+
+        let mut rows = self
+            .entities()
+            .into_iter()
+            .map(|entity| Ok((to_value(&entity)?, entity)))
+            .collect::<Result<Vec<_>>>()?;
+        rows.sort_by(|(a, _), (b, _)| {
+            sort.orders
+                .iter()
+                .map(|order| {
+                    let ordering =
+                        compare(property(a, &order.property), property(b, &order.property));
+                    match order.direction {
+                        Direction::Asc => ordering,
+                        Direction::Desc => ordering.reverse(),
+                    }
+                })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let total = rows.len() as u64;
+        let content = rows
+            .into_iter()
+            .skip(page.offset() as usize)
+            .take(page.size as usize)
+            .map(|(_, entity)| entity)
+            .collect();
+        Ok(PageResult::new(content, page.page, page.size, total))
+    }
+
+    async fn save(&self, entity: &E) -> Result<u64> {
+        let mut value = to_value(entity)?;
+        let mut state = self.write();
+        let current = value.get(&self.id_property).cloned().unwrap_or_default();
+
+        // 数值主键按自增分配，其他主键保持不变，与 MySQL 的 LAST_INSERT_ID 一致返回 0
+        let (id, generated) = if current.is_null() || current.is_number() {
+            let id = state.next_id;
+            state.next_id += 1;
+            (Value::from(id), id)
+        } else {
+            (current, 0)
+        };
+        let key = key(&id);
+        if state.entities.contains_key(&key) {
+            return Err(Error::internal(format!(
+                "数据库操作失败: 主键 {} 重复",
+                key
+            )));
+        }
+
+        if let Value::Object(fields) = &mut value {
+            fields.insert(self.id_property.clone(), id);
+        }
+        state.put(key, from_value(value)?);
+        Ok(generated)
+    }
+
+    async fn update(&self, entity: &E) -> Result<bool> {
+        let key = key(&self.entity_id(&to_value(entity)?)?);
+        let mut state = self.write();
+        if !state.entities.contains_key(&key) {
+            return Ok(false);
+        }
+        state.put(key, entity.clone());
+        Ok(true)
+    }
+
+    async fn delete_by_id(&self, id: Id) -> Result<bool> {
+        let key = key(&to_value(&id)?);
+        Ok(self.write().entities.remove(&key).is_some())
+    }
+}
+
+impl<E, Id> Component for InMemoryRepository<E, Id>
+where
+    E: Send + Sync,
+{
+    fn component_name(&self) -> &'static str {
+        "InMemoryRepository"
+    }
+}
+
+impl<E, Id> std::fmt::Debug for InMemoryRepository<E, Id> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InMemoryRepository")
+            .field("entity", &std::any::type_name::<E>())
+            .field("id_property", &self.id_property)
+            .finish()
+    }
+}
+
+/// 主键在 `HashMap` 中的键
+fn key(id: &Value) -> String {
+    id.to_string()
+}
+
+/// 序列化实体或主键
+fn to_value<T: Serialize>(value: &T) -> Result<Value> {
+    serde_json::to_value(value).map_err(|e| Error::internal(format!("序列化实体失败: {}", e)))
+}
+
+/// 反序列化实体
+fn from_value<E: DeserializeOwned>(value: Value) -> Result<E> {
+    serde_json::from_value(value).map_err(|e| Error::internal(format!("反序列化实体失败: {}", e)))
+}
+
+/// 按点分隔的路径读取字段，不存在时为 `NULL`
+fn property<'a>(value: &'a Value, path: &str) -> &'a Value {
+    path.split('.')
+        .try_fold(value, |value, field| value.get(field))
+        .unwrap_or(&Value::Null)
+}
+
+/// 比较字段值，`NULL` 最小，数值按大小、字符串按字典序比较
+fn compare(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Null, Value::Null) => Ordering::Equal,
+        (Value::Null, _) => Ordering::Less,
+        (_, Value::Null) => Ordering::Greater,
+        (Value::Number(a), Value::Number(b)) => match (a.as_i64(), b.as_i64()) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            _ => a
+                .as_f64()
+                .partial_cmp(&b.as_f64())
+                .unwrap_or(Ordering::Equal),
+        },
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        _ => a.to_string().cmp(&b.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rspring_core::page::Order;
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct User {
+        id: u64,
+        name: String,
+        age: Option<u32>,
+    }
+
+    fn user(id: u64, name: &str, age: Option<u32>) -> User {
+        User {
+            id,
+            name: name.to_string(),
+            age,
+        }
+    }
+
+    /// 测试自增主键、更新删除以及排序分页
+    #[tokio::test]
+    async fn test_in_memory_repository() {
+        let repository = InMemoryRepository::<User>::new();
+        repository.insert(user(10, "carol", Some(30))).unwrap();
+        assert_eq!(repository.save(&user(0, "alice", None)).await.unwrap(), 11);
+        assert_eq!(
+            repository.save(&user(0, "bob", Some(25))).await.unwrap(),
+            12
+        );
+
+        assert_eq!(
+            repository.find_by_id(11).await.unwrap(),
+            Some(user(11, "alice", None))
+        );
+        assert!(repository
+            .update(&user(12, "bobby", Some(26)))
+            .await
+            .unwrap());
+        assert!(!repository.update(&user(99, "nobody", None)).await.unwrap());
+        let names: Vec<_> = repository
+            .find_all()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|user| user.name)
+            .collect();
+        assert_eq!(names, ["carol", "alice", "bobby"]);
+
+        let sort = Sort::by(vec![Order::desc("age")]);
+        let page = repository.find_page(Page::new(0, 2), &sort).await.unwrap();
+        assert_eq!(page.total, 3);
+        assert_eq!(page.total_pages, 2);
+        assert_eq!(
+            page.content,
+            [user(10, "carol", Some(30)), user(12, "bobby", Some(26))]
+        );
+        let page = repository.find_page(Page::new(1, 2), &sort).await.unwrap();
+        assert_eq!(page.content, [user(11, "alice", None)]);
+        assert!(repository
+            .find_page(Page::default(), &Sort::by(vec![Order::asc("name;")]))
+            .await
+            .is_err());
+
+        assert!(repository.delete_by_id(10).await.unwrap());
+        assert!(!repository.delete_by_id(10).await.unwrap());
+        assert_eq!(repository.len(), 2);
+    }
+}
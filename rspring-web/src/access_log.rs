@@ -0,0 +1,399 @@
+//! 访问日志模块
+//!
+//! 按 `[web.access_log]` 配置将每个请求以 Common Log Format 或 JSON 格式写入独立的
+//! 滚动日志文件，与应用日志分开。日志经有界通道交给后台线程写入，不阻塞请求处理，
+//! 通道已满时丢弃记录并计数，后台线程在下一次写入时输出丢弃的记录数
+
+use std::io::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::sync::Arc;
+use std::time::Instant;
+
+use axum::body::HttpBody;
+use axum::extract::Request;
+use axum::http::{header, HeaderMap};
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use axum::Router;
+use chrono::{DateTime, Local};
+use rspring_core::logging::RollingFile;
+use rspring_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::forwarded::client_ip;
+use crate::request_id::RequestId;
+
+/// 通道中最多缓存的访问记录数
+const BUFFERED_LINES: usize = 64_000;
+
+/// 访问日志格式
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AccessLogFormat {
+    /// Common Log Format，如 `127.0.0.1 - - [10/Oct/2024:13:55:36 +0800] "GET / HTTP/1.1" 200 12`
+    #[default]
+    Common,
+    /// 每行一个 JSON 对象，额外包含耗时、请求 ID 和 User-Agent
+    Json,
+}
+
+/// 访问日志配置
+///
+/// 应在其他中间件之后应用，使记录的状态码和耗时包含所有中间件的处理结果；
+/// 需要记录代理还原的客户端地址时，`ProxyConfig` 应在此之后应用
+///
+/// # 示例
+/// ```toml
+/// [web.access_log]
+/// enabled = true
+/// format = "json"
+/// path = "logs/access.log"
+/// max_file_size = 50
+/// max_files = 10
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct AccessLogConfig {
+    /// 是否启用
+    ///
+    /// # 默认值
+    /// `false`
+    #[serde(default)]
+    pub enabled: bool,
+    /// 日志格式，`"common"` 或 `"json"`
+    ///
+    /// # 默认值
+    /// `"common"`
+    #[serde(default)]
+    pub format: AccessLogFormat,
+    /// 日志文件路径
+    ///
+    /// # 默认值
+    /// `"logs/access.log"`
+    #[serde(default = "default_path")]
+    pub path: String,
+    /// 日志文件最大大小（MB）
+    ///
+    /// # 默认值
+    /// `100`
+    #[serde(default = "default_max_file_size")]
+    pub max_file_size: u64,
+    /// 日志文件保留数量
+    ///
+    /// # 默认值
+    /// `7`
+    #[serde(default = "default_max_files")]
+    pub max_files: u32,
+}
+
+impl Default for AccessLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            format: AccessLogFormat::default(),
+            path: default_path(),
+            max_file_size: default_max_file_size(),
+            max_files: default_max_files(),
+        }
+    }
+}
+
+impl AccessLogConfig {
+    /// 将访问日志中间件应用到路由，未启用时原样返回
+    ///
+    /// # 错误
+    /// 日志文件大小超出范围、日志文件无法创建或写入线程无法启动时返回错误
+    pub fn apply<S>(&self, router: Router<S>) -> Result<Router<S>>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        if !self.enabled {
+            return Ok(router);
+        }
+
+        let max_file_size = self.max_file_size.checked_mul(1024 * 1024).ok_or_else(|| {
+            Error::validation(format!(
+                "访问日志文件大小超出范围: {} MB",
+                self.max_file_size
+            ))
+        })?;
+        let file = RollingFile::open(&self.path, max_file_size, self.max_files)?;
+        let writer = spawn_writer(file)?;
+        let format = self.format;
+        Ok(
+            router.layer(middleware::from_fn(move |request: Request, next: Next| {
+                log_access(writer.clone(), format, request, next)
+            })),
+        )
+    }
+}
+
+/// 一条访问记录
+#[derive(Debug, Clone)]
+struct AccessEntry {
+    /// 请求时间
+    time: DateTime<Local>,
+    /// 客户端地址
+    client_ip: Option<String>,
+    /// 请求方法
+    method: String,
+    /// 请求路径（含查询参数）
+    uri: String,
+    /// HTTP 版本，如 `HTTP/1.1`
+    version: String,
+    /// 响应状态码
+    status: u16,
+    /// 响应体字节数，未知时为 `None`
+    bytes: Option<u64>,
+    /// 处理耗时（毫秒）
+    duration_ms: f64,
+    /// 请求 ID
+    request_id: Option<String>,
+    /// Referer 请求头
+    referer: Option<String>,
+    /// User-Agent 请求头
+    user_agent: Option<String>,
+}
+
+impl AccessEntry {
+    /// 按指定格式生成一行日志
+    fn format(&self, format: AccessLogFormat) -> String {
+        match format {
+            AccessLogFormat::Common => format!(
+                "{} - - [{}] \"{} {} {}\" {} {}",
+                self.client_ip.as_deref().unwrap_or("-"),
+                self.time.format("%d/%b/%Y:%H:%M:%S %z"),
+                self.method,
+                self.uri,
+                self.version,
+                self.status,
+                self.bytes
+                    .map(|bytes| bytes.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+            ),
+            AccessLogFormat::Json => json!({
+                "timestamp": self.time.to_rfc3339(),
+                "client_ip": self.client_ip,
+                "method": self.method,
+                "uri": self.uri,
+                "version": self.version,
+                "status": self.status,
+                "bytes": self.bytes,
+                "duration_ms": self.duration_ms,
+                "request_id": self.request_id,
+                "referer": self.referer,
+                "user_agent": self.user_agent,
+            })
+            .to_string(),
+        }
+    }
+}
+
+/// 发送访问记录到后台线程的写入端
+#[derive(Debug, Clone)]
+struct AccessLogWriter {
+    /// 发送日志行的通道
+    sender: SyncSender<String>,
+    /// 因通道已满被丢弃的记录数
+    dropped: Arc<AtomicU64>,
+}
+
+impl AccessLogWriter {
+    /// 发送一行日志，通道已满时丢弃并计数
+    fn send(&self, line: String) {
+        if let Err(TrySendError::Full(_)) = self.sender.try_send(line) {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// 访问日志中间件
+async fn log_access(
+    writer: AccessLogWriter,
+    format: AccessLogFormat,
+    request: Request,
+    next: Next,
+) -> Response {
+    let start = Instant::now();
+    let time = Local::now();
+    let client_ip = client_ip(request.extensions()).map(|ip| ip.to_string());
+    let method = request.method().to_string();
+    let uri = request
+        .uri()
+        .path_and_query()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let version = format!("{:?}", request.version());
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .map(|id| id.0.clone());
+    let referer = header_string(request.headers(), header::REFERER);
+    let user_agent = header_string(request.headers(), header::USER_AGENT);
+
+    let response = next.run(request).await;
+
+    let entry = AccessEntry {
+        time,
+        client_ip,
+        method,
+        uri,
+        version,
+        status: response.status().as_u16(),
+        bytes: response
+            .headers()
+            .get(header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok())
+            .or_else(|| response.body().size_hint().exact()),
+        duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+        request_id,
+        referer,
+        user_agent,
+    };
+    writer.send(entry.format(format));
+    response
+}
+
+/// 启动写入日志文件的后台线程，所有发送端关闭后线程退出
+fn spawn_writer(mut file: RollingFile) -> Result<AccessLogWriter> {
+    let (sender, receiver) = mpsc::sync_channel::<String>(BUFFERED_LINES);
+    let dropped = Arc::new(AtomicU64::new(0));
+    let counter = dropped.clone();
+    std::thread::Builder::new()
+        .name("access-log".to_string())
+        .spawn(move || {
+            for line in receiver {
+                let count = counter.swap(0, Ordering::Relaxed);
+                if count > 0 {
+                    tracing::warn!("访问日志写入过慢，已丢弃 {} 条记录", count);
+                }
+                if let Err(e) = file.write_all(format!("{}\n", line).as_bytes()) {
+                    tracing::error!("写入访问日志失败 ({}): {}", file.path().display(), e);
+                }
+            }
+        })
+        .map_err(|e| Error::runtime(format!("启动访问日志线程失败: {}", e)))?;
+    Ok(AccessLogWriter { sender, dropped })
+}
+
+/// 读取请求头的字符串值
+fn header_string(headers: &HeaderMap, name: header::HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+// 默认值函数
+
+fn default_path() -> String {
+    "logs/access.log".to_string()
+}
+
+fn default_max_file_size() -> u64 {
+    100
+}
+
+fn default_max_files() -> u32 {
+    7
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::routing::get;
+    use std::time::Duration;
+    use tower::ServiceExt;
+
+    /// 构造测试用的访问记录
+    fn entry() -> AccessEntry {
+        AccessEntry {
+            time: DateTime::parse_from_rfc3339("2024-10-10T13:55:36+08:00")
+                .unwrap()
+                .with_timezone(&Local),
+            client_ip: Some("203.0.113.7".to_string()),
+            method: "GET".to_string(),
+            uri: "/api/users?page=2".to_string(),
+            version: "HTTP/1.1".to_string(),
+            status: 200,
+            bytes: None,
+            duration_ms: 1.5,
+            request_id: Some("abc".to_string()),
+            referer: None,
+            user_agent: Some("curl/8.0".to_string()),
+        }
+    }
+
+    /// 测试 Common Log Format 和 JSON 格式
+    #[test]
+    fn test_format() {
+        let entry = entry();
+        let common = entry.format(AccessLogFormat::Common);
+        assert!(common.starts_with("203.0.113.7 - - ["));
+        assert!(common.ends_with("] \"GET /api/users?page=2 HTTP/1.1\" 200 -"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&entry.format(AccessLogFormat::Json)).unwrap();
+        assert_eq!(json["status"], 200);
+        assert_eq!(json["request_id"], "abc");
+        assert_eq!(json["user_agent"], "curl/8.0");
+        assert!(json["bytes"].is_null());
+    }
+
+    /// 测试日志文件大小溢出时返回错误
+    #[test]
+    fn test_max_file_size_overflow() {
+        let config = AccessLogConfig {
+            enabled: true,
+            max_file_size: u64::MAX,
+            ..AccessLogConfig::default()
+        };
+        assert!(config.apply(Router::<()>::new()).is_err());
+    }
+
+    /// 测试通道已满时丢弃记录并计数
+    #[test]
+    fn test_drop_when_full() {
+        let (sender, _receiver) = mpsc::sync_channel(1);
+        let writer = AccessLogWriter {
+            sender,
+            dropped: Arc::new(AtomicU64::new(0)),
+        };
+        writer.send("a".to_string());
+        writer.send("b".to_string());
+        writer.send("c".to_string());
+        assert_eq!(writer.dropped.load(Ordering::Relaxed), 2);
+    }
+
+    /// 测试请求写入访问日志文件
+    #[tokio::test]
+    async fn test_access_log_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("access.log");
+        let config = AccessLogConfig {
+            enabled: true,
+            path: path.to_string_lossy().to_string(),
+            ..AccessLogConfig::default()
+        };
+
+        let router = config
+            .apply(Router::new().route("/ping", get(|| async { "pong" })))
+            .unwrap();
+        let request = Request::get("/ping?x=1").body(Body::empty()).unwrap();
+        router.oneshot(request).await.unwrap();
+
+        // 等待后台线程写入
+        let mut content = String::new();
+        for _ in 0..100 {
+            content = std::fs::read_to_string(&path).unwrap_or_default();
+            if !content.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(content.contains("\"GET /ping?x=1 HTTP/1.1\" 200 4"));
+    }
+}
@@ -0,0 +1,750 @@
+//! Actuator 管理端点模块
+//!
+//! 提供类似 Spring Boot Actuator 的管理端点，按 `[management]` 配置挂载在 `base_path` 下：
+//! - `health` 汇总 [`HealthRegistry`] 中所有健康指示器的状态，`DOWN` 或 `OUT_OF_SERVICE` 时返回 503
+//! - `info` 返回 `[app]` 中的应用信息、[`InfoContributor`] 提供的构建和 Git 信息以及配置中的附加信息
+//! - `loggers` 查看和修改日志级别；启用 `prometheus` 特性后 `prometheus` 导出指标
+//! - `beans` 返回依赖注入容器中的组件；`env` 返回生效的配置属性及其来源，敏感值被替换
+//! - `startup` 返回启动报告：配置来源、各启动阶段和组件的耗时以及已启动的监听器
+//! - `tasks` 返回任务转储：未结束的托管任务、周期任务和 Tokio 运行时信息，用于排查卡住的任务
+//! - `base_path` 本身返回所有已暴露端点的链接
+//!
+//! 只有启用且列在 `exposure` 中的端点可以访问，默认只暴露 `health` 和 `info`。
+//! 设置 `listener` 后端点挂载在 `[[server.listeners]]` 中对应的管理端口上，不再通过主端口访问
+
+mod beans;
+mod env;
+
+pub use env::SANITIZED_VALUE;
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use rspring_core::config::properties::Configuration;
+use rspring_core::config::{AppConfig, ConfigurationManager};
+use rspring_core::container::Container;
+use rspring_core::health::{HealthRegistry, HealthStatus};
+use rspring_core::startup::startup_report;
+use rspring_core::task::dump::task_dump;
+use rspring_core::{ApplicationContext, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use tokio::sync::RwLock;
+
+use crate::exception::error_response;
+use crate::loggers::loggers_router;
+use crate::server::WebServer;
+
+/// 暴露所有端点的通配符
+pub const EXPOSE_ALL: &str = "*";
+
+/// 管理端点配置
+///
+/// # 示例
+/// ```toml
+/// [management]
+/// base_path = "/actuator"
+/// listener = "management"
+/// exposure = ["health", "info", "loggers"]
+///
+/// [management.health]
+/// show_details = false
+///
+/// [management.info.properties]
+/// team = "orders"
+/// git = { commit = "${GIT_COMMIT}" }
+///
+/// [management.env]
+/// keys_to_sanitize = ["password", "secret", "key", "token", "credentials", "hash", "dsn"]
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ActuatorConfig {
+    /// 是否启用
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 端点的路径前缀
+    ///
+    /// # 默认值
+    /// `"/actuator"`
+    #[serde(default = "default_base_path")]
+    pub base_path: String,
+    /// 挂载端点的监听器名称，为空时挂载在主端口上
+    #[serde(default)]
+    pub listener: Option<String>,
+    /// 暴露的端点名称，`"*"` 表示所有端点
+    ///
+    /// # 默认值
+    /// `["health", "info"]`
+    #[serde(default = "default_exposure")]
+    pub exposure: Vec<String>,
+    /// `health` 端点配置
+    #[serde(default)]
+    pub health: HealthEndpointConfig,
+    /// `info` 端点配置
+    #[serde(default)]
+    pub info: InfoEndpointConfig,
+    /// `loggers` 端点配置
+    #[serde(default)]
+    pub loggers: EndpointConfig,
+    /// `prometheus` 端点配置
+    #[serde(default)]
+    pub prometheus: EndpointConfig,
+    /// `beans` 端点配置
+    #[serde(default)]
+    pub beans: EndpointConfig,
+    /// `env` 端点配置
+    #[serde(default)]
+    pub env: EnvEndpointConfig,
+    /// `startup` 端点配置
+    #[serde(default)]
+    pub startup: EndpointConfig,
+    /// `tasks` 端点配置
+    #[serde(default)]
+    pub tasks: EndpointConfig,
+}
+
+/// 端点配置
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct EndpointConfig {
+    /// 是否启用
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+/// `health` 端点配置
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct HealthEndpointConfig {
+    /// 是否启用
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 是否返回各组件的健康信息，关闭时只返回整体状态
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_enabled")]
+    pub show_details: bool,
+}
+
+/// `info` 端点配置
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct InfoEndpointConfig {
+    /// 是否启用
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 附加信息，原样合并到返回结果中
+    #[serde(default)]
+    pub properties: Map<String, Value>,
+}
+
+/// `env` 端点配置
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct EnvEndpointConfig {
+    /// 是否启用
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 需要替换值的键名后缀，不区分大小写，按配置键的最后一段、数组和表中的嵌套键
+    /// 以及 URL 查询参数名匹配
+    ///
+    /// # 默认值
+    /// `["password", "secret", "key", "token", "credentials", "hash"]`
+    #[serde(default = "default_keys_to_sanitize")]
+    pub keys_to_sanitize: Vec<String>,
+}
+
+impl Default for ActuatorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            base_path: default_base_path(),
+            listener: None,
+            exposure: default_exposure(),
+            health: HealthEndpointConfig::default(),
+            info: InfoEndpointConfig::default(),
+            loggers: EndpointConfig::default(),
+            prometheus: EndpointConfig::default(),
+            beans: EndpointConfig::default(),
+            env: EnvEndpointConfig::default(),
+            startup: EndpointConfig::default(),
+            tasks: EndpointConfig::default(),
+        }
+    }
+}
+
+impl Default for EndpointConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+        }
+    }
+}
+
+impl Default for HealthEndpointConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            show_details: default_enabled(),
+        }
+    }
+}
+
+impl Default for EnvEndpointConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            keys_to_sanitize: default_keys_to_sanitize(),
+        }
+    }
+}
+
+impl Default for InfoEndpointConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            properties: Map::new(),
+        }
+    }
+}
+
+impl ActuatorConfig {
+    /// 从配置管理器读取 `[management]` 章节
+    ///
+    /// 未配置时使用默认值
+    pub fn load(config: &ConfigurationManager) -> Result<Self> {
+        if config.contains_key("management") {
+            config.get_section("management")
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// 判断端点是否暴露
+    pub fn is_exposed(&self, endpoint: &str) -> bool {
+        self.exposure
+            .iter()
+            .any(|name| name == EXPOSE_ALL || name == endpoint)
+    }
+}
+
+impl Configuration for ActuatorConfig {}
+
+/// `info` 端点的信息提供者
+///
+/// # 示例
+/// ```rust
+/// pub struct RegionInfo;
+///
+/// impl InfoContributor for RegionInfo {
+///     fn contribute(&self, info: &mut Map<String, Value>) {
+///         info.insert("region".to_string(), json!(std::env::var("REGION").ok()));
+///     }
+/// }
+/// ```
+pub trait InfoContributor: Send + Sync {
+    /// 向返回结果中加入信息
+    fn contribute(&self, info: &mut Map<String, Value>);
+}
+
+/// 构建和 Git 信息
+///
+/// 由应用在编译期读取，输出到 `build` 和 `git` 字段
+///
+/// # 示例
+/// ```rust
+/// let build = BuildInfo::new(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))
+///     .with_time(option_env!("BUILD_TIME"))
+///     .with_git(option_env!("GIT_COMMIT"), option_env!("GIT_BRANCH"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildInfo {
+    /// 构件名称
+    pub name: String,
+    /// 构件版本
+    pub version: String,
+    /// 构建时间
+    pub time: Option<String>,
+    /// Git 提交
+    pub commit: Option<String>,
+    /// Git 分支
+    pub branch: Option<String>,
+}
+
+impl BuildInfo {
+    /// 创建构建信息
+    pub fn new(name: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: version.into(),
+            ..Self::default()
+        }
+    }
+
+    /// 设置构建时间
+    pub fn with_time(mut self, time: Option<&str>) -> Self {
+        self.time = time.map(str::to_string);
+        self
+    }
+
+    /// 设置 Git 提交和分支
+    pub fn with_git(mut self, commit: Option<&str>, branch: Option<&str>) -> Self {
+        self.commit = commit.map(str::to_string);
+        self.branch = branch.map(str::to_string);
+        self
+    }
+}
+
+impl InfoContributor for BuildInfo {
+    fn contribute(&self, info: &mut Map<String, Value>) {
+        let mut build = json!({ "name": self.name, "version": self.version });
+        if let Some(time) = &self.time {
+            build["time"] = json!(time);
+        }
+        info.insert("build".to_string(), build);
+
+        if self.commit.is_some() || self.branch.is_some() {
+            info.insert(
+                "git".to_string(),
+                json!({ "commit": self.commit, "branch": self.branch }),
+            );
+        }
+    }
+}
+
+/// 管理端点
+///
+/// # 示例
+/// ```rust
+/// let actuator = Actuator::from_context(&context)
+///     .await?
+///     .info_contributor(BuildInfo::new(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")));
+///
+/// let server = actuator.mount(WebServer::from_config(context.config_manager(), app)?);
+/// server.run().await?;
+/// ```
+#[derive(Clone)]
+pub struct Actuator {
+    /// 端点配置
+    config: ActuatorConfig,
+    /// 应用信息
+    app: AppConfig,
+    /// 健康指示器注册表
+    health: Arc<HealthRegistry>,
+    /// 信息提供者
+    contributors: Vec<Arc<dyn InfoContributor>>,
+    /// `beans` 端点查看的容器
+    container: Option<Arc<RwLock<Container>>>,
+    /// `env` 端点查看的配置
+    environment: Option<Arc<ConfigurationManager>>,
+}
+
+impl Actuator {
+    /// 创建管理端点
+    pub fn new(config: ActuatorConfig, health: Arc<HealthRegistry>) -> Self {
+        Self {
+            config,
+            app: AppConfig::default(),
+            health,
+            contributors: Vec::new(),
+            container: None,
+            environment: None,
+        }
+    }
+
+    /// 从应用上下文创建管理端点
+    ///
+    /// 读取 `[management]` 和 `[app]` 章节，使用上下文中的 [`HealthRegistry`]，
+    /// 不存在时创建并注册到上下文；`beans` 和 `env` 端点查看上下文的容器和配置
+    ///
+    /// # 错误
+    /// 配置无效时返回错误
+    pub async fn from_context(context: &ApplicationContext) -> Result<Self> {
+        let config = context.config_manager();
+        let app = if config.contains_key("app") {
+            config.get_section("app")?
+        } else {
+            AppConfig::default()
+        };
+
+        let health = match context.get::<HealthRegistry>().await {
+            Some(health) => health,
+            None => {
+                context.register_singleton(HealthRegistry::new()).await;
+                context
+                    .get::<HealthRegistry>()
+                    .await
+                    .unwrap_or_else(|| Arc::new(HealthRegistry::new()))
+            }
+        };
+        Ok(Self::new(ActuatorConfig::load(config)?, health)
+            .app_info(app)
+            .container(context.container().clone())
+            .environment(config.clone()))
+    }
+
+    /// 设置 `info` 端点返回的应用信息
+    pub fn app_info(mut self, app: AppConfig) -> Self {
+        self.app = app;
+        self
+    }
+
+    /// 添加信息提供者
+    pub fn info_contributor(mut self, contributor: impl InfoContributor + 'static) -> Self {
+        self.contributors.push(Arc::new(contributor));
+        self
+    }
+
+    /// 设置 `beans` 端点查看的容器，未设置时不提供该端点
+    pub fn container(mut self, container: Arc<RwLock<Container>>) -> Self {
+        self.container = Some(container);
+        self
+    }
+
+    /// 设置 `env` 端点查看的配置，未设置时不提供该端点
+    pub fn environment(mut self, config: Arc<ConfigurationManager>) -> Self {
+        self.environment = Some(config);
+        self
+    }
+
+    /// 获取端点配置
+    pub fn config(&self) -> &ActuatorConfig {
+        &self.config
+    }
+
+    /// 创建包含所有已暴露端点的路由，未启用时返回空路由
+    pub fn router<S>(&self) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let config = &self.config;
+        if !config.enabled {
+            return Router::new();
+        }
+
+        let base = config.base_path.trim_end_matches('/');
+        let path = |endpoint: &str| format!("{}/{}", base, endpoint);
+        let exposed = |endpoint: &str, enabled: bool| enabled && config.is_exposed(endpoint);
+        let mut router = Router::new();
+        let mut links = BTreeMap::new();
+
+        if exposed("health", config.health.enabled) {
+            let health = self.health.clone();
+            let show_details = config.health.show_details;
+            let component_health = self.health.clone();
+            router = router
+                .route(
+                    &path("health"),
+                    get(move || health_handler(health.clone(), show_details)),
+                )
+                .route(
+                    &format!("{}/:component", path("health")),
+                    get(move |Path(component): Path<String>| {
+                        component_handler(component_health.clone(), component)
+                    }),
+                );
+            links.insert("health", path("health"));
+        }
+
+        if exposed("info", config.info.enabled) {
+            let info = Arc::new(self.info());
+            router = router.route(
+                &path("info"),
+                get(move || async move { Json(info.as_ref().clone()) }),
+            );
+            links.insert("info", path("info"));
+        }
+
+        if exposed("loggers", config.loggers.enabled) {
+            router = router.merge(loggers_router(&path("loggers")));
+            links.insert("loggers", path("loggers"));
+        }
+
+        if let Some(container) = self.container.clone() {
+            if exposed("beans", config.beans.enabled) {
+                router = router.route(
+                    &path("beans"),
+                    get(move || beans::beans_handler(container.clone())),
+                );
+                links.insert("beans", path("beans"));
+            }
+        }
+
+        if let Some(environment) = self.environment.clone() {
+            if exposed("env", config.env.enabled) {
+                let keys = Arc::new(config.env.keys_to_sanitize.clone());
+                let (property_environment, property_keys) = (environment.clone(), keys.clone());
+                router = router
+                    .route(
+                        &path("env"),
+                        get(move || env::env_handler(environment.clone(), keys.clone())),
+                    )
+                    .route(
+                        &format!("{}/:key", path("env")),
+                        get(move |Path(key): Path<String>| {
+                            env::property_handler(
+                                property_environment.clone(),
+                                property_keys.clone(),
+                                key,
+                            )
+                        }),
+                    );
+                links.insert("env", path("env"));
+            }
+        }
+
+        if exposed("startup", config.startup.enabled) {
+            router = router.route(&path("startup"), get(|| async { Json(startup_report()) }));
+            links.insert("startup", path("startup"));
+        }
+
+        if exposed("tasks", config.tasks.enabled) {
+            router = router.route(&path("tasks"), get(|| async { Json(task_dump().await) }));
+            links.insert("tasks", path("tasks"));
+        }
+
+        #[cfg(feature = "prometheus")]
+        if exposed("prometheus", config.prometheus.enabled) {
+            let metrics = rspring_core::metrics::MetricsConfig {
+                enabled: true,
+                path: path("prometheus"),
+                ..Default::default()
+            };
+            router = router.merge(crate::prometheus::prometheus_router(&metrics));
+            links.insert("prometheus", path("prometheus"));
+        }
+
+        let index: BTreeMap<&str, Value> = links
+            .into_iter()
+            .map(|(name, href)| (name, json!({ "href": href })))
+            .collect();
+        let index = Arc::new(json!({ "_links": index }));
+        let index_path = if base.is_empty() { "/" } else { base };
+        router.route(
+            index_path,
+            get(move || async move { Json(index.as_ref().clone()) }),
+        )
+    }
+
+    /// 将端点挂载到 Web 服务器，配置了 `listener` 时挂载在该监听器上
+    pub fn mount(&self, server: WebServer) -> WebServer {
+        server.merge_router(self.config.listener.as_deref(), self.router())
+    }
+
+    /// 组装 `info` 端点返回的信息
+    fn info(&self) -> Value {
+        let mut info = Map::new();
+        let mut app = json!({ "name": self.app.name, "version": self.app.version });
+        if let Some(description) = &self.app.description {
+            app["description"] = json!(description);
+        }
+        info.insert("app".to_string(), app);
+        for contributor in &self.contributors {
+            contributor.contribute(&mut info);
+        }
+        info.extend(self.config.info.properties.clone());
+        Value::Object(info)
+    }
+}
+
+impl std::fmt::Debug for Actuator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Actuator")
+            .field("config", &self.config)
+            .field("health", &self.health)
+            .finish()
+    }
+}
+
+/// 返回汇总的健康状态
+async fn health_handler(health: Arc<HealthRegistry>, show_details: bool) -> Response {
+    let mut composite = health.check().await;
+    if !show_details {
+        composite.components.clear();
+    }
+    (status_code(composite.status), Json(composite)).into_response()
+}
+
+/// 返回单个组件的健康状态
+async fn component_handler(health: Arc<HealthRegistry>, component: String) -> Response {
+    match health.check().await.components.remove(&component) {
+        Some(health) => (status_code(health.status), Json(health)).into_response(),
+        None => error_response(
+            StatusCode::NOT_FOUND,
+            format!("健康指示器 {} 不存在", component),
+        ),
+    }
+}
+
+/// 健康状态对应的响应状态码
+fn status_code(status: HealthStatus) -> StatusCode {
+    match status {
+        HealthStatus::Down | HealthStatus::OutOfService => StatusCode::SERVICE_UNAVAILABLE,
+        HealthStatus::Up | HealthStatus::Unknown => StatusCode::OK,
+    }
+}
+
+// 默认值函数
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_base_path() -> String {
+    "/actuator".to_string()
+}
+
+fn default_exposure() -> Vec<String> {
+    vec!["health".to_string(), "info".to_string()]
+}
+
+fn default_keys_to_sanitize() -> Vec<String> {
+    ["password", "secret", "key", "token", "credentials", "hash"]
+        .iter()
+        .map(|key| key.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::async_trait;
+    use axum::body::Body;
+    use axum::extract::Request;
+    use rspring_core::health::{Health, HealthIndicator};
+    use tower::ServiceExt;
+
+    struct Database(Health);
+
+    struct OrderService;
+
+    impl rspring_core::container::Component for OrderService {
+        fn component_name(&self) -> &'static str {
+            "OrderService"
+        }
+    }
+
+    #[async_trait]
+    impl HealthIndicator for Database {
+        fn name(&self) -> &str {
+            "db"
+        }
+
+        async fn health(&self) -> Health {
+            self.0.clone()
+        }
+    }
+
+    /// 请求路由并解析 JSON 响应
+    async fn call(router: &Router, uri: &str) -> (StatusCode, Value) {
+        let response = router
+            .clone()
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or_default())
+    }
+
+    /// 测试健康、信息端点和端点暴露配置
+    #[tokio::test]
+    async fn test_actuator_endpoints() {
+        let config: ActuatorConfig = serde_json::from_value(json!({
+            "info": { "properties": { "team": "orders" } },
+        }))
+        .unwrap();
+        let health = Arc::new(HealthRegistry::new());
+        health.register(Database(Health::down("connection refused")));
+        let actuator = Actuator::new(config, health)
+            .app_info(AppConfig {
+                name: "orders".to_string(),
+                ..AppConfig::default()
+            })
+            .info_contributor(BuildInfo::new("orders", "1.2.0").with_git(Some("abc123"), None));
+        let router: Router = actuator.router();
+
+        let (status, body) = call(&router, "/actuator/health").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            body["components"]["db"]["details"]["error"],
+            "connection refused"
+        );
+        assert_eq!(
+            call(&router, "/actuator/health/db").await.0,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(
+            call(&router, "/actuator/health/redis").await.0,
+            StatusCode::NOT_FOUND
+        );
+
+        let (status, body) = call(&router, "/actuator/info").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["app"]["name"], "orders");
+        assert_eq!(body["build"]["version"], "1.2.0");
+        assert_eq!(body["git"]["commit"], "abc123");
+        assert_eq!(body["team"], "orders");
+
+        // 默认不暴露 loggers
+        assert_eq!(
+            call(&router, "/actuator/loggers").await.0,
+            StatusCode::NOT_FOUND
+        );
+        let (_, body) = call(&router, "/actuator").await;
+        assert_eq!(body["_links"]["health"]["href"], "/actuator/health");
+        assert!(body["_links"].get("loggers").is_none());
+
+        let config: ActuatorConfig = serde_json::from_value(json!({
+            "exposure": ["*"],
+            "health": { "show_details": false },
+            "info": { "enabled": false },
+        }))
+        .unwrap();
+        let mut container = Container::new();
+        container.register_singleton(OrderService).unwrap();
+        let router: Router = Actuator::new(config, actuator.health.clone())
+            .container(Arc::new(RwLock::new(container)))
+            .router();
+        let (_, body) = call(&router, "/actuator/health").await;
+        assert_eq!(body, json!({ "status": "DOWN" }));
+        assert_eq!(
+            call(&router, "/actuator/info").await.0,
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(call(&router, "/actuator/loggers").await.0, StatusCode::OK);
+        let (_, body) = call(&router, "/actuator/beans").await;
+        assert_eq!(body["components"]["OrderService"]["scope"], "singleton");
+        let (status, body) = call(&router, "/actuator/startup").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body["steps"].is_array());
+        let (status, body) = call(&router, "/actuator/tasks").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body["tasks"].is_array());
+        assert!(body["runtime"]["workers"].is_number());
+        // 未设置配置时不提供 env 端点
+        assert_eq!(
+            call(&router, "/actuator/env").await.0,
+            StatusCode::NOT_FOUND
+        );
+    }
+}
@@ -0,0 +1,33 @@
+//! `beans` 端点模块
+//!
+//! 返回依赖注入容器中的所有组件，包括类型、生命周期和依赖的组件
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::Json;
+use rspring_core::container::Container;
+use serde_json::{json, Value};
+use tokio::sync::RwLock;
+
+/// 返回容器中的组件，以组件名称为键
+pub(super) async fn beans_handler(container: Arc<RwLock<Container>>) -> Json<Value> {
+    let components: BTreeMap<String, Value> = container
+        .read()
+        .await
+        .describe()
+        .into_iter()
+        .map(|component| {
+            (
+                component.name,
+                json!({
+                    "type": component.type_name,
+                    "scope": component.scope,
+                    "dependencies": component.dependencies,
+                    "registered_at": component.registered_at,
+                }),
+            )
+        })
+        .collect();
+    Json(json!({ "components": components }))
+}
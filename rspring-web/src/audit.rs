@@ -0,0 +1,197 @@
+//! 审计事件推送模块
+//!
+//! 启用 `audit-webhook` 特性后，[`WebhookAuditSink`] 将审计事件以 JSON 请求体 `POST` 到
+//! `[audit.webhook]` 配置的 HTTP 端点，如 SIEM 的采集接口。非 2xx 响应视为推送失败，
+//! 由审计记录器记录日志，不影响业务操作
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rspring_core::auditing::{audit_recorder, AuditConfig, AuditEvent, AuditSink};
+use rspring_core::config::ConfigurationManager;
+use rspring_core::trace::outbound_trace_headers;
+use rspring_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::webhook::build_client;
+
+/// 审计事件推送配置
+///
+/// # 示例
+/// ```toml
+/// [audit.webhook]
+/// url = "https://siem.example.com/api/audit"
+/// timeout = "3s"
+///
+/// [audit.webhook.headers]
+/// Authorization = "Bearer ${SIEM_TOKEN}"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct WebhookAuditConfig {
+    /// 接收审计事件的地址
+    pub url: String,
+    /// 附加的请求头，如认证信息
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// 请求超时时间
+    ///
+    /// # 默认值
+    /// `"5s"`
+    #[serde(default = "default_timeout", with = "rspring_core::config::duration")]
+    pub timeout: Duration,
+}
+
+impl WebhookAuditConfig {
+    /// 加载 `[audit.webhook]` 配置，没有该配置时返回 `None`
+    ///
+    /// # 错误
+    /// 配置格式错误时返回错误
+    pub fn load(config: &ConfigurationManager) -> Result<Option<Self>> {
+        if config.contains_key("audit.webhook") {
+            config.get_section("audit.webhook").map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// 将审计事件推送到 HTTP 端点的存储
+#[derive(Debug, Clone)]
+pub struct WebhookAuditSink {
+    /// 推送配置
+    config: WebhookAuditConfig,
+    /// HTTP 客户端
+    http: reqwest::Client,
+}
+
+impl WebhookAuditSink {
+    /// 创建推送存储
+    ///
+    /// # 错误
+    /// 地址或请求头无效时返回错误
+    pub fn new(config: WebhookAuditConfig) -> Result<Self> {
+        let http = build_client(&config.url, &config.headers, config.timeout)?;
+        Ok(Self { config, http })
+    }
+
+    /// 按 `[audit.webhook]` 配置创建推送存储并加入全局审计记录器，
+    /// 没有该配置或 `[audit]` 关闭了审计时不做处理
+    ///
+    /// # 错误
+    /// 配置无效时返回错误
+    ///
+    /// # 示例
+    /// ```rust
+    /// WebhookAuditSink::install(context.config_manager())?;
+    /// ```
+    pub fn install(config: &ConfigurationManager) -> Result<()> {
+        if !AuditConfig::load(config)?.enabled {
+            return Ok(());
+        }
+        if let Some(config) = WebhookAuditConfig::load(config)? {
+            audit_recorder().add_sink(Arc::new(Self::new(config)?));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AuditSink for WebhookAuditSink {
+    async fn record(&self, event: &AuditEvent) -> Result<()> {
+        let mut request = self.http.post(&self.config.url).json(event);
+        for (name, value) in outbound_trace_headers() {
+            request = request.header(name, value);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| Error::internal(format!("推送审计事件失败: {}", e)))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Error::internal(format!(
+                "推送审计事件失败: {} 返回 {}",
+                self.config.url, status
+            )));
+        }
+        Ok(())
+    }
+}
+
+// 默认值函数
+
+fn default_timeout() -> Duration {
+    Duration::from_secs(5)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::State;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use axum::{Json, Router};
+    use std::sync::Mutex;
+
+    type Received = Arc<Mutex<Vec<(Option<String>, serde_json::Value)>>>;
+
+    /// 测试事件以 JSON 推送并附加配置的请求头，非 2xx 响应返回错误
+    #[tokio::test]
+    async fn test_webhook_sink() {
+        let received: Received = Arc::default();
+        let app = Router::new()
+            .route(
+                "/audit",
+                post(
+                    |State(received): State<Received>,
+                     headers: HeaderMap,
+                     Json(body): Json<serde_json::Value>| async move {
+                        let token = headers
+                            .get("authorization")
+                            .and_then(|value| value.to_str().ok())
+                            .map(str::to_string);
+                        received.lock().unwrap().push((token, body));
+                        StatusCode::ACCEPTED
+                    },
+                ),
+            )
+            .route(
+                "/broken",
+                post(|| async { StatusCode::INTERNAL_SERVER_ERROR }),
+            )
+            .with_state(received.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let config = |path: &str| WebhookAuditConfig {
+            url: format!("http://{}{}", addr, path),
+            headers: BTreeMap::from([("Authorization".to_string(), "Bearer secret".to_string())]),
+            timeout: default_timeout(),
+        };
+        let event = AuditEvent::new("order.refund").resource("order:42");
+        WebhookAuditSink::new(config("/audit"))
+            .unwrap()
+            .record(&event)
+            .await
+            .unwrap();
+        assert!(WebhookAuditSink::new(config("/broken"))
+            .unwrap()
+            .record(&event)
+            .await
+            .is_err());
+        assert!(WebhookAuditSink::new(WebhookAuditConfig {
+            url: "not a url".to_string(),
+            ..config("/")
+        })
+        .is_err());
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].0.as_deref(), Some("Bearer secret"));
+        assert_eq!(received[0].1["action"], "order.refund");
+        assert_eq!(received[0].1["resource"], "order:42");
+        assert_eq!(received[0].1["outcome"], "success");
+    }
+}
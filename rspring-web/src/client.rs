@@ -0,0 +1,273 @@
+//! 声明式 HTTP 客户端模块
+//!
+//! `#[HttpClient]` 为 trait 生成的实现通过 [`HttpClient`] 发送请求：
+//! - 基础地址支持 `${key:default}` 配置表达式，按 `ConfigurationManager::resolve` 解析
+//! - 路径变量按路径段编码，查询参数和请求头按值序列化，值为 `None` 时不发送，列表发送多次
+//! - 每个请求附加 [`outbound_trace_headers`] 返回的追踪头，追踪上下文传递到下游服务
+//! - 非 2xx 响应转换为 [`Error`]：400/422 为验证错误、401 为未授权、403 为禁止访问、
+//!   404 为资源未找到，其余为应用程序错误；JSON 响应体的 `message` 字段作为错误描述
+//! - 响应体按 JSON 解析为方法的返回类型，空响应体按 `null` 解析，`()` 和 `Option<T>` 可以接收
+
+use std::time::{Duration, Instant};
+
+use rspring_core::trace::outbound_trace_headers;
+use rspring_core::{ConfigurationManager, Error, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+pub use reqwest::Method;
+
+/// 默认请求超时
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// HTTP 客户端
+///
+/// # 示例
+/// ```rust
+/// let client = HttpClient::from_config(context.config_manager(), "${services.user.url}")?;
+/// let user: User = client
+///     .request(Method::GET, ["users", &id.to_string()])
+///     .header("X-Tenant", &tenant)
+///     .send()
+///     .await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    /// 基础地址
+    base_url: url::Url,
+    /// 底层客户端
+    http: reqwest::Client,
+}
+
+impl HttpClient {
+    /// 使用默认超时创建客户端
+    ///
+    /// # 错误
+    /// 基础地址无效时返回错误
+    pub fn new(base_url: &str) -> Result<Self> {
+        Self::with_timeout(base_url, DEFAULT_TIMEOUT)
+    }
+
+    /// 创建指定请求超时的客户端
+    ///
+    /// # 错误
+    /// 基础地址无效时返回错误
+    pub fn with_timeout(base_url: &str, timeout: Duration) -> Result<Self> {
+        let base_url = url::Url::parse(base_url)
+            .ok()
+            .filter(|url| !url.cannot_be_a_base())
+            .ok_or_else(|| Error::validation(format!("无效的客户端基础地址: {}", base_url)))?;
+        let http = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| Error::internal(format!("创建 HTTP 客户端失败: {}", e)))?;
+        Ok(Self { base_url, http })
+    }
+
+    /// 按配置表达式解析基础地址并创建客户端
+    ///
+    /// # 错误
+    /// 配置不存在或基础地址无效时返回错误
+    pub fn from_config(config: &ConfigurationManager, base_url: &str) -> Result<Self> {
+        Self::new(&config.resolve::<String>(base_url)?)
+    }
+
+    /// 基础地址
+    pub fn base_url(&self) -> &str {
+        self.base_url.as_str()
+    }
+
+    /// 创建请求，`segments` 为基础地址之后的路径段，每段单独编码
+    pub fn request<I>(&self, method: Method, segments: I) -> HttpRequest
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut url = self.base_url.clone();
+        if let Ok(mut path) = url.path_segments_mut() {
+            path.pop_if_empty().extend(segments);
+        }
+        let mut builder = self.http.request(method.clone(), url.clone());
+        for (name, value) in outbound_trace_headers() {
+            builder = builder.header(name, value);
+        }
+        HttpRequest {
+            method,
+            url,
+            builder,
+            error: None,
+        }
+    }
+}
+
+/// 待发送的请求
+#[derive(Debug)]
+pub struct HttpRequest {
+    /// 请求方法
+    method: Method,
+    /// 请求地址，不含查询参数
+    url: url::Url,
+    /// 底层请求
+    builder: reqwest::RequestBuilder,
+    /// 构建请求时的第一个错误，发送时返回
+    error: Option<Error>,
+}
+
+impl HttpRequest {
+    /// 添加查询参数
+    pub fn query<T: Serialize + ?Sized>(mut self, name: &str, value: &T) -> Self {
+        match parameter_values(name, value) {
+            Ok(values) => {
+                for value in values {
+                    self.builder = self.builder.query(&[(name, value)]);
+                }
+            }
+            Err(e) => self.error = self.error.or(Some(e)),
+        }
+        self
+    }
+
+    /// 添加请求头
+    pub fn header<T: Serialize + ?Sized>(mut self, name: &str, value: &T) -> Self {
+        match parameter_values(name, value) {
+            Ok(values) => {
+                for value in values {
+                    self.builder = self.builder.header(name, value);
+                }
+            }
+            Err(e) => self.error = self.error.or(Some(e)),
+        }
+        self
+    }
+
+    /// 设置 JSON 请求体
+    pub fn json<T: Serialize + ?Sized>(mut self, body: &T) -> Self {
+        self.builder = self.builder.json(body);
+        self
+    }
+
+    /// 设置表单请求体
+    pub fn form<T: Serialize + ?Sized>(mut self, body: &T) -> Self {
+        self.builder = self.builder.form(body);
+        self
+    }
+
+    /// 发送请求并按 JSON 解析响应体
+    ///
+    /// # 错误
+    /// 请求失败、响应状态不是 2xx 或响应体无法解析时返回错误
+    pub async fn send<T: DeserializeOwned>(self) -> Result<T> {
+        let Self { method, url, builder, error } = self;
+        if let Some(error) = error {
+            return Err(error);
+        }
+
+        let start = Instant::now();
+        let response = builder
+            .send()
+            .await
+            .map_err(|e| Error::application(format!("请求 {} {} 失败: {}", method, url, e)))?;
+        let status = response.status();
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| Error::application(format!("读取 {} {} 的响应失败: {}", method, url, e)))?;
+        tracing::debug!("{} {} -> {} ({} ms)", method, url, status.as_u16(), start.elapsed().as_millis());
+
+        if !status.is_success() {
+            return Err(status_error(&method, &url, status, &body));
+        }
+        let body: &[u8] = if body.is_empty() { b"null" } else { &body };
+        serde_json::from_slice(body)
+            .map_err(|e| Error::application(format!("解析 {} {} 的响应失败: {}", method, url, e)))
+    }
+}
+
+/// 将查询参数或请求头的值转换为字符串，`None` 没有值，列表每个元素一个值
+fn parameter_values<T: Serialize + ?Sized>(name: &str, value: &T) -> Result<Vec<String>> {
+    let scalar = |value: Value| match value {
+        Value::String(value) => Ok(Some(value)),
+        Value::Null => Ok(None),
+        Value::Bool(_) | Value::Number(_) => Ok(Some(value.to_string())),
+        Value::Array(_) | Value::Object(_) => Err(Error::validation(format!("参数 {} 的值不是简单类型", name))),
+    };
+    match serde_json::to_value(value)? {
+        Value::Array(values) => values.into_iter().filter_map(|value| scalar(value).transpose()).collect(),
+        value => Ok(scalar(value)?.into_iter().collect()),
+    }
+}
+
+/// 按响应状态码转换错误
+fn status_error(method: &Method, url: &url::Url, status: reqwest::StatusCode, body: &[u8]) -> Error {
+    let message = serde_json::from_slice::<Value>(body)
+        .ok()
+        .and_then(|body| body.get("message")?.as_str().map(str::to_string))
+        .unwrap_or_else(|| String::from_utf8_lossy(body).into_owned());
+    match status.as_u16() {
+        400 | 422 => Error::validation(message),
+        401 => Error::Unauthorized,
+        403 => Error::forbidden(message),
+        404 if message.is_empty() => Error::not_found(url.to_string()),
+        404 => Error::not_found(message),
+        _ => Error::application(format!("{} {} 返回 {}: {}", method, url, status, message)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::{Path, Query};
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::{get, post};
+    use axum::{Json, Router};
+    use std::collections::HashMap;
+
+    /// 测试路径编码、查询参数、请求头、请求体和错误转换
+    #[tokio::test]
+    async fn test_http_client() {
+        let app = Router::new()
+            .route(
+                "/api/users/:name",
+                get(|Path(name): Path<String>, Query(query): Query<HashMap<String, String>>, headers: HeaderMap| async move {
+                    let tenant = headers.get("X-Tenant").and_then(|value| value.to_str().ok()).unwrap_or_default();
+                    Json(serde_json::json!({ "name": name, "tenant": tenant, "query": query }))
+                }),
+            )
+            .route("/api/users", post(|Json(body): Json<Value>| async move { Json(body) }))
+            .route(
+                "/api/missing",
+                get(|| async { (StatusCode::NOT_FOUND, Json(serde_json::json!({ "message": "用户不存在" }))) }),
+            )
+            .route("/api/empty", post(|| async { StatusCode::NO_CONTENT }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let client = HttpClient::new(&format!("http://{}/api/", address)).unwrap();
+        let user: Value = client
+            .request(Method::GET, ["users", "a b/c"])
+            .query("page", &2)
+            .query("sort", &None::<String>)
+            .header("X-Tenant", "acme")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(user["name"], "a b/c");
+        assert_eq!(user["tenant"], "acme");
+        assert_eq!(user["query"], serde_json::json!({ "page": "2" }));
+
+        let created: Value = client
+            .request(Method::POST, ["users"])
+            .json(&serde_json::json!({ "name": "alice" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(created["name"], "alice");
+        client.request(Method::POST, ["empty"]).send::<()>().await.unwrap();
+
+        let error = client.request(Method::GET, ["missing"]).send::<Value>().await.unwrap_err();
+        assert!(matches!(&error, Error::NotFound { resource } if resource == "用户不存在"), "{}", error);
+        assert!(HttpClient::new("not a url").is_err());
+    }
+}
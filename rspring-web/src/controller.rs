@@ -1,5 +1,76 @@
+use std::sync::Arc;
+
+use axum::Router;
+
+use crate::openapi::OperationInfo;
+
 /// Web 控制器 trait
 pub trait Controller: rspring_core::Component {
     /// 获取控制器的基础路径
     fn base_path(&self) -> &'static str;
-}
\ No newline at end of file
+}
+
+/// 控制器路由元数据
+///
+/// 由 `#[RequestMapping]` 标注的 impl 块自动实现，描述控制器暴露的全部接口
+///
+/// # 示例
+/// ```rust
+/// #[RequestMapping("/api/users")]
+/// impl UserController {
+///     /// 根据ID查询用户
+///     #[GetMapping("/{id}")]
+///     pub async fn get_user(&self, #[PathVariable] id: u64) -> WebResult<ApiResponse<User>> {
+///         // 处理逻辑
+///     }
+/// }
+///
+/// let operations = UserController::operations();
+/// let app = Router::new().merge(Arc::new(user_controller).router());
+/// ```
+pub trait RestRoutes: Send + Sync + 'static {
+    /// 获取控制器的基础路径
+    fn base_path() -> &'static str;
+
+    /// 获取控制器的接口操作元数据
+    fn operations() -> Vec<OperationInfo>;
+
+    /// 生成控制器的路由
+    ///
+    /// 参数注解会转换为对应的提取逻辑，参数缺失或格式错误时返回 400；
+    /// 启用 `metrics` 特性时记录每个路由的请求指标
+    fn router(self: Arc<Self>) -> Router;
+}
+
+/// 将 `{id}` 形式的路径变量转换为路由使用的 `:id` 形式
+///
+/// `{*path}` 转换为通配符 `*path`
+///
+/// # 示例
+/// ```rust
+/// assert_eq!(route_path("/api/users/{id}"), "/api/users/:id");
+/// ```
+pub fn route_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(name) if name.starts_with('*') => name.to_string(),
+            Some(name) => format!(":{}", name),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试路径变量格式转换
+    #[test]
+    fn test_route_path() {
+        assert_eq!(route_path("/api/users/{id}"), "/api/users/:id");
+        assert_eq!(route_path("/api/users/{id}/orders/{order_id}"), "/api/users/:id/orders/:order_id");
+        assert_eq!(route_path("/files/{*path}"), "/files/*path");
+        assert_eq!(route_path("/"), "/");
+    }
+}
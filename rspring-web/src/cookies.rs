@@ -0,0 +1,732 @@
+//! Cookie 模块
+//!
+//! 提供读取和设置 Cookie 的 `Cookies` 提取器，以及按类型读取的 `Cookie<T>` 提取器。
+//! Cookie 可以是明文、签名（防篡改）或加密（防篡改且内容不可读）的，
+//! 密钥和 Cookie 属性在 `[web.cookies]` 中配置
+
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::async_trait;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum::http::{header, HeaderValue};
+use axum::response::{IntoResponse, IntoResponseParts, Response, ResponseParts};
+use axum::{Extension, Router};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use once_cell::sync::Lazy;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::hmac;
+use ring::rand::{SecureRandom, SystemRandom};
+use rspring_core::{Error, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::extract::ParameterRejection;
+
+/// 密钥的最小长度（字节）
+const MIN_SECRET_LEN: usize = 32;
+
+/// 未应用 `[web.cookies]` 配置时使用的默认设置，密钥在进程启动时随机生成
+static DEFAULT_SETTINGS: Lazy<Arc<CookieSettings>> = Lazy::new(|| {
+    Arc::new(CookieSettings::new(CookieConfig::default()).expect("生成 Cookie 密钥失败"))
+});
+
+/// Cookie 的保护方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CookieProtection {
+    /// 明文
+    #[default]
+    Plain,
+    /// 签名，客户端可读但无法篡改
+    Signed,
+    /// 加密，客户端既不可读也无法篡改
+    Private,
+}
+
+/// 可按类型读写的 Cookie 值
+///
+/// 值按 JSON 序列化后存储，`PROTECTION` 决定是否签名或加密
+///
+/// # 示例
+/// ```rust
+/// #[derive(Serialize, Deserialize)]
+/// struct Session {
+///     user_id: u64,
+/// }
+///
+/// impl CookieValue for Session {
+///     const NAME: &'static str = "session";
+///     const PROTECTION: CookieProtection = CookieProtection::Private;
+/// }
+///
+/// async fn profile(Cookie(session): Cookie<Session>) -> String {
+///     session.user_id.to_string()
+/// }
+///
+/// async fn login(cookies: Cookies) -> WebResult<(Cookies, &'static str)> {
+///     Ok((cookies.set_value(&Session { user_id: 42 })?, "ok"))
+/// }
+/// ```
+pub trait CookieValue: Serialize + DeserializeOwned {
+    /// Cookie 名称
+    const NAME: &'static str;
+
+    /// 保护方式
+    const PROTECTION: CookieProtection = CookieProtection::Plain;
+}
+
+/// SameSite 属性
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SameSite {
+    /// 只在同站请求中发送
+    Strict,
+    /// 同站请求和顶级导航中发送
+    #[default]
+    Lax,
+    /// 跨站请求中也发送，必须同时设置 `secure`
+    None,
+}
+
+/// Cookie 配置
+///
+/// # 示例
+/// ```toml
+/// [web.cookies]
+/// secret = "${COOKIE_SECRET}"
+/// secure = true
+/// same_site = "strict"
+/// max_age = "7d"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct CookieConfig {
+    /// 签名和加密使用的密钥（可选），至少 32 字节
+    ///
+    /// 未设置时在启动时随机生成，重启后之前签名或加密的 Cookie 将失效
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// Path 属性
+    ///
+    /// # 默认值
+    /// `"/"`
+    #[serde(default = "default_path")]
+    pub path: String,
+    /// Domain 属性（可选）
+    #[serde(default)]
+    pub domain: Option<String>,
+    /// 是否设置 Secure 属性
+    ///
+    /// # 默认值
+    /// `false`
+    #[serde(default)]
+    pub secure: bool,
+    /// 是否设置 HttpOnly 属性
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_http_only")]
+    pub http_only: bool,
+    /// SameSite 属性
+    ///
+    /// # 默认值
+    /// `"lax"`
+    #[serde(default)]
+    pub same_site: SameSite,
+    /// Max-Age 属性（可选），未设置时为会话 Cookie
+    #[serde(default, with = "rspring_core::config::duration::option")]
+    pub max_age: Option<Duration>,
+}
+
+impl Default for CookieConfig {
+    fn default() -> Self {
+        Self {
+            secret: None,
+            path: default_path(),
+            domain: None,
+            secure: false,
+            http_only: default_http_only(),
+            same_site: SameSite::default(),
+            max_age: None,
+        }
+    }
+}
+
+impl CookieConfig {
+    /// 将 Cookie 配置应用到路由，供 `Cookies` 和 `Cookie<T>` 提取器读取
+    ///
+    /// # 错误
+    /// 密钥长度不足或 `same_site = "none"` 但未设置 `secure` 时返回验证错误
+    pub fn apply<S>(&self, router: Router<S>) -> Result<Router<S>>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let settings = CookieSettings::new(self.clone())?;
+        Ok(router.layer(Extension(Arc::new(settings))))
+    }
+}
+
+/// 生效的 Cookie 设置，包含由密钥派生的签名和加密密钥
+struct CookieSettings {
+    /// Cookie 配置
+    config: CookieConfig,
+    /// 签名密钥
+    signing_key: hmac::Key,
+    /// 加密密钥
+    encryption_key: LessSafeKey,
+}
+
+impl CookieSettings {
+    /// 根据配置创建设置
+    fn new(config: CookieConfig) -> Result<Self> {
+        if config.same_site == SameSite::None && !config.secure {
+            return Err(Error::validation(
+                "same_site = \"none\" 时必须设置 secure = true",
+            ));
+        }
+
+        let secret = match &config.secret {
+            Some(secret) if secret.len() < MIN_SECRET_LEN => {
+                return Err(Error::validation(format!(
+                    "web.cookies.secret 长度不足，至少需要 {} 字节",
+                    MIN_SECRET_LEN
+                )))
+            }
+            Some(secret) => secret.as_bytes().to_vec(),
+            None => {
+                tracing::warn!(
+                    "未配置 web.cookies.secret，使用随机密钥，重启后已签名或加密的 Cookie 将失效"
+                );
+                let mut secret = vec![0u8; MIN_SECRET_LEN];
+                SystemRandom::new()
+                    .fill(&mut secret)
+                    .map_err(|_| Error::application("生成随机密钥失败"))?;
+                secret
+            }
+        };
+
+        // 按用途从同一密钥派生签名和加密密钥，避免密钥复用
+        let master = hmac::Key::new(hmac::HMAC_SHA256, &secret);
+        let signing_key = hmac::Key::new(
+            hmac::HMAC_SHA256,
+            hmac::sign(&master, b"rspring-cookie-signing").as_ref(),
+        );
+        let encryption_key = UnboundKey::new(
+            &AES_256_GCM,
+            hmac::sign(&master, b"rspring-cookie-encryption").as_ref(),
+        )
+        .map_err(|_| Error::application("创建 Cookie 加密密钥失败"))?;
+
+        Ok(Self {
+            config,
+            signing_key,
+            encryption_key: LessSafeKey::new(encryption_key),
+        })
+    }
+
+    /// 对值签名，格式为 `值.签名`
+    fn sign(&self, name: &str, value: &str) -> String {
+        let payload = URL_SAFE_NO_PAD.encode(value);
+        let tag = hmac::sign(
+            &self.signing_key,
+            format!("{}={}", name, payload).as_bytes(),
+        );
+        format!("{}.{}", payload, URL_SAFE_NO_PAD.encode(tag.as_ref()))
+    }
+
+    /// 校验签名并返回原始值
+    fn verify(&self, name: &str, value: &str) -> Option<String> {
+        let (payload, tag) = value.rsplit_once('.')?;
+        let tag = URL_SAFE_NO_PAD.decode(tag).ok()?;
+        hmac::verify(
+            &self.signing_key,
+            format!("{}={}", name, payload).as_bytes(),
+            &tag,
+        )
+        .ok()?;
+        String::from_utf8(URL_SAFE_NO_PAD.decode(payload).ok()?).ok()
+    }
+
+    /// 加密值，Cookie 名称作为附加数据，防止值被移用到其他 Cookie
+    fn encrypt(&self, name: &str, value: &str) -> Result<String> {
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| Error::application("生成随机数失败"))?;
+
+        let mut data = value.as_bytes().to_vec();
+        self.encryption_key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(name.as_bytes()),
+                &mut data,
+            )
+            .map_err(|_| Error::application(format!("加密 Cookie 失败: {}", name)))?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&data);
+        Ok(URL_SAFE_NO_PAD.encode(sealed))
+    }
+
+    /// 解密并返回原始值
+    fn decrypt(&self, name: &str, value: &str) -> Option<String> {
+        let sealed = URL_SAFE_NO_PAD.decode(value).ok()?;
+        if sealed.len() < NONCE_LEN {
+            return None;
+        }
+        let (nonce, data) = sealed.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).ok()?;
+        let mut data = data.to_vec();
+        let plain = self
+            .encryption_key
+            .open_in_place(nonce, Aad::from(name.as_bytes()), &mut data)
+            .ok()?;
+        String::from_utf8(plain.to_vec()).ok()
+    }
+
+    /// 生成 `Set-Cookie` 响应头的值
+    fn set_cookie(&self, name: &str, value: &str, max_age: Option<Duration>) -> String {
+        let config = &self.config;
+        let mut cookie = format!("{}={}; Path={}", name, value, config.path);
+        if let Some(domain) = &config.domain {
+            let _ = write!(cookie, "; Domain={}", domain);
+        }
+        if let Some(max_age) = max_age.or(config.max_age) {
+            let _ = write!(cookie, "; Max-Age={}", max_age.as_secs());
+        }
+        if config.secure {
+            cookie.push_str("; Secure");
+        }
+        if config.http_only {
+            cookie.push_str("; HttpOnly");
+        }
+        cookie.push_str(match config.same_site {
+            SameSite::Strict => "; SameSite=Strict",
+            SameSite::Lax => "; SameSite=Lax",
+            SameSite::None => "; SameSite=None",
+        });
+        cookie
+    }
+}
+
+/// Cookie 提取器和响应辅助
+///
+/// 读取请求中的 Cookie，并收集需要在响应中设置或清除的 Cookie。
+/// 作为响应（或响应元组的一部分）返回时写入 `Set-Cookie` 响应头
+///
+/// # 示例
+/// ```rust
+/// async fn theme(cookies: Cookies) -> (Cookies, String) {
+///     let theme = cookies.get("theme").unwrap_or_else(|| "light".to_string());
+///     (cookies.set("theme", "dark"), theme)
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Cookies {
+    /// 请求中的 Cookie
+    cookies: Vec<(String, String)>,
+    /// 待写入响应的 `Set-Cookie` 值
+    pending: Vec<String>,
+    /// 生效的 Cookie 设置
+    settings: Arc<CookieSettings>,
+}
+
+impl std::fmt::Debug for Cookies {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cookies")
+            .field(
+                "names",
+                &self
+                    .cookies
+                    .iter()
+                    .map(|(name, _)| name)
+                    .collect::<Vec<_>>(),
+            )
+            .field("pending", &self.pending.len())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Cookies {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> std::result::Result<Self, Self::Rejection> {
+        let settings = parts
+            .extensions
+            .get::<Arc<CookieSettings>>()
+            .cloned()
+            .unwrap_or_else(|| DEFAULT_SETTINGS.clone());
+
+        let cookies = parts
+            .headers
+            .get_all(header::COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(';'))
+            .filter_map(|pair| {
+                let (name, value) = pair.trim().split_once('=')?;
+                Some((name.to_string(), value.trim_matches('"').to_string()))
+            })
+            .collect();
+
+        Ok(Self {
+            cookies,
+            pending: Vec::new(),
+            settings,
+        })
+    }
+}
+
+impl Cookies {
+    /// 读取原始值，同名 Cookie 取第一个
+    fn raw(&self, name: &str) -> Option<&str> {
+        self.cookies
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// 读取明文 Cookie，值按百分号编码解码
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.raw(name).map(percent_decode)
+    }
+
+    /// 读取签名 Cookie，签名无效时返回 `None`
+    pub fn get_signed(&self, name: &str) -> Option<String> {
+        self.settings.verify(name, self.raw(name)?)
+    }
+
+    /// 读取加密 Cookie，无法解密时返回 `None`
+    pub fn get_private(&self, name: &str) -> Option<String> {
+        self.settings.decrypt(name, self.raw(name)?)
+    }
+
+    /// 按类型读取 Cookie，缺失、签名无效或无法解析时返回 `None`
+    pub fn value<T: CookieValue>(&self) -> Option<T> {
+        let json = match T::PROTECTION {
+            CookieProtection::Plain => self.get(T::NAME)?,
+            CookieProtection::Signed => self.get_signed(T::NAME)?,
+            CookieProtection::Private => self.get_private(T::NAME)?,
+        };
+        serde_json::from_str(&json).ok()
+    }
+
+    /// 设置明文 Cookie，值按百分号编码
+    pub fn set(mut self, name: &str, value: &str) -> Self {
+        let cookie = self.settings.set_cookie(name, &percent_encode(value), None);
+        self.pending.push(cookie);
+        self
+    }
+
+    /// 设置签名 Cookie
+    pub fn set_signed(mut self, name: &str, value: &str) -> Self {
+        let cookie = self
+            .settings
+            .set_cookie(name, &self.settings.sign(name, value), None);
+        self.pending.push(cookie);
+        self
+    }
+
+    /// 设置加密 Cookie
+    ///
+    /// # 错误
+    /// 加密失败时返回错误
+    pub fn set_private(mut self, name: &str, value: &str) -> Result<Self> {
+        let cookie = self
+            .settings
+            .set_cookie(name, &self.settings.encrypt(name, value)?, None);
+        self.pending.push(cookie);
+        Ok(self)
+    }
+
+    /// 按类型设置 Cookie
+    ///
+    /// # 错误
+    /// 序列化或加密失败时返回错误
+    pub fn set_value<T: CookieValue>(self, value: &T) -> Result<Self> {
+        let json = serde_json::to_string(value)?;
+        match T::PROTECTION {
+            CookieProtection::Plain => Ok(self.set(T::NAME, &json)),
+            CookieProtection::Signed => Ok(self.set_signed(T::NAME, &json)),
+            CookieProtection::Private => self.set_private(T::NAME, &json),
+        }
+    }
+
+    /// 清除 Cookie，Path 和 Domain 与设置时相同
+    pub fn remove(mut self, name: &str) -> Self {
+        let cookie = self.settings.set_cookie(name, "", Some(Duration::ZERO));
+        self.pending
+            .push(format!("{}; Expires=Thu, 01 Jan 1970 00:00:00 GMT", cookie));
+        self
+    }
+
+    /// 按类型清除 Cookie
+    pub fn remove_value<T: CookieValue>(self) -> Self {
+        self.remove(T::NAME)
+    }
+}
+
+impl IntoResponseParts for Cookies {
+    type Error = std::convert::Infallible;
+
+    fn into_response_parts(
+        self,
+        mut res: ResponseParts,
+    ) -> std::result::Result<ResponseParts, Self::Error> {
+        for cookie in self.pending {
+            match HeaderValue::from_str(&cookie) {
+                Ok(value) => {
+                    res.headers_mut().append(header::SET_COOKIE, value);
+                }
+                Err(_) => tracing::warn!("忽略无效的 Set-Cookie: {}", cookie),
+            }
+        }
+        Ok(res)
+    }
+}
+
+impl IntoResponse for Cookies {
+    fn into_response(self) -> Response {
+        (self, ()).into_response()
+    }
+}
+
+/// 按类型读取 Cookie 的提取器
+///
+/// Cookie 缺失、签名无效或无法解析时返回 400，可使用 `Option<Cookie<T>>` 读取可选 Cookie
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Cookie<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Cookie<T>
+where
+    T: CookieValue,
+    S: Send + Sync,
+{
+    type Rejection = ParameterRejection;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> std::result::Result<Self, Self::Rejection> {
+        let Ok(cookies) = Cookies::from_request_parts(parts, state).await;
+        if cookies.raw(T::NAME).is_none() {
+            return Err(ParameterRejection::new(format!(
+                "缺少 Cookie `{}`",
+                T::NAME
+            )));
+        }
+        cookies
+            .value::<T>()
+            .map(Cookie)
+            .ok_or_else(|| ParameterRejection::new(format!("Cookie `{}` 无效", T::NAME)))
+    }
+}
+
+/// 按百分号编码 Cookie 值中不允许出现的字符
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        // RFC 6265 cookie-octet，另外编码 `%` 以便解码
+        if matches!(byte, 0x21 | 0x23..=0x24 | 0x26..=0x2B | 0x2D..=0x3A | 0x3C..=0x5B | 0x5D..=0x7E)
+        {
+            encoded.push(byte as char);
+        } else {
+            let _ = write!(encoded, "%{:02X}", byte);
+        }
+    }
+    encoded
+}
+
+/// 解码百分号编码的 Cookie 值，无效的编码原样保留
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = (bytes[i] == b'%')
+            .then(|| value.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+// 默认值函数
+
+fn default_path() -> String {
+    "/".to_string()
+}
+
+fn default_http_only() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::extract::Request;
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use tower::ServiceExt;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Session {
+        user_id: u64,
+    }
+
+    impl CookieValue for Session {
+        const NAME: &'static str = "session";
+        const PROTECTION: CookieProtection = CookieProtection::Private;
+    }
+
+    /// 构造带指定 Cookie 请求头的 `Cookies`
+    async fn cookies(header: &str) -> Cookies {
+        let request = Request::get("/")
+            .header(header::COOKIE, header)
+            .body(())
+            .unwrap();
+        let (mut parts, _) = request.into_parts();
+        let Ok(cookies) = Cookies::from_request_parts(&mut parts, &()).await;
+        cookies
+    }
+
+    /// 取出 `Set-Cookie` 中的值
+    fn cookie_value(set_cookie: &str) -> &str {
+        let pair = set_cookie.split(';').next().unwrap();
+        pair.split_once('=').unwrap().1
+    }
+
+    /// 测试明文、签名和加密 Cookie 的读写
+    #[tokio::test]
+    async fn test_cookie_protection() {
+        let jar = cookies("").await;
+        let jar = jar
+            .set("theme", "深色 mode")
+            .set_signed("user", "alice")
+            .set_private("token", "secret")
+            .unwrap();
+
+        let theme = cookie_value(&jar.pending[0]).to_string();
+        let user = cookie_value(&jar.pending[1]).to_string();
+        let token = cookie_value(&jar.pending[2]).to_string();
+        assert!(!token.contains("secret"));
+
+        let header = format!("theme={}; user={}; token={}", theme, user, token);
+        let jar = cookies(&header).await;
+        assert_eq!(jar.get("theme").unwrap(), "深色 mode");
+        assert_eq!(jar.get_signed("user").unwrap(), "alice");
+        assert_eq!(jar.get_private("token").unwrap(), "secret");
+
+        // 篡改或移用到其他名称的 Cookie 无法通过校验
+        let tampered = user.replacen(&user[..2], "AA", 1);
+        let header = format!("user={}; other={}", tampered, token);
+        let jar = cookies(&header).await;
+        assert!(jar.get_signed("user").is_none());
+        assert!(jar.get_private("other").is_none());
+    }
+
+    /// 测试 `Set-Cookie` 属性和清除 Cookie
+    #[test]
+    fn test_set_cookie_attributes() {
+        let config = CookieConfig {
+            secret: Some("0123456789abcdef0123456789abcdef".to_string()),
+            domain: Some("example.com".to_string()),
+            secure: true,
+            same_site: SameSite::Strict,
+            max_age: Some(Duration::from_secs(3600)),
+            ..CookieConfig::default()
+        };
+        let settings = CookieSettings::new(config).unwrap();
+        assert_eq!(
+            settings.set_cookie("theme", "dark", None),
+            "theme=dark; Path=/; Domain=example.com; Max-Age=3600; Secure; HttpOnly; SameSite=Strict"
+        );
+
+        let short = CookieConfig {
+            secret: Some("short".to_string()),
+            ..CookieConfig::default()
+        };
+        assert!(CookieSettings::new(short).is_err());
+        let insecure = CookieConfig {
+            same_site: SameSite::None,
+            ..CookieConfig::default()
+        };
+        assert!(CookieSettings::new(insecure).is_err());
+    }
+
+    /// 测试 `Cookie<T>` 提取器和响应中的 `Set-Cookie`
+    #[tokio::test]
+    async fn test_typed_cookie() {
+        let router = CookieConfig::default()
+            .apply(
+                Router::new()
+                    .route(
+                        "/login",
+                        get(|cookies: Cookies| async move {
+                            cookies.set_value(&Session { user_id: 42 }).unwrap()
+                        }),
+                    )
+                    .route(
+                        "/me",
+                        get(|Cookie(session): Cookie<Session>| async move {
+                            session.user_id.to_string()
+                        }),
+                    )
+                    .route(
+                        "/logout",
+                        get(|cookies: Cookies| async move { cookies.remove_value::<Session>() }),
+                    ),
+            )
+            .unwrap();
+
+        let response = router
+            .clone()
+            .oneshot(Request::get("/login").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let set_cookie = response.headers()[header::SET_COOKIE]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let pair = set_cookie.split(';').next().unwrap();
+
+        let request = Request::get("/me")
+            .header(header::COOKIE, pair)
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = router
+            .clone()
+            .oneshot(Request::get("/me").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = router
+            .oneshot(Request::get("/logout").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let set_cookie = response.headers()[header::SET_COOKIE].to_str().unwrap();
+        assert!(set_cookie.starts_with("session=; Path=/; Max-Age=0"));
+    }
+}
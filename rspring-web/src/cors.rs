@@ -0,0 +1,271 @@
+//! 跨域配置模块
+//!
+//! 根据 `[web.cors]` 配置生成 `CorsLayer`，不同环境可在 profile 配置文件中覆盖
+
+use std::time::Duration;
+
+use axum::http::{HeaderName, HeaderValue, Method};
+use axum::Router;
+use rspring_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+use tower_http::cors::{AllowHeaders, AllowOrigin, CorsLayer, ExposeHeaders};
+
+/// 通配符，表示允许任意值
+const ANY: &str = "*";
+
+/// 跨域配置
+///
+/// # 示例
+/// ```toml
+/// [web.cors]
+/// allowed_origins = ["https://admin.example.com"]
+/// allowed_methods = ["GET", "POST"]
+/// allowed_headers = ["Authorization", "Content-Type"]
+/// allow_credentials = true
+/// max_age = "1h"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct CorsConfig {
+    /// 是否启用
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 允许的来源，`"*"` 表示任意来源
+    ///
+    /// # 默认值
+    /// `["*"]`
+    #[serde(default = "default_any")]
+    pub allowed_origins: Vec<String>,
+    /// 允许的请求方法
+    ///
+    /// # 默认值
+    /// `["GET", "POST", "PUT", "DELETE", "PATCH", "OPTIONS"]`
+    #[serde(default = "default_allowed_methods")]
+    pub allowed_methods: Vec<String>,
+    /// 允许的请求头，`"*"` 表示任意请求头
+    ///
+    /// # 默认值
+    /// `["*"]`
+    #[serde(default = "default_any")]
+    pub allowed_headers: Vec<String>,
+    /// 允许浏览器读取的响应头
+    #[serde(default)]
+    pub exposed_headers: Vec<String>,
+    /// 是否允许携带凭证（Cookie、Authorization）
+    ///
+    /// 启用时 `allowed_origins` 不能包含 `"*"`，须列出具体来源；通配的请求头会改为回显请求中的值
+    ///
+    /// # 默认值
+    /// `false`
+    #[serde(default)]
+    pub allow_credentials: bool,
+    /// 预检请求的缓存时长
+    ///
+    /// # 默认值
+    /// `"1h"`
+    #[serde(default = "default_max_age", with = "rspring_core::config::duration")]
+    pub max_age: Duration,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            allowed_origins: default_any(),
+            allowed_methods: default_allowed_methods(),
+            allowed_headers: default_any(),
+            exposed_headers: Vec::new(),
+            allow_credentials: false,
+            max_age: default_max_age(),
+        }
+    }
+}
+
+impl CorsConfig {
+    /// 生成跨域中间件
+    ///
+    /// # 错误
+    /// 来源、方法或请求头格式无效，或允许携带凭证时来源为 `"*"`，返回验证错误
+    ///
+    /// # 示例
+    /// ```rust
+    /// let app = Router::new()
+    ///     .route("/api/users", get(list_users))
+    ///     .layer(cors_config.layer()?);
+    /// ```
+    pub fn layer(&self) -> Result<CorsLayer> {
+        let allow_origin = if self.allowed_origins.iter().any(|origin| origin == ANY) {
+            if self.allow_credentials {
+                return Err(Error::validation(
+                    "允许携带凭证时 allowed_origins 不能包含 \"*\"，请列出具体的来源",
+                ));
+            }
+            AllowOrigin::any()
+        } else {
+            let origins = self
+                .allowed_origins
+                .iter()
+                .map(|origin| {
+                    HeaderValue::from_str(origin)
+                        .map_err(|_| Error::validation(format!("无效的跨域来源: {}", origin)))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            AllowOrigin::list(origins)
+        };
+
+        let methods = self
+            .allowed_methods
+            .iter()
+            .map(|method| {
+                Method::from_bytes(method.to_uppercase().as_bytes())
+                    .map_err(|_| Error::validation(format!("无效的请求方法: {}", method)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let allow_headers = if self.allowed_headers.iter().any(|header| header == ANY) {
+            if self.allow_credentials {
+                AllowHeaders::mirror_request()
+            } else {
+                AllowHeaders::any()
+            }
+        } else {
+            AllowHeaders::list(parse_header_names(&self.allowed_headers)?)
+        };
+
+        Ok(CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods(methods)
+            .allow_headers(allow_headers)
+            .expose_headers(ExposeHeaders::list(parse_header_names(&self.exposed_headers)?))
+            .allow_credentials(self.allow_credentials)
+            .max_age(self.max_age))
+    }
+
+    /// 将跨域中间件应用到路由，未启用时原样返回
+    ///
+    /// # 示例
+    /// ```rust
+    /// let web_config = WebConfig::load(&config)?;
+    /// if let Some(cors) = &web_config.cors {
+    ///     app = cors.apply(app)?;
+    /// }
+    /// ```
+    pub fn apply<S>(&self, router: Router<S>) -> Result<Router<S>>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        if !self.enabled {
+            return Ok(router);
+        }
+        Ok(router.layer(self.layer()?))
+    }
+}
+
+/// 解析请求头名称列表
+fn parse_header_names(names: &[String]) -> Result<Vec<HeaderName>> {
+    names
+        .iter()
+        .map(|name| {
+            HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| Error::validation(format!("无效的请求头名称: {}", name)))
+        })
+        .collect()
+}
+
+// 默认值函数
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_any() -> Vec<String> {
+    vec![ANY.to_string()]
+}
+
+fn default_allowed_methods() -> Vec<String> {
+    ["GET", "POST", "PUT", "DELETE", "PATCH", "OPTIONS"]
+        .iter()
+        .map(|method| method.to_string())
+        .collect()
+}
+
+fn default_max_age() -> Duration {
+    Duration::from_secs(3600)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::extract::Request;
+    use axum::http::header;
+    use axum::routing::get;
+    use tower::ServiceExt;
+
+    /// 发送预检请求
+    async fn preflight(config: &CorsConfig, origin: &str) -> axum::response::Response {
+        let app = Router::new()
+            .route("/api", get(|| async { "ok" }))
+            .layer(config.layer().unwrap());
+
+        let request = Request::builder()
+            .method(Method::OPTIONS)
+            .uri("/api")
+            .header(header::ORIGIN, origin)
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+            .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "x-token")
+            .body(Body::empty())
+            .unwrap();
+        app.oneshot(request).await.unwrap()
+    }
+
+    /// 测试默认配置允许任意来源
+    #[tokio::test]
+    async fn test_default_cors() {
+        let response = preflight(&CorsConfig::default(), "https://example.com").await;
+        let headers = response.headers();
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+        assert_eq!(headers[header::ACCESS_CONTROL_MAX_AGE], "3600");
+    }
+
+    /// 测试携带凭证时不允许任意来源，通配的请求头回显请求中的值
+    #[tokio::test]
+    async fn test_credentials_mirror_origin() {
+        let config = CorsConfig {
+            allow_credentials: true,
+            ..CorsConfig::default()
+        };
+        assert!(config.layer().unwrap_err().is_validation_error());
+
+        let config = CorsConfig {
+            allowed_origins: vec!["https://example.com".to_string()],
+            allow_credentials: true,
+            ..CorsConfig::default()
+        };
+        let response = preflight(&config, "https://example.com").await;
+        let headers = response.headers();
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_ORIGIN], "https://example.com");
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_CREDENTIALS], "true");
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_HEADERS], "x-token");
+    }
+
+    /// 测试来源白名单与无效配置
+    #[tokio::test]
+    async fn test_allowed_origins() {
+        let config = CorsConfig {
+            allowed_origins: vec!["https://admin.example.com".to_string()],
+            ..CorsConfig::default()
+        };
+
+        let response = preflight(&config, "https://evil.example.com").await;
+        assert!(!response.headers().contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+
+        let config = CorsConfig {
+            allowed_headers: vec!["bad header".to_string()],
+            ..CorsConfig::default()
+        };
+        assert!(config.layer().is_err());
+    }
+}
@@ -0,0 +1,397 @@
+//! 文件下载模块
+//!
+//! 提供用于报表导出、附件下载等接口的流式响应：
+//! - `FileResponse` 从磁盘流式读取文件，支持 `Range` 断点续传
+//! - `StreamResponse` 从任意 `AsyncRead` 流式输出
+//!
+//! 两者都会设置 `Content-Type` 和 `Content-Disposition` 响应头
+
+use std::io::SeekFrom;
+use std::path::Path;
+
+use axum::body::Body;
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use futures::{stream, TryStreamExt};
+use rspring_core::{Error, Result};
+use tokio::fs::File;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeekExt};
+use tokio_util::io::ReaderStream;
+
+/// 默认的内容类型
+const OCTET_STREAM: &str = "application/octet-stream";
+
+/// 文件下载响应
+///
+/// # 示例
+/// ```rust
+/// #[GetMapping("/reports/{id}")]
+/// #[RawResponse]
+/// pub async fn download_report(&self, #[PathVariable] id: u64, headers: HeaderMap) -> WebResult<FileResponse> {
+///     let path = self.report_service.report_path(id).await?;
+///     Ok(FileResponse::open(path).await?.filename("月度报表.xlsx").range(&headers))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct FileResponse {
+    /// 文件句柄
+    file: File,
+    /// 文件大小
+    len: u64,
+    /// 内容类型
+    content_type: String,
+    /// 下载文件名
+    filename: Option<String>,
+    /// 是否在浏览器中直接展示
+    inline: bool,
+    /// 请求中的 `Range` 头
+    range: Option<String>,
+}
+
+impl FileResponse {
+    /// 打开文件，内容类型根据扩展名推断，默认使用原文件名作为下载文件名
+    ///
+    /// # 错误
+    /// 文件不存在时返回 `NotFound` 错误，其他 IO 错误原样返回
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).await.map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => Error::not_found(path.display().to_string()),
+            _ => Error::from(e),
+        })?;
+
+        let metadata = file.metadata().await?;
+        if !metadata.is_file() {
+            return Err(Error::not_found(path.display().to_string()));
+        }
+
+        Ok(Self {
+            file,
+            len: metadata.len(),
+            content_type: mime_guess::from_path(path)
+                .first_or_octet_stream()
+                .to_string(),
+            filename: path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string()),
+            inline: false,
+            range: None,
+        })
+    }
+
+    /// 设置下载文件名
+    pub fn filename(mut self, filename: impl Into<String>) -> Self {
+        self.filename = Some(filename.into());
+        self
+    }
+
+    /// 在浏览器中直接展示，而不是作为附件下载
+    pub fn inline(mut self) -> Self {
+        self.inline = true;
+        self
+    }
+
+    /// 设置内容类型
+    pub fn content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = content_type.into();
+        self
+    }
+
+    /// 按请求中的 `Range` 头返回部分内容
+    pub fn range(mut self, headers: &HeaderMap) -> Self {
+        self.range = headers
+            .get(header::RANGE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        self
+    }
+}
+
+impl IntoResponse for FileResponse {
+    fn into_response(self) -> Response {
+        let len = self.len;
+        let range = match self.range.as_deref().map(|range| parse_range(range, len)) {
+            Some(Err(())) => {
+                return (
+                    StatusCode::RANGE_NOT_SATISFIABLE,
+                    [(header::CONTENT_RANGE, format!("bytes */{}", len))],
+                )
+                    .into_response();
+            }
+            Some(Ok(range)) => range,
+            None => None,
+        };
+
+        let (status, start, count) = match range {
+            Some((start, end)) => (StatusCode::PARTIAL_CONTENT, start, end - start + 1),
+            None => (StatusCode::OK, 0, len),
+        };
+
+        // 定位到起始位置后再按长度读取，读取过程中出现的错误会中断响应体
+        let mut file = self.file;
+        let body = stream::once(async move {
+            file.seek(SeekFrom::Start(start)).await?;
+            Ok::<_, std::io::Error>(ReaderStream::new(file.take(count)))
+        })
+        .try_flatten();
+
+        let mut response = Response::new(Body::from_stream(body));
+        *response.status_mut() = status;
+        let headers = response.headers_mut();
+        headers.insert(
+            header::CONTENT_TYPE,
+            header_value(&self.content_type, OCTET_STREAM),
+        );
+        headers.insert(header::CONTENT_LENGTH, HeaderValue::from(count));
+        headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+        headers.insert(
+            header::CONTENT_DISPOSITION,
+            content_disposition(self.inline, self.filename.as_deref()),
+        );
+        if let Some((start, end)) = range {
+            headers.insert(
+                header::CONTENT_RANGE,
+                header_value(&format!("bytes {}-{}/{}", start, end, len), ""),
+            );
+        }
+        response
+    }
+}
+
+/// 流式下载响应
+///
+/// 用于内容在运行时生成或来自其他存储的场景，不支持 `Range` 请求
+///
+/// # 示例
+/// ```rust
+/// #[GetMapping("/export")]
+/// #[RawResponse]
+/// pub async fn export(&self) -> StreamResponse {
+///     let reader = self.export_service.export_csv().await;
+///     StreamResponse::new(reader).filename("users.csv").content_type("text/csv; charset=utf-8")
+/// }
+/// ```
+pub struct StreamResponse {
+    /// 响应体
+    body: Body,
+    /// 内容类型
+    content_type: String,
+    /// 内容长度（可选）
+    content_length: Option<u64>,
+    /// 下载文件名
+    filename: Option<String>,
+    /// 是否在浏览器中直接展示
+    inline: bool,
+}
+
+impl StreamResponse {
+    /// 从 `AsyncRead` 创建流式响应
+    pub fn new<R>(reader: R) -> Self
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        Self {
+            body: Body::from_stream(ReaderStream::new(reader)),
+            content_type: OCTET_STREAM.to_string(),
+            content_length: None,
+            filename: None,
+            inline: false,
+        }
+    }
+
+    /// 设置下载文件名，未设置内容类型时根据扩展名推断
+    pub fn filename(mut self, filename: impl Into<String>) -> Self {
+        let filename = filename.into();
+        if self.content_type == OCTET_STREAM {
+            self.content_type = mime_guess::from_path(&filename)
+                .first_or_octet_stream()
+                .to_string();
+        }
+        self.filename = Some(filename);
+        self
+    }
+
+    /// 在浏览器中直接展示，而不是作为附件下载
+    pub fn inline(mut self) -> Self {
+        self.inline = true;
+        self
+    }
+
+    /// 设置内容类型
+    pub fn content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = content_type.into();
+        self
+    }
+
+    /// 设置内容长度，已知长度时客户端可以显示下载进度
+    pub fn content_length(mut self, content_length: u64) -> Self {
+        self.content_length = Some(content_length);
+        self
+    }
+}
+
+impl IntoResponse for StreamResponse {
+    fn into_response(self) -> Response {
+        let mut response = Response::new(self.body);
+        let headers = response.headers_mut();
+        headers.insert(
+            header::CONTENT_TYPE,
+            header_value(&self.content_type, OCTET_STREAM),
+        );
+        headers.insert(
+            header::CONTENT_DISPOSITION,
+            content_disposition(self.inline, self.filename.as_deref()),
+        );
+        if let Some(content_length) = self.content_length {
+            headers.insert(header::CONTENT_LENGTH, HeaderValue::from(content_length));
+        }
+        response
+    }
+}
+
+/// 解析单个 `Range` 请求，返回闭区间 `(start, end)`
+///
+/// 不是 `bytes` 单位或包含多个区间时返回 `Ok(None)`，按完整内容响应；
+/// 区间无法满足时返回 `Err(())`
+fn parse_range(range: &str, len: u64) -> std::result::Result<Option<(u64, u64)>, ()> {
+    let Some(spec) = range.trim().strip_prefix("bytes=") else {
+        return Ok(None);
+    };
+    if spec.contains(',') {
+        return Ok(None);
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return Err(());
+    };
+
+    let (start, end) = match (start.trim(), end.trim()) {
+        // 后缀区间，如 `bytes=-500` 表示最后 500 字节
+        ("", suffix) => {
+            let suffix: u64 = suffix.parse().map_err(|_| ())?;
+            if suffix == 0 {
+                return Err(());
+            }
+            (len.saturating_sub(suffix), len.saturating_sub(1))
+        }
+        (start, "") => (start.parse().map_err(|_| ())?, len.saturating_sub(1)),
+        (start, end) => {
+            let start: u64 = start.parse().map_err(|_| ())?;
+            let end: u64 = end.parse().map_err(|_| ())?;
+            (start, end.min(len.saturating_sub(1)))
+        }
+    };
+
+    if len == 0 || start > end || start >= len {
+        return Err(());
+    }
+    Ok(Some((start, end)))
+}
+
+/// 生成 `Content-Disposition` 响应头
+///
+/// 文件名同时以 ASCII 回退形式和 RFC 5987 编码形式输出，保证中文文件名正确显示
+fn content_disposition(inline: bool, filename: Option<&str>) -> HeaderValue {
+    let disposition = if inline { "inline" } else { "attachment" };
+    let Some(filename) = filename else {
+        return HeaderValue::from_static(disposition);
+    };
+
+    let fallback: String = filename
+        .chars()
+        .map(|ch| {
+            if ch == ' ' || (ch.is_ascii_graphic() && ch != '"' && ch != '\\') {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let encoded: String = filename
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect();
+
+    header_value(
+        &format!(
+            "{}; filename=\"{}\"; filename*=UTF-8''{}",
+            disposition, fallback, encoded
+        ),
+        disposition,
+    )
+}
+
+/// 创建响应头值，格式无效时使用回退值
+fn header_value(value: &str, fallback: &'static str) -> HeaderValue {
+    HeaderValue::from_str(value).unwrap_or_else(|_| HeaderValue::from_static(fallback))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+
+    /// 测试 `Range` 头解析
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("bytes=0-9", 100), Ok(Some((0, 9))));
+        assert_eq!(parse_range("bytes=90-", 100), Ok(Some((90, 99))));
+        assert_eq!(parse_range("bytes=-10", 100), Ok(Some((90, 99))));
+        assert_eq!(parse_range("bytes=50-200", 100), Ok(Some((50, 99))));
+        assert_eq!(parse_range("bytes=0-1,5-6", 100), Ok(None));
+        assert_eq!(parse_range("bytes=100-", 100), Err(()));
+        assert_eq!(parse_range("bytes=abc", 100), Err(()));
+    }
+
+    /// 测试文件下载与断点续传
+    #[tokio::test]
+    async fn test_file_response() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.csv");
+        std::fs::write(&path, "id,name\n1,alice\n").unwrap();
+
+        let response = FileResponse::open(&path)
+            .await
+            .unwrap()
+            .filename("报表.csv")
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/csv");
+        assert_eq!(
+            response.headers()[header::CONTENT_DISPOSITION],
+            "attachment; filename=\"__.csv\"; filename*=UTF-8''%E6%8A%A5%E8%A1%A8.csv"
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::RANGE, HeaderValue::from_static("bytes=3-6"));
+        let response = FileResponse::open(&path)
+            .await
+            .unwrap()
+            .range(&headers)
+            .into_response();
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes 3-6/16");
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"name");
+
+        let error = FileResponse::open(dir.path().join("missing.csv"))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::NotFound { .. }));
+    }
+
+    /// 测试流式响应
+    #[tokio::test]
+    async fn test_stream_response() {
+        let response = StreamResponse::new(&b"hello"[..])
+            .filename("hello.txt")
+            .into_response();
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/plain");
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"hello");
+    }
+}
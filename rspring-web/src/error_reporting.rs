@@ -0,0 +1,291 @@
+//! Web 错误上报模块
+//!
+//! 将 Web 层的服务端错误交给 `rspring_core::error::error_reporting()` 上报：
+//! - 控制器返回的服务端错误由全局异常处理器上报，报告带有错误类型
+//! - 控制器生成的路由通过 [`instrument`] 上报其他 5xx 响应，如直接返回 `StatusCode::BAD_GATEWAY`
+//!
+//! 已由全局异常处理器上报的响应带有 [`ErrorReported`] 扩展，不会重复上报。
+//! 启用 `error-webhook` 特性后，[`WebhookErrorReporter`] 将错误报告以 JSON 请求体 `POST` 到
+//! `[error_reporting.webhook]` 配置的 HTTP 端点
+
+use axum::extract::{MatchedPath, Request};
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use axum::Router;
+use rspring_core::error::{error_reporting, ErrorReport, ReportSource};
+
+/// 响应扩展，表示响应对应的错误已经上报
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorReported;
+
+/// 为路由中已添加的所有路由上报 5xx 响应
+///
+/// 只作用于调用前已添加的路由，没有路由时原样返回
+///
+/// # 示例
+/// ```rust
+/// let app = error_reporting::instrument(
+///     Router::new().route("/api/orders", post(create_order)),
+/// );
+/// ```
+pub fn instrument<S>(router: Router<S>) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router.route_layer(middleware::from_fn(report_server_errors))
+}
+
+/// 上报未被全局异常处理器上报的 5xx 响应
+async fn report_server_errors(request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let route = request.extensions().get::<MatchedPath>().map_or_else(
+        || request.uri().path().to_string(),
+        |path| path.as_str().to_string(),
+    );
+    let response = next.run(request).await;
+
+    let status = response.status();
+    if status.is_server_error() && response.extensions().get::<ErrorReported>().is_none() {
+        let message = format!("{} {} 返回 {}", method, route, status);
+        error_reporting()
+            .report(ErrorReport::new(ReportSource::Response, message).status(status.as_u16()));
+    }
+    response
+}
+
+#[cfg(feature = "error-webhook")]
+pub use webhook::{WebhookErrorConfig, WebhookErrorReporter};
+
+#[cfg(feature = "error-webhook")]
+mod webhook {
+    use std::collections::BTreeMap;
+    use std::sync::{Arc, Condvar, Mutex};
+    use std::time::Duration;
+
+    use rspring_core::config::ConfigurationManager;
+    use rspring_core::error::{error_reporting, ErrorReport, ErrorReporter, ErrorReportingConfig};
+    use rspring_core::Result;
+    use serde::{Deserialize, Serialize};
+    use tracing::warn;
+
+    use crate::webhook::build_client;
+
+    /// 错误报告推送配置
+    ///
+    /// # 示例
+    /// ```toml
+    /// [error_reporting.webhook]
+    /// url = "https://alerts.example.com/api/errors"
+    ///
+    /// [error_reporting.webhook.headers]
+    /// Authorization = "Bearer ${ALERT_TOKEN}"
+    /// ```
+    #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+    pub struct WebhookErrorConfig {
+        /// 接收错误报告的地址
+        pub url: String,
+        /// 附加的请求头，如认证信息
+        #[serde(default)]
+        pub headers: BTreeMap<String, String>,
+        /// 请求超时时间
+        ///
+        /// # 默认值
+        /// `"5s"`
+        #[serde(default = "default_timeout", with = "rspring_core::config::duration")]
+        pub timeout: Duration,
+    }
+
+    impl WebhookErrorConfig {
+        /// 加载 `[error_reporting.webhook]` 配置，没有该配置时返回 `None`
+        ///
+        /// # 错误
+        /// 配置格式错误时返回错误
+        pub fn load(config: &ConfigurationManager) -> Result<Option<Self>> {
+            if config.contains_key("error_reporting.webhook") {
+                config.get_section("error_reporting.webhook").map(Some)
+            } else {
+                Ok(None)
+            }
+        }
+    }
+
+    /// 将错误报告推送到 HTTP 端点的上报方式
+    ///
+    /// 报告在 Tokio 运行时中后台发送，不在运行时中时丢弃并记录日志
+    #[derive(Debug, Clone)]
+    pub struct WebhookErrorReporter {
+        /// 推送配置
+        config: WebhookErrorConfig,
+        /// HTTP 客户端
+        http: reqwest::Client,
+        /// 未发送完成的报告
+        pending: Arc<Pending>,
+    }
+
+    /// 未发送完成的报告数量，发送完成时唤醒等待的 `flush`
+    #[derive(Debug, Default)]
+    struct Pending {
+        /// 报告数量
+        count: Mutex<usize>,
+        /// 数量归零的通知
+        done: Condvar,
+    }
+
+    impl Pending {
+        /// 增加一个未发送的报告
+        fn start(&self) {
+            *self.count.lock().unwrap_or_else(|e| e.into_inner()) += 1;
+        }
+
+        /// 一个报告发送完成
+        fn finish(&self) {
+            let mut count = self.count.lock().unwrap_or_else(|e| e.into_inner());
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                self.done.notify_all();
+            }
+        }
+
+        /// 阻塞等待所有报告发送完成，返回是否在超时前完成
+        fn wait(&self, timeout: Duration) -> bool {
+            let count = self.count.lock().unwrap_or_else(|e| e.into_inner());
+            let (count, _) = self
+                .done
+                .wait_timeout_while(count, timeout, |count| *count > 0)
+                .unwrap_or_else(|e| e.into_inner());
+            *count == 0
+        }
+    }
+
+    impl WebhookErrorReporter {
+        /// 创建推送上报方式
+        ///
+        /// # 错误
+        /// 地址或请求头无效时返回错误
+        pub fn new(config: WebhookErrorConfig) -> Result<Self> {
+            let http = build_client(&config.url, &config.headers, config.timeout)?;
+            Ok(Self {
+                config,
+                http,
+                pending: Arc::default(),
+            })
+        }
+
+        /// 按 `[error_reporting.webhook]` 配置创建推送上报方式并加入全局错误上报器，
+        /// 没有该配置或 `[error_reporting]` 关闭了上报时不做处理
+        ///
+        /// # 错误
+        /// 配置无效时返回错误
+        ///
+        /// # 示例
+        /// ```rust
+        /// WebhookErrorReporter::install(context.config_manager())?;
+        /// ```
+        pub fn install(config: &ConfigurationManager) -> Result<()> {
+            if !ErrorReportingConfig::load(config)?.enabled {
+                return Ok(());
+            }
+            if let Some(config) = WebhookErrorConfig::load(config)? {
+                error_reporting().add_reporter(Arc::new(Self::new(config)?));
+            }
+            Ok(())
+        }
+    }
+
+    impl ErrorReporter for WebhookErrorReporter {
+        fn report(&self, report: &ErrorReport) {
+            let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+                warn!("不在 Tokio 运行时中，丢弃错误报告: {}", report.message);
+                return;
+            };
+            let request = self.http.post(&self.config.url).json(report);
+            let url = self.config.url.clone();
+            let pending = self.pending.clone();
+            pending.start();
+            runtime.spawn(async move {
+                match request.send().await {
+                    Ok(response) if !response.status().is_success() => {
+                        warn!("推送错误报告失败: {} 返回 {}", url, response.status());
+                    }
+                    Err(e) => warn!("推送错误报告失败: {}", e),
+                    Ok(_) => {}
+                }
+                pending.finish();
+            });
+        }
+
+        /// 阻塞等待已提交的报告发送完成，异步代码中应通过 `spawn_blocking` 调用
+        fn flush(&self, timeout: Duration) -> bool {
+            self.pending.wait(timeout)
+        }
+    }
+
+    // 默认值函数
+
+    fn default_timeout() -> Duration {
+        Duration::from_secs(5)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exception::WebError;
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use rspring_core::error::{ErrorReporting, InMemoryErrorReporter};
+    use rspring_core::{Error, GlobalScope};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    /// 测试控制器错误和其他 5xx 响应各上报一次，客户端错误不上报
+    #[tokio::test]
+    async fn test_report_server_errors() {
+        let memory = Arc::new(InMemoryErrorReporter::new());
+        let reporting = ErrorReporting::new(Some("2.0.0".to_string()), None);
+        reporting.add_reporter(memory.clone());
+        let scope = Arc::new(GlobalScope::new());
+        scope.insert(Arc::new(reporting));
+
+        let router: Router = instrument(
+            Router::new()
+                .route(
+                    "/orders/:id",
+                    get(|| async {
+                        Err::<(), _>(WebError(Error::internal("订单服务连接超时")))
+                    }),
+                )
+                .route("/upstream", get(|| async { StatusCode::BAD_GATEWAY }))
+                .route(
+                    "/invalid",
+                    get(|| async {
+                        Err::<(), _>(WebError(Error::validation("数量必须大于 0")))
+                    }),
+                ),
+        );
+        scope
+            .run(async {
+                for uri in ["/orders/7", "/upstream", "/invalid"] {
+                    let request = Request::get(uri).body(Body::empty()).unwrap();
+                    router.clone().oneshot(request).await.unwrap();
+                }
+            })
+            .await;
+
+        let reports: Vec<_> = memory
+            .reports()
+            .into_iter()
+            .filter(|report| {
+                report.message.contains("订单服务") || report.message.contains("/upstream")
+            })
+            .collect();
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].source, ReportSource::Error);
+        assert_eq!(reports[0].message, "内部服务器错误: 订单服务连接超时");
+        assert_eq!(reports[0].release.as_deref(), Some("2.0.0"));
+        assert_eq!(reports[1].source, ReportSource::Response);
+        assert_eq!(reports[1].message, "GET /upstream 返回 502 Bad Gateway");
+        assert_eq!(reports[1].status, Some(502));
+    }
+}
@@ -0,0 +1,456 @@
+//! 全局异常处理模块
+//!
+//! 提供类似 Spring `@ControllerAdvice` 的全局异常处理能力：
+//! - 控制器返回 `WebResult<T>`，可直接用 `?` 传播 `rspring_core::Error`
+//! - 错误统一转换为 `ApiResponse` 格式的 JSON 响应，并设置对应的 HTTP 状态码
+//! - 支持注册自定义 `ControllerAdvice` 覆盖默认的错误映射，`#[RestControllerAdvice]` 从异常处理方法生成实现，
+//!   容器中的增强组件通过 [`advice_definition`] 在自动装配时注册
+//! - 可切换为 RFC 7807 `application/problem+json` 错误格式
+
+use std::any::TypeId;
+use std::marker::PhantomData;
+use std::sync::{Arc, RwLock};
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use once_cell::sync::Lazy;
+use rspring_core::error::{error_reporting, handle_error, is_reportable, ErrorReport};
+use rspring_core::{BeanDefinition, BeanDependency, Error};
+
+use crate::error_reporting::ErrorReported;
+use crate::problem::{ErrorFormat, ProblemDetail};
+use crate::response::ApiResponse;
+
+/// Web 层结果类型
+///
+/// 控制器方法返回此类型即可，错误会由全局异常处理器转换为 HTTP 响应
+///
+/// # 示例
+/// ```rust
+/// pub async fn get_user(Path(id): Path<u64>) -> WebResult<ApiResponse<User>> {
+///     let user = user_service.get_user(id).await?;
+///     Ok(ApiResponse::success(user))
+/// }
+/// ```
+pub type WebResult<T> = std::result::Result<T, WebError>;
+
+/// Web 层错误
+///
+/// 对 `rspring_core::Error` 的包装，实现了 `IntoResponse`，
+/// 转换时会依次尝试已注册的 `ControllerAdvice`，都未处理时使用默认映射
+#[derive(Debug)]
+pub struct WebError(pub Error);
+
+impl WebError {
+    /// 获取内部错误的引用
+    pub fn error(&self) -> &Error {
+        &self.0
+    }
+
+    /// 取出内部错误
+    pub fn into_inner(self) -> Error {
+        self.0
+    }
+}
+
+impl From<Error> for WebError {
+    fn from(error: Error) -> Self {
+        Self(error)
+    }
+}
+
+impl std::fmt::Display for WebError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for WebError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl IntoResponse for WebError {
+    fn into_response(self) -> Response {
+        GLOBAL_EXCEPTION_HANDLER.handle(&self.0)
+    }
+}
+
+/// 控制器增强特征
+///
+/// 用于自定义错误到 HTTP 响应的映射，返回 `None` 表示不处理，交由下一个处理器
+///
+/// # 示例
+/// ```rust
+/// struct NotFoundAdvice;
+///
+/// impl ControllerAdvice for NotFoundAdvice {
+///     fn handle(&self, error: &Error) -> Option<Response> {
+///         match error {
+///             Error::NotFound { resource } => Some(
+///                 (StatusCode::NOT_FOUND, format!("{} 不存在", resource)).into_response()
+///             ),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// GLOBAL_EXCEPTION_HANDLER.register(NotFoundAdvice);
+/// ```
+pub trait ControllerAdvice: Send + Sync + 'static {
+    /// 处理错误
+    ///
+    /// # 返回值
+    /// * `Some(Response)` - 已处理，直接返回该响应
+    /// * `None` - 未处理，继续交给后续处理器
+    fn handle(&self, error: &Error) -> Option<Response>;
+}
+
+/// 全局异常处理器
+///
+/// 按注册顺序调用 `ControllerAdvice`，全部未处理时按错误响应格式使用默认映射。
+/// 每种类型的增强只保留一个，重复注册时替换原来的实例
+#[derive(Default)]
+pub struct GlobalExceptionHandler {
+    /// 已注册的控制器增强及其类型
+    advices: RwLock<Vec<(TypeId, Arc<dyn ControllerAdvice>)>>,
+    /// 错误响应格式
+    format: RwLock<ErrorFormat>,
+}
+
+impl GlobalExceptionHandler {
+    /// 创建新的全局异常处理器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册控制器增强
+    ///
+    /// 先注册的增强优先处理，已注册同类型的增强时替换它并保持原来的顺序
+    pub fn register<A: ControllerAdvice>(&self, advice: A) {
+        self.register_shared(Arc::new(advice));
+    }
+
+    /// 注册共享的控制器增强，如容器中的组件
+    pub fn register_shared<A: ControllerAdvice>(&self, advice: Arc<A>) {
+        let type_id = TypeId::of::<A>();
+        let mut advices = self.advices.write().unwrap_or_else(|e| e.into_inner());
+        match advices.iter_mut().find(|(registered, _)| *registered == type_id) {
+            Some((_, existing)) => *existing = advice,
+            None => advices.push((type_id, advice)),
+        }
+    }
+
+    /// 移除类型为 `A` 的控制器增强，返回是否移除
+    pub fn unregister<A: ControllerAdvice>(&self) -> bool {
+        let type_id = TypeId::of::<A>();
+        let mut advices = self.advices.write().unwrap_or_else(|e| e.into_inner());
+        let count = advices.len();
+        advices.retain(|(registered, _)| *registered != type_id);
+        advices.len() < count
+    }
+
+    /// 设置错误响应格式
+    ///
+    /// # 示例
+    /// ```rust
+    /// let web_config = WebConfig::load(&config)?;
+    /// GLOBAL_EXCEPTION_HANDLER.set_error_format(web_config.error_format);
+    /// ```
+    pub fn set_error_format(&self, format: ErrorFormat) {
+        *self.format.write().unwrap_or_else(|e| e.into_inner()) = format;
+    }
+
+    /// 获取错误响应格式
+    pub fn error_format(&self) -> ErrorFormat {
+        *self.format.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 清空所有已注册的控制器增强
+    pub fn clear(&self) {
+        self.advices.write().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    /// 将错误转换为 HTTP 响应
+    ///
+    /// 需要上报的错误在增强和默认映射生成响应后上报，报告带有最终的状态码；
+    /// 增强将其映射为非 5xx 响应时不上报
+    pub fn handle(&self, error: &Error) -> Response {
+        // 复制一份增强列表，避免在调用用户代码时持有锁
+        let advices = self.advices.read().unwrap_or_else(|e| e.into_inner()).clone();
+        let mut response = advices
+            .iter()
+            .find_map(|(_, advice)| advice.handle(error))
+            .unwrap_or_else(|| match self.error_format() {
+                ErrorFormat::ApiResponse => default_error_response(error),
+                ErrorFormat::Problem => problem_error_response(error),
+            });
+
+        let status = response.status();
+        if is_reportable(error) && status.is_server_error() {
+            error_reporting().report(ErrorReport::from_error(error).status(status.as_u16()));
+            response.extensions_mut().insert(ErrorReported);
+        }
+        response
+    }
+}
+
+/// 全局异常处理器实例
+pub static GLOBAL_EXCEPTION_HANDLER: Lazy<GlobalExceptionHandler> =
+    Lazy::new(GlobalExceptionHandler::new);
+
+/// 已注册到全局异常处理器的控制器增强
+///
+/// 由 [`advice_definition`] 注册到容器，表示增强组件 `A` 已经生效
+pub struct AdviceRegistration<A> {
+    /// 增强组件类型
+    advice: PhantomData<fn() -> A>,
+}
+
+impl<A> std::fmt::Debug for AdviceRegistration<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("AdviceRegistration").field(&std::any::type_name::<A>()).finish()
+    }
+}
+
+/// 创建注册控制器增强的 Bean 定义
+///
+/// 自动装配时从容器获取增强组件 `A`，注册到 [`GLOBAL_EXCEPTION_HANDLER`]，
+/// `#[RestControllerAdvice]` 标注的类型可以在 `component_scan!` 中以 `advice(Type)` 列出。
+/// 多次装配（如每个测试上下文）时替换之前注册的同类型增强，不会重复处理
+///
+/// # 示例
+/// ```rust
+/// context.register_singleton(GlobalAdvice::new()).await;
+/// context.container().write().await.register_beans(vec![advice_definition::<GlobalAdvice>()]);
+/// context.auto_wire().await?;
+/// ```
+pub fn advice_definition<A: ControllerAdvice>() -> BeanDefinition {
+    let name = format!("{}Registration", std::any::type_name::<A>().rsplit("::").next().unwrap_or_default());
+    BeanDefinition::new(name, vec![BeanDependency::of::<A>()], |container| {
+        GLOBAL_EXCEPTION_HANDLER.register_shared(container.require::<A>()?);
+        Ok(AdviceRegistration::<A> { advice: PhantomData })
+    })
+}
+
+/// 获取错误对应的 HTTP 状态码
+///
+/// # 映射规则
+/// - `Validation` → 400
+/// - `Business` → 错误携带的状态码，如 `BusinessError` 变体声明的状态码，未设置时为 400
+/// - `Unauthorized` → 401
+/// - `Forbidden` → 403
+/// - `NotFound` → 404
+/// - 其他错误 → 500
+pub fn status_code_of(error: &Error) -> StatusCode {
+    match error {
+        Error::Validation { .. } => StatusCode::BAD_REQUEST,
+        Error::Business { status, .. } => status
+            .and_then(|status| StatusCode::from_u16(status).ok())
+            .unwrap_or(StatusCode::BAD_REQUEST),
+        Error::Unauthorized => StatusCode::UNAUTHORIZED,
+        Error::Forbidden { .. } => StatusCode::FORBIDDEN,
+        Error::NotFound { .. } => StatusCode::NOT_FOUND,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// 默认的错误响应
+///
+/// 记录错误日志，并生成 `ApiResponse<ErrorResponse>` 格式的 JSON 响应
+pub fn default_error_response(error: &Error) -> Response {
+    let status = status_code_of(error);
+    let error_response = handle_error(error, Some("web"));
+
+    let body = ApiResponse::new(
+        status.as_u16() as i32,
+        error_response.message.clone(),
+        Some(error_response),
+    );
+
+    (status, axum::Json(body)).into_response()
+}
+
+/// 按当前错误响应格式生成错误响应
+///
+/// 用于提取器拒绝、超时等不经过 `Error` 的框架内部错误
+pub fn error_response(status: StatusCode, message: impl Into<String>) -> Response {
+    match GLOBAL_EXCEPTION_HANDLER.error_format() {
+        ErrorFormat::ApiResponse => ApiResponse::<()>::error(status.as_u16() as i32, message).into_response(),
+        ErrorFormat::Problem => ProblemDetail::new(status).with_detail(message).into_response(),
+    }
+}
+
+/// RFC 7807 格式的错误响应
+///
+/// 记录错误日志，并生成 `application/problem+json` 响应，错误码作为 `code` 扩展字段
+pub fn problem_error_response(error: &Error) -> Response {
+    let status = status_code_of(error);
+    let error_response = handle_error(error, Some("web"));
+
+    let mut problem = ProblemDetail::new(status)
+        .with_detail(error_response.message)
+        .with_extension("code", error_response.code);
+    if let Some(details) = error_response.details {
+        problem = problem.with_extension("details", details);
+    }
+
+    problem.into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试默认状态码映射
+    #[test]
+    fn test_status_code_mapping() {
+        assert_eq!(status_code_of(&Error::validation("无效")), StatusCode::BAD_REQUEST);
+        assert_eq!(status_code_of(&Error::business("E001", "失败")), StatusCode::BAD_REQUEST);
+        assert_eq!(status_code_of(&Error::business("EXISTS", "已存在").with_status(409)), StatusCode::CONFLICT);
+        assert_eq!(status_code_of(&Error::Unauthorized), StatusCode::UNAUTHORIZED);
+        assert_eq!(status_code_of(&Error::forbidden("需要管理员角色")), StatusCode::FORBIDDEN);
+        assert_eq!(status_code_of(&Error::not_found("用户")), StatusCode::NOT_FOUND);
+        assert_eq!(status_code_of(&Error::internal("崩溃")), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[derive(Debug, rspring_core::BusinessError)]
+    enum OrderError {
+        #[error(status = 409, message = "订单 {0} 已存在")]
+        Exists(u64),
+        #[error(code = "ORDER_LOCKED", message = "订单 {id} 已锁定")]
+        Locked { id: u64 },
+    }
+
+    /// 测试 `#[derive(BusinessError)]` 声明的状态码随错误返回，未声明时为 400
+    #[test]
+    fn test_business_error_status() {
+        let error: Error = OrderError::Exists(7).into();
+        assert_eq!(error.error_code(), Some("EXISTS"));
+        assert!(error.to_string().contains("订单 7 已存在"));
+        assert_eq!(status_code_of(&error), StatusCode::CONFLICT);
+
+        let error: Error = OrderError::Locked { id: 8 }.into();
+        assert_eq!(error.error_code(), Some("ORDER_LOCKED"));
+        assert_eq!(status_code_of(&error), StatusCode::BAD_REQUEST);
+        // 状态码属于错误本身，同一错误码直接创建的错误不受影响
+        assert_eq!(status_code_of(&Error::business("EXISTS", "已存在")), StatusCode::BAD_REQUEST);
+    }
+
+    /// 测试自定义控制器增强优先于默认映射
+    #[test]
+    fn test_controller_advice_override() {
+        struct TeapotAdvice;
+
+        impl ControllerAdvice for TeapotAdvice {
+            fn handle(&self, error: &Error) -> Option<Response> {
+                error.is_business_error().then(|| StatusCode::IM_A_TEAPOT.into_response())
+            }
+        }
+
+        let handler = GlobalExceptionHandler::new();
+        handler.register(TeapotAdvice);
+
+        let response = handler.handle(&Error::business("TEA", "茶壶"));
+        assert_eq!(response.status(), StatusCode::IM_A_TEAPOT);
+
+        // 未被处理的错误走默认映射
+        let response = handler.handle(&Error::not_found("用户"));
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    /// 测试按最终响应状态上报错误，增强映射为非 5xx 响应的错误不上报
+    #[tokio::test]
+    async fn test_report_final_status() {
+        struct TimeoutAdvice;
+
+        impl ControllerAdvice for TimeoutAdvice {
+            fn handle(&self, error: &Error) -> Option<Response> {
+                error.to_string().contains("超时").then(|| StatusCode::SERVICE_UNAVAILABLE.into_response())
+            }
+        }
+
+        struct RetryAdvice;
+
+        impl ControllerAdvice for RetryAdvice {
+            fn handle(&self, error: &Error) -> Option<Response> {
+                error.to_string().contains("重试").then(|| StatusCode::TOO_MANY_REQUESTS.into_response())
+            }
+        }
+
+        let memory = Arc::new(rspring_core::error::InMemoryErrorReporter::new());
+        let reporting = rspring_core::error::ErrorReporting::new(None, None);
+        reporting.add_reporter(memory.clone());
+        let scope = Arc::new(rspring_core::GlobalScope::new());
+        scope.insert(Arc::new(reporting));
+
+        let handler = GlobalExceptionHandler::new();
+        handler.register(TimeoutAdvice);
+        handler.register(RetryAdvice);
+        let (timeout, retry) = scope
+            .run(async {
+                (
+                    handler.handle(&Error::internal("库存服务超时")),
+                    handler.handle(&Error::internal("请稍后重试")),
+                )
+            })
+            .await;
+
+        assert!(timeout.extensions().get::<ErrorReported>().is_some());
+        assert!(retry.extensions().get::<ErrorReported>().is_none());
+        let reports = memory.reports();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].status, Some(503));
+    }
+
+    /// 测试容器中的增强组件在自动装配时注册到全局异常处理器
+    #[test]
+    fn test_advice_definition() {
+        struct QuotaAdvice;
+
+        impl rspring_core::Component for QuotaAdvice {
+            fn component_name(&self) -> &'static str {
+                "QuotaAdvice"
+            }
+        }
+
+        impl ControllerAdvice for QuotaAdvice {
+            fn handle(&self, error: &Error) -> Option<Response> {
+                matches!(error, Error::Business { code, .. } if code == "QUOTA_ADVICE")
+                    .then(|| StatusCode::TOO_MANY_REQUESTS.into_response())
+            }
+        }
+
+        for _ in 0..2 {
+            let mut container = rspring_core::Container::new();
+            container.register_singleton(QuotaAdvice).unwrap();
+            container.register_beans(vec![advice_definition::<QuotaAdvice>()]);
+            container.auto_wire().unwrap();
+            assert!(container.get_singleton::<AdviceRegistration<QuotaAdvice>>().is_some());
+        }
+
+        let count = |handler: &GlobalExceptionHandler| {
+            let advices = handler.advices.read().unwrap();
+            advices.iter().filter(|(type_id, _)| *type_id == TypeId::of::<QuotaAdvice>()).count()
+        };
+        assert_eq!(count(&GLOBAL_EXCEPTION_HANDLER), 1);
+        let response = GLOBAL_EXCEPTION_HANDLER.handle(&Error::business("QUOTA_ADVICE", "超出配额"));
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        assert!(GLOBAL_EXCEPTION_HANDLER.unregister::<QuotaAdvice>());
+        assert_eq!(count(&GLOBAL_EXCEPTION_HANDLER), 0);
+    }
+
+    /// 测试切换为 RFC 7807 错误格式
+    #[test]
+    fn test_problem_error_format() {
+        let handler = GlobalExceptionHandler::new();
+        assert_eq!(handler.error_format(), ErrorFormat::ApiResponse);
+
+        handler.set_error_format(ErrorFormat::Problem);
+        let response = handler.handle(&Error::not_found("用户"));
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.headers()[axum::http::header::CONTENT_TYPE], crate::problem::PROBLEM_JSON);
+    }
+}
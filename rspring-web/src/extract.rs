@@ -0,0 +1,400 @@
+//! 请求参数提取模块
+//!
+//! 为 `#[PathVariable]`、`#[RequestParam]`、`#[RequestHeader]`、`#[RequestBody]`
+//! 和 `#[Form]` 参数注解提供提取器。`#[RequestMapping]` 生成路由时使用这些提取器，
+//! 参数缺失或格式错误时返回带有参数名称的 400 错误响应
+//!
+//! 请求体按 `Content-Type` 协商：`application/x-www-form-urlencoded` 按表单解析，
+//! 其余按 JSON 解析
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use axum::async_trait;
+use axum::extract::{FromRequest, FromRequestParts, Path, Query, Request};
+use axum::http::request::Parts;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::{Form, Json};
+use serde::de::DeserializeOwned;
+
+use crate::exception::error_response;
+
+/// 表单请求体的 Content-Type
+pub const FORM_URLENCODED: &str = "application/x-www-form-urlencoded";
+
+/// 参数提取失败的拒绝响应
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParameterRejection {
+    /// 错误描述
+    pub message: String,
+}
+
+impl ParameterRejection {
+    /// 创建拒绝响应
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParameterRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl IntoResponse for ParameterRejection {
+    fn into_response(self) -> Response {
+        error_response(StatusCode::BAD_REQUEST, self.message)
+    }
+}
+
+/// 按名称读取并转换参数值
+///
+/// 路径变量、请求参数和请求头共用同一套读取和错误提示规则
+pub trait ParameterSource {
+    /// 参数来源的名称，用于错误提示，如 `请求参数`
+    fn kind(&self) -> &'static str;
+
+    /// 按名称查找原始参数值
+    fn lookup(&self, name: &str) -> Option<&str>;
+
+    /// 读取必填参数
+    ///
+    /// # 错误
+    /// 参数缺失或无法转换为 `T` 时返回 400
+    fn required<T>(&self, name: &str) -> Result<T, ParameterRejection>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.optional(name)?
+            .ok_or_else(|| ParameterRejection::new(format!("缺少{} `{}`", self.kind(), name)))
+    }
+
+    /// 读取可选参数，缺失时返回 `None`
+    ///
+    /// # 错误
+    /// 参数无法转换为 `T` 时返回 400
+    fn optional<T>(&self, name: &str) -> Result<Option<T>, ParameterRejection>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.lookup(name)
+            .map(|value| {
+                value.parse::<T>().map_err(|e| {
+                    ParameterRejection::new(format!("{} `{}` 格式错误: {}", self.kind(), name, e))
+                })
+            })
+            .transpose()
+    }
+
+    /// 读取参数，缺失时使用默认值
+    ///
+    /// # 错误
+    /// 参数无法转换为 `T` 时返回 400
+    fn or_else<T, F>(&self, name: &str, default: F) -> Result<T, ParameterRejection>
+    where
+        T: FromStr,
+        T::Err: Display,
+        F: FnOnce() -> T,
+    {
+        Ok(self.optional(name)?.unwrap_or_else(default))
+    }
+
+    /// 读取参数，缺失时解析字符串形式的默认值
+    ///
+    /// # 错误
+    /// 参数或默认值无法转换为 `T` 时返回 400
+    fn or_parse<T>(&self, name: &str, default: &str) -> Result<T, ParameterRejection>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        match self.optional(name)? {
+            Some(value) => Ok(value),
+            None => default.parse::<T>().map_err(|e| {
+                ParameterRejection::new(format!("{} `{}` 的默认值格式错误: {}", self.kind(), name, e))
+            }),
+        }
+    }
+}
+
+/// 路径变量提取器
+///
+/// # 示例
+/// ```rust
+/// async fn get_user(path: PathVariables) -> Result<String, ParameterRejection> {
+///     let id: u64 = path.required("id")?;
+///     Ok(id.to_string())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PathVariables(pub HashMap<String, String>);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for PathVariables {
+    type Rejection = ParameterRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(variables) = Path::<HashMap<String, String>>::from_request_parts(parts, state)
+            .await
+            .map_err(|rejection| ParameterRejection::new(format!("路径变量解析失败: {}", rejection.body_text())))?;
+        Ok(Self(variables))
+    }
+}
+
+impl ParameterSource for PathVariables {
+    fn kind(&self) -> &'static str {
+        "路径变量"
+    }
+
+    fn lookup(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+}
+
+/// 查询参数提取器
+///
+/// 保留参数的原始顺序，同名参数取第一个值
+#[derive(Debug, Clone, Default)]
+pub struct RequestParams(pub Vec<(String, String)>);
+
+impl RequestParams {
+    /// 获取同名参数的全部值
+    pub fn get_all(&self, name: &str) -> Vec<&str> {
+        self.0
+            .iter()
+            .filter(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for RequestParams {
+    type Rejection = ParameterRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(params) = Query::<Vec<(String, String)>>::from_request_parts(parts, state)
+            .await
+            .map_err(|rejection| ParameterRejection::new(format!("请求参数解析失败: {}", rejection.body_text())))?;
+        Ok(Self(params))
+    }
+}
+
+impl ParameterSource for RequestParams {
+    fn kind(&self) -> &'static str {
+        "请求参数"
+    }
+
+    fn lookup(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// 请求头提取器
+#[derive(Debug, Clone, Default)]
+pub struct RequestHeaders(pub HeaderMap);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for RequestHeaders {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self(parts.headers.clone()))
+    }
+}
+
+impl ParameterSource for RequestHeaders {
+    fn kind(&self) -> &'static str {
+        "请求头"
+    }
+
+    fn lookup(&self, name: &str) -> Option<&str> {
+        self.0.get(name).and_then(|value| value.to_str().ok())
+    }
+}
+
+/// JSON 请求体提取器
+///
+/// 与 `Json<T>` 相同，但解析失败时统一返回 400 `ApiResponse`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonBody<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for JsonBody<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ParameterRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state)
+            .await
+            .map_err(|rejection| ParameterRejection::new(format!("请求体解析失败: {}", rejection.body_text())))?;
+        Ok(Self(value))
+    }
+}
+
+/// 表单请求体提取器
+///
+/// 只接受 `application/x-www-form-urlencoded` 请求体，解析失败时返回 400
+///
+/// # 示例
+/// ```rust
+/// async fn login(FormBody(form): FormBody<LoginForm>) -> Redirect {
+///     // ...
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FormBody<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for FormBody<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ParameterRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Form(value) = Form::<T>::from_request(req, state)
+            .await
+            .map_err(|rejection| ParameterRejection::new(format!("表单解析失败: {}", rejection.body_text())))?;
+        Ok(Self(value))
+    }
+}
+
+/// 按 `Content-Type` 协商的请求体提取器
+///
+/// 表单请求按表单解析，其余按 JSON 解析，`#[RequestBody]` 使用此提取器
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NegotiatedBody<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for NegotiatedBody<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ParameterRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        read_body(req, state)
+            .await
+            .map(Self)
+            .map_err(|message| ParameterRejection::new(format!("请求体解析失败: {}", message)))
+    }
+}
+
+/// 按 `Content-Type` 解析请求体
+///
+/// 失败时返回错误描述，由调用方决定拒绝响应的格式
+pub(crate) async fn read_body<T, S>(req: Request, state: &S) -> Result<T, String>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    if is_form(req.headers()) {
+        let Form(value) = Form::<T>::from_request(req, state)
+            .await
+            .map_err(|rejection| rejection.body_text())?;
+        Ok(value)
+    } else {
+        let Json(value) = Json::<T>::from_request(req, state)
+            .await
+            .map_err(|rejection| rejection.body_text())?;
+        Ok(value)
+    }
+}
+
+/// 请求体是否为表单
+fn is_form(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|mime| mime.trim().eq_ignore_ascii_case(FORM_URLENCODED))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试必填、可选和默认值参数
+    #[test]
+    fn test_request_params() {
+        let params = RequestParams(vec![
+            ("limit".to_string(), "20".to_string()),
+            ("tag".to_string(), "a".to_string()),
+            ("tag".to_string(), "b".to_string()),
+        ]);
+
+        assert_eq!(params.required::<u32>("limit").unwrap(), 20);
+        assert_eq!(params.optional::<u32>("offset").unwrap(), None);
+        assert_eq!(params.or_else::<u32, _>("offset", || 10).unwrap(), 10);
+        assert_eq!(params.or_parse::<String>("sort", "id").unwrap(), "id");
+        assert_eq!(params.get_all("tag"), vec!["a", "b"]);
+
+        let error = params.required::<u32>("page").unwrap_err();
+        assert_eq!(error.message, "缺少请求参数 `page`");
+        let error = params.required::<u32>("tag").unwrap_err();
+        assert!(error.message.starts_with("请求参数 `tag` 格式错误"));
+    }
+
+    /// 测试请求头读取
+    #[test]
+    fn test_request_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-tenant-id", "42".parse().unwrap());
+        let headers = RequestHeaders(headers);
+
+        assert_eq!(headers.required::<u64>("X-Tenant-Id").unwrap(), 42);
+        assert_eq!(headers.required::<u64>("x-token").unwrap_err().message, "缺少请求头 `x-token`");
+    }
+
+    /// 测试按 Content-Type 解析表单和 JSON 请求体
+    #[tokio::test]
+    async fn test_negotiated_body() {
+        #[derive(Debug, serde::Deserialize, PartialEq)]
+        struct Login {
+            username: String,
+            remember: bool,
+        }
+
+        let request = |content_type: &str, body: &'static str| {
+            Request::post("/login")
+                .header(header::CONTENT_TYPE, content_type)
+                .body(axum::body::Body::from(body))
+                .unwrap()
+        };
+        let expected = Login {
+            username: "张三".to_string(),
+            remember: true,
+        };
+
+        let form = request(
+            "application/x-www-form-urlencoded; charset=utf-8",
+            "username=%E5%BC%A0%E4%B8%89&remember=true",
+        );
+        let NegotiatedBody(login) = NegotiatedBody::<Login>::from_request(form, &()).await.unwrap();
+        assert_eq!(login, expected);
+
+        let json = request("application/json", r#"{"username":"张三","remember":true}"#);
+        let NegotiatedBody(login) = NegotiatedBody::<Login>::from_request(json, &()).await.unwrap();
+        assert_eq!(login, expected);
+
+        let json = request("application/json", r#"{"username":"张三","remember":true}"#);
+        let error = FormBody::<Login>::from_request(json, &()).await.unwrap_err();
+        assert!(error.message.starts_with("表单解析失败"));
+    }
+}
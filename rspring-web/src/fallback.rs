@@ -0,0 +1,240 @@
+//! 回退处理模块
+//!
+//! 为未匹配的路由（404）和不支持的请求方法（405）提供统一的响应：
+//! 默认按框架的错误响应格式输出，也可以在 `[web.fallback]` 中配置自定义页面，
+//! 或实现 `FallbackHandler` 完全接管
+
+use std::sync::Arc;
+
+use axum::extract::Request;
+use axum::http::{header, Method, StatusCode, Uri};
+use axum::middleware::{self, Next};
+use axum::response::{Html, IntoResponse, Response};
+use axum::Router;
+use rspring_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::exception::error_response;
+
+/// 回退处理器
+///
+/// # 示例
+/// ```rust
+/// struct SpaFallback;
+///
+/// impl FallbackHandler for SpaFallback {
+///     fn not_found(&self, method: &Method, uri: &Uri) -> Response {
+///         if uri.path().starts_with("/api/") {
+///             return DefaultFallbackHandler.not_found(method, uri);
+///         }
+///         Html(include_str!("../public/index.html")).into_response()
+///     }
+/// }
+///
+/// let app = web_config.fallback.apply_with(app, SpaFallback);
+/// ```
+pub trait FallbackHandler: Send + Sync + 'static {
+    /// 处理未匹配的路由
+    fn not_found(&self, method: &Method, uri: &Uri) -> Response {
+        error_response(StatusCode::NOT_FOUND, format!("接口不存在: {} {}", method, uri.path()))
+    }
+
+    /// 处理不支持的请求方法，`Allow` 响应头由框架保留
+    fn method_not_allowed(&self, method: &Method, uri: &Uri) -> Response {
+        error_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            format!("不支持的请求方法: {} {}", method, uri.path()),
+        )
+    }
+}
+
+/// 默认回退处理器，按框架的错误响应格式输出
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultFallbackHandler;
+
+impl FallbackHandler for DefaultFallbackHandler {}
+
+/// 页面回退处理器，返回配置的 HTML 页面，未配置的情况使用默认处理
+#[derive(Debug, Clone, Default)]
+struct PageFallbackHandler {
+    /// 404 页面内容
+    not_found: Option<String>,
+    /// 405 页面内容
+    method_not_allowed: Option<String>,
+}
+
+impl FallbackHandler for PageFallbackHandler {
+    fn not_found(&self, method: &Method, uri: &Uri) -> Response {
+        match &self.not_found {
+            Some(page) => (StatusCode::NOT_FOUND, Html(page.clone())).into_response(),
+            None => DefaultFallbackHandler.not_found(method, uri),
+        }
+    }
+
+    fn method_not_allowed(&self, method: &Method, uri: &Uri) -> Response {
+        match &self.method_not_allowed {
+            Some(page) => (StatusCode::METHOD_NOT_ALLOWED, Html(page.clone())).into_response(),
+            None => DefaultFallbackHandler.method_not_allowed(method, uri),
+        }
+    }
+}
+
+/// 回退处理配置
+///
+/// # 示例
+/// ```toml
+/// [web.fallback]
+/// not_found_page = "./public/404.html"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct FallbackConfig {
+    /// 是否启用
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 404 页面文件（可选），未设置时返回错误响应
+    #[serde(default)]
+    pub not_found_page: Option<String>,
+    /// 405 页面文件（可选），未设置时返回错误响应
+    #[serde(default)]
+    pub method_not_allowed_page: Option<String>,
+}
+
+impl Default for FallbackConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            not_found_page: None,
+            method_not_allowed_page: None,
+        }
+    }
+}
+
+impl FallbackConfig {
+    /// 按配置的页面或默认格式应用回退处理，未启用时原样返回
+    ///
+    /// 应在注册完所有路由后调用；会覆盖之前设置的回退服务
+    ///
+    /// # 错误
+    /// 页面文件读取失败时返回错误
+    pub fn apply<S>(&self, router: Router<S>) -> Result<Router<S>>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let handler = PageFallbackHandler {
+            not_found: read_page(self.not_found_page.as_deref())?,
+            method_not_allowed: read_page(self.method_not_allowed_page.as_deref())?,
+        };
+        Ok(self.apply_with(router, handler))
+    }
+
+    /// 使用自定义回退处理器，未启用时原样返回
+    pub fn apply_with<S, H>(&self, router: Router<S>, handler: H) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+        H: FallbackHandler,
+    {
+        if !self.enabled {
+            return router;
+        }
+
+        let handler: Arc<dyn FallbackHandler> = Arc::new(handler);
+        let not_found = handler.clone();
+        router
+            .fallback(move |method: Method, uri: Uri| async move { not_found.not_found(&method, &uri) })
+            .layer(middleware::from_fn(move |request: Request, next: Next| {
+                replace_method_not_allowed(handler.clone(), request, next)
+            }))
+    }
+}
+
+/// 替换路由返回的默认 405 空响应
+async fn replace_method_not_allowed(handler: Arc<dyn FallbackHandler>, request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let uri = request.uri().clone();
+    let response = next.run(request).await;
+
+    // 只替换路由生成的空响应，处理函数主动返回的 405 保持原样
+    if response.status() != StatusCode::METHOD_NOT_ALLOWED || response.headers().contains_key(header::CONTENT_TYPE) {
+        return response;
+    }
+
+    let allow = response.headers().get(header::ALLOW).cloned();
+    let mut replaced = handler.method_not_allowed(&method, &uri);
+    if let Some(allow) = allow {
+        replaced.headers_mut().insert(header::ALLOW, allow);
+    }
+    replaced
+}
+
+/// 读取页面文件
+fn read_page(path: Option<&str>) -> Result<Option<String>> {
+    path.map(|path| {
+        std::fs::read_to_string(path)
+            .map_err(|e| Error::application(format!("读取回退页面失败 ({}): {}", path, e)))
+    })
+    .transpose()
+}
+
+// 默认值函数
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::routing::get;
+    use tower::ServiceExt;
+
+    /// 发送请求
+    async fn send(router: Router, method: Method, uri: &str) -> Response {
+        let request = Request::builder().method(method).uri(uri).body(Body::empty()).unwrap();
+        router.oneshot(request).await.unwrap()
+    }
+
+    /// 测试默认的 404 和 405 响应
+    #[tokio::test]
+    async fn test_default_fallback() {
+        let router = Router::new().route("/api/users", get(|| async { "users" }));
+        let router = FallbackConfig::default().apply(router).unwrap();
+
+        let response = send(router.clone(), Method::GET, "/api/missing").await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+
+        let response = send(router.clone(), Method::DELETE, "/api/users").await;
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+        assert!(response.headers()[header::ALLOW].to_str().unwrap().contains("GET"));
+
+        let response = send(router, Method::GET, "/api/users").await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    /// 测试自定义 404 页面
+    #[tokio::test]
+    async fn test_not_found_page() {
+        let dir = tempfile::tempdir().unwrap();
+        let page = dir.path().join("404.html");
+        std::fs::write(&page, "<h1>页面不存在</h1>").unwrap();
+
+        let config = FallbackConfig {
+            not_found_page: Some(page.to_string_lossy().to_string()),
+            ..FallbackConfig::default()
+        };
+        let response = send(config.apply(Router::new()).unwrap(), Method::GET, "/missing").await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/html; charset=utf-8");
+
+        let config = FallbackConfig {
+            not_found_page: Some("/nonexistent/404.html".to_string()),
+            ..FallbackConfig::default()
+        };
+        assert!(config.apply(Router::<()>::new()).is_err());
+    }
+}
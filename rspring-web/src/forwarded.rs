@@ -0,0 +1,432 @@
+//! 反向代理模块
+//!
+//! 部署在负载均衡或反向代理之后时，根据 `[web.proxy]` 中配置的受信任代理，
+//! 从 `Forwarded`（RFC 7239）或 `X-Forwarded-For`/`X-Forwarded-Proto`/`X-Forwarded-Host`
+//! 请求头中还原客户端地址、协议和主机名。只有直接连接方是受信任代理时才会读取这些请求头，
+//! 避免客户端伪造地址
+
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use axum::async_trait;
+use axum::extract::{ConnectInfo, FromRequestParts, Request};
+use axum::http::request::Parts;
+use axum::http::{header, HeaderMap};
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use axum::Router;
+use rspring_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+/// 请求的客户端信息
+///
+/// 应用了 `ProxyConfig` 时为还原后的信息，否则取自直接连接方和 `Host` 请求头
+///
+/// # 示例
+/// ```rust
+/// async fn handler(client: ClientInfo) -> String {
+///     format!("{:?} {}", client.ip, client.base_url())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientInfo {
+    /// 客户端地址，无法确定时为 `None`
+    pub ip: Option<IpAddr>,
+    /// 请求协议，`http` 或 `https`
+    pub scheme: String,
+    /// 请求的主机名（可含端口）
+    pub host: Option<String>,
+}
+
+impl ClientInfo {
+    /// 根据协议和主机名生成基础地址，如 `https://example.com`，主机名未知时为空字符串
+    pub fn base_url(&self) -> String {
+        match &self.host {
+            Some(host) => format!("{}://{}", self.scheme, host),
+            None => String::new(),
+        }
+    }
+
+    /// 根据直接连接方和请求头生成客户端信息，不读取转发请求头
+    fn direct(headers: &HeaderMap, peer: Option<IpAddr>) -> Self {
+        Self {
+            ip: peer,
+            scheme: "http".to_string(),
+            host: header_str(headers, header::HOST.as_str()).map(str::to_string),
+        }
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for ClientInfo {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> std::result::Result<Self, Self::Rejection> {
+        if let Some(client) = parts.extensions.get::<ClientInfo>() {
+            return Ok(client.clone());
+        }
+        Ok(Self::direct(&parts.headers, peer_ip(&parts.extensions)))
+    }
+}
+
+/// 反向代理配置
+///
+/// 应在其他中间件（如请求 ID、访问日志）之后应用，使其位于最外层，
+/// 这样其他中间件才能读取到还原后的客户端信息
+///
+/// # 示例
+/// ```toml
+/// [web.proxy]
+/// trusted_proxies = ["127.0.0.1", "10.0.0.0/8", "fd00::/8"]
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ProxyConfig {
+    /// 受信任的代理地址，支持单个 IP 和 CIDR，为空时不读取转发请求头
+    ///
+    /// `"unix"` 表示信任通过 Unix 域套接字连接的代理
+    #[serde(default)]
+    pub trusted_proxies: Vec<String>,
+}
+
+impl ProxyConfig {
+    /// 将客户端信息还原中间件应用到路由
+    ///
+    /// 依赖 `ConnectInfo<SocketAddr>` 获取直接连接方地址，`WebServer` 会自动提供
+    ///
+    /// # 错误
+    /// 代理地址格式错误时返回验证错误
+    pub fn apply<S>(&self, router: Router<S>) -> Result<Router<S>>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let trusted = self
+            .trusted_proxies
+            .iter()
+            .filter(|proxy| *proxy != UNIX_PROXY)
+            .map(|proxy| proxy.parse::<IpNet>())
+            .collect::<Result<Vec<_>>>()?;
+
+        let trusted = Arc::new(TrustedProxies {
+            networks: trusted,
+            unix: self.trusted_proxies.iter().any(|proxy| proxy == UNIX_PROXY),
+        });
+        Ok(
+            router.layer(middleware::from_fn(move |request: Request, next: Next| {
+                resolve_client(trusted.clone(), request, next)
+            })),
+        )
+    }
+}
+
+/// 表示 Unix 域套接字连接的代理地址
+const UNIX_PROXY: &str = "unix";
+
+/// 受信任的代理
+#[derive(Debug, Default)]
+struct TrustedProxies {
+    /// 受信任的地址段
+    networks: Vec<IpNet>,
+    /// 是否信任没有 TCP 地址的连接（Unix 域套接字）
+    unix: bool,
+}
+
+impl TrustedProxies {
+    /// 直接连接方是否受信任，`None` 表示 Unix 域套接字连接
+    fn trusts_peer(&self, peer: Option<IpAddr>) -> bool {
+        match peer {
+            Some(ip) => self.trusts(&ip),
+            None => self.unix,
+        }
+    }
+
+    /// 地址是否受信任
+    fn trusts(&self, ip: &IpAddr) -> bool {
+        self.networks.iter().any(|net| net.contains(ip))
+    }
+}
+
+/// 还原客户端信息并放入请求扩展
+async fn resolve_client(
+    trusted: Arc<TrustedProxies>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let peer = peer_ip(request.extensions());
+    let client = client_info(request.headers(), peer, &trusted);
+    request.extensions_mut().insert(client);
+    next.run(request).await
+}
+
+/// 根据转发请求头还原客户端信息
+///
+/// 从最近的一跳开始向前查找，跳过受信任的代理，第一个不受信任的地址即为客户端地址
+fn client_info(headers: &HeaderMap, peer: Option<IpAddr>, trusted: &TrustedProxies) -> ClientInfo {
+    let mut client = ClientInfo::direct(headers, peer);
+    if !trusted.trusts_peer(peer) {
+        return client;
+    }
+
+    let hops = forwarded_hops(headers);
+    for hop in hops.iter().rev() {
+        if let Some(scheme) = &hop.proto {
+            client.scheme = scheme.to_ascii_lowercase();
+        }
+        if let Some(host) = &hop.host {
+            client.host = Some(host.clone());
+        }
+        // 无法识别的地址（如 `unknown`）不再继续向前查找
+        client.ip = hop.ip;
+        if !hop.ip.is_some_and(|ip| trusted.trusts(&ip)) {
+            break;
+        }
+    }
+    client
+}
+
+/// 代理链中的一跳
+#[derive(Debug, Default)]
+struct Hop {
+    /// 该跳的来源地址
+    ip: Option<IpAddr>,
+    /// 该跳收到请求时的协议
+    proto: Option<String>,
+    /// 该跳收到请求时的主机名
+    host: Option<String>,
+}
+
+/// 解析代理链，`Forwarded` 优先于 `X-Forwarded-*`
+fn forwarded_hops(headers: &HeaderMap) -> Vec<Hop> {
+    let forwarded: Vec<&str> = header_values(headers, header::FORWARDED.as_str());
+    if !forwarded.is_empty() {
+        return forwarded
+            .iter()
+            .flat_map(|value| value.split(','))
+            .map(|element| {
+                let mut hop = Hop::default();
+                for pair in element.split(';') {
+                    let Some((key, value)) = pair.trim().split_once('=') else {
+                        continue;
+                    };
+                    let value = value.trim().trim_matches('"');
+                    match key.trim().to_ascii_lowercase().as_str() {
+                        "for" => hop.ip = parse_node(value),
+                        "proto" => hop.proto = Some(value.to_string()),
+                        "host" => hop.host = Some(value.to_string()),
+                        _ => {}
+                    }
+                }
+                hop
+            })
+            .collect();
+    }
+
+    let mut hops: Vec<Hop> = header_values(headers, "x-forwarded-for")
+        .iter()
+        .flat_map(|value| value.split(','))
+        .map(|node| Hop {
+            ip: parse_node(node.trim()),
+            ..Hop::default()
+        })
+        .collect();
+
+    // X-Forwarded-Proto/Host 由最近的代理设置，取最后一个值
+    if let Some(last) = hops.last_mut() {
+        let last_value = |name: &str| {
+            header_values(headers, name)
+                .iter()
+                .flat_map(|value| value.split(','))
+                .last()
+                .map(|value| value.trim().to_string())
+        };
+        last.proto = last_value("x-forwarded-proto");
+        last.host =
+            last_value("x-forwarded-host").map(|host| match last_value("x-forwarded-port") {
+                Some(port) if !host.contains(':') => format!("{}:{}", host, port),
+                _ => host,
+            });
+    }
+    hops
+}
+
+/// 解析节点地址，支持 `1.2.3.4`、`1.2.3.4:80`、`[::1]:80` 和 `::1`
+fn parse_node(node: &str) -> Option<IpAddr> {
+    if let Some(rest) = node.strip_prefix('[') {
+        return rest.split(']').next()?.parse().ok();
+    }
+    node.parse::<IpAddr>()
+        .ok()
+        .or_else(|| node.parse::<SocketAddr>().ok().map(|addr| addr.ip()))
+}
+
+/// 读取客户端地址，优先使用还原后的客户端信息，其次为直接连接方地址
+pub(crate) fn client_ip(extensions: &axum::http::Extensions) -> Option<IpAddr> {
+    match extensions.get::<ClientInfo>() {
+        Some(client) => client.ip,
+        None => peer_ip(extensions),
+    }
+}
+
+/// 读取直接连接方地址
+fn peer_ip(extensions: &axum::http::Extensions) -> Option<IpAddr> {
+    extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip())
+}
+
+/// 读取同名请求头的全部值
+fn header_values<'a>(headers: &'a HeaderMap, name: &str) -> Vec<&'a str> {
+    headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .collect()
+}
+
+/// 读取请求头的第一个值
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+/// IP 地址段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct IpNet {
+    /// 网络地址
+    addr: IpAddr,
+    /// 前缀长度
+    prefix: u8,
+}
+
+impl IpNet {
+    /// 是否包含指定地址，IPv4 映射的 IPv6 地址按 IPv4 处理
+    fn contains(&self, ip: &IpAddr) -> bool {
+        let ip = match ip {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(*ip),
+            IpAddr::V4(_) => *ip,
+        };
+        match (self.addr, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - u32::from(self.prefix))
+                    .unwrap_or(0);
+                u32::from(net) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - u32::from(self.prefix))
+                    .unwrap_or(0);
+                u128::from(net) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for IpNet {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::validation(format!("无效的代理地址: {}", s));
+        let (addr, prefix) = match s.trim().split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (s.trim(), None),
+        };
+        let addr: IpAddr = addr.parse().map_err(|_| invalid())?;
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(prefix) => prefix
+                .parse::<u8>()
+                .ok()
+                .filter(|prefix| *prefix <= max)
+                .ok_or_else(invalid)?,
+            None => max,
+        };
+        Ok(Self { addr, prefix })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 解析受信任代理列表
+    fn trusted(proxies: &[&str]) -> TrustedProxies {
+        TrustedProxies {
+            networks: proxies.iter().map(|proxy| proxy.parse().unwrap()).collect(),
+            unix: false,
+        }
+    }
+
+    /// 构造请求头
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(*name, value.parse().unwrap());
+        }
+        headers
+    }
+
+    /// 测试地址段匹配
+    #[test]
+    fn test_ip_net() {
+        let net: IpNet = "10.0.0.0/8".parse().unwrap();
+        assert!(net.contains(&"10.1.2.3".parse().unwrap()));
+        assert!(net.contains(&"::ffff:10.1.2.3".parse().unwrap()));
+        assert!(!net.contains(&"11.0.0.1".parse().unwrap()));
+
+        let net: IpNet = "fd00::/8".parse().unwrap();
+        assert!(net.contains(&"fd12::1".parse().unwrap()));
+        assert!("0.0.0.0/0"
+            .parse::<IpNet>()
+            .unwrap()
+            .contains(&"8.8.8.8".parse().unwrap()));
+        assert!("10.0.0.0/33".parse::<IpNet>().is_err());
+        assert!("proxy.local".parse::<IpNet>().is_err());
+    }
+
+    /// 测试从 X-Forwarded-* 请求头还原客户端信息
+    #[test]
+    fn test_x_forwarded() {
+        let trusted = trusted(&["10.0.0.0/8"]);
+        let headers = headers(&[
+            ("host", "backend:8080"),
+            ("x-forwarded-for", "203.0.113.7, 198.51.100.1, 10.0.0.2"),
+            ("x-forwarded-proto", "https"),
+            ("x-forwarded-host", "example.com"),
+        ]);
+
+        let client = client_info(&headers, Some("10.0.0.1".parse().unwrap()), &trusted);
+        // 198.51.100.1 不受信任，更前面的地址可能被伪造
+        assert_eq!(client.ip, Some("198.51.100.1".parse().unwrap()));
+        assert_eq!(client.base_url(), "https://example.com");
+
+        // 直接连接方不受信任时忽略转发请求头
+        let client = client_info(&headers, Some("198.51.100.9".parse().unwrap()), &trusted);
+        assert_eq!(client.ip, Some("198.51.100.9".parse().unwrap()));
+        assert_eq!(client.base_url(), "http://backend:8080");
+    }
+
+    /// 测试从 Forwarded 请求头还原客户端信息
+    #[test]
+    fn test_forwarded() {
+        let mut trusted = trusted(&["127.0.0.1", "10.0.0.0/8"]);
+        let headers = headers(&[(
+            "forwarded",
+            r#"for="[2001:db8::1]:4711";proto=https;host=api.example.com, for=10.0.0.5"#,
+        )]);
+
+        let client = client_info(&headers, Some("127.0.0.1".parse().unwrap()), &trusted);
+        assert_eq!(client.ip, Some("2001:db8::1".parse().unwrap()));
+        assert_eq!(client.scheme, "https");
+        assert_eq!(client.host.as_deref(), Some("api.example.com"));
+
+        // Unix 域套接字连接需显式信任
+        assert_eq!(client_info(&headers, None, &trusted).ip, None);
+        trusted.unix = true;
+        assert_eq!(client_info(&headers, None, &trusted), client);
+    }
+}
@@ -0,0 +1,132 @@
+//! HTTP 服务端指标模块
+//!
+//! 启用 `metrics` 特性后，控制器生成的路由通过 [`instrument`] 记录每个请求的指标：
+//! - `http.server.requests` - 请求数（计数器）
+//! - `http.server.requests.duration` - 处理耗时（直方图，秒）
+//!
+//! 两个指标带有相同的标签，便于在 Prometheus 中按标签聚合：
+//! - `method` - 请求方法，如 `GET`
+//! - `route` - 匹配的路由模板，如 `/api/users/:id`，不含实际的路径参数
+//! - `status` - 响应状态码，如 `404`
+//! - `status_class` - 状态码类别，如 `4xx`
+//!
+//! 未启用特性时 [`instrument`] 原样返回路由
+
+use axum::http::StatusCode;
+use axum::Router;
+
+/// 请求数指标
+pub const HTTP_REQUESTS_METRIC: &str = "http.server.requests";
+
+/// 处理耗时指标
+pub const HTTP_DURATION_METRIC: &str = "http.server.requests.duration";
+
+/// 为路由中已添加的所有路由记录请求指标
+///
+/// 只作用于调用前已添加的路由，没有路由时原样返回
+///
+/// # 示例
+/// ```rust
+/// let app = http_metrics::instrument(
+///     Router::new()
+///         .route("/api/orders", get(list_orders))
+///         .route("/api/orders/:id", get(get_order)),
+/// );
+/// ```
+pub fn instrument<S>(router: Router<S>) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    #[cfg(feature = "metrics")]
+    {
+        router.route_layer(axum::middleware::from_fn(record))
+    }
+    #[cfg(not(feature = "metrics"))]
+    {
+        router
+    }
+}
+
+/// 状态码类别，如 `"2xx"`
+pub fn status_class(status: StatusCode) -> &'static str {
+    match status.as_u16() {
+        100..=199 => "1xx",
+        200..=299 => "2xx",
+        300..=399 => "3xx",
+        400..=499 => "4xx",
+        _ => "5xx",
+    }
+}
+
+/// 记录请求数和处理耗时
+#[cfg(feature = "metrics")]
+async fn record(
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    use axum::extract::MatchedPath;
+    use rspring_core::metrics::{counter, histogram};
+
+    let method = request.method().to_string();
+    let route = request.extensions().get::<MatchedPath>().map_or_else(
+        || request.uri().path().to_string(),
+        |path| path.as_str().to_string(),
+    );
+    let start = std::time::Instant::now();
+    let response = next.run(request).await;
+
+    let status = response.status();
+    let labels = [
+        ("method", method),
+        ("route", route),
+        ("status", status.as_u16().to_string()),
+        ("status_class", status_class(status).to_string()),
+    ];
+    counter!(HTTP_REQUESTS_METRIC, &labels).increment(1);
+    histogram!(HTTP_DURATION_METRIC, &labels).record(start.elapsed().as_secs_f64());
+    response
+}
+
+#[cfg(all(test, feature = "prometheus"))]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::extract::{Path, Request};
+    use axum::routing::get;
+    use rspring_core::metrics::{build_prometheus_recorder, MetricsConfig};
+    use tower::ServiceExt;
+
+    /// 测试按路由模板和状态码记录指标
+    #[tokio::test]
+    async fn test_http_metrics() {
+        let recorder = build_prometheus_recorder(&MetricsConfig::default()).unwrap();
+        let handle = recorder.handle();
+        let _guard = ::metrics::set_default_local_recorder(&recorder);
+
+        let router: Router = instrument(Router::new().route(
+            "/users/:id",
+            get(|Path(id): Path<u32>| async move {
+                if id == 0 {
+                    Err(StatusCode::NOT_FOUND)
+                } else {
+                    Ok("user")
+                }
+            }),
+        ));
+        for uri in ["/users/1", "/users/2", "/users/0", "/missing"] {
+            let request = Request::get(uri).body(Body::empty()).unwrap();
+            router.clone().oneshot(request).await.unwrap();
+        }
+
+        let output = handle.render();
+        assert!(output.contains(
+            "http_server_requests{method=\"GET\",route=\"/users/:id\",status=\"200\",status_class=\"2xx\"} 2"
+        ));
+        assert!(output.contains(
+            "http_server_requests{method=\"GET\",route=\"/users/:id\",status=\"404\",status_class=\"4xx\"} 1"
+        ));
+        assert!(output
+            .contains("http_server_requests_duration_count{method=\"GET\",route=\"/users/:id\""));
+        assert!(!output.contains("/missing"));
+    }
+}
@@ -0,0 +1,314 @@
+//! 处理器拦截器模块
+//!
+//! 提供类似 Spring `HandlerInterceptor` 的拦截器抽象，无需了解 tower 即可编写中间件：
+//! - `pre_handle` 按顺序在处理器之前执行，可直接返回响应中断请求
+//! - `post_handle` 在处理器之后逆序执行，可修改响应
+//! - `after_completion` 在请求结束时逆序执行，仅针对 `pre_handle` 已通过的拦截器
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::async_trait;
+use axum::extract::Request;
+use axum::http::{HeaderMap, Method, StatusCode, Uri};
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use axum::Router;
+use rspring_core::Component;
+
+/// 请求信息
+///
+/// 在 `pre_handle` 之前采集，供 `post_handle` 和 `after_completion` 使用
+#[derive(Debug, Clone)]
+pub struct RequestInfo {
+    /// 请求方法
+    pub method: Method,
+    /// 请求地址
+    pub uri: Uri,
+    /// 请求头
+    pub headers: HeaderMap,
+    /// 请求开始时间
+    pub started_at: Instant,
+}
+
+impl RequestInfo {
+    /// 从请求中采集信息
+    pub fn from_request(request: &Request) -> Self {
+        Self {
+            method: request.method().clone(),
+            uri: request.uri().clone(),
+            headers: request.headers().clone(),
+            started_at: Instant::now(),
+        }
+    }
+
+    /// 获取请求已耗费的时间
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+}
+
+/// 处理器拦截器特征
+///
+/// # 示例
+/// ```rust
+/// #[derive(Component)]
+/// pub struct AuthInterceptor;
+///
+/// #[async_trait]
+/// impl HandlerInterceptor for AuthInterceptor {
+///     fn order(&self) -> i32 {
+///         -100
+///     }
+///
+///     async fn pre_handle(&self, request: &mut Request) -> std::result::Result<(), Response> {
+///         if request.headers().contains_key("Authorization") {
+///             Ok(())
+///         } else {
+///             Err(WebError::from(Error::Unauthorized).into_response())
+///         }
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait HandlerInterceptor: Component + 'static {
+    /// 拦截器顺序，数值越小越先执行
+    ///
+    /// # 默认值
+    /// `0`，顺序相同时按注册顺序执行
+    fn order(&self) -> i32 {
+        0
+    }
+
+    /// 处理器执行前调用
+    ///
+    /// # 返回值
+    /// * `Ok(())` - 继续执行后续拦截器和处理器
+    /// * `Err(Response)` - 中断请求，直接返回该响应
+    async fn pre_handle(&self, _request: &mut Request) -> std::result::Result<(), Response> {
+        Ok(())
+    }
+
+    /// 处理器执行后调用，请求被中断时不会调用
+    async fn post_handle(&self, _request: &RequestInfo, _response: &mut Response) {}
+
+    /// 请求完成后调用，无论请求是否被中断
+    ///
+    /// # 参数
+    /// * `request` - 请求信息
+    /// * `status` - 最终响应的状态码
+    async fn after_completion(&self, _request: &RequestInfo, _status: StatusCode) {}
+}
+
+/// 拦截器注册表
+///
+/// 作为单例组件注册到容器中，按 `order` 和注册顺序维护拦截器链
+///
+/// # 示例
+/// ```rust
+/// let mut interceptors = InterceptorRegistry::new();
+/// interceptors.add(AuthInterceptor).add(TimingInterceptor);
+///
+/// let app = interceptors.apply(Router::new().route("/users", get(list_users)));
+/// ```
+#[derive(Default, Clone)]
+pub struct InterceptorRegistry {
+    /// 已排序的拦截器链
+    interceptors: Vec<Arc<dyn HandlerInterceptor>>,
+}
+
+impl InterceptorRegistry {
+    /// 创建空的拦截器注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册拦截器
+    pub fn add<I: HandlerInterceptor>(&mut self, interceptor: I) -> &mut Self {
+        self.add_shared(Arc::new(interceptor))
+    }
+
+    /// 注册共享的拦截器实例
+    pub fn add_shared(&mut self, interceptor: Arc<dyn HandlerInterceptor>) -> &mut Self {
+        self.interceptors.push(interceptor);
+        // 稳定排序，顺序相同时保持注册顺序
+        self.interceptors.sort_by_key(|interceptor| interceptor.order());
+        self
+    }
+
+    /// 获取拦截器数量
+    pub fn len(&self) -> usize {
+        self.interceptors.len()
+    }
+
+    /// 是否没有注册拦截器
+    pub fn is_empty(&self) -> bool {
+        self.interceptors.is_empty()
+    }
+
+    /// 获取按执行顺序排列的拦截器名称
+    pub fn names(&self) -> Vec<&'static str> {
+        self.interceptors
+            .iter()
+            .map(|interceptor| interceptor.component_name())
+            .collect()
+    }
+
+    /// 将拦截器链应用到路由
+    pub fn apply<S>(&self, router: Router<S>) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        if self.is_empty() {
+            return router;
+        }
+
+        let chain: Arc<[Arc<dyn HandlerInterceptor>]> = self.interceptors.clone().into();
+        router.layer(middleware::from_fn(move |request: Request, next: Next| {
+            run_chain(chain.clone(), request, next)
+        }))
+    }
+}
+
+impl Component for InterceptorRegistry {
+    fn component_name(&self) -> &'static str {
+        "InterceptorRegistry"
+    }
+}
+
+/// 执行拦截器链
+async fn run_chain(
+    chain: Arc<[Arc<dyn HandlerInterceptor>]>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let info = RequestInfo::from_request(&request);
+
+    // 记录 pre_handle 已通过的拦截器数量
+    let mut passed = 0;
+    let mut rejected = None;
+    for interceptor in chain.iter() {
+        if let Err(response) = interceptor.pre_handle(&mut request).await {
+            rejected = Some(response);
+            break;
+        }
+        passed += 1;
+    }
+
+    let response = match rejected {
+        Some(response) => response,
+        None => {
+            let mut response = next.run(request).await;
+            for interceptor in chain[..passed].iter().rev() {
+                interceptor.post_handle(&info, &mut response).await;
+            }
+            response
+        }
+    };
+
+    let status = response.status();
+    for interceptor in chain[..passed].iter().rev() {
+        interceptor.after_completion(&info, status).await;
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::response::IntoResponse;
+    use axum::routing::get;
+    use std::sync::Mutex;
+    use tower::ServiceExt;
+
+    struct Ordered(&'static str, i32);
+
+    impl Component for Ordered {
+        fn component_name(&self) -> &'static str {
+            self.0
+        }
+    }
+
+    #[async_trait]
+    impl HandlerInterceptor for Ordered {
+        fn order(&self) -> i32 {
+            self.1
+        }
+    }
+
+    /// 测试拦截器按 order 和注册顺序排列
+    #[test]
+    fn test_interceptor_order() {
+        let mut registry = InterceptorRegistry::new();
+        registry
+            .add(Ordered("logging", 0))
+            .add(Ordered("auth", -10))
+            .add(Ordered("metrics", 0))
+            .add(Ordered("cache", 10));
+
+        assert_eq!(registry.len(), 4);
+        assert_eq!(registry.names(), vec!["auth", "logging", "metrics", "cache"]);
+    }
+
+    struct Recording {
+        name: &'static str,
+        reject: bool,
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Component for Recording {
+        fn component_name(&self) -> &'static str {
+            self.name
+        }
+    }
+
+    #[async_trait]
+    impl HandlerInterceptor for Recording {
+        async fn pre_handle(&self, _request: &mut Request) -> std::result::Result<(), Response> {
+            self.events.lock().unwrap().push(format!("pre:{}", self.name));
+            if self.reject {
+                Err(StatusCode::FORBIDDEN.into_response())
+            } else {
+                Ok(())
+            }
+        }
+
+        async fn post_handle(&self, _request: &RequestInfo, _response: &mut Response) {
+            self.events.lock().unwrap().push(format!("post:{}", self.name));
+        }
+
+        async fn after_completion(&self, _request: &RequestInfo, status: StatusCode) {
+            self.events.lock().unwrap().push(format!("after:{}:{}", self.name, status.as_u16()));
+        }
+    }
+
+    /// 测试拦截器链的执行顺序与中断
+    #[tokio::test]
+    async fn test_interceptor_chain() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recording = |name, reject| Recording { name, reject, events: events.clone() };
+
+        let mut registry = InterceptorRegistry::new();
+        registry.add(recording("a", false)).add(recording("b", false));
+        let app = registry.apply(Router::new().route("/", get(|| async { "ok" })));
+
+        let response = app.oneshot(Request::new(Body::empty())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec!["pre:a", "pre:b", "post:b", "post:a", "after:b:200", "after:a:200"]
+        );
+
+        // 被中断时不执行处理器和 post_handle，只回调已通过的拦截器
+        events.lock().unwrap().clear();
+        let mut registry = InterceptorRegistry::new();
+        registry.add(recording("a", false)).add(recording("b", true));
+        let app = registry.apply(Router::new().route("/", get(|| async { "ok" })));
+
+        let response = app.oneshot(Request::new(Body::empty())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(*events.lock().unwrap(), vec!["pre:a", "pre:b", "after:a:403"]);
+    }
+}
@@ -1,14 +1,95 @@
+pub mod access_log;
+pub mod actuator;
+#[cfg(feature = "audit-webhook")]
+pub mod audit;
+#[cfg(feature = "http-client")]
+pub mod client;
 pub mod controller;
+pub mod cookies;
+pub mod cors;
+pub mod download;
+pub mod error_reporting;
+pub mod exception;
+pub mod extract;
+pub mod fallback;
+pub mod forwarded;
+pub mod http_metrics;
+pub mod interceptor;
+pub mod loggers;
 pub mod macros;
+pub mod openapi;
+pub mod pageable;
+pub mod problem;
+pub mod properties;
+#[cfg(feature = "prometheus")]
+pub mod prometheus;
+pub mod rate_limit;
+pub mod request_id;
 pub mod response;
+pub mod security;
+pub mod server;
+pub mod slow_request;
+pub mod static_files;
+pub mod swagger;
+pub mod timeout;
+pub mod trace_context;
+pub mod validation;
+#[cfg(any(feature = "audit-webhook", feature = "error-webhook"))]
+mod webhook;
+#[cfg(feature = "websocket")]
+pub mod websocket;
 
 // Re-export core functionality
 pub use rspring_core::*;
 
 // Re-export Web-specific types
+pub use access_log::{AccessLogConfig, AccessLogFormat};
+pub use actuator::{Actuator, ActuatorConfig, BuildInfo, EnvEndpointConfig, InfoContributor};
+#[cfg(feature = "audit-webhook")]
+pub use audit::{WebhookAuditConfig, WebhookAuditSink};
+#[cfg(feature = "http-client")]
+pub use client::{HttpClient, HttpRequest};
 pub use controller::*;
+pub use cookies::{Cookie, CookieConfig, CookieProtection, CookieValue, Cookies, SameSite};
+pub use cors::CorsConfig;
+pub use download::{FileResponse, StreamResponse};
+#[cfg(feature = "error-webhook")]
+pub use error_reporting::{WebhookErrorConfig, WebhookErrorReporter};
+pub use exception::*;
+pub use extract::{
+    FormBody, JsonBody, NegotiatedBody, ParameterRejection, ParameterSource, PathVariables, RequestHeaders,
+    RequestParams, FORM_URLENCODED,
+};
+pub use fallback::{DefaultFallbackHandler, FallbackConfig, FallbackHandler};
+pub use forwarded::{ClientInfo, ProxyConfig};
+pub use interceptor::*;
+pub use loggers::loggers_router;
 pub use macros::*;
+pub use openapi::{ApiSchema, OpenApi, OperationInfo, ParameterInfo, ParameterLocation, OPENAPI_PATH};
+pub use pageable::{Direction, Order, Pageable, PageableConfig, Sort};
+pub use problem::{ErrorFormat, ProblemDetail};
+pub use properties::*;
+#[cfg(feature = "prometheus")]
+pub use prometheus::prometheus_router;
+pub use rate_limit::{RateLimitConfig, RoleRateLimit};
+pub use request_id::{current_request_id, current_request_path, with_request_id, RequestId, RequestIdConfig};
 pub use response::*;
+pub use security::{ApiKeyStore, AuthMode, AuthPrincipal, CurrentUser, RouteGroupConfig, SecurityConfig};
+#[cfg(feature = "login")]
+pub use security::{FormLogin, LoginConfig, UserDetailsService};
+#[cfg(feature = "oauth2")]
+pub use security::{OAuth2Config, OidcProvider};
+pub use server::WebServer;
+pub use slow_request::SlowRequestConfig;
+pub use static_files::StaticConfig;
+pub use swagger::docs_page;
+pub use timeout::{RouteTimeout, TimeoutConfig};
+pub use trace_context::TraceContextConfig;
+pub use validation::*;
+#[cfg(feature = "websocket")]
+pub use websocket::{BrokerEnvelope, BrokerRelay, MessageBroker, MessageContext};
+#[cfg(feature = "websocket-redis")]
+pub use websocket::RedisBrokerRelay;
 
 // Re-export axum types for convenience
 pub use axum::{
@@ -19,6 +100,12 @@ pub use axum::{
     Json, Router,
 };
 
+// Re-export serde_json for generated schema code
+pub use serde_json;
+
+// Re-export validator types
+pub use validator::Validate;
+
 // Re-export tower types
 pub use tower::ServiceBuilder;
 pub use tower_http::cors::CorsLayer;
\ No newline at end of file
@@ -0,0 +1,121 @@
+//! 日志级别管理端点模块
+//!
+//! 提供查看和修改日志级别的管理端点，修改立即生效：
+//! - `GET {path}` 返回根级别和按模块的级别
+//! - `PUT {path}/{target}` 设置模块的级别，请求体为 `{"level": "debug"}`，`level` 为 `null` 时清除
+//!
+//! 端点可以修改应用的日志输出，应通过 `SecurityConfig` 的受保护路径限制访问。
+//! 每次修改记录动作为 `loggers.update` 的审计事件
+
+use std::collections::BTreeMap;
+
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::routing::{get, put};
+use axum::{Json, Router};
+use rspring_core::auditing::{audit, AuditEvent};
+use rspring_core::logging::{clear_log_level, log_levels, set_log_level};
+use serde::Deserialize;
+
+use crate::exception::WebResult;
+
+/// 修改日志级别的请求体
+#[derive(Debug, Clone, Deserialize)]
+pub struct LogLevelRequest {
+    /// 新的级别，为空时清除模块的级别
+    pub level: Option<String>,
+}
+
+/// 创建日志级别管理端点的路由
+///
+/// # 示例
+/// ```rust
+/// let app = Router::new()
+///     .merge(user_routes)
+///     .merge(loggers_router("/admin/loggers"));
+///
+/// // curl -X PUT /admin/loggers/my_app::repository -d '{"level": "debug"}'
+/// ```
+pub fn loggers_router<S>(path: &str) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let path = path.trim_end_matches('/');
+    Router::new()
+        .route(path, get(list_levels))
+        .route(&format!("{}/:target", path), put(change_level))
+}
+
+/// 返回当前的日志级别
+async fn list_levels() -> Json<BTreeMap<String, String>> {
+    Json(log_levels())
+}
+
+/// 设置或清除模块的日志级别
+async fn change_level(Path(target): Path<String>, Json(request): Json<LogLevelRequest>) -> WebResult<StatusCode> {
+    match &request.level {
+        Some(level) => set_log_level(&target, level)?,
+        None => clear_log_level(&target)?,
+    }
+    tracing::info!("日志级别已修改: {} = {}", target, log_levels().get(&target).map_or("-", String::as_str));
+    audit(
+        AuditEvent::new("loggers.update")
+            .resource(target)
+            .metadata("level", request.level),
+    )
+    .await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::extract::Request;
+    use axum::http::header;
+    use rspring_core::auditing::{audit_recorder, InMemoryAuditSink};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    /// 测试修改和查看日志级别，修改记录审计事件
+    #[tokio::test]
+    async fn test_loggers_router() {
+        let audits = Arc::new(InMemoryAuditSink::new());
+        audit_recorder().add_sink(audits.clone());
+        let router: Router = loggers_router("/admin/loggers/");
+        let change = |target: &str, body: &str| {
+            let request = Request::put(format!("/admin/loggers/{}", target))
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            router.clone().oneshot(request)
+        };
+
+        let response = change("my_app::repo", r#"{"level": "DEBUG"}"#).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let response = change("my_app::repo", r#"{"level": "loud"}"#).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let response = change("my_app,hyper=trace", r#"{"level": "info"}"#).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let list = || router.clone().oneshot(Request::get("/admin/loggers").body(Body::empty()).unwrap());
+        let body = axum::body::to_bytes(list().await.unwrap().into_body(), usize::MAX).await.unwrap();
+        let levels: BTreeMap<String, String> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(levels["my_app::repo"], "debug");
+        assert!(levels.contains_key("root"));
+
+        change("my_app::repo", r#"{"level": null}"#).await.unwrap();
+        let body = axum::body::to_bytes(list().await.unwrap().into_body(), usize::MAX).await.unwrap();
+        let levels: BTreeMap<String, String> = serde_json::from_slice(&body).unwrap();
+        assert!(!levels.contains_key("my_app::repo"));
+
+        audit_recorder().flush().await;
+        let updates: Vec<_> = audits
+            .events()
+            .into_iter()
+            .filter(|event| event.action == "loggers.update" && event.resource.as_deref() == Some("my_app::repo"))
+            .map(|event| event.metadata["level"].clone())
+            .collect();
+        assert_eq!(updates, vec![serde_json::json!("DEBUG"), serde_json::Value::Null]);
+    }
+}
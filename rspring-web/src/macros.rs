@@ -1,6 +1,10 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput, ItemStruct};
+use syn::ext::IdentExt;
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Expr, Fields, FnArg, ImplItem, Item,
+    ItemFn, ItemImpl, LitStr, Pat, ReturnType, Type,
+};
 
 /// REST 控制器注解
 /// 
@@ -29,7 +33,7 @@ pub fn rest_controller_derive(input: TokenStream) -> TokenStream {
             }
         }
         
-        impl crate::Controller for #name {
+        impl rspring_web::Controller for #name {
             fn base_path(&self) -> &'static str {
                 // TODO: 从 RequestMapping 属性中提取路径
                 "/"
@@ -42,29 +46,755 @@ pub fn rest_controller_derive(input: TokenStream) -> TokenStream {
 
 /// 请求映射注解（用作属性）
 /// 
-/// 定义控制器的基础路由路径
+/// 定义控制器的基础路由路径。标注在 impl 块上时，会收集其中
+/// `#[GetMapping]`/`#[PostMapping]` 等方法的元数据并实现 `RestRoutes`，
+/// 用于生成路由和 OpenAPI 文档。方法参数按注解提取：
+/// - `#[PathVariable] id: u64` 读取路径变量
+/// - `#[RequestParam(default = 10)] limit: u32` 读取查询参数，`Option<T>` 表示可选
+/// - `#[RequestHeader("X-Token")] token: String` 读取请求头
+/// - `#[RequestBody] dto: CreateUser` 按 `Content-Type` 解析 JSON 或表单请求体
+/// - `#[Form] form: LoginForm` 只接受表单请求体
+/// - `#[Valid] dto: CreateUser` 解析 JSON 或表单请求体后执行校验
+/// - 未标注的参数直接作为 axum 提取器使用
+///
+/// 参数缺失或格式错误时返回 400。
+///
+/// 设置 `wrap_response` 后，返回普通值的方法会自动包装为 `ApiResponse<T>`，
+/// 返回 `Result<T, E>` 时包装 `Ok` 中的值；已经返回 `ApiResponse`、`impl Trait`
+/// 或标注了 `#[RawResponse]` 的方法保持原样
 /// 
 /// # 示例
 /// 
 /// ```rust
 /// #[derive(RestController)]
-/// #[RequestMapping("/api/users")]
 /// pub struct UserController;
+///
+/// #[RequestMapping("/api/users")]
+/// impl UserController {
+///     /// 根据ID查询用户
+///     #[GetMapping("/{id}")]
+///     pub async fn get_user(&self, #[PathVariable] id: u64) -> WebResult<ApiResponse<User>> {
+///         // 处理逻辑
+///     }
+///
+///     #[GetMapping]
+///     pub async fn list_users(&self, #[RequestParam(default = 10)] limit: u32) -> WebResult<ApiResponse<Vec<User>>> {
+///         // 处理逻辑
+///     }
+/// }
+///
+/// let app = Arc::new(UserController).router();
+/// ```
+///
+/// 自动包装返回值：
+///
+/// ```rust
+/// #[RequestMapping("/api/orders", wrap_response)]
+/// impl OrderController {
+///     #[GetMapping("/{id}")]
+///     pub async fn get_order(&self, #[PathVariable] id: u64) -> WebResult<Order> {
+///         // 响应体为 {"code": 200, "message": "success", "data": {...}}
+///     }
+///
+///     #[GetMapping("/{id}/invoice")]
+///     #[RawResponse]
+///     pub async fn download_invoice(&self, #[PathVariable] id: u64) -> Response {
+///         // 原样返回
+///     }
+/// }
 /// ```
 #[proc_macro_attribute]
 pub fn RequestMapping(args: TokenStream, input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as ItemStruct);
-    
-    // TODO: 解析路径参数并存储到结构体中
-    // 现在简单地保持原结构体不变
-    
+    let args = parse_macro_input!(args as MappingArgs);
+
+    match parse_macro_input!(input as Item) {
+        Item::Impl(item_impl) => expand_request_mapping_impl(&args, item_impl),
+        // 用于结构体等其他位置时保持原样
+        other => TokenStream::from(quote! { #other }),
+    }
+}
+
+/// 请求映射注解的参数
+#[derive(Default)]
+struct MappingArgs {
+    /// 基础路径
+    path: String,
+    /// 是否将返回值自动包装为 `ApiResponse`
+    wrap_response: bool,
+}
+
+impl syn::parse::Parse for MappingArgs {
+    /// 解析 `"/path"`、`"/path", wrap_response` 或 `path = "/path", wrap_response = true`
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut args = Self::default();
+        if input.peek(LitStr) {
+            args.path = input.parse::<LitStr>()?.value();
+            if !input.is_empty() {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+
+        while !input.is_empty() {
+            let name = input.call(syn::Ident::parse_any)?;
+            match name.to_string().as_str() {
+                "path" => {
+                    input.parse::<syn::Token![=]>()?;
+                    args.path = input.parse::<LitStr>()?.value();
+                }
+                "wrap_response" => {
+                    args.wrap_response = if input.peek(syn::Token![=]) {
+                        input.parse::<syn::Token![=]>()?;
+                        input.parse::<syn::LitBool>()?.value
+                    } else {
+                        true
+                    };
+                }
+                _ => return Err(syn::Error::new(name.span(), "未知的 RequestMapping 参数，可选值: path, wrap_response")),
+            }
+            if !input.is_empty() {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+/// 方法参数的提取方式
+enum ParamKind {
+    /// `#[PathVariable]`
+    Path { name: String },
+    /// `#[RequestParam]`，可带默认值
+    Query { name: String, default: Option<Expr> },
+    /// `#[RequestHeader]`
+    Header { name: String },
+    /// `#[RequestBody]`
+    Body,
+    /// `#[Form]`
+    Form,
+    /// `#[Valid]`
+    Valid,
+    /// 未标注，直接作为 axum 提取器使用
+    Extractor,
+}
+
+impl ParamKind {
+    /// 根据参数注解确定提取方式，注解参数格式错误时返回错误
+    fn of(pat_type: &syn::PatType) -> syn::Result<Self> {
+        let mut kind = Self::Extractor;
+        for attr in &pat_type.attrs {
+            let attr_kind = attr_name(attr);
+            kind = match attr_kind.as_str() {
+                "PathVariable" | "RequestParam" | "RequestHeader" => {
+                    let (name, default) = param_args(attr)?;
+                    let name = name.unwrap_or_else(|| pat_name(&pat_type.pat));
+                    match attr_kind.as_str() {
+                        "PathVariable" => Self::Path { name },
+                        "RequestParam" => Self::Query { name, default },
+                        _ => Self::Header { name },
+                    }
+                }
+                "RequestBody" if !matches!(kind, Self::Valid) => Self::Body,
+                "Form" if !matches!(kind, Self::Valid) => Self::Form,
+                "Valid" => Self::Valid,
+                _ => continue,
+            };
+        }
+        Ok(kind)
+    }
+}
+
+/// 解析参数注解的参数，返回参数名称和默认值
+///
+/// 支持 `#[RequestParam("name")]` 和 `#[RequestParam(name = "name", default = 10)]` 两种写法，
+/// 参数格式错误时返回错误
+fn param_args(attr: &Attribute) -> syn::Result<(Option<String>, Option<Expr>)> {
+    if let syn::Meta::Path(_) = attr.meta {
+        return Ok((None, None));
+    }
+    if let Ok(name) = attr.parse_args::<LitStr>() {
+        return Ok((Some(name.value()), None));
+    }
+
+    let mut name = None;
+    let mut default = None;
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("name") || meta.path.is_ident("value") {
+            name = Some(meta.value()?.parse::<LitStr>()?.value());
+        } else if meta.path.is_ident("default") {
+            default = Some(meta.value()?.parse::<Expr>()?);
+        } else {
+            return Err(meta.error("未知的参数，可选值: name, default"));
+        }
+        Ok(())
+    })?;
+    Ok((name, default))
+}
+
+/// 生成从参数来源读取单个参数的代码
+fn read_param(source: &proc_macro2::TokenStream, ty: &Type, name: &str, default: Option<&Expr>) -> proc_macro2::TokenStream {
+    if let Some(inner) = generic_arg_of(ty, &["Option"]) {
+        return quote! { rspring_web::ParameterSource::optional::<#inner>(&#source, #name) };
+    }
+    match default {
+        // 字符串默认值按参数类型解析，便于为数字、枚举等类型提供默认值
+        Some(Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(default), .. })) => {
+            quote! { rspring_web::ParameterSource::or_parse::<#ty>(&#source, #name, #default) }
+        }
+        Some(default) => quote! { rspring_web::ParameterSource::or_else::<#ty, _>(&#source, #name, || #default) },
+        None => quote! { rspring_web::ParameterSource::required::<#ty>(&#source, #name) },
+    }
+}
+
+/// 展开标注在 impl 块上的请求映射注解
+fn expand_request_mapping_impl(args: &MappingArgs, mut item_impl: ItemImpl) -> TokenStream {
+    let base_path = args.path.as_str();
+    let self_ty = &item_impl.self_ty;
+    let tag = quote!(#self_ty).to_string().replace(' ', "");
+
+    let mut operations = Vec::new();
+    let mut routes = Vec::new();
+    for impl_item in &item_impl.items {
+        let ImplItem::Fn(method) = impl_item else {
+            continue;
+        };
+        let Some((http_method, path)) = mapping_of(&method.attrs) else {
+            continue;
+        };
+
+        let operation_id = method.sig.ident.unraw().to_string();
+        let summary = match doc_of(&method.attrs).and_then(|doc| doc.lines().next().map(str::to_string)) {
+            Some(summary) => quote! { Some(#summary.to_string()) },
+            None => quote! { None },
+        };
+
+        // 根据参数注解收集参数元数据，并生成路由处理函数的提取逻辑
+        let mut parameters = Vec::new();
+        let mut request_body = quote! { None };
+        let mut request_content_types = quote! { Vec::new() };
+        let (mut uses_path, mut uses_query, mut uses_headers) = (false, false, false);
+        let mut extractors = Vec::new();
+        let mut body_extractor = None;
+        let mut bindings = Vec::new();
+        let mut call_args = Vec::new();
+        for (index, arg) in method.sig.inputs.iter().enumerate() {
+            let FnArg::Typed(pat_type) = arg else {
+                continue;
+            };
+            let ty = &pat_type.ty;
+            let arg_ident = quote::format_ident!("__arg{}", index);
+
+            let kind = match ParamKind::of(pat_type) {
+                Ok(kind) => kind,
+                Err(error) => return error.to_compile_error().into(),
+            };
+            let (source, location, name, default) = match kind {
+                ParamKind::Path { name } => {
+                    uses_path = true;
+                    (quote! { __path }, quote! { rspring_web::ParameterLocation::Path }, name, None)
+                }
+                ParamKind::Query { name, default } => {
+                    uses_query = true;
+                    (quote! { __query }, quote! { rspring_web::ParameterLocation::Query }, name, default)
+                }
+                ParamKind::Header { name } => {
+                    uses_headers = true;
+                    (quote! { __headers }, quote! { rspring_web::ParameterLocation::Header }, name, None)
+                }
+                ParamKind::Body => {
+                    request_body = quote! { Some(<#ty as rspring_web::ApiSchema>::schema()) };
+                    request_content_types = quote! { vec!["application/json", rspring_web::FORM_URLENCODED] };
+                    body_extractor = Some(quote! { #arg_ident: rspring_web::NegotiatedBody<#ty> });
+                    call_args.push(quote! { #arg_ident.0 });
+                    continue;
+                }
+                ParamKind::Form => {
+                    request_body = quote! { Some(<#ty as rspring_web::ApiSchema>::schema()) };
+                    request_content_types = quote! { vec![rspring_web::FORM_URLENCODED] };
+                    body_extractor = Some(quote! { #arg_ident: rspring_web::FormBody<#ty> });
+                    call_args.push(quote! { #arg_ident.0 });
+                    continue;
+                }
+                ParamKind::Valid => {
+                    request_body = quote! { Some(<#ty as rspring_web::ApiSchema>::schema()) };
+                    request_content_types = quote! { vec!["application/json", rspring_web::FORM_URLENCODED] };
+                    body_extractor = Some(quote! { #arg_ident: rspring_web::Valid<#ty> });
+                    call_args.push(quote! { #arg_ident });
+                    continue;
+                }
+                ParamKind::Extractor => {
+                    extractors.push(quote! { #arg_ident: #ty });
+                    call_args.push(quote! { #arg_ident });
+                    continue;
+                }
+            };
+
+            let required = default.is_none();
+            parameters.push(quote! {
+                {
+                    let mut parameter = rspring_web::ParameterInfo::of::<#ty>(#name, #location);
+                    parameter.required &= #required;
+                    parameter
+                }
+            });
+
+            let read = read_param(&source, ty, &name, default.as_ref());
+            bindings.push(quote! {
+                let #arg_ident = match #read {
+                    Ok(value) => value,
+                    Err(rejection) => return rspring_web::IntoResponse::into_response(rejection),
+                };
+            });
+            call_args.push(quote! { #arg_ident });
+        }
+
+        let wrap = if args.wrap_response && !has_attr(&method.attrs, "RawResponse") {
+            response_wrap_of(&method.sig.output)
+        } else {
+            ResponseWrap::None
+        };
+
+        // `impl Trait` 返回值无法描述，不生成响应 Schema
+        let response = match (&wrap, &method.sig.output) {
+            (ResponseWrap::Value(ty) | ResponseWrap::Result(ty), _) => {
+                quote! { Some(<rspring_web::ApiResponse<#ty> as rspring_web::ApiSchema>::schema()) }
+            }
+            (ResponseWrap::None, ReturnType::Type(_, ty)) if !matches!(**ty, Type::ImplTrait(_)) => {
+                quote! { Some(<#ty as rspring_web::ApiSchema>::schema()) }
+            }
+            _ => quote! { None },
+        };
+
+        operations.push(quote! {
+            rspring_web::OperationInfo {
+                method: #http_method,
+                path: rspring_web::openapi::join_path(#base_path, #path),
+                operation_id: #operation_id.to_string(),
+                tag: #tag.to_string(),
+                summary: #summary,
+                parameters: vec![#(#parameters),*],
+                request_body: #request_body,
+                request_content_types: #request_content_types,
+                response: #response,
+            }
+        });
+
+        // 读取请求头部的提取器在前，消费请求体的提取器必须放在最后
+        let mut handler_args = Vec::new();
+        if uses_path {
+            handler_args.push(quote! { __path: rspring_web::PathVariables });
+        }
+        if uses_query {
+            handler_args.push(quote! { __query: rspring_web::RequestParams });
+        }
+        if uses_headers {
+            handler_args.push(quote! { __headers: rspring_web::RequestHeaders });
+        }
+        handler_args.extend(extractors);
+        handler_args.extend(body_extractor);
+
+        let ident = &method.sig.ident;
+        let has_receiver = method.sig.receiver().is_some();
+        let call = if has_receiver {
+            quote! { __controller.#ident(#(#call_args),*) }
+        } else {
+            quote! { Self::#ident(#(#call_args),*) }
+        };
+        let call = if method.sig.asyncness.is_some() {
+            quote! { #call.await }
+        } else {
+            call
+        };
+        let call = match wrap {
+            ResponseWrap::Value(_) => quote! { rspring_web::response::wrap(#call) },
+            ResponseWrap::Result(_) => quote! { rspring_web::response::wrap_result(#call) },
+            ResponseWrap::None => quote! { rspring_web::IntoResponse::into_response(#call) },
+        };
+        let capture = has_receiver.then(|| quote! { let __controller = ::std::sync::Arc::clone(&self); });
+        let routing = quote::format_ident!("{}", http_method);
+
+        routes.push(quote! {
+            .route(
+                &rspring_web::route_path(&rspring_web::openapi::join_path(#base_path, #path)),
+                rspring_web::#routing({
+                    #capture
+                    move |#(#handler_args),*| async move {
+                        #(#bindings)*
+                        #call
+                    }
+                }),
+            )
+        });
+    }
+
+    // 参数注解已转换为提取逻辑，收集后移除（`#[Valid]` 交由请求映射注解改写）
+    for impl_item in item_impl.items.iter_mut() {
+        let ImplItem::Fn(method) = impl_item else {
+            continue;
+        };
+        method.attrs.retain(|attr| attr_name(attr) != "RawResponse");
+        for arg in method.sig.inputs.iter_mut() {
+            if let FnArg::Typed(pat_type) = arg {
+                pat_type.attrs.retain(|attr| {
+                    !matches!(
+                        attr_name(attr).as_str(),
+                        "PathVariable" | "RequestParam" | "RequestHeader" | "RequestBody" | "Form"
+                    )
+                });
+            }
+        }
+    }
+
+    // 没有路由时不能添加路由层
+    let router = if routes.is_empty() {
+        quote! { rspring_web::Router::new() }
+    } else {
+        quote! {
+            rspring_web::http_metrics::instrument(rspring_web::error_reporting::instrument(
+                rspring_web::Router::new() #(#routes)*
+            ))
+        }
+    };
+
+    let self_ty = &item_impl.self_ty;
+    let (impl_generics, _, where_clause) = item_impl.generics.split_for_impl();
     let expanded = quote! {
-        #input
+        #item_impl
+
+        impl #impl_generics rspring_web::RestRoutes for #self_ty #where_clause {
+            fn base_path() -> &'static str {
+                #base_path
+            }
+
+            fn operations() -> Vec<rspring_web::OperationInfo> {
+                vec![#(#operations),*]
+            }
+
+            fn router(self: ::std::sync::Arc<Self>) -> rspring_web::Router {
+                #router
+            }
+        }
     };
 
     TokenStream::from(expanded)
 }
 
+/// 返回值的包装方式
+enum ResponseWrap {
+    /// 不包装
+    None,
+    /// 包装普通返回值，携带数据类型
+    Value(Type),
+    /// 包装 `Result` 中的 `Ok` 值，携带数据类型
+    Result(Type),
+}
+
+/// 根据返回值类型确定包装方式
+///
+/// 已经是 `ApiResponse` 或 `impl Trait` 的返回值不包装
+fn response_wrap_of(output: &ReturnType) -> ResponseWrap {
+    let ty = match output {
+        ReturnType::Default => return ResponseWrap::Value(parse_quote!(())),
+        ReturnType::Type(_, ty) => &**ty,
+    };
+    if matches!(ty, Type::ImplTrait(_)) || last_segment_is(ty, "ApiResponse") {
+        return ResponseWrap::None;
+    }
+
+    match generic_arg_of(ty, &["Result", "WebResult"]) {
+        Some(ok) if last_segment_is(ok, "ApiResponse") => ResponseWrap::None,
+        Some(ok) => ResponseWrap::Result(ok.clone()),
+        None => ResponseWrap::Value(ty.clone()),
+    }
+}
+
+/// 判断类型路径的最后一段是否为指定名称
+fn last_segment_is(ty: &Type, name: &str) -> bool {
+    matches!(ty, Type::Path(type_path) if type_path.path.segments.last().is_some_and(|segment| segment.ident == name))
+}
+
+/// 获取指定泛型类型的第一个类型参数，如 `Option<T>` 中的 `T`
+fn generic_arg_of<'a>(ty: &'a Type, names: &[&str]) -> Option<&'a Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if !names.iter().any(|name| segment.ident == name) {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+            syn::GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+/// 判断注解列表中是否包含指定注解
+fn has_attr(attrs: &[Attribute], name: &str) -> bool {
+    attrs.iter().any(|attr| attr_name(attr) == name)
+}
+
+/// 从方法注解中识别请求映射，返回 HTTP 方法和路径
+fn mapping_of(attrs: &[Attribute]) -> Option<(&'static str, String)> {
+    attrs.iter().find_map(|attr| {
+        let method = match attr_name(attr).as_str() {
+            "GetMapping" => "get",
+            "PostMapping" => "post",
+            "PutMapping" => "put",
+            "DeleteMapping" => "delete",
+            "PatchMapping" => "patch",
+            _ => return None,
+        };
+        let path = attr.parse_args::<LitStr>().map(|path| path.value()).unwrap_or_default();
+        Some((method, path))
+    })
+}
+
+/// 获取注解名称（路径的最后一段）
+fn attr_name(attr: &Attribute) -> String {
+    attr.path()
+        .segments
+        .last()
+        .map(|segment| segment.ident.to_string())
+        .unwrap_or_default()
+}
+
+/// 获取参数模式中的变量名
+fn pat_name(pat: &Pat) -> String {
+    match pat {
+        Pat::Ident(pat_ident) => pat_ident.ident.unraw().to_string(),
+        _ => "arg".to_string(),
+    }
+}
+
+/// 提取文档注释，多行之间以换行连接
+fn doc_of(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(meta) => match &meta.value {
+                Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(doc), .. }) => Some(doc.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+
+    let doc = lines.join("\n").trim().to_string();
+    (!doc.is_empty()).then_some(doc)
+}
+
+/// API Schema 派生宏
+///
+/// 为结构体或枚举实现 `ApiSchema`，用于生成 OpenAPI 文档中的请求/响应类型描述。
+/// 支持 `#[serde(rename)]`、`#[serde(rename_all)]`、`#[serde(skip)]` 和 `#[serde(default)]`，
+/// 文档注释会作为描述输出
+///
+/// # 示例
+///
+/// ```rust
+/// #[derive(Serialize, Deserialize, ApiSchema)]
+/// #[serde(rename_all = "camelCase")]
+/// pub struct User {
+///     /// 用户ID
+///     pub id: u64,
+///     /// 用户昵称
+///     pub nick_name: Option<String>,
+/// }
+/// ```
+#[proc_macro_derive(ApiSchema)]
+pub fn api_schema_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(rspring_web::ApiSchema));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let container = SerdeAttrs::parse(&input.attrs);
+    let rename_all = container.rename_all.as_deref();
+
+    let schema = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => {
+                let mut properties = Vec::new();
+                for field in &fields.named {
+                    let attrs = SerdeAttrs::parse(&field.attrs);
+                    if attrs.skip {
+                        continue;
+                    }
+
+                    let ident = field.ident.as_ref().map(|ident| ident.unraw().to_string()).unwrap_or_default();
+                    let field_name = attrs
+                        .rename
+                        .unwrap_or_else(|| rename_all.map_or(ident.clone(), |rule| apply_rename_rule(&ident, rule)));
+                    let ty = &field.ty;
+
+                    let mut field_schema = quote! { <#ty as rspring_web::ApiSchema>::schema() };
+                    if let Some(doc) = doc_of(&field.attrs) {
+                        field_schema = quote! { rspring_web::openapi::with_description(#field_schema, #doc) };
+                    }
+                    let required = if attrs.default {
+                        quote! { false }
+                    } else {
+                        quote! { <#ty as rspring_web::ApiSchema>::required() }
+                    };
+
+                    properties.push(quote! {
+                        properties.insert(#field_name.to_string(), #field_schema);
+                        if #required {
+                            required.push(rspring_web::serde_json::Value::from(#field_name));
+                        }
+                    });
+                }
+
+                quote! {
+                    let mut properties = rspring_web::serde_json::Map::new();
+                    let mut required: Vec<rspring_web::serde_json::Value> = Vec::new();
+                    #(#properties)*
+                    rspring_web::serde_json::json!({
+                        "type": "object",
+                        "properties": properties,
+                        "required": required,
+                    })
+                }
+            }
+            // 新类型结构体使用内部类型的 Schema
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let ty = &fields.unnamed[0].ty;
+                quote! { <#ty as rspring_web::ApiSchema>::schema() }
+            }
+            _ => quote! { rspring_web::serde_json::json!({ "type": "object" }) },
+        },
+        // 只包含单元变体的枚举描述为字符串枚举
+        Data::Enum(data) if data.variants.iter().all(|variant| matches!(variant.fields, Fields::Unit)) => {
+            let variants: Vec<String> = data
+                .variants
+                .iter()
+                .filter_map(|variant| {
+                    let attrs = SerdeAttrs::parse(&variant.attrs);
+                    if attrs.skip {
+                        return None;
+                    }
+                    let ident = variant.ident.unraw().to_string();
+                    Some(attrs.rename.unwrap_or_else(|| rename_all.map_or(ident.clone(), |rule| apply_rename_rule(&ident, rule))))
+                })
+                .collect();
+
+            quote! {
+                rspring_web::serde_json::json!({ "type": "string", "enum": [#(#variants),*] })
+            }
+        }
+        _ => quote! { rspring_web::serde_json::json!({ "type": "object" }) },
+    };
+
+    let schema = match doc_of(&input.attrs) {
+        Some(doc) => quote! { rspring_web::openapi::with_description({ #schema }, #doc) },
+        None => quote! { { #schema } },
+    };
+
+    let expanded = quote! {
+        impl #impl_generics rspring_web::ApiSchema for #name #ty_generics #where_clause {
+            fn schema() -> rspring_web::serde_json::Value {
+                #schema
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// 与 Schema 相关的 serde 注解
+#[derive(Default)]
+struct SerdeAttrs {
+    /// `rename = "..."`
+    rename: Option<String>,
+    /// `rename_all = "..."`
+    rename_all: Option<String>,
+    /// `skip`
+    skip: bool,
+    /// `default`
+    default: bool,
+}
+
+impl SerdeAttrs {
+    /// 解析 `#[serde(...)]` 注解，忽略不关心的选项
+    fn parse(attrs: &[Attribute]) -> Self {
+        let mut result = Self::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") && meta.input.peek(syn::Token![=]) {
+                    result.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("rename_all") && meta.input.peek(syn::Token![=]) {
+                    result.rename_all = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("skip") {
+                    result.skip = true;
+                } else if meta.path.is_ident("default") {
+                    result.default = true;
+                    if meta.input.peek(syn::Token![=]) {
+                        meta.value()?.parse::<Expr>()?;
+                    }
+                } else if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<Expr>()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    meta.input.parse::<proc_macro2::Group>()?;
+                }
+                Ok(())
+            });
+        }
+
+        result
+    }
+}
+
+/// 按 serde 的 `rename_all` 规则转换名称
+fn apply_rename_rule(name: &str, rule: &str) -> String {
+    // 同时支持 snake_case 字段名和 PascalCase 变体名的分词
+    let mut words: Vec<String> = Vec::new();
+    for part in name.split('_').filter(|part| !part.is_empty()) {
+        let mut word = String::new();
+        for ch in part.chars() {
+            if ch.is_uppercase() && !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            word.push(ch);
+        }
+        words.push(word);
+    }
+
+    let capitalize = |word: &String| {
+        let mut chars = word.chars();
+        chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect())
+            .unwrap_or_default()
+    };
+    let lower: Vec<String> = words.iter().map(|word| word.to_lowercase()).collect();
+
+    match rule {
+        "lowercase" => lower.concat(),
+        "UPPERCASE" => lower.concat().to_uppercase(),
+        "PascalCase" => words.iter().map(capitalize).collect::<Vec<String>>().concat(),
+        "camelCase" => words
+            .iter()
+            .enumerate()
+            .map(|(index, word)| if index == 0 { word.to_lowercase() } else { capitalize(word) })
+            .collect::<Vec<String>>()
+            .concat(),
+        "snake_case" => lower.join("_"),
+        "SCREAMING_SNAKE_CASE" => lower.join("_").to_uppercase(),
+        "kebab-case" => lower.join("-"),
+        "SCREAMING-KEBAB-CASE" => lower.join("-").to_uppercase(),
+        _ => name.to_string(),
+    }
+}
+
 /// GET 请求映射注解
 /// 
 /// 标记方法处理 GET 请求
@@ -79,33 +809,65 @@ pub fn RequestMapping(args: TokenStream, input: TokenStream) -> TokenStream {
 /// ```
 #[proc_macro_attribute]
 pub fn GetMapping(_args: TokenStream, input: TokenStream) -> TokenStream {
-    // TODO: 实现路由方法生成
-    // 现在简单地保持原方法不变
-    input
+    expand_mapping(input)
 }
 
 /// POST 请求映射注解
 #[proc_macro_attribute] 
 pub fn PostMapping(_args: TokenStream, input: TokenStream) -> TokenStream {
-    input
+    expand_mapping(input)
 }
 
 /// PUT 请求映射注解
 #[proc_macro_attribute]
 pub fn PutMapping(_args: TokenStream, input: TokenStream) -> TokenStream {
-    input
+    expand_mapping(input)
 }
 
 /// DELETE 请求映射注解
 #[proc_macro_attribute]
 pub fn DeleteMapping(_args: TokenStream, input: TokenStream) -> TokenStream {
-    input
+    expand_mapping(input)
 }
 
 /// PATCH 请求映射注解
 #[proc_macro_attribute]
 pub fn PatchMapping(_args: TokenStream, input: TokenStream) -> TokenStream {
-    input
+    expand_mapping(input)
+}
+
+/// 展开请求映射注解
+/// 
+/// 处理方法参数上的注解，将其改写为对应的 axum 提取器：
+/// - `#[Valid] request: T` → `rspring_web::Valid(request): rspring_web::Valid<T>`
+/// - `#[Form] form: T` → `rspring_web::FormBody(form): rspring_web::FormBody<T>`
+///
+/// 同时标注 `#[Valid]` 和 `#[Form]` 时按 `#[Valid]` 处理，表单请求体同样会被校验
+fn expand_mapping(input: TokenStream) -> TokenStream {
+    let mut function = parse_macro_input!(input as ItemFn);
+
+    for arg in function.sig.inputs.iter_mut() {
+        let FnArg::Typed(pat_type) = arg else {
+            continue;
+        };
+
+        // 取出参数上的 #[Valid] 和 #[Form] 注解
+        let valid = has_attr(&pat_type.attrs, "Valid");
+        let form = has_attr(&pat_type.attrs, "Form");
+        pat_type.attrs.retain(|attr| !attr.path().is_ident("Valid") && !attr.path().is_ident("Form"));
+
+        let pat = &pat_type.pat;
+        let ty = &pat_type.ty;
+        if valid {
+            *pat_type.pat = parse_quote!(rspring_web::Valid(#pat));
+            *pat_type.ty = parse_quote!(rspring_web::Valid<#ty>);
+        } else if form {
+            *pat_type.pat = parse_quote!(rspring_web::FormBody(#pat));
+            *pat_type.ty = parse_quote!(rspring_web::FormBody<#ty>);
+        }
+    }
+
+    TokenStream::from(quote! { #function })
 }
 
 /// 路径变量注解
@@ -120,6 +882,12 @@ pub fn RequestBody(_args: TokenStream, input: TokenStream) -> TokenStream {
     input
 }
 
+/// 表单请求体注解
+#[proc_macro_attribute]
+pub fn Form(_args: TokenStream, input: TokenStream) -> TokenStream {
+    input
+}
+
 /// 请求参数注解
 #[proc_macro_attribute]
 pub fn RequestParam(_args: TokenStream, input: TokenStream) -> TokenStream {
@@ -130,4 +898,397 @@ pub fn RequestParam(_args: TokenStream, input: TokenStream) -> TokenStream {
 #[proc_macro_attribute]
 pub fn RequestHeader(_args: TokenStream, input: TokenStream) -> TokenStream {
     input
-}
\ No newline at end of file
+}
+
+/// 原样返回注解
+///
+/// 在设置了 `wrap_response` 的控制器中，标注此注解的方法不包装为 `ApiResponse`
+#[proc_macro_attribute]
+pub fn RawResponse(_args: TokenStream, input: TokenStream) -> TokenStream {
+    input
+}
+
+/// HTTP 客户端的请求方法注解
+const CLIENT_METHODS: &[&str] = &["Get", "Post", "Put", "Delete", "Patch"];
+
+/// 声明式 HTTP 客户端注解
+///
+/// 标注在 trait 上，为 trait 生成基于 reqwest 的实现 `Http<Trait>`，需要启用 `http-client` 特性：
+/// - `base_url` 为基础地址，支持 `${key:default}` 配置表达式，`Http<Trait>::from_config` 从配置创建客户端
+/// - 方法须为 `async fn`，以 `&self` 为接收者，返回 `Result<T>`，`T` 从 JSON 响应体解析
+/// - 方法标注 `#[Get("/users/{id}")]`、`#[Post]`、`#[Put]`、`#[Delete]` 或 `#[Patch]`，
+///   路径变量对应同名参数或 `#[PathVariable("id")]` 参数
+/// - `#[RequestParam]` 参数作为查询参数，`#[RequestHeader("X-Tenant")]` 参数作为请求头，
+///   `#[RequestBody]` 参数作为 JSON 请求体，`#[Form]` 参数作为表单请求体
+/// - 请求附加追踪头，非 2xx 响应转换为 `Error`，见 `rspring_web::client`
+///
+/// `Http<Trait>` 实现 `Component`，可以手动注册到容器，或在 `component_scan!` 中列出。
+/// trait 中的方法返回 `impl Future + Send`，可以在 `tokio::spawn` 的任务中调用
+///
+/// # 示例
+///
+/// ```rust
+/// #[HttpClient(base_url = "${services.user.url}")]
+/// pub trait UserClient {
+///     #[Get("/users/{id}")]
+///     async fn get_user(&self, id: u64) -> Result<User>;
+///
+///     #[Get("/users")]
+///     async fn search(&self, #[RequestParam("q")] query: &str, #[RequestParam] page: Option<u32>) -> Result<Vec<User>>;
+///
+///     #[Post("/users")]
+///     async fn create(&self, #[RequestHeader("X-Tenant")] tenant: &str, #[RequestBody] user: &CreateUser) -> Result<User>;
+/// }
+///
+/// context.register_singleton(HttpUserClient::from_config(context.config_manager())?).await;
+///
+/// #[derive(Service)]
+/// pub struct OrderService {
+///     users: Arc<HttpUserClient>,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn HttpClient(args: TokenStream, input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as syn::ItemTrait);
+    let mut base_url = None;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("base_url") {
+            base_url = Some(meta.value()?.parse::<LitStr>()?);
+            Ok(())
+        } else {
+            Err(meta.error("不支持的参数，可用参数: base_url"))
+        }
+    });
+    parse_macro_input!(args with parser);
+
+    let Some(base_url) = base_url else {
+        let error = syn::Error::new_spanned(&item.ident, "缺少 base_url 参数，如 #[HttpClient(base_url = \"${services.user.url}\")]");
+        return error.to_compile_error().into();
+    };
+    match expand_http_client(item, &base_url) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// 展开 `#[HttpClient]`，将请求方法改写为返回 `impl Future + Send` 并生成 `Http<Trait>` 实现
+fn expand_http_client(mut item: syn::ItemTrait, base_url: &LitStr) -> syn::Result<proc_macro2::TokenStream> {
+    if !item.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&item.generics, "#[HttpClient] 不支持泛型 trait"));
+    }
+
+    let mut methods = Vec::new();
+    for trait_item in &mut item.items {
+        let syn::TraitItem::Fn(method) = trait_item else {
+            continue;
+        };
+        let Some(index) = method.attrs.iter().position(|attr| CLIENT_METHODS.contains(&attr_name(attr).as_str())) else {
+            if method.default.is_none() {
+                return Err(syn::Error::new_spanned(
+                    &method.sig,
+                    "方法须标注 #[Get]、#[Post]、#[Put]、#[Delete] 或 #[Patch]，或提供默认实现",
+                ));
+            }
+            continue;
+        };
+        let attr = method.attrs.remove(index);
+        methods.push(client_method(method, &attr)?);
+    }
+
+    let name = &item.ident;
+    let vis = &item.vis;
+    let client = quote::format_ident!("Http{}", name);
+    let doc = format!("`{}` 的 HTTP 实现", name);
+    Ok(quote! {
+        #item
+
+        #[doc = #doc]
+        #[derive(Debug, Clone)]
+        #vis struct #client {
+            client: rspring_web::client::HttpClient,
+        }
+
+        impl #client {
+            /// 使用指定的 HTTP 客户端创建
+            pub fn new(client: rspring_web::client::HttpClient) -> Self {
+                Self { client }
+            }
+
+            /// 按 `base_url` 配置创建
+            pub fn from_config(config: &rspring_web::ConfigurationManager) -> rspring_web::Result<Self> {
+                rspring_web::client::HttpClient::from_config(config, #base_url).map(Self::new)
+            }
+
+            /// `component_scan!` 使用的 Bean 定义
+            #[doc(hidden)]
+            #[allow(dead_code)]
+            pub fn __scan_definition(
+                __config: &rspring_web::ConfigurationManager,
+            ) -> rspring_web::Result<rspring_web::BeanDefinition> {
+                let client = Self::from_config(__config)?;
+                Ok(rspring_web::BeanDefinition::new(stringify!(#client), ::std::vec::Vec::new(), move |_| Ok(client)))
+            }
+        }
+
+        impl rspring_web::Component for #client {
+            fn component_name(&self) -> &'static str {
+                stringify!(#client)
+            }
+        }
+
+        impl #name for #client {
+            #(#methods)*
+        }
+    })
+}
+
+/// 生成请求方法的实现，并将 trait 中的方法签名改写为返回 `impl Future + Send`
+fn client_method(method: &mut syn::TraitItemFn, attr: &Attribute) -> syn::Result<proc_macro2::TokenStream> {
+    let http_method = quote::format_ident!("{}", attr_name(attr).to_uppercase());
+    let template: LitStr = attr.parse_args()?;
+    let sig = &mut method.sig;
+    if sig.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(sig.fn_token, "#[HttpClient] 的请求方法须为 async fn"));
+    }
+    match sig.inputs.first() {
+        Some(FnArg::Receiver(receiver)) if receiver.reference.is_some() && receiver.mutability.is_none() => {}
+        _ => return Err(syn::Error::new_spanned(&sig.inputs, "#[HttpClient] 的请求方法须以 &self 为接收者")),
+    }
+
+    let mut path_args = Vec::new();
+    let mut request = Vec::new();
+    for arg in sig.inputs.iter_mut().skip(1) {
+        let FnArg::Typed(pat_type) = arg else {
+            continue;
+        };
+        let Pat::Ident(pat_ident) = &*pat_type.pat else {
+            return Err(syn::Error::new_spanned(&pat_type.pat, "#[HttpClient] 的参数须为标识符"));
+        };
+        let ident = pat_ident.ident.clone();
+        match ParamKind::of(pat_type)? {
+            ParamKind::Path { name } => path_args.push((name, ident)),
+            ParamKind::Query { name, .. } => request.push(quote! { .query(#name, &#ident) }),
+            ParamKind::Header { name } => request.push(quote! { .header(#name, &#ident) }),
+            ParamKind::Body | ParamKind::Valid => request.push(quote! { .json(&#ident) }),
+            ParamKind::Form => request.push(quote! { .form(&#ident) }),
+            // 未标注的参数按名称对应路径变量
+            ParamKind::Extractor => path_args.push((ident.unraw().to_string(), ident)),
+        }
+        pat_type.attrs.retain(|attr| {
+            !matches!(
+                attr_name(attr).as_str(),
+                "PathVariable" | "RequestParam" | "RequestHeader" | "RequestBody" | "Form" | "Valid"
+            )
+        });
+    }
+
+    let segments = client_path_segments(&template, &path_args)?;
+    let count = segments.len();
+    let impl_sig = sig.clone();
+    let output = match &sig.output {
+        ReturnType::Type(_, ty) => quote! { #ty },
+        ReturnType::Default => quote! { () },
+    };
+    sig.asyncness = None;
+    sig.output = parse_quote!(-> impl ::std::future::Future<Output = #output> + Send);
+
+    Ok(quote! {
+        #impl_sig {
+            let __segments: [::std::string::String; #count] = [#(#segments),*];
+            Ok(self
+                .client
+                .request(rspring_web::client::Method::#http_method, __segments)
+                #(#request)*
+                .send()
+                .await?)
+        }
+    })
+}
+
+/// 将路径模板拆分为路径段，`{name}` 替换为对应参数，检查路径变量与参数一一对应
+fn client_path_segments(
+    template: &LitStr,
+    path_args: &[(String, syn::Ident)],
+) -> syn::Result<Vec<proc_macro2::TokenStream>> {
+    let path = template.value();
+    if path.contains('?') || path.contains('#') {
+        return Err(syn::Error::new_spanned(template, "路径中不能包含查询参数，请使用 #[RequestParam]"));
+    }
+
+    let mut used = Vec::new();
+    let mut segments = Vec::new();
+    for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+        let mut format = String::new();
+        let mut args = Vec::new();
+        let mut rest = segment;
+        while let Some(start) = rest.find('{') {
+            let end = rest[start..]
+                .find('}')
+                .map(|end| start + end)
+                .ok_or_else(|| syn::Error::new_spanned(template, "路径变量缺少右括号"))?;
+            let name = &rest[start + 1..end];
+            let (_, ident) = path_args
+                .iter()
+                .find(|(arg, _)| arg == name)
+                .ok_or_else(|| syn::Error::new_spanned(template, format!("路径变量 {{{}}} 没有对应的参数", name)))?;
+            format.push_str(&rest[..start].replace('{', "{{").replace('}', "}}"));
+            format.push_str("{}");
+            args.push(ident.clone());
+            used.push(name.to_string());
+            rest = &rest[end + 1..];
+        }
+        format.push_str(&rest.replace('}', "}}"));
+        segments.push(if args.is_empty() {
+            quote! { ::std::string::ToString::to_string(#format) }
+        } else {
+            quote! { format!(#format, #(#args),*) }
+        });
+    }
+
+    if let Some((name, ident)) = path_args.iter().find(|(name, _)| !used.contains(name)) {
+        return Err(syn::Error::new_spanned(
+            ident,
+            format!("参数 {} 不是路径变量，请标注 #[RequestParam]、#[RequestHeader] 或 #[RequestBody]", name),
+        ));
+    }
+    Ok(segments)
+}
+
+/// REST 控制器增强注解
+///
+/// 标注在 impl 块上，由其中 `#[ExceptionHandler]` 标注的方法实现 `ControllerAdvice`，
+/// 自定义错误到 HTTP 响应的映射：
+/// - 方法签名为 `fn handle(&self, error: &Error) -> R`，`R` 实现 `IntoResponse`，如 `ApiResponse<()>`，
+///   不需要错误时可以省略 `error` 参数
+/// - `#[ExceptionHandler(NotFound, Forbidden)]` 只处理列出的 `Error` 变体，不带参数时处理所有错误
+/// - 返回 `Option<R>` 的方法返回 `None` 时不处理，交由后续方法
+/// - 按方法声明顺序匹配，都未处理时交由后续注册的增强和默认映射
+///
+/// 类型需要同时派生 `Component`，在 `component_scan!` 中以 `advice(Type)` 列出时，
+/// 会在自动装配后将容器中的实例注册到全局异常处理器，也可以通过 `advice_definition` 或
+/// `GLOBAL_EXCEPTION_HANDLER.register` 手动注册
+///
+/// # 示例
+///
+/// ```rust
+/// #[derive(Component)]
+/// pub struct GlobalAdvice {
+///     support: Arc<SupportConfig>,
+/// }
+///
+/// #[RestControllerAdvice]
+/// impl GlobalAdvice {
+///     #[ExceptionHandler(NotFound)]
+///     fn not_found(&self, error: &Error) -> ApiResponse<()> {
+///         ApiResponse::<()>::error(404, format!("{}，请联系 {}", error, self.support.email))
+///     }
+///
+///     #[ExceptionHandler(Business)]
+///     fn business(&self, error: &Error) -> Option<ApiResponse<()>> {
+///         match error {
+///             Error::Business { code, message, .. } if code == "QUOTA" => Some(ApiResponse::<()>::error(429, message.clone())),
+///             _ => None,
+///         }
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn RestControllerAdvice(_args: TokenStream, input: TokenStream) -> TokenStream {
+    let item_impl = parse_macro_input!(input as ItemImpl);
+    match expand_controller_advice(item_impl) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// 展开 `#[RestControllerAdvice]`，按 `#[ExceptionHandler]` 方法生成 `ControllerAdvice` 实现
+fn expand_controller_advice(mut item_impl: ItemImpl) -> syn::Result<proc_macro2::TokenStream> {
+    if item_impl.trait_.is_some() || !item_impl.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &item_impl.self_ty,
+            "#[RestControllerAdvice] 只能标注在非泛型类型的固有 impl 块上",
+        ));
+    }
+
+    let mut handlers = Vec::new();
+    for item in &mut item_impl.items {
+        let ImplItem::Fn(method) = item else {
+            continue;
+        };
+        let Some(index) = method.attrs.iter().position(|attr| attr_name(attr) == "ExceptionHandler") else {
+            continue;
+        };
+        let attr = method.attrs.remove(index);
+        handlers.push(exception_handler(method, &attr)?);
+    }
+    if handlers.is_empty() {
+        return Err(syn::Error::new_spanned(&item_impl.self_ty, "#[RestControllerAdvice] 中没有 #[ExceptionHandler] 方法"));
+    }
+
+    let self_ty = &item_impl.self_ty;
+    Ok(quote! {
+        #item_impl
+
+        impl #self_ty {
+            /// `component_scan!` 使用的增强注册定义
+            #[doc(hidden)]
+            #[allow(dead_code)]
+            pub fn __advice_definition() -> rspring_web::BeanDefinition {
+                rspring_web::exception::advice_definition::<Self>()
+            }
+        }
+
+        impl rspring_web::ControllerAdvice for #self_ty {
+            #[allow(unused_variables)]
+            fn handle(&self, error: &rspring_web::Error) -> ::std::option::Option<rspring_web::Response> {
+                ::std::option::Option::None #(.or_else(|| #handlers))*
+            }
+        }
+    })
+}
+
+/// 生成异常处理方法的匹配和调用表达式，未匹配或 `Option<R>` 返回 `None` 时为 `None`
+fn exception_handler(method: &syn::ImplItemFn, attr: &Attribute) -> syn::Result<proc_macro2::TokenStream> {
+    let sig = &method.sig;
+    if sig.asyncness.is_some() {
+        return Err(syn::Error::new_spanned(sig.asyncness, "#[ExceptionHandler] 方法不能是 async fn"));
+    }
+    if !matches!(sig.inputs.first(), Some(FnArg::Receiver(receiver)) if receiver.reference.is_some()) {
+        return Err(syn::Error::new_spanned(&sig.ident, "#[ExceptionHandler] 方法须以 &self 为接收者"));
+    }
+    let ident = &sig.ident;
+    let call = match sig.inputs.len() {
+        1 => quote! { self.#ident() },
+        2 => quote! { self.#ident(error) },
+        _ => return Err(syn::Error::new_spanned(&sig.inputs, "#[ExceptionHandler] 方法只能接收 &Error 参数")),
+    };
+
+    // 变体名补全为 `Error::Variant`，完整路径保持原样
+    let variants = match &attr.meta {
+        syn::Meta::Path(_) => Vec::new(),
+        _ => attr
+            .parse_args_with(syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated)?
+            .into_iter()
+            .map(|path| match path.get_ident() {
+                Some(variant) => quote! { rspring_web::Error::#variant { .. } },
+                None => quote! { #path { .. } },
+            })
+            .collect(),
+    };
+    let optional = matches!(
+        &sig.output,
+        ReturnType::Type(_, ty) if matches!(&**ty, Type::Path(path) if path.path.segments.last().is_some_and(|segment| segment.ident == "Option"))
+    );
+    let handle = if optional {
+        quote! { #call.map(rspring_web::IntoResponse::into_response) }
+    } else {
+        quote! { ::std::option::Option::Some(rspring_web::IntoResponse::into_response(#call)) }
+    };
+    Ok(if variants.is_empty() {
+        handle
+    } else {
+        quote! {
+            if matches!(error, #(#variants)|*) { #handle } else { ::std::option::Option::None }
+        }
+    })
+}
@@ -0,0 +1,479 @@
+//! OpenAPI 文档生成模块
+//!
+//! 根据控制器上的 `RequestMapping`/`GetMapping` 等注解元数据以及
+//! 请求/响应类型的 `ApiSchema` 实现生成 OpenAPI 3 文档，并通过 `/api-docs` 暴露
+
+use std::collections::{BTreeMap, HashMap};
+
+use axum::routing::get;
+use axum::{Json, Router};
+use rspring_core::config::AppConfig;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::controller::RestRoutes;
+use crate::response::{ApiResponse, PageResult};
+use crate::validation::Valid;
+
+/// OpenAPI 文档的默认访问路径
+pub const OPENAPI_PATH: &str = "/api-docs";
+
+/// 类型的 JSON Schema 描述
+///
+/// 可通过 `#[derive(ApiSchema)]` 自动实现，支持 `#[serde(rename)]`、
+/// `#[serde(rename_all)]` 和 `#[serde(skip)]`，字段文档注释会作为描述输出
+///
+/// # 示例
+/// ```rust
+/// #[derive(Serialize, ApiSchema)]
+/// pub struct User {
+///     /// 用户ID
+///     pub id: u64,
+///     /// 用户昵称
+///     pub nickname: Option<String>,
+/// }
+/// ```
+pub trait ApiSchema {
+    /// 获取类型的 JSON Schema
+    fn schema() -> Value;
+
+    /// 作为字段或参数时是否必填
+    ///
+    /// # 默认值
+    /// `true`，`Option<T>` 为 `false`
+    fn required() -> bool {
+        true
+    }
+}
+
+/// 为 Schema 附加描述信息
+pub fn with_description(mut schema: Value, description: &str) -> Value {
+    if let Value::Object(map) = &mut schema {
+        map.insert("description".to_string(), Value::String(description.to_string()));
+    }
+    schema
+}
+
+/// 拼接控制器基础路径和方法路径
+///
+/// # 示例
+/// ```rust
+/// assert_eq!(join_path("/api/users", "/{id}"), "/api/users/{id}");
+/// assert_eq!(join_path("/api/users", ""), "/api/users");
+/// ```
+pub fn join_path(base: &str, path: &str) -> String {
+    let base = base.trim_end_matches('/');
+    let path = path.trim_start_matches('/');
+
+    match (base.is_empty(), path.is_empty()) {
+        (true, true) => "/".to_string(),
+        (true, false) => format!("/{}", path),
+        (false, true) => base.to_string(),
+        (false, false) => format!("{}/{}", base, path),
+    }
+}
+
+/// 参数位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParameterLocation {
+    /// 路径参数
+    Path,
+    /// 查询参数
+    Query,
+    /// 请求头
+    Header,
+}
+
+/// 接口参数元数据
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterInfo {
+    /// 参数名称
+    pub name: String,
+    /// 参数位置
+    pub location: ParameterLocation,
+    /// 是否必填
+    pub required: bool,
+    /// 参数类型的 Schema
+    pub schema: Value,
+}
+
+impl ParameterInfo {
+    /// 根据参数类型创建参数元数据
+    pub fn of<T: ApiSchema>(name: impl Into<String>, location: ParameterLocation) -> Self {
+        Self {
+            name: name.into(),
+            location,
+            // 路径参数总是必填
+            required: location == ParameterLocation::Path || T::required(),
+            schema: T::schema(),
+        }
+    }
+}
+
+/// 接口操作元数据
+///
+/// 由 `#[RequestMapping]` 标注的 impl 块根据其中的请求映射方法生成
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationInfo {
+    /// HTTP 方法（小写），如 `get`
+    pub method: &'static str,
+    /// 完整路径，路径变量使用 `{name}` 形式
+    pub path: String,
+    /// 操作 ID，默认为方法名
+    pub operation_id: String,
+    /// 所属控制器名称，作为 OpenAPI 标签
+    pub tag: String,
+    /// 摘要，取自方法文档注释的第一行
+    pub summary: Option<String>,
+    /// 参数列表
+    pub parameters: Vec<ParameterInfo>,
+    /// 请求体 Schema
+    pub request_body: Option<Value>,
+    /// 请求体支持的 Content-Type，为空时为 `application/json`
+    pub request_content_types: Vec<&'static str>,
+    /// 响应体 Schema
+    pub response: Option<Value>,
+}
+
+impl OperationInfo {
+    /// 转换为 OpenAPI 的 Operation 对象
+    pub fn to_json(&self) -> Value {
+        let mut operation = Map::new();
+        operation.insert("operationId".to_string(), json!(self.operation_id));
+        operation.insert("tags".to_string(), json!([self.tag]));
+
+        if let Some(summary) = &self.summary {
+            operation.insert("summary".to_string(), json!(summary));
+        }
+
+        if !self.parameters.is_empty() {
+            let parameters: Vec<Value> = self
+                .parameters
+                .iter()
+                .map(|parameter| {
+                    json!({
+                        "name": parameter.name,
+                        "in": parameter.location,
+                        "required": parameter.required,
+                        "schema": parameter.schema,
+                    })
+                })
+                .collect();
+            operation.insert("parameters".to_string(), Value::Array(parameters));
+        }
+
+        if let Some(schema) = &self.request_body {
+            let content_types = match self.request_content_types.as_slice() {
+                [] => &["application/json"][..],
+                content_types => content_types,
+            };
+            let content: Map<String, Value> = content_types
+                .iter()
+                .map(|content_type| (content_type.to_string(), json!({ "schema": schema })))
+                .collect();
+            operation.insert(
+                "requestBody".to_string(),
+                json!({
+                    "required": true,
+                    "content": content,
+                }),
+            );
+        }
+
+        let response = match &self.response {
+            Some(schema) => json!({
+                "description": "OK",
+                "content": { "application/json": { "schema": schema } },
+            }),
+            None => json!({ "description": "OK" }),
+        };
+        operation.insert("responses".to_string(), json!({ "200": response }));
+
+        Value::Object(operation)
+    }
+}
+
+/// OpenAPI 文档构建器
+///
+/// # 示例
+/// ```rust
+/// let openapi = OpenApi::new("用户服务", "1.0.0")
+///     .controller::<UserController>()
+///     .controller::<OrderController>();
+///
+/// let app = Router::new().merge(openapi.router());
+/// ```
+#[derive(Debug, Clone)]
+pub struct OpenApi {
+    /// 文档标题
+    title: String,
+    /// 文档版本
+    version: String,
+    /// 文档描述
+    description: Option<String>,
+    /// 接口操作列表
+    operations: Vec<OperationInfo>,
+}
+
+impl OpenApi {
+    /// 创建新的 OpenAPI 文档
+    pub fn new(title: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            version: version.into(),
+            description: None,
+            operations: Vec::new(),
+        }
+    }
+
+    /// 使用应用配置中的名称、版本和描述创建文档
+    pub fn from_config(config: &AppConfig) -> Self {
+        let mut openapi = Self::new(&config.name, &config.version);
+        openapi.description = config.description.clone();
+        openapi
+    }
+
+    /// 设置文档描述
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// 添加控制器的全部接口
+    pub fn controller<C: RestRoutes>(mut self) -> Self {
+        self.operations.extend(C::operations());
+        self
+    }
+
+    /// 添加单个接口操作
+    pub fn operation(mut self, operation: OperationInfo) -> Self {
+        self.operations.push(operation);
+        self
+    }
+
+    /// 获取文档标题
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// 获取已收集的接口操作
+    pub fn operations(&self) -> &[OperationInfo] {
+        &self.operations
+    }
+
+    /// 生成 OpenAPI 3 JSON 文档
+    pub fn to_json(&self) -> Value {
+        // 使用 BTreeMap 保证路径输出顺序稳定
+        let mut paths: BTreeMap<String, Map<String, Value>> = BTreeMap::new();
+        for operation in &self.operations {
+            paths
+                .entry(operation.path.clone())
+                .or_default()
+                .insert(operation.method.to_string(), operation.to_json());
+        }
+
+        let mut info = json!({ "title": self.title, "version": self.version });
+        if let Some(description) = &self.description {
+            info["description"] = json!(description);
+        }
+
+        json!({
+            "openapi": "3.0.3",
+            "info": info,
+            "paths": paths,
+        })
+    }
+
+    /// 生成暴露 `/api-docs` 的路由
+    pub fn router(self) -> Router {
+        self.router_at(OPENAPI_PATH)
+    }
+
+    /// 生成在指定路径暴露文档的路由
+    pub fn router_at(self, path: &str) -> Router {
+        let document = self.to_json();
+        Router::new().route(path, get(move || async move { Json(document) }))
+    }
+}
+
+// 基础类型的 Schema 实现
+
+macro_rules! impl_api_schema {
+    ($schema:tt => $($ty:ty),+) => {
+        $(
+            impl ApiSchema for $ty {
+                fn schema() -> Value {
+                    json!($schema)
+                }
+            }
+        )+
+    };
+}
+
+impl_api_schema!({ "type": "string" } => String, str, char);
+impl_api_schema!({ "type": "boolean" } => bool);
+impl_api_schema!({ "type": "integer", "format": "int32" } => i8, i16, i32, u8, u16, u32);
+impl_api_schema!({ "type": "integer", "format": "int64" } => i64, u64, i128, u128, isize, usize);
+impl_api_schema!({ "type": "number", "format": "float" } => f32);
+impl_api_schema!({ "type": "number", "format": "double" } => f64);
+impl_api_schema!({ "type": "object" } => (), serde_json::Value, axum::response::Response);
+impl_api_schema!({ "type": "string", "format": "date-time" } => chrono::DateTime<chrono::Utc>);
+
+impl<T: ApiSchema + ?Sized> ApiSchema for &T {
+    fn schema() -> Value {
+        T::schema()
+    }
+
+    fn required() -> bool {
+        T::required()
+    }
+}
+
+impl<T: ApiSchema + ?Sized> ApiSchema for Box<T> {
+    fn schema() -> Value {
+        T::schema()
+    }
+
+    fn required() -> bool {
+        T::required()
+    }
+}
+
+impl<T: ApiSchema> ApiSchema for Option<T> {
+    fn schema() -> Value {
+        T::schema()
+    }
+
+    fn required() -> bool {
+        false
+    }
+}
+
+impl<T: ApiSchema> ApiSchema for Vec<T> {
+    fn schema() -> Value {
+        json!({ "type": "array", "items": T::schema() })
+    }
+}
+
+impl<T: ApiSchema> ApiSchema for HashMap<String, T> {
+    fn schema() -> Value {
+        json!({ "type": "object", "additionalProperties": T::schema() })
+    }
+}
+
+impl<T: ApiSchema> ApiSchema for BTreeMap<String, T> {
+    fn schema() -> Value {
+        json!({ "type": "object", "additionalProperties": T::schema() })
+    }
+}
+
+/// 处理器返回 `Result` 时，只描述成功分支
+impl<T: ApiSchema, E> ApiSchema for std::result::Result<T, E> {
+    fn schema() -> Value {
+        T::schema()
+    }
+}
+
+impl<T: ApiSchema> ApiSchema for Json<T> {
+    fn schema() -> Value {
+        T::schema()
+    }
+}
+
+impl<T: ApiSchema> ApiSchema for Valid<T> {
+    fn schema() -> Value {
+        T::schema()
+    }
+}
+
+impl<T: ApiSchema> ApiSchema for ApiResponse<T> {
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "code": { "type": "integer", "format": "int32" },
+                "message": { "type": "string" },
+                "data": T::schema(),
+                "timestamp": { "type": "integer", "format": "int64" },
+                "request_id": { "type": "string" },
+            },
+            "required": ["code", "message", "timestamp"],
+        })
+    }
+}
+
+impl<T: ApiSchema> ApiSchema for PageResult<T> {
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "content": { "type": "array", "items": T::schema() },
+                "page": { "type": "integer", "format": "int64" },
+                "size": { "type": "integer", "format": "int64" },
+                "total": { "type": "integer", "format": "int64" },
+                "total_pages": { "type": "integer", "format": "int64" },
+                "first": { "type": "boolean" },
+                "last": { "type": "boolean" },
+                "empty": { "type": "boolean" },
+            },
+            "required": ["content", "page", "size", "total", "total_pages", "first", "last", "empty"],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试路径拼接
+    #[test]
+    fn test_join_path() {
+        assert_eq!(join_path("/api/users", "/{id}"), "/api/users/{id}");
+        assert_eq!(join_path("/api/users/", "{id}"), "/api/users/{id}");
+        assert_eq!(join_path("/api/users", ""), "/api/users");
+        assert_eq!(join_path("", ""), "/");
+        assert_eq!(join_path("/", "/health"), "/health");
+    }
+
+    /// 测试 Option 参数不是必填的
+    #[test]
+    fn test_optional_parameter() {
+        let parameter = ParameterInfo::of::<Option<u32>>("limit", ParameterLocation::Query);
+        assert!(!parameter.required);
+        assert_eq!(parameter.schema["type"], "integer");
+
+        // 路径参数总是必填
+        let parameter = ParameterInfo::of::<Option<u64>>("id", ParameterLocation::Path);
+        assert!(parameter.required);
+    }
+
+    /// 测试文档生成
+    #[test]
+    fn test_openapi_document() {
+        let operation = OperationInfo {
+            method: "get",
+            path: "/api/users/{id}".to_string(),
+            operation_id: "get_user".to_string(),
+            tag: "UserController".to_string(),
+            summary: Some("根据ID查询用户".to_string()),
+            parameters: vec![ParameterInfo::of::<u64>("id", ParameterLocation::Path)],
+            request_body: None,
+            request_content_types: Vec::new(),
+            response: Some(<ApiResponse<String> as ApiSchema>::schema()),
+        };
+
+        let document = OpenApi::new("测试服务", "1.0.0").operation(operation).to_json();
+
+        assert_eq!(document["openapi"], "3.0.3");
+        assert_eq!(document["info"]["title"], "测试服务");
+
+        let get = &document["paths"]["/api/users/{id}"]["get"];
+        assert_eq!(get["operationId"], "get_user");
+        assert_eq!(get["parameters"][0]["in"], "path");
+        assert_eq!(
+            get["responses"]["200"]["content"]["application/json"]["schema"]["properties"]["data"]["type"],
+            "string"
+        );
+    }
+}
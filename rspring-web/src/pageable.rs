@@ -0,0 +1,250 @@
+//! 分页与排序模块
+//!
+//! 提供 `Pageable` 提取器，从查询参数中解析分页和排序：
+//! `?page=1&size=20&sort=name,desc&sort=id,asc`
+//!
+//! 分页大小受 `[web.pageable]` 中的 `max_size` 限制，偏移量不能超过 `max_offset`，
+//! 排序字段只允许字母、数字、下划线和点，可直接用于拼接 `ORDER BY` 子句
+
+use axum::async_trait;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum::{Extension, Router};
+use rspring_core::Error;
+use serde::{Deserialize, Serialize};
+
+use crate::extract::{ParameterRejection, ParameterSource, RequestParams};
+use crate::response::{Page, PageResult};
+
+pub use rspring_core::page::{Direction, Order, Sort};
+
+/// 分页请求，包含分页参数和排序条件
+///
+/// # 示例
+/// ```rust
+/// #[GetMapping]
+/// pub async fn list_users(&self, pageable: Pageable) -> WebResult<ApiResponse<PageResult<User>>> {
+///     let (users, total) = self.user_service.find_page(pageable.offset(), pageable.size(), pageable.sort()).await?;
+///     Ok(ApiResponse::success(pageable.result(users, total)))
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pageable {
+    /// 分页参数
+    pub page: Page,
+    /// 排序条件
+    pub sort: Sort,
+}
+
+impl Pageable {
+    /// 创建分页请求
+    pub fn new(page: u64, size: u64, sort: Sort) -> Self {
+        Self {
+            page: Page::new(page, size),
+            sort,
+        }
+    }
+
+    /// 当前页码（从0开始）
+    pub fn page_number(&self) -> u64 {
+        self.page.page
+    }
+
+    /// 每页大小
+    pub fn size(&self) -> u64 {
+        self.page.size
+    }
+
+    /// 偏移量
+    pub fn offset(&self) -> u64 {
+        self.page.offset()
+    }
+
+    /// 排序条件
+    pub fn sort(&self) -> &Sort {
+        &self.sort
+    }
+
+    /// 使用当前分页参数创建分页结果
+    pub fn result<T>(&self, content: Vec<T>, total: u64) -> PageResult<T> {
+        PageResult::new(content, self.page.page, self.page.size, total)
+    }
+
+    /// 按分页配置解析查询参数
+    fn from_params(params: &RequestParams, config: &PageableConfig) -> Result<Self, ParameterRejection> {
+        let first_page = if config.one_indexed { 1 } else { 0 };
+        let page = params.or_else::<u64, _>("page", || first_page)?;
+        if page < first_page {
+            return Err(ParameterRejection::new(format!("页码不能小于 {}", first_page)));
+        }
+
+        let size = params.or_else::<u64, _>("size", || config.default_size)?;
+        if size == 0 {
+            return Err(ParameterRejection::new("分页大小必须大于 0"));
+        }
+
+        let page = page - first_page;
+        let size = size.min(config.max_size);
+        if !matches!(page.checked_mul(size), Some(offset) if offset <= config.max_offset) {
+            return Err(ParameterRejection::new(format!("分页偏移量不能超过 {}", config.max_offset)));
+        }
+
+        let sort = Sort::parse(params.get_all("sort")).map_err(|e| match e {
+            Error::Validation { message } => ParameterRejection::new(message),
+            other => ParameterRejection::new(other.to_string()),
+        })?;
+        Ok(Self::new(page, size, sort))
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Pageable {
+    type Rejection = ParameterRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let params = RequestParams::from_request_parts(parts, state).await?;
+        let config = parts.extensions.get::<PageableConfig>().cloned().unwrap_or_default();
+        Self::from_params(&params, &config)
+    }
+}
+
+/// 分页参数配置
+///
+/// # 示例
+/// ```toml
+/// [web.pageable]
+/// default_size = 20
+/// max_size = 200
+/// one_indexed = true
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PageableConfig {
+    /// 未指定 `size` 时的分页大小
+    ///
+    /// # 默认值
+    /// `20`
+    #[serde(default = "default_size")]
+    pub default_size: u64,
+    /// 分页大小上限，超过时按上限处理
+    ///
+    /// # 默认值
+    /// `100`
+    #[serde(default = "default_max_size")]
+    pub max_size: u64,
+    /// 偏移量（页码 × 分页大小）上限，超过时拒绝请求
+    ///
+    /// # 默认值
+    /// `i64::MAX`，即数据库 `OFFSET` 可接受的最大值
+    #[serde(default = "default_max_offset")]
+    pub max_offset: u64,
+    /// 页码是否从 1 开始
+    ///
+    /// # 默认值
+    /// `false`
+    #[serde(default)]
+    pub one_indexed: bool,
+}
+
+impl Default for PageableConfig {
+    fn default() -> Self {
+        Self {
+            default_size: default_size(),
+            max_size: default_max_size(),
+            max_offset: default_max_offset(),
+            one_indexed: false,
+        }
+    }
+}
+
+impl PageableConfig {
+    /// 将分页配置应用到路由，供 `Pageable` 提取器读取
+    pub fn apply<S>(&self, router: Router<S>) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        router.layer(Extension(self.clone()))
+    }
+}
+
+// 默认值函数
+
+fn default_size() -> u64 {
+    20
+}
+
+fn default_max_size() -> u64 {
+    100
+}
+
+fn default_max_offset() -> u64 {
+    i64::MAX as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 构造查询参数
+    fn params(query: &[(&str, &str)]) -> RequestParams {
+        RequestParams(
+            query
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        )
+    }
+
+    /// 测试分页与多字段排序解析
+    #[test]
+    fn test_parse_pageable() {
+        let query = params(&[("page", "1"), ("size", "20"), ("sort", "name,desc"), ("sort", "id,asc")]);
+        let pageable = Pageable::from_params(&query, &PageableConfig::default()).unwrap();
+
+        assert_eq!(pageable.page_number(), 1);
+        assert_eq!(pageable.size(), 20);
+        assert_eq!(pageable.offset(), 20);
+        assert_eq!(pageable.sort, Sort::by(vec![Order::desc("name"), Order::asc("id")]));
+        assert_eq!(pageable.sort.to_sql().as_deref(), Some("name DESC, id ASC"));
+
+        let result = pageable.result(vec![1, 2], 45);
+        assert_eq!(result.total_pages, 3);
+        assert!(result.has_next());
+    }
+
+    /// 测试默认值、分页大小上限和从 1 开始的页码
+    #[test]
+    fn test_pageable_guards() {
+        let config = PageableConfig {
+            max_size: 50,
+            one_indexed: true,
+            ..PageableConfig::default()
+        };
+
+        let pageable = Pageable::from_params(&params(&[]), &config).unwrap();
+        assert_eq!((pageable.page_number(), pageable.size()), (0, 20));
+        assert!(pageable.sort.is_unsorted());
+
+        let pageable = Pageable::from_params(&params(&[("page", "3"), ("size", "1000")]), &config).unwrap();
+        assert_eq!((pageable.page_number(), pageable.size()), (2, 50));
+
+        assert!(Pageable::from_params(&params(&[("page", "0")]), &config).is_err());
+        assert!(Pageable::from_params(&params(&[("size", "0")]), &config).is_err());
+        assert!(Pageable::from_params(&params(&[("sort", "name;drop table")]), &config).is_err());
+    }
+
+    /// 测试页码过大导致偏移量溢出或超过上限时拒绝请求
+    #[test]
+    fn test_pageable_offset_limit() {
+        let config = PageableConfig::default();
+        let page = u64::MAX.to_string();
+        assert!(Pageable::from_params(&params(&[("page", &page), ("size", "20")]), &config).is_err());
+
+        let config = PageableConfig {
+            max_offset: 1000,
+            ..PageableConfig::default()
+        };
+        let pageable = Pageable::from_params(&params(&[("page", "50"), ("size", "20")]), &config).unwrap();
+        assert_eq!(pageable.offset(), 1000);
+        assert!(Pageable::from_params(&params(&[("page", "51"), ("size", "20")]), &config).is_err());
+    }
+}
@@ -0,0 +1,150 @@
+//! RFC 7807 问题详情模块
+//!
+//! 提供 `application/problem+json` 格式的错误响应。通过 `[web] error_format = "problem"`
+//! 开启后，全局异常处理、参数校验和参数提取的错误都会使用该格式输出
+
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::request_id::{current_request_id, current_request_path};
+
+/// 问题详情的媒体类型
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// 默认的问题类型，表示除状态码外没有额外语义
+const ABOUT_BLANK: &str = "about:blank";
+
+/// 错误响应格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorFormat {
+    /// `ApiResponse` 统一响应格式
+    #[default]
+    ApiResponse,
+    /// RFC 7807 `application/problem+json` 格式
+    Problem,
+}
+
+/// RFC 7807 问题详情
+///
+/// # 示例
+/// ```rust
+/// let problem = ProblemDetail::new(StatusCode::CONFLICT)
+///     .with_type("https://example.com/problems/duplicate-email")
+///     .with_detail("邮箱已被注册")
+///     .with_extension("email", "user@example.com");
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProblemDetail {
+    /// 问题类型 URI
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    /// 问题类型的简短描述
+    pub title: String,
+    /// HTTP 状态码
+    pub status: u16,
+    /// 本次问题的具体描述
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// 发生问题的请求路径
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// 扩展字段，如错误码、字段错误列表
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
+}
+
+impl ProblemDetail {
+    /// 根据状态码创建问题详情
+    ///
+    /// 标题使用状态码的标准描述，在请求 ID 中间件的上下文中自动填充 `instance` 和 `request_id`
+    pub fn new(status: StatusCode) -> Self {
+        let mut extensions = Map::new();
+        if let Some(request_id) = current_request_id() {
+            extensions.insert("request_id".to_string(), Value::String(request_id));
+        }
+
+        Self {
+            problem_type: ABOUT_BLANK.to_string(),
+            title: status.canonical_reason().unwrap_or("Unknown Error").to_string(),
+            status: status.as_u16(),
+            detail: None,
+            instance: current_request_path(),
+            extensions,
+        }
+    }
+
+    /// 设置问题类型 URI
+    pub fn with_type(mut self, problem_type: impl Into<String>) -> Self {
+        self.problem_type = problem_type.into();
+        self
+    }
+
+    /// 设置标题
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// 设置具体描述
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    /// 设置请求路径
+    pub fn with_instance(mut self, instance: impl Into<String>) -> Self {
+        self.instance = Some(instance.into());
+        self
+    }
+
+    /// 添加扩展字段
+    pub fn with_extension(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.extensions.insert(name.into(), value.into());
+        self
+    }
+}
+
+impl IntoResponse for ProblemDetail {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let mut response = (status, axum::Json(self)).into_response();
+        response
+            .headers_mut()
+            .insert(header::CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+
+    /// 测试问题详情的序列化与响应头
+    #[tokio::test]
+    async fn test_problem_response() {
+        let response = ProblemDetail::new(StatusCode::NOT_FOUND)
+            .with_detail("用户未找到")
+            .with_extension("code", "NOT_FOUND")
+            .into_response();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "type": "about:blank",
+                "title": "Not Found",
+                "status": 404,
+                "detail": "用户未找到",
+                "code": "NOT_FOUND",
+            })
+        );
+    }
+}
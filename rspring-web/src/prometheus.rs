@@ -0,0 +1,82 @@
+//! Prometheus 抓取端点模块
+//!
+//! 启用 `prometheus` 特性后，在 `[metrics]` 配置的路径上以 Prometheus 文本格式导出
+//! 应用启动时安装的全局记录器中的所有指标
+
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use rspring_core::metrics::{render_prometheus, MetricsConfig};
+
+use crate::exception::error_response;
+
+/// Prometheus 文本格式的 Content-Type
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// 创建 Prometheus 抓取端点的路由，指标未启用时返回空路由
+///
+/// # 示例
+/// ```rust
+/// let metrics_config = MetricsConfig::load(&config)?;
+/// let app = Router::new()
+///     .merge(user_routes)
+///     .merge(prometheus_router(&metrics_config));
+/// ```
+pub fn prometheus_router<S>(config: &MetricsConfig) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    if !config.enabled {
+        return Router::new();
+    }
+    Router::new().route(&config.path, get(scrape))
+}
+
+/// 导出指标，未安装记录器时返回 503
+async fn scrape() -> Response {
+    match render_prometheus() {
+        Some(body) => (
+            [(header::CONTENT_TYPE, HeaderValue::from_static(PROMETHEUS_CONTENT_TYPE))],
+            body,
+        )
+            .into_response(),
+        None => error_response(StatusCode::SERVICE_UNAVAILABLE, "指标记录器未安装"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::extract::Request;
+    use rspring_core::metrics::install_prometheus;
+    use tower::ServiceExt;
+
+    /// 测试导出已安装记录器中的指标
+    #[tokio::test]
+    async fn test_prometheus_router() {
+        let config = MetricsConfig::default();
+        let router: Router = prometheus_router(&config);
+        let scrape = || router.clone().oneshot(Request::get("/metrics").body(Body::empty()).unwrap());
+        assert_eq!(scrape().await.unwrap().status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        install_prometheus(&config).unwrap();
+        rspring_core::metrics::counter!("orders.created").increment(1);
+        let response = scrape().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], PROMETHEUS_CONTENT_TYPE);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(String::from_utf8_lossy(&body).contains("orders_created 1"));
+
+        let disabled = MetricsConfig {
+            enabled: false,
+            ..config
+        };
+        let response = prometheus_router::<()>(&disabled)
+            .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,204 @@
+//! Web 配置属性模块
+//!
+//! 定义 `[web]` 配置章节，在 application.toml 中按子章节配置 Web 层功能
+
+use rspring_core::config::properties::Configuration;
+use rspring_core::config::ConfigurationManager;
+use rspring_core::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::access_log::AccessLogConfig;
+use crate::cookies::CookieConfig;
+use crate::cors::CorsConfig;
+use crate::fallback::FallbackConfig;
+use crate::forwarded::ProxyConfig;
+use crate::pageable::PageableConfig;
+use crate::problem::ErrorFormat;
+use crate::rate_limit::RateLimitConfig;
+use crate::request_id::RequestIdConfig;
+use crate::slow_request::SlowRequestConfig;
+use crate::static_files::StaticConfig;
+use crate::timeout::TimeoutConfig;
+use crate::trace_context::TraceContextConfig;
+
+/// Web 配置
+///
+/// # 示例
+/// ```toml
+/// [web.openapi]
+/// path = "/api-docs"
+/// ui = "swagger"
+/// ui_path = "/swagger-ui"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct WebConfig {
+    /// OpenAPI 文档配置
+    #[serde(default)]
+    pub openapi: OpenApiConfig,
+    /// 静态资源配置（可选），对应 `[web.static]`
+    #[serde(default, rename = "static")]
+    pub static_files: Option<StaticConfig>,
+    /// 跨域配置（可选），对应 `[web.cors]`
+    #[serde(default)]
+    pub cors: Option<CorsConfig>,
+    /// 请求 ID 配置，对应 `[web.request_id]`，默认启用
+    #[serde(default)]
+    pub request_id: RequestIdConfig,
+    /// 链路追踪上下文配置，对应 `[web.trace_context]`，默认启用
+    #[serde(default)]
+    pub trace_context: TraceContextConfig,
+    /// 分页参数配置，对应 `[web.pageable]`
+    #[serde(default)]
+    pub pageable: PageableConfig,
+    /// 错误响应格式，`"api_response"` 或 `"problem"`
+    ///
+    /// 需通过 `GLOBAL_EXCEPTION_HANDLER.set_error_format` 生效
+    ///
+    /// # 默认值
+    /// `"api_response"`
+    #[serde(default)]
+    pub error_format: ErrorFormat,
+    /// 请求超时配置（可选），对应 `[web.timeout]`
+    #[serde(default)]
+    pub timeout: Option<TimeoutConfig>,
+    /// 404/405 回退处理配置，对应 `[web.fallback]`
+    #[serde(default)]
+    pub fallback: FallbackConfig,
+    /// Cookie 配置，对应 `[web.cookies]`
+    #[serde(default)]
+    pub cookies: CookieConfig,
+    /// 反向代理配置，对应 `[web.proxy]`
+    #[serde(default)]
+    pub proxy: ProxyConfig,
+    /// 访问日志配置，对应 `[web.access_log]`，默认关闭
+    #[serde(default, alias = "access-log")]
+    pub access_log: AccessLogConfig,
+    /// 请求限流配置（可选），对应 `[web.rate_limit]`
+    #[serde(default)]
+    pub rate_limit: Option<RateLimitConfig>,
+    /// 慢请求日志配置（可选），对应 `[web.slow_request]`
+    #[serde(default, alias = "slow-request")]
+    pub slow_request: Option<SlowRequestConfig>,
+}
+
+impl WebConfig {
+    /// 从配置管理器读取 `[web]` 章节
+    ///
+    /// 未配置时使用默认值
+    pub fn load(config: &ConfigurationManager) -> Result<Self> {
+        if config.contains_key("web") {
+            config.get_section("web")
+        } else {
+            Ok(Self::default())
+        }
+    }
+}
+
+impl Configuration for WebConfig {}
+
+/// 文档界面类型
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DocsUi {
+    /// Swagger UI
+    #[default]
+    Swagger,
+    /// RapiDoc
+    Rapidoc,
+    /// 不提供文档界面，仅暴露 JSON 文档
+    None,
+}
+
+/// OpenAPI 文档配置
+///
+/// 通过 profile 配置文件控制是否启用，例如在 `application-prod.toml` 中设置
+/// `enabled = false`；未显式配置时仅在非 `prod` 环境启用
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct OpenApiConfig {
+    /// 是否启用文档及文档界面
+    ///
+    /// # 默认值
+    /// 非 `prod` 环境启用
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// OpenAPI JSON 文档路径
+    ///
+    /// # 默认值
+    /// `"/api-docs"`
+    #[serde(default = "default_openapi_path")]
+    pub path: String,
+    /// 文档界面类型
+    ///
+    /// # 默认值
+    /// `"swagger"`
+    #[serde(default)]
+    pub ui: DocsUi,
+    /// 文档界面路径
+    ///
+    /// # 默认值
+    /// `"/swagger-ui"`
+    #[serde(default = "default_ui_path")]
+    pub ui_path: String,
+    /// 文档界面静态资源地址（可选）
+    ///
+    /// 未设置时使用公共 CDN，内网环境可指向自托管的资源目录
+    #[serde(default)]
+    pub assets_url: Option<String>,
+}
+
+impl OpenApiConfig {
+    /// 判断在指定环境下是否启用
+    pub fn is_enabled(&self, profile: &str) -> bool {
+        self.enabled.unwrap_or(profile != "prod")
+    }
+}
+
+impl Default for OpenApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: None,
+            path: default_openapi_path(),
+            ui: DocsUi::default(),
+            ui_path: default_ui_path(),
+            assets_url: None,
+        }
+    }
+}
+
+// 默认值函数
+
+fn default_openapi_path() -> String {
+    crate::openapi::OPENAPI_PATH.to_string()
+}
+
+fn default_ui_path() -> String {
+    "/swagger-ui".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试 OpenAPI 配置默认值
+    #[test]
+    fn test_openapi_config_default() {
+        let config = OpenApiConfig::default();
+        assert_eq!(config.path, "/api-docs");
+        assert_eq!(config.ui, DocsUi::Swagger);
+        assert_eq!(config.ui_path, "/swagger-ui");
+    }
+
+    /// 测试按环境启用文档
+    #[test]
+    fn test_openapi_enabled_by_profile() {
+        let config = OpenApiConfig::default();
+        assert!(config.is_enabled("dev"));
+        assert!(!config.is_enabled("prod"));
+
+        let config = OpenApiConfig {
+            enabled: Some(true),
+            ..OpenApiConfig::default()
+        };
+        assert!(config.is_enabled("prod"));
+    }
+}
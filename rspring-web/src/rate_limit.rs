@@ -0,0 +1,420 @@
+//! 请求限流模块
+//!
+//! 根据 `[web.rate_limit]` 配置按调用方限制请求频率，使用固定窗口计数：
+//! - API 密钥认证的请求按密钥 ID 计数，其他已认证的请求按主体名称计数，
+//!   未认证的请求按客户端地址计数
+//! - 已认证主体按角色使用不同的配额，按配置顺序取第一个匹配的角色，都不匹配时使用默认配额
+//! - 响应中带有 `X-RateLimit-Limit`、`X-RateLimit-Remaining` 和 `X-RateLimit-Reset` 请求头，
+//!   超过配额时返回 429 并带有 `Retry-After`
+//!
+//! 计数窗口按键的哈希分片保存，总数超过 `max_keys` 时淘汰最久未使用的窗口，
+//! 已结束的窗口由后台任务定期清理
+//!
+//! 限流中间件读取认证中间件设置的认证主体，应在应用 `SecurityConfig` 之前应用
+
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::extract::Request;
+use axum::http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use axum::Router;
+use hashlink::LruCache;
+use rspring_core::security::Principal;
+use rspring_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::exception::error_response;
+use crate::forwarded::client_ip;
+use crate::security::{matches_any_prefix, ApiKeyId};
+
+/// 周期内允许的请求数
+pub const X_RATELIMIT_LIMIT: HeaderName = HeaderName::from_static("x-ratelimit-limit");
+
+/// 当前周期内剩余的请求数
+pub const X_RATELIMIT_REMAINING: HeaderName = HeaderName::from_static("x-ratelimit-remaining");
+
+/// 距当前周期结束的秒数
+pub const X_RATELIMIT_RESET: HeaderName = HeaderName::from_static("x-ratelimit-reset");
+
+/// 计数窗口的分片数量
+const SHARDS: usize = 64;
+
+/// 清理已结束窗口的间隔
+const EVICTION_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 限流设置，每个周期内最多允许的请求数
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RateLimit {
+    /// 每个周期允许的请求数
+    pub requests: u32,
+    /// 周期
+    #[serde(with = "rspring_core::config::duration")]
+    pub period: Duration,
+}
+
+/// 请求限流配置
+///
+/// # 示例
+/// ```toml
+/// [web.rate_limit]
+/// paths = ["/api/"]
+/// default = { requests = 60, period = "1m" }
+///
+/// [[web.rate_limit.roles]]
+/// role = "PREMIUM"
+/// requests = 1000
+/// period = "1m"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    /// 是否启用
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 限流的路径前缀，为空时限制所有路径
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// 默认配额，未匹配角色配额的调用方使用
+    pub default: RateLimit,
+    /// 按角色的配额
+    #[serde(default)]
+    pub roles: Vec<RoleRateLimit>,
+    /// 最多保存的计数窗口数，超过时淘汰最久未使用的窗口
+    ///
+    /// # 默认值
+    /// `100000`
+    #[serde(default = "default_max_keys")]
+    pub max_keys: usize,
+}
+
+/// 按角色的配额
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RoleRateLimit {
+    /// 角色，可以省略 `ROLE_` 前缀
+    pub role: String,
+    /// 每个周期允许的请求数
+    pub requests: u32,
+    /// 周期
+    #[serde(with = "rspring_core::config::duration")]
+    pub period: Duration,
+}
+
+impl RateLimitConfig {
+    /// 获取调用方的配额
+    pub fn limit_for(&self, principal: Option<&Principal>) -> RateLimit {
+        principal
+            .and_then(|principal| self.roles.iter().find(|quota| principal.has_role(&quota.role)))
+            .map_or(self.default, |quota| RateLimit {
+                requests: quota.requests,
+                period: quota.period,
+            })
+    }
+
+    /// 将限流中间件应用到路由，未启用时原样返回
+    ///
+    /// # 错误
+    /// 配额的周期为 0 时返回验证错误
+    pub fn apply<S>(&self, router: Router<S>) -> Result<Router<S>>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        if !self.enabled {
+            return Ok(router);
+        }
+        let mut periods = std::iter::once(self.default.period).chain(self.roles.iter().map(|quota| quota.period));
+        if periods.any(|period| period.is_zero()) {
+            return Err(Error::validation("web.rate_limit 中的周期必须大于 0"));
+        }
+
+        let config = Arc::new(self.clone());
+        let limiter = Arc::new(RateLimiter::with_max_keys(self.max_keys));
+        limiter.spawn_eviction(EVICTION_INTERVAL);
+        Ok(router.layer(middleware::from_fn(move |request: Request, next: Next| {
+            enforce_rate_limit(config.clone(), limiter.clone(), request, next)
+        })))
+    }
+}
+
+/// 固定窗口限流器，按键分别计数
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// 按键的哈希分片记录的当前窗口
+    shards: Vec<Mutex<LruCache<String, RateWindow>>>,
+    /// 选择分片的哈希
+    hasher: RandomState,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::with_max_keys(default_max_keys())
+    }
+}
+
+/// 固定窗口限流的当前窗口
+#[derive(Debug, Clone, Copy)]
+struct RateWindow {
+    /// 窗口开始时间
+    started: Instant,
+    /// 窗口长度
+    period: Duration,
+    /// 窗口内的请求数
+    count: u32,
+}
+
+/// 一次限流判断的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitDecision {
+    /// 是否允许请求
+    pub allowed: bool,
+    /// 周期内允许的请求数
+    pub limit: u32,
+    /// 当前周期内剩余的请求数
+    pub remaining: u32,
+    /// 距当前周期结束的时间
+    pub reset: Duration,
+}
+
+impl RateLimiter {
+    /// 创建限流器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 创建最多保存 `max_keys` 个计数窗口的限流器
+    pub fn with_max_keys(max_keys: usize) -> Self {
+        let capacity = max_keys.div_ceil(SHARDS).max(1);
+        Self {
+            shards: (0..SHARDS).map(|_| Mutex::new(LruCache::new(capacity))).collect(),
+            hasher: RandomState::new(),
+        }
+    }
+
+    /// 按配额记录一次请求
+    pub fn acquire(&self, key: &str, limit: RateLimit) -> RateLimitDecision {
+        let now = Instant::now();
+        let mut windows = self.shard(key).lock().expect("限流锁已损坏");
+        if !windows.contains_key(key) {
+            windows.insert(
+                key.to_string(),
+                RateWindow {
+                    started: now,
+                    period: limit.period,
+                    count: 0,
+                },
+            );
+        }
+
+        let window = windows.get_mut(key).expect("计数窗口已插入");
+        if now.duration_since(window.started) >= window.period || window.period != limit.period {
+            *window = RateWindow {
+                started: now,
+                period: limit.period,
+                count: 0,
+            };
+        }
+        let allowed = window.count < limit.requests;
+        if allowed {
+            window.count += 1;
+        }
+        RateLimitDecision {
+            allowed,
+            limit: limit.requests,
+            remaining: limit.requests.saturating_sub(window.count),
+            reset: window.period.saturating_sub(now.duration_since(window.started)),
+        }
+    }
+
+    /// 获取保存的计数窗口数
+    pub fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.lock().expect("限流锁已损坏").len())
+            .sum()
+    }
+
+    /// 是否没有计数窗口
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 清理已结束的窗口，逐个分片加锁
+    pub fn evict_expired(&self) {
+        let now = Instant::now();
+        for shard in &self.shards {
+            let mut windows = shard.lock().expect("限流锁已损坏");
+            let expired: Vec<String> = windows
+                .iter()
+                .filter(|(_, window)| now.duration_since(window.started) >= window.period)
+                .map(|(key, _)| key.clone())
+                .collect();
+            for key in expired {
+                windows.remove(&key);
+            }
+        }
+    }
+
+    /// 启动定期清理已结束窗口的后台任务，限流器释放后任务退出；不在 Tokio 运行时中时不启动
+    pub fn spawn_eviction(self: &Arc<Self>, interval: Duration) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let limiter = Arc::downgrade(self);
+        runtime.spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(limiter) = limiter.upgrade() else {
+                    break;
+                };
+                limiter.evict_expired();
+            }
+        });
+    }
+
+    /// 获取键所在的分片
+    fn shard(&self, key: &str) -> &Mutex<LruCache<String, RateWindow>> {
+        &self.shards[self.hasher.hash_one(key) as usize % self.shards.len()]
+    }
+}
+
+impl RateLimitDecision {
+    /// 写入 `X-RateLimit-*` 响应头
+    pub fn write_headers(&self, headers: &mut HeaderMap) {
+        headers.insert(X_RATELIMIT_LIMIT, HeaderValue::from(self.limit));
+        headers.insert(X_RATELIMIT_REMAINING, HeaderValue::from(self.remaining));
+        headers.insert(X_RATELIMIT_RESET, HeaderValue::from(self.reset_secs()));
+    }
+
+    /// 超过配额时的 429 响应
+    pub fn rejection(&self) -> Response {
+        let mut response = error_response(StatusCode::TOO_MANY_REQUESTS, "请求过于频繁");
+        self.write_headers(response.headers_mut());
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(self.reset_secs()));
+        response
+    }
+
+    /// 距当前周期结束的秒数，至少为 1
+    fn reset_secs(&self) -> u64 {
+        self.reset.as_secs_f64().ceil().max(1.0) as u64
+    }
+}
+
+/// 限流中间件
+async fn enforce_rate_limit(
+    config: Arc<RateLimitConfig>,
+    limiter: Arc<RateLimiter>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !config.paths.is_empty() && !matches_any_prefix(request.uri().path(), &config.paths) {
+        return next.run(request).await;
+    }
+
+    let principal = request.extensions().get::<Principal>();
+    let key = match (request.extensions().get::<ApiKeyId>(), principal) {
+        (Some(ApiKeyId(id)), _) => format!("api_key:{}", id),
+        (None, Some(principal)) => format!("principal:{}", principal.name),
+        (None, None) => match client_ip(request.extensions()) {
+            Some(ip) => format!("ip:{}", ip),
+            None => "anonymous".to_string(),
+        },
+    };
+    let decision = limiter.acquire(&key, config.limit_for(principal));
+    if !decision.allowed {
+        tracing::debug!("{} 超过限流", key);
+        return decision.rejection();
+    }
+
+    let mut response = next.run(request).await;
+    decision.write_headers(response.headers_mut());
+    response
+}
+
+// 默认值函数
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_max_keys() -> usize {
+    100_000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::routing::get;
+    use tower::ServiceExt;
+
+    /// 测试按主体、API 密钥和客户端地址分别计数，以及按角色的配额
+    #[tokio::test]
+    async fn test_rate_limit_by_principal() {
+        let config: RateLimitConfig = serde_json::from_value(serde_json::json!({
+            "default": { "requests": 1, "period": "1h" },
+            "roles": [{ "role": "PREMIUM", "requests": 3, "period": "1h" }],
+        }))
+        .unwrap();
+        let app = config
+            .apply(Router::new().route("/orders", get(|| async { "ok" })))
+            .unwrap();
+        let call = |principal: Option<Principal>, api_key: Option<&str>| {
+            let mut request = Request::get("/orders").body(Body::empty()).unwrap();
+            if let Some(principal) = principal {
+                request.extensions_mut().insert(principal);
+            }
+            if let Some(id) = api_key {
+                request.extensions_mut().insert(ApiKeyId(id.to_string()));
+            }
+            app.clone().oneshot(request)
+        };
+        let alice = || Some(Principal::new("alice"));
+        let bob = || Some(Principal::new("bob").with_roles(["PREMIUM"]));
+
+        let response = call(alice(), None).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[X_RATELIMIT_LIMIT], "1");
+        assert_eq!(response.headers()[X_RATELIMIT_REMAINING], "0");
+        assert_eq!(response.headers()[X_RATELIMIT_RESET], "3600");
+        let response = call(alice(), None).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "3600");
+
+        // 同名的 API 密钥和匿名请求使用各自的计数
+        assert_eq!(call(alice(), Some("alice")).await.unwrap().status(), StatusCode::OK);
+        assert_eq!(call(None, None).await.unwrap().status(), StatusCode::OK);
+
+        for remaining in ["2", "1", "0"] {
+            let response = call(bob(), None).await.unwrap();
+            assert_eq!(response.headers()[X_RATELIMIT_REMAINING], remaining);
+        }
+        assert_eq!(call(bob(), None).await.unwrap().status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    /// 测试计数窗口数量受限，以及清理已结束的窗口
+    #[test]
+    fn test_rate_limiter_bounded() {
+        let limiter = RateLimiter::with_max_keys(SHARDS);
+        let limit = RateLimit {
+            requests: 1,
+            period: Duration::from_millis(20),
+        };
+        for index in 0..SHARDS * 10 {
+            limiter.acquire(&format!("ip:10.0.{}.{}", index / 256, index % 256), limit);
+        }
+        assert!(limiter.len() <= SHARDS);
+
+        std::thread::sleep(Duration::from_millis(30));
+        limiter.evict_expired();
+        assert!(limiter.is_empty());
+    }
+}
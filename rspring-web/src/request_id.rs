@@ -0,0 +1,280 @@
+//! 请求 ID 模块
+//!
+//! 为每个请求生成（或沿用上游传入的）请求 ID：
+//! - 保存在任务本地上下文中，可通过 `current_request_id()` 在任意位置获取
+//! - 作为 `request` 日志 span 的 `request_id` 字段输出到日志，同时记录客户端地址 `client_ip`
+//! - 作为日志上下文的 `request_id` 字段附加到请求处理期间的每一条日志
+//! - 写入响应头以及 `ApiResponse`/错误响应的 `request_id` 字段
+
+use std::convert::Infallible;
+
+use axum::async_trait;
+use axum::extract::{FromRequestParts, Request};
+use axum::http::request::Parts;
+use axum::http::{HeaderName, HeaderValue};
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use axum::Router;
+use rspring_core::logging::{with_log_context, REQUEST_ID_FIELD};
+use rspring_core::Error;
+use serde::{Deserialize, Serialize};
+use tracing::Instrument;
+
+use crate::forwarded::client_ip;
+
+/// 默认的请求 ID 请求头
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// 上游请求 ID 的最大长度，超过时重新生成
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    /// 当前请求的上下文
+    static CURRENT_REQUEST: RequestContext;
+}
+
+/// 请求上下文，在请求 ID 中间件中设置
+#[derive(Debug, Clone)]
+struct RequestContext {
+    /// 请求 ID
+    request_id: String,
+    /// 请求路径
+    path: String,
+}
+
+/// 获取当前请求的 ID
+///
+/// 不在请求处理上下文中时返回 `None`
+///
+/// # 示例
+/// ```rust
+/// if let Some(request_id) = current_request_id() {
+///     client.header("X-Request-Id", request_id);
+/// }
+/// ```
+pub fn current_request_id() -> Option<String> {
+    CURRENT_REQUEST.try_with(|context| context.request_id.clone()).ok()
+}
+
+/// 获取当前请求的路径
+///
+/// 不在请求处理上下文中时返回 `None`，用于错误响应中的 `instance` 字段
+pub fn current_request_path() -> Option<String> {
+    CURRENT_REQUEST.try_with(|context| context.path.clone()).ok()
+}
+
+/// 在指定请求 ID 的上下文中执行异步任务
+///
+/// 用于将请求 ID 传递到 `tokio::spawn` 的后台任务中
+pub async fn with_request_id<F: std::future::Future>(request_id: String, future: F) -> F::Output {
+    let path = current_request_path().unwrap_or_default();
+    let fields = [(REQUEST_ID_FIELD, request_id.clone())];
+    CURRENT_REQUEST
+        .scope(RequestContext { request_id, path }, with_log_context(fields, future))
+        .await
+}
+
+/// 请求 ID 提取器
+///
+/// # 示例
+/// ```rust
+/// async fn handler(RequestId(request_id): RequestId) -> String {
+///     request_id
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for RequestId {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let request_id = parts
+            .extensions
+            .get::<RequestId>()
+            .cloned()
+            .or_else(|| current_request_id().map(RequestId))
+            .unwrap_or_else(|| RequestId(generate_request_id()));
+        Ok(request_id)
+    }
+}
+
+/// 请求 ID 配置
+///
+/// # 示例
+/// ```toml
+/// [web.request_id]
+/// header = "X-Correlation-Id"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RequestIdConfig {
+    /// 是否启用
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 读取和写回请求 ID 的请求头
+    ///
+    /// # 默认值
+    /// `"X-Request-Id"`
+    #[serde(default = "default_header")]
+    pub header: String,
+}
+
+impl Default for RequestIdConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            header: default_header(),
+        }
+    }
+}
+
+impl RequestIdConfig {
+    /// 将请求 ID 中间件应用到路由，未启用时原样返回
+    ///
+    /// 应放在其他中间件的最外层，使拦截器和访问日志也能获取到请求 ID
+    ///
+    /// # 错误
+    /// 配置的请求头名称无效时返回错误
+    pub fn apply<S>(&self, router: Router<S>) -> rspring_core::Result<Router<S>>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        if !self.enabled {
+            return Ok(router);
+        }
+
+        let header = HeaderName::from_bytes(self.header.as_bytes()).map_err(|_| {
+            Error::validation(format!("无效的请求 ID 请求头: {}", self.header))
+        })?;
+        Ok(router.layer(middleware::from_fn(move |request: Request, next: Next| {
+            propagate_request_id(header.clone(), request, next)
+        })))
+    }
+}
+
+/// 请求 ID 中间件
+async fn propagate_request_id(header: HeaderName, mut request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(&header)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| is_valid_request_id(value))
+        .map(str::to_string)
+        .unwrap_or_else(generate_request_id);
+
+    let header_value = HeaderValue::from_str(&request_id).expect("请求 ID 已校验为可见 ASCII 字符");
+    request.headers_mut().insert(header.clone(), header_value.clone());
+    request.extensions_mut().insert(RequestId(request_id.clone()));
+
+    let client_ip = client_ip(request.extensions())
+        .map(|ip| ip.to_string())
+        .unwrap_or_else(|| "-".to_string());
+    let span = tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        method = %request.method(),
+        path = %request.uri().path(),
+        client_ip = %client_ip,
+    );
+
+    let fields = [(REQUEST_ID_FIELD, request_id.clone())];
+    let context = RequestContext {
+        request_id,
+        path: request.uri().path().to_string(),
+    };
+    let mut response = CURRENT_REQUEST
+        .scope(context, with_log_context(fields, next.run(request).instrument(span)))
+        .await;
+    response.headers_mut().insert(header, header_value);
+    response
+}
+
+/// 生成新的请求 ID
+fn generate_request_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+/// 校验上游传入的请求 ID，只接受长度合理的可见 ASCII 字符
+fn is_valid_request_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_REQUEST_ID_LEN
+        && value.bytes().all(|byte| byte.is_ascii_graphic())
+}
+
+// 默认值函数
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_header() -> String {
+    "X-Request-Id".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::routing::get;
+    use tower::ServiceExt;
+
+    /// 构造返回当前请求 ID 的路由
+    fn app() -> Router {
+        RequestIdConfig::default()
+            .apply(Router::new().route(
+                "/",
+                get(|RequestId(id): RequestId| async move {
+                    assert_eq!(current_request_id().as_deref(), Some(id.as_str()));
+                    assert_eq!(rspring_core::logging::log_context()[REQUEST_ID_FIELD], id);
+                    id
+                }),
+            ))
+            .unwrap()
+    }
+
+    /// 测试沿用上游传入的请求 ID
+    #[tokio::test]
+    async fn test_propagate_request_id() {
+        let request = Request::builder()
+            .uri("/")
+            .header("X-Request-Id", "upstream-123")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app().oneshot(request).await.unwrap();
+        assert_eq!(response.headers()["x-request-id"], "upstream-123");
+    }
+
+    /// 测试缺失或无效时生成新的请求 ID
+    #[tokio::test]
+    async fn test_generate_request_id() {
+        let request = Request::builder()
+            .uri("/")
+            .header("X-Request-Id", "bad id")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app().oneshot(request).await.unwrap();
+        let request_id = response.headers()["x-request-id"].to_str().unwrap();
+        assert_eq!(request_id.len(), 32);
+        assert!(current_request_id().is_none());
+    }
+
+    /// 测试请求头名称无效时返回错误
+    #[test]
+    fn test_invalid_header() {
+        let config = RequestIdConfig {
+            header: "X Request Id".to_string(),
+            ..RequestIdConfig::default()
+        };
+        let error = config.apply(Router::<()>::new()).unwrap_err();
+        assert!(error.to_string().contains("无效的请求 ID 请求头"));
+
+        let disabled = RequestIdConfig { enabled: false, ..config };
+        assert!(disabled.apply(Router::<()>::new()).is_ok());
+    }
+}
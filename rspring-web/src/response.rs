@@ -1,3 +1,5 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
 use serde::{Deserialize, Serialize};
 
 /// API 统一响应格式
@@ -11,37 +13,36 @@ pub struct ApiResponse<T> {
     pub data: Option<T>,
     /// 响应时间戳
     pub timestamp: i64,
+    /// 请求 ID，在请求 ID 中间件的上下文中自动填充
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 impl<T> ApiResponse<T> {
-    /// 创建成功响应
-    pub fn success(data: T) -> Self {
+    /// 创建响应
+    pub fn new(code: i32, message: impl Into<String>, data: Option<T>) -> Self {
         Self {
-            code: 200,
-            message: "success".to_string(),
-            data: Some(data),
-            timestamp: chrono::Utc::now().timestamp(),
+            code,
+            message: message.into(),
+            data,
+            timestamp: rspring_core::clock::now().timestamp(),
+            request_id: crate::request_id::current_request_id(),
         }
     }
     
+    /// 创建成功响应
+    pub fn success(data: T) -> Self {
+        Self::new(200, "success", Some(data))
+    }
+    
     /// 创建成功响应（无数据）
     pub fn success_empty() -> ApiResponse<()> {
-        ApiResponse {
-            code: 200,
-            message: "success".to_string(),
-            data: None,
-            timestamp: chrono::Utc::now().timestamp(),
-        }
+        ApiResponse::new(200, "success", None)
     }
     
     /// 创建错误响应
     pub fn error(code: i32, message: impl Into<String>) -> ApiResponse<()> {
-        ApiResponse {
-            code,
-            message: message.into(),
-            data: None,
-            timestamp: chrono::Utc::now().timestamp(),
-        }
+        ApiResponse::new(code, message, None)
     }
     
     /// 创建业务错误响应
@@ -75,104 +76,58 @@ impl<T> ApiResponse<T> {
     }
 }
 
-/// 分页参数
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Page {
-    /// 页码（从0开始）
-    pub page: u64,
-    /// 每页大小
-    pub size: u64,
+impl<T: Serialize> IntoResponse for ApiResponse<T> {
+    /// 转换为 JSON 响应
+    /// 
+    /// 响应代码为合法的 HTTP 状态码时同时作为响应状态，否则使用 200
+    fn into_response(self) -> Response {
+        let status = u16::try_from(self.code)
+            .ok()
+            .and_then(|code| StatusCode::from_u16(code).ok())
+            .unwrap_or(StatusCode::OK);
+        
+        (status, axum::Json(self)).into_response()
+    }
 }
 
-impl Default for Page {
-    fn default() -> Self {
-        Self { page: 0, size: 20 }
-    }
+/// 将处理函数的返回值包装为成功的 `ApiResponse`
+///
+/// 由 `#[RequestMapping(wrap_response)]` 生成的路由调用
+pub fn wrap<T: Serialize>(data: T) -> Response {
+    ApiResponse::success(data).into_response()
 }
 
-impl Page {
-    /// 创建分页参数
-    pub fn new(page: u64, size: u64) -> Self {
-        Self { page, size }
-    }
-    
-    /// 计算偏移量
-    pub fn offset(&self) -> u64 {
-        self.page * self.size
+/// 将 `Result` 返回值包装为成功的 `ApiResponse`，错误原样转换为响应
+///
+/// 由 `#[RequestMapping(wrap_response)]` 生成的路由调用
+pub fn wrap_result<T: Serialize, E: IntoResponse>(result: Result<T, E>) -> Response {
+    match result {
+        Ok(data) => wrap(data),
+        Err(error) => error.into_response(),
     }
 }
 
-/// 分页结果
-#[derive(Debug, Serialize, Deserialize)]
-pub struct PageResult<T> {
-    /// 数据列表
-    pub content: Vec<T>,
-    /// 当前页码
-    pub page: u64,
-    /// 每页大小
-    pub size: u64,
-    /// 总记录数
-    pub total: u64,
-    /// 总页数
-    pub total_pages: u64,
-    /// 是否第一页
-    pub first: bool,
-    /// 是否最后一页
-    pub last: bool,
-    /// 是否为空
-    pub empty: bool,
-}
+pub use rspring_core::page::{Page, PageResult};
 
-impl<T> PageResult<T> {
-    /// 创建分页结果
-    pub fn new(content: Vec<T>, page: u64, size: u64, total: u64) -> Self {
-        let total_pages = if size > 0 { (total + size - 1) / size } else { 0 };
-        let first = page == 0;
-        let last = page >= total_pages.saturating_sub(1);
-        let empty = content.is_empty();
-        
-        Self {
-            content,
-            page,
-            size,
-            total,
-            total_pages,
-            first,
-            last,
-            empty,
-        }
-    }
-    
-    /// 创建空分页结果
-    pub fn empty(page: u64, size: u64) -> Self {
-        Self::new(Vec::new(), page, size, 0)
-    }
-    
-    /// 是否有下一页
-    pub fn has_next(&self) -> bool {
-        !self.last && self.page + 1 < self.total_pages
-    }
-    
-    /// 是否有上一页
-    pub fn has_previous(&self) -> bool {
-        !self.first && self.page > 0
-    }
-    
-    /// 获取下一页页码
-    pub fn next_page(&self) -> Option<u64> {
-        if self.has_next() {
-            Some(self.page + 1)
-        } else {
-            None
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+
+    /// 读取响应体 JSON
+    async fn body_json(response: Response) -> serde_json::Value {
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
     }
-    
-    /// 获取上一页页码
-    pub fn previous_page(&self) -> Option<u64> {
-        if self.has_previous() {
-            Some(self.page - 1)
-        } else {
-            None
-        }
+
+    /// 测试返回值自动包装
+    #[tokio::test]
+    async fn test_wrap_result() {
+        let body = body_json(wrap(vec![1, 2])).await;
+        assert_eq!(body["code"], 200);
+        assert_eq!(body["data"], serde_json::json!([1, 2]));
+
+        let result: Result<String, StatusCode> = Err(StatusCode::CONFLICT);
+        assert_eq!(wrap_result(result).status(), StatusCode::CONFLICT);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,300 @@
+//! Web 安全模块
+//!
+//! 定义 `[security]` 配置章节，并将配置的认证方式应用到路由。
+//! 认证成功后，认证主体以 `Extension<Principal>` 的形式放入请求扩展，
+//! 同时通过 `with_principal` 在后续处理中可由 `current_principal()` 获取
+//!
+//! 认证方式：
+//! - OAuth2 / OIDC（`oauth2` 特性）：授权码登录和资源服务器令牌校验，对应 `[security.oauth2]`
+//! - API 密钥：从请求头或查询参数读取密钥，对应 `[security.api_key]`
+//! - 表单登录（`login` 特性）：现成的登录、登出和当前用户端点，对应 `[security.login]`，
+//!   需要提供 `UserDetailsService`，通过 `FormLogin` 单独应用
+//!
+//! `[[security.routes]]` 按路径前缀把路由分组，为每组选择认证方式和要求的授权范围。
+//! 控制器方法通过 `AuthPrincipal` 和 `CurrentUser<T>` 提取器获取当前用户
+//!
+//! 认证失败、授权拒绝、登录和登出通过 `rspring_core::auditing::audit` 记录审计事件，
+//! 动作分别为 `security.authentication`、`security.authorization`、`security.login` 和 `security.logout`
+
+pub mod api_key;
+pub mod current_user;
+#[cfg(feature = "login")]
+pub mod login;
+#[cfg(feature = "oauth2")]
+pub mod oauth2;
+
+use std::sync::Arc;
+
+use axum::extract::Request;
+use axum::http::StatusCode;
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use axum::Router;
+use rspring_core::auditing::{audit, AuditEvent, AuditOutcome};
+use rspring_core::config::properties::Configuration;
+use rspring_core::config::ConfigurationManager;
+use rspring_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::exception::error_response;
+
+pub use api_key::{
+    generate_api_key, hash_api_key, ApiKey, ApiKeyAuthenticator, ApiKeyConfig, ApiKeyId, ApiKeyStore,
+    InMemoryApiKeyStore, SCOPE_PREFIX,
+};
+pub use crate::rate_limit::RateLimit;
+pub use current_user::{AuthPrincipal, CurrentUser};
+pub use rspring_core::security::{
+    check_access, current_principal, require_principal, with_principal, AccessExpression, FromPrincipal, Principal,
+    SecurityContext, ROLE_PREFIX,
+};
+
+#[cfg(feature = "login")]
+pub use login::{FormLogin, InMemoryUserDetailsService, LoginConfig, UserDetails, UserDetailsService};
+#[cfg(feature = "oauth2")]
+pub use oauth2::{OAuth2Config, OAuth2LoginConfig, OidcProvider, ProviderMetadata};
+
+/// 安全配置
+///
+/// # 示例
+/// ```toml
+/// [security.oauth2]
+/// issuer = "https://accounts.example.com/realms/demo"
+/// audience = "orders-api"
+///
+/// [[security.routes]]
+/// paths = ["/api/"]
+/// auth = "oauth2"
+///
+/// [[security.routes]]
+/// paths = ["/partner/"]
+/// auth = "api_key"
+/// scopes = ["orders:read"]
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct SecurityConfig {
+    /// OAuth2 / OIDC 配置（可选），对应 `[security.oauth2]`
+    #[cfg(feature = "oauth2")]
+    #[serde(default)]
+    pub oauth2: Option<OAuth2Config>,
+    /// API 密钥认证配置，对应 `[security.api_key]`
+    #[serde(default)]
+    pub api_key: ApiKeyConfig,
+    /// 路由组，对应 `[[security.routes]]`
+    #[serde(default)]
+    pub routes: Vec<RouteGroupConfig>,
+}
+
+/// 认证方式
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthMode {
+    /// API 密钥
+    ApiKey,
+    /// OAuth2 访问令牌或登录会话，需要启用 `oauth2` 特性
+    #[serde(rename = "oauth2")]
+    OAuth2,
+}
+
+/// 路由组配置
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RouteGroupConfig {
+    /// 路径前缀
+    pub paths: Vec<String>,
+    /// 认证方式
+    pub auth: AuthMode,
+    /// 要求的授权范围，认证主体需拥有全部范围对应的 `SCOPE_` 权限，否则返回 403
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+impl SecurityConfig {
+    /// 从配置管理器读取 `[security]` 章节
+    ///
+    /// 未配置时使用默认值
+    pub fn load(config: &ConfigurationManager) -> Result<Self> {
+        if config.contains_key("security") {
+            config.get_section("security")
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// 将配置的认证方式应用到路由，API 密钥使用 `[security.api_key]` 中配置的密钥
+    ///
+    /// 认证中间件只作用于调用前已注册的路由，应在注册完业务路由之后调用
+    ///
+    /// # 错误
+    /// 获取身份提供方元数据失败或配置无效时返回错误
+    pub async fn apply<S>(&self, router: Router<S>) -> Result<Router<S>>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let store = Arc::new(InMemoryApiKeyStore::new(self.api_key.keys.clone()));
+        self.apply_with_api_key_store(router, store).await
+    }
+
+    /// 将配置的认证方式应用到路由，API 密钥从指定的存储中查找
+    ///
+    /// # 错误
+    /// 获取身份提供方元数据失败或配置无效时返回错误
+    ///
+    /// # 示例
+    /// ```rust
+    /// let security = SecurityConfig::load(&config)?;
+    /// let router = security
+    ///     .apply_with_api_key_store(router, Arc::new(DbApiKeyStore::new(pool.clone())))
+    ///     .await?;
+    /// ```
+    pub async fn apply_with_api_key_store<S>(&self, router: Router<S>, store: Arc<dyn ApiKeyStore>) -> Result<Router<S>>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        // 后添加的中间件先执行：API 密钥认证 → OAuth2 认证 → 授权范围检查
+        let scoped: Vec<RouteGroupConfig> = self
+            .routes
+            .iter()
+            .filter(|group| !group.scopes.is_empty())
+            .cloned()
+            .collect();
+        let router = if scoped.is_empty() {
+            router
+        } else {
+            let scoped = Arc::new(scoped);
+            router.layer(middleware::from_fn(move |request: Request, next: Next| {
+                authorize_scopes(scoped.clone(), request, next)
+            }))
+        };
+
+        let oauth2_paths = self.group_paths(AuthMode::OAuth2);
+        #[cfg(feature = "oauth2")]
+        let router = match &self.oauth2 {
+            Some(oauth2) => {
+                let mut oauth2 = oauth2.clone();
+                oauth2.protected_paths.extend(oauth2_paths);
+                oauth2.apply(router).await?
+            }
+            None if !oauth2_paths.is_empty() => {
+                return Err(Error::validation("路由组使用 oauth2 认证，但未配置 [security.oauth2]"));
+            }
+            None => router,
+        };
+        #[cfg(not(feature = "oauth2"))]
+        if !oauth2_paths.is_empty() {
+            return Err(Error::validation("路由组使用 oauth2 认证，需要启用 rspring-web 的 oauth2 特性"));
+        }
+
+        let api_key_paths = self.group_paths(AuthMode::ApiKey);
+        if api_key_paths.is_empty() {
+            return Ok(router);
+        }
+        let authenticator = ApiKeyAuthenticator::new(&self.api_key, api_key_paths, store)?;
+        Ok(Arc::new(authenticator).apply(router))
+    }
+
+    /// 获取使用指定认证方式的路由组的路径前缀
+    fn group_paths(&self, auth: AuthMode) -> Vec<String> {
+        self.routes
+            .iter()
+            .filter(|group| group.auth == auth)
+            .flat_map(|group| group.paths.iter().cloned())
+            .collect()
+    }
+}
+
+impl Configuration for SecurityConfig {}
+
+/// 授权范围检查中间件，请求匹配的路由组要求的范围需全部拥有
+async fn authorize_scopes(groups: Arc<Vec<RouteGroupConfig>>, request: Request, next: Next) -> Response {
+    let path = request.uri().path();
+    let required: Vec<&String> = groups
+        .iter()
+        .filter(|group| matches_any_prefix(path, &group.paths))
+        .flat_map(|group| &group.scopes)
+        .collect();
+    if required.is_empty() {
+        return next.run(request).await;
+    }
+
+    let denied = AuditEvent::new("security.authorization")
+        .resource(format!("{} {}", request.method(), path))
+        .outcome(AuditOutcome::Denied)
+        .metadata("required_scopes", required.iter().map(|scope| scope.as_str()).collect::<Vec<_>>());
+    match request.extensions().get::<Principal>() {
+        None => {
+            audit(denied).await;
+            error_response(StatusCode::UNAUTHORIZED, "未认证")
+        }
+        Some(principal)
+            if required
+                .iter()
+                .all(|scope| principal.has_authority(&format!("{}{}", SCOPE_PREFIX, scope))) =>
+        {
+            next.run(request).await
+        }
+        Some(principal) => {
+            tracing::debug!("主体 {} 缺少路由组要求的授权范围 {:?}", principal.name, required);
+            audit(denied.actor(principal.name.clone())).await;
+            error_response(StatusCode::FORBIDDEN, "权限不足")
+        }
+    }
+}
+
+/// 是否为站内的相对地址，登录后只允许重定向到站内，防止开放重定向
+///
+/// 拒绝包含空白或控制字符的地址（浏览器会去掉其中的制表符和换行，且无法写入响应头），
+/// 并要求按相对地址解析后没有协议和主机部分
+pub(crate) fn is_local_redirect(redirect: &str) -> bool {
+    if !redirect.starts_with('/') || redirect.starts_with("//") || redirect.contains('\\') {
+        return false;
+    }
+    if redirect.chars().any(|c| c.is_control() || c.is_whitespace()) {
+        return false;
+    }
+    if !matches!(url::Url::parse(redirect), Err(url::ParseError::RelativeUrlWithoutBase)) {
+        return false;
+    }
+    let base = url::Url::parse("http://localhost/").expect("有效的基础地址");
+    base.join(redirect).is_ok_and(|url| {
+        url.scheme() == "http"
+            && url.host_str() == Some("localhost")
+            && url.port().is_none()
+            && url.username().is_empty()
+            && url.password().is_none()
+    })
+}
+
+/// 判断请求路径是否匹配路径前缀列表
+///
+/// 前缀以 `/` 结尾时匹配其下的所有路径，也匹配去掉末尾 `/` 的路径本身
+pub(crate) fn matches_any_prefix(path: &str, prefixes: &[String]) -> bool {
+    prefixes.iter().any(|prefix| {
+        path.starts_with(prefix.as_str())
+            || prefix
+                .strip_suffix('/')
+                .is_some_and(|prefix| path == prefix)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试只允许站内重定向
+    #[test]
+    fn test_is_local_redirect() {
+        assert!(is_local_redirect("/"));
+        assert!(is_local_redirect("/orders?page=2"));
+        assert!(is_local_redirect("/orders#list"));
+
+        assert!(!is_local_redirect("orders"));
+        assert!(!is_local_redirect("https://evil.example.com"));
+        assert!(!is_local_redirect("//evil.example.com"));
+        assert!(!is_local_redirect("/\\/evil.example.com"));
+        assert!(!is_local_redirect("/\\evil.example.com"));
+        assert!(!is_local_redirect("/\t/evil.example.com"));
+        assert!(!is_local_redirect("/\n/evil.example.com"));
+        assert!(!is_local_redirect("/orders\r\nSet-Cookie: a=b"));
+        assert!(!is_local_redirect("/ /evil.example.com"));
+    }
+}
@@ -0,0 +1,367 @@
+//! API 密钥认证模块
+//!
+//! 从请求头（或查询参数）读取 API 密钥，按 SHA-256 哈希在 [`ApiKeyStore`] 中查找：
+//! - 存储中只保存密钥的哈希，原始密钥只在创建时返回给调用方一次
+//! - 每个密钥有自己的授权范围，映射为 `SCOPE_` 开头的权限
+//! - 每个密钥可以设置独立的限流，超过时返回 429，响应中带有 `X-RateLimit-*` 请求头
+//!
+//! 哪些路由使用 API 密钥认证由 `[[security.routes]]` 中 `auth = "api_key"` 的路由组决定
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::extract::Request;
+use axum::http::{HeaderName, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use axum::Router;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use ring::rand::{SecureRandom, SystemRandom};
+use rspring_core::auditing::{audit, AuditEvent, AuditOutcome};
+use rspring_core::security::{with_principal, Principal};
+use rspring_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+use super::matches_any_prefix;
+use crate::exception::error_response;
+use crate::rate_limit::{RateLimit, RateLimiter};
+
+/// 授权范围映射为权限时添加的前缀，与 OAuth2 令牌的默认映射一致
+pub const SCOPE_PREFIX: &str = "SCOPE_";
+
+/// API 密钥认证配置
+///
+/// # 示例
+/// ```toml
+/// [security.api_key]
+/// header = "X-API-Key"
+/// query_param = "api_key"
+///
+/// [[security.api_key.keys]]
+/// id = "partner-acme"
+/// key_hash = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+/// scopes = ["orders:read"]
+/// rate_limit = { requests = 100, period = "1m" }
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ApiKeyConfig {
+    /// 读取密钥的请求头
+    ///
+    /// # 默认值
+    /// `"X-API-Key"`
+    #[serde(default = "default_header")]
+    pub header: String,
+    /// 读取密钥的查询参数（可选），请求头中没有密钥时使用
+    ///
+    /// 查询参数容易出现在访问日志和浏览器历史中，只建议在无法设置请求头的场景使用
+    #[serde(default)]
+    pub query_param: Option<String>,
+    /// 配置文件中的密钥，未通过 `SecurityConfig::apply_with_api_key_store` 指定存储时使用
+    #[serde(default)]
+    pub keys: Vec<ApiKey>,
+}
+
+impl Default for ApiKeyConfig {
+    fn default() -> Self {
+        Self {
+            header: default_header(),
+            query_param: None,
+            keys: Vec::new(),
+        }
+    }
+}
+
+/// API 密钥
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ApiKey {
+    /// 密钥 ID，作为认证主体的名称
+    pub id: String,
+    /// 密钥的 SHA-256 哈希（小写十六进制），通过 [`hash_api_key`] 计算
+    pub key_hash: String,
+    /// 授权范围
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// 限流（可选），未设置时不限流
+    #[serde(default)]
+    pub rate_limit: Option<RateLimit>,
+    /// 过期时间（可选）
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    /// 是否已过期
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= rspring_core::clock::now())
+    }
+
+    /// 映射为认证主体，授权范围映射为 `SCOPE_` 开头的权限
+    pub fn principal(&self) -> Principal {
+        Principal::new(&self.id).with_authorities(
+            self.scopes
+                .iter()
+                .map(|scope| format!("{}{}", SCOPE_PREFIX, scope)),
+        )
+    }
+}
+
+/// 认证成功的 API 密钥 ID，作为请求扩展供限流等按密钥区分调用方
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ApiKeyId(pub String);
+
+/// API 密钥存储特征
+///
+/// # 示例
+/// ```rust
+/// struct DbApiKeyStore {
+///     pool: MySqlPool,
+/// }
+///
+/// #[async_trait]
+/// impl ApiKeyStore for DbApiKeyStore {
+///     async fn find_by_hash(&self, key_hash: &str) -> Result<Option<ApiKey>> {
+///         // SELECT ... FROM api_keys WHERE key_hash = ?
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait ApiKeyStore: Send + Sync {
+    /// 按密钥哈希查找密钥
+    async fn find_by_hash(&self, key_hash: &str) -> Result<Option<ApiKey>>;
+}
+
+/// 内存中的 API 密钥存储
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryApiKeyStore {
+    /// 按哈希索引的密钥
+    keys: HashMap<String, ApiKey>,
+}
+
+impl InMemoryApiKeyStore {
+    /// 创建包含指定密钥的存储
+    pub fn new(keys: impl IntoIterator<Item = ApiKey>) -> Self {
+        Self {
+            keys: keys
+                .into_iter()
+                .map(|key| (key.key_hash.to_ascii_lowercase(), key))
+                .collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl ApiKeyStore for InMemoryApiKeyStore {
+    async fn find_by_hash(&self, key_hash: &str) -> Result<Option<ApiKey>> {
+        Ok(self.keys.get(key_hash).cloned())
+    }
+}
+
+/// 计算 API 密钥的 SHA-256 哈希（小写十六进制）
+pub fn hash_api_key(raw: &str) -> String {
+    ring::digest::digest(&ring::digest::SHA256, raw.as_bytes())
+        .as_ref()
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{:02x}", byte);
+            hex
+        })
+}
+
+/// 生成新的 API 密钥，返回原始密钥和它的哈希
+///
+/// 原始密钥只应返回给调用方一次，存储中只保存哈希
+///
+/// # 错误
+/// 生成随机数失败时返回错误
+pub fn generate_api_key() -> Result<(String, String)> {
+    let mut bytes = [0u8; 32];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| Error::internal("生成随机数失败"))?;
+    let raw = URL_SAFE_NO_PAD.encode(bytes);
+    let hash = hash_api_key(&raw);
+    Ok((raw, hash))
+}
+
+/// API 密钥认证器
+pub struct ApiKeyAuthenticator {
+    /// 读取密钥的请求头
+    header: HeaderName,
+    /// 读取密钥的查询参数
+    query_param: Option<String>,
+    /// 使用 API 密钥认证的路径前缀
+    paths: Vec<String>,
+    /// 密钥存储
+    store: Arc<dyn ApiKeyStore>,
+    /// 按密钥 ID 计数的限流器
+    limiter: RateLimiter,
+}
+
+impl ApiKeyAuthenticator {
+    /// 创建认证器，只对 `paths` 下的请求要求 API 密钥
+    ///
+    /// # 错误
+    /// 请求头名称无效时返回验证错误
+    pub fn new(config: &ApiKeyConfig, paths: Vec<String>, store: Arc<dyn ApiKeyStore>) -> Result<Self> {
+        let header = HeaderName::from_bytes(config.header.as_bytes())
+            .map_err(|_| Error::validation(format!("无效的 security.api_key.header: {}", config.header)))?;
+        Ok(Self {
+            header,
+            query_param: config.query_param.clone(),
+            paths,
+            store,
+            limiter: RateLimiter::new(),
+        })
+    }
+
+    /// 将认证中间件应用到路由
+    pub fn apply<S>(self: Arc<Self>, router: Router<S>) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        router.layer(middleware::from_fn(move |request: Request, next: Next| {
+            authenticate(self.clone(), request, next)
+        }))
+    }
+
+    /// 从请求头或查询参数读取密钥
+    fn extract(&self, request: &Request) -> Option<String> {
+        if let Some(value) = request.headers().get(&self.header) {
+            return value
+                .to_str()
+                .ok()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string);
+        }
+        let query_param = self.query_param.as_deref()?;
+        url::form_urlencoded::parse(request.uri().query()?.as_bytes())
+            .find(|(name, _)| name == query_param)
+            .map(|(_, value)| value.into_owned())
+            .filter(|value| !value.is_empty())
+    }
+}
+
+impl std::fmt::Debug for ApiKeyAuthenticator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiKeyAuthenticator")
+            .field("header", &self.header)
+            .field("query_param", &self.query_param)
+            .field("paths", &self.paths)
+            .finish()
+    }
+}
+
+/// API 密钥认证中间件
+async fn authenticate(authenticator: Arc<ApiKeyAuthenticator>, mut request: Request, next: Next) -> Response {
+    if !matches_any_prefix(request.uri().path(), &authenticator.paths) {
+        return next.run(request).await;
+    }
+    let Some(raw) = authenticator.extract(&request) else {
+        return error_response(StatusCode::UNAUTHORIZED, "缺少 API 密钥");
+    };
+
+    let key = match authenticator.store.find_by_hash(&hash_api_key(&raw)).await {
+        Ok(Some(key)) if !key.is_expired() => key,
+        Ok(_) => {
+            let event = AuditEvent::new("security.authentication")
+                .resource(format!("{} {}", request.method(), request.uri().path()))
+                .outcome(AuditOutcome::Failure)
+                .metadata("method", "api_key");
+            audit(event).await;
+            return error_response(StatusCode::UNAUTHORIZED, "API 密钥无效或已过期");
+        }
+        Err(e) => {
+            tracing::error!("查找 API 密钥失败: {}", e);
+            return error_response(StatusCode::INTERNAL_SERVER_ERROR, "内部服务器错误");
+        }
+    };
+    let decision = key
+        .rate_limit
+        .map(|limit| authenticator.limiter.acquire(&key.id, limit));
+    if let Some(decision) = decision.filter(|decision| !decision.allowed) {
+        tracing::debug!("API 密钥 {} 超过限流", key.id);
+        return decision.rejection();
+    }
+
+    let principal = key.principal();
+    request.extensions_mut().insert(principal.clone());
+    request.extensions_mut().insert(ApiKeyId(key.id));
+    let mut response = with_principal(principal, next.run(request)).await;
+    if let Some(decision) = decision {
+        decision.write_headers(response.headers_mut());
+    }
+    response
+}
+
+// 默认值函数
+
+fn default_header() -> String {
+    "X-API-Key".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::SecurityConfig;
+    use axum::body::Body;
+    use axum::http::header;
+    use axum::routing::get;
+    use tower::ServiceExt;
+
+    /// 测试按路由组要求 API 密钥、检查授权范围和限流
+    #[tokio::test]
+    async fn test_api_key_route_group() {
+        let config: SecurityConfig = serde_json::from_value(serde_json::json!({
+            "api_key": {
+                "query_param": "api_key",
+                "keys": [
+                    {
+                        "id": "acme",
+                        "key_hash": hash_api_key("acme-secret"),
+                        "scopes": ["orders:read"],
+                        "rate_limit": { "requests": 2, "period": "1h" },
+                    },
+                    { "id": "reporting", "key_hash": hash_api_key("report-secret"), "scopes": ["reports:read"] },
+                ],
+            },
+            "routes": [{ "paths": ["/partner/"], "auth": "api_key", "scopes": ["orders:read"] }],
+        }))
+        .unwrap();
+        let router = Router::new()
+            .route("/partner/orders", get(|| async { rspring_core::current_principal().unwrap().name }))
+            .route("/health", get(|| async { "UP" }));
+        let app = config.apply(router).await.unwrap();
+
+        let call = |uri: &str, key: Option<&str>| {
+            let mut request = Request::builder().uri(uri);
+            if let Some(key) = key {
+                request = request.header("X-API-Key", key);
+            }
+            app.clone().oneshot(request.body(Body::empty()).unwrap())
+        };
+
+        assert_eq!(call("/health", None).await.unwrap().status(), StatusCode::OK);
+        assert_eq!(call("/partner/orders", None).await.unwrap().status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(call("/partner/orders", Some("wrong")).await.unwrap().status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            call("/partner/orders", Some("report-secret")).await.unwrap().status(),
+            StatusCode::FORBIDDEN
+        );
+
+        let response = call("/partner/orders", Some("acme-secret")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"acme");
+        assert_eq!(call("/partner/orders?api_key=acme-secret", None).await.unwrap().status(), StatusCode::OK);
+
+        let response = call("/partner/orders", Some("acme-secret")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(response.headers().contains_key(header::RETRY_AFTER));
+        assert_eq!(response.headers()[crate::rate_limit::X_RATELIMIT_REMAINING], "0");
+    }
+}
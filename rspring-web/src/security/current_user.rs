@@ -0,0 +1,146 @@
+//! 当前用户提取器模块
+//!
+//! 为控制器方法提供认证主体提取器：
+//! - [`AuthPrincipal`] 提取认证主体
+//! - [`CurrentUser<T>`] 通过 [`FromPrincipal`] 将认证主体映射为应用的用户类型
+//!
+//! 未认证时返回 401；需要可选的认证主体时使用 `Option<AuthPrincipal>`。
+//! 服务中读取当前用户使用可注入的 `SecurityContext`
+
+use std::ops::Deref;
+
+use axum::async_trait;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use rspring_core::security::{current_principal, FromPrincipal, Principal};
+use rspring_core::Error;
+
+use crate::exception::WebError;
+
+/// 认证主体提取器
+///
+/// 读取认证中间件设置的请求扩展，未设置时读取任务本地的认证主体
+///
+/// # 示例
+/// ```rust
+/// #[GetMapping("/profile")]
+/// pub async fn profile(&self, principal: AuthPrincipal) -> WebResult<ApiResponse<String>> {
+///     Ok(ApiResponse::success(principal.name.clone()))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthPrincipal(pub Principal);
+
+impl Deref for AuthPrincipal {
+    type Target = Principal;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for AuthPrincipal {
+    type Rejection = WebError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<Principal>()
+            .cloned()
+            .or_else(current_principal)
+            .map(Self)
+            .ok_or(WebError(Error::Unauthorized))
+    }
+}
+
+/// 当前用户提取器
+///
+/// 将认证主体映射为应用的用户类型，映射失败时按错误类型返回响应
+///
+/// # 示例
+/// ```rust
+/// #[GetMapping("/orders")]
+/// pub async fn my_orders(&self, CurrentUser(user): CurrentUser<AppUser>) -> WebResult<ApiResponse<Vec<Order>>> {
+///     Ok(ApiResponse::success(self.order_service.find_by_owner(user.id).await?))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurrentUser<T>(pub T);
+
+impl<T> Deref for CurrentUser<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for CurrentUser<T>
+where
+    T: FromPrincipal,
+    S: Send + Sync,
+{
+    type Rejection = WebError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let AuthPrincipal(principal) = AuthPrincipal::from_request_parts(parts, state).await?;
+        Ok(Self(T::from_principal(&principal).await?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::extract::Request;
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use axum::{Extension, Router};
+    use rspring_core::Result;
+    use tower::ServiceExt;
+
+    /// 应用的用户类型
+    struct AppUser {
+        id: u64,
+    }
+
+    #[async_trait]
+    impl FromPrincipal for AppUser {
+        async fn from_principal(principal: &Principal) -> Result<Self> {
+            principal
+                .name
+                .strip_prefix("user-")
+                .and_then(|id| id.parse().ok())
+                .map(|id| Self { id })
+                .ok_or_else(|| Error::not_found(format!("用户 {}", principal.name)))
+        }
+    }
+
+    /// 测试提取认证主体并映射为用户类型
+    #[tokio::test]
+    async fn test_current_user() {
+        let router = Router::new()
+            .route("/name", get(|principal: AuthPrincipal| async move { principal.name.clone() }))
+            .route("/id", get(|CurrentUser(user): CurrentUser<AppUser>| async move { user.id.to_string() }))
+            .route(
+                "/optional",
+                get(|principal: Option<AuthPrincipal>| async move { principal.is_some().to_string() }),
+            );
+        let authenticated = |name: &str| router.clone().layer(Extension(Principal::new(name)));
+        let get = |uri: &str| Request::get(uri).body(Body::empty()).unwrap();
+
+        let response = router.clone().oneshot(get("/name")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = router.clone().oneshot(get("/optional")).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"false");
+
+        let response = authenticated("user-42").oneshot(get("/id")).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"42");
+        let response = authenticated("admin").oneshot(get("/id")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,659 @@
+//! 表单登录模块
+//!
+//! 为简单应用提供现成的登录、登出和当前用户端点，无需自己编写控制器：
+//! - 用户从可插拔的 [`UserDetailsService`] 加载，密码使用 [`DelegatingPasswordEncoder`] 校验，
+//!   校验成功且哈希需要升级时通过 [`UserDetailsService::update_password`] 保存新的哈希
+//! - 登录接受表单（`application/x-www-form-urlencoded`）或 JSON 请求体，
+//!   表单登录按结果重定向，JSON 登录返回当前用户或 401
+//! - 认证主体保存在加密的会话 Cookie 中，密钥在 `[web.cookies]` 中配置
+//!
+//! 登录页面（`GET` 登录路径）由应用自己提供
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use axum::extract::{FromRequest, FromRequestParts, Request};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Redirect, Response};
+use axum::routing::{get, post};
+use axum::{Extension, Form, Json, Router};
+use rspring_core::auditing::{audit, AuditEvent, AuditOutcome};
+use rspring_core::config::properties::Configuration;
+use rspring_core::config::ConfigurationManager;
+use rspring_core::security::password::{
+    DelegatingPasswordEncoder, PasswordEncoder, PasswordVerification,
+};
+use rspring_core::security::{with_principal, Principal};
+use rspring_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+use super::{is_local_redirect, matches_any_prefix};
+use crate::cookies::{CookieProtection, CookieValue, Cookies};
+use crate::exception::error_response;
+
+/// 用户信息
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct UserDetails {
+    /// 用户名
+    pub username: String,
+    /// 编码后的密码
+    pub password: String,
+    /// 拥有的权限，角色以 `ROLE_` 开头
+    #[serde(default)]
+    pub authorities: Vec<String>,
+    /// 是否启用，停用的用户无法登录
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+impl UserDetails {
+    /// 创建启用的用户
+    pub fn new(username: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+            password: password.into(),
+            authorities: Vec::new(),
+            enabled: true,
+        }
+    }
+
+    /// 设置权限
+    pub fn with_authorities<I, S>(mut self, authorities: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.authorities = authorities.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// 映射为认证主体
+    pub fn principal(&self) -> Principal {
+        Principal::new(self.username.clone()).with_authorities(self.authorities.iter().cloned())
+    }
+}
+
+/// 用户信息服务特征
+///
+/// # 示例
+/// ```rust
+/// struct DbUserDetailsService {
+///     pool: MySqlPool,
+/// }
+///
+/// #[async_trait]
+/// impl UserDetailsService for DbUserDetailsService {
+///     async fn load_user_by_username(&self, username: &str) -> Result<Option<UserDetails>> {
+///         // SELECT ... FROM users WHERE username = ?
+///     }
+///
+///     async fn update_password(&self, username: &str, encoded: &str) -> Result<()> {
+///         // UPDATE users SET password = ? WHERE username = ?
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait UserDetailsService: Send + Sync {
+    /// 按用户名加载用户
+    async fn load_user_by_username(&self, username: &str) -> Result<Option<UserDetails>>;
+
+    /// 保存重新编码的密码，默认不保存
+    async fn update_password(&self, _username: &str, _encoded: &str) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// 内存中的用户信息服务
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryUserDetailsService {
+    /// 按用户名索引的用户
+    users: HashMap<String, UserDetails>,
+}
+
+impl InMemoryUserDetailsService {
+    /// 创建包含指定用户的服务
+    pub fn new(users: impl IntoIterator<Item = UserDetails>) -> Self {
+        Self {
+            users: users
+                .into_iter()
+                .map(|user| (user.username.clone(), user))
+                .collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl UserDetailsService for InMemoryUserDetailsService {
+    async fn load_user_by_username(&self, username: &str) -> Result<Option<UserDetails>> {
+        Ok(self.users.get(username).cloned())
+    }
+}
+
+/// 表单登录配置
+///
+/// # 示例
+/// ```toml
+/// [security.login]
+/// protected_paths = ["/app/"]
+/// session_timeout = "2h"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct LoginConfig {
+    /// 登录路径（`POST`）
+    ///
+    /// # 默认值
+    /// `"/login"`
+    #[serde(default = "default_login_path")]
+    pub login_path: String,
+    /// 登出路径（`POST`）
+    ///
+    /// # 默认值
+    /// `"/logout"`
+    #[serde(default = "default_logout_path")]
+    pub logout_path: String,
+    /// 获取当前用户的路径（`GET`）
+    ///
+    /// # 默认值
+    /// `"/me"`
+    #[serde(default = "default_me_path")]
+    pub me_path: String,
+    /// 需要登录的路径前缀
+    ///
+    /// 未登录的浏览器请求重定向到登录路径，其他请求返回 401
+    #[serde(default)]
+    pub protected_paths: Vec<String>,
+    /// 表单登录成功后未指定返回页面时重定向的地址
+    ///
+    /// # 默认值
+    /// `"/"`
+    #[serde(default = "default_success_url")]
+    pub default_success_url: String,
+    /// 表单登录失败后重定向的地址
+    ///
+    /// # 默认值
+    /// `"/login?error"`
+    #[serde(default = "default_failure_url")]
+    pub failure_url: String,
+    /// 浏览器登出后重定向的地址
+    ///
+    /// # 默认值
+    /// `"/login?logout"`
+    #[serde(default = "default_logout_success_url")]
+    pub logout_success_url: String,
+    /// 登录会话的有效期
+    ///
+    /// # 默认值
+    /// `"8h"`
+    #[serde(
+        default = "default_session_timeout",
+        with = "rspring_core::config::duration"
+    )]
+    pub session_timeout: Duration,
+}
+
+impl Default for LoginConfig {
+    fn default() -> Self {
+        Self {
+            login_path: default_login_path(),
+            logout_path: default_logout_path(),
+            me_path: default_me_path(),
+            protected_paths: Vec::new(),
+            default_success_url: default_success_url(),
+            failure_url: default_failure_url(),
+            logout_success_url: default_logout_success_url(),
+            session_timeout: default_session_timeout(),
+        }
+    }
+}
+
+impl LoginConfig {
+    /// 从配置管理器读取 `[security.login]` 章节
+    ///
+    /// 未配置时使用默认值
+    pub fn load(config: &ConfigurationManager) -> Result<Self> {
+        if config.contains_key("security.login") {
+            config.get_section("security.login")
+        } else {
+            Ok(Self::default())
+        }
+    }
+}
+
+impl Configuration for LoginConfig {}
+
+/// 表单登录
+///
+/// # 示例
+/// ```rust
+/// let encoder = Arc::new(PasswordEncoderConfig::load(&config)?.build()?);
+/// let users = Arc::new(DbUserDetailsService::new(pool.clone()));
+/// let login = FormLogin::new(LoginConfig::load(&config)?, users, encoder)?;
+/// let router = Arc::new(login).apply(router);
+/// ```
+pub struct FormLogin {
+    /// 配置
+    config: LoginConfig,
+    /// 用户信息服务
+    users: Arc<dyn UserDetailsService>,
+    /// 密码编码器
+    encoder: Arc<DelegatingPasswordEncoder>,
+    /// 用户不存在时用于校验的哈希，使响应时间与用户存在时一致
+    dummy_password: String,
+}
+
+impl FormLogin {
+    /// 创建表单登录
+    ///
+    /// # 错误
+    /// 路径无效或编码密码失败时返回错误
+    pub fn new(
+        config: LoginConfig,
+        users: Arc<dyn UserDetailsService>,
+        encoder: Arc<DelegatingPasswordEncoder>,
+    ) -> Result<Self> {
+        for path in [&config.login_path, &config.logout_path, &config.me_path] {
+            if !path.starts_with('/') {
+                return Err(Error::validation(format!(
+                    "security.login 中的路径必须以 / 开头: {}",
+                    path
+                )));
+            }
+        }
+        let dummy_password = encoder.encode("rspring-dummy-password")?;
+        Ok(Self {
+            config,
+            users,
+            encoder,
+            dummy_password,
+        })
+    }
+
+    /// 将登录、登出和当前用户路由以及会话认证中间件应用到路由
+    pub fn apply<S>(self: Arc<Self>, router: Router<S>) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let login = self.clone();
+        let login_handler = move |cookies: Cookies, request: Request| {
+            let login = login.clone();
+            async move { login.login(cookies, request).await }
+        };
+        let logout_success_url = self.config.logout_success_url.clone();
+        let logout_handler = move |cookies: Cookies, headers: HeaderMap| {
+            let target = logout_success_url.clone();
+            async move {
+                if cookies.value::<UserSession>().is_some() {
+                    audit(AuditEvent::new("security.logout")).await;
+                }
+                let cookies = cookies.remove_value::<UserSession>();
+                if accepts_html(&headers) {
+                    (cookies, Redirect::to(&target)).into_response()
+                } else {
+                    (cookies, StatusCode::NO_CONTENT).into_response()
+                }
+            }
+        };
+        let me_handler = |principal: Option<Extension<Principal>>| async move {
+            match principal {
+                Some(Extension(principal)) => Json(principal).into_response(),
+                None => error_response(StatusCode::UNAUTHORIZED, "未登录"),
+            }
+        };
+
+        router
+            .route(&self.config.login_path, post(login_handler))
+            .route(&self.config.logout_path, post(logout_handler))
+            .route(&self.config.me_path, get(me_handler))
+            .layer(middleware::from_fn(move |request: Request, next: Next| {
+                authenticate(self.clone(), request, next)
+            }))
+    }
+
+    /// 校验用户名和密码，成功时返回认证主体
+    ///
+    /// # 错误
+    /// 加载用户或校验密码失败时返回错误
+    pub async fn authenticate(&self, username: &str, password: &str) -> Result<Option<Principal>> {
+        let user = self
+            .users
+            .load_user_by_username(username)
+            .await?
+            .filter(|user| user.enabled);
+        let encoded = user
+            .as_ref()
+            .map_or_else(|| self.dummy_password.clone(), |user| user.password.clone());
+
+        let encoder = self.encoder.clone();
+        let raw = password.to_string();
+        let verification =
+            tokio::task::spawn_blocking(move || encoder.verify_and_upgrade(&raw, &encoded))
+                .await
+                .map_err(|e| Error::internal(format!("校验密码失败: {}", e)))??;
+
+        let Some(user) = user else {
+            return Ok(None);
+        };
+        match verification {
+            PasswordVerification::Mismatch => Ok(None),
+            PasswordVerification::Matched => Ok(Some(user.principal())),
+            PasswordVerification::Upgraded(encoded) => {
+                if let Err(e) = self.users.update_password(&user.username, &encoded).await {
+                    tracing::warn!("保存用户 {} 重新编码的密码失败: {}", user.username, e);
+                }
+                Ok(Some(user.principal()))
+            }
+        }
+    }
+
+    /// 处理登录请求
+    async fn login(&self, cookies: Cookies, request: Request) -> Response {
+        let json = request
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|content_type| content_type.starts_with("application/json"));
+        let credentials = if json {
+            Json::<LoginRequest>::from_request(request, &())
+                .await
+                .map(|Json(credentials)| credentials)
+                .map_err(|e| e.body_text())
+        } else {
+            Form::<LoginRequest>::from_request(request, &())
+                .await
+                .map(|Form(credentials)| credentials)
+                .map_err(|e| e.body_text())
+        };
+        let credentials = match credentials {
+            Ok(credentials) => credentials,
+            Err(message) => return error_response(StatusCode::BAD_REQUEST, &message),
+        };
+
+        let principal = match self
+            .authenticate(&credentials.username, &credentials.password)
+            .await
+        {
+            Ok(Some(principal)) => principal,
+            Ok(None) => {
+                tracing::debug!("用户 {} 登录失败", credentials.username);
+                let event = AuditEvent::new("security.login")
+                    .actor(credentials.username.clone())
+                    .outcome(AuditOutcome::Failure);
+                audit(event).await;
+                return if json {
+                    error_response(StatusCode::UNAUTHORIZED, "用户名或密码错误")
+                } else {
+                    Redirect::to(&self.config.failure_url).into_response()
+                };
+            }
+            Err(e) => {
+                tracing::error!("用户 {} 登录时发生错误: {}", credentials.username, e);
+                return error_response(StatusCode::INTERNAL_SERVER_ERROR, "内部服务器错误");
+            }
+        };
+
+        audit(AuditEvent::new("security.login").actor(principal.name.clone())).await;
+
+        let session = UserSession {
+            principal: principal.clone(),
+            expires_at: rspring_core::clock::now().timestamp()
+                + self.config.session_timeout.as_secs() as i64,
+        };
+        let cookies = match cookies.set_value(&session) {
+            Ok(cookies) => cookies,
+            Err(e) => {
+                tracing::error!("设置登录会话失败: {}", e);
+                return error_response(StatusCode::INTERNAL_SERVER_ERROR, "内部服务器错误");
+            }
+        };
+        if json {
+            (cookies, Json(principal)).into_response()
+        } else {
+            let target = credentials
+                .redirect
+                .filter(|redirect| is_local_redirect(redirect))
+                .unwrap_or_else(|| self.config.default_success_url.clone());
+            (cookies, Redirect::to(&target)).into_response()
+        }
+    }
+}
+
+impl std::fmt::Debug for FormLogin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FormLogin")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+/// 登录请求体
+#[derive(Debug, Deserialize)]
+struct LoginRequest {
+    /// 用户名
+    username: String,
+    /// 密码
+    password: String,
+    /// 登录后返回的页面（仅表单登录）
+    #[serde(default)]
+    redirect: Option<String>,
+}
+
+/// 登录会话，保存在加密 Cookie 中
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+struct UserSession {
+    /// 认证主体
+    principal: Principal,
+    /// 过期时间（Unix 时间戳，秒）
+    expires_at: i64,
+}
+
+impl CookieValue for UserSession {
+    const NAME: &'static str = "rspring_login";
+    const PROTECTION: CookieProtection = CookieProtection::Private;
+}
+
+/// 会话认证中间件
+///
+/// 有效的登录会话设置请求扩展和任务本地的认证主体；已由其他认证方式认证的请求直接放行
+async fn authenticate(login: Arc<FormLogin>, request: Request, next: Next) -> Response {
+    if request.extensions().get::<Principal>().is_some() {
+        return next.run(request).await;
+    }
+    let (mut parts, body) = request.into_parts();
+    let Ok(cookies) = Cookies::from_request_parts(&mut parts, &()).await;
+    let principal = cookies
+        .value::<UserSession>()
+        .filter(|session| session.expires_at > rspring_core::clock::now().timestamp())
+        .map(|session| session.principal);
+
+    let mut request = Request::from_parts(parts, body);
+    match principal {
+        Some(principal) => {
+            request.extensions_mut().insert(principal.clone());
+            with_principal(principal, next.run(request)).await
+        }
+        None if matches_any_prefix(request.uri().path(), &login.config.protected_paths) => {
+            if accepts_html(request.headers()) {
+                let target = request
+                    .uri()
+                    .path_and_query()
+                    .map_or("/", |path| path.as_str());
+                let redirect: String =
+                    url::form_urlencoded::byte_serialize(target.as_bytes()).collect();
+                Redirect::to(&format!(
+                    "{}?redirect={}",
+                    login.config.login_path, redirect
+                ))
+                .into_response()
+            } else {
+                error_response(StatusCode::UNAUTHORIZED, "未登录")
+            }
+        }
+        None => next.run(request).await,
+    }
+}
+
+/// 请求是否来自浏览器页面
+fn accepts_html(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|accept| accept.contains("text/html"))
+}
+
+// 默认值函数
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_login_path() -> String {
+    "/login".to_string()
+}
+
+fn default_logout_path() -> String {
+    "/logout".to_string()
+}
+
+fn default_me_path() -> String {
+    "/me".to_string()
+}
+
+fn default_success_url() -> String {
+    "/".to_string()
+}
+
+fn default_failure_url() -> String {
+    "/login?error".to_string()
+}
+
+fn default_logout_success_url() -> String {
+    "/login?logout".to_string()
+}
+
+fn default_session_timeout() -> Duration {
+    Duration::from_secs(8 * 60 * 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use rspring_core::security::password::Pbkdf2PasswordEncoder;
+    use tower::ServiceExt;
+
+    /// 测试 JSON 和表单登录、会话认证和登出
+    #[tokio::test]
+    async fn test_login_logout_me() {
+        let encoder = Arc::new(DelegatingPasswordEncoder::new(Arc::new(
+            Pbkdf2PasswordEncoder::new(1000).unwrap(),
+        )));
+        let users = Arc::new(InMemoryUserDetailsService::new([UserDetails::new(
+            "alice",
+            encoder.encode("secret").unwrap(),
+        )
+        .with_authorities(["ROLE_USER"])]));
+        let config = LoginConfig {
+            protected_paths: vec!["/app/".to_string()],
+            ..LoginConfig::default()
+        };
+        let login = FormLogin::new(config, users, encoder).unwrap();
+        let router = Router::new().route(
+            "/app/home",
+            get(|| async { rspring_core::current_principal().unwrap().name }),
+        );
+        let app = Arc::new(login).apply(router);
+
+        let send = |request: Request<Body>| {
+            let app = app.clone();
+            async move { app.oneshot(request).await.unwrap() }
+        };
+        let json_login = |password: &str| {
+            Request::post("/login")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(
+                    serde_json::json!({ "username": "alice", "password": password }).to_string(),
+                ))
+                .unwrap()
+        };
+
+        let response = send(json_login("wrong")).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = send(Request::get("/app/home").body(Body::empty()).unwrap()).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = send(
+            Request::get("/app/home")
+                .header(header::ACCEPT, "text/html")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(
+            response.headers()[header::LOCATION],
+            "/login?redirect=%2Fapp%2Fhome"
+        );
+
+        let response = send(json_login("secret")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let cookie = response.headers()[header::SET_COOKIE].to_str().unwrap();
+        let session = cookie.split(';').next().unwrap().to_string();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let principal: Principal = serde_json::from_slice(&body).unwrap();
+        assert!(principal.has_role("USER"));
+
+        let response = send(
+            Request::get("/me")
+                .header(header::COOKIE, &session)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = send(
+            Request::get("/app/home")
+                .header(header::COOKIE, &session)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"alice");
+        let response = send(Request::get("/me").body(Body::empty()).unwrap()).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let form_login = |password: &str| {
+            Request::post("/login")
+                .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .body(Body::from(format!(
+                    "username=alice&password={}&redirect=%2Fapp%2Fhome",
+                    password
+                )))
+                .unwrap()
+        };
+        let response = send(form_login("secret")).await;
+        assert_eq!(response.headers()[header::LOCATION], "/app/home");
+        assert!(response.headers().contains_key(header::SET_COOKIE));
+        let response = send(form_login("wrong")).await;
+        assert_eq!(response.headers()[header::LOCATION], "/login?error");
+
+        let response = send(
+            Request::post("/logout")
+                .header(header::COOKIE, &session)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let cookie = response.headers()[header::SET_COOKIE].to_str().unwrap();
+        assert!(cookie.starts_with("rspring_login=;"));
+    }
+}
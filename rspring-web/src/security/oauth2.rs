@@ -0,0 +1,898 @@
+//! OAuth2 / OIDC 模块
+//!
+//! 基于 OpenID Connect 身份提供方（如 Keycloak、Auth0、Azure AD）提供两种认证：
+//! - 资源服务器：校验 `Authorization: Bearer` 中的 JWT 访问令牌，签名密钥从提供方的 JWKS 获取，
+//!   遇到未知的 `kid` 时刷新，以支持密钥轮换
+//! - 授权码登录：重定向到提供方登录，使用 state、nonce 和 PKCE 防止伪造和重放，
+//!   回调中换取令牌、校验 id_token 并合并 UserInfo 声明，认证主体保存在加密的会话 Cookie 中
+//!
+//! 令牌声明按 [`OAuth2Config`] 中的声明名称映射为 [`Principal`] 的名称和权限
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::extract::{FromRequestParts, Query, Request};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Redirect, Response};
+use axum::routing::get;
+use axum::Router;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use jsonwebtoken::jwk::{Jwk, JwkSet};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use ring::rand::{SecureRandom, SystemRandom};
+use rspring_core::auditing::{audit, AuditEvent, AuditOutcome};
+use rspring_core::security::{with_principal, Principal};
+use rspring_core::{Error, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tokio::sync::RwLock;
+
+use super::{is_local_redirect, matches_any_prefix};
+use crate::cookies::{CookieProtection, CookieValue, Cookies};
+use crate::exception::error_response;
+
+/// OIDC 发现文档的路径
+const DISCOVERY_PATH: &str = "/.well-known/openid-configuration";
+
+/// 请求身份提供方的超时时间
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 遇到未知 `kid` 时两次刷新 JWKS 的最小间隔，避免伪造的令牌频繁触发刷新
+const JWKS_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 允许的签名算法，只接受非对称算法
+const ALLOWED_ALGORITHMS: &[Algorithm] = &[
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::PS384,
+    Algorithm::PS512,
+    Algorithm::ES256,
+    Algorithm::ES384,
+    Algorithm::EdDSA,
+];
+
+/// OAuth2 / OIDC 配置
+///
+/// # 示例
+/// ```toml
+/// [security.oauth2]
+/// issuer = "https://accounts.example.com/realms/demo"
+/// audience = "orders-api"
+/// username_claim = "preferred_username"
+/// roles_claim = "realm_access.roles"
+/// protected_paths = ["/api/", "/admin/"]
+///
+/// [security.oauth2.login]
+/// client_id = "orders-web"
+/// client_secret = "${OIDC_CLIENT_SECRET}"
+/// redirect_uri = "https://orders.example.com/login/oauth2/callback"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct OAuth2Config {
+    /// 身份提供方的签发者地址，元数据从 `{issuer}/.well-known/openid-configuration` 获取
+    pub issuer: String,
+    /// 访问令牌的受众（可选），设置后校验 `aud` 声明
+    #[serde(default)]
+    pub audience: Option<String>,
+    /// JWKS 地址（可选），未设置时使用元数据中的 `jwks_uri`
+    #[serde(default)]
+    pub jwks_uri: Option<String>,
+    /// 作为主体名称的声明
+    ///
+    /// # 默认值
+    /// `"sub"`
+    #[serde(default = "default_username_claim")]
+    pub username_claim: String,
+    /// 作为权限的声明，值可以是空格分隔的字符串或字符串数组
+    ///
+    /// # 默认值
+    /// `"scope"`
+    #[serde(default = "default_authorities_claim")]
+    pub authorities_claim: String,
+    /// 权限声明中每一项添加的前缀
+    ///
+    /// # 默认值
+    /// `"SCOPE_"`
+    #[serde(default = "default_authority_prefix")]
+    pub authority_prefix: String,
+    /// 作为角色的声明（可选），支持 `.` 分隔的嵌套路径，每一项映射为 `ROLE_` 开头的权限
+    #[serde(default)]
+    pub roles_claim: Option<String>,
+    /// 需要认证的路径前缀
+    ///
+    /// 未认证的请求返回 401；配置了登录时，浏览器请求重定向到登录地址
+    #[serde(default)]
+    pub protected_paths: Vec<String>,
+    /// 授权码登录配置（可选），对应 `[security.oauth2.login]`，未配置时只作为资源服务器
+    #[serde(default)]
+    pub login: Option<OAuth2LoginConfig>,
+}
+
+impl OAuth2Config {
+    /// 获取身份提供方元数据和 JWKS，并将认证中间件和登录路由应用到路由
+    ///
+    /// 认证中间件只作用于调用前已注册的路由
+    ///
+    /// # 错误
+    /// 获取元数据或 JWKS 失败、登录配置无效时返回错误
+    pub async fn apply<S>(&self, router: Router<S>) -> Result<Router<S>>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let provider = OidcProvider::discover(self.clone()).await?;
+        Arc::new(provider).apply(router)
+    }
+
+    /// 将令牌声明映射为认证主体
+    ///
+    /// # 错误
+    /// 缺少作为主体名称的声明时返回验证错误
+    pub fn principal_from_claims(&self, claims: &Map<String, Value>) -> Result<Principal> {
+        let name = claim(claims, &self.username_claim)
+            .and_then(Value::as_str)
+            .ok_or_else(|| Error::validation(format!("令牌缺少 {} 声明", self.username_claim)))?;
+
+        let mut authorities: Vec<String> = claim(claims, &self.authorities_claim)
+            .map(claim_values)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|authority| format!("{}{}", self.authority_prefix, authority))
+            .collect();
+        if let Some(roles_claim) = &self.roles_claim {
+            authorities.extend(
+                claim(claims, roles_claim)
+                    .map(claim_values)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|role| format!("ROLE_{}", role)),
+            );
+        }
+        Ok(Principal::new(name).with_authorities(authorities))
+    }
+}
+
+/// 授权码登录配置
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct OAuth2LoginConfig {
+    /// 客户端 ID
+    pub client_id: String,
+    /// 客户端密钥（可选），未设置时作为公共客户端只使用 PKCE
+    #[serde(default)]
+    pub client_secret: Option<String>,
+    /// 回调地址，需在身份提供方注册，其路径即回调路由
+    pub redirect_uri: String,
+    /// 请求的授权范围，必须包含 `openid`
+    ///
+    /// # 默认值
+    /// `["openid", "profile", "email"]`
+    #[serde(default = "default_scopes")]
+    pub scopes: Vec<String>,
+    /// 发起登录的路径，可通过 `?redirect=/path` 指定登录后返回的页面
+    ///
+    /// # 默认值
+    /// `"/oauth2/login"`
+    #[serde(default = "default_login_path")]
+    pub login_path: String,
+    /// 登出路径，清除会话后重定向到 `logout_success_url`
+    ///
+    /// # 默认值
+    /// `"/logout"`
+    #[serde(default = "default_logout_path")]
+    pub logout_path: String,
+    /// 登录成功后未指定返回页面时重定向的地址
+    ///
+    /// # 默认值
+    /// `"/"`
+    #[serde(default = "default_success_url")]
+    pub default_success_url: String,
+    /// 登出后重定向的地址
+    ///
+    /// # 默认值
+    /// `"/"`
+    #[serde(default = "default_success_url")]
+    pub logout_success_url: String,
+    /// 登录会话的有效期
+    ///
+    /// # 默认值
+    /// `"8h"`
+    #[serde(
+        default = "default_session_timeout",
+        with = "rspring_core::config::duration"
+    )]
+    pub session_timeout: Duration,
+    /// 是否从 UserInfo 端点获取声明并合并到 id_token 的声明中
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_user_info")]
+    pub user_info: bool,
+}
+
+impl OAuth2LoginConfig {
+    /// 校验配置并获取回调路由的路径
+    fn callback_path(&self) -> Result<String> {
+        if self.client_id.is_empty() {
+            return Err(Error::validation(
+                "security.oauth2.login.client_id 不能为空",
+            ));
+        }
+        if !self.scopes.iter().any(|scope| scope == "openid") {
+            return Err(Error::validation(
+                "security.oauth2.login.scopes 必须包含 openid",
+            ));
+        }
+        let redirect_uri = url::Url::parse(&self.redirect_uri).map_err(|e| {
+            Error::validation(format!(
+                "无效的 security.oauth2.login.redirect_uri ({}): {}",
+                self.redirect_uri, e
+            ))
+        })?;
+        Ok(redirect_uri.path().to_string())
+    }
+}
+
+/// 身份提供方元数据，来自 OIDC 发现文档
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ProviderMetadata {
+    /// 签发者
+    pub issuer: String,
+    /// 授权端点
+    pub authorization_endpoint: String,
+    /// 令牌端点
+    pub token_endpoint: String,
+    /// JWKS 地址
+    pub jwks_uri: String,
+    /// UserInfo 端点（可选）
+    #[serde(default)]
+    pub userinfo_endpoint: Option<String>,
+}
+
+/// 缓存的签名密钥
+#[derive(Debug)]
+struct JwksCache {
+    /// 密钥集合
+    keys: JwkSet,
+    /// 获取时间
+    fetched_at: Instant,
+}
+
+/// OIDC 身份提供方
+///
+/// 通常由 [`OAuth2Config::apply`] 创建；需要在中间件之外校验令牌时可以单独创建
+///
+/// # 示例
+/// ```rust
+/// let provider = Arc::new(OidcProvider::discover(oauth2_config).await?);
+/// let principal = provider.validate_token(token).await?;
+/// let router = provider.apply(router)?;
+/// ```
+#[derive(Debug)]
+pub struct OidcProvider {
+    /// OAuth2 配置
+    config: OAuth2Config,
+    /// 身份提供方元数据
+    metadata: ProviderMetadata,
+    /// HTTP 客户端
+    http: reqwest::Client,
+    /// 签名密钥缓存
+    jwks: RwLock<JwksCache>,
+}
+
+impl OidcProvider {
+    /// 获取身份提供方元数据和 JWKS
+    ///
+    /// # 错误
+    /// 请求失败、元数据中的签发者与配置不一致时返回错误
+    pub async fn discover(config: OAuth2Config) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(HTTP_TIMEOUT)
+            .build()
+            .map_err(|e| Error::internal(format!("创建 HTTP 客户端失败: {}", e)))?;
+
+        let issuer = config.issuer.trim_end_matches('/');
+        let discovery_url = format!("{}{}", issuer, DISCOVERY_PATH);
+        let metadata: ProviderMetadata = get_json(&http, &discovery_url, None).await?;
+        if metadata.issuer.trim_end_matches('/') != issuer {
+            return Err(Error::validation(format!(
+                "身份提供方的签发者 {} 与配置的 {} 不一致",
+                metadata.issuer, config.issuer
+            )));
+        }
+
+        let jwks_uri = config.jwks_uri.as_deref().unwrap_or(&metadata.jwks_uri);
+        let keys: JwkSet = get_json(&http, jwks_uri, None).await?;
+        tracing::info!(
+            "已加载 OIDC 身份提供方 {} 的 {} 个签名密钥",
+            metadata.issuer,
+            keys.keys.len()
+        );
+
+        Ok(Self {
+            config,
+            metadata,
+            http,
+            jwks: RwLock::new(JwksCache {
+                keys,
+                fetched_at: Instant::now(),
+            }),
+        })
+    }
+
+    /// 获取身份提供方元数据
+    pub fn metadata(&self) -> &ProviderMetadata {
+        &self.metadata
+    }
+
+    /// 将认证中间件和登录路由应用到路由
+    ///
+    /// # 错误
+    /// 登录配置无效时返回验证错误
+    pub fn apply<S>(self: &Arc<Self>, router: Router<S>) -> Result<Router<S>>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let provider = self.clone();
+        let mut router = router.layer(middleware::from_fn(move |request: Request, next: Next| {
+            authenticate(provider.clone(), request, next)
+        }));
+
+        if let Some(login) = &self.config.login {
+            let callback_path = login.callback_path()?;
+            let provider = self.clone();
+            let start = move |cookies: Cookies, Query(params): Query<LoginParams>| {
+                let provider = provider.clone();
+                async move { provider.start_login(cookies, params.redirect).await }
+            };
+            let provider = self.clone();
+            let finish = move |cookies: Cookies, Query(params): Query<CallbackParams>| {
+                let provider = provider.clone();
+                async move { provider.finish_login(cookies, params).await }
+            };
+            let logout_success_url = login.logout_success_url.clone();
+            let logout = move |cookies: Cookies| {
+                let target = logout_success_url.clone();
+                async move {
+                    (
+                        cookies.remove_value::<LoginSession>(),
+                        Redirect::to(&target),
+                    )
+                }
+            };
+
+            router = router
+                .route(&login.login_path, get(start))
+                .route(&callback_path, get(finish))
+                .route(&login.logout_path, get(logout.clone()).post(logout));
+        }
+        Ok(router)
+    }
+
+    /// 校验访问令牌并映射为认证主体
+    ///
+    /// # 错误
+    /// 令牌格式、签名、签发者、受众无效或已过期时返回验证错误
+    pub async fn validate_token(&self, token: &str) -> Result<Principal> {
+        let claims = self.decode(token, self.config.audience.as_deref()).await?;
+        self.config.principal_from_claims(&claims)
+    }
+
+    /// 校验 JWT 并返回声明
+    async fn decode(&self, token: &str, audience: Option<&str>) -> Result<Map<String, Value>> {
+        let header = jsonwebtoken::decode_header(token)
+            .map_err(|e| Error::validation(format!("无效的令牌: {}", e)))?;
+        if !ALLOWED_ALGORITHMS.contains(&header.alg) {
+            return Err(Error::validation(format!(
+                "不支持的令牌签名算法: {:?}",
+                header.alg
+            )));
+        }
+
+        let jwk = self.find_key(header.kid.as_deref()).await?;
+        let key = DecodingKey::from_jwk(&jwk)
+            .map_err(|e| Error::validation(format!("无效的签名密钥: {}", e)))?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&self.metadata.issuer]);
+        match audience {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+        jsonwebtoken::decode::<Map<String, Value>>(token, &key, &validation)
+            .map(|data| data.claims)
+            .map_err(|e| Error::validation(format!("令牌校验失败: {}", e)))
+    }
+
+    /// 按 `kid` 查找签名密钥，找不到时刷新 JWKS 后再查找一次
+    async fn find_key(&self, kid: Option<&str>) -> Result<Jwk> {
+        {
+            let cache = self.jwks.read().await;
+            if let Some(jwk) = select_key(&cache.keys, kid) {
+                return Ok(jwk);
+            }
+            if cache.fetched_at.elapsed() < JWKS_REFRESH_INTERVAL {
+                return Err(Error::validation(format!(
+                    "未知的签名密钥: {}",
+                    kid.unwrap_or("-")
+                )));
+            }
+        }
+
+        let mut cache = self.jwks.write().await;
+        // 等待写锁期间可能已被其他请求刷新
+        if cache.fetched_at.elapsed() >= JWKS_REFRESH_INTERVAL {
+            let jwks_uri = self
+                .config
+                .jwks_uri
+                .as_deref()
+                .unwrap_or(&self.metadata.jwks_uri);
+            cache.keys = get_json(&self.http, jwks_uri, None).await?;
+            cache.fetched_at = Instant::now();
+            tracing::info!("已刷新 OIDC 身份提供方 {} 的签名密钥", self.metadata.issuer);
+        }
+        select_key(&cache.keys, kid)
+            .ok_or_else(|| Error::validation(format!("未知的签名密钥: {}", kid.unwrap_or("-"))))
+    }
+
+    /// 获取登录配置
+    fn login(&self) -> Result<&OAuth2LoginConfig> {
+        self.config
+            .login
+            .as_ref()
+            .ok_or_else(|| Error::internal("未配置 security.oauth2.login"))
+    }
+
+    /// 发起登录：保存 state、nonce 和 PKCE 校验码，重定向到授权端点
+    async fn start_login(&self, cookies: Cookies, redirect: Option<String>) -> Response {
+        let result = async {
+            let login = self.login()?;
+            let state = LoginState {
+                state: random_token()?,
+                nonce: random_token()?,
+                code_verifier: random_token()?,
+                redirect: redirect.filter(|redirect| is_local_redirect(redirect)),
+            };
+
+            let mut url = url::Url::parse(&self.metadata.authorization_endpoint)
+                .map_err(|e| Error::validation(format!("无效的授权端点: {}", e)))?;
+            url.query_pairs_mut()
+                .append_pair("response_type", "code")
+                .append_pair("client_id", &login.client_id)
+                .append_pair("redirect_uri", &login.redirect_uri)
+                .append_pair("scope", &login.scopes.join(" "))
+                .append_pair("state", &state.state)
+                .append_pair("nonce", &state.nonce)
+                .append_pair("code_challenge", &pkce_challenge(&state.code_verifier))
+                .append_pair("code_challenge_method", "S256");
+            Ok::<_, Error>((cookies.set_value(&state)?, Redirect::to(url.as_str())))
+        }
+        .await;
+
+        match result {
+            Ok(response) => response.into_response(),
+            Err(e) => {
+                tracing::error!("发起 OAuth2 登录失败: {}", e);
+                error_response(StatusCode::INTERNAL_SERVER_ERROR, "发起登录失败")
+            }
+        }
+    }
+
+    /// 登录回调：校验 state，换取令牌并创建会话
+    async fn finish_login(&self, cookies: Cookies, params: CallbackParams) -> Response {
+        let state = cookies.value::<LoginState>();
+        let cookies = cookies.remove_value::<LoginState>();
+        let result = async {
+            let login = self.login()?;
+            let state = state.ok_or_else(|| Error::validation("登录状态不存在或已失效"))?;
+            if let Some(error) = params.error {
+                return Err(Error::validation(format!(
+                    "身份提供方拒绝了登录请求: {}",
+                    params.error_description.unwrap_or(error)
+                )));
+            }
+            if params.state.as_deref() != Some(state.state.as_str()) {
+                return Err(Error::validation("登录回调的 state 不匹配"));
+            }
+            let code = params
+                .code
+                .ok_or_else(|| Error::validation("登录回调缺少授权码"))?;
+
+            let principal = self.authenticate_code(login, &code, &state).await?;
+            tracing::info!("用户 {} 通过 OIDC 登录", principal.name);
+            let session = LoginSession {
+                principal,
+                expires_at: rspring_core::clock::now().timestamp()
+                    + login.session_timeout.as_secs() as i64,
+            };
+            let target = state
+                .redirect
+                .unwrap_or_else(|| login.default_success_url.clone());
+            Ok((cookies.clone().set_value(&session)?, Redirect::to(&target)))
+        }
+        .await;
+
+        match result {
+            Ok(response) => response.into_response(),
+            Err(e) => {
+                tracing::warn!("OAuth2 登录失败: {}", e);
+                (
+                    cookies,
+                    error_response(StatusCode::UNAUTHORIZED, "登录失败"),
+                )
+                    .into_response()
+            }
+        }
+    }
+
+    /// 用授权码换取令牌，校验 id_token 并映射为认证主体
+    async fn authenticate_code(
+        &self,
+        login: &OAuth2LoginConfig,
+        code: &str,
+        state: &LoginState,
+    ) -> Result<Principal> {
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", login.redirect_uri.as_str()),
+            ("code_verifier", state.code_verifier.as_str()),
+        ];
+        let mut request = self.http.post(&self.metadata.token_endpoint);
+        match &login.client_secret {
+            Some(secret) => request = request.basic_auth(&login.client_id, Some(secret)),
+            None => form.push(("client_id", login.client_id.as_str())),
+        }
+        let response = request
+            .form(&form)
+            .send()
+            .await
+            .map_err(|e| Error::application(format!("请求令牌端点失败: {}", e)))?;
+        let token: TokenResponse = read_json(response).await?;
+
+        let id_token = token
+            .id_token
+            .ok_or_else(|| Error::validation("令牌响应缺少 id_token"))?;
+        let mut claims = self.decode(&id_token, Some(&login.client_id)).await?;
+        if claims.get("nonce").and_then(Value::as_str) != Some(state.nonce.as_str()) {
+            return Err(Error::validation("id_token 的 nonce 不匹配"));
+        }
+
+        if login.user_info {
+            if let Some(endpoint) = &self.metadata.userinfo_endpoint {
+                let user_info: Map<String, Value> =
+                    get_json(&self.http, endpoint, Some(&token.access_token)).await?;
+                if user_info.get("sub") != claims.get("sub") {
+                    return Err(Error::validation("UserInfo 的 sub 与 id_token 不一致"));
+                }
+                claims.extend(user_info);
+            }
+        }
+        // id_token 通常不包含授权范围，使用令牌响应中实际授予的范围
+        if let Some(scope) = token.scope {
+            claims
+                .entry(self.config.authorities_claim.clone())
+                .or_insert(Value::String(scope));
+        }
+        self.config.principal_from_claims(&claims)
+    }
+
+    /// 未认证时的响应：浏览器请求重定向到登录地址，其他请求返回 401
+    fn challenge(&self, request: &Request) -> Response {
+        let accepts_html = request
+            .headers()
+            .get(header::ACCEPT)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|accept| accept.contains("text/html"));
+        match &self.config.login {
+            Some(login) if accepts_html => {
+                let target = request
+                    .uri()
+                    .path_and_query()
+                    .map_or("/", |path| path.as_str());
+                let redirect: String =
+                    url::form_urlencoded::byte_serialize(target.as_bytes()).collect();
+                Redirect::to(&format!("{}?redirect={}", login.login_path, redirect)).into_response()
+            }
+            _ => unauthorized(HeaderValue::from_static("Bearer")),
+        }
+    }
+}
+
+/// 登录请求参数
+#[derive(Debug, Deserialize)]
+struct LoginParams {
+    /// 登录后返回的页面
+    redirect: Option<String>,
+}
+
+/// 登录回调参数
+#[derive(Debug, Deserialize)]
+struct CallbackParams {
+    /// 授权码
+    code: Option<String>,
+    /// 发起登录时的 state
+    state: Option<String>,
+    /// 错误码
+    error: Option<String>,
+    /// 错误描述
+    error_description: Option<String>,
+}
+
+/// 令牌端点响应
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    /// 访问令牌
+    access_token: String,
+    /// id_token
+    #[serde(default)]
+    id_token: Option<String>,
+    /// 实际授予的范围
+    #[serde(default)]
+    scope: Option<String>,
+}
+
+/// 登录过程中保存在加密 Cookie 中的状态
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+struct LoginState {
+    /// 防止跨站请求伪造的 state
+    state: String,
+    /// 防止 id_token 重放的 nonce
+    nonce: String,
+    /// PKCE 校验码
+    code_verifier: String,
+    /// 登录后返回的页面
+    redirect: Option<String>,
+}
+
+impl CookieValue for LoginState {
+    const NAME: &'static str = "rspring_oauth2_login";
+    const PROTECTION: CookieProtection = CookieProtection::Private;
+}
+
+/// 登录会话，保存在加密 Cookie 中
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+struct LoginSession {
+    /// 认证主体
+    principal: Principal,
+    /// 过期时间（Unix 时间戳，秒）
+    expires_at: i64,
+}
+
+impl CookieValue for LoginSession {
+    const NAME: &'static str = "rspring_session";
+    const PROTECTION: CookieProtection = CookieProtection::Private;
+}
+
+/// 认证中间件
+///
+/// 优先使用 Bearer 令牌，其次使用登录会话；认证成功时设置请求扩展和任务本地的认证主体。
+/// 已由其他认证方式（如 API 密钥）认证的请求直接放行
+async fn authenticate(provider: Arc<OidcProvider>, request: Request, next: Next) -> Response {
+    if request.extensions().get::<Principal>().is_some() {
+        return next.run(request).await;
+    }
+    let (mut parts, body) = request.into_parts();
+    let principal = match bearer_token(&parts.headers) {
+        Some(token) => match provider.validate_token(&token).await {
+            Ok(principal) => Some(principal),
+            Err(e) => {
+                tracing::debug!("访问令牌校验失败: {}", e);
+                let event = AuditEvent::new("security.authentication")
+                    .resource(format!("{} {}", parts.method, parts.uri.path()))
+                    .outcome(AuditOutcome::Failure)
+                    .metadata("method", "bearer");
+                audit(event).await;
+                return unauthorized(HeaderValue::from_static("Bearer error=\"invalid_token\""));
+            }
+        },
+        None if provider.config.login.is_some() => {
+            let Ok(cookies) = Cookies::from_request_parts(&mut parts, &()).await;
+            cookies
+                .value::<LoginSession>()
+                .filter(|session| session.expires_at > rspring_core::clock::now().timestamp())
+                .map(|session| session.principal)
+        }
+        None => None,
+    };
+
+    let mut request = Request::from_parts(parts, body);
+    match principal {
+        Some(principal) => {
+            request.extensions_mut().insert(principal.clone());
+            with_principal(principal, next.run(request)).await
+        }
+        None if matches_any_prefix(request.uri().path(), &provider.config.protected_paths) => {
+            provider.challenge(&request)
+        }
+        None => next.run(request).await,
+    }
+}
+
+/// 读取 `Authorization: Bearer` 中的令牌
+fn bearer_token(headers: &HeaderMap) -> Option<String> {
+    let value = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, token) = value.split_once(' ')?;
+    scheme
+        .eq_ignore_ascii_case("bearer")
+        .then(|| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+}
+
+/// 401 响应
+fn unauthorized(challenge: HeaderValue) -> Response {
+    let mut response = error_response(StatusCode::UNAUTHORIZED, "未认证或令牌无效");
+    response
+        .headers_mut()
+        .insert(header::WWW_AUTHENTICATE, challenge);
+    response
+}
+
+/// 按 `kid` 选择密钥，令牌未指定 `kid` 且只有一个密钥时使用该密钥
+fn select_key(keys: &JwkSet, kid: Option<&str>) -> Option<Jwk> {
+    match kid {
+        Some(kid) => keys.find(kid).cloned(),
+        None if keys.keys.len() == 1 => keys.keys.first().cloned(),
+        None => None,
+    }
+}
+
+/// 读取声明，不存在同名声明时按 `.` 分隔的嵌套路径查找
+fn claim<'a>(claims: &'a Map<String, Value>, name: &str) -> Option<&'a Value> {
+    if let Some(value) = claims.get(name) {
+        return Some(value);
+    }
+    let mut segments = name.split('.');
+    let mut value = claims.get(segments.next()?)?;
+    for segment in segments {
+        value = value.get(segment)?;
+    }
+    Some(value)
+}
+
+/// 将声明的值展开为字符串列表，支持空格分隔的字符串和字符串数组
+fn claim_values(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(value) => value.split_whitespace().map(str::to_string).collect(),
+        Value::Array(values) => values
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// 生成 32 字节的随机令牌，按 Base64 URL 编码
+fn random_token() -> Result<String> {
+    let mut bytes = [0u8; 32];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| Error::internal("生成随机数失败"))?;
+    Ok(URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// 计算 PKCE 的 S256 质询码
+fn pkce_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(ring::digest::digest(
+        &ring::digest::SHA256,
+        code_verifier.as_bytes(),
+    ))
+}
+
+/// 发送 GET 请求并解析 JSON 响应
+async fn get_json<T: DeserializeOwned>(
+    http: &reqwest::Client,
+    url: &str,
+    bearer: Option<&str>,
+) -> Result<T> {
+    let mut request = http.get(url);
+    if let Some(token) = bearer {
+        request = request.bearer_auth(token);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| Error::application(format!("请求 {} 失败: {}", url, e)))?;
+    read_json(response).await
+}
+
+/// 检查响应状态并解析 JSON
+async fn read_json<T: DeserializeOwned>(response: reqwest::Response) -> Result<T> {
+    let url = response.url().clone();
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(Error::application(format!(
+            "请求 {} 失败 ({}): {}",
+            url, status, body
+        )));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| Error::application(format!("解析 {} 的响应失败: {}", url, e)))
+}
+
+// 默认值函数
+
+fn default_username_claim() -> String {
+    "sub".to_string()
+}
+
+fn default_authorities_claim() -> String {
+    "scope".to_string()
+}
+
+fn default_authority_prefix() -> String {
+    "SCOPE_".to_string()
+}
+
+fn default_scopes() -> Vec<String> {
+    vec![
+        "openid".to_string(),
+        "profile".to_string(),
+        "email".to_string(),
+    ]
+}
+
+fn default_login_path() -> String {
+    "/oauth2/login".to_string()
+}
+
+fn default_logout_path() -> String {
+    "/logout".to_string()
+}
+
+fn default_success_url() -> String {
+    "/".to_string()
+}
+
+fn default_session_timeout() -> Duration {
+    Duration::from_secs(8 * 60 * 60)
+}
+
+fn default_user_info() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试声明映射为认证主体以及 PKCE 质询码
+    #[test]
+    fn test_claims_to_principal() {
+        let config: OAuth2Config = serde_json::from_value(serde_json::json!({
+            "issuer": "https://accounts.example.com",
+            "username_claim": "preferred_username",
+            "roles_claim": "realm_access.roles",
+        }))
+        .unwrap();
+        let claims = serde_json::json!({
+            "sub": "8f2c",
+            "preferred_username": "alice",
+            "scope": "orders:read orders:write",
+            "realm_access": { "roles": ["ADMIN"] },
+        });
+
+        let principal = config
+            .principal_from_claims(claims.as_object().unwrap())
+            .unwrap();
+        assert_eq!(principal.name, "alice");
+        assert_eq!(
+            principal.authorities,
+            vec!["SCOPE_orders:read", "SCOPE_orders:write", "ROLE_ADMIN"]
+        );
+        assert!(config.principal_from_claims(&Map::new()).is_err());
+
+        // RFC 7636 附录 B 的示例
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+}
@@ -0,0 +1,710 @@
+//! Web 服务器模块
+//!
+//! 根据 `[server]` 配置启动 HTTP/HTTPS 服务，支持：
+//! - 基于 rustls 的 HTTPS，证书和私钥使用 PEM 文件
+//! - 可选的 HTTP → HTTPS 重定向端口
+//! - HTTP/2（HTTPS 下通过 ALPN 协商）和明文 h2c
+//! - `[[server.listeners]]` 配置的额外监听器，如独立端口上的管理接口
+//! - `server.socket` 配置的 Unix 域套接字
+//! - 收到停止信号后优雅关闭
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use axum::extract::Request;
+use axum::http::{header, StatusCode, Uri};
+use axum::response::{IntoResponse, Redirect, Response};
+use axum::Router;
+use axum_server::tls_rustls::RustlsConfig;
+use axum_server::Handle;
+use futures::future::{try_join_all, BoxFuture};
+use futures::FutureExt;
+use hyper_util::rt::TokioExecutor;
+use hyper_util::server::conn::auto;
+use rspring_core::config::{ConfigurationManager, Http2Config, ServerConfig, SslConfig};
+use rspring_core::startup::{startup_recorder, StartupHold};
+use rspring_core::{Error, Result};
+use tokio_util::sync::CancellationToken;
+
+/// 优雅关闭的默认等待时间
+const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Web 服务器
+///
+/// # 示例
+/// ```rust
+/// let app = Router::new().route("/api/users", get(list_users));
+///
+/// WebServer::from_config(&config, app)?
+///     .listener_router("admin", Router::new().route("/health", get(health)))
+///     .run()
+///     .await?;
+/// ```
+pub struct WebServer {
+    /// 服务器配置
+    config: ServerConfig,
+    /// 应用路由
+    router: Router,
+    /// 按名称注册的额外监听器路由
+    listener_routers: HashMap<String, Router>,
+    /// 优雅关闭的等待时间
+    shutdown_timeout: Duration,
+    /// 推迟启动完成，所有监听器绑定地址后释放
+    startup: Option<StartupHold<'static>>,
+}
+
+impl WebServer {
+    /// 创建 Web 服务器
+    pub fn new(config: ServerConfig, router: Router) -> Self {
+        Self {
+            config,
+            router,
+            listener_routers: HashMap::new(),
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            startup: Some(startup_recorder().hold()),
+        }
+    }
+
+    /// 使用配置文件中的 `[server]` 章节创建 Web 服务器
+    ///
+    /// 未配置时使用默认值
+    pub fn from_config(config: &ConfigurationManager, router: Router) -> Result<Self> {
+        let server_config = if config.contains_key("server") {
+            config.get_section("server")?
+        } else {
+            ServerConfig::default()
+        };
+        Ok(Self::new(server_config, router))
+    }
+
+    /// 设置优雅关闭的等待时间
+    ///
+    /// # 默认值
+    /// 30 秒
+    pub fn shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_timeout = timeout;
+        self
+    }
+
+    /// 为 `[[server.listeners]]` 中指定名称的监听器设置路由
+    ///
+    /// 每个配置的监听器都须设置路由，未设置时启动失败，避免在管理端口上暴露应用路由
+    pub fn listener_router(mut self, name: impl Into<String>, router: Router) -> Self {
+        self.listener_routers.insert(name.into(), router);
+        self
+    }
+
+    /// 合并路由，指定监听器时合并到该监听器的路由，否则合并到应用路由
+    ///
+    /// 监听器尚未设置路由时，合并后该监听器只提供合并的路由
+    pub fn merge_router(mut self, listener: Option<&str>, router: Router) -> Self {
+        match listener {
+            Some(name) => {
+                let merged = match self.listener_routers.remove(name) {
+                    Some(existing) => existing.merge(router),
+                    None => router,
+                };
+                self.listener_routers.insert(name.to_string(), merged);
+            }
+            None => self.router = self.router.merge(router),
+        }
+        self
+    }
+
+    /// 获取服务器配置
+    pub fn config(&self) -> &ServerConfig {
+        &self.config
+    }
+
+    /// 获取应用路由
+    pub fn router(&self) -> &Router {
+        &self.router
+    }
+
+    /// 启动服务器，收到 Ctrl+C 后优雅关闭
+    pub async fn run(self) -> Result<()> {
+        self.run_until(async {
+            let _ = tokio::signal::ctrl_c().await;
+            tracing::info!("收到停止信号，正在关闭 Web 服务器");
+        })
+        .await
+    }
+
+    /// 启动服务器，`shutdown` 完成后优雅关闭
+    ///
+    /// 主端口和所有额外监听器同时启动，任一监听器异常退出时返回错误
+    pub async fn run_until<F>(mut self, shutdown: F) -> Result<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.check_listeners()?;
+        let ssl = self.config.ssl_enabled();
+        if self.config.socket.is_some() && ssl.is_some() {
+            return Err(Error::validation(
+                "Unix 域套接字不支持 HTTPS，请关闭 server.ssl",
+            ));
+        }
+
+        let handle = Handle::new();
+        let shutdown_handle = handle.clone();
+        let shutdown_token = CancellationToken::new();
+        let cancel = shutdown_token.clone();
+        let shutdown_timeout = self.shutdown_timeout;
+        tokio::spawn(async move {
+            shutdown.await;
+            shutdown_handle.graceful_shutdown(Some(shutdown_timeout));
+            cancel.cancel();
+        });
+
+        let http2 = &self.config.http2;
+        if let Some(redirect_port) = ssl.and_then(|ssl| ssl.redirect_http_port) {
+            let redirect_addr = resolve_addr(&self.config.host, redirect_port).await?;
+            spawn_redirect(redirect_addr, self.config.port, handle.clone())?;
+        }
+
+        // 先绑定所有地址，全部成功后再开始处理请求，启动报告中只包含已绑定的监听器
+        let primary: BoxFuture<'_, Result<()>> = match &self.config.socket {
+            Some(path) => serve_unix(
+                bind_unix(path, self.config.socket_permissions.as_deref())?,
+                path,
+                self.router.clone(),
+                http2,
+                shutdown_token.clone(),
+                shutdown_timeout,
+            )
+            .boxed(),
+            None => {
+                let addr = resolve_addr(&self.config.host, self.config.port).await?;
+                serve(
+                    bind_listener("Web 服务器", addr, ssl.is_some())?,
+                    self.router.clone(),
+                    ssl,
+                    http2,
+                    handle.clone(),
+                )
+                .boxed()
+            }
+        };
+
+        let mut servers = vec![primary];
+        for listener in &self.config.listeners {
+            let host = listener.host.as_deref().unwrap_or(&self.config.host);
+            let router = self
+                .listener_routers
+                .remove(&listener.name)
+                .ok_or_else(|| Error::validation(format!("监听器 {} 未设置路由", listener.name)))?;
+            let addr = resolve_addr(host, listener.port).await?;
+            let name = format!("监听器 {}", listener.name);
+            let ssl = listener.ssl_enabled();
+            servers.push(
+                serve(
+                    bind_listener(&name, addr, ssl.is_some())?,
+                    router,
+                    ssl,
+                    http2,
+                    handle.clone(),
+                )
+                .boxed(),
+            );
+        }
+        drop(self.startup.take());
+
+        let result = try_join_all(servers).await;
+        // 任一监听器失败时关闭其余监听器
+        if result.is_err() {
+            handle.shutdown();
+            shutdown_token.cancel();
+        }
+        result?;
+
+        tracing::info!("Web 服务器已关闭");
+        Ok(())
+    }
+
+    /// 检查监听器配置
+    ///
+    /// 监听器名称不能重复，每个监听器都须设置路由，注册了路由的名称必须在配置中存在
+    fn check_listeners(&self) -> Result<()> {
+        let mut names = std::collections::HashSet::new();
+        for listener in &self.config.listeners {
+            if !names.insert(listener.name.as_str()) {
+                return Err(Error::validation(format!(
+                    "监听器名称重复: {}",
+                    listener.name
+                )));
+            }
+            if !self.listener_routers.contains_key(&listener.name) {
+                return Err(Error::validation(format!(
+                    "监听器 {} 未设置路由",
+                    listener.name
+                )));
+            }
+        }
+
+        match self
+            .listener_routers
+            .keys()
+            .find(|name| !names.contains(name.as_str()))
+        {
+            Some(name) => Err(Error::validation(format!("未配置的监听器: {}", name))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// 绑定监听地址，绑定成功后记录到启动报告
+fn bind_listener(name: &str, addr: SocketAddr, tls: bool) -> Result<std::net::TcpListener> {
+    let listener = std::net::TcpListener::bind(addr)
+        .map_err(|e| Error::application(format!("{}绑定 {} 失败: {}", name, addr, e)))?;
+    let scheme = if tls { "https" } else { "http" };
+    let addr = listener.local_addr()?;
+    tracing::info!("{}启动于 {}://{}", name, scheme, addr);
+    startup_recorder().listener(name, format!("{}://{}", scheme, addr));
+    Ok(listener)
+}
+
+/// 在已绑定的地址上启动 HTTP 或 HTTPS 服务
+async fn serve(
+    listener: std::net::TcpListener,
+    router: Router,
+    ssl: Option<&SslConfig>,
+    http2: &Http2Config,
+    handle: Handle,
+) -> Result<()> {
+    let service = router.into_make_service_with_connect_info::<SocketAddr>();
+
+    match ssl {
+        Some(ssl) => {
+            let tls_config = load_tls_config(ssl, http2).await?;
+            let mut server = axum_server::from_tcp_rustls(listener, tls_config).handle(handle);
+            configure_http2(server.http_builder(), http2, true);
+            server.serve(service).await?;
+        }
+        None => {
+            let mut server = axum_server::from_tcp(listener).handle(handle);
+            configure_http2(server.http_builder(), http2, false);
+            server.serve(service).await?;
+        }
+    }
+    Ok(())
+}
+
+/// 绑定 Unix 域套接字，绑定成功后记录到启动报告
+///
+/// 绑定前删除残留的套接字文件。配置了权限时先在仅当前用户可访问的临时目录中绑定并设置权限，
+/// 再移动到配置的路径，套接字在配置的路径上出现时已是配置的权限
+#[cfg(unix)]
+fn bind_unix(path: &str, permissions: Option<&str>) -> Result<tokio::net::UnixListener> {
+    let mode = permissions.map(parse_permissions).transpose()?;
+    remove_stale_socket(path)?;
+    let bind_error =
+        |e: std::io::Error| Error::application(format!("绑定 Unix 域套接字失败 ({}): {}", path, e));
+    let listener = match mode {
+        Some(mode) => bind_unix_with_mode(std::path::Path::new(path), mode).map_err(bind_error)?,
+        None => tokio::net::UnixListener::bind(path).map_err(bind_error)?,
+    };
+
+    tracing::info!("Web 服务器启动于 unix:{}", path);
+    startup_recorder().listener("Web 服务器", format!("unix:{}", path));
+    Ok(listener)
+}
+
+/// 在套接字所在目录下的临时目录中绑定并设置权限，再移动到 `path`
+#[cfg(unix)]
+fn bind_unix_with_mode(
+    path: &std::path::Path,
+    mode: u32,
+) -> std::io::Result<tokio::net::UnixListener> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| std::path::Path::new("."));
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let staging = parent.join(format!(".{}.{}", name, std::process::id()));
+    // 残留的临时目录来自同一进程号的上次运行
+    let _ = std::fs::remove_dir_all(&staging);
+    std::fs::DirBuilder::new().mode(0o700).create(&staging)?;
+
+    let staged = staging.join("socket");
+    let listener = tokio::net::UnixListener::bind(&staged).and_then(|listener| {
+        std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(mode))?;
+        std::fs::rename(&staged, path)?;
+        Ok(listener)
+    });
+    let _ = std::fs::remove_dir_all(&staging);
+    listener
+}
+
+/// 非 Unix 平台不支持 Unix 域套接字
+#[cfg(not(unix))]
+fn bind_unix(path: &str, _permissions: Option<&str>) -> Result<std::convert::Infallible> {
+    Err(Error::validation(format!(
+        "当前平台不支持 Unix 域套接字: {}",
+        path
+    )))
+}
+
+/// 在已绑定的 Unix 域套接字上启动 HTTP 服务
+///
+/// 连接方没有网络地址，请求的 `ConnectInfo<SocketAddr>` 为回环地址，
+/// 与同一主机上的反向代理经 TCP 转发时一致。关闭后删除套接字文件
+#[cfg(unix)]
+async fn serve_unix(
+    listener: tokio::net::UnixListener,
+    path: &str,
+    router: Router,
+    http2: &Http2Config,
+    shutdown: CancellationToken,
+    shutdown_timeout: Duration,
+) -> Result<()> {
+    use hyper_util::rt::{TokioIo, TokioTimer};
+    use hyper_util::server::graceful::GracefulShutdown;
+    use hyper_util::service::TowerToHyperService;
+
+    let mut builder = auto::Builder::new(TokioExecutor::new());
+    builder.http2().timer(TokioTimer::new());
+    configure_http2(&mut builder, http2, false);
+
+    let peer = SocketAddr::from((std::net::Ipv4Addr::LOCALHOST, 0));
+    let router = router.layer(axum::Extension(axum::extract::ConnectInfo(peer)));
+    let graceful = GracefulShutdown::new();
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let stream = match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        tracing::warn!("接受 Unix 域套接字连接失败: {}", e);
+                        continue;
+                    }
+                };
+                let service = TowerToHyperService::new(router.clone());
+                let connection = builder
+                    .serve_connection_with_upgrades(TokioIo::new(stream), service)
+                    .into_owned();
+                let connection = graceful.watch(connection);
+                tokio::spawn(async move {
+                    if let Err(e) = connection.await {
+                        tracing::debug!("Unix 域套接字连接异常结束: {}", e);
+                    }
+                });
+            }
+            _ = shutdown.cancelled() => break,
+        }
+    }
+
+    drop(listener);
+    let _ = std::fs::remove_file(path);
+    if tokio::time::timeout(shutdown_timeout, graceful.shutdown())
+        .await
+        .is_err()
+    {
+        tracing::warn!("等待 Unix 域套接字连接关闭超时");
+    }
+    Ok(())
+}
+
+/// 非 Unix 平台不支持 Unix 域套接字
+#[cfg(not(unix))]
+async fn serve_unix(
+    listener: std::convert::Infallible,
+    _path: &str,
+    _router: Router,
+    _http2: &Http2Config,
+    _shutdown: CancellationToken,
+    _shutdown_timeout: Duration,
+) -> Result<()> {
+    match listener {}
+}
+
+/// 解析八进制的文件权限，如 `660` 或 `0o660`
+fn parse_permissions(permissions: &str) -> Result<u32> {
+    let digits = permissions.trim().trim_start_matches("0o");
+    u32::from_str_radix(digits, 8)
+        .ok()
+        .filter(|mode| *mode <= 0o777)
+        .ok_or_else(|| Error::validation(format!("无效的套接字权限: {}", permissions)))
+}
+
+/// 删除上次运行残留的套接字文件，路径被其他类型的文件占用时返回错误
+#[cfg(unix)]
+fn remove_stale_socket(path: &str) -> Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => Ok(std::fs::remove_file(path)?),
+        Ok(_) => Err(Error::validation(format!(
+            "套接字路径已被其他文件占用: {}",
+            path
+        ))),
+        Err(_) => Ok(()),
+    }
+}
+
+/// 启动 HTTP → HTTPS 重定向服务
+fn spawn_redirect(addr: SocketAddr, https_port: u16, handle: Handle) -> Result<()> {
+    let listener = bind_listener("HTTP 重定向服务", addr, false)?;
+    tokio::spawn(async move {
+        let router = Router::new().fallback(move |request: Request| async move {
+            redirect_to_https(request, https_port)
+        });
+
+        if let Err(e) = axum_server::from_tcp(listener)
+            .handle(handle)
+            .serve(router.into_make_service())
+            .await
+        {
+            tracing::error!("HTTP 重定向服务异常退出: {}", e);
+        }
+    });
+    Ok(())
+}
+
+/// 解析监听地址
+async fn resolve_addr(host: &str, port: u16) -> Result<SocketAddr> {
+    tokio::net::lookup_host((host, port))
+        .await?
+        .next()
+        .ok_or_else(|| Error::validation(format!("无法解析监听地址: {}:{}", host, port)))
+}
+
+/// 应用 HTTP/2 连接参数
+///
+/// 关闭 HTTP/2 或明文连接未开启 h2c 时只接受 HTTP/1 连接
+fn configure_http2(builder: &mut auto::Builder<TokioExecutor>, http2: &Http2Config, tls: bool) {
+    if !http2.enabled || (!tls && !http2.h2c) {
+        *builder = builder.clone().http1_only();
+        return;
+    }
+    builder
+        .http2()
+        .max_concurrent_streams(http2.max_concurrent_streams)
+        .keep_alive_interval(http2.keep_alive_interval);
+}
+
+/// 加载 PEM 格式的证书和私钥
+///
+/// 关闭 HTTP/2 时 ALPN 只协商 HTTP/1.1
+async fn load_tls_config(ssl: &SslConfig, http2: &Http2Config) -> Result<RustlsConfig> {
+    // 使用 ring 作为加密实现，重复安装时忽略错误
+    let _ = rustls::crypto::ring::default_provider().install_default();
+
+    let tls_config = RustlsConfig::from_pem_file(&ssl.cert, &ssl.key)
+        .await
+        .map_err(|e| {
+            Error::application(format!(
+                "加载 TLS 证书失败 (cert: {}, key: {}): {}",
+                ssl.cert, ssl.key, e
+            ))
+        })?;
+
+    if !http2.enabled {
+        let mut server_config = (*tls_config.get_inner()).clone();
+        server_config.alpn_protocols = vec![b"http/1.1".to_vec()];
+        tls_config.reload_from_config(std::sync::Arc::new(server_config));
+    }
+
+    Ok(tls_config)
+}
+
+/// 将 HTTP 请求重定向到 HTTPS
+fn redirect_to_https(request: Request, https_port: u16) -> Response {
+    let host = request
+        .headers()
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok());
+
+    match host.and_then(|host| https_uri(host, https_port, request.uri())) {
+        Some(uri) => Redirect::permanent(&uri).into_response(),
+        None => StatusCode::BAD_REQUEST.into_response(),
+    }
+}
+
+/// 生成 HTTPS 地址
+///
+/// 去掉 `Host` 中的端口，HTTPS 端口为 443 时不拼接端口
+fn https_uri(host: &str, https_port: u16, uri: &Uri) -> Option<String> {
+    let authority: axum::http::uri::Authority = host.parse().ok()?;
+    let path_and_query = uri.path_and_query().map(|pq| pq.as_str()).unwrap_or("/");
+
+    Some(if https_port == 443 {
+        format!("https://{}{}", authority.host(), path_and_query)
+    } else {
+        format!(
+            "https://{}:{}{}",
+            authority.host(),
+            https_port,
+            path_and_query
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rspring_core::config::ListenerConfig;
+
+    /// 测试 HTTPS 重定向地址
+    #[test]
+    fn test_https_uri() {
+        let uri: Uri = "/api/users?page=2".parse().unwrap();
+        assert_eq!(
+            https_uri("example.com:8080", 8443, &uri).unwrap(),
+            "https://example.com:8443/api/users?page=2"
+        );
+        assert_eq!(
+            https_uri("example.com", 443, &uri).unwrap(),
+            "https://example.com/api/users?page=2"
+        );
+        assert!(https_uri("bad host", 443, &uri).is_none());
+    }
+
+    /// 测试证书文件不存在时返回错误
+    #[tokio::test]
+    async fn test_missing_certificate() {
+        let ssl = SslConfig {
+            enabled: true,
+            cert: "/nonexistent/server.crt".to_string(),
+            key: "/nonexistent/server.key".to_string(),
+            redirect_http_port: None,
+        };
+
+        let error = load_tls_config(&ssl, &Http2Config::default())
+            .await
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("加载 TLS 证书失败"));
+    }
+
+    /// 测试套接字权限解析
+    #[test]
+    fn test_parse_permissions() {
+        assert_eq!(parse_permissions("660").unwrap(), 0o660);
+        assert_eq!(parse_permissions("0o600").unwrap(), 0o600);
+        assert_eq!(parse_permissions("0777").unwrap(), 0o777);
+        assert!(parse_permissions("rw").is_err());
+        assert!(parse_permissions("1777").is_err());
+    }
+
+    /// 测试通过 Unix 域套接字处理请求并在关闭后删除套接字文件
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unix_socket() {
+        use std::os::unix::fs::PermissionsExt;
+
+        use axum::extract::ConnectInfo;
+        use axum::routing::get;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.sock");
+        let socket = path.to_string_lossy().to_string();
+        std::fs::write(&path, "").unwrap();
+
+        let config = ServerConfig {
+            socket: Some(socket.clone()),
+            socket_permissions: Some("600".to_string()),
+            ..ServerConfig::default()
+        };
+        let router =
+            Router::new()
+                .route("/ping", get(|| async { "pong" }))
+                .route(
+                    "/peer",
+                    get(|ConnectInfo(peer): ConnectInfo<SocketAddr>| async move {
+                        peer.ip().to_string()
+                    }),
+                );
+        let server =
+            WebServer::new(config.clone(), router.clone()).run_until(std::future::pending());
+        // 路径被普通文件占用时拒绝启动
+        assert!(server
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("已被其他文件占用"));
+        std::fs::remove_file(&path).unwrap();
+
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(WebServer::new(config, router).run_until(async {
+            let _ = rx.await;
+        }));
+        while !path.exists() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(
+            std::fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+            0o600
+        );
+        // 绑定用的临时目录已删除
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        let send = |uri: &'static str| {
+            let path = path.clone();
+            async move {
+                let mut stream = tokio::net::UnixStream::connect(&path).await.unwrap();
+                let request = format!(
+                    "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+                    uri
+                );
+                stream.write_all(request.as_bytes()).await.unwrap();
+                let mut response = String::new();
+                stream.read_to_string(&mut response).await.unwrap();
+                response
+            }
+        };
+        let response = send("/ping").await;
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.ends_with("pong"));
+        assert!(send("/peer").await.ends_with("127.0.0.1"));
+
+        tx.send(()).unwrap();
+        server.await.unwrap().unwrap();
+        assert!(!path.exists());
+    }
+
+    /// 测试监听器配置检查
+    #[test]
+    fn test_check_listeners() {
+        let listener = |name: &str| ListenerConfig {
+            name: name.to_string(),
+            host: None,
+            port: 9090,
+            ssl: None,
+        };
+
+        let config = ServerConfig {
+            listeners: vec![listener("admin")],
+            ..ServerConfig::default()
+        };
+        let server =
+            WebServer::new(config.clone(), Router::new()).listener_router("admin", Router::new());
+        assert!(server.check_listeners().is_ok());
+
+        let server = WebServer::new(config.clone(), Router::new())
+            .listener_router("admin", Router::new())
+            .listener_router("metrics", Router::new());
+        assert!(server
+            .check_listeners()
+            .unwrap_err()
+            .to_string()
+            .contains("metrics"));
+
+        let error = WebServer::new(config, Router::new())
+            .check_listeners()
+            .unwrap_err();
+        assert!(error.to_string().contains("admin"));
+
+        let config = ServerConfig {
+            listeners: vec![listener("admin"), listener("admin")],
+            ..ServerConfig::default()
+        };
+        assert!(WebServer::new(config, Router::new())
+            .check_listeners()
+            .is_err());
+    }
+}
@@ -0,0 +1,306 @@
+//! 慢请求日志模块
+//!
+//! 按 `[web.slow_request]` 配置记录处理耗时超过阈值的请求，与访问日志分开：
+//! - 以 `rspring::web::slow_request` 为目标输出 WARN 日志，带有请求方法、路由模板、路径、
+//!   参数摘要、认证主体、客户端地址、请求 ID、状态码和耗时
+//! - 参数摘要包括路径参数和查询参数，敏感参数的值被替换，过长的值和摘要被截断
+//! - 启用 `metrics` 特性后同时增加 `http.server.requests.slow` 计数器，标签为 `method` 和 `route`
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::extract::{FromRequestParts, MatchedPath, RawPathParams, Request};
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use axum::Router;
+use rspring_core::security::{current_principal, Principal};
+use serde::{Deserialize, Serialize};
+
+use crate::actuator::SANITIZED_VALUE;
+use crate::forwarded::client_ip;
+use crate::request_id::RequestId;
+
+/// 慢请求日志的目标
+pub const SLOW_REQUEST_TARGET: &str = "rspring::web::slow_request";
+
+/// 慢请求数指标
+pub const SLOW_REQUESTS_METRIC: &str = "http.server.requests.slow";
+
+/// 参数摘要中单个参数值的最大字符数
+const MAX_VALUE_CHARS: usize = 64;
+
+/// 慢请求日志配置
+///
+/// 应在注册完所有路由后调用 [`apply`](Self::apply)，以便记录路由模板和路径参数；
+/// 需要记录认证主体时，安全中间件应在此之后应用
+///
+/// # 示例
+/// ```toml
+/// [web.slow_request]
+/// threshold = "500ms"
+/// sensitive_params = ["password", "token", "card_no"]
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct SlowRequestConfig {
+    /// 是否启用
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 慢请求阈值，处理耗时超过此值的请求被记录
+    ///
+    /// # 默认值
+    /// `"1s"`
+    #[serde(default = "default_threshold", with = "rspring_core::config::duration")]
+    pub threshold: Duration,
+    /// 参数摘要的最大字符数
+    ///
+    /// # 默认值
+    /// `256`
+    #[serde(default = "default_max_params_length")]
+    pub max_params_length: usize,
+    /// 需要替换值的参数名，不区分大小写，参数名包含其中任一项时替换
+    ///
+    /// # 默认值
+    /// `["password", "secret", "token", "credentials"]`
+    #[serde(default = "default_sensitive_params")]
+    pub sensitive_params: Vec<String>,
+}
+
+impl Default for SlowRequestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            threshold: default_threshold(),
+            max_params_length: default_max_params_length(),
+            sensitive_params: default_sensitive_params(),
+        }
+    }
+}
+
+impl SlowRequestConfig {
+    /// 将慢请求日志中间件应用到路由，未启用时原样返回
+    pub fn apply<S>(&self, router: Router<S>) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        if !self.enabled {
+            return router;
+        }
+
+        let config = Arc::new(self.clone());
+        router.layer(middleware::from_fn(move |request: Request, next: Next| {
+            log_slow_request(config.clone(), request, next)
+        }))
+    }
+
+    /// 生成路径参数和查询参数的摘要，如 `id=42 status=paid token=******`
+    fn summarize<'a>(
+        &self,
+        path_params: impl IntoIterator<Item = (&'a str, &'a str)>,
+        query: Option<&str>,
+    ) -> String {
+        let query_params: Vec<(String, String)> = query
+            .map(|query| {
+                url::form_urlencoded::parse(query.as_bytes())
+                    .into_owned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        let params = path_params
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .chain(query_params);
+
+        let mut summary = String::new();
+        for (name, value) in params {
+            let value = if self.is_sensitive(&name) {
+                SANITIZED_VALUE.to_string()
+            } else {
+                truncate(&value, MAX_VALUE_CHARS)
+            };
+            if !summary.is_empty() {
+                summary.push(' ');
+            }
+            summary.push_str(&format!("{}={}", name, value));
+        }
+        truncate(&summary, self.max_params_length)
+    }
+
+    /// 判断参数是否敏感
+    fn is_sensitive(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        self.sensitive_params
+            .iter()
+            .any(|sensitive| name.contains(&sensitive.to_lowercase()))
+    }
+}
+
+/// 慢请求日志中间件
+async fn log_slow_request(
+    config: Arc<SlowRequestConfig>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let start = Instant::now();
+    let (mut parts, body) = request.into_parts();
+    let path_params = RawPathParams::from_request_parts(&mut parts, &())
+        .await
+        .ok();
+    let request = Request::from_parts(parts, body);
+
+    let method = request.method().to_string();
+    let path = request.uri().path().to_string();
+    let query = request.uri().query().map(str::to_string);
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or_else(|| path.clone(), |matched| matched.as_str().to_string());
+    let client_ip = client_ip(request.extensions()).map(|ip| ip.to_string());
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .map(|id| id.0.clone());
+    let principal = request
+        .extensions()
+        .get::<Principal>()
+        .cloned()
+        .or_else(current_principal)
+        .map(|principal| principal.name);
+
+    let response = next.run(request).await;
+
+    let duration = start.elapsed();
+    if duration < config.threshold {
+        return response;
+    }
+    let params = config.summarize(
+        path_params.iter().flat_map(|params| params.iter()),
+        query.as_deref(),
+    );
+    let duration_ms = duration.as_millis() as u64;
+    tracing::warn!(
+        target: SLOW_REQUEST_TARGET,
+        method = %method,
+        route = %route,
+        path = %path,
+        params = %params,
+        principal = principal.as_deref().unwrap_or("-"),
+        client_ip = client_ip.as_deref().unwrap_or("-"),
+        request_id = request_id.as_deref().unwrap_or("-"),
+        status = response.status().as_u16(),
+        duration_ms,
+        "慢请求: {} {} 耗时 {}ms，超过阈值 {}ms",
+        method,
+        route,
+        duration_ms,
+        config.threshold.as_millis()
+    );
+    #[cfg(feature = "metrics")]
+    rspring_core::metrics::counter!(SLOW_REQUESTS_METRIC, "method" => method, "route" => route)
+        .increment(1);
+    response
+}
+
+/// 将字符串截断到指定字符数，截断时以 `...` 结尾
+fn truncate(value: &str, max_chars: usize) -> String {
+    match value.char_indices().nth(max_chars) {
+        Some((index, _)) => format!("{}...", &value[..index]),
+        None => value.to_string(),
+    }
+}
+
+// 默认值函数
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_threshold() -> Duration {
+    Duration::from_secs(1)
+}
+
+fn default_max_params_length() -> usize {
+    256
+}
+
+fn default_sensitive_params() -> Vec<String> {
+    ["password", "secret", "token", "credentials"]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::extract::Path;
+    use axum::routing::get;
+    use std::io;
+    use std::sync::Mutex;
+    use tower::ServiceExt;
+
+    /// 收集日志输出的写入器
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// 测试只记录超过阈值的请求，参数摘要替换敏感值并截断过长的值
+    #[tokio::test]
+    async fn test_slow_request_log() {
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let writer = output.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_ansi(false)
+            .with_writer(move || Captured(writer.clone()))
+            .finish();
+        let _default = tracing::subscriber::set_default(subscriber);
+
+        let config = SlowRequestConfig {
+            threshold: Duration::from_millis(20),
+            ..SlowRequestConfig::default()
+        };
+        let router: Router = config.apply(
+            Router::new()
+                .route(
+                    "/orders/:id",
+                    get(|Path(_id): Path<u32>| async {
+                        tokio::time::sleep(Duration::from_millis(30)).await;
+                        "order"
+                    }),
+                )
+                .route("/ping", get(|| async { "pong" })),
+        );
+        let long = "x".repeat(100);
+        for uri in [
+            format!("/orders/7?access_token=abc&note={}", long),
+            "/ping".to_string(),
+        ] {
+            let request = Request::get(uri).body(Body::empty()).unwrap();
+            router.clone().oneshot(request).await.unwrap();
+        }
+
+        let output = String::from_utf8(output.lock().unwrap().clone()).unwrap();
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].contains("慢请求: GET /orders/:id 耗时"));
+        assert!(lines[0].contains(SLOW_REQUEST_TARGET));
+        assert!(lines[0].contains(&format!(
+            "params=id=7 access_token=****** note={}...",
+            "x".repeat(MAX_VALUE_CHARS)
+        )));
+        assert!(lines[0].contains("status=200"));
+        assert!(lines[0].contains("principal=\"-\""));
+    }
+}
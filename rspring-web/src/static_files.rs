@@ -0,0 +1,221 @@
+//! 静态资源模块
+//!
+//! 根据 `[web.static]` 配置挂载静态资源目录，支持缓存头和单页应用的 `index.html` 回退
+
+use std::path::Path;
+use std::time::Duration;
+
+use axum::http::{header, HeaderValue};
+use axum::Router;
+use serde::{Deserialize, Serialize};
+use tower::Layer;
+use tower_http::services::{ServeDir, ServeFile};
+use tower_http::set_header::SetResponseHeaderLayer;
+
+/// 静态资源配置
+///
+/// # 示例
+/// ```toml
+/// [web.static]
+/// path = "/assets"
+/// dir = "./public"
+/// cache_max_age = "1h"
+/// spa_fallback = true
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct StaticConfig {
+    /// 是否启用
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 访问路径前缀
+    ///
+    /// # 默认值
+    /// `"/static"`
+    #[serde(default = "default_static_path")]
+    pub path: String,
+    /// 静态资源目录
+    ///
+    /// # 默认值
+    /// `"./public"`
+    #[serde(default = "default_static_dir")]
+    pub dir: String,
+    /// 浏览器缓存时长（可选），如 `"1h"`、`"7d"`
+    ///
+    /// 设置后为响应添加 `Cache-Control: public, max-age=...`
+    #[serde(default, with = "rspring_core::config::duration::option")]
+    pub cache_max_age: Option<Duration>,
+    /// 文件不存在时是否回退到 `index.html`，用于单页应用的前端路由
+    ///
+    /// # 默认值
+    /// `false`
+    #[serde(default)]
+    pub spa_fallback: bool,
+}
+
+impl Default for StaticConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            path: default_static_path(),
+            dir: default_static_dir(),
+            cache_max_age: None,
+            spa_fallback: false,
+        }
+    }
+}
+
+impl StaticConfig {
+    /// 将静态资源目录挂载到路由
+    ///
+    /// 路径为 `/` 时作为路由的回退服务，不会覆盖已注册的接口
+    ///
+    /// # 示例
+    /// ```rust
+    /// let web_config = WebConfig::load(&config)?;
+    /// let mut app = Router::new().route("/api/users", get(list_users));
+    /// if let Some(static_config) = &web_config.static_files {
+    ///     app = static_config.mount(app);
+    /// }
+    /// ```
+    pub fn mount<S>(&self, router: Router<S>) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        if !self.enabled {
+            return router;
+        }
+
+        let serve_dir = ServeDir::new(&self.dir).append_index_html_on_directories(true);
+        let cache_control = self.cache_control();
+        let cache_layer = SetResponseHeaderLayer::if_not_present(header::CACHE_CONTROL, move |_: &_| {
+            cache_control.clone()
+        });
+
+        tracing::info!("挂载静态资源目录 {} -> {}", self.path, self.dir);
+
+        if self.spa_fallback {
+            let index = ServeFile::new(Path::new(&self.dir).join("index.html"));
+            self.mount_service(router, cache_layer.layer(serve_dir.fallback(index)))
+        } else {
+            self.mount_service(router, cache_layer.layer(serve_dir))
+        }
+    }
+
+    /// 按访问路径挂载服务
+    fn mount_service<S, T>(&self, router: Router<S>, service: T) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+        T: tower::Service<axum::extract::Request, Error = std::convert::Infallible> + Clone + Send + 'static,
+        T::Response: axum::response::IntoResponse,
+        T::Future: Send + 'static,
+    {
+        let path = self.path.trim_end_matches('/');
+        if path.is_empty() {
+            router.fallback_service(service)
+        } else {
+            router.nest_service(path, service)
+        }
+    }
+
+    /// 生成 `Cache-Control` 响应头
+    fn cache_control(&self) -> Option<HeaderValue> {
+        self.cache_max_age.map(|max_age| {
+            HeaderValue::from_str(&format!("public, max-age={}", max_age.as_secs()))
+                .expect("Cache-Control 头格式无效")
+        })
+    }
+}
+
+// 默认值函数
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_static_path() -> String {
+    "/static".to_string()
+}
+
+fn default_static_dir() -> String {
+    "./public".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::extract::Request;
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use std::fs;
+    use tower::ServiceExt;
+
+    /// 构造一个包含 index.html 和 app.js 的临时目录
+    fn public_dir() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("index.html"), "<html>index</html>").unwrap();
+        fs::write(dir.path().join("app.js"), "console.log('app')").unwrap();
+        dir
+    }
+
+    /// 发送 GET 请求
+    async fn get_status(router: Router, uri: &str) -> (StatusCode, Option<String>) {
+        let response = router
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let cache_control = response
+            .headers()
+            .get(header::CACHE_CONTROL)
+            .map(|value| value.to_str().unwrap().to_string());
+        (response.status(), cache_control)
+    }
+
+    /// 测试静态资源挂载与缓存头
+    #[tokio::test]
+    async fn test_mount_static_dir() {
+        let dir = public_dir();
+        let config = StaticConfig {
+            path: "/assets".to_string(),
+            dir: dir.path().to_string_lossy().to_string(),
+            cache_max_age: Some(Duration::from_secs(3600)),
+            ..StaticConfig::default()
+        };
+
+        let router = config.mount(Router::new().route("/api", get(|| async { "api" })));
+
+        let (status, cache_control) = get_status(router.clone(), "/assets/app.js").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(cache_control.as_deref(), Some("public, max-age=3600"));
+
+        let (status, _) = get_status(router.clone(), "/assets/missing.js").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _) = get_status(router, "/api").await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    /// 测试单页应用回退到 index.html
+    #[tokio::test]
+    async fn test_spa_fallback() {
+        let dir = public_dir();
+        let config = StaticConfig {
+            path: "/".to_string(),
+            dir: dir.path().to_string_lossy().to_string(),
+            spa_fallback: true,
+            ..StaticConfig::default()
+        };
+
+        let router = config.mount(Router::new().route("/api", get(|| async { "api" })));
+
+        let (status, cache_control) = get_status(router.clone(), "/users/42").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(cache_control.is_none());
+
+        let (status, _) = get_status(router, "/api").await;
+        assert_eq!(status, StatusCode::OK);
+    }
+}
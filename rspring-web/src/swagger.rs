@@ -0,0 +1,170 @@
+//! 文档界面模块
+//!
+//! 提供内置的 Swagger UI / RapiDoc 页面，页面加载 `[web.openapi]` 配置的 OpenAPI 文档
+
+use axum::response::Html;
+use axum::routing::get;
+use axum::Router;
+
+use crate::openapi::OpenApi;
+use crate::properties::{DocsUi, OpenApiConfig};
+
+/// Swagger UI 默认静态资源地址
+const SWAGGER_UI_ASSETS: &str = "https://unpkg.com/swagger-ui-dist@5";
+
+/// RapiDoc 默认静态资源地址
+const RAPIDOC_ASSETS: &str = "https://unpkg.com/rapidoc@9/dist";
+
+/// 生成文档界面页面
+///
+/// # 参数
+/// * `ui` - 文档界面类型
+/// * `spec_url` - OpenAPI JSON 文档地址
+/// * `assets_url` - 静态资源地址，为 `None` 时使用公共 CDN
+/// * `title` - 页面标题
+///
+/// # 返回值
+/// 页面 HTML，`DocsUi::None` 时返回 `None`。标题和地址按所在位置转义，
+/// 不会破坏页面结构
+pub fn docs_page(ui: DocsUi, spec_url: &str, assets_url: Option<&str>, title: &str) -> Option<String> {
+    let title = escape_html(title);
+    let page = match ui {
+        DocsUi::Swagger => {
+            let assets = escape_html(assets_url.unwrap_or(SWAGGER_UI_ASSETS).trim_end_matches('/'));
+            let spec_url = script_string(spec_url);
+            format!(
+                r##"<!DOCTYPE html>
+<html lang="zh-CN">
+<head>
+  <meta charset="utf-8" />
+  <title>{title}</title>
+  <link rel="stylesheet" href="{assets}/swagger-ui.css" />
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="{assets}/swagger-ui-bundle.js"></script>
+  <script>
+    window.onload = () => {{
+      window.ui = SwaggerUIBundle({{ url: {spec_url}, dom_id: "#swagger-ui" }});
+    }};
+  </script>
+</body>
+</html>
+"##
+            )
+        }
+        DocsUi::Rapidoc => {
+            let assets = escape_html(assets_url.unwrap_or(RAPIDOC_ASSETS).trim_end_matches('/'));
+            let spec_url = escape_html(spec_url);
+            format!(
+                r##"<!DOCTYPE html>
+<html lang="zh-CN">
+<head>
+  <meta charset="utf-8" />
+  <title>{title}</title>
+  <script type="module" src="{assets}/rapidoc-min.js"></script>
+</head>
+<body>
+  <rapi-doc spec-url="{spec_url}" render-style="read"></rapi-doc>
+</body>
+</html>
+"##
+            )
+        }
+        DocsUi::None => return None,
+    };
+
+    Some(page)
+}
+
+/// 转义 HTML 文本和属性值中的特殊字符
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 生成 `<script>` 中的 JavaScript 字符串字面量，`<` 转义为 `\u003c`，避免提前结束脚本
+fn script_string(value: &str) -> String {
+    serde_json::Value::from(value).to_string().replace('<', "\\u003c")
+}
+
+impl OpenApi {
+    /// 按配置生成文档路由
+    ///
+    /// 同时暴露 OpenAPI JSON 文档和文档界面；当前环境未启用时返回空路由
+    ///
+    /// # 参数
+    /// * `config` - `[web.openapi]` 配置
+    /// * `profile` - 当前激活的环境
+    ///
+    /// # 示例
+    /// ```rust
+    /// let web_config = WebConfig::load(&config)?;
+    /// let docs = OpenApi::from_config(&app_config)
+    ///     .controller::<UserController>()
+    ///     .docs_router(&web_config.openapi, config.profile());
+    ///
+    /// let app = Router::new().merge(docs);
+    /// ```
+    pub fn docs_router(self, config: &OpenApiConfig, profile: &str) -> Router {
+        if !config.is_enabled(profile) {
+            return Router::new();
+        }
+
+        let page = docs_page(config.ui, &config.path, config.assets_url.as_deref(), self.title());
+        let router = self.router_at(&config.path);
+
+        match page {
+            Some(page) => router.route(&config.ui_path, get(move || async move { Html(page) })),
+            None => router,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试 Swagger UI 页面指向配置的文档地址
+    #[test]
+    fn test_swagger_page() {
+        let page = docs_page(DocsUi::Swagger, "/api-docs", None, "测试服务").unwrap();
+        assert!(page.contains(r#"url: "/api-docs""#));
+        assert!(page.contains("https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"));
+        assert!(page.contains("<title>测试服务</title>"));
+    }
+
+    /// 测试自托管资源地址和关闭界面
+    #[test]
+    fn test_custom_assets_and_none() {
+        let page = docs_page(DocsUi::Rapidoc, "/docs.json", Some("/assets/rapidoc/"), "测试").unwrap();
+        assert!(page.contains(r#"src="/assets/rapidoc/rapidoc-min.js""#));
+        assert!(page.contains(r#"spec-url="/docs.json""#));
+
+        assert!(docs_page(DocsUi::None, "/api-docs", None, "测试").is_none());
+    }
+
+    /// 测试标题和文档地址中的特殊字符被转义
+    #[test]
+    fn test_escape() {
+        let title = "<script>alert(1)</script>";
+        let spec_url = r#"/docs"</script><script>alert(1)//"#;
+        let page = docs_page(DocsUi::Swagger, spec_url, None, title).unwrap();
+        assert!(page.contains("<title>&lt;script&gt;alert(1)&lt;/script&gt;</title>"));
+        assert!(page.contains(r#"url: "/docs\"\u003c/script>\u003cscript>alert(1)//""#));
+        assert!(!page.contains("alert(1)</script>"));
+
+        let page = docs_page(DocsUi::Rapidoc, spec_url, None, "测试").unwrap();
+        assert!(page.contains(r#"spec-url="/docs&quot;&lt;/script&gt;&lt;script&gt;alert(1)//""#));
+    }
+}
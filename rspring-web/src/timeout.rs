@@ -0,0 +1,239 @@
+//! 请求超时模块
+//!
+//! 根据 `[web.timeout]` 配置为请求设置全局和按路径的超时时间。超时后处理函数的
+//! future 会被丢弃（即取消执行），并返回 503 或 408 错误响应
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::{MatchedPath, Request};
+use axum::http::StatusCode;
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use axum::Router;
+use rspring_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::controller::route_path;
+use crate::exception::error_response;
+
+/// 请求超时配置
+///
+/// # 示例
+/// ```toml
+/// [web.timeout]
+/// default = "30s"
+/// status = 503
+///
+/// [[web.timeout.routes]]
+/// path = "/api/reports/**"
+/// timeout = "2m"
+///
+/// [[web.timeout.routes]]
+/// path = "/api/users/{id}"
+/// timeout = "5s"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TimeoutConfig {
+    /// 是否启用
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 全局超时时间（可选），未匹配到路径配置的请求使用此值，不设置则不限制
+    #[serde(default, with = "rspring_core::config::duration::option")]
+    pub default: Option<Duration>,
+    /// 超时响应的状态码，只能为 503 或 408
+    ///
+    /// # 默认值
+    /// `503`
+    #[serde(default = "default_status")]
+    pub status: u16,
+    /// 按路径的超时配置，优先于全局超时
+    #[serde(default)]
+    pub routes: Vec<RouteTimeout>,
+}
+
+/// 按路径的超时配置
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RouteTimeout {
+    /// 路由路径，支持 `{id}` 路径变量，以 `/**` 结尾时匹配该前缀下的所有路径
+    pub path: String,
+    /// 超时时间
+    #[serde(with = "rspring_core::config::duration")]
+    pub timeout: Duration,
+}
+
+impl Default for TimeoutConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            default: None,
+            status: default_status(),
+            routes: Vec::new(),
+        }
+    }
+}
+
+impl TimeoutConfig {
+    /// 获取请求的超时时间
+    ///
+    /// 精确匹配的路由优先，其次为最长的前缀匹配，都未匹配时使用全局超时
+    ///
+    /// # 参数
+    /// * `matched_path` - 路由模板，如 `/api/users/:id`
+    /// * `path` - 实际请求路径
+    pub fn timeout_for(&self, matched_path: Option<&str>, path: &str) -> Option<Duration> {
+        let exact = self.routes.iter().find(|route| {
+            !route.path.ends_with("/**")
+                && (route.path == path || matched_path == Some(route_path(&route.path).as_str()))
+        });
+        if let Some(route) = exact {
+            return Some(route.timeout);
+        }
+
+        self.routes
+            .iter()
+            .filter_map(|route| {
+                let prefix = route.path.strip_suffix("/**")?;
+                let matched = path == prefix || path.starts_with(&format!("{}/", prefix.trim_end_matches('/')));
+                matched.then_some((prefix.len(), route.timeout))
+            })
+            .max_by_key(|(len, _)| *len)
+            .map(|(_, timeout)| timeout)
+            .or(self.default)
+    }
+
+    /// 将超时中间件应用到路由，未启用时原样返回
+    ///
+    /// 应在注册完所有路由后调用，以便按路由模板匹配
+    ///
+    /// # 错误
+    /// 超时状态码不是 503 或 408 时返回验证错误
+    pub fn apply<S>(&self, router: Router<S>) -> Result<Router<S>>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        if !self.enabled {
+            return Ok(router);
+        }
+
+        let status = match StatusCode::from_u16(self.status) {
+            Ok(status @ (StatusCode::SERVICE_UNAVAILABLE | StatusCode::REQUEST_TIMEOUT)) => status,
+            _ => {
+                return Err(Error::validation(format!(
+                    "无效的超时状态码: {}，只支持 503 或 408",
+                    self.status
+                )))
+            }
+        };
+
+        let config = Arc::new(self.clone());
+        Ok(router.layer(middleware::from_fn(move |request: Request, next: Next| {
+            enforce_timeout(config.clone(), status, request, next)
+        })))
+    }
+}
+
+/// 超时中间件
+async fn enforce_timeout(config: Arc<TimeoutConfig>, status: StatusCode, request: Request, next: Next) -> Response {
+    let matched_path = request.extensions().get::<MatchedPath>().map(|path| path.as_str().to_string());
+    let path = request.uri().path().to_string();
+
+    let Some(timeout) = config.timeout_for(matched_path.as_deref(), &path) else {
+        return next.run(request).await;
+    };
+
+    match tokio::time::timeout(timeout, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => {
+            tracing::warn!("请求处理超时 ({:?}): {}", timeout, path);
+            error_response(status, format!("请求处理超时 ({}ms)", timeout.as_millis()))
+        }
+    }
+}
+
+// 默认值函数
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_status() -> u16 {
+    StatusCode::SERVICE_UNAVAILABLE.as_u16()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::routing::get;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use tower::ServiceExt;
+
+    /// 构造包含路径超时配置的配置
+    fn config() -> TimeoutConfig {
+        TimeoutConfig {
+            default: Some(Duration::from_millis(50)),
+            routes: vec![
+                RouteTimeout {
+                    path: "/api/reports/**".to_string(),
+                    timeout: Duration::from_secs(120),
+                },
+                RouteTimeout {
+                    path: "/api/users/{id}".to_string(),
+                    timeout: Duration::from_secs(5),
+                },
+            ],
+            ..TimeoutConfig::default()
+        }
+    }
+
+    /// 测试超时时间匹配规则
+    #[test]
+    fn test_timeout_for() {
+        let config = config();
+        assert_eq!(
+            config.timeout_for(Some("/api/users/:id"), "/api/users/42"),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(
+            config.timeout_for(None, "/api/reports/2024/daily"),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(config.timeout_for(None, "/api/reportsx"), Some(Duration::from_millis(50)));
+        assert_eq!(TimeoutConfig::default().timeout_for(None, "/"), None);
+    }
+
+    /// 测试超时后返回错误响应并取消处理函数
+    #[tokio::test]
+    async fn test_request_timeout() {
+        let finished = Arc::new(AtomicBool::new(false));
+        let flag = finished.clone();
+        let router = Router::new().route(
+            "/slow",
+            get(move || async move {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                flag.store(true, Ordering::SeqCst);
+                "done"
+            }),
+        );
+
+        let router = config().apply(router).unwrap();
+        let response = router
+            .oneshot(Request::get("/slow").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        assert!(!finished.load(Ordering::SeqCst));
+
+        let invalid = TimeoutConfig {
+            status: 500,
+            ..TimeoutConfig::default()
+        };
+        assert!(invalid.apply(Router::<()>::new()).is_err());
+    }
+}
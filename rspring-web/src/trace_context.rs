@@ -0,0 +1,133 @@
+//! 链路追踪上下文模块
+//!
+//! 根据 `[web.trace_context]` 配置读取入站请求的 `traceparent`、`tracestate` 请求头，
+//! 在上游链路的子跨度中处理请求，没有或无效时开始新的链路。处理期间：
+//! - 可通过 `rspring_core::trace::current_trace_context()` 获取当前链路
+//! - 链路 ID 和跨度 ID 附加到每一条日志
+//! - 出站 HTTP 请求通过 [`inject`] 附加链路头，SQS 消息自动附加
+
+use axum::extract::Request;
+use axum::http::{HeaderMap, HeaderName, HeaderValue};
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use axum::Router;
+use rspring_core::trace::{continue_trace, outbound_trace_headers, TraceContext};
+use serde::{Deserialize, Serialize};
+
+/// 链路追踪上下文配置
+///
+/// # 示例
+/// ```toml
+/// [web.trace_context]
+/// enabled = false
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TraceContextConfig {
+    /// 是否启用
+    ///
+    /// # 默认值
+    /// `true`
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+impl Default for TraceContextConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+        }
+    }
+}
+
+impl TraceContextConfig {
+    /// 将链路追踪中间件应用到路由，未启用时原样返回
+    ///
+    /// 应放在请求 ID 中间件之内、其他中间件之外，使访问日志和拦截器的日志也带有链路 ID
+    pub fn apply<S>(&self, router: Router<S>) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        if !self.enabled {
+            return router;
+        }
+        router.layer(middleware::from_fn(propagate_trace_context))
+    }
+}
+
+/// 向出站请求的请求头附加当前链路的 `traceparent` 和 `tracestate`
+///
+/// 不在链路上下文中时不做修改
+///
+/// # 示例
+/// ```rust
+/// let mut headers = HeaderMap::new();
+/// trace_context::inject(&mut headers);
+/// let response = http.get(url).headers(headers).send().await?;
+/// ```
+pub fn inject(headers: &mut HeaderMap) {
+    for (name, value) in outbound_trace_headers() {
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            headers.insert(HeaderName::from_static(name), value);
+        }
+    }
+}
+
+/// 链路追踪中间件
+async fn propagate_trace_context(request: Request, next: Next) -> Response {
+    let headers = request.headers();
+    let parent =
+        TraceContext::extract(|name| headers.get(name).and_then(|value| value.to_str().ok()));
+    continue_trace(parent, next.run(request)).await
+}
+
+// 默认值函数
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::routing::get;
+    use rspring_core::trace::{current_trace_context, TRACEPARENT_HEADER};
+    use tower::ServiceExt;
+
+    /// 测试延续上游链路，出站请求头携带当前跨度
+    #[tokio::test]
+    async fn test_trace_context_middleware() {
+        let router: Router = TraceContextConfig::default().apply(Router::new().route(
+            "/",
+            get(|| async {
+                let mut headers = HeaderMap::new();
+                inject(&mut headers);
+                let current = current_trace_context().unwrap();
+                assert_eq!(headers[TRACEPARENT_HEADER], current.traceparent());
+                current.trace_id
+            }),
+        ));
+        let call = |traceparent: &'static str| {
+            let request = Request::get("/")
+                .header(TRACEPARENT_HEADER, traceparent)
+                .body(Body::empty())
+                .unwrap();
+            router.clone().oneshot(request)
+        };
+
+        let response = call("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"4bf92f3577b34da6a3ce929d0e0e4736");
+
+        let response = call("garbage").await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body.len(), 32);
+        assert_ne!(&body[..], b"4bf92f3577b34da6a3ce929d0e0e4736");
+    }
+}
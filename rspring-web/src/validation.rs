@@ -0,0 +1,218 @@
+//! 请求参数校验模块
+//!
+//! 提供 `Valid<T>` 提取器，在反序列化请求体后执行 `validator` 风格的校验规则，
+//! 校验失败时返回带有字段级错误信息的 400 `ApiResponse`。JSON 和表单请求体
+//! 使用同一套校验流程
+
+use std::borrow::Cow;
+
+use axum::async_trait;
+use axum::extract::{FromRequest, Request};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationErrors, ValidationErrorsKind};
+
+use crate::exception::GLOBAL_EXCEPTION_HANDLER;
+use crate::extract::read_body;
+use crate::problem::{ErrorFormat, ProblemDetail};
+use crate::response::ApiResponse;
+
+/// 经过校验的请求体提取器
+///
+/// 将请求体按 `Content-Type` 反序列化为 `T`（表单或 JSON），并调用 `Validate::validate` 执行校验
+///
+/// # 示例
+/// ```rust
+/// #[derive(Deserialize, Validate)]
+/// pub struct CreateUserRequest {
+///     #[validate(length(min = 2, max = 50))]
+///     pub name: String,
+///     #[validate(email)]
+///     pub email: String,
+/// }
+///
+/// async fn create_user(Valid(request): Valid<CreateUserRequest>) -> WebResult<ApiResponse<User>> {
+///     // request 已通过校验
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Valid<T>(pub T);
+
+impl<T> std::ops::Deref for Valid<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[async_trait]
+impl<T, S> FromRequest<S> for Valid<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = ValidationRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let value = read_body(req, state).await.map_err(ValidationRejection::Body)?;
+        validate(value).map(Valid)
+    }
+}
+
+/// 执行校验
+///
+/// 供各类请求体提取器复用同一套校验流程
+pub fn validate<T: Validate>(value: T) -> Result<T, ValidationRejection> {
+    match value.validate() {
+        Ok(()) => Ok(value),
+        Err(errors) => Err(ValidationRejection::Invalid(collect_field_errors(&errors))),
+    }
+}
+
+/// 字段级校验错误
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    /// 字段路径，嵌套字段使用 `.` 分隔，列表元素使用 `[下标]`
+    pub field: String,
+    /// 校验规则代码，如 `length`、`email`
+    pub code: String,
+    /// 错误描述
+    pub message: String,
+}
+
+/// 校验失败的拒绝响应
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationRejection {
+    /// 请求体无法解析
+    Body(String),
+    /// 字段校验未通过
+    Invalid(Vec<FieldError>),
+}
+
+impl IntoResponse for ValidationRejection {
+    fn into_response(self) -> Response {
+        let (message, errors) = match self {
+            Self::Body(message) => (format!("请求体解析失败: {}", message), Vec::new()),
+            Self::Invalid(errors) => ("请求参数校验失败".to_string(), errors),
+        };
+
+        if GLOBAL_EXCEPTION_HANDLER.error_format() == ErrorFormat::Problem {
+            let errors = serde_json::to_value(&errors).unwrap_or_default();
+            return ProblemDetail::new(StatusCode::BAD_REQUEST)
+                .with_detail(message)
+                .with_extension("errors", errors)
+                .into_response();
+        }
+
+        let body = ApiResponse::new(StatusCode::BAD_REQUEST.as_u16() as i32, message, Some(errors));
+
+        (StatusCode::BAD_REQUEST, Json(body)).into_response()
+    }
+}
+
+/// 将 `ValidationErrors` 展开为扁平的字段错误列表
+///
+/// 结果按字段路径排序，保证响应内容稳定
+pub fn collect_field_errors(errors: &ValidationErrors) -> Vec<FieldError> {
+    let mut result = Vec::new();
+    flatten_errors("", errors, &mut result);
+    result.sort_by(|a, b| a.field.cmp(&b.field).then_with(|| a.code.cmp(&b.code)));
+    result
+}
+
+/// 递归展开嵌套结构和列表中的校验错误
+fn flatten_errors(prefix: &str, errors: &ValidationErrors, result: &mut Vec<FieldError>) {
+    for (field, kind) in errors.errors() {
+        let path = if prefix.is_empty() {
+            field.to_string()
+        } else {
+            format!("{}.{}", prefix, field)
+        };
+
+        match kind {
+            ValidationErrorsKind::Field(field_errors) => {
+                for error in field_errors {
+                    let message = error
+                        .message
+                        .clone()
+                        .unwrap_or_else(|| Cow::Owned(format!("字段 {} 不满足 {} 规则", path, error.code)));
+
+                    result.push(FieldError {
+                        field: path.clone(),
+                        code: error.code.to_string(),
+                        message: message.into_owned(),
+                    });
+                }
+            }
+            ValidationErrorsKind::Struct(nested) => flatten_errors(&path, nested, result),
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    flatten_errors(&format!("{}[{}]", path, index), nested, result);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use validator::ValidationError;
+
+    struct Signup {
+        name: String,
+        age: u32,
+    }
+
+    impl Validate for Signup {
+        fn validate(&self) -> Result<(), ValidationErrors> {
+            let mut errors = ValidationErrors::new();
+            if self.name.is_empty() {
+                let mut error = ValidationError::new("length");
+                error.message = Some(Cow::Borrowed("用户名不能为空"));
+                errors.add("name", error);
+            }
+            if self.age < 18 {
+                errors.add("age", ValidationError::new("range"));
+            }
+            if errors.is_empty() {
+                Ok(())
+            } else {
+                Err(errors)
+            }
+        }
+    }
+
+    /// 测试校验通过时原样返回
+    #[test]
+    fn test_validate_success() {
+        let signup = validate(Signup { name: "rspring".to_string(), age: 20 }).unwrap();
+        assert_eq!(signup.name, "rspring");
+    }
+
+    /// 测试字段错误的收集与排序
+    #[test]
+    fn test_collect_field_errors() {
+        let rejection = validate(Signup { name: String::new(), age: 3 }).err().unwrap();
+
+        let ValidationRejection::Invalid(errors) = rejection else {
+            panic!("应返回字段校验错误");
+        };
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].field, "age");
+        assert_eq!(errors[0].code, "range");
+        assert_eq!(errors[1].field, "name");
+        assert_eq!(errors[1].message, "用户名不能为空");
+    }
+
+    /// 测试拒绝响应的状态码
+    #[test]
+    fn test_rejection_status() {
+        let response = ValidationRejection::Body("EOF".to_string()).into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
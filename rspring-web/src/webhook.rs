@@ -0,0 +1,34 @@
+//! Webhook 客户端模块
+//!
+//! 审计事件推送和错误报告推送共用的 HTTP 客户端构建
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use rspring_core::{Error, Result};
+
+/// 创建推送使用的 HTTP 客户端，每个请求附加配置的请求头
+///
+/// # 错误
+/// 地址或请求头无效时返回错误
+pub(crate) fn build_client(
+    url: &str,
+    headers: &BTreeMap<String, String>,
+    timeout: Duration,
+) -> Result<reqwest::Client> {
+    url::Url::parse(url)
+        .map_err(|e| Error::validation(format!("无效的推送地址 {}: {}", url, e)))?;
+    let mut default_headers = reqwest::header::HeaderMap::new();
+    for (name, value) in headers {
+        let name = reqwest::header::HeaderName::from_bytes(name.as_bytes())
+            .map_err(|e| Error::validation(format!("无效的请求头名称 {}: {}", name, e)))?;
+        let value = reqwest::header::HeaderValue::from_str(value)
+            .map_err(|e| Error::validation(format!("请求头 {} 的值无效: {}", name, e)))?;
+        default_headers.insert(name, value);
+    }
+    reqwest::Client::builder()
+        .timeout(timeout)
+        .default_headers(default_headers)
+        .build()
+        .map_err(|e| Error::internal(format!("创建 HTTP 客户端失败: {}", e)))
+}
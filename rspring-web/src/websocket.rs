@@ -0,0 +1,721 @@
+//! WebSocket 消息代理模块
+//!
+//! 提供类似 Spring Messaging 的简单消息代理，客户端通过 WebSocket 以 JSON 帧通信：
+//! - `/topic/...` 广播目的地，所有订阅者都会收到消息
+//! - `/user/queue/...` 用户目的地，服务端通过 [`MessageBroker::send_to_user`] 发送给指定用户的所有会话
+//! - `/app/...` 应用目的地，客户端发送的消息交给注册的处理函数，返回值广播到同名的 `/topic/...`
+//!
+//! 客户端默认不能直接向 `/topic/...` 发送消息，避免伪造服务端广播，需要时通过
+//! [`MessageBroker::allow_client_topic_publish`] 开启；每个会话的订阅数量受
+//! [`MessageBroker::max_subscriptions`] 限制
+//!
+//! 多实例部署时可通过 [`BrokerRelay`] 在实例之间转发消息，启用 `websocket-redis` 特性后
+//! 可使用基于 Redis Pub/Sub 的 [`RedisBrokerRelay`]
+//!
+//! # 帧格式
+//! ```json
+//! {"type": "subscribe", "id": "sub-0", "destination": "/topic/chat"}
+//! {"type": "unsubscribe", "id": "sub-0"}
+//! {"type": "send", "destination": "/app/chat", "body": {"text": "hello"}}
+//! {"type": "message", "subscription": "sub-0", "destination": "/topic/chat", "body": {"text": "hello"}}
+//! {"type": "error", "message": "不支持的目的地: /foo"}
+//! ```
+
+#[cfg(feature = "websocket-redis")]
+mod redis_relay;
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock, Weak};
+
+use async_trait::async_trait;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::Extension;
+use axum::response::Response;
+use axum::routing::get;
+use axum::Router;
+use futures::future::BoxFuture;
+use futures::stream::BoxStream;
+use futures::{FutureExt, SinkExt, StreamExt};
+use rspring_core::security::{current_principal, with_principal, Principal};
+use rspring_core::{Error, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::mpsc;
+
+#[cfg(feature = "websocket-redis")]
+pub use redis_relay::RedisBrokerRelay;
+
+/// 广播目的地前缀
+pub const TOPIC_PREFIX: &str = "/topic/";
+/// 点对点目的地前缀，配合用户目的地使用
+pub const QUEUE_PREFIX: &str = "/queue/";
+/// 用户目的地前缀
+pub const USER_PREFIX: &str = "/user";
+/// 应用目的地前缀
+pub const APP_PREFIX: &str = "/app/";
+
+/// 每个会话待发送消息的缓冲数量，超出时丢弃消息
+const SESSION_BUFFER: usize = 256;
+/// 每个会话默认的最大订阅数量
+const DEFAULT_MAX_SUBSCRIPTIONS: usize = 64;
+
+/// 客户端发送的帧
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientFrame {
+    /// 订阅目的地
+    Subscribe {
+        /// 订阅 ID，由客户端生成，在会话内唯一
+        id: String,
+        /// 目的地
+        destination: String,
+    },
+    /// 取消订阅
+    Unsubscribe {
+        /// 订阅 ID
+        id: String,
+    },
+    /// 发送消息
+    Send {
+        /// 目的地
+        destination: String,
+        /// 消息体
+        #[serde(default)]
+        body: Value,
+    },
+}
+
+/// 服务端发送的帧
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerFrame {
+    /// 订阅的消息
+    Message {
+        /// 订阅 ID
+        subscription: String,
+        /// 目的地
+        destination: String,
+        /// 消息体
+        body: Value,
+    },
+    /// 错误信息
+    Error {
+        /// 错误描述
+        message: String,
+    },
+}
+
+/// 在实例之间转发的消息
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct BrokerEnvelope {
+    /// 发出消息的代理实例 ID，用于忽略自己发出的消息
+    pub origin: String,
+    /// 目的地
+    pub destination: String,
+    /// 目标用户，为空时为广播消息
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// 消息体
+    pub body: Value,
+}
+
+/// 消息转发特征
+///
+/// 多实例部署时，每个实例的代理将本地发出的消息发布到转发通道，并投递其他实例发布的消息
+#[async_trait]
+pub trait BrokerRelay: Send + Sync {
+    /// 发布消息到所有实例
+    async fn publish(&self, envelope: &BrokerEnvelope) -> Result<()>;
+
+    /// 订阅所有实例发布的消息（包括自己发布的）
+    async fn subscribe(&self) -> Result<BoxStream<'static, BrokerEnvelope>>;
+}
+
+/// 处理函数的调用上下文
+#[derive(Debug, Clone)]
+pub struct MessageContext {
+    /// 会话 ID
+    pub session_id: String,
+    /// 建立连接时的用户，未认证时为 `None`
+    pub principal: Option<Principal>,
+    /// 消息的目的地，如 `/app/chat`
+    pub destination: String,
+}
+
+/// 应用目的地的处理函数
+type MessageHandler =
+    Arc<dyn Fn(MessageContext, Value) -> BoxFuture<'static, Result<Option<Value>>> + Send + Sync>;
+
+/// 已连接的会话
+struct Session {
+    /// 建立连接时的用户名
+    user: Option<String>,
+    /// 订阅 ID 到目的地的映射
+    subscriptions: HashMap<String, String>,
+    /// 待发送的帧
+    sender: mpsc::Sender<ServerFrame>,
+}
+
+/// 消息代理的共享状态
+struct BrokerInner {
+    /// 实例 ID
+    id: String,
+    /// 已连接的会话
+    sessions: RwLock<HashMap<String, Session>>,
+    /// 应用目的地的处理函数
+    handlers: RwLock<HashMap<String, MessageHandler>>,
+    /// 实例间的消息转发
+    relay: Option<Arc<dyn BrokerRelay>>,
+    /// 是否允许客户端直接向广播目的地发送消息
+    client_topic_publish: AtomicBool,
+    /// 每个会话的最大订阅数量
+    max_subscriptions: AtomicUsize,
+}
+
+/// WebSocket 消息代理
+///
+/// # 示例
+/// ```rust
+/// let broker = MessageBroker::new();
+/// broker.handle("/app/chat", |ctx: MessageContext, message: ChatMessage| async move {
+///     Ok(Some(ChatMessage { from: ctx.principal.map(|p| p.name), ..message }))
+/// });
+///
+/// let app = Router::new().merge(broker.router("/ws"));
+///
+/// // 在业务代码中推送消息
+/// broker.send("/topic/notice", &notice).await?;
+/// broker.send_to_user("alice", "/queue/orders", &order).await?;
+/// ```
+#[derive(Clone)]
+pub struct MessageBroker {
+    inner: Arc<BrokerInner>,
+}
+
+impl Default for MessageBroker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageBroker {
+    /// 创建只在本实例内投递消息的代理
+    pub fn new() -> Self {
+        Self::build(None)
+    }
+
+    /// 创建通过转发通道在实例之间投递消息的代理
+    ///
+    /// # 错误
+    /// 订阅转发通道失败时返回错误
+    pub async fn with_relay(relay: Arc<dyn BrokerRelay>) -> Result<Self> {
+        let broker = Self::build(Some(relay.clone()));
+        let mut stream = relay.subscribe().await?;
+        let weak: Weak<BrokerInner> = Arc::downgrade(&broker.inner);
+        tokio::spawn(async move {
+            while let Some(envelope) = stream.next().await {
+                let Some(inner) = weak.upgrade() else { break };
+                if envelope.origin != inner.id {
+                    deliver(
+                        &inner,
+                        &envelope.destination,
+                        envelope.user.as_deref(),
+                        &envelope.body,
+                    );
+                }
+            }
+        });
+        Ok(broker)
+    }
+
+    fn build(relay: Option<Arc<dyn BrokerRelay>>) -> Self {
+        Self {
+            inner: Arc::new(BrokerInner {
+                id: uuid::Uuid::new_v4().to_string(),
+                sessions: RwLock::new(HashMap::new()),
+                handlers: RwLock::new(HashMap::new()),
+                relay,
+                client_topic_publish: AtomicBool::new(false),
+                max_subscriptions: AtomicUsize::new(DEFAULT_MAX_SUBSCRIPTIONS),
+            }),
+        }
+    }
+
+    /// 注册应用目的地的处理函数
+    ///
+    /// 消息体反序列化为 `T` 后交给处理函数，返回 `Some` 时广播到去掉 `/app` 前缀后的
+    /// `/topic/...` 目的地，如 `/app/chat` 的返回值发送到 `/topic/chat`。
+    /// 处理函数在建立连接的用户上下文中执行，可通过 `current_principal()` 获取用户
+    ///
+    /// # 参数
+    /// * `destination` - 以 `/app/` 开头的目的地
+    pub fn handle<T, R, F, Fut>(&self, destination: impl Into<String>, handler: F) -> &Self
+    where
+        T: DeserializeOwned + Send + 'static,
+        R: Serialize + Send + 'static,
+        F: Fn(MessageContext, T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Option<R>>> + Send + 'static,
+    {
+        let handler = Arc::new(handler);
+        let handler: MessageHandler = Arc::new(move |ctx, body| {
+            let handler = handler.clone();
+            async move {
+                let message: T = serde_json::from_value(body)
+                    .map_err(|e| Error::validation(format!("消息格式错误: {}", e)))?;
+                match handler(ctx, message).await? {
+                    Some(reply) => Ok(Some(serde_json::to_value(reply)?)),
+                    None => Ok(None),
+                }
+            }
+            .boxed()
+        });
+        self.inner
+            .handlers
+            .write()
+            .expect("消息处理函数锁已损坏")
+            .insert(destination.into(), handler);
+        self
+    }
+
+    /// 设置是否允许客户端直接向 `/topic/...` 发送消息
+    ///
+    /// 关闭时客户端只能通过 `/app/...` 的处理函数广播消息，避免伪造服务端的广播
+    ///
+    /// # 默认值
+    /// `false`
+    pub fn allow_client_topic_publish(&self, allow: bool) -> &Self {
+        self.inner
+            .client_topic_publish
+            .store(allow, Ordering::Relaxed);
+        self
+    }
+
+    /// 设置每个会话的最大订阅数量，超出时拒绝新的订阅
+    ///
+    /// # 默认值
+    /// `64`
+    pub fn max_subscriptions(&self, max: usize) -> &Self {
+        self.inner.max_subscriptions.store(max, Ordering::Relaxed);
+        self
+    }
+
+    /// 向广播目的地发送消息
+    ///
+    /// # 错误
+    /// 目的地不以 `/topic/` 开头、消息无法序列化或转发失败时返回错误
+    pub async fn send<T: Serialize + ?Sized>(&self, destination: &str, message: &T) -> Result<()> {
+        if !destination.starts_with(TOPIC_PREFIX) {
+            return Err(Error::validation(format!(
+                "广播目的地必须以 {} 开头: {}",
+                TOPIC_PREFIX, destination
+            )));
+        }
+        self.publish(destination, None, serde_json::to_value(message)?)
+            .await
+    }
+
+    /// 向指定用户的所有会话发送消息
+    ///
+    /// 用户订阅 `/user/queue/orders` 后，可通过 `send_to_user("alice", "/queue/orders", ..)` 接收消息
+    ///
+    /// # 错误
+    /// 目的地不以 `/queue/` 开头、消息无法序列化或转发失败时返回错误
+    pub async fn send_to_user<T: Serialize + ?Sized>(
+        &self,
+        user: &str,
+        destination: &str,
+        message: &T,
+    ) -> Result<()> {
+        if !destination.starts_with(QUEUE_PREFIX) {
+            return Err(Error::validation(format!(
+                "用户目的地必须以 {} 开头: {}",
+                QUEUE_PREFIX, destination
+            )));
+        }
+        let destination = format!("{}{}", USER_PREFIX, destination);
+        self.publish(&destination, Some(user), serde_json::to_value(message)?)
+            .await
+    }
+
+    /// 获取本实例已连接的会话数量
+    pub fn session_count(&self) -> usize {
+        self.inner.sessions.read().expect("会话锁已损坏").len()
+    }
+
+    /// 创建 WebSocket 端点的路由
+    ///
+    /// 用户取自请求扩展中的 [`Principal`]，其次为当前任务上下文中的用户
+    pub fn router<S>(&self, path: &str) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let broker = self.clone();
+        Router::new().route(
+            path,
+            get(move |ws: WebSocketUpgrade, principal: Option<Extension<Principal>>| {
+                let broker = broker.clone();
+                async move { broker.upgrade(ws, principal.map(|Extension(principal)| principal)) }
+            }),
+        )
+    }
+
+    /// 升级为 WebSocket 连接
+    fn upgrade(self, ws: WebSocketUpgrade, principal: Option<Principal>) -> Response {
+        let principal = principal.or_else(current_principal);
+        ws.on_upgrade(move |socket| async move { self.run_session(socket, principal).await })
+    }
+
+    /// 处理一个 WebSocket 连接，直到连接关闭
+    async fn run_session(self, socket: WebSocket, principal: Option<Principal>) {
+        let session_id = uuid::Uuid::new_v4().to_string();
+        let mut receiver = self.register(&session_id, principal.as_ref().map(|p| p.name.clone()));
+        let (mut sink, mut stream) = socket.split();
+
+        let writer = tokio::spawn(async move {
+            while let Some(frame) = receiver.recv().await {
+                let text = match serde_json::to_string(&frame) {
+                    Ok(text) => text,
+                    Err(e) => {
+                        tracing::warn!("WebSocket 帧序列化失败: {}", e);
+                        continue;
+                    }
+                };
+                if sink.send(Message::Text(text)).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        while let Some(Ok(message)) = stream.next().await {
+            let result = match message {
+                Message::Text(text) => match serde_json::from_str::<ClientFrame>(&text) {
+                    Ok(frame) => self.on_frame(&session_id, principal.clone(), frame).await,
+                    Err(e) => Err(Error::validation(format!("无效的帧: {}", e))),
+                },
+                Message::Binary(_) => Err(Error::validation("不支持二进制帧")),
+                Message::Close(_) => break,
+                Message::Ping(_) | Message::Pong(_) => Ok(()),
+            };
+            if let Err(e) = result {
+                self.reply(
+                    &session_id,
+                    ServerFrame::Error {
+                        message: e.to_string(),
+                    },
+                );
+            }
+        }
+
+        self.unregister(&session_id);
+        writer.abort();
+    }
+
+    /// 注册会话，返回待发送帧的接收端
+    fn register(&self, session_id: &str, user: Option<String>) -> mpsc::Receiver<ServerFrame> {
+        let (sender, receiver) = mpsc::channel(SESSION_BUFFER);
+        self.inner.sessions.write().expect("会话锁已损坏").insert(
+            session_id.to_string(),
+            Session {
+                user,
+                subscriptions: HashMap::new(),
+                sender,
+            },
+        );
+        receiver
+    }
+
+    /// 移除会话及其所有订阅
+    fn unregister(&self, session_id: &str) {
+        self.inner
+            .sessions
+            .write()
+            .expect("会话锁已损坏")
+            .remove(session_id);
+    }
+
+    /// 处理客户端发送的帧
+    async fn on_frame(
+        &self,
+        session_id: &str,
+        principal: Option<Principal>,
+        frame: ClientFrame,
+    ) -> Result<()> {
+        match frame {
+            ClientFrame::Subscribe { id, destination } => {
+                let user_queue = destination
+                    .strip_prefix(USER_PREFIX)
+                    .is_some_and(|rest| rest.starts_with(QUEUE_PREFIX));
+                if user_queue && principal.is_none() {
+                    return Err(Error::validation(format!(
+                        "订阅用户目的地需要认证: {}",
+                        destination
+                    )));
+                }
+                if !user_queue && !destination.starts_with(TOPIC_PREFIX) {
+                    return Err(Error::validation(format!(
+                        "不支持订阅的目的地: {}",
+                        destination
+                    )));
+                }
+                if let Some(session) = self
+                    .inner
+                    .sessions
+                    .write()
+                    .expect("会话锁已损坏")
+                    .get_mut(session_id)
+                {
+                    let max = self.inner.max_subscriptions.load(Ordering::Relaxed);
+                    if !session.subscriptions.contains_key(&id)
+                        && session.subscriptions.len() >= max
+                    {
+                        return Err(Error::validation(format!("订阅数量超过上限 {}", max)));
+                    }
+                    session.subscriptions.insert(id, destination);
+                }
+                Ok(())
+            }
+            ClientFrame::Unsubscribe { id } => {
+                if let Some(session) = self
+                    .inner
+                    .sessions
+                    .write()
+                    .expect("会话锁已损坏")
+                    .get_mut(session_id)
+                {
+                    session.subscriptions.remove(&id);
+                }
+                Ok(())
+            }
+            ClientFrame::Send { destination, body } => {
+                if destination.starts_with(TOPIC_PREFIX) {
+                    if !self.inner.client_topic_publish.load(Ordering::Relaxed) {
+                        return Err(Error::validation(format!(
+                            "不允许客户端直接发送到广播目的地: {}",
+                            destination
+                        )));
+                    }
+                    return self.publish(&destination, None, body).await;
+                }
+                let Some(name) = destination.strip_prefix(APP_PREFIX) else {
+                    return Err(Error::validation(format!(
+                        "不支持发送的目的地: {}",
+                        destination
+                    )));
+                };
+
+                let handler = self
+                    .inner
+                    .handlers
+                    .read()
+                    .expect("消息处理函数锁已损坏")
+                    .get(&destination)
+                    .cloned()
+                    .ok_or_else(|| {
+                        Error::not_found(format!("目的地 {} 的处理函数", destination))
+                    })?;
+                let reply_to = format!("{}{}", TOPIC_PREFIX, name);
+                let ctx = MessageContext {
+                    session_id: session_id.to_string(),
+                    principal: principal.clone(),
+                    destination,
+                };
+                let reply = match principal {
+                    Some(principal) => with_principal(principal, handler(ctx, body)).await?,
+                    None => handler(ctx, body).await?,
+                };
+                match reply {
+                    Some(reply) => self.publish(&reply_to, None, reply).await,
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+
+    /// 投递到本实例的会话并转发到其他实例
+    async fn publish(&self, destination: &str, user: Option<&str>, body: Value) -> Result<()> {
+        deliver(&self.inner, destination, user, &body);
+        if let Some(relay) = &self.inner.relay {
+            let envelope = BrokerEnvelope {
+                origin: self.inner.id.clone(),
+                destination: destination.to_string(),
+                user: user.map(str::to_string),
+                body,
+            };
+            relay.publish(&envelope).await?;
+        }
+        Ok(())
+    }
+
+    /// 向单个会话发送帧
+    fn reply(&self, session_id: &str, frame: ServerFrame) {
+        if let Some(session) = self
+            .inner
+            .sessions
+            .read()
+            .expect("会话锁已损坏")
+            .get(session_id)
+        {
+            let _ = session.sender.try_send(frame);
+        }
+    }
+}
+
+impl std::fmt::Debug for MessageBroker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MessageBroker")
+            .field("id", &self.inner.id)
+            .field("sessions", &self.session_count())
+            .field("relay", &self.inner.relay.is_some())
+            .finish()
+    }
+}
+
+/// 将消息投递到订阅了目的地的本地会话
+///
+/// `user` 不为空时只投递到该用户的会话
+fn deliver(inner: &BrokerInner, destination: &str, user: Option<&str>, body: &Value) {
+    let sessions = inner.sessions.read().expect("会话锁已损坏");
+    for (session_id, session) in sessions.iter() {
+        if user.is_some() && session.user.as_deref() != user {
+            continue;
+        }
+        for (id, subscribed) in &session.subscriptions {
+            if subscribed != destination {
+                continue;
+            }
+            let frame = ServerFrame::Message {
+                subscription: id.clone(),
+                destination: destination.to_string(),
+                body: body.clone(),
+            };
+            if session.sender.try_send(frame).is_err() {
+                tracing::warn!(
+                    "WebSocket 会话 {} 的发送缓冲已满，丢弃 {} 的消息",
+                    session_id,
+                    destination
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// 测试订阅、广播、用户目的地和应用目的地
+    #[tokio::test]
+    async fn test_broker_fan_out() {
+        let broker = MessageBroker::new();
+        broker.handle("/app/echo", |ctx: MessageContext, body: Value| async move {
+            Ok(Some(
+                json!({ "from": ctx.principal.map(|p| p.name), "body": body }),
+            ))
+        });
+
+        let alice = Some(Principal::new("alice"));
+        let mut first = broker.register("s1", Some("alice".to_string()));
+        let mut second = broker.register("s2", None);
+
+        let frame: ClientFrame =
+            serde_json::from_str(r#"{"type":"subscribe","id":"a","destination":"/topic/echo"}"#)
+                .unwrap();
+        broker.on_frame("s1", alice.clone(), frame).await.unwrap();
+        let subscribe = |id: &str, destination: &str| ClientFrame::Subscribe {
+            id: id.to_string(),
+            destination: destination.to_string(),
+        };
+        broker
+            .on_frame("s1", alice.clone(), subscribe("b", "/user/queue/orders"))
+            .await
+            .unwrap();
+        broker
+            .on_frame("s2", None, subscribe("c", "/topic/echo"))
+            .await
+            .unwrap();
+        assert!(broker
+            .on_frame("s2", None, subscribe("d", "/user/queue/orders"))
+            .await
+            .is_err());
+        assert!(broker
+            .on_frame("s2", None, subscribe("e", "/queue/orders"))
+            .await
+            .is_err());
+
+        let send = ClientFrame::Send {
+            destination: "/app/echo".to_string(),
+            body: json!("hi"),
+        };
+        broker.on_frame("s1", alice.clone(), send).await.unwrap();
+        for receiver in [&mut first, &mut second] {
+            let frame = receiver.try_recv().unwrap();
+            let json = serde_json::to_value(&frame).unwrap();
+            assert_eq!(json["type"], "message");
+            assert_eq!(json["destination"], "/topic/echo");
+            assert_eq!(json["body"], json!({ "from": "alice", "body": "hi" }));
+        }
+
+        broker
+            .send_to_user("alice", "/queue/orders", &json!({ "id": 1 }))
+            .await
+            .unwrap();
+        assert_eq!(
+            first.try_recv().unwrap(),
+            ServerFrame::Message {
+                subscription: "b".to_string(),
+                destination: "/user/queue/orders".to_string(),
+                body: json!({ "id": 1 }),
+            }
+        );
+        assert!(second.try_recv().is_err());
+        assert!(broker.send("/queue/orders", &1).await.is_err());
+
+        broker
+            .on_frame(
+                "s2",
+                None,
+                ClientFrame::Unsubscribe {
+                    id: "c".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        broker.send("/topic/echo", &2).await.unwrap();
+        assert!(first.try_recv().is_ok());
+        assert!(second.try_recv().is_err());
+
+        broker.unregister("s1");
+        assert_eq!(broker.session_count(), 1);
+    }
+
+    /// 测试客户端直接发送到广播目的地和订阅数量上限
+    #[tokio::test]
+    async fn test_client_frame_limits() {
+        let broker = MessageBroker::new();
+        broker.max_subscriptions(2);
+        let mut receiver = broker.register("s1", None);
+        let subscribe = |id: &str| ClientFrame::Subscribe {
+            id: id.to_string(),
+            destination: "/topic/notice".to_string(),
+        };
+        broker.on_frame("s1", None, subscribe("a")).await.unwrap();
+        broker.on_frame("s1", None, subscribe("b")).await.unwrap();
+        broker.on_frame("s1", None, subscribe("b")).await.unwrap();
+        assert!(broker.on_frame("s1", None, subscribe("c")).await.is_err());
+
+        let spoof = || ClientFrame::Send {
+            destination: "/topic/notice".to_string(),
+            body: json!("maintenance"),
+        };
+        assert!(broker.on_frame("s1", None, spoof()).await.is_err());
+        assert!(receiver.try_recv().is_err());
+
+        broker.allow_client_topic_publish(true);
+        broker.on_frame("s1", None, spoof()).await.unwrap();
+        assert!(receiver.try_recv().is_ok());
+    }
+}
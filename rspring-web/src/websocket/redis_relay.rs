@@ -0,0 +1,127 @@
+//! 基于 Redis Pub/Sub 的消息转发
+
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use rspring_core::config::{ConfigurationManager, RedisConfig};
+use rspring_core::{Error, Result};
+
+use super::{BrokerEnvelope, BrokerRelay};
+
+/// 默认的转发频道
+const DEFAULT_CHANNEL: &str = "rspring:websocket";
+
+/// 基于 Redis Pub/Sub 的消息转发
+///
+/// 所有实例发布和订阅同一个频道，订阅使用独立的连接
+///
+/// # 示例
+/// ```rust
+/// let relay = RedisBrokerRelay::from_config(&config).await?;
+/// let broker = MessageBroker::with_relay(Arc::new(relay)).await?;
+/// ```
+pub struct RedisBrokerRelay {
+    /// Redis 客户端，用于创建订阅连接
+    client: redis::Client,
+    /// 发布消息的连接，断开后自动重连
+    connection: ConnectionManager,
+    /// 转发频道
+    channel: String,
+}
+
+impl RedisBrokerRelay {
+    /// 连接 Redis
+    ///
+    /// # 错误
+    /// URL 无效或连接失败时返回错误
+    pub async fn connect(url: &str) -> Result<Self> {
+        let client = redis::Client::open(url)
+            .map_err(|e| Error::validation(format!("无效的 Redis URL ({}): {}", url, e)))?;
+        let connection = client
+            .get_connection_manager()
+            .await
+            .map_err(|e| Error::application(format!("连接 Redis 失败: {}", e)))?;
+        Ok(Self {
+            client,
+            connection,
+            channel: DEFAULT_CHANNEL.to_string(),
+        })
+    }
+
+    /// 使用配置文件中的 `[redis]` 章节连接 Redis
+    ///
+    /// 未配置时使用默认值，集群模式下连接第一个节点
+    ///
+    /// # 错误
+    /// 配置无效或连接失败时返回错误
+    pub async fn from_config(config: &ConfigurationManager) -> Result<Self> {
+        let redis_config: RedisConfig = if config.contains_key("redis") {
+            config.get_section("redis")?
+        } else {
+            RedisConfig::default()
+        };
+        let url = if redis_config.cluster {
+            redis_config
+                .cluster_nodes()
+                .into_iter()
+                .next()
+                .unwrap_or(redis_config.url)
+        } else {
+            redis_config.url
+        };
+        Self::connect(&url).await
+    }
+
+    /// 设置转发频道
+    ///
+    /// # 默认值
+    /// `"rspring:websocket"`
+    pub fn with_channel(mut self, channel: impl Into<String>) -> Self {
+        self.channel = channel.into();
+        self
+    }
+}
+
+#[async_trait]
+impl BrokerRelay for RedisBrokerRelay {
+    async fn publish(&self, envelope: &BrokerEnvelope) -> Result<()> {
+        let payload = serde_json::to_string(envelope)?;
+        let mut connection = self.connection.clone();
+        connection
+            .publish::<_, _, ()>(&self.channel, payload)
+            .await
+            .map_err(|e| Error::application(format!("发布 WebSocket 消息到 Redis 失败: {}", e)))
+    }
+
+    async fn subscribe(&self) -> Result<BoxStream<'static, BrokerEnvelope>> {
+        let mut pubsub = self
+            .client
+            .get_tokio_connection()
+            .await
+            .map_err(|e| Error::application(format!("连接 Redis 失败: {}", e)))?
+            .into_pubsub();
+        pubsub.subscribe(&self.channel).await.map_err(|e| {
+            Error::application(format!("订阅 Redis 频道 {} 失败: {}", self.channel, e))
+        })?;
+
+        let channel = self.channel.clone();
+        let stream = pubsub.into_on_message().filter_map(move |message| {
+            let envelope = serde_json::from_slice::<BrokerEnvelope>(message.get_payload_bytes());
+            if let Err(e) = &envelope {
+                tracing::warn!("Redis 频道 {} 中的 WebSocket 消息格式错误: {}", channel, e);
+            }
+            futures::future::ready(envelope.ok())
+        });
+        Ok(stream.boxed())
+    }
+}
+
+impl std::fmt::Debug for RedisBrokerRelay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisBrokerRelay")
+            .field("channel", &self.channel)
+            .finish()
+    }
+}